@@ -2,10 +2,11 @@
 //! 这个程序创建一个最小化的3节点集群验证
 
 use conflux::config::{AppConfig, StorageConfig};
-use conflux::raft::node::ResourceLimits;
+use conflux::raft::node::{ResourceLimits, SnapshotStreamConfig};
 use conflux::raft::{
     network::NetworkConfig,
     node::{NodeConfig, RaftNode},
+    types::DEFAULT_ELECTION_PRIORITY,
 };
 use openraft::Config as RaftConfig;
 use std::collections::HashMap;
@@ -55,6 +56,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             election_timeout_min: 300,
             election_timeout_max: 600,
             resource_limits: ResourceLimits::default(),
+            election_priority: DEFAULT_ELECTION_PRIORITY,
+            snapshot_stream: SnapshotStreamConfig::default(),
+            pre_vote_enabled: false,
+            compress_log_entries: false,
         },
         NodeConfig {
             node_id: 2,
@@ -70,6 +75,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             election_timeout_min: 300,
             election_timeout_max: 600,
             resource_limits: ResourceLimits::default(),
+            election_priority: DEFAULT_ELECTION_PRIORITY,
+            snapshot_stream: SnapshotStreamConfig::default(),
+            pre_vote_enabled: false,
+            compress_log_entries: false,
         },
         NodeConfig {
             node_id: 3,
@@ -85,6 +94,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             election_timeout_min: 300,
             election_timeout_max: 600,
             resource_limits: ResourceLimits::default(),
+            election_priority: DEFAULT_ELECTION_PRIORITY,
+            snapshot_stream: SnapshotStreamConfig::default(),
+            pre_vote_enabled: false,
+            compress_log_entries: false,
         },
     ];
 
@@ -96,10 +109,9 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         .map(|dir| AppConfig {
             storage: StorageConfig {
                 data_dir: dir.path().to_string_lossy().to_string(),
-                max_open_files: 1000,
                 cache_size_mb: 64,
-                write_buffer_size_mb: 64,
                 max_write_buffer_number: 2,
+                ..Default::default()
             },
             ..Default::default()
         })
@@ -23,6 +23,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         concurrency: 1,
         warmup_duration: Duration::from_secs(2),
         test_interval: Duration::from_millis(50),
+        ..Default::default()
     };
 
     // 1. 单节点性能测试
@@ -0,0 +1,236 @@
+//! 配置变更事件的外部发布
+//!
+//! 状态机应用命令后，将产生的 [`ConfigChangeEvent`] 以JSON消息发布到NATS或Kafka，
+//! 供集群外的服务订阅。消息代理不可用时事件在有界通道中缓冲，恢复后按顺序补发。
+
+use super::EventSinkConfig;
+use crate::error::{ConfluxError, Result};
+use crate::raft::store::ConfigChangeEvent;
+use async_trait::async_trait;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc::{self, error::TrySendError};
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+
+/// 消息代理不可用时最多缓冲的事件数量
+pub const EVENT_SINK_BUFFER_SIZE: usize = 10_000;
+
+/// 发布失败后重试的间隔
+const EVENT_SINK_RETRY_INTERVAL: Duration = Duration::from_secs(1);
+
+/// 配置变更事件的发布目标
+#[async_trait]
+pub trait EventSink: Send + Sync {
+    /// 发布一个配置变更事件
+    ///
+    /// # Errors
+    /// 事件无法发送到消息代理时返回错误
+    async fn publish(&self, event: &ConfigChangeEvent) -> Result<()>;
+}
+
+/// 根据配置创建发布目标，并包装为带缓冲的 [`BufferedEventSink`]
+///
+/// # Errors
+/// 对应的消息代理支持未编译进来（`nats` / `kafka` feature）或客户端创建失败时返回错误
+pub fn connect_event_sink(config: &EventSinkConfig) -> Result<Arc<dyn EventSink>> {
+    let sink = broker_sink(config)?;
+    info!("Publishing config change events to {}", config.kind());
+    Ok(Arc::new(BufferedEventSink::spawn(sink, EVENT_SINK_BUFFER_SIZE)))
+}
+
+/// 直接发布到消息代理的目标
+fn broker_sink(config: &EventSinkConfig) -> Result<Arc<dyn EventSink>> {
+    match config {
+        #[cfg(feature = "nats")]
+        EventSinkConfig::Nats { url, subject_prefix } => {
+            Ok(Arc::new(NatsEventSink::new(url.clone(), subject_prefix.clone())))
+        }
+        #[cfg(feature = "kafka")]
+        EventSinkConfig::Kafka { brokers, topic } => {
+            Ok(Arc::new(KafkaEventSink::new(brokers, topic.clone())?))
+        }
+        #[allow(unreachable_patterns)]
+        other => Err(ConfluxError::Config(config::ConfigError::Message(format!(
+            "Event sink {kind} requires building with the `{kind}` feature",
+            kind = other.kind()
+        )))),
+    }
+}
+
+/// 事件以JSON消息发布
+#[cfg(any(feature = "nats", feature = "kafka"))]
+fn event_payload(event: &ConfigChangeEvent) -> Result<Vec<u8>> {
+    Ok(serde_json::to_vec(event)?)
+}
+
+/// 在有界通道中缓冲事件，由后台任务依次发布到内部目标
+///
+/// `publish` 只入队不等待消息代理，因此不会阻塞状态机；内部目标发布失败时后台任务
+/// 按固定间隔重试同一事件，期间新事件继续缓冲，缓冲区满时丢弃新事件并记录警告。
+pub struct BufferedEventSink {
+    sender: mpsc::Sender<ConfigChangeEvent>,
+    worker: JoinHandle<()>,
+}
+
+impl BufferedEventSink {
+    /// 启动后台发布任务
+    ///
+    /// # Arguments
+    /// * `inner` - 实际发布事件的目标
+    /// * `capacity` - 最多缓冲的事件数量
+    pub fn spawn(inner: Arc<dyn EventSink>, capacity: usize) -> Self {
+        let (sender, mut receiver) = mpsc::channel::<ConfigChangeEvent>(capacity);
+        let worker = tokio::spawn(async move {
+            while let Some(event) = receiver.recv().await {
+                let mut failures = 0u64;
+                while let Err(e) = inner.publish(&event).await {
+                    if failures == 0 {
+                        warn!(
+                            "Event sink unavailable, buffering config change events: {}",
+                            e
+                        );
+                    }
+                    failures += 1;
+                    tokio::time::sleep(EVENT_SINK_RETRY_INTERVAL).await;
+                }
+                if failures > 0 {
+                    info!("Event sink recovered after {} failed attempts", failures);
+                }
+            }
+        });
+        Self { sender, worker }
+    }
+}
+
+impl Drop for BufferedEventSink {
+    fn drop(&mut self) {
+        self.worker.abort();
+    }
+}
+
+#[async_trait]
+impl EventSink for BufferedEventSink {
+    async fn publish(&self, event: &ConfigChangeEvent) -> Result<()> {
+        match self.sender.try_send(event.clone()) {
+            Ok(()) => Ok(()),
+            Err(TrySendError::Full(_)) => {
+                warn!(
+                    "Event sink buffer is full, dropping change event for config {}",
+                    event.config_id
+                );
+                Err(ConfluxError::internal("Event sink buffer is full"))
+            }
+            Err(TrySendError::Closed(_)) => {
+                Err(ConfluxError::internal("Event sink publisher has stopped"))
+            }
+        }
+    }
+}
+
+/// 发布到NATS，主题为 `{subject_prefix}.{tenant}.{app}.{env}`
+///
+/// 首次发布时连接服务器，连接断开后由客户端自动重连
+#[cfg(feature = "nats")]
+pub struct NatsEventSink {
+    url: String,
+    subject_prefix: String,
+    client: tokio::sync::OnceCell<async_nats::Client>,
+}
+
+#[cfg(feature = "nats")]
+impl NatsEventSink {
+    /// 创建NATS发布目标，此时不连接服务器
+    pub fn new(url: String, subject_prefix: String) -> Self {
+        Self {
+            url,
+            subject_prefix,
+            client: tokio::sync::OnceCell::new(),
+        }
+    }
+
+    /// 事件发布到的主题；`.` 是NATS主题的分隔符，因此命名空间中的 `.` 替换为 `_`
+    pub fn subject(&self, event: &ConfigChangeEvent) -> String {
+        let token = |value: &str| value.replace(['.', ' ', '*', '>'], "_");
+        format!(
+            "{}.{}.{}.{}",
+            self.subject_prefix,
+            token(&event.namespace.tenant),
+            token(&event.namespace.app),
+            token(&event.namespace.env)
+        )
+    }
+}
+
+#[cfg(feature = "nats")]
+#[async_trait]
+impl EventSink for NatsEventSink {
+    async fn publish(&self, event: &ConfigChangeEvent) -> Result<()> {
+        let client = self
+            .client
+            .get_or_try_init(|| async_nats::connect(self.url.as_str()))
+            .await
+            .map_err(|e| ConfluxError::internal(format!("Failed to connect to NATS: {}", e)))?;
+
+        client
+            .publish(self.subject(event), event_payload(event)?.into())
+            .await
+            .map_err(|e| ConfluxError::internal(format!("Failed to publish to NATS: {}", e)))?;
+        client
+            .flush()
+            .await
+            .map_err(|e| ConfluxError::internal(format!("Failed to flush NATS client: {}", e)))
+    }
+}
+
+/// 发布到Kafka主题，消息键为配置ID，保证同一配置的事件有序
+#[cfg(feature = "kafka")]
+pub struct KafkaEventSink {
+    producer: rdkafka::producer::FutureProducer,
+    topic: String,
+}
+
+#[cfg(feature = "kafka")]
+impl KafkaEventSink {
+    /// Kafka确认单条消息的超时时间
+    const MESSAGE_TIMEOUT: Duration = Duration::from_secs(5);
+
+    /// 创建Kafka生产者
+    ///
+    /// # Errors
+    /// 生产者配置无效时返回错误
+    pub fn new(brokers: &[String], topic: String) -> Result<Self> {
+        let producer = rdkafka::ClientConfig::new()
+            .set("bootstrap.servers", brokers.join(","))
+            .set("message.timeout.ms", Self::MESSAGE_TIMEOUT.as_millis().to_string())
+            .create()
+            .map_err(|e| {
+                ConfluxError::Config(config::ConfigError::Message(format!(
+                    "Failed to create Kafka producer: {}",
+                    e
+                )))
+            })?;
+        Ok(Self { producer, topic })
+    }
+}
+
+#[cfg(feature = "kafka")]
+#[async_trait]
+impl EventSink for KafkaEventSink {
+    async fn publish(&self, event: &ConfigChangeEvent) -> Result<()> {
+        let key = event.config_id.to_string();
+        let payload = event_payload(event)?;
+        let record = rdkafka::producer::FutureRecord::to(&self.topic)
+            .key(&key)
+            .payload(&payload);
+        self.producer
+            .send(record, rdkafka::util::Timeout::After(Self::MESSAGE_TIMEOUT))
+            .await
+            .map(|_| ())
+            .map_err(|(e, _)| ConfluxError::internal(format!("Failed to publish to Kafka: {}", e)))
+    }
+}
+
+#[cfg(test)]
+#[path = "event_sink_tests.rs"]
+mod tests;
@@ -0,0 +1,220 @@
+use super::*;
+use crate::raft::store::{ConfigChangeType, StateMachineManager, Store};
+use crate::raft::types::{ConfigFormat, ConfigNamespace, RaftCommand};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use tempfile::tempdir;
+
+/// Records published events; fails while the broker is "down"
+struct RecordingSink {
+    available: AtomicBool,
+    events: Mutex<Vec<ConfigChangeEvent>>,
+}
+
+impl RecordingSink {
+    fn new(available: bool) -> Arc<Self> {
+        Arc::new(Self {
+            available: AtomicBool::new(available),
+            events: Mutex::default(),
+        })
+    }
+
+    fn config_ids(&self) -> Vec<u64> {
+        self.events.lock().unwrap().iter().map(|event| event.config_id).collect()
+    }
+}
+
+#[async_trait]
+impl EventSink for RecordingSink {
+    async fn publish(&self, event: &ConfigChangeEvent) -> Result<()> {
+        if !self.available.load(Ordering::SeqCst) {
+            return Err(ConfluxError::internal("connection lost"));
+        }
+        self.events.lock().unwrap().push(event.clone());
+        Ok(())
+    }
+}
+
+fn event(config_id: u64) -> ConfigChangeEvent {
+    ConfigChangeEvent {
+        config_id,
+        namespace: ConfigNamespace {
+            tenant: "tenant".to_string(),
+            app: "app".to_string(),
+            env: "prod".to_string(),
+        },
+        name: "db.json".to_string(),
+        version_id: 1,
+        change_type: ConfigChangeType::Created,
+    }
+}
+
+async fn wait_for(condition: impl Fn() -> bool) {
+    for _ in 0..100 {
+        if condition() {
+            return;
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+    panic!("condition not reached");
+}
+
+#[tokio::test]
+async fn test_buffered_sink_delivers_after_reconnect() {
+    let broker = RecordingSink::new(false);
+    let sink = BufferedEventSink::spawn(broker.clone(), 16);
+
+    for config_id in 1..=3 {
+        sink.publish(&event(config_id)).await.unwrap();
+    }
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert!(broker.config_ids().is_empty());
+
+    // Buffered events are delivered in order once the broker is back
+    broker.available.store(true, Ordering::SeqCst);
+    wait_for(|| broker.config_ids().len() == 3).await;
+    assert_eq!(broker.config_ids(), vec![1, 2, 3]);
+}
+
+#[tokio::test]
+async fn test_buffered_sink_drops_events_when_full() {
+    let broker = RecordingSink::new(false);
+    let sink = BufferedEventSink::spawn(broker.clone(), 2);
+
+    // The first event is taken by the publisher task and retried
+    sink.publish(&event(1)).await.unwrap();
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    sink.publish(&event(2)).await.unwrap();
+    sink.publish(&event(3)).await.unwrap();
+    assert!(sink.publish(&event(4)).await.is_err());
+
+    broker.available.store(true, Ordering::SeqCst);
+    wait_for(|| broker.config_ids().len() == 3).await;
+    assert_eq!(broker.config_ids(), vec![1, 2, 3]);
+}
+
+#[tokio::test]
+async fn test_state_machine_publishes_applied_changes() {
+    let dir = tempdir().unwrap();
+    let (store, rx) = Store::new(dir.path()).await.unwrap();
+    let store = Arc::new(store);
+    let broker = RecordingSink::new(true);
+    let mut manager = StateMachineManager::new(store.clone(), rx).with_event_sink(broker.clone());
+    tokio::spawn(async move { manager.run().await });
+
+    let response = store
+        .submit_command(RaftCommand::CreateConfig {
+            namespace: event(0).namespace,
+            name: "db.json".to_string(),
+            content: br#"{"pool":10}"#.to_vec(),
+            format: ConfigFormat::Json,
+            schema: None,
+            creator_id: 1,
+            description: "initial".to_string(),
+        })
+        .await
+        .unwrap();
+    assert!(response.success);
+
+    wait_for(|| !broker.config_ids().is_empty()).await;
+    let events = broker.events.lock().unwrap().clone();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].config_id, response.config_id.unwrap());
+    assert_eq!(events[0].name, "db.json");
+    assert_eq!(events[0].change_type, ConfigChangeType::Created);
+}
+
+#[test]
+fn test_event_sink_config_deserialize() {
+    let config: EventSinkConfig = serde_json::from_value(serde_json::json!({
+        "type": "kafka",
+        "brokers": ["kafka-1:9092", "kafka-2:9092"],
+        "topic": "conflux-changes",
+    }))
+    .unwrap();
+    assert_eq!(
+        config,
+        EventSinkConfig::Kafka {
+            brokers: vec!["kafka-1:9092".to_string(), "kafka-2:9092".to_string()],
+            topic: "conflux-changes".to_string(),
+        }
+    );
+    assert_eq!(config.kind(), "kafka");
+}
+
+#[cfg(not(feature = "kafka"))]
+#[tokio::test]
+async fn test_unsupported_sink_is_rejected() {
+    let config = EventSinkConfig::Kafka {
+        brokers: vec!["localhost:9092".to_string()],
+        topic: "conflux-changes".to_string(),
+    };
+    let error = connect_event_sink(&config).err().unwrap();
+    assert!(error.to_string().contains("`kafka` feature"), "{}", error);
+}
+
+#[cfg(feature = "nats")]
+mod nats {
+    use super::*;
+    use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+    use tokio::net::TcpListener;
+
+    /// Minimal NATS server accepting one client and forwarding its `PUB`s
+    async fn mock_nats_server() -> (String, mpsc::Receiver<(String, Vec<u8>)>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let (sender, receiver) = mpsc::channel(16);
+
+        tokio::spawn(async move {
+            let (socket, _) = listener.accept().await.unwrap();
+            let (read, mut write) = socket.into_split();
+            let info = format!(
+                "INFO {{\"server_id\":\"mock\",\"server_name\":\"mock\",\"version\":\"2.10.0\",\
+                 \"go\":\"go1.21\",\"host\":\"127.0.0.1\",\"port\":{},\"headers\":true,\
+                 \"max_payload\":1048576,\"proto\":1}}\r\n",
+                address.port()
+            );
+            write.write_all(info.as_bytes()).await.unwrap();
+
+            let mut reader = BufReader::new(read);
+            let mut line = String::new();
+            while reader.read_line(&mut line).await.unwrap_or(0) > 0 {
+                let parts: Vec<&str> = line.split_whitespace().collect();
+                match parts.first().copied() {
+                    Some("PING") => write.write_all(b"PONG\r\n").await.unwrap(),
+                    Some("PUB") => {
+                        let size: usize = parts.last().unwrap().parse().unwrap();
+                        // Payload followed by CRLF
+                        let mut payload = vec![0; size + 2];
+                        reader.read_exact(&mut payload).await.unwrap();
+                        payload.truncate(size);
+                        let _ = sender.send((parts[1].to_string(), payload)).await;
+                    }
+                    _ => {}
+                }
+                line.clear();
+            }
+        });
+
+        (format!("nats://{}", address), receiver)
+    }
+
+    #[tokio::test]
+    async fn test_nats_sink_publishes_json() {
+        let (url, mut published) = mock_nats_server().await;
+        let sink = NatsEventSink::new(url, "conflux.changes".to_string());
+
+        let mut change = event(7);
+        change.namespace.app = "billing.api".to_string();
+        sink.publish(&change).await.unwrap();
+
+        let (subject, payload) = tokio::time::timeout(Duration::from_secs(5), published.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(subject, "conflux.changes.tenant.billing_api.prod");
+        let body: serde_json::Value = serde_json::from_slice(&payload).unwrap();
+        assert_eq!(body["config_id"], 7);
+        assert_eq!(body["change_type"], "Created");
+    }
+}
@@ -0,0 +1,263 @@
+use crate::auth::{AuthContext, AuthzService, JwtAuthenticator};
+use crate::config::AppConfig;
+use crate::error::{ConfluxError, Result};
+use crate::raft::client::helpers::{create_client_write_request, create_validate_request};
+use crate::raft::client::{ClientWriteRequest, RaftClient};
+use crate::raft::node::ANONYMOUS_CLIENT_ID;
+use crate::raft::store::{
+    ConsistencyChecker, Store, WebhookNotifier, IDEMPOTENCY_KEY_CONFLICT,
+};
+use crate::raft::types::{ClientWriteResponse, RaftCommand};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tokio::sync::broadcast::{self, error::RecvError};
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+
+mod event_sink;
+
+pub use event_sink::{connect_event_sink, BufferedEventSink, EventSink, EVENT_SINK_BUFFER_SIZE};
+#[cfg(feature = "kafka")]
+pub use event_sink::KafkaEventSink;
+#[cfg(feature = "nats")]
+pub use event_sink::NatsEventSink;
+
+/// 配置变更事件的外部消息代理
+///
+/// 需要以对应的 `nats` / `kafka` feature 编译
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum EventSinkConfig {
+    /// 发布到NATS，主题为 `{subject_prefix}.{tenant}.{app}.{env}`
+    Nats { url: String, subject_prefix: String },
+    /// 发布到Kafka主题，消息键为配置ID
+    Kafka { brokers: Vec<String>, topic: String },
+}
+
+impl EventSinkConfig {
+    /// 消息代理类型，与启用它的feature同名
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Nats { .. } => "nats",
+            Self::Kafka { .. } => "kafka",
+        }
+    }
+}
+
+/// 核心应用句柄，封装了所有核心服务的引用
+/// 这个结构体是协议层与核心业务逻辑之间的桥梁
+#[derive(Clone)]
+pub struct CoreAppHandle {
+    /// Raft 客户端，用于处理分布式共识操作
+    pub raft_client: Arc<RaftClient>,
+    
+    /// 存储实例，用于直接访问数据（读操作）
+    pub store: Arc<Store>,
+
+    /// 认证授权服务
+    pub authz_service: Arc<AuthzService>,
+
+    /// JWT认证器，用于从请求中解析认证上下文
+    pub jwt_authenticator: Arc<JwtAuthenticator>,
+
+    // TODO: 在后续的 Epic 中添加更多服务
+    // pub metadata_service: Arc<MetadataService>,
+    // pub watch_service: Arc<WatchService>,
+}
+
+impl CoreAppHandle {
+    /// 创建新的核心应用句柄
+    pub fn new(
+        raft_client: Arc<RaftClient>,
+        store: Arc<Store>,
+        authz_service: Arc<AuthzService>,
+        jwt_authenticator: Arc<JwtAuthenticator>,
+    ) -> Self {
+        Self {
+            raft_client,
+            store,
+            authz_service,
+            jwt_authenticator,
+        }
+    }
+    
+    /// 获取 Raft 客户端的引用
+    pub fn raft_client(&self) -> &RaftClient {
+        &self.raft_client
+    }
+    
+    /// 获取存储实例的引用
+    pub fn store(&self) -> &Store {
+        &self.store
+    }
+
+    /// 获取认证授权服务的引用
+    pub fn authz_service(&self) -> &AuthzService {
+        &self.authz_service
+    }
+
+    /// 获取JWT认证器的引用
+    pub fn jwt_authenticator(&self) -> &JwtAuthenticator {
+        &self.jwt_authenticator
+    }
+
+    /// 订阅配置热更新，JWT有效期变化时应用到之后签发的token
+    ///
+    /// # Arguments
+    /// * `updates` - 配置文件监听器发出的新配置
+    ///
+    /// # Returns
+    /// 订阅任务的句柄，配置通道关闭后任务结束
+    pub fn subscribe_config_updates(
+        &self,
+        mut updates: broadcast::Receiver<AppConfig>,
+    ) -> JoinHandle<()> {
+        let jwt_authenticator = self.jwt_authenticator.clone();
+        tokio::spawn(async move {
+            loop {
+                match updates.recv().await {
+                    Ok(config) => {
+                        let hours = config.security.jwt_expiration_hours;
+                        if jwt_authenticator.expiration_hours() != hours {
+                            info!("JWT expiration reloaded: {} hours", hours);
+                            jwt_authenticator.set_expiration_hours(hours);
+                        }
+                    }
+                    Err(RecvError::Lagged(skipped)) => {
+                        warn!("Skipped {} config updates", skipped);
+                    }
+                    Err(RecvError::Closed) => break,
+                }
+            }
+        })
+    }
+
+    /// 启动配置变更Webhook通知任务
+    ///
+    /// 存在本地Raft节点时只在该节点为领导者时发送，避免每个副本重复通知
+    ///
+    /// # Returns
+    /// 通知任务的句柄，存储的变更通道关闭后任务结束
+    pub async fn start_webhook_notifier(&self) -> JoinHandle<()> {
+        let mut notifier = WebhookNotifier::new(self.store.clone());
+        if let Some(node) = self.raft_client.raft_node() {
+            let node = node.read().await;
+            if let Some(raft) = node.get_raft() {
+                notifier = notifier.with_leader_check(raft.clone(), node.node_id());
+            }
+        }
+        notifier.spawn()
+    }
+
+    /// 启动定时存储一致性检查任务
+    ///
+    /// 每小时比较一次内存状态与RocksDB，发现不一致时记录警告
+    ///
+    /// # Arguments
+    /// * `repair` - 发现不一致时是否从磁盘重新加载内存状态
+    ///
+    /// # Returns
+    /// 检查任务的句柄
+    pub fn start_consistency_checker(&self, repair: bool) -> JoinHandle<()> {
+        ConsistencyChecker::new(self.store.clone())
+            .with_repair(repair)
+            .spawn()
+    }
+
+    /// 以请求者身份提交写命令
+    ///
+    /// 速率限制按认证用户分别计算，未认证的请求共享同一个匿名限额
+    ///
+    /// # Arguments
+    /// * `command` - 要提交的Raft命令
+    /// * `auth_ctx` - 请求者的认证上下文（可选）
+    ///
+    /// # Errors
+    /// 超出速率限制或写入失败时返回错误
+    pub async fn write(
+        &self,
+        command: RaftCommand,
+        auth_ctx: Option<&AuthContext>,
+    ) -> Result<ClientWriteResponse> {
+        self.write_idempotent(command, auth_ctx, None).await
+    }
+
+    /// 以请求者身份提交带幂等键的写命令
+    ///
+    /// 幂等键按租户和请求者区分，不同租户或请求者使用相同的键互不影响。保留期内以同一个键
+    /// 重试同一命令时，返回首次应用的响应而不会再次应用命令；以同一个键提交不同命令时返回冲突错误。
+    /// 匿名请求无法区分请求者，其幂等键被忽略
+    ///
+    /// # Arguments
+    /// * `command` - 要提交的Raft命令
+    /// * `auth_ctx` - 请求者的认证上下文（可选）
+    /// * `idempotency_key` - 客户端提供的幂等键（可选）
+    ///
+    /// # Errors
+    /// 超出速率限制、写入失败或幂等键已用于其他命令时返回错误
+    pub async fn write_idempotent(
+        &self,
+        command: RaftCommand,
+        auth_ctx: Option<&AuthContext>,
+        idempotency_key: Option<&str>,
+    ) -> Result<ClientWriteResponse> {
+        let client_id = auth_ctx.map_or(ANONYMOUS_CLIENT_ID, |ctx| ctx.user_id.as_str());
+        let request_id = auth_ctx
+            .zip(idempotency_key)
+            .map(|(ctx, key)| format!("{}:{}:{}", ctx.tenant_id, ctx.user_id, key));
+        let request = ClientWriteRequest {
+            request_id,
+            ..create_client_write_request(command, client_id)
+        };
+        let response = self.raft_client.write(request).await?;
+        if !response.success && response.message.starts_with(IDEMPOTENCY_KEY_CONFLICT) {
+            return Err(ConfluxError::conflict(response.message));
+        }
+        Ok(response)
+    }
+
+    /// 以请求者身份校验写命令而不实际执行
+    ///
+    /// 命令在只复制了相关内存状态的临时存储上执行全部校验，不经过Raft共识，也不会持久化
+    ///
+    /// # Arguments
+    /// * `command` - 要校验的Raft命令
+    /// * `auth_ctx` - 请求者的认证上下文（可选）
+    ///
+    /// # Returns
+    /// 返回命令实际执行时将得到的响应
+    ///
+    /// # Errors
+    /// 命令处理器返回错误时返回错误
+    pub async fn validate(
+        &self,
+        command: RaftCommand,
+        auth_ctx: Option<&AuthContext>,
+    ) -> Result<ClientWriteResponse> {
+        let client_id = auth_ctx.map_or(ANONYMOUS_CLIENT_ID, |ctx| ctx.user_id.as_str());
+        self.raft_client
+            .write(create_validate_request(command, client_id))
+            .await
+    }
+}
+
+// TODO: 更新测试以包含AuthzService
+// #[cfg(test)]
+// mod tests {
+//     use super::*;
+//     use crate::raft::Store;
+//     use tempfile::TempDir;
+
+//     #[tokio::test]
+//     async fn test_core_app_handle_creation() {
+//         let temp_dir = TempDir::new().unwrap();
+//         let store = Arc::new(Store::new(temp_dir.path()).await.unwrap());
+//         let raft_client = Arc::new(RaftClient::new(store.clone()));
+
+//         let handle = CoreAppHandle::new(raft_client.clone(), store.clone());
+
+//         // 验证句柄正确包含了服务引用
+//         assert!(Arc::ptr_eq(&handle.raft_client, &raft_client));
+//         assert!(Arc::ptr_eq(&handle.store, &store));
+//     }
+// }
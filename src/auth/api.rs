@@ -3,14 +3,17 @@ use axum::{
     http::StatusCode,
     response::Json,
     routing::{delete, get, post},
-    Router,
+    Extension, Router,
 };
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::Arc;
-use tracing::{debug, info};
+use tracing::{debug, info, warn};
 
-use super::AuthzService;
+use super::{
+    AuthContext, AuthzService, PolicyExport, PolicyImportSummary, PolicyRule, RoleAssignment,
+};
+use crate::error::ConfluxError;
 
 /// 权限检查请求
 #[derive(Debug, Deserialize)]
@@ -66,29 +69,34 @@ where
         // 权限检查端点（主要用于调试）
         .route("/_auth/check", post(check_permission))
         // 租户角色管理
-        .route("/tenants/:tenant/roles", get(list_tenant_roles))
+        .route("/tenants/{tenant}/roles", get(list_tenant_roles))
         // 角色权限管理
         .route(
-            "/tenants/:tenant/roles/:role/permissions",
+            "/tenants/{tenant}/roles/{role}/permissions",
             post(add_role_permission),
         )
         .route(
-            "/tenants/:tenant/roles/:role/permissions",
+            "/tenants/{tenant}/roles/{role}/permissions",
             delete(remove_role_permission),
         )
         // 用户角色管理
         .route(
-            "/tenants/:tenant/users/:user_id/roles",
+            "/tenants/{tenant}/users/{user_id}/roles",
             get(get_user_roles),
         )
         .route(
-            "/tenants/:tenant/users/:user_id/roles",
+            "/tenants/{tenant}/users/{user_id}/roles",
             post(assign_user_role),
         )
         .route(
-            "/tenants/:tenant/users/:user_id/roles/:role",
+            "/tenants/{tenant}/users/{user_id}/roles/{role}",
             delete(revoke_user_role),
         )
+        // 策略审计与导入导出（需要租户管理员权限）
+        .route("/tenants/{tenant}/policies", get(list_tenant_policies))
+        .route("/tenants/{tenant}/policies/export", get(export_tenant_policies))
+        .route("/tenants/{tenant}/policies/import", post(import_tenant_policies))
+        .route("/tenants/{tenant}/role-assignments", get(list_tenant_role_assignments))
         // 策略重新加载
         .route("/_auth/reload", post(reload_policies))
         .with_state(authz_service)
@@ -287,3 +295,177 @@ async fn reload_policies(
         message: "Policies reloaded successfully".to_string(),
     }))
 }
+
+/// 检查请求者是否为目标租户的管理员
+///
+/// # Arguments
+/// * `authz_service` - 授权服务
+/// * `auth_ctx` - 认证中间件注入的认证上下文
+/// * `tenant` - 目标租户
+///
+/// # Returns
+/// 有权限时返回Ok(())，否则返回对应的HTTP状态码
+async fn require_tenant_admin(
+    authz_service: &AuthzService,
+    auth_ctx: Option<&AuthContext>,
+    tenant: &str,
+) -> std::result::Result<(), StatusCode> {
+    let auth_ctx = auth_ctx.ok_or(StatusCode::UNAUTHORIZED)?;
+    if auth_ctx.tenant_id != tenant {
+        warn!(
+            "User {} of tenant {} attempted to access policies of tenant {}",
+            auth_ctx.user_id, auth_ctx.tenant_id, tenant
+        );
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    match authz_service.is_tenant_admin(&auth_ctx.user_id, tenant).await {
+        Ok(true) => Ok(()),
+        Ok(false) => {
+            warn!("Tenant admin permission denied for user {}", auth_ctx.user_id);
+            Err(StatusCode::FORBIDDEN)
+        }
+        Err(e) => {
+            tracing::error!("Tenant admin check failed: {}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// 列出租户的角色权限规则
+async fn list_tenant_policies(
+    Path(tenant): Path<String>,
+    State(authz_service): State<Arc<AuthzService>>,
+    auth_ctx: Option<Extension<AuthContext>>,
+) -> std::result::Result<Json<Vec<PolicyRule>>, StatusCode> {
+    require_tenant_admin(&authz_service, auth_ctx.as_deref(), &tenant).await?;
+
+    let policies = authz_service.list_policies(&tenant).await.map_err(|e| {
+        tracing::error!("Failed to list policies: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    Ok(Json(policies))
+}
+
+/// 列出租户的角色分配规则
+async fn list_tenant_role_assignments(
+    Path(tenant): Path<String>,
+    State(authz_service): State<Arc<AuthzService>>,
+    auth_ctx: Option<Extension<AuthContext>>,
+) -> std::result::Result<Json<Vec<RoleAssignment>>, StatusCode> {
+    require_tenant_admin(&authz_service, auth_ctx.as_deref(), &tenant).await?;
+
+    let assignments = authz_service
+        .list_role_assignments(&tenant)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to list role assignments: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    Ok(Json(assignments))
+}
+
+/// 导出租户的RBAC策略
+async fn export_tenant_policies(
+    Path(tenant): Path<String>,
+    State(authz_service): State<Arc<AuthzService>>,
+    auth_ctx: Option<Extension<AuthContext>>,
+) -> std::result::Result<Json<PolicyExport>, StatusCode> {
+    require_tenant_admin(&authz_service, auth_ctx.as_deref(), &tenant).await?;
+
+    let export = authz_service.export_policies(&tenant).await.map_err(|e| {
+        tracing::error!("Failed to export policies: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    Ok(Json(export))
+}
+
+/// 批量导入租户的RBAC策略
+async fn import_tenant_policies(
+    Path(tenant): Path<String>,
+    State(authz_service): State<Arc<AuthzService>>,
+    auth_ctx: Option<Extension<AuthContext>>,
+    Json(export): Json<PolicyExport>,
+) -> std::result::Result<Json<PolicyImportSummary>, StatusCode> {
+    require_tenant_admin(&authz_service, auth_ctx.as_deref(), &tenant).await?;
+
+    info!("Importing policies into tenant {}", tenant);
+
+    let summary = authz_service
+        .import_policies(&tenant, &export)
+        .await
+        .map_err(|e| match e {
+            ConfluxError::Validation(_) => {
+                warn!("Rejected policy import: {}", e);
+                StatusCode::BAD_REQUEST
+            }
+            _ => {
+                tracing::error!("Failed to import policies: {}", e);
+                StatusCode::INTERNAL_SERVER_ERROR
+            }
+        })?;
+
+    Ok(Json(summary))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::roles;
+    use axum::{body::Body, extract::Request};
+    use tower::ServiceExt;
+
+    async fn setup_service() -> Arc<AuthzService> {
+        let service = AuthzService::new_in_memory().await.unwrap();
+        for tenant in ["tenant1", "tenant2"] {
+            service
+                .add_permission_for_role(roles::VIEWER, tenant, "/tenants/*", "read")
+                .await
+                .unwrap();
+        }
+        service
+            .assign_role_to_user("alice", roles::TENANT_ADMIN, "tenant1")
+            .await
+            .unwrap();
+        service
+            .assign_role_to_user("bob", roles::VIEWER, "tenant1")
+            .await
+            .unwrap();
+        Arc::new(service)
+    }
+
+    async fn get(service: Arc<AuthzService>, user: &str, path: &str) -> (StatusCode, String) {
+        let app: Router = create_auth_routes(service).layer(Extension(AuthContext::new(
+            user.to_string(),
+            "tenant1".to_string(),
+        )));
+        let response = app
+            .oneshot(Request::builder().uri(path).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, String::from_utf8(body.to_vec()).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_policy_endpoints_require_tenant_admin() {
+        let service = setup_service().await;
+
+        let (status, body) = get(service.clone(), "alice", "/tenants/tenant1/policies").await;
+        assert_eq!(status, StatusCode::OK);
+        let policies: Vec<PolicyRule> = serde_json::from_str(&body).unwrap();
+        assert_eq!(policies.len(), 1);
+        assert_eq!(policies[0].tenant, "tenant1");
+
+        let (status, _) = get(service.clone(), "bob", "/tenants/tenant1/policies").await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+
+        // 租户管理员不能查看其他租户的策略
+        let (status, _) = get(service, "alice", "/tenants/tenant2/policies/export").await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+    }
+}
@@ -0,0 +1,215 @@
+//! 权限检查缓存
+//!
+//! 缓存 (user, tenant, resource, action) 的检查结果，减少对Casbin Enforcer的重复调用。
+//! 任何策略变更都会递增代数（generation），使旧的缓存条目整体失效。
+
+use dashmap::DashMap;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+/// 默认的缓存过期时间
+pub const DEFAULT_PERMISSION_CACHE_TTL: Duration = Duration::from_secs(5);
+
+/// 缓存键：(user, tenant, resource, action)
+type CacheKey = (String, String, String, String);
+
+/// 缓存条目
+#[derive(Debug, Clone, Copy)]
+struct CacheEntry {
+    allowed: bool,
+    inserted_at: Instant,
+    generation: u64,
+}
+
+/// 权限缓存统计信息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PermissionCacheStats {
+    /// 命中次数
+    pub hits: u64,
+    /// 未命中次数
+    pub misses: u64,
+    /// 命中率（0.0 - 1.0）
+    pub hit_rate: f64,
+    /// 当前缓存条目数
+    pub entries: usize,
+    /// 当前策略代数
+    pub generation: u64,
+}
+
+/// 带TTL的权限检查缓存
+#[derive(Debug)]
+pub struct PermissionCache {
+    entries: DashMap<CacheKey, CacheEntry>,
+    ttl: Duration,
+    generation: AtomicU64,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl PermissionCache {
+    /// 创建新的权限缓存
+    ///
+    /// # Arguments
+    /// * `ttl` - 缓存条目的有效期，为零时禁用缓存
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            entries: DashMap::new(),
+            ttl,
+            generation: AtomicU64::new(0),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    /// 缓存是否启用
+    pub fn is_enabled(&self) -> bool {
+        !self.ttl.is_zero()
+    }
+
+    /// 当前策略代数
+    pub fn generation(&self) -> u64 {
+        self.generation.load(Ordering::Acquire)
+    }
+
+    /// 查询缓存，命中时返回检查结果
+    pub fn get(&self, user_id: &str, tenant: &str, resource: &str, action: &str) -> Option<bool> {
+        if !self.is_enabled() {
+            return None;
+        }
+
+        let key = Self::key(user_id, tenant, resource, action);
+        let generation = self.generation();
+
+        let cached = self.entries.get(&key).map(|entry| *entry);
+        match cached {
+            Some(entry)
+                if entry.generation == generation && entry.inserted_at.elapsed() < self.ttl =>
+            {
+                self.hits.fetch_add(1, Ordering::Relaxed);
+                Some(entry.allowed)
+            }
+            Some(_) => {
+                self.entries.remove(&key);
+                self.misses.fetch_add(1, Ordering::Relaxed);
+                None
+            }
+            None => {
+                self.misses.fetch_add(1, Ordering::Relaxed);
+                None
+            }
+        }
+    }
+
+    /// 写入检查结果
+    ///
+    /// `generation` 应为执行检查前读取的代数，防止并发的策略变更被旧结果覆盖
+    pub fn insert(
+        &self,
+        user_id: &str,
+        tenant: &str,
+        resource: &str,
+        action: &str,
+        allowed: bool,
+        generation: u64,
+    ) {
+        if !self.is_enabled() || generation != self.generation() {
+            return;
+        }
+
+        self.entries.insert(
+            Self::key(user_id, tenant, resource, action),
+            CacheEntry {
+                allowed,
+                inserted_at: Instant::now(),
+                generation,
+            },
+        );
+    }
+
+    /// 使所有缓存条目失效（策略变更时调用）
+    pub fn invalidate(&self) {
+        self.generation.fetch_add(1, Ordering::AcqRel);
+        self.entries.clear();
+    }
+
+    /// 获取缓存统计信息
+    pub fn stats(&self) -> PermissionCacheStats {
+        let hits = self.hits.load(Ordering::Relaxed);
+        let misses = self.misses.load(Ordering::Relaxed);
+        let total = hits + misses;
+
+        PermissionCacheStats {
+            hits,
+            misses,
+            hit_rate: if total == 0 { 0.0 } else { hits as f64 / total as f64 },
+            entries: self.entries.len(),
+            generation: self.generation(),
+        }
+    }
+
+    fn key(user_id: &str, tenant: &str, resource: &str, action: &str) -> CacheKey {
+        (
+            user_id.to_string(),
+            tenant.to_string(),
+            resource.to_string(),
+            action.to_string(),
+        )
+    }
+}
+
+impl Default for PermissionCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_PERMISSION_CACHE_TTL)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cache_hit_and_miss() {
+        let cache = PermissionCache::default();
+        assert_eq!(cache.get("u", "t", "/r", "read"), None);
+
+        cache.insert("u", "t", "/r", "read", true, cache.generation());
+        assert_eq!(cache.get("u", "t", "/r", "read"), Some(true));
+
+        let stats = cache.stats();
+        assert_eq!(stats.hits, 1);
+        assert_eq!(stats.misses, 1);
+        assert_eq!(stats.hit_rate, 0.5);
+    }
+
+    #[test]
+    fn test_invalidate_bumps_generation() {
+        let cache = PermissionCache::default();
+        let generation = cache.generation();
+        cache.insert("u", "t", "/r", "read", true, generation);
+
+        cache.invalidate();
+        assert_eq!(cache.generation(), generation + 1);
+        assert_eq!(cache.get("u", "t", "/r", "read"), None);
+
+        // 旧代数的结果不会被写入
+        cache.insert("u", "t", "/r", "read", true, generation);
+        assert_eq!(cache.get("u", "t", "/r", "read"), None);
+    }
+
+    #[test]
+    fn test_entry_expires_after_ttl() {
+        let cache = PermissionCache::new(Duration::from_millis(10));
+        cache.insert("u", "t", "/r", "read", true, cache.generation());
+        std::thread::sleep(Duration::from_millis(20));
+        assert_eq!(cache.get("u", "t", "/r", "read"), None);
+    }
+
+    #[test]
+    fn test_zero_ttl_disables_cache() {
+        let cache = PermissionCache::new(Duration::ZERO);
+        cache.insert("u", "t", "/r", "read", true, cache.generation());
+        assert_eq!(cache.get("u", "t", "/r", "read"), None);
+        assert_eq!(cache.stats().misses, 0);
+    }
+}
@@ -0,0 +1,300 @@
+//! JWT认证
+//!
+//! 从 `Authorization: Bearer <token>` 头中提取并校验JWT，
+//! 解析 `user_id` 与 `tenant_id` 声明并构建 [`AuthContext`] 放入请求扩展中。
+
+use axum::{
+    extract::{Request, State},
+    http::{header::AUTHORIZATION, HeaderMap, StatusCode},
+    middleware::Next,
+    response::Response,
+};
+use jsonwebtoken::{decode, encode, Algorithm, DecodingKey, EncodingKey, Header, Validation};
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use tracing::{debug, warn};
+
+use super::middleware::is_public_endpoint;
+use super::AuthContext;
+use crate::config::SecurityConfig;
+use crate::error::{ConfluxError, Result};
+
+/// JWT声明
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Claims {
+    /// 用户ID
+    pub user_id: String,
+    /// 租户ID
+    pub tenant_id: String,
+    /// 用户角色（可选）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub roles: Option<Vec<String>>,
+    /// 签发时间（Unix秒）
+    pub iat: i64,
+    /// 过期时间（Unix秒）
+    pub exp: i64,
+}
+
+/// JWT签发与校验器
+pub struct JwtAuthenticator {
+    encoding_key: EncodingKey,
+    decoding_key: DecodingKey,
+    validation: Validation,
+    /// 签发token的有效期（小时），可在运行时热更新
+    expiration_hours: AtomicU64,
+}
+
+impl JwtAuthenticator {
+    /// 创建新的JWT校验器
+    ///
+    /// # Arguments
+    /// * `secret` - HMAC-SHA256签名密钥
+    /// * `expiration_hours` - 签发token的有效期（小时）
+    pub fn new(secret: &str, expiration_hours: u64) -> Self {
+        let mut validation = Validation::new(Algorithm::HS256);
+        validation.leeway = 0;
+        validation.set_required_spec_claims(&["exp"]);
+
+        Self {
+            encoding_key: EncodingKey::from_secret(secret.as_bytes()),
+            decoding_key: DecodingKey::from_secret(secret.as_bytes()),
+            validation,
+            expiration_hours: AtomicU64::new(expiration_hours),
+        }
+    }
+
+    /// 根据安全配置创建JWT校验器
+    pub fn from_config(config: &SecurityConfig) -> Self {
+        Self::new(&config.jwt_secret, config.jwt_expiration_hours)
+    }
+
+    /// 更新之后签发token的有效期，已签发的token不受影响
+    ///
+    /// # Arguments
+    /// * `expiration_hours` - 新的有效期（小时）
+    pub fn set_expiration_hours(&self, expiration_hours: u64) {
+        self.expiration_hours.store(expiration_hours, Ordering::Relaxed);
+    }
+
+    /// 当前签发token的有效期（小时）
+    pub fn expiration_hours(&self) -> u64 {
+        self.expiration_hours.load(Ordering::Relaxed)
+    }
+
+    /// 为用户签发token
+    ///
+    /// # Arguments
+    /// * `user_id` - 用户ID
+    /// * `tenant_id` - 租户ID
+    ///
+    /// # Returns
+    /// 签名后的JWT字符串
+    pub fn issue_token(&self, user_id: &str, tenant_id: &str) -> Result<String> {
+        let now = chrono::Utc::now();
+        let expiration = chrono::Duration::hours(self.expiration_hours() as i64);
+        self.encode_claims(&Claims {
+            user_id: user_id.to_string(),
+            tenant_id: tenant_id.to_string(),
+            roles: None,
+            iat: now.timestamp(),
+            exp: (now + expiration).timestamp(),
+        })
+    }
+
+    /// 对任意声明签名
+    pub fn encode_claims(&self, claims: &Claims) -> Result<String> {
+        encode(&Header::new(Algorithm::HS256), claims, &self.encoding_key)
+            .map_err(|e| ConfluxError::AuthError(format!("Failed to sign token: {}", e)))
+    }
+
+    /// 校验token并构建认证上下文
+    ///
+    /// # Arguments
+    /// * `token` - JWT字符串（不含 "Bearer " 前缀）
+    ///
+    /// # Returns
+    /// 签名有效且未过期时返回认证上下文，否则返回AuthError
+    pub fn verify(&self, token: &str) -> Result<AuthContext> {
+        let data = decode::<Claims>(token, &self.decoding_key, &self.validation)
+            .map_err(|e| ConfluxError::AuthError(format!("Invalid token: {}", e)))?;
+        let claims = data.claims;
+
+        if claims.user_id.is_empty() || claims.tenant_id.is_empty() {
+            return Err(ConfluxError::AuthError("Empty user_id or tenant_id".to_string()));
+        }
+
+        Ok(match claims.roles {
+            Some(roles) => AuthContext::with_roles(claims.user_id, claims.tenant_id, roles),
+            None => AuthContext::new(claims.user_id, claims.tenant_id),
+        })
+    }
+}
+
+/// Axum JWT认证中间件
+///
+/// 对非公共端点校验Bearer token，成功后将 [`AuthContext`] 插入请求扩展，
+/// token缺失、过期或无效时返回401
+pub async fn jwt_auth_middleware(
+    State(authenticator): State<Arc<JwtAuthenticator>>,
+    mut request: Request,
+    next: Next,
+) -> std::result::Result<Response, StatusCode> {
+    if is_public_endpoint(request.uri().path()) {
+        return Ok(next.run(request).await);
+    }
+
+    let auth_context = bearer_token(request.headers())
+        .and_then(|token| authenticator.verify(token))
+        .map_err(|e| {
+            warn!("JWT authentication failed for {}: {}", request.uri().path(), e);
+            StatusCode::UNAUTHORIZED
+        })?;
+
+    debug!(
+        "Authenticated user={}, tenant={}",
+        auth_context.user_id, auth_context.tenant_id
+    );
+    request.extensions_mut().insert(auth_context.clone());
+    let mut response = next.run(request).await;
+    // 外层的请求日志中间件看不到内层注入的请求扩展，通过响应扩展回传认证信息
+    response.extensions_mut().insert(auth_context);
+    Ok(response)
+}
+
+/// 从请求头中提取Bearer token
+fn bearer_token(headers: &HeaderMap) -> Result<&str> {
+    let value = headers
+        .get(AUTHORIZATION)
+        .ok_or_else(|| ConfluxError::AuthError("Missing authorization header".to_string()))?
+        .to_str()
+        .map_err(|_| ConfluxError::AuthError("Invalid authorization header".to_string()))?;
+
+    value
+        .strip_prefix("Bearer ")
+        .filter(|token| !token.is_empty())
+        .ok_or_else(|| ConfluxError::AuthError("Invalid authorization format".to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, middleware::from_fn_with_state, routing::get, Extension, Router};
+    use tower::ServiceExt;
+
+    const SECRET: &str = "test-secret";
+
+    fn authenticator() -> Arc<JwtAuthenticator> {
+        Arc::new(JwtAuthenticator::new(SECRET, 1))
+    }
+
+    fn app(authenticator: Arc<JwtAuthenticator>) -> Router {
+        Router::new()
+            .route(
+                "/api/v1/whoami",
+                get(|Extension(ctx): Extension<AuthContext>| async move {
+                    format!("{}@{}", ctx.user_id, ctx.tenant_id)
+                }),
+            )
+            .route("/health", get(|| async { "ok" }))
+            .layer(from_fn_with_state(authenticator, jwt_auth_middleware))
+    }
+
+    async fn call(app: Router, path: &str, token: Option<&str>) -> (StatusCode, String) {
+        let mut builder = Request::builder().uri(path);
+        if let Some(token) = token {
+            builder = builder.header(AUTHORIZATION, format!("Bearer {}", token));
+        }
+        let response = app.oneshot(builder.body(Body::empty()).unwrap()).await.unwrap();
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, String::from_utf8(body.to_vec()).unwrap())
+    }
+
+    #[test]
+    fn test_issue_and_verify_token() {
+        let auth = authenticator();
+        let token = auth.issue_token("user1", "tenant1").unwrap();
+
+        let ctx = auth.verify(&token).unwrap();
+        assert_eq!(ctx.user_id, "user1");
+        assert_eq!(ctx.tenant_id, "tenant1");
+        assert!(ctx.roles.is_none());
+    }
+
+    #[test]
+    fn test_tampered_token_is_rejected() {
+        let auth = authenticator();
+        let token = auth.issue_token("user1", "tenant1").unwrap();
+
+        // 替换payload为另一个用户的声明，但保留原签名
+        let forged = auth.issue_token("admin", "tenant1").unwrap();
+        let parts: Vec<&str> = token.split('.').collect();
+        let forged_parts: Vec<&str> = forged.split('.').collect();
+        let tampered = format!("{}.{}.{}", parts[0], forged_parts[1], parts[2]);
+
+        assert!(auth.verify(&tampered).is_err());
+    }
+
+    #[test]
+    fn test_token_signed_with_other_secret_is_rejected() {
+        let token = JwtAuthenticator::new("other-secret", 1)
+            .issue_token("user1", "tenant1")
+            .unwrap();
+        assert!(authenticator().verify(&token).is_err());
+    }
+
+    #[test]
+    fn test_expiration_update_applies_to_new_tokens() {
+        let auth = authenticator();
+        auth.set_expiration_hours(2);
+        assert_eq!(auth.expiration_hours(), 2);
+
+        let token = auth.issue_token("user1", "tenant1").unwrap();
+        let claims = decode::<Claims>(&token, &auth.decoding_key, &auth.validation)
+            .unwrap()
+            .claims;
+        assert_eq!(claims.exp - claims.iat, 2 * 3600);
+    }
+
+    #[test]
+    fn test_expired_token_is_rejected() {
+        let auth = authenticator();
+        let now = chrono::Utc::now().timestamp();
+        let token = auth
+            .encode_claims(&Claims {
+                user_id: "user1".to_string(),
+                tenant_id: "tenant1".to_string(),
+                roles: None,
+                iat: now - 7200,
+                exp: now - 3600,
+            })
+            .unwrap();
+        assert!(auth.verify(&token).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_middleware_populates_auth_context() {
+        let auth = authenticator();
+        let token = auth.issue_token("user1", "tenant1").unwrap();
+
+        let (status, body) = call(app(auth), "/api/v1/whoami", Some(&token)).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, "user1@tenant1");
+    }
+
+    #[tokio::test]
+    async fn test_middleware_rejects_missing_and_invalid_tokens() {
+        let auth = authenticator();
+
+        let (status, _) = call(app(auth.clone()), "/api/v1/whoami", None).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+        let (status, _) = call(app(auth.clone()), "/api/v1/whoami", Some("not-a-jwt")).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+        // 公共端点无需token
+        let (status, _) = call(app(auth), "/health", None).await;
+        assert_eq!(status, StatusCode::OK);
+    }
+}
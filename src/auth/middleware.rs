@@ -160,11 +160,12 @@ fn parse_resource_and_action(method: &Method, uri: &Uri) -> Result<(String, Stri
 }
 
 /// 检查是否为公共端点（不需要认证）
-fn is_public_endpoint(path: &str) -> bool {
+pub(crate) fn is_public_endpoint(path: &str) -> bool {
     let public_paths = [
         "/health",
         "/ready",
         "/_cluster/status",
+        "/_cluster/ping",     // 节点间连接心跳
         "/metrics",
         "/api/v1/auth/login", // 登录端点
     ];
@@ -222,6 +223,9 @@ mod tests {
         assert!(is_public_endpoint("/health"));
         assert!(is_public_endpoint("/ready"));
         assert!(is_public_endpoint("/api/v1/auth/login"));
+        assert!(is_public_endpoint("/_cluster/ping"));
         assert!(!is_public_endpoint("/api/v1/configs"));
+        assert!(!is_public_endpoint("/_cluster/pre-vote"));
+        assert!(!is_public_endpoint("/_cluster/metrics/local"));
     }
 }
@@ -3,14 +3,20 @@
 //! 基于Casbin实现的RBAC权限控制系统，支持多租户架构
 
 pub mod api;
+pub mod cache;
+pub mod jwt;
 pub mod middleware;
+pub mod policy;
 pub mod service;
 
 #[cfg(test)]
 mod unit_tests;
 
 pub use api::create_auth_routes;
+pub use cache::{PermissionCache, PermissionCacheStats};
+pub use jwt::{jwt_auth_middleware, Claims, JwtAuthenticator};
 pub use middleware::{authz_middleware, AuthzMiddleware};
+pub use policy::{PolicyExport, PolicyImportSummary, PolicyRule, RoleAssignment};
 pub use service::AuthzService;
 
 /// 认证上下文
@@ -111,6 +117,13 @@ pub mod roles {
     pub const CLUSTER_ADMIN: &str = "cluster_admin";
     pub const CLUSTER_OPERATOR: &str = "cluster_operator";
     pub const CLUSTER_VIEWER: &str = "cluster_viewer";
+
+    /// 默认角色层级：(父角色, 子角色)，父角色继承子角色的全部权限
+    pub const DEFAULT_HIERARCHY: &[(&str, &str)] = &[
+        (SUPER_ADMIN, TENANT_ADMIN),
+        (TENANT_ADMIN, DEVELOPER),
+        (DEVELOPER, VIEWER),
+    ];
 }
 
 /// 资源路径构建器
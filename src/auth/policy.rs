@@ -0,0 +1,100 @@
+//! 策略导出与导入的数据结构
+//!
+//! 对应Casbin模型中的 `p` 规则（角色权限）和 `g` 规则（角色分配与继承），
+//! 导出格式为JSON，可直接用于批量导入，便于将RBAC策略纳入版本管理
+
+use serde::{Deserialize, Serialize};
+
+/// 角色权限规则（Casbin `p` 规则）
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct PolicyRule {
+    /// 角色名称
+    pub role: String,
+    /// 租户ID
+    pub tenant: String,
+    /// 资源路径模式
+    pub resource: String,
+    /// 操作类型
+    pub action: String,
+}
+
+impl PolicyRule {
+    /// 从Casbin规则字段构建，字段数量不符时返回None
+    pub(crate) fn from_rule(rule: &[String]) -> Option<Self> {
+        match rule {
+            [role, tenant, resource, action, ..] => Some(Self {
+                role: role.clone(),
+                tenant: tenant.clone(),
+                resource: resource.clone(),
+                action: action.clone(),
+            }),
+            _ => None,
+        }
+    }
+
+    /// 转换为Casbin规则字段
+    pub(crate) fn to_rule(&self) -> Vec<String> {
+        vec![
+            self.role.clone(),
+            self.tenant.clone(),
+            self.resource.clone(),
+            self.action.clone(),
+        ]
+    }
+}
+
+/// 角色分配规则（Casbin `g` 规则）
+///
+/// `subject` 可以是用户，也可以是继承 `role` 权限的父角色
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct RoleAssignment {
+    /// 用户ID或父角色名称
+    pub subject: String,
+    /// 被分配的角色
+    pub role: String,
+    /// 租户ID
+    pub tenant: String,
+}
+
+impl RoleAssignment {
+    /// 从Casbin规则字段构建，字段数量不符时返回None
+    pub(crate) fn from_rule(rule: &[String]) -> Option<Self> {
+        match rule {
+            [subject, role, tenant, ..] => Some(Self {
+                subject: subject.clone(),
+                role: role.clone(),
+                tenant: tenant.clone(),
+            }),
+            _ => None,
+        }
+    }
+
+    /// 转换为Casbin规则字段
+    pub(crate) fn to_rule(&self) -> Vec<String> {
+        vec![self.subject.clone(), self.role.clone(), self.tenant.clone()]
+    }
+}
+
+/// 租户策略导出
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PolicyExport {
+    /// 租户ID
+    pub tenant: String,
+    /// 角色权限规则
+    #[serde(default)]
+    pub policies: Vec<PolicyRule>,
+    /// 角色分配规则
+    #[serde(default)]
+    pub role_assignments: Vec<RoleAssignment>,
+}
+
+/// 策略导入结果
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PolicyImportSummary {
+    /// 新增的权限规则数量
+    pub policies_added: usize,
+    /// 新增的角色分配数量
+    pub role_assignments_added: usize,
+    /// 已存在而跳过的规则数量
+    pub skipped: usize,
+}
@@ -1,9 +1,13 @@
-use casbin::{CoreApi, Enforcer, MgmtApi, RbacApi};
+use casbin::{Adapter, CoreApi, Enforcer, MemoryAdapter, MgmtApi, RbacApi};
 use sqlx_adapter::SqlxAdapter;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::RwLock;
 use tracing::{debug, error, info};
 
+use super::cache::{PermissionCache, PermissionCacheStats};
+use super::policy::{PolicyExport, PolicyImportSummary, PolicyRule, RoleAssignment};
+use super::roles;
 use crate::error::{ConfluxError, Result};
 
 /// 认证授权服务
@@ -12,6 +16,8 @@ use crate::error::{ConfluxError, Result};
 #[derive(Clone)]
 pub struct AuthzService {
     enforcer: Arc<RwLock<Enforcer>>,
+    /// 权限检查结果缓存，策略变更时失效
+    cache: Arc<PermissionCache>,
 }
 
 impl AuthzService {
@@ -33,6 +39,22 @@ impl AuthzService {
                 ConfluxError::AuthError(format!("Failed to create SqlxAdapter: {}", e))
             })?;
 
+        Self::with_adapter(adapter).await
+    }
+
+    /// 创建一个使用内存适配器的AuthzService实例
+    ///
+    /// 策略不会持久化，主要用于测试和单机开发环境
+    ///
+    /// # Returns
+    /// * `Result<Self>` - 成功时返回AuthzService实例
+    pub async fn new_in_memory() -> Result<Self> {
+        info!("Initializing in-memory AuthzService with Casbin");
+        Self::with_adapter(MemoryAdapter::default()).await
+    }
+
+    /// 使用指定的适配器创建Enforcer
+    async fn with_adapter<A: Adapter + 'static>(adapter: A) -> Result<Self> {
         // 创建Enforcer，使用model.conf文件
         let model_path = "src/auth/model.conf";
         let mut enforcer = Enforcer::new(model_path, adapter)
@@ -52,9 +74,27 @@ impl AuthzService {
         
         Ok(Self {
             enforcer: Arc::new(RwLock::new(enforcer)),
+            cache: Arc::new(PermissionCache::default()),
         })
     }
 
+    /// 设置权限检查缓存的过期时间
+    ///
+    /// # Arguments
+    /// * `ttl` - 缓存有效期，为零时禁用缓存
+    ///
+    /// # Returns
+    /// * `Self` - 使用新缓存配置的AuthzService
+    pub fn with_cache_ttl(mut self, ttl: Duration) -> Self {
+        self.cache = Arc::new(PermissionCache::new(ttl));
+        self
+    }
+
+    /// 获取权限缓存的命中率等统计信息
+    pub fn cache_stats(&self) -> PermissionCacheStats {
+        self.cache.stats()
+    }
+
     /// 核心检查函数：检查一个用户在特定租户下是否有权对资源执行操作
     /// 
     /// # Arguments
@@ -77,6 +117,16 @@ impl AuthzService {
             user_id, tenant, resource, action
         );
 
+        // 必须在读取缓存之前获取代数，避免并发的策略变更被旧结果覆盖
+        let generation = self.cache.generation();
+        if let Some(allowed) = self.cache.get(user_id, tenant, resource, action) {
+            debug!(
+                "Permission cache hit: user={}, tenant={}, resource={}, action={}, allowed={}",
+                user_id, tenant, resource, action, allowed
+            );
+            return Ok(allowed);
+        }
+
         let enforcer = self.enforcer.read().await;
         let result = enforcer
             .enforce((user_id, tenant, resource, action))
@@ -84,6 +134,7 @@ impl AuthzService {
                 error!("Permission check failed: {}", e);
                 ConfluxError::AuthError(format!("Permission check failed: {}", e))
             })?;
+        self.cache.insert(user_id, tenant, resource, action, result, generation);
 
         debug!(
             "Permission check result: user={}, tenant={}, resource={}, action={}, allowed={}",
@@ -93,6 +144,70 @@ impl AuthzService {
         Ok(result)
     }
 
+    /// 批量检查用户在特定租户下对多个资源的权限
+    ///
+    /// 先查询缓存，未命中的条目在同一次Enforcer读锁内完成检查并写回缓存
+    ///
+    /// # Arguments
+    /// * `user_id` - 发起请求的用户唯一标识
+    /// * `tenant` - 请求所属的租户
+    /// * `requests` - 待检查的 (资源路径, 操作) 列表
+    ///
+    /// # Returns
+    /// * `Result<Vec<bool>>` - 与输入顺序一致的检查结果
+    pub async fn check_batch<R, A>(
+        &self,
+        user_id: &str,
+        tenant: &str,
+        requests: &[(R, A)],
+    ) -> Result<Vec<bool>>
+    where
+        R: AsRef<str>,
+        A: AsRef<str>,
+    {
+        debug!(
+            "Checking {} permissions in batch: user={}, tenant={}",
+            requests.len(),
+            user_id,
+            tenant
+        );
+
+        let generation = self.cache.generation();
+        let cached: Vec<Option<bool>> = requests
+            .iter()
+            .map(|(resource, action)| {
+                self.cache
+                    .get(user_id, tenant, resource.as_ref(), action.as_ref())
+            })
+            .collect();
+
+        if cached.iter().all(Option::is_some) {
+            return Ok(cached.into_iter().flatten().collect());
+        }
+
+        let enforcer = self.enforcer.read().await;
+        requests
+            .iter()
+            .zip(cached)
+            .map(|((resource, action), cached)| {
+                if let Some(allowed) = cached {
+                    return Ok(allowed);
+                }
+
+                let (resource, action) = (resource.as_ref(), action.as_ref());
+                let allowed = enforcer
+                    .enforce((user_id, tenant, resource, action))
+                    .map_err(|e| {
+                        error!("Permission check failed: {}", e);
+                        ConfluxError::AuthError(format!("Permission check failed: {}", e))
+                    })?;
+                self.cache
+                    .insert(user_id, tenant, resource, action, allowed, generation);
+                Ok(allowed)
+            })
+            .collect()
+    }
+
     /// 为角色添加权限
     /// 
     /// # Arguments
@@ -127,6 +242,7 @@ impl AuthzService {
             error!("Failed to add permission: {}", e);
             ConfluxError::AuthError(format!("Failed to add permission: {}", e))
         })?;
+        self.cache.invalidate();
 
         info!(
             "Permission added successfully: role={}, tenant={}, resource={}, action={}",
@@ -170,6 +286,7 @@ impl AuthzService {
             error!("Failed to remove permission: {}", e);
             ConfluxError::AuthError(format!("Failed to remove permission: {}", e))
         })?;
+        self.cache.invalidate();
 
         info!(
             "Permission removed successfully: role={}, tenant={}, resource={}, action={}",
@@ -207,6 +324,7 @@ impl AuthzService {
                 error!("Failed to assign role: {}", e);
                 ConfluxError::AuthError(format!("Failed to assign role: {}", e))
             })?;
+        self.cache.invalidate();
 
         info!(
             "Role assigned successfully: user={}, role={}, tenant={}",
@@ -218,6 +336,8 @@ impl AuthzService {
 
     /// 撤销用户的角色
     /// 
+    /// 与 [`AuthzService::remove_role_for_user`] 相同
+    /// 
     /// # Arguments
     /// * `user_id` - 用户ID
     /// * `role` - 角色名称
@@ -230,29 +350,180 @@ impl AuthzService {
         user_id: &str,
         role: &str,
         tenant: &str,
+    ) -> Result<bool> {
+        self.remove_role_for_user(user_id, role, tenant).await
+    }
+
+    /// 移除用户在租户下的角色分配
+    ///
+    /// 通过Enforcer删除对应的 `g` 规则并持久化，同时使权限缓存失效
+    ///
+    /// # Arguments
+    /// * `user_id` - 用户ID
+    /// * `role` - 角色名称
+    /// * `tenant` - 租户ID
+    ///
+    /// # Returns
+    /// * `Result<bool>` - 是否移除了已存在的角色分配
+    pub async fn remove_role_for_user(
+        &self,
+        user_id: &str,
+        role: &str,
+        tenant: &str,
     ) -> Result<bool> {
         info!(
-            "Revoking role from user: user={}, role={}, tenant={}",
+            "Removing role from user: user={}, role={}, tenant={}",
             user_id, role, tenant
         );
 
         let mut enforcer = self.enforcer.write().await;
+        let rule = vec![user_id.to_string(), role.to_string(), tenant.to_string()];
+        let result = enforcer.remove_grouping_policy(rule).await.map_err(|e| {
+            error!("Failed to remove role: {}", e);
+            ConfluxError::AuthError(format!("Failed to remove role: {}", e))
+        })?;
+        self.cache.invalidate();
+
+        info!(
+            "Role removal finished: user={}, role={}, tenant={}, removed={}",
+            user_id, role, tenant, result
+        );
+
+        Ok(result)
+    }
+
+    /// 添加角色继承关系
+    ///
+    /// 父角色将自动拥有子角色的全部权限，继承关系可传递
+    ///
+    /// # Arguments
+    /// * `parent_role` - 父角色（更高级别的角色）
+    /// * `child_role` - 子角色（被继承的角色）
+    /// * `tenant` - 租户ID
+    ///
+    /// # Returns
+    /// * `Result<bool>` - 是否成功添加
+    pub async fn add_role_inheritance(
+        &self,
+        parent_role: &str,
+        child_role: &str,
+        tenant: &str,
+    ) -> Result<bool> {
+        info!(
+            "Adding role inheritance: parent={}, child={}, tenant={}",
+            parent_role, child_role, tenant
+        );
+
+        if parent_role == child_role {
+            return Err(ConfluxError::AuthError(format!(
+                "Role cannot inherit from itself: {}",
+                parent_role
+            )));
+        }
+
+        let mut enforcer = self.enforcer.write().await;
+
+        // 防止形成循环继承
+        let child_ancestors = enforcer.get_implicit_roles_for_user(child_role, Some(tenant));
+        if child_ancestors.iter().any(|r| r == parent_role) {
+            return Err(ConfluxError::AuthError(format!(
+                "Role inheritance cycle detected: {} -> {}",
+                parent_role, child_role
+            )));
+        }
+
         let result = enforcer
-            .delete_role_for_user(user_id, role, Some(tenant))
+            .add_grouping_policy(vec![
+                parent_role.to_string(),
+                child_role.to_string(),
+                tenant.to_string(),
+            ])
             .await
             .map_err(|e| {
-                error!("Failed to revoke role: {}", e);
-                ConfluxError::AuthError(format!("Failed to revoke role: {}", e))
+                error!("Failed to add role inheritance: {}", e);
+                ConfluxError::AuthError(format!("Failed to add role inheritance: {}", e))
             })?;
+        self.cache.invalidate();
 
         info!(
-            "Role revoked successfully: user={}, role={}, tenant={}",
-            user_id, role, tenant
+            "Role inheritance added successfully: parent={}, child={}, tenant={}",
+            parent_role, child_role, tenant
         );
 
         Ok(result)
     }
 
+    /// 移除角色继承关系
+    ///
+    /// # Arguments
+    /// * `parent_role` - 父角色
+    /// * `child_role` - 子角色
+    /// * `tenant` - 租户ID
+    ///
+    /// # Returns
+    /// * `Result<bool>` - 是否成功移除
+    pub async fn remove_role_inheritance(
+        &self,
+        parent_role: &str,
+        child_role: &str,
+        tenant: &str,
+    ) -> Result<bool> {
+        info!(
+            "Removing role inheritance: parent={}, child={}, tenant={}",
+            parent_role, child_role, tenant
+        );
+
+        let mut enforcer = self.enforcer.write().await;
+        let result = enforcer
+            .remove_grouping_policy(vec![
+                parent_role.to_string(),
+                child_role.to_string(),
+                tenant.to_string(),
+            ])
+            .await
+            .map_err(|e| {
+                error!("Failed to remove role inheritance: {}", e);
+                ConfluxError::AuthError(format!("Failed to remove role inheritance: {}", e))
+            })?;
+        self.cache.invalidate();
+
+        Ok(result)
+    }
+
+    /// 为租户应用默认的角色层级
+    ///
+    /// 层级定义见 [`roles::DEFAULT_HIERARCHY`]：
+    /// super_admin > tenant_admin > developer > viewer
+    ///
+    /// # Arguments
+    /// * `tenant` - 租户ID
+    ///
+    /// # Returns
+    /// * `Result<()>` - 是否成功应用
+    pub async fn apply_default_role_hierarchy(&self, tenant: &str) -> Result<()> {
+        for (parent, child) in roles::DEFAULT_HIERARCHY {
+            self.add_role_inheritance(parent, child, tenant).await?;
+        }
+        Ok(())
+    }
+
+    /// 获取用户在租户下的所有角色（包含继承得到的角色）
+    ///
+    /// # Arguments
+    /// * `user_id` - 用户ID
+    /// * `tenant` - 租户ID
+    ///
+    /// # Returns
+    /// * `Result<Vec<String>>` - 角色列表
+    pub async fn get_implicit_roles_for_user_in_tenant(
+        &self,
+        user_id: &str,
+        tenant: &str,
+    ) -> Result<Vec<String>> {
+        let enforcer = self.enforcer.read().await;
+        Ok(enforcer.get_implicit_roles_for_user(user_id, Some(tenant)))
+    }
+
     /// 获取用户在租户下的所有角色
     /// 
     /// # Arguments
@@ -279,6 +550,140 @@ impl AuthzService {
         Ok(roles)
     }
 
+    /// 检查用户是否为租户管理员
+    ///
+    /// 直接或通过继承拥有 tenant_admin 或 super_admin 角色的用户视为租户管理员
+    ///
+    /// # Arguments
+    /// * `user_id` - 用户ID
+    /// * `tenant` - 租户ID
+    ///
+    /// # Returns
+    /// * `Result<bool>` - 是否为租户管理员
+    pub async fn is_tenant_admin(&self, user_id: &str, tenant: &str) -> Result<bool> {
+        let roles = self.get_implicit_roles_for_user_in_tenant(user_id, tenant).await?;
+        Ok(roles
+            .iter()
+            .any(|role| role == roles::TENANT_ADMIN || role == roles::SUPER_ADMIN))
+    }
+
+    /// 列出租户下的所有角色权限规则（`p` 规则）
+    ///
+    /// # Arguments
+    /// * `tenant` - 租户ID
+    ///
+    /// # Returns
+    /// * `Result<Vec<PolicyRule>>` - 按字段排序的权限规则
+    pub async fn list_policies(&self, tenant: &str) -> Result<Vec<PolicyRule>> {
+        let enforcer = self.enforcer.read().await;
+        let mut policies: Vec<PolicyRule> = enforcer
+            .get_filtered_policy(1, vec![tenant.to_string()])
+            .iter()
+            .filter_map(|rule| PolicyRule::from_rule(rule))
+            .collect();
+        policies.sort();
+        Ok(policies)
+    }
+
+    /// 列出租户下的所有角色分配规则（`g` 规则，包含角色继承）
+    ///
+    /// # Arguments
+    /// * `tenant` - 租户ID
+    ///
+    /// # Returns
+    /// * `Result<Vec<RoleAssignment>>` - 按字段排序的角色分配规则
+    pub async fn list_role_assignments(&self, tenant: &str) -> Result<Vec<RoleAssignment>> {
+        let enforcer = self.enforcer.read().await;
+        let mut assignments: Vec<RoleAssignment> = enforcer
+            .get_filtered_grouping_policy(2, vec![tenant.to_string()])
+            .iter()
+            .filter_map(|rule| RoleAssignment::from_rule(rule))
+            .collect();
+        assignments.sort();
+        Ok(assignments)
+    }
+
+    /// 导出租户的全部RBAC策略
+    ///
+    /// # Arguments
+    /// * `tenant` - 租户ID
+    ///
+    /// # Returns
+    /// * `Result<PolicyExport>` - 可用于 [`AuthzService::import_policies`] 的导出数据
+    pub async fn export_policies(&self, tenant: &str) -> Result<PolicyExport> {
+        Ok(PolicyExport {
+            tenant: tenant.to_string(),
+            policies: self.list_policies(tenant).await?,
+            role_assignments: self.list_role_assignments(tenant).await?,
+        })
+    }
+
+    /// 批量导入租户的RBAC策略
+    ///
+    /// 导入是增量的：已存在的规则会被跳过，不会删除现有规则。
+    /// 所有规则必须属于 `tenant`，否则整个导入被拒绝
+    ///
+    /// # Arguments
+    /// * `tenant` - 目标租户ID
+    /// * `export` - 由 [`AuthzService::export_policies`] 生成的导出数据
+    ///
+    /// # Returns
+    /// * `Result<PolicyImportSummary>` - 新增与跳过的规则数量
+    pub async fn import_policies(
+        &self,
+        tenant: &str,
+        export: &PolicyExport,
+    ) -> Result<PolicyImportSummary> {
+        let foreign_rule = export.tenant != tenant
+            || export.policies.iter().any(|p| p.tenant != tenant)
+            || export.role_assignments.iter().any(|g| g.tenant != tenant);
+        if foreign_rule {
+            return Err(ConfluxError::validation(format!(
+                "Policy import for tenant {} contains rules of another tenant",
+                tenant
+            )));
+        }
+
+        info!(
+            "Importing {} policies and {} role assignments into tenant {}",
+            export.policies.len(),
+            export.role_assignments.len(),
+            tenant
+        );
+
+        let mut summary = PolicyImportSummary::default();
+        let mut enforcer = self.enforcer.write().await;
+        for policy in &export.policies {
+            let added = enforcer.add_policy(policy.to_rule()).await.map_err(|e| {
+                error!("Failed to import policy: {}", e);
+                ConfluxError::AuthError(format!("Failed to import policy: {}", e))
+            })?;
+            if added {
+                summary.policies_added += 1;
+            } else {
+                summary.skipped += 1;
+            }
+        }
+        for assignment in &export.role_assignments {
+            let added = enforcer
+                .add_grouping_policy(assignment.to_rule())
+                .await
+                .map_err(|e| {
+                    error!("Failed to import role assignment: {}", e);
+                    ConfluxError::AuthError(format!("Failed to import role assignment: {}", e))
+                })?;
+            if added {
+                summary.role_assignments_added += 1;
+            } else {
+                summary.skipped += 1;
+            }
+        }
+        self.cache.invalidate();
+
+        info!("Policy import into tenant {} completed: {:?}", tenant, summary);
+        Ok(summary)
+    }
+
     /// 重新加载策略（用于热更新）
     /// 
     /// # Returns
@@ -297,6 +702,7 @@ impl AuthzService {
             error!("Failed to rebuild role links: {}", e);
             ConfluxError::AuthError(format!("Failed to rebuild role links: {}", e))
         })?;
+        self.cache.invalidate();
 
         info!("Casbin policies reloaded successfully");
         Ok(())
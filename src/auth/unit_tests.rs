@@ -154,3 +154,477 @@ mod unit_tests {
         assert_eq!(roles::VIEWER, "viewer");
     }
 }
+
+#[cfg(test)]
+mod role_hierarchy_tests {
+    use super::*;
+
+    async fn setup_service() -> AuthzService {
+        let service = AuthzService::new_in_memory().await.unwrap();
+        service
+            .add_permission_for_role(
+                roles::DEVELOPER,
+                "tenant1",
+                "/tenants/tenant1/*",
+                actions::WRITE,
+            )
+            .await
+            .unwrap();
+        service
+            .add_permission_for_role(
+                roles::VIEWER,
+                "tenant1",
+                "/tenants/tenant1/*",
+                actions::READ,
+            )
+            .await
+            .unwrap();
+        service
+    }
+
+    #[tokio::test]
+    async fn test_tenant_admin_inherits_developer_permissions() {
+        let service = setup_service().await;
+        service
+            .add_role_inheritance(roles::TENANT_ADMIN, roles::DEVELOPER, "tenant1")
+            .await
+            .unwrap();
+        service
+            .assign_role_to_user("alice", roles::TENANT_ADMIN, "tenant1")
+            .await
+            .unwrap();
+
+        let allowed = service
+            .check("alice", "tenant1", "/tenants/tenant1/configs", actions::WRITE)
+            .await
+            .unwrap();
+        assert!(allowed);
+    }
+
+    #[tokio::test]
+    async fn test_inheritance_is_transitive() {
+        let service = setup_service().await;
+        service.apply_default_role_hierarchy("tenant1").await.unwrap();
+        service
+            .assign_role_to_user("root", roles::SUPER_ADMIN, "tenant1")
+            .await
+            .unwrap();
+
+        // super_admin -> tenant_admin -> developer -> viewer
+        assert!(service
+            .check("root", "tenant1", "/tenants/tenant1/configs", actions::READ)
+            .await
+            .unwrap());
+
+        let implicit = service
+            .get_implicit_roles_for_user_in_tenant("root", "tenant1")
+            .await
+            .unwrap();
+        assert!(implicit.contains(&roles::VIEWER.to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_inheritance_is_scoped_to_tenant() {
+        let service = setup_service().await;
+        service
+            .add_role_inheritance(roles::TENANT_ADMIN, roles::DEVELOPER, "tenant2")
+            .await
+            .unwrap();
+        service
+            .assign_role_to_user("alice", roles::TENANT_ADMIN, "tenant1")
+            .await
+            .unwrap();
+
+        let allowed = service
+            .check("alice", "tenant1", "/tenants/tenant1/configs", actions::WRITE)
+            .await
+            .unwrap();
+        assert!(!allowed);
+    }
+
+    #[tokio::test]
+    async fn test_lower_role_does_not_gain_higher_permissions() {
+        let service = setup_service().await;
+        service.apply_default_role_hierarchy("tenant1").await.unwrap();
+        service
+            .assign_role_to_user("bob", roles::VIEWER, "tenant1")
+            .await
+            .unwrap();
+
+        let allowed = service
+            .check("bob", "tenant1", "/tenants/tenant1/configs", actions::WRITE)
+            .await
+            .unwrap();
+        assert!(!allowed);
+    }
+
+    #[tokio::test]
+    async fn test_inheritance_cycle_is_rejected() {
+        let service = setup_service().await;
+        service
+            .add_role_inheritance(roles::TENANT_ADMIN, roles::DEVELOPER, "tenant1")
+            .await
+            .unwrap();
+
+        assert!(service
+            .add_role_inheritance(roles::DEVELOPER, roles::TENANT_ADMIN, "tenant1")
+            .await
+            .is_err());
+        assert!(service
+            .add_role_inheritance(roles::DEVELOPER, roles::DEVELOPER, "tenant1")
+            .await
+            .is_err());
+    }
+}
+
+#[cfg(test)]
+mod permission_cache_tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_repeated_checks_are_served_from_cache() {
+        let service = AuthzService::new_in_memory().await.unwrap();
+        service
+            .add_permission_for_role(roles::VIEWER, "tenant1", "/tenants/tenant1/*", actions::READ)
+            .await
+            .unwrap();
+        service
+            .assign_role_to_user("alice", roles::VIEWER, "tenant1")
+            .await
+            .unwrap();
+
+        for _ in 0..3 {
+            assert!(service
+                .check("alice", "tenant1", "/tenants/tenant1/configs", actions::READ)
+                .await
+                .unwrap());
+        }
+
+        let stats = service.cache_stats();
+        assert_eq!(stats.misses, 1);
+        assert_eq!(stats.hits, 2);
+    }
+
+    #[tokio::test]
+    async fn test_policy_change_invalidates_cached_allow() {
+        let service = AuthzService::new_in_memory().await.unwrap();
+        service
+            .add_permission_for_role(roles::VIEWER, "tenant1", "/tenants/tenant1/*", actions::READ)
+            .await
+            .unwrap();
+        service
+            .assign_role_to_user("alice", roles::VIEWER, "tenant1")
+            .await
+            .unwrap();
+
+        assert!(service
+            .check("alice", "tenant1", "/tenants/tenant1/configs", actions::READ)
+            .await
+            .unwrap());
+
+        service
+            .remove_permission_for_role(
+                roles::VIEWER,
+                "tenant1",
+                "/tenants/tenant1/*",
+                actions::READ,
+            )
+            .await
+            .unwrap();
+
+        assert!(!service
+            .check("alice", "tenant1", "/tenants/tenant1/configs", actions::READ)
+            .await
+            .unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_zero_ttl_disables_cache() {
+        let service = AuthzService::new_in_memory()
+            .await
+            .unwrap()
+            .with_cache_ttl(std::time::Duration::ZERO);
+
+        for _ in 0..2 {
+            service
+                .check("alice", "tenant1", "/tenants/tenant1/configs", actions::READ)
+                .await
+                .unwrap();
+        }
+
+        assert_eq!(service.cache_stats().hits, 0);
+    }
+}
+
+#[cfg(test)]
+mod batch_check_tests {
+    use super::*;
+
+    async fn setup_service() -> AuthzService {
+        let service = AuthzService::new_in_memory().await.unwrap();
+        service
+            .add_permission_for_role(roles::VIEWER, "tenant1", "/tenants/tenant1/*", actions::READ)
+            .await
+            .unwrap();
+        service
+            .assign_role_to_user("alice", roles::VIEWER, "tenant1")
+            .await
+            .unwrap();
+        service
+    }
+
+    #[tokio::test]
+    async fn test_check_batch_preserves_input_order() {
+        let service = setup_service().await;
+
+        let results = service
+            .check_batch(
+                "alice",
+                "tenant1",
+                &[
+                    ("/tenants/tenant1/configs", actions::WRITE),
+                    ("/tenants/tenant1/configs", actions::READ),
+                    ("/tenants/tenant2/configs", actions::READ),
+                    ("/tenants/tenant1/apps", actions::READ),
+                ],
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(results, vec![false, true, false, true]);
+    }
+
+    #[tokio::test]
+    async fn test_check_batch_matches_single_checks() {
+        let service = setup_service().await;
+        let requests = [
+            ("/tenants/tenant1/a", actions::READ),
+            ("/tenants/tenant1/b", actions::DELETE),
+            ("/other", actions::READ),
+        ];
+
+        let batch = service.check_batch("alice", "tenant1", &requests).await.unwrap();
+        for ((resource, action), allowed) in requests.iter().zip(batch) {
+            let single = service.check("alice", "tenant1", resource, action).await.unwrap();
+            assert_eq!(single, allowed);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_check_batch_uses_permission_cache() {
+        let service = setup_service().await;
+        assert!(service
+            .check("alice", "tenant1", "/tenants/tenant1/configs", actions::READ)
+            .await
+            .unwrap());
+
+        let results = service
+            .check_batch(
+                "alice",
+                "tenant1",
+                &[
+                    ("/tenants/tenant1/configs", actions::READ),
+                    ("/tenants/tenant1/other", actions::READ),
+                ],
+            )
+            .await
+            .unwrap();
+        assert_eq!(results, vec![true, true]);
+
+        let stats = service.cache_stats();
+        assert_eq!(stats.hits, 1);
+        assert_eq!(stats.misses, 2);
+
+        let empty: [(&str, &str); 0] = [];
+        assert!(service.check_batch("alice", "tenant1", &empty).await.unwrap().is_empty());
+    }
+}
+
+#[cfg(test)]
+mod policy_export_tests {
+    use super::*;
+
+    async fn setup_service() -> AuthzService {
+        let service = AuthzService::new_in_memory().await.unwrap();
+        for tenant in ["tenant1", "tenant2"] {
+            service
+                .add_permission_for_role(
+                    roles::DEVELOPER,
+                    tenant,
+                    &format!("/tenants/{}/*", tenant),
+                    actions::WRITE,
+                )
+                .await
+                .unwrap();
+            service
+                .add_role_inheritance(roles::TENANT_ADMIN, roles::DEVELOPER, tenant)
+                .await
+                .unwrap();
+        }
+        service
+            .assign_role_to_user("alice", roles::TENANT_ADMIN, "tenant1")
+            .await
+            .unwrap();
+        service
+            .assign_role_to_user("bob", roles::DEVELOPER, "tenant2")
+            .await
+            .unwrap();
+        service
+    }
+
+    #[tokio::test]
+    async fn test_tenant_only_sees_own_policies() {
+        let service = setup_service().await;
+
+        let policies = service.list_policies("tenant1").await.unwrap();
+        assert_eq!(
+            policies,
+            vec![PolicyRule {
+                role: roles::DEVELOPER.to_string(),
+                tenant: "tenant1".to_string(),
+                resource: "/tenants/tenant1/*".to_string(),
+                action: actions::WRITE.to_string(),
+            }]
+        );
+
+        let assignments = service.list_role_assignments("tenant1").await.unwrap();
+        assert_eq!(assignments.len(), 2);
+        assert!(assignments.iter().all(|a| a.tenant == "tenant1"));
+        assert!(assignments.iter().any(|a| a.subject == "alice"));
+        assert!(!assignments.iter().any(|a| a.subject == "bob"));
+
+        assert!(service.list_policies("tenant3").await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_export_can_be_imported_into_another_service() {
+        let source = setup_service().await;
+        let export = source.export_policies("tenant1").await.unwrap();
+
+        // 导出格式为JSON，可以原样导入
+        let json = serde_json::to_string(&export).unwrap();
+        let parsed: PolicyExport = serde_json::from_str(&json).unwrap();
+
+        let target = AuthzService::new_in_memory().await.unwrap();
+        let summary = target.import_policies("tenant1", &parsed).await.unwrap();
+        assert_eq!(summary.policies_added, 1);
+        assert_eq!(summary.role_assignments_added, 2);
+        assert_eq!(summary.skipped, 0);
+        assert_eq!(target.export_policies("tenant1").await.unwrap(), export);
+
+        assert!(target
+            .check("alice", "tenant1", "/tenants/tenant1/configs", actions::WRITE)
+            .await
+            .unwrap());
+
+        // 重复导入时跳过已存在的规则
+        let summary = target.import_policies("tenant1", &parsed).await.unwrap();
+        assert_eq!(summary.skipped, 3);
+    }
+
+    #[tokio::test]
+    async fn test_import_rejects_rules_of_other_tenants() {
+        let source = setup_service().await;
+        let mut export = source.export_policies("tenant1").await.unwrap();
+        export.role_assignments.push(RoleAssignment {
+            subject: "mallory".to_string(),
+            role: roles::TENANT_ADMIN.to_string(),
+            tenant: "tenant2".to_string(),
+        });
+
+        let target = AuthzService::new_in_memory().await.unwrap();
+        assert!(target.import_policies("tenant1", &export).await.is_err());
+        assert!(target.list_policies("tenant1").await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_is_tenant_admin() {
+        let service = setup_service().await;
+        assert!(service.is_tenant_admin("alice", "tenant1").await.unwrap());
+        assert!(!service.is_tenant_admin("alice", "tenant2").await.unwrap());
+        assert!(!service.is_tenant_admin("bob", "tenant2").await.unwrap());
+    }
+}
+
+#[cfg(test)]
+mod revoke_tests {
+    use super::*;
+
+    async fn setup_service() -> AuthzService {
+        let service = AuthzService::new_in_memory().await.unwrap();
+        service
+            .add_permission_for_role(roles::DEVELOPER, "tenant1", "/tenants/tenant1/*", actions::WRITE)
+            .await
+            .unwrap();
+        service
+            .assign_role_to_user("alice", roles::DEVELOPER, "tenant1")
+            .await
+            .unwrap();
+        service
+    }
+
+    async fn can_write(service: &AuthzService) -> bool {
+        service
+            .check("alice", "tenant1", "/tenants/tenant1/configs", actions::WRITE)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_remove_permission_for_role_revokes_access() {
+        let service = setup_service().await;
+        assert!(can_write(&service).await);
+
+        let removed = service
+            .remove_permission_for_role(
+                roles::DEVELOPER,
+                "tenant1",
+                "/tenants/tenant1/*",
+                actions::WRITE,
+            )
+            .await
+            .unwrap();
+        assert!(removed);
+        assert!(!can_write(&service).await);
+        assert!(service.list_policies("tenant1").await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_remove_role_for_user_revokes_access() {
+        let service = setup_service().await;
+        assert!(can_write(&service).await);
+
+        assert!(service
+            .remove_role_for_user("alice", roles::DEVELOPER, "tenant1")
+            .await
+            .unwrap());
+        assert!(!can_write(&service).await);
+        assert!(service
+            .get_roles_for_user_in_tenant("alice", "tenant1")
+            .await
+            .unwrap()
+            .is_empty());
+
+        // 重新授予后权限恢复
+        service
+            .assign_role_to_user("alice", roles::DEVELOPER, "tenant1")
+            .await
+            .unwrap();
+        assert!(can_write(&service).await);
+    }
+
+    #[tokio::test]
+    async fn test_removing_missing_rules_returns_false() {
+        let service = setup_service().await;
+
+        assert!(!service
+            .remove_role_for_user("alice", roles::VIEWER, "tenant1")
+            .await
+            .unwrap());
+        assert!(!service
+            .remove_permission_for_role(roles::VIEWER, "tenant1", "/tenants/tenant1/*", actions::READ)
+            .await
+            .unwrap());
+        assert!(can_write(&service).await);
+    }
+}
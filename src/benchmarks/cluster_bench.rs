@@ -0,0 +1,386 @@
+//! 集群性能基准测试
+//!
+//! 启动3个节点并组成同一个Raft组：节点1初始化集群，节点2和3先作为学习者加入再提升为投票成员。
+//! 写入被路由到领导者，只有在多数节点复制后才会返回，因此写入延迟即共识复制延迟
+
+use super::write_bench::{lift_version_history_limit, make_payload};
+use super::{run_concurrent_workers, BenchmarkConfig, BenchmarkResults};
+use crate::config::{AppConfig, StorageConfig};
+use crate::raft::{
+    network::NetworkConfig,
+    network_server::serve_raft_rpc,
+    node::{NodeConfig, RaftNode, ResourceLimits, SnapshotStreamConfig},
+    types::{
+        ClientRequest, ConfigFormat, ConfigNamespace, NodeId, RaftCommand,
+        DEFAULT_ELECTION_PRIORITY,
+    },
+};
+use openraft::Config as RaftConfig;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tempfile::TempDir;
+use tokio::net::TcpListener;
+use tokio::task::JoinHandle;
+use tokio::time::sleep;
+use tracing::{info, warn};
+
+/// 集群节点数量
+const CLUSTER_SIZE: u64 = 3;
+
+/// 等待集群选出稳定领导者的最长时间
+const LEADER_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// 写入结束后等待所有节点应用完日志的最长时间
+const CATCH_UP_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// 集群写入基准测试使用的配置名称
+const CLUSTER_BENCHMARK_CONFIG_NAME: &str = "cluster-benchmark.json";
+
+/// 集群写入基准测试结果
+#[derive(Debug, Clone)]
+pub struct ClusterWriteBenchmarkResults {
+    /// 处理写入的领导者节点
+    pub leader_id: NodeId,
+    /// 写入统计，延迟包含复制到多数节点的时间
+    pub write: BenchmarkResults,
+    /// 写入结束时各节点的应用落后量（领导者最后日志索引减去节点已应用索引）
+    pub apply_lag: BTreeMap<NodeId, u64>,
+    /// 写入结束后所有节点应用到领导者最后日志索引所需的时间，超时为None
+    pub catch_up: Option<Duration>,
+}
+
+impl ClusterWriteBenchmarkResults {
+    /// 显示测试结果
+    pub fn display(&self, test_name: &str) {
+        info!("{} 领导者: 节点{}", test_name, self.leader_id);
+        self.write.display(test_name);
+        for (node_id, lag) in &self.apply_lag {
+            info!("节点{} 应用落后: {} 条日志", node_id, lag);
+        }
+        match self.catch_up {
+            Some(catch_up) => info!("所有节点追平耗时: {:?}", catch_up),
+            None => warn!("节点未能在 {:?} 内追平领导者", CATCH_UP_TIMEOUT),
+        }
+    }
+}
+
+/// 集群性能基准测试
+pub struct ClusterBenchmark {
+    nodes: Vec<Arc<RaftNode>>,
+    servers: Vec<JoinHandle<()>>,
+    _temp_dirs: Vec<TempDir>,
+}
+
+impl ClusterBenchmark {
+    /// 创建3节点集群测试环境
+    ///
+    /// 每个节点在随机端口上提供Raft RPC服务；节点1初始化集群并将其余节点加入为投票成员，
+    /// 返回前等待所有节点认可同一个领导者
+    pub async fn new() -> Result<Self, Box<dyn std::error::Error>> {
+        let mut listeners = Vec::new();
+        let mut node_addresses = HashMap::new();
+        for node_id in 1..=CLUSTER_SIZE {
+            let listener = TcpListener::bind("127.0.0.1:0").await?;
+            node_addresses.insert(node_id, listener.local_addr()?.to_string());
+            listeners.push(listener);
+        }
+        let network_config = NetworkConfig::new(node_addresses.clone());
+
+        let mut temp_dirs = Vec::new();
+        let mut nodes = Vec::new();
+        let mut servers = Vec::new();
+        for (node_id, listener) in (1..=CLUSTER_SIZE).zip(listeners) {
+            let temp_dir = TempDir::new()?;
+            let node_config = NodeConfig {
+                node_id,
+                address: node_addresses[&node_id].clone(),
+                raft_config: RaftConfig::default(),
+                network_config: network_config.clone(),
+                heartbeat_interval: 150,
+                election_timeout_min: 300,
+                election_timeout_max: 600,
+                resource_limits: ResourceLimits::default(),
+                election_priority: DEFAULT_ELECTION_PRIORITY,
+                snapshot_stream: SnapshotStreamConfig::default(),
+                pre_vote_enabled: false,
+                compress_log_entries: false,
+            };
+            let app_config = AppConfig {
+                storage: StorageConfig {
+                    data_dir: temp_dir.path().to_string_lossy().to_string(),
+                    cache_size_mb: 64,
+                    max_write_buffer_number: 2,
+                    ..Default::default()
+                },
+                ..Default::default()
+            };
+
+            let mut node = RaftNode::new(node_config, &app_config).await?;
+            if node_id == 1 {
+                node.start().await?;
+            } else {
+                node.start_as_learner().await?;
+            }
+            lift_version_history_limit(&node);
+
+            let raft = node.get_raft().cloned().ok_or("Raft not initialized")?;
+            servers.push(serve_raft_rpc(listener, raft));
+            nodes.push(Arc::new(node));
+            temp_dirs.push(temp_dir);
+        }
+
+        let benchmark = Self {
+            nodes,
+            servers,
+            _temp_dirs: temp_dirs,
+        };
+        benchmark.form_cluster(&node_addresses).await?;
+        Ok(benchmark)
+    }
+
+    /// 将节点2和3加入节点1初始化的集群，并等待领导者稳定
+    async fn form_cluster(
+        &self,
+        node_addresses: &HashMap<NodeId, String>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let first = &self.nodes[0];
+        first.wait_for_leadership(LEADER_TIMEOUT).await?;
+
+        for node in &self.nodes[1..] {
+            let node_id = node.node_id();
+            first.add_learner(node_id, node_addresses[&node_id].clone()).await?;
+        }
+        let voters = first.promote_learners().await?;
+        info!("集群已组建，投票成员: {:?}", voters);
+
+        // 所有节点应用成员变更日志后集群才算组建完成
+        let membership_index = first.get_metrics().await?.last_log_index;
+        for node in &self.nodes {
+            node.wait_for_applied(membership_index, LEADER_TIMEOUT).await?;
+        }
+
+        let leader = self.wait_for_stable_leader(LEADER_TIMEOUT).await?;
+        info!("集群领导者已稳定: 节点{}", leader);
+        Ok(())
+    }
+
+    /// 等待所有节点认可同一个领导者
+    ///
+    /// # Arguments
+    /// * `timeout` - 最长等待时间
+    ///
+    /// # Returns
+    /// * `NodeId` - 领导者节点ID
+    pub async fn wait_for_stable_leader(
+        &self,
+        timeout: Duration,
+    ) -> Result<NodeId, Box<dyn std::error::Error>> {
+        let start = Instant::now();
+        loop {
+            let mut leaders = Vec::with_capacity(self.nodes.len());
+            for node in &self.nodes {
+                leaders.push(node.get_leader().await);
+            }
+            if let Some(Some(leader)) = leaders.first().copied() {
+                if leaders.iter().all(|l| *l == Some(leader)) {
+                    return Ok(leader);
+                }
+            }
+            if start.elapsed() > timeout {
+                return Err(format!("No stable leader after {:?}: {:?}", timeout, leaders).into());
+            }
+            sleep(Duration::from_millis(50)).await;
+        }
+    }
+
+    /// 当前领导者节点
+    async fn leader(&self) -> Result<Arc<RaftNode>, Box<dyn std::error::Error>> {
+        let leader_id = self.wait_for_stable_leader(LEADER_TIMEOUT).await?;
+        self.nodes
+            .iter()
+            .find(|node| node.node_id() == leader_id)
+            .cloned()
+            .ok_or_else(|| format!("Leader {} is not part of the benchmark", leader_id).into())
+    }
+
+    /// 集群性能测试
+    ///
+    /// 仅测量各节点 `get_metrics()` 的延迟，真实写入请使用 [`ClusterBenchmark::run_cluster_write_benchmark`]
+    pub async fn run_cluster_performance_test(&self, config: &BenchmarkConfig) -> BenchmarkResults {
+        info!("开始集群性能测试...");
+
+        // 预热
+        info!("集群预热阶段...");
+        self.warmup_cluster(config.warmup_duration).await;
+
+        // 性能测试
+        info!(
+            "开始集群性能测试，持续时间: {:?}，并发数: {}",
+            config.duration, config.concurrency
+        );
+        let nodes = self.nodes.clone();
+        run_concurrent_workers(config, move |worker, seq| {
+            // 各工作任务轮流访问不同节点
+            let node = nodes[(worker + seq as usize) % nodes.len()].clone();
+            async move { node.get_metrics().await.is_ok() }
+        })
+        .await
+    }
+
+    /// 集群写入性能测试
+    ///
+    /// 向领导者并发提交 `CreateVersion` 命令，结束后统计各节点的应用落后量并等待其追平
+    ///
+    /// # Arguments
+    /// * `config` - 测试配置
+    /// * `payload_size` - 每次写入的配置内容大小 (字节)
+    ///
+    /// # Returns
+    /// * `ClusterWriteBenchmarkResults` - 写入统计及各节点的复制情况
+    pub async fn run_cluster_write_benchmark(
+        &self,
+        config: &BenchmarkConfig,
+        payload_size: usize,
+    ) -> Result<ClusterWriteBenchmarkResults, Box<dyn std::error::Error>> {
+        let leader = self.leader().await?;
+        let leader_id = leader.node_id();
+        info!("开始集群写入测试，领导者: 节点{}，负载大小: {} 字节", leader_id, payload_size);
+
+        let response = leader
+            .client_write(ClientRequest {
+                command: RaftCommand::CreateConfig {
+                    namespace: ConfigNamespace {
+                        tenant: "benchmark".to_string(),
+                        app: "cluster".to_string(),
+                        env: "test".to_string(),
+                    },
+                    name: CLUSTER_BENCHMARK_CONFIG_NAME.to_string(),
+                    content: make_payload(0, payload_size),
+                    format: ConfigFormat::Json,
+                    schema: None,
+                    creator_id: 1,
+                    description: "cluster benchmark config".to_string(),
+                },
+                idempotency_key: None,
+            })
+            .await?;
+        let config_id = response
+            .config_id
+            .ok_or_else(|| format!("Failed to create benchmark config: {}", response.message))?;
+
+        let writer = leader.clone();
+        let write = run_concurrent_workers(config, move |worker, seq| {
+            let leader = writer.clone();
+            async move {
+                let command = RaftCommand::CreateVersion {
+                    config_id,
+                    content: make_payload(seq, payload_size),
+                    format: None,
+                    creator_id: 1,
+                    description: format!("cluster benchmark {}-{}", worker, seq),
+                };
+                match leader.client_write(ClientRequest { command, idempotency_key: None }).await {
+                    Ok(response) if response.success => true,
+                    Ok(response) => {
+                        warn!("Cluster benchmark write rejected: {}", response.message);
+                        false
+                    }
+                    Err(e) => {
+                        warn!("Cluster benchmark write failed: {}", e);
+                        false
+                    }
+                }
+            }
+        })
+        .await;
+
+        let target_index = leader.get_metrics().await?.last_log_index;
+        let apply_lag = self.apply_lag(target_index).await;
+        let catch_up = self.wait_for_catch_up(target_index, CATCH_UP_TIMEOUT).await;
+
+        Ok(ClusterWriteBenchmarkResults {
+            leader_id,
+            write,
+            apply_lag,
+            catch_up,
+        })
+    }
+
+    /// 各节点已应用索引相对 `target_index` 的落后量
+    async fn apply_lag(&self, target_index: u64) -> BTreeMap<NodeId, u64> {
+        let mut lag = BTreeMap::new();
+        for node in &self.nodes {
+            let applied = node.get_metrics().await.map_or(0, |m| m.last_applied);
+            lag.insert(node.node_id(), target_index.saturating_sub(applied));
+        }
+        lag
+    }
+
+    /// 等待所有节点应用到 `target_index`，返回所需时间
+    async fn wait_for_catch_up(&self, target_index: u64, timeout: Duration) -> Option<Duration> {
+        let start = Instant::now();
+        for node in &self.nodes {
+            let remaining = timeout.checked_sub(start.elapsed())?;
+            if let Err(e) = node.wait_for_applied(target_index, remaining).await {
+                warn!("{}", e);
+                return None;
+            }
+        }
+        Some(start.elapsed())
+    }
+
+    /// 集群预热
+    async fn warmup_cluster(&self, duration: Duration) {
+        let start_time = Instant::now();
+        let mut node_idx = 0;
+
+        while start_time.elapsed() < duration {
+            let _ = self.nodes[node_idx].get_metrics().await;
+            node_idx = (node_idx + 1) % self.nodes.len();
+            sleep(Duration::from_millis(50)).await;
+        }
+    }
+}
+
+impl Drop for ClusterBenchmark {
+    fn drop(&mut self) {
+        for server in &self.servers {
+            server.abort();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_cluster_write_benchmark() {
+        let benchmark = ClusterBenchmark::new().await.expect("Failed to form cluster");
+
+        // 所有节点都是同一集群的投票成员
+        for node in &benchmark.nodes {
+            let metrics = node.get_metrics().await.unwrap();
+            assert_eq!(metrics.membership.len(), CLUSTER_SIZE as usize);
+        }
+
+        let config = BenchmarkConfig {
+            duration: Duration::from_secs(2),
+            concurrency: 2,
+            warmup_duration: Duration::from_secs(0),
+            test_interval: Duration::from_millis(20),
+            ..Default::default()
+        };
+        let results = benchmark.run_cluster_write_benchmark(&config, 256).await.unwrap();
+        results.display("三节点写入性能");
+
+        assert!(results.write.successful_operations > 0);
+        assert_eq!(results.apply_lag.len(), CLUSTER_SIZE as usize);
+        assert!(results.catch_up.is_some());
+
+        // 写入已复制到所有节点的存储中
+        let target = benchmark.leader().await.unwrap().get_metrics().await.unwrap().last_log_index;
+        assert!(benchmark.apply_lag(target).await.values().all(|&lag| lag == 0));
+    }
+}
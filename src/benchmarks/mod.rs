@@ -5,18 +5,45 @@
 //! - 内存使用基准测试
 //! - 网络延迟测试
 //! - 集群性能测试
+//! - 速率限制算法对比
 
 use crate::config::{AppConfig, StorageConfig};
+use crate::error::ConfluxError;
 use crate::raft::{
     network::NetworkConfig,
-    node::{NodeConfig, RaftNode, ResourceLimits},
+    node::{NodeConfig, RaftNode, ResourceLimits, SnapshotStreamConfig},
+    types::DEFAULT_ELECTION_PRIORITY,
 };
 use openraft::Config as RaftConfig;
-use std::collections::HashMap;
+use prometheus::Gauge;
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::path::Path;
+use std::sync::{Arc, LazyLock};
 use std::time::{Duration, Instant};
 use tempfile::TempDir;
+use tokio::task::JoinSet;
 use tokio::time::sleep;
-use tracing::info;
+use tracing::{info, warn};
+
+mod cluster_bench;
+mod rate_limit_bench;
+mod report;
+mod write_bench;
+
+pub use cluster_bench::{ClusterBenchmark, ClusterWriteBenchmarkResults};
+pub use rate_limit_bench::{run_rate_limit_benchmark, RateLimitBenchmarkResults};
+pub use report::{BenchmarkRecord, BenchmarkReport, CSV_HEADER};
+pub use write_bench::WriteBenchmarkResults;
+
+/// 测试持续时间（秒）的环境变量
+pub const BENCH_DURATION_SECS_ENV: &str = "CONFLUX_BENCH_DURATION_SECS";
+/// 并发连接数的环境变量
+pub const BENCH_CONCURRENCY_ENV: &str = "CONFLUX_BENCH_CONCURRENCY";
+/// 预热时间（秒）的环境变量
+pub const BENCH_WARMUP_SECS_ENV: &str = "CONFLUX_BENCH_WARMUP_SECS";
+/// 测试间隔（毫秒）的环境变量
+pub const BENCH_INTERVAL_MS_ENV: &str = "CONFLUX_BENCH_INTERVAL_MS";
 
 /// 性能测试配置
 #[derive(Debug, Clone)]
@@ -29,6 +56,8 @@ pub struct BenchmarkConfig {
     pub warmup_duration: Duration,
     /// 测试间隔
     pub test_interval: Duration,
+    /// 服务等级目标
+    pub slo: BenchmarkSlo,
 }
 
 impl Default for BenchmarkConfig {
@@ -38,12 +67,132 @@ impl Default for BenchmarkConfig {
             concurrency: 10,
             warmup_duration: Duration::from_secs(5),
             test_interval: Duration::from_millis(100),
+            slo: BenchmarkSlo::default(),
         }
     }
 }
 
+impl BenchmarkConfig {
+    /// 从TOML文件加载测试配置，文件中未给出的项使用默认值
+    ///
+    /// 文件格式见仓库中的 `config/benchmark_slo.toml`
+    ///
+    /// # Arguments
+    /// * `path` - 配置文件路径
+    ///
+    /// # Errors
+    /// 文件无法读取或不是有效的配置时返回错误
+    pub fn load_from_file(path: impl AsRef<Path>) -> Result<Self, Box<dyn std::error::Error>> {
+        let file: BenchmarkConfigFile = toml::from_str(&std::fs::read_to_string(path)?)?;
+        let defaults = Self::default();
+        let run = file.benchmark;
+        Ok(Self {
+            duration: run.duration_secs.map_or(defaults.duration, Duration::from_secs),
+            concurrency: run.concurrency.unwrap_or(defaults.concurrency),
+            warmup_duration: run.warmup_secs.map_or(defaults.warmup_duration, Duration::from_secs),
+            test_interval: run
+                .test_interval_ms
+                .map_or(defaults.test_interval, Duration::from_millis),
+            slo: file.slo,
+        })
+    }
+
+    /// 从环境变量读取测试配置，便于CI按需调整参数
+    ///
+    /// 读取 `CONFLUX_BENCH_DURATION_SECS`、`CONFLUX_BENCH_CONCURRENCY`、
+    /// `CONFLUX_BENCH_WARMUP_SECS` 和 `CONFLUX_BENCH_INTERVAL_MS`，
+    /// 未设置或无法解析的变量使用默认值
+    pub fn from_env() -> Self {
+        Self::from_vars(|name| std::env::var(name).ok())
+    }
+
+    /// 适合CI流水线的短时配置：持续5秒、单并发、间隔100毫秒
+    pub fn for_ci() -> Self {
+        Self {
+            duration: Duration::from_secs(5),
+            concurrency: 1,
+            warmup_duration: Duration::from_secs(1),
+            test_interval: Duration::from_millis(100),
+            slo: BenchmarkSlo::default(),
+        }
+    }
+
+    /// 校验配置，持续时间或并发数为0时返回错误
+    pub fn validate(&self) -> crate::error::Result<()> {
+        if self.duration.is_zero() {
+            return Err(ConfluxError::validation("Benchmark duration must be greater than zero"));
+        }
+        if self.concurrency == 0 {
+            return Err(ConfluxError::validation("Benchmark concurrency must be greater than zero"));
+        }
+        Ok(())
+    }
+
+    /// 按变量名查找取值构造配置，`from_env` 的实现
+    fn from_vars(lookup: impl Fn(&str) -> Option<String>) -> Self {
+        let defaults = Self::default();
+        let parse = |name: &str| -> Option<u64> {
+            let value = lookup(name)?;
+            match value.trim().parse() {
+                Ok(parsed) => Some(parsed),
+                Err(_) => {
+                    warn!("Ignoring invalid value {:?} of {}, using the default", value, name);
+                    None
+                }
+            }
+        };
+        Self {
+            duration: parse(BENCH_DURATION_SECS_ENV).map_or(defaults.duration, Duration::from_secs),
+            concurrency: parse(BENCH_CONCURRENCY_ENV)
+                .map_or(defaults.concurrency, |concurrency| concurrency as usize),
+            warmup_duration: parse(BENCH_WARMUP_SECS_ENV)
+                .map_or(defaults.warmup_duration, Duration::from_secs),
+            test_interval: parse(BENCH_INTERVAL_MS_ENV)
+                .map_or(defaults.test_interval, Duration::from_millis),
+            slo: defaults.slo,
+        }
+    }
+}
+
+/// 服务等级目标 (SLO)，用于判定基准测试是否通过
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BenchmarkSlo {
+    /// P99延迟上限 (毫秒)
+    pub p99_latency_ms: f64,
+    /// 错误率上限 (百分比)
+    pub error_rate_pct: f64,
+}
+
+impl Default for BenchmarkSlo {
+    fn default() -> Self {
+        Self {
+            p99_latency_ms: 100.0,
+            error_rate_pct: 1.0,
+        }
+    }
+}
+
+/// 基准测试配置文件的结构
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct BenchmarkConfigFile {
+    benchmark: BenchmarkRunFile,
+    slo: BenchmarkSlo,
+}
+
+/// 配置文件中的运行参数，时间以秒或毫秒为单位
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct BenchmarkRunFile {
+    duration_secs: Option<u64>,
+    concurrency: Option<usize>,
+    warmup_secs: Option<u64>,
+    test_interval_ms: Option<u64>,
+}
+
 /// 性能测试结果
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BenchmarkResults {
     /// 操作总数
     pub total_operations: u64,
@@ -59,6 +208,12 @@ pub struct BenchmarkResults {
     pub p95_latency_ms: f64,
     /// P99延迟 (毫秒)
     pub p99_latency_ms: f64,
+    /// P99.9延迟 (毫秒)
+    #[serde(default)]
+    pub p999_latency_ms: f64,
+    /// 延迟的样本标准差 (毫秒)
+    #[serde(default)]
+    pub std_dev_ms: f64,
     /// QPS (每秒查询数)
     pub qps: f64,
     /// 错误率
@@ -67,6 +222,8 @@ pub struct BenchmarkResults {
 
 impl BenchmarkResults {
     /// 计算性能指标
+    ///
+    /// 平均值和标准差用Welford在线算法在一次遍历中计算，避免大样本求和时的精度损失
     pub fn calculate(
         operations: u64,
         successful: u64,
@@ -76,22 +233,30 @@ impl BenchmarkResults {
         latencies.sort();
         
         let failed = operations - successful;
-        let avg_latency = latencies.iter().sum::<Duration>().as_millis() as f64 / latencies.len() as f64;
-        
-        let p50_latency = if !latencies.is_empty() {
-            latencies[latencies.len() * 50 / 100].as_millis() as f64
-        } else { 0.0 };
-        
-        let p95_latency = if !latencies.is_empty() {
-            latencies[latencies.len() * 95 / 100].as_millis() as f64
-        } else { 0.0 };
+        let (mut avg_latency, mut squared_deviations) = (0.0, 0.0);
+        for (count, latency) in (1..).zip(latencies.iter()) {
+            let value = latency.as_secs_f64() * 1000.0;
+            let delta = value - avg_latency;
+            avg_latency += delta / count as f64;
+            squared_deviations += delta * (value - avg_latency);
+        }
+        let std_dev = if latencies.len() > 1 {
+            (squared_deviations / (latencies.len() - 1) as f64).sqrt()
+        } else {
+            0.0
+        };
         
-        let p99_latency = if !latencies.is_empty() {
-            latencies[latencies.len() * 99 / 100].as_millis() as f64
-        } else { 0.0 };
+        let p50_latency = percentile_ms(latencies, 0.50);
+        let p95_latency = percentile_ms(latencies, 0.95);
+        let p99_latency = percentile_ms(latencies, 0.99);
+        let p999_latency = percentile_ms(latencies, 0.999);
         
         let qps = successful as f64 / total_duration.as_secs_f64();
-        let error_rate = failed as f64 / operations as f64 * 100.0;
+        let error_rate = if operations == 0 {
+            0.0
+        } else {
+            failed as f64 / operations as f64 * 100.0
+        };
 
         Self {
             total_operations: operations,
@@ -101,6 +266,8 @@ impl BenchmarkResults {
             p50_latency_ms: p50_latency,
             p95_latency_ms: p95_latency,
             p99_latency_ms: p99_latency,
+            p999_latency_ms: p999_latency,
+            std_dev_ms: std_dev,
             qps,
             error_rate,
         }
@@ -118,6 +285,8 @@ impl BenchmarkResults {
         info!("P50延迟: {:.2}ms", self.p50_latency_ms);
         info!("P95延迟: {:.2}ms", self.p95_latency_ms);
         info!("P99延迟: {:.2}ms", self.p99_latency_ms);
+        info!("P99.9延迟: {:.2}ms", self.p999_latency_ms);
+        info!("延迟标准差: {:.2}ms", self.std_dev_ms);
         info!("========================");
     }
 
@@ -128,8 +297,135 @@ impl BenchmarkResults {
         self.error_rate < 1.0 && 
         self.avg_latency_ms < 100.0
     }
+
+    /// 断言结果满足服务等级目标
+    ///
+    /// # Arguments
+    /// * `p99_ms` - P99延迟上限 (毫秒)
+    /// * `error_rate_pct` - 错误率上限 (百分比)
+    ///
+    /// # Panics
+    /// P99延迟或错误率超过上限时panic，信息中列出所有超标的指标
+    pub fn assert_within_slo(&self, p99_ms: f64, error_rate_pct: f64) {
+        let mut violations = Vec::new();
+        if self.p99_latency_ms > p99_ms {
+            violations.push(format!(
+                "P99延迟 {:.2}ms 超过上限 {:.2}ms",
+                self.p99_latency_ms, p99_ms
+            ));
+        }
+        if self.error_rate > error_rate_pct {
+            violations.push(format!(
+                "错误率 {:.2}% 超过上限 {:.2}%",
+                self.error_rate, error_rate_pct
+            ));
+        }
+        assert!(violations.is_empty(), "未满足SLO: {}", violations.join("; "));
+    }
+}
+
+/// 计算已排序延迟样本的分位数 (毫秒)
+///
+/// 使用相邻秩之间的线性插值 (R-7 方法，与 NumPy 默认方法一致)：
+/// 位置 `h = (n - 1) * q`，结果为 `x[floor(h)] + (h - floor(h)) * (x[floor(h) + 1] - x[floor(h)])`
+///
+/// # Arguments
+/// * `sorted_latencies` - 升序排列的延迟样本
+/// * `quantile` - 分位数，取值范围 [0, 1]
+///
+/// # Returns
+/// * `f64` - 分位数对应的延迟 (毫秒)，样本为空时返回0
+pub fn percentile_ms(sorted_latencies: &[Duration], quantile: f64) -> f64 {
+    let to_ms = |d: &Duration| d.as_secs_f64() * 1000.0;
+    match sorted_latencies {
+        [] => 0.0,
+        [only] => to_ms(only),
+        _ => {
+            let rank = (sorted_latencies.len() - 1) as f64 * quantile.clamp(0.0, 1.0);
+            let lower = rank.floor() as usize;
+            let upper = (lower + 1).min(sorted_latencies.len() - 1);
+            let fraction = rank - lower as f64;
+            let low = to_ms(&sorted_latencies[lower]);
+            low + fraction * (to_ms(&sorted_latencies[upper]) - low)
+        }
+    }
+}
+
+/// 并发执行基准测试操作
+///
+/// 启动 `config.concurrency` 个工作任务（至少1个），每个任务在测试持续时间内循环执行操作，
+/// 操作之间间隔 `config.test_interval`。所有任务的延迟样本汇总后计算统计结果，
+/// QPS 基于整体耗时计算，因此反映并发后的总吞吐量
+///
+/// # Arguments
+/// * `config` - 测试配置
+/// * `operation` - 接收工作任务编号和该任务内操作序号，返回操作是否成功
+///
+/// # Returns
+/// * `BenchmarkResults` - 汇总后的测试结果
+pub async fn run_concurrent_workers<F, Fut>(config: &BenchmarkConfig, operation: F) -> BenchmarkResults
+where
+    F: Fn(usize, u64) -> Fut + Clone + Send + 'static,
+    Fut: Future<Output = bool> + Send,
+{
+    let start_time = Instant::now();
+    let deadline = start_time + config.duration;
+    let mut workers = JoinSet::new();
+
+    for worker in 0..config.concurrency.max(1) {
+        let operation = operation.clone();
+        let interval = config.test_interval;
+        workers.spawn(async move {
+            let (mut operations, mut successful, mut latencies) = (0u64, 0u64, Vec::new());
+            while Instant::now() < deadline {
+                let op_start = Instant::now();
+                if operation(worker, operations).await {
+                    successful += 1;
+                    latencies.push(op_start.elapsed());
+                }
+                operations += 1;
+
+                // 控制测试频率
+                sleep(interval).await;
+            }
+            (operations, successful, latencies)
+        });
+    }
+
+    let (mut operations, mut successful, mut latencies) = (0u64, 0u64, Vec::new());
+    while let Some(result) = workers.join_next().await {
+        match result {
+            Ok((worker_operations, worker_successful, worker_latencies)) => {
+                operations += worker_operations;
+                successful += worker_successful;
+                latencies.extend(worker_latencies);
+            }
+            Err(e) => tracing::warn!("Benchmark worker failed: {}", e),
+        }
+    }
+
+    let total_duration = start_time.elapsed();
+    BenchmarkResults::calculate(operations, successful, &mut latencies, total_duration)
 }
 
+/// 空载时可接受的内存使用上限 (MB)
+pub const IDLE_MEMORY_LIMIT_MB: f64 = 200.0;
+
+/// 最近一次采样的进程常驻内存
+static MEMORY_RSS_BYTES: LazyLock<Gauge> = LazyLock::new(|| {
+    prometheus::register_gauge!("memory_rss_bytes", "Resident memory of the process at the last sample")
+        .expect("memory_rss_bytes is registered once")
+});
+
+/// 采样期间观察到的进程常驻内存峰值
+static MEMORY_PEAK_RSS_BYTES: LazyLock<Gauge> = LazyLock::new(|| {
+    prometheus::register_gauge!(
+        "memory_peak_rss_bytes",
+        "Peak resident memory of the process observed while sampling"
+    )
+    .expect("memory_peak_rss_bytes is registered once")
+});
+
 /// 内存使用统计
 #[derive(Debug, Clone)]
 pub struct MemoryStats {
@@ -144,18 +440,22 @@ pub struct MemoryStats {
 }
 
 impl MemoryStats {
-    /// 获取当前内存使用情况
-    pub fn current() -> Self {
-        // 简化的内存统计实现
-        // 在实际产品中应该使用更精确的内存监控
+    /// 以当前内存使用为基线创建快照
+    pub fn new_snapshot() -> Self {
         let current_mb = Self::get_memory_usage_mb();
-        
-        Self {
+        let stats = Self {
             initial_memory_mb: current_mb,
             peak_memory_mb: current_mb,
             current_memory_mb: current_mb,
             memory_growth_mb: 0.0,
-        }
+        };
+        stats.export();
+        stats
+    }
+
+    /// 获取当前内存使用情况，等同于 [`MemoryStats::new_snapshot`]
+    pub fn current() -> Self {
+        Self::new_snapshot()
     }
 
     /// 更新内存统计
@@ -166,27 +466,23 @@ impl MemoryStats {
             self.peak_memory_mb = current;
         }
         self.memory_growth_mb = self.current_memory_mb - self.initial_memory_mb;
+        self.export();
+    }
+
+    /// 相对基线快照的内存增长 (MB)，内存减少时为负数
+    pub fn delta_from(&self, baseline: &MemoryStats) -> f64 {
+        self.current_memory_mb - baseline.current_memory_mb
     }
 
-    /// 获取内存使用量 (MB)
+    /// 获取内存使用量 (MB)，无法获取时返回0
     fn get_memory_usage_mb() -> f64 {
-        // 简化实现：使用系统内存信息
-        // 在生产环境中应该使用更准确的内存监控工具
-        use std::process::Command;
-        
-        if let Ok(output) = Command::new("ps")
-            .args(&["-o", "rss=", "-p", &std::process::id().to_string()])
-            .output()
-        {
-            if let Ok(rss_str) = String::from_utf8(output.stdout) {
-                if let Ok(rss_kb) = rss_str.trim().parse::<f64>() {
-                    return rss_kb / 1024.0; // 转换为 MB
-                }
-            }
-        }
-        
-        // 回退：返回估算值
-        0.0
+        process_rss_bytes().map_or(0.0, |bytes| bytes as f64 / 1024.0 / 1024.0)
+    }
+
+    /// 将当前和峰值内存写入Prometheus指标
+    fn export(&self) {
+        MEMORY_RSS_BYTES.set(self.current_memory_mb * 1024.0 * 1024.0);
+        MEMORY_PEAK_RSS_BYTES.set(self.peak_memory_mb * 1024.0 * 1024.0);
     }
 
     /// 显示内存统计
@@ -199,16 +495,63 @@ impl MemoryStats {
         info!("=======================");
     }
 
-    /// 检查内存使用是否在合理范围内
+    /// 检查内存使用是否在合理范围内，即低于 [`IDLE_MEMORY_LIMIT_MB`]
     pub fn is_memory_usage_acceptable(&self) -> bool {
-        // 基础内存目标：空载 < 200MB
-        self.current_memory_mb < 200.0
+        self.current_memory_mb < IDLE_MEMORY_LIMIT_MB
+    }
+}
+
+/// 获取当前进程的常驻内存 (RSS，字节)
+///
+/// Linux 上直接读取 `/proc/self/status` 中的 `VmRSS`；其他平台通过 sysinfo
+/// 调用相应的系统接口（macOS 上为 `task_info`），不依赖外部命令
+///
+/// # Returns
+/// * `Option<u64>` - 常驻内存字节数，当前平台不支持或读取失败时返回None
+pub fn process_rss_bytes() -> Option<u64> {
+    #[cfg(target_os = "linux")]
+    if let Some(kb) = std::fs::read_to_string("/proc/self/status")
+        .ok()
+        .and_then(|status| parse_vm_rss_kb(&status))
+        .filter(|&kb| kb > 0)
+    {
+        return Some(kb.saturating_mul(1024));
+    }
+    sysinfo_rss_bytes()
+}
+
+/// 从 `/proc/self/status` 的内容中解析 `VmRSS`（KB）
+#[cfg(any(target_os = "linux", test))]
+fn parse_vm_rss_kb(status: &str) -> Option<u64> {
+    status
+        .lines()
+        .find_map(|line| line.strip_prefix("VmRSS:"))
+        .and_then(|value| value.trim().trim_end_matches("kB").trim().parse().ok())
+}
+
+/// 通过 sysinfo 读取进程常驻内存
+fn sysinfo_rss_bytes() -> Option<u64> {
+    use sysinfo::{ProcessRefreshKind, ProcessesToUpdate, System};
+
+    if !sysinfo::IS_SUPPORTED_SYSTEM {
+        return None;
     }
+    let pid = sysinfo::get_current_pid().ok()?;
+    let mut system = System::new();
+    system.refresh_processes_specifics(
+        ProcessesToUpdate::Some(&[pid]),
+        false,
+        ProcessRefreshKind::nothing().with_memory(),
+    );
+    system
+        .process(pid)
+        .map(|process| process.memory())
+        .filter(|&bytes| bytes > 0)
 }
 
 /// 单节点性能基准测试
 pub struct SingleNodeBenchmark {
-    node: RaftNode,
+    node: Arc<RaftNode>,
     _temp_dir: TempDir,
 }
 
@@ -226,15 +569,18 @@ impl SingleNodeBenchmark {
             election_timeout_min: 300,
             election_timeout_max: 600,
             resource_limits: ResourceLimits::default(),
+            election_priority: DEFAULT_ELECTION_PRIORITY,
+            snapshot_stream: SnapshotStreamConfig::default(),
+            pre_vote_enabled: false,
+            compress_log_entries: false,
         };
 
         let app_config = AppConfig {
             storage: StorageConfig {
                 data_dir: temp_dir.path().to_string_lossy().to_string(),
-                max_open_files: 1000,
                 cache_size_mb: 64,
-                write_buffer_size_mb: 64,
                 max_write_buffer_number: 2,
+                ..Default::default()
             },
             ..Default::default()
         };
@@ -243,12 +589,14 @@ impl SingleNodeBenchmark {
         node.start().await?;
 
         Ok(Self {
-            node,
+            node: Arc::new(node),
             _temp_dir: temp_dir,
         })
     }
 
     /// 基础性能测试
+    ///
+    /// 仅测量 `get_metrics()` 的延迟，真实写入吞吐请使用 [`SingleNodeBenchmark::run_write_benchmark`]
     pub async fn run_basic_performance_test(&self, config: &BenchmarkConfig) -> BenchmarkResults {
         info!("开始基础性能测试...");
         
@@ -257,34 +605,17 @@ impl SingleNodeBenchmark {
         self.warmup(config.warmup_duration).await;
 
         // 性能测试
-        info!("开始性能测试，持续时间: {:?}", config.duration);
-        let start_time = Instant::now();
-        let mut operations = 0u64;
-        let mut successful = 0u64;
-        let mut latencies = Vec::new();
-
-        while start_time.elapsed() < config.duration {
-            let op_start = Instant::now();
-            
-            // 模拟基础操作（获取metrics）
-            match self.node.get_metrics().await {
-                Ok(_) => {
-                    successful += 1;
-                    latencies.push(op_start.elapsed());
-                }
-                Err(_) => {
-                    // 操作失败
-                }
-            }
-            
-            operations += 1;
-            
-            // 控制测试频率
-            sleep(config.test_interval).await;
-        }
-
-        let total_duration = start_time.elapsed();
-        BenchmarkResults::calculate(operations, successful, &mut latencies, total_duration)
+        info!(
+            "开始性能测试，持续时间: {:?}，并发数: {}",
+            config.duration, config.concurrency
+        );
+        let node = self.node.clone();
+        run_concurrent_workers(config, move |_worker, _seq| {
+            let node = node.clone();
+            // 基础操作（获取metrics）
+            async move { node.get_metrics().await.is_ok() }
+        })
+        .await
     }
 
     /// 内存使用测试
@@ -336,138 +667,227 @@ impl SingleNodeBenchmark {
     }
 }
 
-/// 集群性能基准测试
-pub struct ClusterBenchmark {
-    nodes: Vec<RaftNode>,
-    _temp_dirs: Vec<TempDir>,
-}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracing_test::traced_test;
 
-impl ClusterBenchmark {
-    /// 创建3节点集群测试环境
-    pub async fn new() -> Result<Self, Box<dyn std::error::Error>> {
-        let mut temp_dirs = Vec::new();
-        let mut nodes = Vec::new();
+    fn millis(values: &[u64]) -> Vec<Duration> {
+        values.iter().map(|&ms| Duration::from_millis(ms)).collect()
+    }
 
-        for _i in 0..3 {
-            temp_dirs.push(TempDir::new()?);
-        }
+    fn assert_close(actual: f64, expected: f64) {
+        assert!((actual - expected).abs() < 1e-9, "expected {}, got {}", expected, actual);
+    }
 
-        let mut node_addresses = HashMap::new();
-        for i in 1..=3u64 {
-            node_addresses.insert(i, format!("127.0.0.1:{}", 18100 + i - 1));
-        }
+    #[test]
+    fn test_percentile_small_samples() {
+        assert_close(percentile_ms(&[], 0.99), 0.0);
+        assert_close(percentile_ms(&millis(&[7]), 0.5), 7.0);
+        assert_close(percentile_ms(&millis(&[7]), 0.99), 7.0);
+
+        // 两个样本之间线性插值
+        let two = millis(&[10, 20]);
+        assert_close(percentile_ms(&two, 0.0), 10.0);
+        assert_close(percentile_ms(&two, 0.5), 15.0);
+        assert_close(percentile_ms(&two, 0.95), 19.5);
+        assert_close(percentile_ms(&two, 1.0), 20.0);
+    }
 
-        let network_config = NetworkConfig::new(node_addresses.clone());
+    #[test]
+    fn test_percentile_matches_r7_reference_values() {
+        // 参考值与 numpy.percentile(1..=10, q) 一致
+        let samples = millis(&[1, 2, 3, 4, 5, 6, 7, 8, 9, 10]);
+        assert_close(percentile_ms(&samples, 0.50), 5.5);
+        assert_close(percentile_ms(&samples, 0.95), 9.55);
+        assert_close(percentile_ms(&samples, 0.99), 9.91);
+
+        let samples = millis(&[15, 20, 35, 40, 50]);
+        assert_close(percentile_ms(&samples, 0.40), 29.0);
+        assert_close(percentile_ms(&samples, 0.75), 40.0);
+    }
 
-        for i in 0..3 {
-            let node_id = (i + 1) as u64;
-            
-            let node_config = NodeConfig {
-                node_id,
-                address: node_addresses[&node_id].clone(),
-                raft_config: RaftConfig::default(),
-                network_config: network_config.clone(),
-                heartbeat_interval: 150,
-                election_timeout_min: 300,
-                election_timeout_max: 600,
-                resource_limits: ResourceLimits::default(),
-            };
+    #[test]
+    fn test_calculate_results() {
+        let mut latencies = millis(&[40, 10, 30, 20]);
+        let results = BenchmarkResults::calculate(5, 4, &mut latencies, Duration::from_secs(2));
+
+        assert_eq!(results.failed_operations, 1);
+        assert_close(results.avg_latency_ms, 25.0);
+        assert_close(results.p50_latency_ms, 25.0);
+        assert_close(results.p95_latency_ms, 38.5);
+        assert_close(results.qps, 2.0);
+        assert_close(results.error_rate, 20.0);
+
+        let empty = BenchmarkResults::calculate(0, 0, &mut Vec::new(), Duration::from_secs(1));
+        assert_close(empty.avg_latency_ms, 0.0);
+        assert_close(empty.p99_latency_ms, 0.0);
+        assert_close(empty.p999_latency_ms, 0.0);
+        assert_close(empty.std_dev_ms, 0.0);
+        assert_close(empty.error_rate, 0.0);
+    }
 
-            let app_config = AppConfig {
-                storage: StorageConfig {
-                    data_dir: temp_dirs[i].path().to_string_lossy().to_string(),
-                    max_open_files: 1000,
-                    cache_size_mb: 64,
-                    write_buffer_size_mb: 64,
-                    max_write_buffer_number: 2,
-                },
-                ..Default::default()
-            };
+    #[test]
+    fn test_tail_latency_and_std_dev() {
+        // 样本标准差: sqrt(((10-25)^2 + (20-25)^2 + (30-25)^2 + (40-25)^2) / 3)
+        let mut latencies = millis(&[40, 10, 30, 20]);
+        let results = BenchmarkResults::calculate(4, 4, &mut latencies, Duration::from_secs(1));
+        assert_close(results.std_dev_ms, (500.0f64 / 3.0).sqrt());
+
+        // 长尾分布：1000个样本中有少量慢请求
+        let mut latencies: Vec<Duration> = (0..1000u64)
+            .map(|i| Duration::from_micros(if i % 200 == 0 { 50_000 } else { 1_000 + i }))
+            .collect();
+        let results = BenchmarkResults::calculate(1000, 1000, &mut latencies, Duration::from_secs(1));
+        assert!(results.p999_latency_ms >= results.p99_latency_ms);
+        assert!(results.p999_latency_ms > 40.0, "{}", results.p999_latency_ms);
+        assert!(results.std_dev_ms > 0.0);
+
+        let mut same = millis(&[5, 5, 5]);
+        let results = BenchmarkResults::calculate(3, 3, &mut same, Duration::from_secs(1));
+        assert_close(results.std_dev_ms, 0.0);
+    }
 
-            let mut node = RaftNode::new(node_config, &app_config).await?;
-            node.start().await?;
-            nodes.push(node);
-        }
+    #[test]
+    fn test_assert_within_slo() {
+        let mut latencies = millis(&[10, 20, 30]);
+        let results = BenchmarkResults::calculate(4, 3, &mut latencies, Duration::from_secs(1));
+        results.assert_within_slo(30.0, 25.0);
 
-        // 等待集群稳定
-        sleep(Duration::from_secs(2)).await;
+        let panic = std::panic::catch_unwind(|| results.assert_within_slo(10.0, 1.0)).unwrap_err();
+        let message = panic.downcast_ref::<String>().unwrap();
+        assert!(message.contains("P99延迟"), "{}", message);
+        assert!(message.contains("错误率 25.00%"), "{}", message);
+    }
 
-        Ok(Self {
-            nodes,
-            _temp_dirs: temp_dirs,
-        })
+    #[test]
+    fn test_load_config_from_file() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("config/benchmark_slo.toml");
+        let config = BenchmarkConfig::load_from_file(path).unwrap();
+        assert_eq!(config.concurrency, 10);
+        assert_eq!(config.test_interval, Duration::from_millis(100));
+        assert_eq!(config.slo, BenchmarkSlo::default());
+
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("slo.toml");
+        std::fs::write(&path, "[benchmark]\nduration_secs = 2\n\n[slo]\np99_latency_ms = 5.5\n").unwrap();
+        let config = BenchmarkConfig::load_from_file(&path).unwrap();
+        assert_eq!(config.duration, Duration::from_secs(2));
+        assert_eq!(config.concurrency, BenchmarkConfig::default().concurrency);
+        assert_close(config.slo.p99_latency_ms, 5.5);
+        assert_close(config.slo.error_rate_pct, 1.0);
+
+        std::fs::write(&path, "[slo]\np99_latency_ms = \"fast\"\n").unwrap();
+        assert!(BenchmarkConfig::load_from_file(&path).is_err());
+        assert!(BenchmarkConfig::load_from_file(dir.path().join("missing.toml")).is_err());
     }
 
-    /// 集群性能测试
-    pub async fn run_cluster_performance_test(&self, config: &BenchmarkConfig) -> BenchmarkResults {
-        info!("开始集群性能测试...");
-        
-        // 预热
-        info!("集群预热阶段...");
-        self.warmup_cluster(config.warmup_duration).await;
+    #[test]
+    fn test_config_from_env_vars() {
+        let vars = std::collections::HashMap::from([
+            (BENCH_DURATION_SECS_ENV, "12"),
+            (BENCH_CONCURRENCY_ENV, " 3 "),
+            (BENCH_INTERVAL_MS_ENV, "fast"),
+        ]);
+        let config = BenchmarkConfig::from_vars(|name| vars.get(name).map(|v| v.to_string()));
+        assert_eq!(config.duration, Duration::from_secs(12));
+        assert_eq!(config.concurrency, 3);
+        // 未设置和无法解析的变量使用默认值
+        let defaults = BenchmarkConfig::default();
+        assert_eq!(config.warmup_duration, defaults.warmup_duration);
+        assert_eq!(config.test_interval, defaults.test_interval);
+
+        let config = BenchmarkConfig::from_vars(|_| None);
+        assert_eq!(config.duration, defaults.duration);
+        assert_eq!(config.concurrency, defaults.concurrency);
+    }
 
-        // 性能测试
-        info!("开始集群性能测试，持续时间: {:?}", config.duration);
-        let start_time = Instant::now();
-        let mut operations = 0u64;
-        let mut successful = 0u64;
-        let mut latencies = Vec::new();
+    #[test]
+    fn test_config_validation() {
+        assert!(BenchmarkConfig::default().validate().is_ok());
+        assert!(BenchmarkConfig::for_ci().validate().is_ok());
+        assert_eq!(BenchmarkConfig::for_ci().duration, Duration::from_secs(5));
 
-        while start_time.elapsed() < config.duration {
-            let op_start = Instant::now();
-            
-            // 随机选择一个节点进行操作
-            let node_idx = operations as usize % self.nodes.len();
-            
-            match self.nodes[node_idx].get_metrics().await {
-                Ok(_) => {
-                    successful += 1;
-                    latencies.push(op_start.elapsed());
-                }
-                Err(_) => {
-                    // 操作失败
-                }
-            }
-            
-            operations += 1;
-            
-            // 控制测试频率
-            sleep(config.test_interval).await;
+        let zero_duration = BenchmarkConfig {
+            duration: Duration::ZERO,
+            ..BenchmarkConfig::for_ci()
+        };
+        assert!(matches!(zero_duration.validate(), Err(ConfluxError::Validation(_))));
+        let zero_concurrency = BenchmarkConfig {
+            concurrency: 0,
+            ..BenchmarkConfig::for_ci()
+        };
+        assert!(zero_concurrency.validate().is_err());
+    }
+
+    #[test]
+    fn test_rate_limit_benchmark_compares_algorithms() {
+        let results = run_rate_limit_benchmark(100);
+        for result in &results {
+            result.display();
+        }
+        let find = |algorithm: &str, pattern: &str| {
+            results
+                .iter()
+                .find(|r| r.algorithm == algorithm && r.pattern == pattern)
+                .unwrap()
+        };
+
+        // 以限制速率均匀到达时两种算法都不拒绝
+        for algorithm in ["fixed_window", "sliding_window"] {
+            assert_eq!(find(algorithm, "steady").rejected, 0);
+            assert_eq!(find(algorithm, "steady").max_accepted_per_window, 100);
         }
 
-        let total_duration = start_time.elapsed();
-        BenchmarkResults::calculate(operations, successful, &mut latencies, total_duration)
+        // 固定窗口在边界处接受了接近两倍限制的请求，滑动窗口不会
+        assert_eq!(find("fixed_window", "boundary_burst").max_accepted_per_window, 199);
+        let sliding = find("sliding_window", "boundary_burst");
+        assert_eq!(sliding.max_accepted_per_window, 100);
+        assert!(sliding.rejected > find("fixed_window", "boundary_burst").rejected);
     }
 
-    /// 集群预热
-    async fn warmup_cluster(&self, duration: Duration) {
-        let start_time = Instant::now();
-        let mut node_idx = 0;
-        
-        while start_time.elapsed() < duration {
-            let _ = self.nodes[node_idx].get_metrics().await;
-            node_idx = (node_idx + 1) % self.nodes.len();
-            sleep(Duration::from_millis(50)).await;
-        }
+    #[test]
+    #[cfg(any(target_os = "linux", target_os = "macos", target_os = "windows"))]
+    fn test_memory_usage_is_reported() {
+        assert!(process_rss_bytes().is_some_and(|bytes| bytes > 0));
+        assert!(MemoryStats::get_memory_usage_mb() > 0.0);
+        assert!(MemoryStats::current().current_memory_mb > 0.0);
+
+        let baseline = MemoryStats::new_snapshot();
+        assert_close(baseline.initial_memory_mb, baseline.current_memory_mb);
+        let mut stats = baseline.clone();
+        stats.update();
+        assert_close(stats.delta_from(&baseline), stats.current_memory_mb - baseline.current_memory_mb);
+        assert!(MEMORY_RSS_BYTES.get() > 0.0);
+        assert!(MEMORY_PEAK_RSS_BYTES.get() > 0.0);
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use tracing_test::traced_test;
+    #[test]
+    fn test_parse_vm_rss() {
+        let status = "Name:\tconflux\nVmPeak:\t  204800 kB\nVmRSS:\t   10240 kB\nThreads:\t4\n";
+        assert_eq!(parse_vm_rss_kb(status), Some(10240));
+        assert_eq!(parse_vm_rss_kb("Name:\tconflux\n"), None);
+    }
+
+    #[test]
+    fn test_memory_usage_limit() {
+        let stats = |current_memory_mb| MemoryStats {
+            initial_memory_mb: 0.0,
+            peak_memory_mb: current_memory_mb,
+            current_memory_mb,
+            memory_growth_mb: current_memory_mb,
+        };
+        assert!(stats(IDLE_MEMORY_LIMIT_MB - 1.0).is_memory_usage_acceptable());
+        assert!(!stats(IDLE_MEMORY_LIMIT_MB).is_memory_usage_acceptable());
+        assert_close(stats(150.0).delta_from(&stats(100.0)), 50.0);
+    }
 
     #[tokio::test]
     #[traced_test]
     async fn test_single_node_benchmark() {
         let benchmark = SingleNodeBenchmark::new().await.expect("Failed to create benchmark");
         
-        let config = BenchmarkConfig {
-            duration: Duration::from_secs(5),
-            concurrency: 1,
-            warmup_duration: Duration::from_secs(1),
-            test_interval: Duration::from_millis(50),
-        };
+        let config = BenchmarkConfig::for_ci();
 
         let results = benchmark.run_basic_performance_test(&config).await;
         results.display("单节点基础性能");
@@ -478,6 +898,45 @@ mod tests {
         assert!(results.qps > 0.0);
     }
 
+    #[tokio::test]
+    async fn test_concurrency_increases_qps() {
+        let benchmark = SingleNodeBenchmark::new().await.expect("Failed to create benchmark");
+
+        let mut qps = Vec::new();
+        for concurrency in [1, 4] {
+            let config = BenchmarkConfig {
+                duration: Duration::from_secs(1),
+                concurrency,
+                warmup_duration: Duration::from_millis(0),
+                test_interval: Duration::from_millis(20),
+                ..BenchmarkConfig::for_ci()
+            };
+            let results = benchmark.run_basic_performance_test(&config).await;
+            assert_eq!(results.failed_operations, 0);
+            qps.push(results.qps);
+        }
+
+        // 操作间隔主导单任务耗时，4个并发任务的吞吐量应明显高于单任务
+        assert!(qps[1] > qps[0] * 2.0, "qps: {:?}", qps);
+    }
+
+    #[tokio::test]
+    async fn test_run_concurrent_workers_aggregates_samples() {
+        let config = BenchmarkConfig {
+            duration: Duration::from_millis(200),
+            concurrency: 3,
+            warmup_duration: Duration::from_millis(0),
+            test_interval: Duration::from_millis(10),
+            ..BenchmarkConfig::for_ci()
+        };
+
+        // 工作任务1的操作全部失败
+        let results = run_concurrent_workers(&config, |worker, _seq| async move { worker != 1 }).await;
+        assert!(results.total_operations >= 3);
+        assert!(results.failed_operations > 0);
+        assert!(results.successful_operations > results.failed_operations);
+    }
+
     #[tokio::test]
     #[traced_test]
     async fn test_memory_benchmark() {
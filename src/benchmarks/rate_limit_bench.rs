@@ -0,0 +1,166 @@
+//! 速率限制算法基准测试
+//!
+//! 用合成的请求时间序列对比旧的固定窗口计数和 [`RateLimitState`] 的滑动窗口计数，
+//! 统计每种流量模式下被拒绝的请求数、任意窗口内被接受的最多请求数以及单次检查的耗时
+
+use crate::raft::node::RateLimitState;
+use serde::Serialize;
+use std::time::Duration;
+use tokio::time::Instant;
+use tracing::info;
+
+/// 速率限制的窗口长度
+const WINDOW: Duration = Duration::from_secs(1);
+
+/// 每种流量模式重复的周期数
+const ROUNDS: u32 = 50;
+
+/// 旧的固定窗口计数器，仅用于对比
+///
+/// 窗口从重置后的第一个请求开始，满1秒后的第一个请求重置计数
+struct FixedWindowCounter {
+    request_count: u32,
+    window_start: Option<Instant>,
+}
+
+impl FixedWindowCounter {
+    fn try_acquire(&mut self, now: Instant, max_requests: u32) -> bool {
+        match self.window_start {
+            Some(start) if now.duration_since(start) < WINDOW => {}
+            _ => {
+                self.request_count = 0;
+                self.window_start = Some(now);
+            }
+        }
+        if self.request_count >= max_requests {
+            return false;
+        }
+        self.request_count += 1;
+        true
+    }
+}
+
+/// 单个算法在单种流量模式下的结果
+#[derive(Debug, Clone, Serialize)]
+pub struct RateLimitBenchmarkResults {
+    /// 算法名称（`fixed_window` 或 `sliding_window`）
+    pub algorithm: &'static str,
+    /// 流量模式名称
+    pub pattern: &'static str,
+    /// 请求总数
+    pub requests: u64,
+    /// 被拒绝的请求数
+    pub rejected: u64,
+    /// 任意1秒窗口内被接受的最多请求数，超过限制说明出现了边界突发
+    pub max_accepted_per_window: usize,
+    /// 单次检查的平均耗时 (纳秒)
+    pub ns_per_check: f64,
+}
+
+impl RateLimitBenchmarkResults {
+    /// 显示测试结果
+    pub fn display(&self) {
+        info!(
+            "{} / {}: 请求 {}，拒绝 {}，窗口内最多接受 {}，每次检查 {:.1}ns",
+            self.algorithm,
+            self.pattern,
+            self.requests,
+            self.rejected,
+            self.max_accepted_per_window,
+            self.ns_per_check
+        );
+    }
+}
+
+/// 对比固定窗口和滑动窗口两种速率限制算法
+///
+/// 流量模式：
+/// - `steady`：以恰好等于限制的速率均匀到达
+/// - `boundary_burst`：窗口开始时1个请求，窗口末尾和下一个窗口开始时各一次突发
+///
+/// # Arguments
+/// * `max_requests_per_second` - 每秒最大请求数
+///
+/// # Returns
+/// * 每种流量模式下两种算法的结果
+pub fn run_rate_limit_benchmark(max_requests_per_second: u32) -> Vec<RateLimitBenchmarkResults> {
+    let start = Instant::now();
+    let mut results = Vec::new();
+    for (pattern, offsets) in [
+        ("steady", steady_offsets(max_requests_per_second)),
+        ("boundary_burst", boundary_burst_offsets(max_requests_per_second)),
+    ] {
+        let arrivals: Vec<Instant> = offsets.into_iter().map(|offset| start + offset).collect();
+
+        let mut fixed = FixedWindowCounter {
+            request_count: 0,
+            window_start: None,
+        };
+        results.push(measure("fixed_window", pattern, &arrivals, |now| {
+            fixed.try_acquire(now, max_requests_per_second)
+        }));
+
+        let mut sliding = RateLimitState::new(WINDOW);
+        results.push(measure("sliding_window", pattern, &arrivals, |now| {
+            sliding.try_acquire(now, max_requests_per_second).is_ok()
+        }));
+    }
+    results
+}
+
+/// 依次检查每个到达的请求并统计结果
+fn measure(
+    algorithm: &'static str,
+    pattern: &'static str,
+    arrivals: &[Instant],
+    mut check: impl FnMut(Instant) -> bool,
+) -> RateLimitBenchmarkResults {
+    let mut accepted = Vec::with_capacity(arrivals.len());
+    let timer = std::time::Instant::now();
+    for &now in arrivals {
+        if check(now) {
+            accepted.push(now);
+        }
+    }
+    let elapsed = timer.elapsed();
+
+    RateLimitBenchmarkResults {
+        algorithm,
+        pattern,
+        requests: arrivals.len() as u64,
+        rejected: (arrivals.len() - accepted.len()) as u64,
+        max_accepted_per_window: max_per_window(&accepted),
+        ns_per_check: elapsed.as_nanos() as f64 / arrivals.len().max(1) as f64,
+    }
+}
+
+/// 任意长度为 [`WINDOW`] 的区间内最多的请求数
+fn max_per_window(sorted: &[Instant]) -> usize {
+    let mut first = 0;
+    let mut max = 0;
+    for (last, &now) in sorted.iter().enumerate() {
+        while now.duration_since(sorted[first]) >= WINDOW {
+            first += 1;
+        }
+        max = max.max(last - first + 1);
+    }
+    max
+}
+
+/// 以限制速率均匀到达的请求
+fn steady_offsets(max_requests: u32) -> Vec<Duration> {
+    let interval = WINDOW / max_requests.max(1);
+    (0..max_requests * ROUNDS).map(|i| interval * i).collect()
+}
+
+/// 每2秒一个周期：周期开始时1个请求，0.9秒时 `max - 1` 个，1秒时 `max` 个
+fn boundary_burst_offsets(max_requests: u32) -> Vec<Duration> {
+    let mut offsets = Vec::new();
+    for round in 0..ROUNDS {
+        let period = WINDOW * 2 * round;
+        offsets.push(period);
+        offsets.extend((1..max_requests).map(|_| period + WINDOW * 9 / 10));
+        offsets.extend((0..max_requests).map(|_| period + WINDOW));
+    }
+    offsets
+}
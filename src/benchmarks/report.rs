@@ -0,0 +1,200 @@
+//! 基准测试结果的机器可读输出
+//!
+//! 将测试结果序列化为JSON或CSV，便于在CI中作为产物保存并跨提交比较性能回归
+
+use super::BenchmarkResults;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// CSV输出的表头，列顺序与 [`BenchmarkResults::to_csv_row`] 一致
+pub const CSV_HEADER: &str = "test_name,timestamp,total_operations,successful_operations,failed_operations,\
+avg_latency_ms,p50_latency_ms,p95_latency_ms,p99_latency_ms,p999_latency_ms,std_dev_ms,qps,error_rate,\
+meets_performance_targets";
+
+/// 单条带名称和时间戳的测试结果记录
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BenchmarkRecord {
+    /// 测试名称
+    pub test_name: String,
+    /// 记录时间
+    pub timestamp: DateTime<Utc>,
+    /// 测试结果
+    #[serde(flatten)]
+    pub results: BenchmarkResults,
+    /// 是否达到性能目标
+    pub meets_performance_targets: bool,
+}
+
+impl BenchmarkRecord {
+    /// 以当前时间创建测试结果记录
+    ///
+    /// # Arguments
+    /// * `test_name` - 测试名称
+    /// * `results` - 测试结果
+    pub fn new(test_name: &str, results: &BenchmarkResults) -> Self {
+        Self {
+            test_name: test_name.to_string(),
+            timestamp: Utc::now(),
+            results: results.clone(),
+            meets_performance_targets: results.meets_performance_targets(),
+        }
+    }
+
+    /// 序列化为一行CSV（不含换行符），列顺序见 [`CSV_HEADER`]
+    pub fn to_csv_row(&self) -> String {
+        let r = &self.results;
+        format!(
+            "{},{},{},{},{},{:.3},{:.3},{:.3},{:.3},{:.3},{:.3},{:.3},{:.3},{}",
+            csv_field(&self.test_name),
+            self.timestamp.to_rfc3339(),
+            r.total_operations,
+            r.successful_operations,
+            r.failed_operations,
+            r.avg_latency_ms,
+            r.p50_latency_ms,
+            r.p95_latency_ms,
+            r.p99_latency_ms,
+            r.p999_latency_ms,
+            r.std_dev_ms,
+            r.qps,
+            r.error_rate,
+            self.meets_performance_targets
+        )
+    }
+}
+
+impl BenchmarkResults {
+    /// 序列化为JSON，包含测试名称、时间戳和是否达到性能目标
+    ///
+    /// # Arguments
+    /// * `test_name` - 测试名称
+    pub fn to_json(&self, test_name: &str) -> serde_json::Result<String> {
+        serde_json::to_string(&BenchmarkRecord::new(test_name, self))
+    }
+
+    /// 序列化为一行CSV，列顺序见 [`CSV_HEADER`]
+    ///
+    /// # Arguments
+    /// * `test_name` - 测试名称
+    pub fn to_csv_row(&self, test_name: &str) -> String {
+        BenchmarkRecord::new(test_name, self).to_csv_row()
+    }
+}
+
+/// 收集多个命名测试结果的报告
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BenchmarkReport {
+    /// 按添加顺序排列的测试记录
+    pub records: Vec<BenchmarkRecord>,
+}
+
+impl BenchmarkReport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 添加一条测试结果，记录时间为当前时间
+    ///
+    /// # Arguments
+    /// * `test_name` - 测试名称
+    /// * `results` - 测试结果
+    pub fn add(&mut self, test_name: &str, results: &BenchmarkResults) {
+        self.records.push(BenchmarkRecord::new(test_name, results));
+    }
+
+    /// 序列化为格式化的JSON
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// 序列化为带表头的CSV
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from(CSV_HEADER);
+        csv.push('\n');
+        for record in &self.records {
+            csv.push_str(&record.to_csv_row());
+            csv.push('\n');
+        }
+        csv
+    }
+
+    /// 将报告写入文件，扩展名为 `.csv` 时输出CSV，否则输出JSON
+    ///
+    /// # Arguments
+    /// * `path` - 输出文件路径
+    pub fn write_to_file(&self, path: impl AsRef<Path>) -> Result<(), Box<dyn std::error::Error>> {
+        let path = path.as_ref();
+        let is_csv = path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("csv"));
+        let content = if is_csv { self.to_csv() } else { self.to_json()? };
+        std::fs::write(path, content)?;
+        Ok(())
+    }
+}
+
+/// 按RFC 4180转义CSV字段：包含逗号、引号或换行时用双引号包裹
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+    use tempfile::TempDir;
+
+    fn sample_results() -> BenchmarkResults {
+        let mut latencies = vec![Duration::from_millis(2), Duration::from_millis(4)];
+        BenchmarkResults::calculate(300, 300, &mut latencies, Duration::from_secs(1))
+    }
+
+    #[test]
+    fn test_to_json_includes_metadata() {
+        let json: serde_json::Value =
+            serde_json::from_str(&sample_results().to_json("read").unwrap()).unwrap();
+        assert_eq!(json["test_name"], "read");
+        assert_eq!(json["total_operations"], 300);
+        assert_eq!(json["meets_performance_targets"], true);
+        assert!(json["timestamp"].as_str().unwrap().parse::<DateTime<Utc>>().is_ok());
+    }
+
+    #[test]
+    fn test_csv_row_matches_header() {
+        let row = sample_results().to_csv_row("write, large");
+        assert!(row.starts_with("\"write, large\","));
+        assert!(row.ends_with(",true"));
+        // 转义后的名称中含一个逗号，其余列数与表头一致
+        assert_eq!(row.split(',').count(), CSV_HEADER.split(',').count() + 1);
+        assert_eq!(csv_field("a\"b"), "\"a\"\"b\"");
+    }
+
+    #[test]
+    fn test_report_round_trips_through_file() {
+        let mut report = BenchmarkReport::new();
+        report.add("read", &sample_results());
+        report.add("write", &BenchmarkResults::calculate(10, 5, &mut Vec::new(), Duration::from_secs(1)));
+
+        let dir = TempDir::new().unwrap();
+        let json_path = dir.path().join("bench.json");
+        report.write_to_file(&json_path).unwrap();
+        let loaded: BenchmarkReport =
+            serde_json::from_str(&std::fs::read_to_string(&json_path).unwrap()).unwrap();
+        assert_eq!(loaded.records.len(), 2);
+        assert_eq!(loaded.records[1].test_name, "write");
+        assert!(!loaded.records[1].meets_performance_targets);
+
+        let csv_path = dir.path().join("bench.csv");
+        report.write_to_file(&csv_path).unwrap();
+        let csv = std::fs::read_to_string(&csv_path).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], CSV_HEADER);
+        assert!(lines[2].starts_with("write,"));
+    }
+}
@@ -0,0 +1,209 @@
+//! 写入性能基准测试
+//!
+//! 通过节点的 `client_write` 路径提交真实的配置写入命令，
+//! 测量端到端的共识延迟和QPS，并在每次写入后读取已发布配置以测量读取延迟
+
+use super::{BenchmarkConfig, BenchmarkResults, SingleNodeBenchmark};
+use crate::raft::node::RaftNode;
+use crate::raft::store::ContentLimits;
+use crate::raft::types::{ClientRequest, ConfigFormat, ConfigNamespace, RaftCommand};
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+use tokio::time::sleep;
+use tracing::{info, warn};
+
+/// 基准测试写入的配置名称
+const BENCHMARK_CONFIG_NAME: &str = "benchmark.json";
+
+/// 写入基准测试结果，读写分别统计
+#[derive(Debug, Clone)]
+pub struct WriteBenchmarkResults {
+    /// 写入负载大小 (字节)
+    pub payload_size: usize,
+    /// 写入操作统计
+    pub write: BenchmarkResults,
+    /// 读取操作统计
+    pub read: BenchmarkResults,
+}
+
+impl WriteBenchmarkResults {
+    /// 显示测试结果
+    pub fn display(&self, test_name: &str) {
+        info!("{} 负载大小: {} 字节", test_name, self.payload_size);
+        self.write.display(&format!("{} (写入)", test_name));
+        self.read.display(&format!("{} (读取)", test_name));
+    }
+}
+
+impl SingleNodeBenchmark {
+    /// 写入性能测试
+    ///
+    /// 先创建基准配置，然后交替提交 `UpdateConfig` 和 `CreateVersion` 命令，
+    /// 每次写入后读取已发布配置
+    ///
+    /// # Arguments
+    /// * `config` - 测试配置
+    /// * `payload_size` - 每次写入的配置内容大小 (字节)
+    ///
+    /// # Returns
+    /// * `WriteBenchmarkResults` - 读写分别统计的测试结果
+    pub async fn run_write_benchmark(
+        &self,
+        config: &BenchmarkConfig,
+        payload_size: usize,
+    ) -> Result<WriteBenchmarkResults, Box<dyn std::error::Error>> {
+        info!("开始写入性能测试，负载大小: {} 字节", payload_size);
+
+        let namespace = ConfigNamespace {
+            tenant: "benchmark".to_string(),
+            app: "bench".to_string(),
+            env: "test".to_string(),
+        };
+        let config_id = self.create_benchmark_config(&namespace, payload_size).await?;
+
+        info!("开始写入测试，持续时间: {:?}", config.duration);
+        let start_time = Instant::now();
+        let mut seq = 0u64;
+        let (mut writes, mut successful_writes, mut write_latencies) = (0u64, 0u64, Vec::new());
+        let (mut reads, mut successful_reads, mut read_latencies) = (0u64, 0u64, Vec::new());
+        let labels = BTreeMap::new();
+
+        while start_time.elapsed() < config.duration {
+            seq += 1;
+            let content = make_payload(seq, payload_size);
+            let command = if seq.is_multiple_of(2) {
+                RaftCommand::UpdateConfig {
+                    config_id,
+                    namespace: namespace.clone(),
+                    name: BENCHMARK_CONFIG_NAME.to_string(),
+                    content,
+                    format: ConfigFormat::Json,
+                    schema: None,
+                    description: format!("benchmark update {}", seq),
+                }
+            } else {
+                RaftCommand::CreateVersion {
+                    config_id,
+                    content,
+                    format: None,
+                    creator_id: 1,
+                    description: format!("benchmark version {}", seq),
+                }
+            };
+
+            let op_start = Instant::now();
+            match self.node.client_write(ClientRequest { command, idempotency_key: None }).await {
+                Ok(response) if response.success => {
+                    successful_writes += 1;
+                    write_latencies.push(op_start.elapsed());
+                }
+                Ok(response) => warn!("Benchmark write rejected: {}", response.message),
+                Err(e) => warn!("Benchmark write failed: {}", e),
+            }
+            writes += 1;
+
+            let op_start = Instant::now();
+            if self
+                .node
+                .store()
+                .get_published_config(&namespace, BENCHMARK_CONFIG_NAME, &labels)
+                .await
+                .is_some()
+            {
+                successful_reads += 1;
+                read_latencies.push(op_start.elapsed());
+            }
+            reads += 1;
+
+            // 控制测试频率
+            sleep(config.test_interval).await;
+        }
+
+        let total_duration = start_time.elapsed();
+        Ok(WriteBenchmarkResults {
+            payload_size,
+            write: BenchmarkResults::calculate(writes, successful_writes, &mut write_latencies, total_duration),
+            read: BenchmarkResults::calculate(reads, successful_reads, &mut read_latencies, total_duration),
+        })
+    }
+
+    /// 创建基准测试使用的配置，返回配置ID
+    async fn create_benchmark_config(
+        &self,
+        namespace: &ConfigNamespace,
+        payload_size: usize,
+    ) -> Result<u64, Box<dyn std::error::Error>> {
+        self.node.wait_for_leadership(Duration::from_secs(5)).await?;
+        lift_version_history_limit(&self.node);
+
+        let response = self
+            .node
+            .client_write(ClientRequest {
+                command: RaftCommand::CreateConfig {
+                    namespace: namespace.clone(),
+                    name: BENCHMARK_CONFIG_NAME.to_string(),
+                    content: make_payload(0, payload_size),
+                    format: ConfigFormat::Json,
+                    schema: None,
+                    creator_id: 1,
+                    description: "benchmark config".to_string(),
+                },
+                idempotency_key: None,
+            })
+            .await?;
+
+        response
+            .config_id
+            .ok_or_else(|| format!("Failed to create benchmark config: {}", response.message).into())
+    }
+}
+
+/// 生成指定大小的JSON配置内容，序号保证每次写入的内容不同
+pub(super) fn make_payload(seq: u64, size: usize) -> Vec<u8> {
+    let prefix = format!("{{\"seq\":{},\"data\":\"", seq);
+    let suffix = "\"}";
+    let padding = size.saturating_sub(prefix.len() + suffix.len());
+    format!("{}{}{}", prefix, "x".repeat(padding), suffix).into_bytes()
+}
+
+/// 取消节点的版本数量限制，避免长时间写入测试触及默认的版本历史上限
+pub(super) fn lift_version_history_limit(node: &RaftNode) {
+    let limits = node.store().content_limits();
+    limits.set_defaults(ContentLimits {
+        max_version_history: usize::MAX,
+        ..limits.defaults()
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_make_payload_size() {
+        assert_eq!(make_payload(1, 1024).len(), 1024);
+        assert_ne!(make_payload(1, 256), make_payload(2, 256));
+        assert!(serde_json::from_slice::<serde_json::Value>(&make_payload(3, 64)).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_write_benchmark() {
+        let benchmark = SingleNodeBenchmark::new().await.expect("Failed to create benchmark");
+
+        let config = BenchmarkConfig {
+            duration: Duration::from_secs(2),
+            concurrency: 1,
+            warmup_duration: Duration::from_secs(0),
+            test_interval: Duration::from_millis(20),
+            ..Default::default()
+        };
+
+        let results = benchmark.run_write_benchmark(&config, 1024).await.unwrap();
+        results.display("单节点写入性能");
+
+        assert_eq!(results.payload_size, 1024);
+        assert!(results.write.successful_operations > 0);
+        assert!(results.write.qps > 0.0);
+        assert_eq!(results.read.successful_operations, results.read.total_operations);
+    }
+}
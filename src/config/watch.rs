@@ -0,0 +1,132 @@
+//! Config file hot reload
+//!
+//! The watched file is polled for content changes. Only fields that can change
+//! without a restart are taken from the new file; changes to the rest are
+//! logged and ignored until the server restarts.
+
+use super::AppConfig;
+use anyhow::Result;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::sync::broadcast;
+use tracing::{info, warn};
+
+/// How often the watched config file is checked for changes
+pub const CONFIG_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Reloaded configs buffered for slow subscribers
+const CONFIG_UPDATE_CAPACITY: usize = 16;
+
+/// Watch a config file and emit the running config whenever a reloadable field changes
+///
+/// The file is parsed with [`AppConfig::load_from`]. Invalid edits are logged
+/// and skipped. The watcher stops once every receiver has been dropped.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read or parsed initially
+pub fn watch_config_file(path: &Path) -> Result<broadcast::Receiver<AppConfig>> {
+    watch_with_interval(path, CONFIG_POLL_INTERVAL)
+}
+
+fn watch_with_interval(path: &Path, interval: Duration) -> Result<broadcast::Receiver<AppConfig>> {
+    let contents = std::fs::read(path)?;
+    let current = AppConfig::load_from(path)?;
+    let (tx, rx) = broadcast::channel(CONFIG_UPDATE_CAPACITY);
+    tokio::spawn(poll_config_file(path.to_path_buf(), interval, current, contents, tx));
+    Ok(rx)
+}
+
+async fn poll_config_file(
+    path: PathBuf,
+    interval: Duration,
+    mut current: AppConfig,
+    mut contents: Vec<u8>,
+    tx: broadcast::Sender<AppConfig>,
+) {
+    let mut ticker = tokio::time::interval(interval);
+    loop {
+        ticker.tick().await;
+        if tx.receiver_count() == 0 {
+            break;
+        }
+
+        let latest = match tokio::fs::read(&path).await {
+            Ok(latest) => latest,
+            Err(e) => {
+                warn!("Failed to read config file {}: {}", path.display(), e);
+                continue;
+            }
+        };
+        if latest == contents {
+            continue;
+        }
+        contents = latest;
+
+        let parsed = match AppConfig::load_from(&path) {
+            Ok(parsed) => parsed,
+            Err(e) => {
+                warn!("Ignoring invalid config file {}: {}", path.display(), e);
+                continue;
+            }
+        };
+        let reloaded = current.reload_from(&parsed);
+        if reloaded != current {
+            info!("Reloaded config from {}", path.display());
+            current = reloaded;
+            if tx.send(current.clone()).is_err() {
+                break;
+            }
+        }
+    }
+}
+
+impl AppConfig {
+    /// Copy of this config with the reloadable fields taken from `new`
+    ///
+    /// Reloadable fields are `resource_limits`, `observability.log_level` and
+    /// `security.jwt_expiration_hours`. Other differences are logged as
+    /// warnings and not applied.
+    pub fn reload_from(&self, new: &AppConfig) -> AppConfig {
+        let mut reloaded = self.clone();
+        reloaded.resource_limits = new.resource_limits.clone();
+        reloaded.observability.log_level = new.observability.log_level.clone();
+        reloaded.security.jwt_expiration_hours = new.security.jwt_expiration_hours;
+
+        let restart_required = reloaded.restart_required_changes(new);
+        if !restart_required.is_empty() {
+            warn!(
+                "Config changes to {} require a restart and were not applied",
+                restart_required.join(", ")
+            );
+        }
+        reloaded
+    }
+
+    /// Settings that differ from `new`, named for the restart warning
+    fn restart_required_changes(&self, new: &AppConfig) -> Vec<&'static str> {
+        let mut changed = Vec::new();
+        if self.server.port != new.server.port {
+            changed.push("server.port");
+        }
+        if self.raft.data_dir != new.raft.data_dir {
+            changed.push("raft.data_dir");
+        }
+        if self.storage.data_dir != new.storage.data_dir {
+            changed.push("storage.data_dir");
+        }
+
+        let mut rest = new.clone();
+        rest.server.port = self.server.port;
+        rest.raft.data_dir = self.raft.data_dir.clone();
+        rest.storage.data_dir = self.storage.data_dir.clone();
+        if rest != *self {
+            changed.push("other settings");
+        }
+        changed
+    }
+}
+
+#[cfg(test)]
+#[path = "watch_tests.rs"]
+mod tests;
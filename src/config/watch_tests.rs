@@ -0,0 +1,91 @@
+use super::*;
+use tempfile::TempDir;
+use tokio::time::timeout;
+
+const POLL: Duration = Duration::from_millis(20);
+
+fn write_config(path: &Path, port: u16, data_dir: &str, log_level: &str, max_rps: u32) {
+    let contents = format!(
+        "[server]\nport = {port}\n\n[storage]\ndata_dir = \"{data_dir}\"\n\n\
+         [observability]\nlog_level = \"{log_level}\"\n\n\
+         [resource_limits]\nmax_requests_per_second = {max_rps}\n"
+    );
+    std::fs::write(path, contents).unwrap();
+}
+
+async fn next_update(rx: &mut broadcast::Receiver<AppConfig>) -> Option<AppConfig> {
+    timeout(Duration::from_millis(500), rx.recv()).await.ok()?.ok()
+}
+
+#[test]
+fn test_reload_from_only_takes_reloadable_fields() {
+    let current = AppConfig::default();
+    let mut new = AppConfig::default();
+    new.server.port = 9999;
+    new.storage.data_dir = "/tmp/elsewhere".to_string();
+    new.security.jwt_expiration_hours = 48;
+    new.observability.log_level = "debug".to_string();
+    new.resource_limits.max_requests_per_second = 5;
+
+    let reloaded = current.reload_from(&new);
+    assert_eq!(reloaded.server.port, current.server.port);
+    assert_eq!(reloaded.storage.data_dir, current.storage.data_dir);
+    assert_eq!(reloaded.security.jwt_expiration_hours, 48);
+    assert_eq!(reloaded.observability.log_level, "debug");
+    assert_eq!(reloaded.resource_limits.max_requests_per_second, 5);
+
+    assert_eq!(
+        reloaded.restart_required_changes(&new),
+        vec!["server.port", "storage.data_dir"]
+    );
+    new.database.max_connections += 1;
+    assert_eq!(
+        reloaded.restart_required_changes(&new),
+        vec!["server.port", "storage.data_dir", "other settings"]
+    );
+}
+
+#[tokio::test]
+async fn test_watch_emits_reloadable_changes_only() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("config.toml");
+    write_config(&path, 8081, "./data/a", "info", 100);
+
+    let mut rx = watch_with_interval(&path, POLL).unwrap();
+
+    // Restart-only changes are not emitted
+    write_config(&path, 9999, "./data/b", "info", 100);
+    assert!(next_update(&mut rx).await.is_none());
+
+    write_config(&path, 9999, "./data/b", "debug", 5);
+    let reloaded = next_update(&mut rx).await.unwrap();
+    assert_eq!(reloaded.observability.log_level, "debug");
+    assert_eq!(reloaded.resource_limits.max_requests_per_second, 5);
+    assert_eq!(reloaded.server.port, 8081);
+    assert_eq!(reloaded.storage.data_dir, "./data/a");
+}
+
+#[tokio::test]
+async fn test_watch_skips_invalid_edits() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("config.toml");
+    write_config(&path, 8081, "./data/a", "info", 100);
+    let mut rx = watch_with_interval(&path, POLL).unwrap();
+
+    std::fs::write(&path, "[server\nport = ").unwrap();
+    assert!(next_update(&mut rx).await.is_none());
+    // Port 0 fails validation
+    write_config(&path, 0, "./data/a", "warn", 100);
+    assert!(next_update(&mut rx).await.is_none());
+
+    write_config(&path, 8081, "./data/a", "warn", 100);
+    let reloaded = next_update(&mut rx).await.unwrap();
+    assert_eq!(reloaded.observability.log_level, "warn");
+}
+
+#[test]
+fn test_watch_requires_valid_initial_file() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("missing.toml");
+    assert!(watch_config_file(&path).is_err());
+}
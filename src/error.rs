@@ -1,3 +1,10 @@
+use crate::raft::types::NodeId;
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Json, Response},
+};
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+use serde_json::json;
 use thiserror::Error;
 
 /// Main error type for the Conflux application
@@ -16,7 +23,7 @@ pub enum ConfluxError {
     Database(#[from] sqlx::Error),
 
     #[error("Raft error: {0}")]
-    Raft(String),
+    Raft(#[from] RaftError),
 
     #[error("Storage error: {0}")]
     Storage(String),
@@ -42,8 +49,72 @@ pub enum ConfluxError {
     #[error("Validation error: {0}")]
     Validation(String),
 
+    #[error("Not found: {0}")]
+    NotFound(String),
+
+    /// The request conflicts with the current state, e.g. an idempotency key
+    /// reused for a different request
+    #[error("Conflict: {0}")]
+    Conflict(String),
+
     #[error("Internal error: {0}")]
     Internal(String),
+
+    /// An error wrapped with a description of what was being done
+    ///
+    /// Created by [`ConfluxError::with_context`]; nested contexts form a
+    /// chain that is rendered outermost first.
+    #[error("{context}: {source}")]
+    Context {
+        context: String,
+        source: Box<ConfluxError>,
+    },
+}
+
+/// Raft errors callers can tell apart without matching on messages
+///
+/// The messages match the ones previously carried as plain strings, so logs
+/// and clients that inspect them keep working.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum RaftError {
+    /// The local Raft instance has not been started
+    #[error("Raft not initialized")]
+    NotInitialized,
+
+    /// No Raft node is attached to serve the request
+    #[error("{0}")]
+    Unavailable(String),
+
+    /// The cluster currently has no leader
+    #[error("No leader available")]
+    NoLeader,
+
+    /// The request must be handled by the leader, which this node is not
+    #[error("Node {node_id} is not the leader")]
+    NotLeader {
+        node_id: NodeId,
+        leader: Option<NodeId>,
+    },
+
+    /// The request payload is larger than the configured maximum
+    #[error("Request size {size} exceeds limit {limit}")]
+    RequestTooLarge { size: usize, limit: usize },
+
+    /// The client sent more requests per second than allowed
+    #[error("Rate limit exceeded for client {client}: {requests} requests/second")]
+    RateLimited { client: String, requests: u32 },
+
+    /// A node-wide resource limit (memory, concurrency) is exhausted
+    #[error("{0}")]
+    ResourceLimitExceeded(String),
+
+    /// The request was not committed within the configured request timeout
+    #[error("Request timed out after {timeout_ms}ms")]
+    Timeout { timeout_ms: u64 },
+
+    /// Any other Raft failure
+    #[error("{0}")]
+    Other(String),
 }
 
 /// Result type alias for convenience
@@ -51,7 +122,7 @@ pub type Result<T> = std::result::Result<T, ConfluxError>;
 
 impl ConfluxError {
     pub fn raft(msg: impl Into<String>) -> Self {
-        Self::Raft(msg.into())
+        Self::Raft(RaftError::Other(msg.into()))
     }
 
     pub fn storage(msg: impl Into<String>) -> Self {
@@ -70,7 +141,160 @@ impl ConfluxError {
         Self::Validation(msg.into())
     }
 
+    pub fn not_found(msg: impl Into<String>) -> Self {
+        Self::NotFound(msg.into())
+    }
+
+    pub fn conflict(msg: impl Into<String>) -> Self {
+        Self::Conflict(msg.into())
+    }
+
     pub fn internal(msg: impl Into<String>) -> Self {
         Self::Internal(msg.into())
     }
+
+    /// Wrap the error with a description of what was being done, like
+    /// `anyhow::Context`
+    ///
+    /// The error is displayed as `"{ctx}: {error}"`; status and error code
+    /// stay those of the [root cause](Self::root_cause).
+    pub fn with_context(self, ctx: impl Into<String>) -> Self {
+        Self::Context {
+            context: ctx.into(),
+            source: Box::new(self),
+        }
+    }
+
+    /// The innermost error, below every context added with [`Self::with_context`]
+    pub fn root_cause(&self) -> &ConfluxError {
+        let mut error = self;
+        while let Self::Context { source, .. } = error {
+            error = source;
+        }
+        error
+    }
+
+    /// The contexts wrapping the error, outermost first
+    pub fn context_chain(&self) -> Vec<&str> {
+        let mut chain = Vec::new();
+        let mut error = self;
+        while let Self::Context { context, source } = error {
+            chain.push(context.as_str());
+            error = source;
+        }
+        chain
+    }
+
+    /// HTTP status code the error is reported with
+    pub fn status_code(&self) -> StatusCode {
+        match self.root_cause() {
+            Self::Validation(_) => StatusCode::BAD_REQUEST,
+            Self::Auth(_) | Self::AuthError(_) => StatusCode::UNAUTHORIZED,
+            Self::Authz(_) => StatusCode::FORBIDDEN,
+            Self::NotFound(_) => StatusCode::NOT_FOUND,
+            Self::Conflict(_) => StatusCode::CONFLICT,
+            Self::Raft(raft_error) => match raft_error {
+                RaftError::RateLimited { .. } => StatusCode::TOO_MANY_REQUESTS,
+                RaftError::RequestTooLarge { .. } => StatusCode::PAYLOAD_TOO_LARGE,
+                RaftError::NotInitialized
+                | RaftError::Unavailable(_)
+                | RaftError::NoLeader
+                | RaftError::NotLeader { .. }
+                | RaftError::ResourceLimitExceeded(_) => StatusCode::SERVICE_UNAVAILABLE,
+                RaftError::Timeout { .. } => StatusCode::GATEWAY_TIMEOUT,
+                RaftError::Other(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            },
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    /// Stable machine-readable code returned in API error bodies
+    pub fn error_code(&self) -> &'static str {
+        match self.root_cause() {
+            Self::Validation(_) => "validation_failed",
+            Self::Auth(_) | Self::AuthError(_) => "unauthenticated",
+            Self::Authz(_) => "forbidden",
+            Self::NotFound(_) => "not_found",
+            Self::Conflict(_) => "conflict",
+            Self::Raft(raft_error) => match raft_error {
+                RaftError::RateLimited { .. } => "rate_limited",
+                RaftError::RequestTooLarge { .. } => "payload_too_large",
+                RaftError::NotInitialized | RaftError::Unavailable(_) => "unavailable",
+                RaftError::NoLeader => "no_leader",
+                RaftError::NotLeader { .. } => "not_leader",
+                RaftError::ResourceLimitExceeded(_) => "resource_exhausted",
+                RaftError::Timeout { .. } => "timeout",
+                RaftError::Other(_) => "internal_error",
+            },
+            _ => "internal_error",
+        }
+    }
+}
+
+/// Adds context to the error of a `Result`, like `anyhow::Context`
+pub trait ResultExt<T> {
+    /// Convert the error into a [`ConfluxError`] and wrap it with `ctx`
+    fn with_context(self, ctx: impl Into<String>) -> Result<T>;
+}
+
+impl<T, E: Into<ConfluxError>> ResultExt<T> for std::result::Result<T, E> {
+    fn with_context(self, ctx: impl Into<String>) -> Result<T> {
+        self.map_err(|e| e.into().with_context(ctx))
+    }
+}
+
+impl<E: std::error::Error> From<openraft::error::RaftError<NodeId, E>> for ConfluxError {
+    fn from(error: openraft::error::RaftError<NodeId, E>) -> Self {
+        Self::raft(error.to_string())
+    }
 }
+
+impl From<openraft::error::Fatal<NodeId>> for ConfluxError {
+    fn from(error: openraft::error::Fatal<NodeId>) -> Self {
+        Self::raft(error.to_string())
+    }
+}
+
+/// Serializes as `{ "code", "message", "context", "root_cause" }`
+///
+/// Wrapped library errors (IO, database, HTTP client) are not serializable
+/// themselves, so every variant is represented by its rendered messages.
+impl Serialize for ConfluxError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("ConfluxError", 4)?;
+        state.serialize_field("code", self.error_code())?;
+        state.serialize_field("message", &self.to_string())?;
+        state.serialize_field("context", &self.context_chain())?;
+        state.serialize_field("root_cause", &self.root_cause().to_string())?;
+        state.end()
+    }
+}
+
+/// Renders the error as `{ "error": { "code", "message" } }` with the status
+/// from [`ConfluxError::status_code`]
+///
+/// Messages of internal errors are logged rather than returned, since they
+/// may describe storage or database internals.
+impl IntoResponse for ConfluxError {
+    fn into_response(self) -> Response {
+        let status = self.status_code();
+        let message = if status == StatusCode::INTERNAL_SERVER_ERROR {
+            tracing::error!("Internal error while handling request: {}", self);
+            "Internal server error".to_string()
+        } else {
+            self.to_string()
+        };
+
+        let body = json!({
+            "error": {
+                "code": self.error_code(),
+                "message": message,
+            }
+        });
+        (status, Json(body)).into_response()
+    }
+}
+
+#[cfg(test)]
+#[path = "error_tests.rs"]
+mod tests;
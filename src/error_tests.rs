@@ -0,0 +1,140 @@
+use super::*;
+use axum::body::to_bytes;
+use serde_json::Value;
+
+async fn render(error: ConfluxError) -> (StatusCode, Value) {
+    let response = error.into_response();
+    let status = response.status();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&body).unwrap())
+}
+
+#[tokio::test]
+async fn test_error_status_codes() {
+    let cases = [
+        (ConfluxError::validation("bad input"), StatusCode::BAD_REQUEST, "validation_failed"),
+        (ConfluxError::auth("bad token"), StatusCode::UNAUTHORIZED, "unauthenticated"),
+        (
+            ConfluxError::AuthError("Missing authorization header".to_string()),
+            StatusCode::UNAUTHORIZED,
+            "unauthenticated",
+        ),
+        (ConfluxError::authz("denied"), StatusCode::FORBIDDEN, "forbidden"),
+        (ConfluxError::not_found("config 7"), StatusCode::NOT_FOUND, "not_found"),
+        (ConfluxError::conflict("key reused"), StatusCode::CONFLICT, "conflict"),
+        (
+            RaftError::RateLimited { client: "alice".to_string(), requests: 2 }.into(),
+            StatusCode::TOO_MANY_REQUESTS,
+            "rate_limited",
+        ),
+        (
+            RaftError::RequestTooLarge { size: 10, limit: 5 }.into(),
+            StatusCode::PAYLOAD_TOO_LARGE,
+            "payload_too_large",
+        ),
+        (RaftError::NoLeader.into(), StatusCode::SERVICE_UNAVAILABLE, "no_leader"),
+        (
+            RaftError::NotLeader { node_id: 1, leader: Some(2) }.into(),
+            StatusCode::SERVICE_UNAVAILABLE,
+            "not_leader",
+        ),
+        (RaftError::NotInitialized.into(), StatusCode::SERVICE_UNAVAILABLE, "unavailable"),
+        (
+            RaftError::Unavailable("no Raft node".to_string()).into(),
+            StatusCode::SERVICE_UNAVAILABLE,
+            "unavailable",
+        ),
+        (
+            RaftError::ResourceLimitExceeded("Memory limit exceeded".to_string()).into(),
+            StatusCode::SERVICE_UNAVAILABLE,
+            "resource_exhausted",
+        ),
+        (
+            RaftError::Timeout { timeout_ms: 5000 }.into(),
+            StatusCode::GATEWAY_TIMEOUT,
+            "timeout",
+        ),
+        (ConfluxError::raft("write failed"), StatusCode::INTERNAL_SERVER_ERROR, "internal_error"),
+        (ConfluxError::storage("disk full"), StatusCode::INTERNAL_SERVER_ERROR, "internal_error"),
+        (ConfluxError::internal("bug"), StatusCode::INTERNAL_SERVER_ERROR, "internal_error"),
+    ];
+
+    for (error, status, code) in cases {
+        let description = error.to_string();
+        assert_eq!(error.status_code(), status, "{}", description);
+        assert_eq!(error.error_code(), code, "{}", description);
+
+        let (rendered_status, body) = render(error).await;
+        assert_eq!(rendered_status, status, "{}", description);
+        assert_eq!(body["error"]["code"], code, "{}", description);
+    }
+}
+
+#[tokio::test]
+async fn test_error_body_message() {
+    let (_, body) = render(ConfluxError::not_found("config tenant/app/prod/db.toml")).await;
+    assert_eq!(body["error"]["message"], "Not found: config tenant/app/prod/db.toml");
+
+    // Internal details are not returned to the client
+    let (_, body) = render(ConfluxError::storage("Meta column family not found")).await;
+    assert_eq!(body["error"]["message"], "Internal server error");
+}
+
+#[tokio::test]
+async fn test_context_chain_preserved() {
+    let error = ConfluxError::storage("disk full")
+        .with_context("Failed to persist config tenant/app/prod/db.toml")
+        .with_context("Failed to apply log entry 42");
+
+    assert_eq!(
+        error.to_string(),
+        "Failed to apply log entry 42: Failed to persist config tenant/app/prod/db.toml: \
+         Storage error: disk full"
+    );
+    assert_eq!(
+        error.context_chain(),
+        vec![
+            "Failed to apply log entry 42",
+            "Failed to persist config tenant/app/prod/db.toml",
+        ]
+    );
+    assert!(matches!(error.root_cause(), ConfluxError::Storage(message) if message == "disk full"));
+    assert!(ConfluxError::storage("disk full").context_chain().is_empty());
+
+    let source = std::error::Error::source(&error).unwrap();
+    assert!(source.to_string().starts_with("Failed to persist config"));
+}
+
+#[tokio::test]
+async fn test_context_keeps_root_status() {
+    let error = ConfluxError::from(RaftError::NotLeader { node_id: 1, leader: Some(2) })
+        .with_context("Failed to submit write");
+    assert_eq!(error.status_code(), StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(error.error_code(), "not_leader");
+
+    let error = ConfluxError::not_found("config 7").with_context("Loading release");
+    let (status, body) = render(error).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["error"]["message"], "Loading release: Not found: config 7");
+}
+
+#[test]
+fn test_result_with_context() {
+    let result: std::result::Result<(), std::io::Error> =
+        Err(std::io::Error::new(std::io::ErrorKind::NotFound, "missing"));
+    let error = result.with_context("Failed to open store").unwrap_err();
+
+    assert_eq!(error.context_chain(), vec!["Failed to open store"]);
+    assert!(matches!(error.root_cause(), ConfluxError::Io(_)));
+}
+
+#[test]
+fn test_error_serialization() {
+    let error = ConfluxError::validation("name is empty").with_context("Creating config");
+    let value = serde_json::to_value(&error).unwrap();
+
+    assert_eq!(value["code"], "validation_failed");
+    assert_eq!(value["message"], "Creating config: Validation error: name is empty");
+    assert_eq!(value["context"], serde_json::json!(["Creating config"]));
+    assert_eq!(value["root_cause"], "Validation error: name is empty");
+}
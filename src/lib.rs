@@ -8,4 +8,4 @@ pub mod app;
 // 性能基准测试模块
 pub mod benchmarks;
 
-pub use error::{ConfluxError, Result};
+pub use error::{ConfluxError, RaftError, Result};
@@ -6,14 +6,22 @@ mod protocol;
 mod app;
 
 use anyhow::Result;
-use config::AppConfig;
-use tracing::info;
-use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+use config::{watch_config_file, AppConfig, CONFIG_FILES};
+use protocol::ProtocolManager;
+use std::path::Path;
+use std::time::Duration;
+use tracing::{info, warn};
+use tracing_subscriber::{layer::SubscriberExt, reload, util::SubscriberInitExt, EnvFilter, Registry};
+
+type LogFilterHandle = reload::Handle<EnvFilter, Registry>;
+
+/// How long protocol plugins get to stop before they are aborted
+const PROTOCOL_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(10);
 
 #[tokio::main]
 async fn main() -> Result<()> {
     // Initialize tracing
-    init_tracing()?;
+    let log_filter = init_tracing()?;
 
     info!("Starting Conflux distributed configuration center");
 
@@ -21,27 +29,60 @@ async fn main() -> Result<()> {
     let config = AppConfig::load().await?;
     info!("Configuration loaded successfully");
 
+    // Reload mutable settings when the highest-precedence config file changes
+    if let Some(path) = CONFIG_FILES.iter().rev().map(Path::new).find(|p| p.exists()) {
+        let mut updates = watch_config_file(path)?;
+        info!("Watching {} for config changes", path.display());
+        let mut log_level = config.observability.log_level.clone();
+        tokio::spawn(async move {
+            while let Ok(reloaded) = updates.recv().await {
+                if reloaded.observability.log_level != log_level {
+                    log_level = reloaded.observability.log_level;
+                    apply_log_level(&log_filter, &log_level);
+                }
+            }
+        });
+    }
+
     // TODO: Initialize and start the application
     info!(
         "Conflux server starting on {}:{}",
         config.server.host, config.server.port
     );
+    let protocol_manager = ProtocolManager::new();
 
     // Keep the application running
     tokio::signal::ctrl_c().await?;
     info!("Shutting down Conflux server");
 
+    for result in protocol_manager.shutdown_all(PROTOCOL_SHUTDOWN_TIMEOUT).await {
+        if let Err(e) = result {
+            warn!("Protocol plugin task ended abnormally: {}", e);
+        }
+    }
+
     Ok(())
 }
 
-fn init_tracing() -> Result<()> {
+fn init_tracing() -> Result<LogFilterHandle> {
+    let (filter, handle) = reload::Layer::new(
+        EnvFilter::try_from_default_env()
+            .unwrap_or_else(|_| "conflux=debug,tower_http=debug".into()),
+    );
     tracing_subscriber::registry()
-        .with(
-            tracing_subscriber::EnvFilter::try_from_default_env()
-                .unwrap_or_else(|_| "conflux=debug,tower_http=debug".into()),
-        )
+        .with(filter)
         .with(tracing_subscriber::fmt::layer())
         .init();
 
-    Ok(())
+    Ok(handle)
+}
+
+fn apply_log_level(handle: &LogFilterHandle, log_level: &str) {
+    match EnvFilter::try_new(log_level) {
+        Ok(filter) => match handle.reload(filter) {
+            Ok(()) => info!("Log level reloaded: {}", log_level),
+            Err(e) => warn!("Failed to reload log level: {}", e),
+        },
+        Err(e) => warn!("Invalid log level {}: {}", log_level, e),
+    }
 }
@@ -0,0 +1,261 @@
+//! 发布审批HTTP处理器
+//!
+//! 需要审批的命名空间中，版本发布前必须由申请人以外的用户批准
+
+use super::namespace_handlers::require_namespace_permission;
+use super::{write_error_status, ApprovalPolicyRequest, AppState};
+use crate::auth::{actions, AuthContext};
+use crate::raft::types::{ClientWriteResponse, Config, ConfigNamespace, RaftCommand};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Json,
+    Extension,
+};
+use serde_json::{json, Value};
+use tracing::{error, info, warn};
+
+/// 根据路径参数查找配置并检查命名空间权限
+///
+/// # Arguments
+/// * `app_state` - 应用状态
+/// * `auth_ctx` - 认证上下文
+/// * `namespace` - 配置所在命名空间
+/// * `name` - 配置名称
+/// * `action` - 需要的操作权限
+///
+/// # Returns
+/// 找到配置且有权限时返回配置，否则返回对应的HTTP状态码
+async fn authorized_config(
+    app_state: &AppState,
+    auth_ctx: &AuthContext,
+    namespace: &ConfigNamespace,
+    name: &str,
+    action: &str,
+) -> Result<Config, StatusCode> {
+    require_namespace_permission(app_state, auth_ctx, namespace, action).await?;
+    app_state
+        .core_handle
+        .store()
+        .get_config(namespace, name)
+        .await
+        .ok_or_else(|| {
+            warn!("Config not found: {}/{}", namespace, name);
+            StatusCode::NOT_FOUND
+        })
+}
+
+/// 将认证用户ID解析为审批记录使用的数字ID
+///
+/// # Returns
+/// 用户ID不是数字时返回400
+fn numeric_user_id(auth_ctx: &AuthContext) -> Result<u64, StatusCode> {
+    auth_ctx.user_id.parse().map_err(|_| {
+        warn!("User ID {} is not numeric and cannot take part in approvals", auth_ctx.user_id);
+        StatusCode::BAD_REQUEST
+    })
+}
+
+/// 通过Raft提交审批命令，失败的写入响应映射为HTTP状态码
+///
+/// 审批自己的申请返回403，配置或版本不存在返回404，审批状态冲突返回409，其他失败返回400
+///
+/// # Arguments
+/// * `app_state` - 应用状态
+/// * `auth_ctx` - 请求者的认证上下文，用于速率限制
+/// * `command` - 要提交的Raft命令
+async fn submit(
+    app_state: &AppState,
+    auth_ctx: &AuthContext,
+    command: RaftCommand,
+) -> Result<ClientWriteResponse, StatusCode> {
+    match app_state.core_handle.write(command, Some(auth_ctx)).await {
+        Ok(response) if response.success => Ok(response),
+        Ok(response) => {
+            warn!("Approval command rejected: {}", response.message);
+            let message = response.message.as_str();
+            if message.contains("own approval request") {
+                Err(StatusCode::FORBIDDEN)
+            } else if message.contains("does not exist") || message.contains("not found") {
+                Err(StatusCode::NOT_FOUND)
+            } else if message.contains("pending") {
+                Err(StatusCode::CONFLICT)
+            } else {
+                Err(StatusCode::BAD_REQUEST)
+            }
+        }
+        Err(e) => {
+            error!("Failed to submit approval command: {}", e);
+            Err(write_error_status(&e))
+        }
+    }
+}
+
+/// 申请发布审批处理器
+/// POST /api/v1/configs/{tenant}/{app}/{env}/{name}/versions/{version_id}/request-approval
+///
+/// 需要命名空间写权限，同一版本同时只能有一个待审批的申请
+pub async fn request_approval_handler(
+    Path((tenant, app, env, name, version_id)): Path<(String, String, String, String, u64)>,
+    State(app_state): State<AppState>,
+    auth_ctx: Option<Extension<AuthContext>>,
+) -> Result<Json<Value>, StatusCode> {
+    let auth_ctx = auth_ctx.ok_or(StatusCode::UNAUTHORIZED)?;
+    let namespace = ConfigNamespace { tenant, app, env };
+    let config = authorized_config(&app_state, &auth_ctx, &namespace, &name, actions::WRITE).await?;
+    let requested_by = numeric_user_id(&auth_ctx)?;
+
+    info!("User {} requests approval for version {} of config {}", requested_by, version_id, config.id);
+    let response = submit(
+        &app_state,
+        &auth_ctx,
+        RaftCommand::RequestReleaseApproval {
+            config_id: config.id,
+            version_id,
+            requested_by,
+        },
+    )
+    .await?;
+
+    Ok(Json(json!({
+        "success": true,
+        "data": response.data,
+        "message": response.message
+    })))
+}
+
+/// 批准发布处理器
+/// POST /api/v1/configs/{tenant}/{app}/{env}/{name}/versions/{version_id}/approve
+///
+/// 需要命名空间管理权限，申请人不能批准自己的申请
+pub async fn approve_release_handler(
+    Path((tenant, app, env, name, version_id)): Path<(String, String, String, String, u64)>,
+    State(app_state): State<AppState>,
+    auth_ctx: Option<Extension<AuthContext>>,
+) -> Result<Json<Value>, StatusCode> {
+    let namespace = ConfigNamespace { tenant, app, env };
+    review_release(&app_state, auth_ctx, &namespace, &name, version_id, true).await
+}
+
+/// 驳回发布处理器
+/// POST /api/v1/configs/{tenant}/{app}/{env}/{name}/versions/{version_id}/reject
+///
+/// 需要命名空间管理权限，申请人不能驳回自己的申请
+pub async fn reject_release_handler(
+    Path((tenant, app, env, name, version_id)): Path<(String, String, String, String, u64)>,
+    State(app_state): State<AppState>,
+    auth_ctx: Option<Extension<AuthContext>>,
+) -> Result<Json<Value>, StatusCode> {
+    let namespace = ConfigNamespace { tenant, app, env };
+    review_release(&app_state, auth_ctx, &namespace, &name, version_id, false).await
+}
+
+/// 批准或驳回版本的待审批申请
+async fn review_release(
+    app_state: &AppState,
+    auth_ctx: Option<Extension<AuthContext>>,
+    namespace: &ConfigNamespace,
+    name: &str,
+    version_id: u64,
+    approve: bool,
+) -> Result<Json<Value>, StatusCode> {
+    let auth_ctx = auth_ctx.ok_or(StatusCode::UNAUTHORIZED)?;
+    let config = authorized_config(app_state, &auth_ctx, namespace, name, actions::ADMIN).await?;
+    let approver_id = numeric_user_id(&auth_ctx)?;
+
+    let config_id = config.id;
+    let command = if approve {
+        RaftCommand::ApproveRelease { config_id, version_id, approver_id }
+    } else {
+        RaftCommand::RejectRelease { config_id, version_id, approver_id }
+    };
+    info!(
+        "User {} {} version {} of config {}",
+        approver_id,
+        if approve { "approves" } else { "rejects" },
+        version_id,
+        config_id
+    );
+    let response = submit(app_state, &auth_ctx, command).await?;
+
+    Ok(Json(json!({
+        "success": true,
+        "data": response.data,
+        "message": response.message
+    })))
+}
+
+/// 查询配置审批记录处理器
+/// GET /api/v1/configs/{tenant}/{app}/{env}/{name}/approvals
+pub async fn list_approvals_handler(
+    Path((tenant, app, env, name)): Path<(String, String, String, String)>,
+    State(app_state): State<AppState>,
+    auth_ctx: Option<Extension<AuthContext>>,
+) -> Result<Json<Value>, StatusCode> {
+    let auth_ctx = auth_ctx.ok_or(StatusCode::UNAUTHORIZED)?;
+    let namespace = ConfigNamespace { tenant, app, env };
+    let config = authorized_config(&app_state, &auth_ctx, &namespace, &name, actions::READ).await?;
+    let store = app_state.core_handle.store();
+
+    Ok(Json(json!({
+        "config_id": config.id,
+        "requires_approval": store.is_approval_required(&namespace),
+        "approvals": store.get_approvals(config.id).await
+    })))
+}
+
+/// 查询命名空间审批策略处理器
+/// GET /api/v1/namespaces/{tenant}/{app}/{env}/approval-policy
+pub async fn get_approval_policy_handler(
+    Path((tenant, app, env)): Path<(String, String, String)>,
+    State(app_state): State<AppState>,
+    auth_ctx: Option<Extension<AuthContext>>,
+) -> Result<Json<Value>, StatusCode> {
+    let auth_ctx = auth_ctx.ok_or(StatusCode::UNAUTHORIZED)?;
+    let namespace = ConfigNamespace { tenant, app, env };
+    require_namespace_permission(&app_state, &auth_ctx, &namespace, actions::READ).await?;
+
+    Ok(Json(json!({
+        "namespace": namespace,
+        "requires_approval": app_state.core_handle.store().is_approval_required(&namespace)
+    })))
+}
+
+/// 设置命名空间审批策略处理器
+/// PUT /api/v1/namespaces/{tenant}/{app}/{env}/approval-policy
+///
+/// 需要命名空间管理权限
+pub async fn set_approval_policy_handler(
+    Path((tenant, app, env)): Path<(String, String, String)>,
+    State(app_state): State<AppState>,
+    auth_ctx: Option<Extension<AuthContext>>,
+    Json(request): Json<ApprovalPolicyRequest>,
+) -> Result<Json<Value>, StatusCode> {
+    let auth_ctx = auth_ctx.ok_or(StatusCode::UNAUTHORIZED)?;
+    let namespace = ConfigNamespace { tenant, app, env };
+    require_namespace_permission(&app_state, &auth_ctx, &namespace, actions::ADMIN).await?;
+
+    info!(
+        "User {} sets release approval of {} to {}",
+        auth_ctx.user_id, namespace, request.requires_approval
+    );
+    let response = submit(
+        &app_state,
+        &auth_ctx,
+        RaftCommand::SetApprovalRequired {
+            namespace,
+            required: request.requires_approval,
+        },
+    )
+    .await?;
+
+    Ok(Json(json!({
+        "success": true,
+        "data": response.data,
+        "message": response.message
+    })))
+}
+
+#[cfg(test)]
+#[path = "approval_handlers_tests.rs"]
+mod tests;
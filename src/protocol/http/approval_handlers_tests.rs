@@ -0,0 +1,205 @@
+use super::*;
+use crate::auth::{roles, AuthzService};
+use crate::protocol::http::test_support::app_state;
+use crate::raft::client::helpers::create_write_request;
+use crate::raft::client::RaftClient;
+use crate::raft::node::test_support::app_config;
+use crate::raft::node::{NodeConfig, RaftNode};
+use crate::raft::store::APPROVAL_REQUIRED;
+use crate::raft::types::ConfigFormat;
+use std::sync::Arc;
+use std::time::Duration;
+use tempfile::TempDir;
+use tokio::sync::RwLock;
+
+/// 启动单节点Raft并创建带两个版本的配置
+///
+/// 用户1是开发者（读写权限），用户2是租户管理员（读写和管理权限）
+async fn create_app_state(temp_dir: &TempDir) -> AppState {
+    let mut node = RaftNode::new(NodeConfig::default(), &app_config(temp_dir)).await.unwrap();
+    node.start().await.unwrap();
+    node.wait_for_leadership(Duration::from_secs(5)).await.unwrap();
+    let store = node.store();
+    let raft_client = RaftClient::new_with_raft_node(store.clone(), Arc::new(RwLock::new(node)));
+
+    let response = raft_client
+        .write(create_write_request(RaftCommand::CreateConfig {
+            namespace: namespace(),
+            name: "app.json".to_string(),
+            content: b"{}".to_vec(),
+            format: ConfigFormat::Json,
+            schema: None,
+            creator_id: 1,
+            description: "initial".to_string(),
+        }))
+        .await
+        .unwrap();
+    raft_client
+        .write(create_write_request(RaftCommand::CreateVersion {
+            config_id: response.config_id.unwrap(),
+            content: br#"{"v":2}"#.to_vec(),
+            format: None,
+            creator_id: 1,
+            description: "second".to_string(),
+        }))
+        .await
+        .unwrap();
+
+    let authz_service = Arc::new(AuthzService::new_in_memory().await.unwrap());
+    for (role, action) in [
+        (roles::VIEWER, actions::READ),
+        (roles::DEVELOPER, actions::WRITE),
+        (roles::TENANT_ADMIN, actions::ADMIN),
+    ] {
+        authz_service
+            .add_permission_for_role(role, "acme", "/tenants/acme/*", action)
+            .await
+            .unwrap();
+    }
+    for role in [roles::VIEWER, roles::DEVELOPER] {
+        for user in ["1", "2"] {
+            authz_service.assign_role_to_user(user, role, "acme").await.unwrap();
+        }
+    }
+    authz_service
+        .assign_role_to_user("2", roles::TENANT_ADMIN, "acme")
+        .await
+        .unwrap();
+
+    app_state(raft_client, store, authz_service)
+}
+
+fn namespace() -> ConfigNamespace {
+    ConfigNamespace {
+        tenant: "acme".to_string(),
+        app: "web".to_string(),
+        env: "prod".to_string(),
+    }
+}
+
+fn user(id: &str) -> Option<Extension<AuthContext>> {
+    Some(Extension(AuthContext::new(id.to_string(), "acme".to_string())))
+}
+
+fn version_path(version_id: u64) -> Path<(String, String, String, String, u64)> {
+    Path((
+        "acme".to_string(),
+        "web".to_string(),
+        "prod".to_string(),
+        "app.json".to_string(),
+        version_id,
+    ))
+}
+
+async fn require_approval(app_state: &AppState) {
+    let Json(body) = set_approval_policy_handler(
+        Path(("acme".to_string(), "web".to_string(), "prod".to_string())),
+        State(app_state.clone()),
+        user("2"),
+        Json(ApprovalPolicyRequest { requires_approval: true }),
+    )
+    .await
+    .unwrap();
+    assert_eq!(body["data"]["requires_approval"], json!(true));
+}
+
+async fn release(app_state: &AppState, version_id: u64) -> ClientWriteResponse {
+    app_state
+        .core_handle
+        .raft_client()
+        .write(create_write_request(RaftCommand::ReleaseVersion {
+            config_id: 1,
+            version_id,
+        }))
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_approval_flow_unlocks_release() {
+    let temp_dir = TempDir::new().unwrap();
+    let app_state = create_app_state(&temp_dir).await;
+
+    // 只有管理员可以修改审批策略
+    let status = set_approval_policy_handler(
+        Path(("acme".to_string(), "web".to_string(), "prod".to_string())),
+        State(app_state.clone()),
+        user("1"),
+        Json(ApprovalPolicyRequest { requires_approval: true }),
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    require_approval(&app_state).await;
+
+    let response = release(&app_state, 2).await;
+    assert!(!response.success);
+    assert!(response.message.contains(APPROVAL_REQUIRED));
+
+    let Json(body) = request_approval_handler(version_path(2), State(app_state.clone()), user("1"))
+        .await
+        .unwrap();
+    assert_eq!(body["data"]["status"], json!("Pending"));
+    let status = request_approval_handler(version_path(2), State(app_state.clone()), user("1"))
+        .await
+        .unwrap_err();
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    // 开发者没有管理权限，管理员也不能批准自己的申请
+    let status = approve_release_handler(version_path(2), State(app_state.clone()), user("1"))
+        .await
+        .unwrap_err();
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let Json(body) = approve_release_handler(version_path(2), State(app_state.clone()), user("2"))
+        .await
+        .unwrap();
+    assert_eq!(body["data"]["status"], json!("Approved"));
+    assert_eq!(body["data"]["approver_id"], json!(2));
+
+    assert!(release(&app_state, 2).await.success);
+    // 批准只对申请的版本有效
+    assert!(!release(&app_state, 1).await.success);
+}
+
+#[tokio::test]
+async fn test_rejection_flow_keeps_release_blocked() {
+    let temp_dir = TempDir::new().unwrap();
+    let app_state = create_app_state(&temp_dir).await;
+    require_approval(&app_state).await;
+
+    // 没有待审批的申请时无法驳回
+    let status = reject_release_handler(version_path(2), State(app_state.clone()), user("2"))
+        .await
+        .unwrap_err();
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    let Json(body) = request_approval_handler(version_path(2), State(app_state.clone()), user("1"))
+        .await
+        .unwrap();
+    assert_eq!(body["data"]["status"], json!("Pending"));
+    let Json(body) = reject_release_handler(version_path(2), State(app_state.clone()), user("2"))
+        .await
+        .unwrap();
+    assert_eq!(body["data"]["status"], json!("Rejected"));
+
+    let response = release(&app_state, 2).await;
+    assert!(!response.success);
+    assert!(response.message.contains(APPROVAL_REQUIRED));
+
+    let Json(body) = list_approvals_handler(
+        Path(("acme".to_string(), "web".to_string(), "prod".to_string(), "app.json".to_string())),
+        State(app_state.clone()),
+        user("1"),
+    )
+    .await
+    .unwrap();
+    assert_eq!(body["requires_approval"], json!(true));
+    assert_eq!(body["approvals"][0]["status"], json!("Rejected"));
+
+    // 不存在的版本无法申请审批
+    let status = request_approval_handler(version_path(9), State(app_state), user("1"))
+        .await
+        .unwrap_err();
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
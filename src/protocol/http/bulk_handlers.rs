@@ -0,0 +1,150 @@
+//! 批量创建配置HTTP处理器
+//!
+//! 用于一次导入大量配置，所有配置作为一条Raft日志提交。与事务不同，
+//! 单个配置创建失败不会影响其余配置，失败的配置在响应中逐条列出
+
+use super::namespace_handlers::require_namespace_permission;
+use super::{write_error_status, AppState, BulkCreateConfigItem, MAX_BULK_CONFIG_ENTRIES};
+use crate::auth::{actions, AuthContext};
+use crate::raft::types::{BulkConfigEntry, ConfigFormat, ConfigNamespace, RaftCommand};
+use axum::{extract::State, http::StatusCode, response::Json, Extension};
+use base64::{prelude::BASE64_STANDARD, Engine};
+use serde_json::{json, Value};
+use std::collections::HashSet;
+use tracing::{error, info, warn};
+
+/// 批量创建配置处理器
+/// POST /api/v1/bulk/configs
+///
+/// 请求体为配置数组，最多 [`MAX_BULK_CONFIG_ENTRIES`] 个，大小不超过
+/// [`MAX_BULK_PAYLOAD_BYTES`](super::MAX_BULK_PAYLOAD_BYTES)（超过时返回413）。
+/// 调用者只能创建本租户的配置，并需要每个命名空间的写权限，否则整个请求返回403。
+/// 响应数据为 [`BulkCreateResponse`](crate::raft::types::BulkCreateResponse)，
+/// 已存在等原因未能创建的配置列在 `errors` 中
+pub async fn bulk_create_configs_handler(
+    State(app_state): State<AppState>,
+    auth_ctx: Option<Extension<AuthContext>>,
+    Json(items): Json<Vec<BulkCreateConfigItem>>,
+) -> Result<Json<Value>, StatusCode> {
+    let auth_ctx = auth_ctx.ok_or(StatusCode::UNAUTHORIZED)?;
+    if items.is_empty() || items.len() > MAX_BULK_CONFIG_ENTRIES {
+        warn!(
+            "Bulk create of {} configs is outside 1..={}",
+            items.len(),
+            MAX_BULK_CONFIG_ENTRIES
+        );
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let creator_id = auth_ctx.user_id.parse().unwrap_or(0);
+    let mut checked = HashSet::new();
+    let mut entries = Vec::with_capacity(items.len());
+    for item in items {
+        let namespace = ConfigNamespace {
+            tenant: item.tenant,
+            app: item.app,
+            env: item.env,
+        };
+        if namespace.tenant != auth_ctx.tenant_id {
+            warn!(
+                "Tenant isolation violation: user {} of tenant {} bulk created {}/{}",
+                auth_ctx.user_id, auth_ctx.tenant_id, namespace, item.name
+            );
+            return Err(StatusCode::FORBIDDEN);
+        }
+        if checked.insert(namespace.clone()) {
+            require_namespace_permission(&app_state, &auth_ctx, &namespace, actions::WRITE)
+                .await?;
+        }
+
+        let content = if item.format == ConfigFormat::Binary {
+            BASE64_STANDARD.decode(&item.content).map_err(|e| {
+                warn!("Binary content of {}/{} is not valid base64: {}", namespace, item.name, e);
+                StatusCode::BAD_REQUEST
+            })?
+        } else {
+            item.content.into_bytes()
+        };
+        entries.push(BulkConfigEntry {
+            namespace,
+            name: item.name,
+            content,
+            format: item.format,
+            schema: item.schema,
+            creator_id,
+            description: item
+                .description
+                .unwrap_or_else(|| "Created via bulk import".to_string()),
+        });
+    }
+
+    info!("User {} bulk creates {} configs", auth_ctx.user_id, entries.len());
+    match app_state
+        .core_handle
+        .write(RaftCommand::BulkCreateConfigs { entries }, Some(&auth_ctx))
+        .await
+    {
+        Ok(response) if response.success => Ok(Json(json!({
+            "success": true,
+            "data": response.data,
+            "message": response.message
+        }))),
+        Ok(response) => {
+            warn!("Bulk create rejected: {}", response.message);
+            Err(StatusCode::BAD_REQUEST)
+        }
+        Err(e) => {
+            error!("Failed to submit bulk create: {}", e);
+            Err(write_error_status(&e))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::http::test_support::{authz_with_writer, local_app_state};
+    use crate::raft::store::Store;
+    use std::sync::Arc;
+    use tempfile::TempDir;
+
+    fn item(tenant: &str) -> BulkCreateConfigItem {
+        BulkCreateConfigItem {
+            tenant: tenant.to_string(),
+            app: "app".to_string(),
+            env: "prod".to_string(),
+            name: "app.json".to_string(),
+            content: "{}".to_string(),
+            format: ConfigFormat::Json,
+            schema: None,
+            description: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_bulk_create_rejects_other_tenant_configs() {
+        let temp_dir = TempDir::new().unwrap();
+        let (store, _rx) = Store::new(temp_dir.path()).await.unwrap();
+        let store = Arc::new(store);
+        let app_state = local_app_state(store.clone(), authz_with_writer(&["acme", "other"]).await);
+        let auth_ctx = AuthContext::new("writer".to_string(), "acme".to_string());
+
+        // 即使用户在另一个租户中也有写权限，请求体中的其他租户配置仍使整个请求被拒绝
+        let status = bulk_create_configs_handler(
+            State(app_state),
+            Some(Extension(auth_ctx)),
+            Json(vec![item("acme"), item("other")]),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        for tenant in ["acme", "other"] {
+            let namespace = ConfigNamespace {
+                tenant: tenant.to_string(),
+                app: "app".to_string(),
+                env: "prod".to_string(),
+            };
+            assert!(store.get_config(&namespace, "app.json").await.is_none());
+        }
+    }
+}
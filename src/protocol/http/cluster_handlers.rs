@@ -0,0 +1,540 @@
+//! 集群运维HTTP处理器
+//!
+//! 提供需要集群管理员权限的运维端点，例如手动日志压缩、快照信息查询、领导权移交、节点下线、
+//! 选举优先级设置、节点间的选举触发与优先级转发、死信队列查询、存储一致性检查、内容去重统计、RocksDB压缩与刷盘、对等节点连接状态、集群指标汇总和集群事件流
+
+use super::{
+    AppState, CompactStorageQuery, ConsistencyCheckQuery, SetNodePriorityRequest,
+    TransferLeadershipRequest, UpdatePeerAddressRequest,
+};
+use crate::auth::{actions, AuthContext, ResourcePath};
+use crate::raft::client::DeadLetterQueue;
+use crate::raft::metrics::AggregatedClusterMetrics;
+use crate::raft::network::NodePriorityUpdate;
+use crate::raft::node::update_node_priority;
+use crate::raft::StateSummary;
+use crate::raft::store::{StorageStats, Store};
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        Json,
+    },
+    Extension,
+};
+use serde_json::{json, Value};
+use std::convert::Infallible;
+use tokio_stream::{
+    wrappers::{errors::BroadcastStreamRecvError, BroadcastStream},
+    Stream, StreamExt,
+};
+use tracing::{error, info, warn};
+
+/// 检查请求者是否拥有集群管理员权限
+///
+/// # Arguments
+/// * `app_state` - 应用状态
+/// * `auth_ctx` - 授权中间件注入的认证上下文
+///
+/// # Returns
+/// 有权限时返回Ok(())，否则返回对应的HTTP状态码
+pub async fn require_cluster_admin(
+    app_state: &AppState,
+    auth_ctx: Option<&AuthContext>,
+) -> Result<(), StatusCode> {
+    let auth_ctx = auth_ctx.ok_or(StatusCode::UNAUTHORIZED)?;
+    let resource = ResourcePath::cluster(&auth_ctx.tenant_id);
+
+    match app_state
+        .core_handle
+        .authz_service()
+        .check(&auth_ctx.user_id, &auth_ctx.tenant_id, &resource, actions::CLUSTER_ADMIN)
+        .await
+    {
+        Ok(true) => Ok(()),
+        Ok(false) => {
+            warn!("Cluster admin permission denied for user {}", auth_ctx.user_id);
+            Err(StatusCode::FORBIDDEN)
+        }
+        Err(e) => {
+            error!("Cluster admin permission check failed: {}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// 手动触发日志压缩处理器
+/// POST /_cluster/compact
+pub async fn compact_handler(
+    State(app_state): State<AppState>,
+    auth_ctx: Option<Extension<AuthContext>>,
+) -> Result<Json<Value>, StatusCode> {
+    require_cluster_admin(&app_state, auth_ctx.as_deref()).await?;
+
+    let raft_node = app_state
+        .core_handle
+        .raft_client()
+        .raft_node()
+        .ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
+    let node = raft_node.read().await;
+
+    if let Err(e) = node.trigger_log_compaction().await {
+        error!("Log compaction failed: {}", e);
+        return Err(StatusCode::INTERNAL_SERVER_ERROR);
+    }
+
+    let snapshot = node.get_snapshot_info().await.map_err(|e| {
+        error!("Failed to get snapshot info after compaction: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    info!("Log compaction completed at index {}", snapshot.last_snapshot_index);
+    Ok(Json(json!({
+        "success": true,
+        "snapshot": snapshot
+    })))
+}
+
+/// 存储一致性检查处理器
+/// GET /_cluster/consistency-check
+///
+/// 比较内存状态与RocksDB，`?repair=true` 且发现不一致时从磁盘重新加载内存状态
+pub async fn consistency_check_handler(
+    Query(query): Query<ConsistencyCheckQuery>,
+    State(app_state): State<AppState>,
+    auth_ctx: Option<Extension<AuthContext>>,
+) -> Result<Json<Value>, StatusCode> {
+    require_cluster_admin(&app_state, auth_ctx.as_deref()).await?;
+
+    let store = app_state.core_handle.store();
+    let report = store.check_consistency().await.map_err(|e| {
+        error!("Consistency check failed: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let repaired = query.repair && !report.is_consistent;
+    if repaired {
+        warn!("Store diverged from disk, resynchronizing: {:?}", report);
+        store.resync_from_disk().await.map_err(|e| {
+            error!("Failed to resynchronize store from disk: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+    }
+
+    Ok(Json(json!({
+        "report": report,
+        "repaired": repaired
+    })))
+}
+
+/// 快照信息处理器
+/// GET /_cluster/snapshot-info
+pub async fn snapshot_info_handler(
+    State(app_state): State<AppState>,
+    auth_ctx: Option<Extension<AuthContext>>,
+) -> Result<Json<Value>, StatusCode> {
+    require_cluster_admin(&app_state, auth_ctx.as_deref()).await?;
+
+    let raft_node = app_state
+        .core_handle
+        .raft_client()
+        .raft_node()
+        .ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
+    let node = raft_node.read().await;
+
+    match node.get_snapshot_info().await {
+        Ok(info) => Ok(Json(json!(info))),
+        Err(e) => {
+            error!("Failed to get snapshot info: {}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// 状态机检查处理器
+/// GET /_cluster/state-machine
+///
+/// 返回本节点状态机已应用的配置数、版本数和最后应用的日志索引，无需重放日志
+pub async fn state_machine_handler(
+    State(app_state): State<AppState>,
+    auth_ctx: Option<Extension<AuthContext>>,
+) -> Result<Json<StateSummary>, StatusCode> {
+    require_cluster_admin(&app_state, auth_ctx.as_deref()).await?;
+
+    let raft_node = app_state
+        .core_handle
+        .raft_client()
+        .raft_node()
+        .ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
+    let node = raft_node.read().await;
+    let state_machine = node.state_machine().ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
+
+    Ok(Json(state_machine.dump_state_summary().await))
+}
+
+/// 内容去重统计处理器
+/// GET /_cluster/storage/dedup-stats
+///
+/// 返回本节点存储中不同内容的数量、版本总数以及去重比例
+pub async fn dedup_stats_handler(
+    State(app_state): State<AppState>,
+    auth_ctx: Option<Extension<AuthContext>>,
+) -> Result<Json<Value>, StatusCode> {
+    require_cluster_admin(&app_state, auth_ctx.as_deref()).await?;
+
+    match app_state.core_handle.store().dedup_stats().await {
+        Ok(stats) => Ok(Json(json!(stats))),
+        Err(e) => {
+            error!("Failed to compute dedup stats: {}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// RocksDB手动压缩处理器
+/// POST /_cluster/storage/compact
+///
+/// 压缩 `?cf=` 指定的列族（默认全部列族），返回压缩前后的存储统计
+pub async fn compact_storage_handler(
+    Query(query): Query<CompactStorageQuery>,
+    State(app_state): State<AppState>,
+    auth_ctx: Option<Extension<AuthContext>>,
+) -> Result<Json<Value>, StatusCode> {
+    require_cluster_admin(&app_state, auth_ctx.as_deref()).await?;
+
+    let store = app_state.core_handle.store();
+    let before = storage_stats(store).await?;
+    store.compact_range(query.cf.as_deref()).await.map_err(|e| {
+        error!("Storage compaction failed: {}", e);
+        e.status_code()
+    })?;
+    let after = storage_stats(store).await?;
+
+    info!(
+        "Compacted column family {} ({} -> {} SST bytes)",
+        query.cf.as_deref().unwrap_or("*"),
+        before.sst_size_bytes,
+        after.sst_size_bytes
+    );
+    Ok(Json(json!({
+        "success": true,
+        "column_family": query.cf,
+        "before": before,
+        "after": after
+    })))
+}
+
+/// RocksDB手动刷盘处理器
+/// POST /_cluster/storage/flush
+///
+/// 将内存表刷写到磁盘，返回刷盘前后的存储统计
+pub async fn flush_storage_handler(
+    State(app_state): State<AppState>,
+    auth_ctx: Option<Extension<AuthContext>>,
+) -> Result<Json<Value>, StatusCode> {
+    require_cluster_admin(&app_state, auth_ctx.as_deref()).await?;
+
+    let store = app_state.core_handle.store();
+    let before = storage_stats(store).await?;
+    store.flush_to_disk().await.map_err(|e| {
+        error!("Storage flush failed: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    let after = storage_stats(store).await?;
+
+    Ok(Json(json!({
+        "success": true,
+        "before": before,
+        "after": after
+    })))
+}
+
+async fn storage_stats(store: &Store) -> Result<StorageStats, StatusCode> {
+    store.get_storage_stats().await.map_err(|e| {
+        error!("Failed to get storage stats: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })
+}
+
+/// 对等节点连接状态处理器
+/// GET /_cluster/peers
+///
+/// 返回本节点到各对等节点的连接池状态、请求统计和心跳判定的节点状态
+pub async fn peers_handler(
+    State(app_state): State<AppState>,
+    auth_ctx: Option<Extension<AuthContext>>,
+) -> Result<Json<Value>, StatusCode> {
+    require_cluster_admin(&app_state, auth_ctx.as_deref()).await?;
+
+    let raft_node = app_state
+        .core_handle
+        .raft_client()
+        .raft_node()
+        .ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
+    let node = raft_node.read().await;
+
+    Ok(Json(json!({
+        "node_id": node.node_id(),
+        "peers": node.peer_connections().await
+    })))
+}
+
+/// 集群指标汇总处理器
+/// GET /_cluster/metrics
+///
+/// 只能在领导者上调用，返回各节点的任期、应用延迟、请求速率和错误率以及集群总计；
+/// 无法拉取指标的节点标记为 `unknown`
+pub async fn cluster_metrics_handler(
+    State(app_state): State<AppState>,
+    auth_ctx: Option<Extension<AuthContext>>,
+) -> Result<Json<AggregatedClusterMetrics>, StatusCode> {
+    require_cluster_admin(&app_state, auth_ctx.as_deref()).await?;
+
+    let raft_node = app_state
+        .core_handle
+        .raft_client()
+        .raft_node()
+        .ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
+    let node = raft_node.read().await;
+
+    node.aggregate_cluster_metrics().await.map(Json).map_err(|e| {
+        error!("Failed to aggregate cluster metrics: {}", e);
+        e.status_code()
+    })
+}
+
+/// 领导权移交处理器
+/// POST /_cluster/transfer-leadership
+///
+/// 请求体可选，`{"target": N}` 指定目标节点，省略时自动选择跟随者
+pub async fn transfer_leadership_handler(
+    State(app_state): State<AppState>,
+    auth_ctx: Option<Extension<AuthContext>>,
+    request: Option<Json<TransferLeadershipRequest>>,
+) -> Result<Json<Value>, StatusCode> {
+    require_cluster_admin(&app_state, auth_ctx.as_deref()).await?;
+    let target = request.and_then(|Json(request)| request.target);
+
+    let raft_node = app_state
+        .core_handle
+        .raft_client()
+        .raft_node()
+        .ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
+    let node = raft_node.read().await;
+
+    if let Err(e) = node.transfer_leadership(target).await {
+        error!("Leadership transfer failed: {}", e);
+        return Err(StatusCode::INTERNAL_SERVER_ERROR);
+    }
+
+    let leader = node.get_leader().await;
+    info!("Leadership transferred from node {} to {:?}", node.node_id(), leader);
+    Ok(Json(json!({
+        "success": true,
+        "previous_leader": node.node_id(),
+        "current_leader": leader
+    })))
+}
+
+/// 节点下线处理器
+/// DELETE /_cluster/nodes/{node_id}/decommission
+pub async fn decommission_node_handler(
+    Path(node_id): Path<u64>,
+    State(app_state): State<AppState>,
+    auth_ctx: Option<Extension<AuthContext>>,
+) -> Result<Json<Value>, StatusCode> {
+    require_cluster_admin(&app_state, auth_ctx.as_deref()).await?;
+
+    let raft_node = app_state
+        .core_handle
+        .raft_client()
+        .raft_node()
+        .ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
+    let node = raft_node.read().await;
+
+    if let Err(e) = node.decommission_node(node_id).await {
+        error!("Failed to decommission node {}: {}", node_id, e);
+        return Err(StatusCode::INTERNAL_SERVER_ERROR);
+    }
+
+    info!("Node {} decommissioned", node_id);
+    Ok(Json(json!({
+        "success": true,
+        "node_id": node_id,
+        "members": node.get_members().await
+    })))
+}
+
+/// 节点选举优先级设置处理器
+/// PUT /_cluster/nodes/{node_id}/priority
+///
+/// 请求体为 `{"priority": N}`，优先级写入集群成员元数据，领导者随后会将领导权移交给日志已追平的更高优先级节点
+pub async fn set_node_priority_handler(
+    Path(node_id): Path<u64>,
+    State(app_state): State<AppState>,
+    auth_ctx: Option<Extension<AuthContext>>,
+    Json(request): Json<SetNodePriorityRequest>,
+) -> Result<Json<Value>, StatusCode> {
+    require_cluster_admin(&app_state, auth_ctx.as_deref()).await?;
+
+    let raft_node = app_state
+        .core_handle
+        .raft_client()
+        .raft_node()
+        .ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
+    let node = raft_node.read().await;
+
+    if let Err(e) = node.set_node_priority(node_id, request.priority).await {
+        error!("Failed to set election priority of node {}: {}", node_id, e);
+        return Err(StatusCode::INTERNAL_SERVER_ERROR);
+    }
+
+    info!("Election priority of node {} set to {}", node_id, request.priority);
+    Ok(Json(json!({
+        "success": true,
+        "node_id": node_id,
+        "priority": request.priority,
+        "priorities": node.node_priorities().await
+    })))
+}
+
+/// 立即发起选举处理器
+/// POST /_cluster/trigger-elect
+///
+/// 由移交领导权的节点调用，本节点以更高的任期参选，现任领导者看到新的投票后退位
+pub async fn trigger_elect_handler(
+    State(app_state): State<AppState>,
+    auth_ctx: Option<Extension<AuthContext>>,
+) -> Result<StatusCode, StatusCode> {
+    require_cluster_admin(&app_state, auth_ctx.as_deref()).await?;
+
+    let raft_node = app_state
+        .core_handle
+        .raft_client()
+        .raft_node()
+        .ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
+    let node = raft_node.read().await;
+    let raft = node.get_raft().ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
+
+    raft.trigger().elect().await.map_err(|e| {
+        error!("Failed to trigger election: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    info!("Node {} started an election on request", node.node_id());
+    Ok(StatusCode::OK)
+}
+
+/// 节点选举优先级更新处理器
+/// POST /_cluster/node-priority
+///
+/// 由非领导者节点转发选举优先级设置时调用，本节点须为领导者
+pub async fn node_priority_handler(
+    State(app_state): State<AppState>,
+    auth_ctx: Option<Extension<AuthContext>>,
+    Json(update): Json<NodePriorityUpdate>,
+) -> Result<StatusCode, StatusCode> {
+    require_cluster_admin(&app_state, auth_ctx.as_deref()).await?;
+
+    let raft_node = app_state
+        .core_handle
+        .raft_client()
+        .raft_node()
+        .ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
+    let node = raft_node.read().await;
+    let raft = node.get_raft().ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
+
+    update_node_priority(raft, update.node_id, update.priority)
+        .await
+        .map_err(|e| {
+            error!("Failed to set election priority of node {}: {}", update.node_id, e);
+            e.status_code()
+        })?;
+    Ok(StatusCode::OK)
+}
+
+/// 对等节点地址更新处理器
+/// PUT /_cluster/nodes/{node_id}/address
+///
+/// 请求体为 `{"address": "ip:port"}`，只更新处理请求的节点的地址表，发往该节点的下一次RPC即使用新地址。
+/// 地址无效或目标是本节点时返回400
+pub async fn update_peer_address_handler(
+    Path(node_id): Path<u64>,
+    State(app_state): State<AppState>,
+    auth_ctx: Option<Extension<AuthContext>>,
+    Json(request): Json<UpdatePeerAddressRequest>,
+) -> Result<Json<Value>, StatusCode> {
+    require_cluster_admin(&app_state, auth_ctx.as_deref()).await?;
+
+    let raft_node = app_state
+        .core_handle
+        .raft_client()
+        .raft_node()
+        .ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
+    let node = raft_node.read().await;
+
+    if let Err(e) = node.update_peer_address(node_id, request.address.clone()).await {
+        warn!("Failed to update address of node {}: {}", node_id, e);
+        return Err(e.status_code());
+    }
+
+    info!("Address of node {} updated to {}", node_id, request.address);
+    Ok(Json(json!({
+        "success": true,
+        "node_id": node_id,
+        "address": request.address
+    })))
+}
+
+/// 死信队列查询处理器
+/// GET /_cluster/dead-letters
+///
+/// 返回未能通过共识提交的写请求，不会将其从队列中移除
+pub async fn dead_letters_handler(
+    State(app_state): State<AppState>,
+    auth_ctx: Option<Extension<AuthContext>>,
+) -> Result<Json<Value>, StatusCode> {
+    require_cluster_admin(&app_state, auth_ctx.as_deref()).await?;
+
+    let entries = app_state.core_handle.raft_client().dead_letters().summaries().await;
+    Ok(Json(json!({
+        "count": entries.len(),
+        "total": DeadLetterQueue::total_count(),
+        "entries": entries
+    })))
+}
+
+/// 集群事件流处理器
+/// GET /_cluster/events/stream
+///
+/// 以SSE推送本节点观察到的集群事件（领导权变更、成员变更、日志压缩），
+/// 事件名为事件类型，数据为事件的JSON表示；订阅者落后过多时跳过丢失的事件
+pub async fn cluster_events_stream_handler(
+    State(app_state): State<AppState>,
+    auth_ctx: Option<Extension<AuthContext>>,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, StatusCode> {
+    require_cluster_admin(&app_state, auth_ctx.as_deref()).await?;
+
+    let raft_node = app_state
+        .core_handle
+        .raft_client()
+        .raft_node()
+        .ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
+    let receiver = raft_node.read().await.subscribe_events();
+
+    let stream = BroadcastStream::new(receiver).filter_map(|result| match result {
+        Ok(event) => match Event::default().event(event.kind.as_str()).json_data(&event) {
+            Ok(sse_event) => Some(Ok(sse_event)),
+            Err(e) => {
+                error!("Failed to encode cluster event: {}", e);
+                None
+            }
+        },
+        Err(BroadcastStreamRecvError::Lagged(skipped)) => {
+            warn!("Cluster event stream lagged, skipped {} events", skipped);
+            None
+        }
+    });
+
+    Ok(Sse::new(stream).keep_alive(KeepAlive::default()))
+}
@@ -0,0 +1,311 @@
+//! 配置依赖HTTP处理器
+//!
+//! 提供配置依赖关系的查询与维护，以及支持级联的配置回滚端点
+
+use super::{write_error_status, AddDependencyRequest, AppState, RollbackRequest};
+use crate::auth::AuthContext;
+use crate::error::ConfluxError;
+use crate::raft::store::APPROVAL_REQUIRED;
+use crate::raft::types::{ClientWriteResponse, Config, ConfigNamespace, RaftCommand};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Json,
+    Extension,
+};
+use serde_json::{json, Value};
+use tracing::{error, info, warn};
+
+/// 根据路径参数查找配置
+///
+/// # Arguments
+/// * `app_state` - 应用状态
+/// * `tenant`, `app`, `env`, `name` - 配置路径
+///
+/// # Returns
+/// 找到时返回配置，否则返回404
+async fn find_config(
+    app_state: &AppState,
+    tenant: String,
+    app: String,
+    env: String,
+    name: &str,
+) -> Result<Config, StatusCode> {
+    let namespace = ConfigNamespace { tenant, app, env };
+    app_state
+        .core_handle
+        .store()
+        .get_config(&namespace, name)
+        .await
+        .ok_or_else(|| {
+            warn!(
+                "Config not found: {}/{}/{}/{}",
+                namespace.tenant, namespace.app, namespace.env, name
+            );
+            StatusCode::NOT_FOUND
+        })
+}
+
+/// 通过Raft提交命令，失败的写入响应映射为HTTP状态码
+///
+/// 依赖成环返回409，配置不存在返回404，版本发布未经审批返回428，其他失败返回400
+///
+/// # Arguments
+/// * `app_state` - 应用状态
+/// * `auth_ctx` - 请求者的认证上下文，用于速率限制
+/// * `command` - 要提交的Raft命令
+async fn submit(
+    app_state: &AppState,
+    auth_ctx: Option<&AuthContext>,
+    command: RaftCommand,
+) -> Result<ClientWriteResponse, StatusCode> {
+    match app_state.core_handle.write(command, auth_ctx).await {
+        Ok(response) if response.success => Ok(response),
+        Ok(response) => {
+            warn!("Dependency command rejected: {}", response.message);
+            if response.message.contains("cycle") {
+                Err(StatusCode::CONFLICT)
+            } else if response.message.contains("not found") {
+                Err(StatusCode::NOT_FOUND)
+            } else if response.message.contains(APPROVAL_REQUIRED) {
+                Err(StatusCode::PRECONDITION_REQUIRED)
+            } else {
+                Err(StatusCode::BAD_REQUEST)
+            }
+        }
+        Err(e) => {
+            error!("Failed to submit command: {}", e);
+            Err(write_error_status(&e))
+        }
+    }
+}
+
+/// 查询配置依赖处理器
+/// GET /api/v1/configs/{tenant}/{app}/{env}/{name}/dependencies
+///
+/// 返回直接依赖、直接被依赖的配置，以及包含所有传递依赖的部署顺序（被依赖者在前）
+pub async fn get_dependencies_handler(
+    Path((tenant, app, env, name)): Path<(String, String, String, String)>,
+    State(app_state): State<AppState>,
+) -> Result<Json<Value>, StatusCode> {
+    let config = find_config(&app_state, tenant, app, env, &name).await?;
+    let store = app_state.core_handle.store();
+
+    let mut dependencies = Vec::new();
+    for config_id in store.get_dependencies(config.id).await {
+        if let Some(dependency) = store.get_config_meta(config_id).await {
+            dependencies.push(json!({
+                "config_id": dependency.id,
+                "namespace": dependency.namespace,
+                "name": dependency.name
+            }));
+        }
+    }
+
+    let deployment_order = match store.topological_sort(&[config.id]).await {
+        Ok(order) => order,
+        Err(ConfluxError::Validation(e)) => {
+            error!("Dependency graph of config {} is invalid: {}", config.id, e);
+            return Err(StatusCode::CONFLICT);
+        }
+        Err(e) => {
+            error!("Failed to sort dependencies of config {}: {}", config.id, e);
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    };
+
+    Ok(Json(json!({
+        "config_id": config.id,
+        "dependencies": dependencies,
+        "dependents": store.get_dependents(config.id).await,
+        "deployment_order": deployment_order
+    })))
+}
+
+/// 添加配置依赖处理器
+/// POST /api/v1/configs/{tenant}/{app}/{env}/{name}/dependencies
+pub async fn add_dependency_handler(
+    Path((tenant, app, env, name)): Path<(String, String, String, String)>,
+    State(app_state): State<AppState>,
+    auth_ctx: Option<Extension<AuthContext>>,
+    Json(request): Json<AddDependencyRequest>,
+) -> Result<Json<Value>, StatusCode> {
+    let config = find_config(&app_state, tenant, app, env, &name).await?;
+    if app_state.core_handle.store().get_config_meta(request.depends_on).await.is_none() {
+        warn!("Dependency target config {} not found", request.depends_on);
+        return Err(StatusCode::NOT_FOUND);
+    }
+
+    info!("Adding dependency {} -> {}", config.id, request.depends_on);
+    let response = submit(
+        &app_state,
+        auth_ctx.as_deref(),
+        RaftCommand::AddConfigDependency {
+            from_config_id: config.id,
+            to_config_id: request.depends_on,
+        },
+    )
+    .await?;
+
+    Ok(Json(json!({
+        "success": true,
+        "data": response.data,
+        "message": response.message
+    })))
+}
+
+/// 移除配置依赖处理器
+/// DELETE /api/v1/configs/{tenant}/{app}/{env}/{name}/dependencies/{depends_on}
+pub async fn remove_dependency_handler(
+    Path((tenant, app, env, name, depends_on)): Path<(String, String, String, String, u64)>,
+    State(app_state): State<AppState>,
+    auth_ctx: Option<Extension<AuthContext>>,
+) -> Result<Json<Value>, StatusCode> {
+    let config = find_config(&app_state, tenant, app, env, &name).await?;
+
+    info!("Removing dependency {} -> {}", config.id, depends_on);
+    let response = submit(
+        &app_state,
+        auth_ctx.as_deref(),
+        RaftCommand::RemoveConfigDependency {
+            from_config_id: config.id,
+            to_config_id: depends_on,
+        },
+    )
+    .await?;
+
+    Ok(Json(json!({
+        "success": true,
+        "message": response.message
+    })))
+}
+
+/// 配置回滚处理器
+/// POST /api/v1/configs/{tenant}/{app}/{env}/{name}/rollback
+///
+/// 将默认发布回滚到指定版本（默认为当前发布版本的上一个版本）。`cascade=true` 时
+/// 先按逆拓扑顺序将依赖此配置的其他配置各自回滚到上一个版本，没有更早版本的配置将被跳过
+pub async fn rollback_config_handler(
+    Path((tenant, app, env, name)): Path<(String, String, String, String)>,
+    State(app_state): State<AppState>,
+    auth_ctx: Option<Extension<AuthContext>>,
+    Json(request): Json<RollbackRequest>,
+) -> Result<Json<Value>, StatusCode> {
+    let config = find_config(&app_state, tenant, app, env, &name).await?;
+    let store = app_state.core_handle.store();
+
+    let target_version = match request.version_id {
+        Some(version_id) => version_id,
+        None => store.previous_release_version(config.id).await.ok_or_else(|| {
+            warn!("Config {} has no earlier version to roll back to", config.id);
+            StatusCode::BAD_REQUEST
+        })?,
+    };
+
+    let order = store.rollback_order(config.id, request.cascade).await.map_err(|e| {
+        error!("Failed to compute rollback order for config {}: {}", config.id, e);
+        StatusCode::CONFLICT
+    })?;
+
+    let mut rolled_back = Vec::new();
+    let mut skipped = Vec::new();
+    for config_id in order {
+        let version_id = if config_id == config.id {
+            target_version
+        } else {
+            match store.previous_release_version(config_id).await {
+                Some(version_id) => version_id,
+                None => {
+                    skipped.push(config_id);
+                    continue;
+                }
+            }
+        };
+
+        info!("Rolling back config {} to version {}", config_id, version_id);
+        let command = RaftCommand::ReleaseVersion { config_id, version_id };
+        submit(&app_state, auth_ctx.as_deref(), command).await?;
+        rolled_back.push(json!({ "config_id": config_id, "version_id": version_id }));
+    }
+
+    Ok(Json(json!({
+        "success": true,
+        "rolled_back": rolled_back,
+        "skipped": skipped
+    })))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::AuthzService;
+    use crate::protocol::http::test_support::{create_store_with_configs, local_app_state};
+    use std::sync::Arc;
+    use tempfile::TempDir;
+
+    async fn create_app_state(temp_dir: &TempDir) -> AppState {
+        let store = create_store_with_configs(temp_dir, &["app.json", "db.json", "base.json"]).await;
+        local_app_state(store, Arc::new(AuthzService::new_in_memory().await.unwrap()))
+    }
+
+    fn path(name: &str) -> Path<(String, String, String, String)> {
+        Path(("acme".to_string(), "app".to_string(), "prod".to_string(), name.to_string()))
+    }
+
+    #[tokio::test]
+    async fn test_get_dependencies_returns_deployment_order() {
+        let temp_dir = TempDir::new().unwrap();
+        let app_state = create_app_state(&temp_dir).await;
+        let store = app_state.core_handle.store();
+        // 配置ID按创建顺序分配：app.json=1, db.json=2, base.json=3
+        for (from, to) in [(1, 2), (2, 3)] {
+            store
+                .apply_command(&RaftCommand::AddConfigDependency {
+                    from_config_id: from,
+                    to_config_id: to,
+                })
+                .await
+                .unwrap();
+        }
+
+        let Json(body) = get_dependencies_handler(path("app.json"), State(app_state.clone()))
+            .await
+            .unwrap();
+        assert_eq!(body["config_id"], json!(1));
+        assert_eq!(body["dependencies"][0]["name"], json!("db.json"));
+        assert_eq!(body["deployment_order"], json!([3, 2, 1]));
+
+        let Json(body) = get_dependencies_handler(path("base.json"), State(app_state.clone()))
+            .await
+            .unwrap();
+        assert_eq!(body["dependents"], json!([2]));
+
+        let status = get_dependencies_handler(path("missing.json"), State(app_state))
+            .await
+            .unwrap_err();
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_add_dependency_and_rollback_precheck() {
+        let temp_dir = TempDir::new().unwrap();
+        let app_state = create_app_state(&temp_dir).await;
+
+        let request = Json(AddDependencyRequest { depends_on: 42 });
+        let status = add_dependency_handler(path("app.json"), State(app_state.clone()), None, request)
+            .await
+            .unwrap_err();
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        // 只有一个版本的配置无法回滚到上一个版本
+        let status = rollback_config_handler(
+            path("app.json"),
+            State(app_state),
+            None,
+            Json(RollbackRequest::default()),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+}
@@ -1,22 +1,64 @@
-use crate::protocol::http::{AppState, CreateVersionRequest, UpdateReleasesRequest, FetchConfigResponse};
+use crate::protocol::http::{
+    require_cluster_admin, AppState, BatchFetchEntry, IdempotencyKey, BatchFetchError, BatchFetchRequest, BatchFetchResponse,
+    CreateVersionRequest, MAX_BATCH_FETCH_ITEMS, DiffVersionsQuery, DryRunQuery, UpdateReleasesRequest, FetchConfigResponse, SearchConfigsQuery,
+    ScheduleReleaseRequest, CanaryReleaseRequest, SetReleaseWeightsRequest, ListConfigsQuery,
+};
+use crate::auth::AuthContext;
+use crate::raft::network::{PreVoteRequest, PreVoteResponse};
+use crate::raft::metrics::NodeMetricsSummary;
+use crate::raft::node::handle_pre_vote;
+use crate::raft::store::{ConfigChangeEvent, CONTENT_TOO_LARGE, QUOTA_EXCEEDED, VERSION_LIMIT_REACHED};
 use crate::raft::types::*;
-use crate::raft::client::helpers::{create_write_request, create_get_config_request};
+use crate::error::ConfluxError;
+use crate::raft::client::helpers::{
+    create_get_config_at_request, create_list_configs_request, create_render_config_request,
+    create_search_configs_request,
+};
+use super::version_body::{decode_version_content, parse_version_body};
 use axum::{
+    body::Bytes,
     extract::{Path, Query, State},
-    http::StatusCode,
-    response::Json,
+    http::{header, HeaderMap, StatusCode},
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        IntoResponse, Json, Response,
+    },
+    Extension,
 };
+use base64::{prelude::BASE64_STANDARD, Engine};
+use chrono::{DateTime, Utc};
 use serde_json::{json, Value};
-use std::collections::BTreeMap;
-use tracing::{debug, error, info};
+use std::collections::{BTreeMap, HashMap};
+use std::convert::Infallible;
+use std::time::Duration;
+use tokio::sync::broadcast;
+use tokio_stream::{
+    wrappers::{errors::BroadcastStreamRecvError, BroadcastStream},
+    Stream, StreamExt,
+};
+use tracing::{debug, error, info, warn};
 
 /// 创建配置版本处理器
 /// POST /api/v1/configs/{tenant}/{app}/{env}/{name}/versions
+///
+/// 请求体可以是JSON格式的 [`CreateVersionRequest`]，也可以直接是配置内容：
+/// 此时格式取自 `Content-Type`，未提供或为 `application/octet-stream` 时自动检测。
+///
+/// `?dry_run=true` 时只校验版本（格式、大小和版本数量限制、Schema等）而不写入，
+/// 返回200及 `success` 表示版本是否会被接受，失败原因在 `message` 中。
+///
+/// 与发布规则、定时发布和灰度发布的处理器一样支持 `Idempotency-Key` 头，
+/// 以同一个键重试时不会重复创建版本
 pub async fn create_version_handler(
     Path((tenant, app, env, name)): Path<(String, String, String, String)>,
+    Query(query): Query<DryRunQuery>,
     State(app_state): State<AppState>,
-    Json(request): Json<CreateVersionRequest>,
+    auth_ctx: Option<Extension<AuthContext>>,
+    idempotency_key: IdempotencyKey,
+    headers: HeaderMap,
+    body: Bytes,
 ) -> Result<Json<Value>, StatusCode> {
+    let mut request: CreateVersionRequest = parse_version_body(&headers, body)?;
     info!("Creating version for config: {}/{}/{}/{}", tenant, app, env, name);
 
     let namespace = ConfigNamespace { tenant, app, env };
@@ -30,18 +72,37 @@ pub async fn create_version_handler(
         }
     };
 
-    // 创建 Raft 命令
-    let command = RaftCommand::CreateVersion {
-        config_id: config.id,
-        content: request.content.into_bytes(),
-        format: request.format,
-        creator_id: request.creator_id.unwrap_or_else(|| "system".to_string()).parse().unwrap_or(0),
-        description: request.description.unwrap_or_else(|| "Created via API".to_string()),
+    let store = app_state.core_handle.store();
+    let content = std::mem::take(&mut request.content);
+    let content = decode_version_content(store, &config, content, request.format.as_ref()).await?;
+
+    if query.dry_run {
+        let command = create_version_command(config.id, content, request);
+        return dry_run_version(&app_state, command, auth_ctx.as_deref()).await;
+    }
+
+    // 提交前检查租户的内容大小和版本数量限制
+    let limit_check = match store.check_content_size(&namespace.tenant, content.len()) {
+        Ok(()) => store.check_version_history(&namespace.tenant, config.id).await,
+        Err(e) => Err(e),
     };
+    if let Err(e) = limit_check {
+        warn!("Rejected version for {}/{}/{}/{}: {}", namespace.tenant, namespace.app, namespace.env, name, e);
+        return Err(content_limit_status(&e.to_string()).unwrap_or(StatusCode::BAD_REQUEST));
+    }
+
+    // 创建 Raft 命令
+    let command = create_version_command(config.id, content, request);
 
     // 提交到 Raft
-    let write_request = create_write_request(command);
-    match app_state.core_handle.raft_client().write(write_request).await {
+    match app_state
+        .core_handle
+        .write_idempotent(command, auth_ctx.as_deref(), idempotency_key.as_deref())
+        .await {
+        Ok(response) if !response.success => {
+            error!("Failed to create version: {}", response.message);
+            Err(content_limit_status(&response.message).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR))
+        }
         Ok(response) => {
             info!("Version created successfully for {}/{}/{}/{}", namespace.tenant, namespace.app, namespace.env, name);
             Ok(Json(json!({
@@ -52,16 +113,92 @@ pub async fn create_version_handler(
         }
         Err(e) => {
             error!("Failed to create version: {}", e);
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
+            Err(write_error_status(&e))
         }
     }
 }
 
+/// 根据创建版本请求构建Raft命令
+///
+/// `content` 为已解码的配置内容，请求中的 `content` 字段不再使用
+fn create_version_command(config_id: u64, content: Vec<u8>, request: CreateVersionRequest) -> RaftCommand {
+    RaftCommand::CreateVersion {
+        config_id,
+        content,
+        format: request.format,
+        creator_id: request.creator_id.unwrap_or_else(|| "system".to_string()).parse().unwrap_or(0),
+        description: request.description.unwrap_or_else(|| "Created via API".to_string()),
+    }
+}
+
+/// 试运行创建版本命令，返回版本将被接受还是拒绝
+///
+/// 校验失败不是请求错误，以 `success: false` 和原因返回；无法完成校验时返回对应的状态码
+async fn dry_run_version(
+    app_state: &AppState,
+    command: RaftCommand,
+    auth_ctx: Option<&AuthContext>,
+) -> Result<Json<Value>, StatusCode> {
+    let response = match app_state.core_handle.validate(command, auth_ctx).await {
+        Ok(response) => response,
+        Err(ConfluxError::Validation(message)) => ClientWriteResponse {
+            config_id: None,
+            success: false,
+            message,
+            data: None,
+            log_index: None,
+        },
+        Err(e) => {
+            error!("Failed to validate version: {}", e);
+            return Err(write_error_status(&e));
+        }
+    };
+
+    Ok(Json(json!({
+        "success": response.success,
+        "dry_run": true,
+        "data": response.data,
+        "message": response.message
+    })))
+}
+
+/// 将提交写请求时的错误映射为HTTP状态码
+///
+/// 与 [`ConfluxError::status_code`] 一致：超出客户端速率限制返回429，请求过大返回413，
+/// 没有领导者、Raft不可用或节点资源耗尽返回503，校验失败返回400，其他错误返回500
+///
+/// # Arguments
+/// * `error` - 提交写请求返回的错误
+pub fn write_error_status(error: &ConfluxError) -> StatusCode {
+    error.status_code()
+}
+
+/// 将内容限制错误映射为HTTP状态码
+///
+/// 内容过大返回413，版本数量达到上限、超出租户配额或内容不符合配置引用的Schema返回422，其他错误返回None
+///
+/// # Arguments
+/// * `message` - 错误信息或失败的写入响应信息
+pub fn content_limit_status(message: &str) -> Option<StatusCode> {
+    if message.contains(CONTENT_TOO_LARGE) {
+        Some(StatusCode::PAYLOAD_TOO_LARGE)
+    } else if message.contains(VERSION_LIMIT_REACHED)
+        || message.contains(QUOTA_EXCEEDED)
+        || message.contains(SCHEMA_VALIDATION_FAILED)
+    {
+        Some(StatusCode::UNPROCESSABLE_ENTITY)
+    } else {
+        None
+    }
+}
+
 /// 更新发布规则处理器
 /// PUT /api/v1/configs/{tenant}/{app}/{env}/{name}/releases
 pub async fn update_releases_handler(
     Path((tenant, app, env, name)): Path<(String, String, String, String)>,
     State(app_state): State<AppState>,
+    auth_ctx: Option<Extension<AuthContext>>,
+    idempotency_key: IdempotencyKey,
     Json(request): Json<UpdateReleasesRequest>,
 ) -> Result<Json<Value>, StatusCode> {
     info!("Updating releases for config: {}/{}/{}/{}", tenant, app, env, name);
@@ -84,8 +221,10 @@ pub async fn update_releases_handler(
     };
 
     // 提交到 Raft
-    let write_request = create_write_request(command);
-    match app_state.core_handle.raft_client().write(write_request).await {
+    match app_state
+        .core_handle
+        .write_idempotent(command, auth_ctx.as_deref(), idempotency_key.as_deref())
+        .await {
         Ok(response) => {
             info!("Releases updated successfully for {}/{}/{}/{}", namespace.tenant, namespace.app, namespace.env, name);
             Ok(Json(json!({
@@ -96,61 +235,552 @@ pub async fn update_releases_handler(
         }
         Err(e) => {
             error!("Failed to update releases: {}", e);
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
+            Err(write_error_status(&e))
         }
     }
 }
 
+/// 定时发布处理器
+/// POST /api/v1/configs/{tenant}/{app}/{env}/{name}/releases/schedule
+pub async fn schedule_release_handler(
+    Path((tenant, app, env, name)): Path<(String, String, String, String)>,
+    State(app_state): State<AppState>,
+    auth_ctx: Option<Extension<AuthContext>>,
+    idempotency_key: IdempotencyKey,
+    Json(request): Json<ScheduleReleaseRequest>,
+) -> Result<Json<Value>, StatusCode> {
+    info!(
+        "Scheduling release of version {} for config {}/{}/{}/{} at {}",
+        request.version_id, tenant, app, env, name, request.effective_at
+    );
+
+    if request.effective_at <= chrono::Utc::now() {
+        error!("Scheduled release effective_at must be in the future");
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let namespace = ConfigNamespace { tenant, app, env };
+
+    let config = match app_state.core_handle.store().get_config(&namespace, &name).await {
+        Some(config) => config,
+        None => {
+            error!("Config not found: {}/{}/{}/{}", namespace.tenant, namespace.app, namespace.env, name);
+            return Err(StatusCode::NOT_FOUND);
+        }
+    };
+
+    let command = RaftCommand::ScheduleRelease {
+        config_id: config.id,
+        version_id: request.version_id,
+        labels: request.labels,
+        effective_at: request.effective_at,
+    };
+
+    match app_state
+        .core_handle
+        .write_idempotent(command, auth_ctx.as_deref(), idempotency_key.as_deref())
+        .await {
+        Ok(response) if response.success => Ok(Json(json!({
+            "success": true,
+            "data": response.data,
+            "message": response.message
+        }))),
+        Ok(response) => {
+            error!("Failed to schedule release: {}", response.message);
+            Err(StatusCode::BAD_REQUEST)
+        }
+        Err(e) => {
+            error!("Failed to schedule release: {}", e);
+            Err(write_error_status(&e))
+        }
+    }
+}
+
+/// 金丝雀发布处理器
+/// PUT /api/v1/configs/{tenant}/{app}/{env}/{name}/releases/canary
+///
+/// 将版本发布给匹配标签的一部分客户端，再次调用可调整百分比；
+/// 没有 `instance_id` 标签或未被选中的客户端继续获得原有发布规则的版本
+pub async fn canary_release_handler(
+    Path((tenant, app, env, name)): Path<(String, String, String, String)>,
+    State(app_state): State<AppState>,
+    auth_ctx: Option<Extension<AuthContext>>,
+    idempotency_key: IdempotencyKey,
+    Json(request): Json<CanaryReleaseRequest>,
+) -> Result<Json<Value>, StatusCode> {
+    info!(
+        "Setting canary of version {} for config {}/{}/{}/{} to {}%",
+        request.version_id, tenant, app, env, name, request.percent
+    );
+
+    if request.percent > 100 {
+        error!("Canary percent must be between 0 and 100");
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let namespace = ConfigNamespace { tenant, app, env };
+
+    let config = match app_state.core_handle.store().get_config(&namespace, &name).await {
+        Some(config) => config,
+        None => {
+            error!("Config not found: {}/{}/{}/{}", namespace.tenant, namespace.app, namespace.env, name);
+            return Err(StatusCode::NOT_FOUND);
+        }
+    };
+
+    let command = RaftCommand::SetCanaryPercent {
+        config_id: config.id,
+        version_id: request.version_id,
+        labels: request.labels,
+        percent: request.percent,
+    };
+
+    match app_state
+        .core_handle
+        .write_idempotent(command, auth_ctx.as_deref(), idempotency_key.as_deref())
+        .await {
+        Ok(response) if response.success => Ok(Json(json!({
+            "success": true,
+            "data": response.data,
+            "message": response.message
+        }))),
+        Ok(response) => {
+            error!("Failed to set canary release: {}", response.message);
+            Err(StatusCode::BAD_REQUEST)
+        }
+        Err(e) => {
+            error!("Failed to set canary release: {}", e);
+            Err(write_error_status(&e))
+        }
+    }
+}
+
+/// 发布流量权重处理器
+/// PUT /api/v1/configs/{tenant}/{app}/{env}/{name}/releases/weights
+///
+/// 用每个版本一条的默认发布规则替换现有的默认发布，按权重比例在版本间分配流量，
+/// 用于A/B测试；客户端按 `instance_id` 标签稳定分配，没有该标签的客户端获得权重最大的版本
+pub async fn set_release_weights_handler(
+    Path((tenant, app, env, name)): Path<(String, String, String, String)>,
+    State(app_state): State<AppState>,
+    auth_ctx: Option<Extension<AuthContext>>,
+    idempotency_key: IdempotencyKey,
+    Json(request): Json<SetReleaseWeightsRequest>,
+) -> Result<Json<Value>, StatusCode> {
+    info!(
+        "Setting release weights of config {}/{}/{}/{} for {} versions",
+        tenant, app, env, name, request.weights.len()
+    );
+
+    if request.weights.iter().all(|w| w.weight == 0) {
+        error!("Release weights must sum to more than 0");
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let namespace = ConfigNamespace { tenant, app, env };
+
+    let config = match app_state.core_handle.store().get_config(&namespace, &name).await {
+        Some(config) => config,
+        None => {
+            error!("Config not found: {}/{}/{}/{}", namespace.tenant, namespace.app, namespace.env, name);
+            return Err(StatusCode::NOT_FOUND);
+        }
+    };
+
+    let command = RaftCommand::SetReleaseWeights {
+        config_id: config.id,
+        weights: request
+            .weights
+            .iter()
+            .map(|w| (w.version_id, w.weight))
+            .collect(),
+    };
+
+    match app_state
+        .core_handle
+        .write_idempotent(command, auth_ctx.as_deref(), idempotency_key.as_deref())
+        .await {
+        Ok(response) if response.success => Ok(Json(json!({
+            "success": true,
+            "data": response.data,
+            "message": response.message
+        }))),
+        Ok(response) => {
+            error!("Failed to set release weights: {}", response.message);
+            Err(StatusCode::BAD_REQUEST)
+        }
+        Err(e) => {
+            error!("Failed to set release weights: {}", e);
+            Err(write_error_status(&e))
+        }
+    }
+}
+
+/// 长轮询的最大等待时间，超过该值的 `wait` 参数会被截断
+pub const MAX_LONG_POLL_WAIT: Duration = Duration::from_secs(60);
+
 /// 获取发布配置处理器
 /// GET /api/v1/fetch/configs/{tenant}/{app}/{env}/{name}
+///
+/// 查询参数作为客户端标签参与发布规则匹配；`vars[NAME]=value` 形式的参数作为模板变量，
+/// 替换配置内容中的 `${NAME}` 占位符，`vars_strict=true` 时缺失变量返回400
+///
+/// 支持长轮询：携带 `?wait=30s&current_version=5` 时，如果客户端已知的版本仍是当前版本，
+/// 请求会挂起直到发布了新版本（返回新配置）或等待超时（返回304）。
+/// 等待时间最长为 [`MAX_LONG_POLL_WAIT`]。挂起期间不会派生后台任务，
+/// 客户端断开连接时处理器future被丢弃，变更订阅随之释放
+///
+/// 响应携带 `ETag` 头（返回版本的内容哈希），请求的 `If-None-Match` 与之匹配时返回无内容的304。
+/// 配置不存在，或者没有对客户端标签发布的版本时返回带说明的404
 pub async fn fetch_config_handler(
     Path((tenant, app, env, name)): Path<(String, String, String, String)>,
     Query(params): Query<BTreeMap<String, String>>,
     State(app_state): State<AppState>,
-) -> Result<Json<FetchConfigResponse>, StatusCode> {
-    debug!("Fetching config: {}/{}/{}/{} with labels: {:?}", tenant, app, env, name, params);
+    headers: HeaderMap,
+) -> Result<Response, Response> {
+    debug!("Fetching config: {}/{}/{}/{} with params: {:?}", tenant, app, env, name, params);
+
+    let namespace = ConfigNamespace { tenant, app, env };
+    let fetched = match fetch_or_wait(&app_state, &namespace, &name, params).await {
+        Ok(fetched) => fetched,
+        Err(StatusCode::NOT_FOUND) => {
+            return Err(fetch_not_found(&app_state, &namespace, &name).await.into_response())
+        }
+        Err(status) => return Err(status.into_response()),
+    };
+
+    // ETag取自实际返回的版本（发布规则解析之后）
+    let etag = format!("\"{}\"", fetched.hash);
+    if if_none_match(&headers, &fetched.hash) {
+        debug!("Config not modified: {}/{} at version {}", namespace, name, fetched.version_id);
+        return Ok((StatusCode::NOT_MODIFIED, [(header::ETAG, etag)]).into_response());
+    }
+    if fetched.format == ConfigFormat::Binary {
+        return binary_config_response(&headers, etag, fetched).map_err(IntoResponse::into_response);
+    }
+    Ok(([(header::ETAG, etag)], Json(fetched)).into_response())
+}
+
+/// 说明获取不到配置的原因：配置不存在（包括继承的环境中也不存在），或者没有对客户端标签发布的版本
+async fn fetch_not_found(app_state: &AppState, namespace: &ConfigNamespace, name: &str) -> ConfluxError {
+    let store = app_state.core_handle.store();
+    if store.find_inherited_config(namespace, name).await.is_none() {
+        config_not_found(namespace, name)
+    } else {
+        ConfluxError::NotFound(format!(
+            "No version of configuration {}/{} is published for the given labels",
+            namespace, name
+        ))
+    }
+}
+
+/// 配置不存在的错误
+fn config_not_found(namespace: &ConfigNamespace, name: &str) -> ConfluxError {
+    debug!("Config not found: {}/{}", namespace, name);
+    ConfluxError::NotFound(format!("Configuration {}/{} not found", namespace, name))
+}
+
+/// 批量获取配置处理器
+/// POST /api/v1/fetch/batch
+///
+/// 按请求顺序返回每个配置对客户端标签发布的版本。与单个获取一样，调用者只能获取本租户的配置；
+/// 不存在的配置和其他租户的配置以错误条目返回，不影响其余配置。
+/// 配置数超过 [`MAX_BATCH_FETCH_ITEMS`] 时返回400
+pub async fn batch_fetch_handler(
+    State(app_state): State<AppState>,
+    auth_ctx: Option<Extension<AuthContext>>,
+    Json(request): Json<BatchFetchRequest>,
+) -> Result<Json<BatchFetchResponse>, StatusCode> {
+    let auth_ctx = auth_ctx.ok_or(StatusCode::UNAUTHORIZED)?;
+    if request.configs.len() > MAX_BATCH_FETCH_ITEMS {
+        warn!(
+            "Batch fetch of {} configs exceeds the limit of {}",
+            request.configs.len(),
+            MAX_BATCH_FETCH_ITEMS
+        );
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    debug!("Batch fetching {} configs", request.configs.len());
+
+    let store = app_state.core_handle.store();
+    let mut results = Vec::with_capacity(request.configs.len());
+    for item in request.configs {
+        let namespace = ConfigNamespace {
+            tenant: item.tenant,
+            app: item.app,
+            env: item.env,
+        };
+        let outcome = if namespace.tenant != auth_ctx.tenant_id {
+            warn!(
+                "Tenant isolation violation: user {} of tenant {} batch fetched {}/{}",
+                auth_ctx.user_id, auth_ctx.tenant_id, namespace, item.name
+            );
+            Err(BatchFetchError {
+                code: "forbidden".to_string(),
+                message: "Config belongs to another tenant".to_string(),
+            })
+        } else {
+            match store.get_published_config(&namespace, &item.name, &item.labels).await {
+                Some((_, version)) => Ok(FetchConfigResponse::from_version(
+                    namespace.clone(),
+                    item.name.clone(),
+                    version,
+                )),
+                None => Err(BatchFetchError {
+                    code: "not_found".to_string(),
+                    message: format!("Config {}/{} not found", namespace, item.name),
+                }),
+            }
+        };
+        let (config, error) = match outcome {
+            Ok(config) => (Some(config), None),
+            Err(error) => (None, Some(error)),
+        };
+        results.push(BatchFetchEntry {
+            namespace,
+            name: item.name,
+            config,
+            error,
+        });
+    }
+
+    let failed = results.iter().filter(|entry| entry.error.is_some()).count();
+    info!("Batch fetched {} configs, {} failed", results.len(), failed);
+    Ok(Json(BatchFetchResponse {
+        succeeded: results.len() - failed,
+        failed,
+        results,
+    }))
+}
+
+/// 配置键值获取处理器
+/// GET /api/v1/fetch/configs/{tenant}/{app}/{env}/{name}/keys/{key_path}
+///
+/// 按格式解析对客户端标签发布的版本，沿点分隔的键路径（如 `database.hosts.0`）取值，
+/// 只返回该值本身并保留其类型。配置或键不存在时返回404，内容无法按键读取（如二进制配置）时返回400
+pub async fn fetch_config_value_handler(
+    Path((tenant, app, env, name, key_path)): Path<(String, String, String, String, String)>,
+    Query(params): Query<BTreeMap<String, String>>,
+    State(app_state): State<AppState>,
+) -> Result<Json<Value>, ConfluxError> {
+    debug!("Fetching key {} of config: {}/{}/{}/{}", key_path, tenant, app, env, name);
 
     let namespace = ConfigNamespace { tenant, app, env };
-    
+    let value = app_state
+        .core_handle
+        .store()
+        .get_config_value(&namespace, &name, &key_path, &params)
+        .await?;
+    Ok(Json(value))
+}
+
+/// 二进制配置的版本ID响应头
+const CONFIG_VERSION_HEADER: &str = "x-config-version";
+
+/// 以 `application/octet-stream` 返回二进制配置的内容
+///
+/// 版本ID在 `x-config-version` 响应头中。客户端的 `Accept-Encoding` 包含 `base64` 时
+/// 返回base64编码的内容并设置 `Content-Encoding: base64`，否则返回原始字节
+fn binary_config_response(
+    headers: &HeaderMap,
+    etag: String,
+    fetched: FetchConfigResponse,
+) -> Result<Response, StatusCode> {
+    let version_id = fetched.version_id.to_string();
+    let content_type = (header::CONTENT_TYPE, "application/octet-stream".to_string());
+    if accepts_encoding(headers, "base64") {
+        return Ok((
+            [
+                (header::ETAG, etag),
+                content_type,
+                (header::CONTENT_ENCODING, "base64".to_string()),
+                (header::HeaderName::from_static(CONFIG_VERSION_HEADER), version_id),
+            ],
+            fetched.content,
+        )
+            .into_response());
+    }
+
+    let content = BASE64_STANDARD.decode(&fetched.content).map_err(|e| {
+        error!("Failed to decode binary config {}: {}", fetched.name, e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    Ok((
+        [
+            (header::ETAG, etag),
+            content_type,
+            (header::HeaderName::from_static(CONFIG_VERSION_HEADER), version_id),
+        ],
+        content,
+    )
+        .into_response())
+}
+
+/// 检查 `Accept-Encoding` 请求头是否接受给定的编码
+///
+/// `q=0` 的编码视为不接受
+fn accepts_encoding(headers: &HeaderMap, encoding: &str) -> bool {
+    headers
+        .get_all(header::ACCEPT_ENCODING)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .any(|item| {
+            let mut params = item.split(';').map(str::trim);
+            let accepted = params.next().is_some_and(|name| name.eq_ignore_ascii_case(encoding));
+            let disabled = params.any(|param| {
+                param
+                    .strip_prefix("q=")
+                    .and_then(|q| q.parse::<f32>().ok())
+                    .is_some_and(|q| q == 0.0)
+            });
+            accepted && !disabled
+        })
+}
+
+/// 检查 `If-None-Match` 请求头是否包含给定的内容哈希
+///
+/// 支持逗号分隔的多个ETag、弱校验前缀 `W/` 和通配符 `*`
+fn if_none_match(headers: &HeaderMap, hash: &str) -> bool {
+    headers
+        .get_all(header::IF_NONE_MATCH)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(|tag| tag.trim())
+        .any(|tag| {
+            tag == "*" || tag.strip_prefix("W/").unwrap_or(tag).trim_matches('"') == hash
+        })
+}
+
+/// 读取已发布配置，携带长轮询参数时等待新版本发布
+async fn fetch_or_wait(
+    app_state: &AppState,
+    namespace: &ConfigNamespace,
+    name: &str,
+    mut params: BTreeMap<String, String>,
+) -> Result<FetchConfigResponse, StatusCode> {
+    let long_poll = parse_long_poll_params(&mut params)?;
+    let (labels, variables, strict) = split_template_params(params);
+
+    // 先订阅变更再读取当前版本，避免错过读取与等待之间发布的版本
+    let mut changes = app_state.core_handle.store().subscribe_changes();
+    let fetch = || {
+        fetch_published_config(
+            app_state,
+            namespace,
+            name,
+            labels.clone(),
+            variables.clone(),
+            strict,
+        )
+    };
+
+    let current = fetch().await?;
+    let Some((wait, known_version)) = long_poll else {
+        return Ok(current);
+    };
+    if current.version_id != known_version {
+        return Ok(current);
+    }
+
+    debug!(
+        "Holding fetch of {}/{}/{}/{} for up to {:?} at version {}",
+        namespace.tenant, namespace.app, namespace.env, name, wait, known_version
+    );
+    let deadline = tokio::time::Instant::now() + wait;
+    loop {
+        match tokio::time::timeout_at(deadline, changes.recv()).await {
+            Err(_) => return Err(StatusCode::NOT_MODIFIED),
+            Ok(Err(broadcast::error::RecvError::Closed)) => return Err(StatusCode::NOT_MODIFIED),
+            Ok(Ok(event)) if event.namespace != *namespace || event.name != name => continue,
+            // 匹配的变更或者错过了部分事件时重新读取
+            Ok(_) => {}
+        }
+
+        match fetch().await {
+            Ok(latest) if latest.version_id != known_version => return Ok(latest),
+            Ok(_) => continue,
+            Err(status) => return Err(status),
+        }
+    }
+}
+
+/// 从查询参数中取出长轮询参数 `wait` 和 `current_version`
+///
+/// 两者都提供时返回截断到 [`MAX_LONG_POLL_WAIT`] 的等待时间和客户端已知的版本，
+/// 都未提供时返回None，只提供其一或格式无效时返回400
+fn parse_long_poll_params(
+    params: &mut BTreeMap<String, String>,
+) -> Result<Option<(Duration, u64)>, StatusCode> {
+    let wait = params.remove("wait");
+    let current_version = params.remove("current_version");
+
+    match (wait, current_version) {
+        (None, None) => Ok(None),
+        (Some(wait), Some(current_version)) => {
+            let wait = parse_wait_duration(&wait).ok_or(StatusCode::BAD_REQUEST)?;
+            let current_version = current_version.parse().map_err(|_| StatusCode::BAD_REQUEST)?;
+            Ok(Some((wait.min(MAX_LONG_POLL_WAIT), current_version)))
+        }
+        _ => {
+            warn!("Long-poll requires both wait and current_version");
+            Err(StatusCode::BAD_REQUEST)
+        }
+    }
+}
+
+/// 解析等待时间，支持 `500ms`、`30s`、`1m` 以及不带单位的秒数
+fn parse_wait_duration(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    if let Some(ms) = value.strip_suffix("ms") {
+        ms.parse().ok().map(Duration::from_millis)
+    } else if let Some(secs) = value.strip_suffix('s') {
+        secs.parse().ok().map(Duration::from_secs)
+    } else if let Some(mins) = value.strip_suffix('m') {
+        mins.parse::<u64>().ok().map(|mins| Duration::from_secs(mins.saturating_mul(60)))
+    } else {
+        value.parse().ok().map(Duration::from_secs)
+    }
+}
+
+/// 读取并渲染客户端标签对应的已发布配置
+async fn fetch_published_config(
+    app_state: &AppState,
+    namespace: &ConfigNamespace,
+    name: &str,
+    labels: BTreeMap<String, String>,
+    variables: HashMap<String, String>,
+    strict: bool,
+) -> Result<FetchConfigResponse, StatusCode> {
     // 创建读取请求
-    let read_request = create_get_config_request(namespace.clone(), name.clone(), params);
-    
+    let read_request =
+        create_render_config_request(namespace.clone(), name.to_string(), labels, variables, strict);
+
     match app_state.core_handle.raft_client().read(read_request).await {
         Ok(response) => {
-            if let Some(data) = response.data {
-                // 解析返回的数据
-                if let Ok(config_data) = serde_json::from_value::<serde_json::Value>(data) {
-                    if let (Some(config), Some(version)) = (
-                        config_data.get("config"),
-                        config_data.get("version")
-                    ) {
-                        let fetch_response = FetchConfigResponse {
-                            namespace: namespace.clone(),
-                            name: name.clone(),
-                            content: version.get("content").and_then(|v| v.as_str()).unwrap_or("").to_string(),
-                            format: config.get("format").and_then(|v| v.as_str()).and_then(|s| {
-                                match s {
-                                    "Json" => Some(ConfigFormat::Json),
-                                    "Yaml" => Some(ConfigFormat::Yaml),
-                                    "Toml" => Some(ConfigFormat::Toml),
-                                    "Properties" => Some(ConfigFormat::Properties),
-                                    "Xml" => Some(ConfigFormat::Xml),
-                                    _ => None,
-                                }
-                            }).unwrap_or(ConfigFormat::Json),
-                            version_id: version.get("id").and_then(|v| v.as_u64()).unwrap_or(0),
-                            hash: version.get("hash").and_then(|v| v.as_str()).unwrap_or("").to_string(),
-                            created_at: chrono::Utc::now(), // TODO: 从实际数据中获取
-                        };
-                        
-                        info!("Config fetched successfully: {}/{}/{}/{}", namespace.tenant, namespace.app, namespace.env, name);
-                        return Ok(Json(fetch_response));
-                    }
-                }
-            }
-            
-            error!("Config not found: {}/{}/{}/{}", namespace.tenant, namespace.app, namespace.env, name);
-            Err(StatusCode::NOT_FOUND)
+            let Some(data) = response.data else {
+                debug!("Config not found: {}/{}/{}/{}", namespace.tenant, namespace.app, namespace.env, name);
+                return Err(StatusCode::NOT_FOUND);
+            };
+            // 解析返回的数据，读取结果格式不符属于内部错误而不是配置不存在
+            let version = data
+                .get("version")
+                .and_then(|version| serde_json::from_value::<ConfigVersion>(version.clone()).ok())
+                .ok_or_else(|| {
+                    error!("Malformed read result for config {}/{}", namespace, name);
+                    StatusCode::INTERNAL_SERVER_ERROR
+                })?;
+            info!("Config fetched successfully: {}/{}/{}/{}", namespace.tenant, namespace.app, namespace.env, name);
+            Ok(FetchConfigResponse::from_version(
+                namespace.clone(),
+                name.to_string(),
+                version,
+            ))
+        }
+        Err(ConfluxError::Validation(e)) => {
+            warn!("Failed to render config template: {}", e);
+            Err(StatusCode::BAD_REQUEST)
         }
         Err(e) => {
             error!("Failed to fetch config: {}", e);
@@ -159,12 +789,111 @@ pub async fn fetch_config_handler(
     }
 }
 
+/// 将查询参数拆分为客户端标签、模板变量和严格模式标志
+///
+/// # Arguments
+/// * `params` - 原始查询参数
+///
+/// # Returns
+/// * `(标签, 模板变量, 是否严格模式)`
+fn split_template_params(
+    params: BTreeMap<String, String>,
+) -> (BTreeMap<String, String>, HashMap<String, String>, bool) {
+    let mut labels = BTreeMap::new();
+    let mut variables = HashMap::new();
+    let mut strict = false;
+
+    for (key, value) in params {
+        if let Some(var) = key.strip_prefix("vars[").and_then(|rest| rest.strip_suffix(']')) {
+            variables.insert(var.to_string(), value);
+        } else if key == "vars_strict" {
+            strict = value == "true";
+        } else {
+            labels.insert(key, value);
+        }
+    }
+
+    (labels, variables, strict)
+}
+
+/// 配置变更监听处理器
+/// GET /api/v1/watch/{tenant}/{app}/{env}/{name}
+///
+/// 以SSE推送该配置的变更，事件名为 `change`，数据包含配置ID、新版本ID和变更类型；
+/// 订阅者落后过多丢失事件或存储关闭时推送 `reconnect` 事件并结束流，
+/// 客户端应重新连接并重新获取配置。客户端断开时流被丢弃，订阅随之取消
+pub async fn watch_config_handler(
+    Path((tenant, app, env, name)): Path<(String, String, String, String)>,
+    State(app_state): State<AppState>,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    info!("Client watching config: {}/{}/{}/{}", tenant, app, env, name);
+
+    let namespace = ConfigNamespace { tenant, app, env };
+    let receiver = app_state.core_handle.store().subscribe_changes();
+    let stream = watch_config_changes(receiver, namespace, name).map(|event| Ok(event.into_sse()));
+    Sse::new(stream).keep_alive(KeepAlive::default())
+}
+
+/// 配置变更监听流中的事件
+#[derive(Debug, Clone)]
+enum WatchEvent {
+    /// 监听的配置发生了变更
+    Change(ConfigChangeEvent),
+    /// 订阅已失效，客户端需要重新连接
+    Reconnect,
+}
+
+impl WatchEvent {
+    fn into_sse(self) -> Event {
+        match self {
+            WatchEvent::Change(change) => Event::default()
+                .event("change")
+                .id(change.version_id.to_string())
+                .data(
+                    json!({
+                        "config_id": change.config_id,
+                        "version_id": change.version_id,
+                        "change_type": change.change_type,
+                    })
+                    .to_string(),
+                ),
+            WatchEvent::Reconnect => Event::default().event("reconnect").data(
+                json!({ "message": "Change events were missed, reconnect and fetch the config again" })
+                    .to_string(),
+            ),
+        }
+    }
+}
+
+/// 从变更广播中筛选出指定配置的事件
+///
+/// 订阅落后或广播关闭后以一个 [`WatchEvent::Reconnect`] 结束
+fn watch_config_changes(
+    receiver: broadcast::Receiver<ConfigChangeEvent>,
+    namespace: ConfigNamespace,
+    name: String,
+) -> impl Stream<Item = WatchEvent> {
+    BroadcastStream::new(receiver)
+        .map_while(|result| match result {
+            Ok(event) => Some(event),
+            Err(BroadcastStreamRecvError::Lagged(skipped)) => {
+                warn!("Config watch lagged, skipped {} change events", skipped);
+                None
+            }
+        })
+        .filter(move |event| event.namespace == namespace && event.name == name)
+        .map(WatchEvent::Change)
+        .chain(tokio_stream::once(WatchEvent::Reconnect))
+}
+
 /// 获取配置元数据处理器
 /// GET /api/v1/configs/{tenant}/{app}/{env}/{name}
+///
+/// 配置不存在时返回带说明的404
 pub async fn get_config_handler(
     Path((tenant, app, env, name)): Path<(String, String, String, String)>,
     State(app_state): State<AppState>,
-) -> Result<Json<Value>, StatusCode> {
+) -> Result<Json<Value>, ConfluxError> {
     debug!("Getting config metadata: {}/{}/{}/{}", tenant, app, env, name);
 
     let namespace = ConfigNamespace { tenant, app, env };
@@ -175,31 +904,29 @@ pub async fn get_config_handler(
             info!("Config metadata retrieved: {}/{}/{}/{}", namespace.tenant, namespace.app, namespace.env, name);
             Ok(Json(json!(config)))
         }
-        None => {
-            debug!("Config not found: {}/{}/{}/{}", namespace.tenant, namespace.app, namespace.env, name);
-            Err(StatusCode::NOT_FOUND)
-        }
+        None => Err(config_not_found(&namespace, &name)),
     }
 }
 
 /// 列出配置版本处理器
 /// GET /api/v1/configs/{tenant}/{app}/{env}/{name}/versions
+///
+/// 配置不存在时返回带说明的404
 pub async fn list_versions_handler(
     Path((tenant, app, env, name)): Path<(String, String, String, String)>,
     State(app_state): State<AppState>,
-) -> Result<Json<Value>, StatusCode> {
+) -> Result<Json<Value>, ConfluxError> {
     debug!("Listing versions for config: {}/{}/{}/{}", tenant, app, env, name);
 
     let namespace = ConfigNamespace { tenant, app, env };
 
     // 首先需要找到配置的ID
-    let config = match app_state.core_handle.store().get_config(&namespace, &name).await {
-        Some(config) => config,
-        None => {
-            debug!("Config not found: {}/{}/{}/{}", namespace.tenant, namespace.app, namespace.env, name);
-            return Err(StatusCode::NOT_FOUND);
-        }
-    };
+    let config = app_state
+        .core_handle
+        .store()
+        .get_config(&namespace, &name)
+        .await
+        .ok_or_else(|| config_not_found(&namespace, &name))?;
 
     // 从存储中获取配置版本列表
     let versions = app_state.core_handle.store().list_config_versions(config.id).await;
@@ -210,6 +937,193 @@ pub async fn list_versions_handler(
     })))
 }
 
+/// 配置历史查询处理器
+/// GET /api/v1/configs/{tenant}/{app}/{env}/{name}/history?at=2024-01-01T00:00:00Z
+///
+/// 返回 `at` 时刻发布给客户端的配置版本，`at` 以外的查询参数作为客户端标签；
+/// `at` 缺失或不是合法的ISO 8601时间时返回400，该时刻配置尚不存在时返回404
+pub async fn config_history_handler(
+    Path((tenant, app, env, name)): Path<(String, String, String, String)>,
+    Query(mut params): Query<BTreeMap<String, String>>,
+    State(app_state): State<AppState>,
+) -> Result<Json<Value>, StatusCode> {
+    let at = params
+        .remove("at")
+        .and_then(|at| at.parse::<DateTime<Utc>>().ok())
+        .ok_or_else(|| {
+            warn!("Config history requires an ISO 8601 `at` timestamp");
+            StatusCode::BAD_REQUEST
+        })?;
+    debug!("Getting config {}/{}/{}/{} at {}", tenant, app, env, name, at);
+
+    let namespace = ConfigNamespace { tenant, app, env };
+    let read_request = create_get_config_at_request(namespace.clone(), name.clone(), at, params);
+    match app_state.core_handle.raft_client().read(read_request).await {
+        Ok(response) => response.data.map(Json).ok_or_else(|| {
+            debug!("Config {}/{} not found at {}", namespace, name, at);
+            StatusCode::NOT_FOUND
+        }),
+        Err(e) => {
+            error!("Failed to read config history: {}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// 版本对比处理器
+/// GET /api/v1/configs/{tenant}/{app}/{env}/{name}/diff?from=1&to=2
+///
+/// 文本配置返回行级补丁；任一版本为二进制格式时只返回内容是否变化及大小变化的字节数
+pub async fn diff_versions_handler(
+    Path((tenant, app, env, name)): Path<(String, String, String, String)>,
+    Query(query): Query<DiffVersionsQuery>,
+    State(app_state): State<AppState>,
+) -> Result<Json<VersionDiff>, StatusCode> {
+    debug!("Diffing versions {} and {} of config: {}/{}/{}/{}", query.from, query.to, tenant, app, env, name);
+
+    let namespace = ConfigNamespace { tenant, app, env };
+    let store = app_state.core_handle.store();
+    let config = store.get_config(&namespace, &name).await.ok_or_else(|| {
+        debug!("Config not found: {}/{}", namespace, name);
+        StatusCode::NOT_FOUND
+    })?;
+
+    let from = store.get_config_version(config.id, query.from).await;
+    let to = store.get_config_version(config.id, query.to).await;
+    match (from, to) {
+        (Some(from), Some(to)) => Ok(Json(VersionDiff::between(&from, &to))),
+        _ => {
+            debug!("Version {} or {} of config {}/{} not found", query.from, query.to, namespace, name);
+            Err(StatusCode::NOT_FOUND)
+        }
+    }
+}
+
+/// 版本哈希链校验处理器
+/// GET /api/v1/configs/{tenant}/{app}/{env}/{name}/verify-chain
+///
+/// 按版本顺序校验每个版本的内容哈希及其与前一版本内容的链接，返回校验报告；
+/// 发现被篡改的版本时仍返回200，由报告中的 `tampered_versions` 和 `broken_links` 指出
+pub async fn verify_chain_handler(
+    Path((tenant, app, env, name)): Path<(String, String, String, String)>,
+    State(app_state): State<AppState>,
+) -> Result<Json<Value>, StatusCode> {
+    debug!("Verifying version chain of config: {}/{}/{}/{}", tenant, app, env, name);
+
+    let namespace = ConfigNamespace { tenant, app, env };
+    let store = app_state.core_handle.store();
+    let config = store.get_config(&namespace, &name).await.ok_or_else(|| {
+        debug!("Config not found: {}/{}", namespace, name);
+        StatusCode::NOT_FOUND
+    })?;
+
+    match store.verify_version_chain(config.id).await {
+        Ok(report) => Ok(Json(json!({
+            "valid": report.is_valid(),
+            "report": report
+        }))),
+        Err(ConfluxError::NotFound(_)) => Err(StatusCode::NOT_FOUND),
+        Err(e) => {
+            error!("Failed to verify version chain of {}/{}: {}", namespace, name, e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// 搜索配置处理器
+/// GET /api/v1/search?namespace=tenant/app/env&name_prefix=...&created_after=...&q=...
+///
+/// 路径中没有租户，由处理器将搜索限定在调用者的租户内：
+/// 未认证时返回401，`namespace` 属于其他租户时返回403。不返回软删除的配置
+pub async fn search_configs_handler(
+    Query(query): Query<SearchConfigsQuery>,
+    State(app_state): State<AppState>,
+    auth_ctx: Option<Extension<AuthContext>>,
+) -> Result<Json<Value>, StatusCode> {
+    debug!("Searching configs with query: {:?}", query);
+    let auth_ctx = auth_ctx.ok_or(StatusCode::UNAUTHORIZED)?;
+
+    let mut filter = match query.into_filter() {
+        Some(filter) => filter,
+        None => {
+            debug!("Invalid namespace in search query");
+            return Err(StatusCode::BAD_REQUEST);
+        }
+    };
+    if let Some(namespace) = filter
+        .namespace
+        .as_ref()
+        .filter(|namespace| namespace.tenant != auth_ctx.tenant_id)
+    {
+        warn!(
+            "Tenant isolation violation: user {} of tenant {} searched {}",
+            auth_ctx.user_id, auth_ctx.tenant_id, namespace
+        );
+        return Err(StatusCode::FORBIDDEN);
+    }
+    filter.tenant = Some(auth_ctx.tenant_id.clone());
+
+    let read_request = create_search_configs_request(filter);
+    match app_state.core_handle.raft_client().read(read_request).await {
+        Ok(response) => {
+            let configs = response.data.unwrap_or_else(|| json!([]));
+            let count = configs.as_array().map(|c| c.len()).unwrap_or(0);
+            info!("Config search returned {} results", count);
+            Ok(Json(json!({
+                "configs": configs,
+                "count": count
+            })))
+        }
+        Err(e) => {
+            error!("Failed to search configs: {}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// 配置列表处理器
+/// GET /api/v1/configs?tenant=..&app=..&env=..
+///
+/// 可选 `prefix` 按名称前缀过滤，`label_query` 按发布规则标签过滤，
+/// 例如 `?label_query=env%3Dprod+AND+region+IN+[us-east-1,eu-west-1]`；标签查询无法解析时返回400，
+/// 错误体格式见 [`ConfluxError`] 的 `IntoResponse` 实现
+pub async fn list_configs_handler(
+    Query(query): Query<ListConfigsQuery>,
+    State(app_state): State<AppState>,
+) -> Result<Json<Value>, ConfluxError> {
+    debug!("Listing configs with query: {:?}", query);
+
+    // 先在本地校验标签查询，解析错误属于客户端错误
+    if let Some(ref label_query) = query.label_query {
+        if let Err(e) = LabelQuery::parse(label_query) {
+            warn!("Invalid label query {:?}: {}", label_query, e);
+            return Err(ConfluxError::validation(e.to_string()));
+        }
+    }
+
+    let namespace = ConfigNamespace {
+        tenant: query.tenant,
+        app: query.app,
+        env: query.env,
+    };
+    let read_request = create_list_configs_request(namespace, query.prefix, query.label_query);
+    match app_state.core_handle.raft_client().read(read_request).await {
+        Ok(response) => {
+            let configs = response.data.unwrap_or_else(|| json!([]));
+            let count = configs.as_array().map(|c| c.len()).unwrap_or(0);
+            debug!("Config list returned {} results", count);
+            Ok(Json(json!({
+                "configs": configs,
+                "count": count
+            })))
+        }
+        Err(e) => {
+            error!("Failed to list configs: {}", e);
+            Err(e)
+        }
+    }
+}
+
 /// 集群状态处理器
 /// GET /_cluster/status
 pub async fn cluster_status_handler(
@@ -250,3 +1164,806 @@ pub async fn remove_node_handler(
     info!("Remove node request received (not implemented yet)");
     Err(StatusCode::NOT_IMPLEMENTED)
 }
+
+/// 预投票处理器
+/// POST /_cluster/pre-vote
+///
+/// 由准备发起选举的其他节点调用，不会改变本节点的任期和投票。
+/// 请求方须持有集群管理员权限，避免任意客户端探测或干扰选举
+pub async fn pre_vote_handler(
+    State(app_state): State<AppState>,
+    auth_ctx: Option<Extension<AuthContext>>,
+    Json(request): Json<PreVoteRequest>,
+) -> Result<Json<PreVoteResponse>, StatusCode> {
+    require_cluster_admin(&app_state, auth_ctx.as_deref()).await?;
+
+    let raft_node = app_state
+        .core_handle
+        .raft_client()
+        .raft_node()
+        .ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
+    let node = raft_node.read().await;
+    let raft = node.get_raft().ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
+
+    handle_pre_vote(raft, &request).await.map(Json).map_err(|e| {
+        error!("Failed to handle pre-vote from node {}: {}", request.candidate_id, e);
+        StatusCode::SERVICE_UNAVAILABLE
+    })
+}
+
+/// 本节点指标处理器
+/// GET /_cluster/metrics/local
+///
+/// 由领导者汇总集群指标时调用，返回本节点的任期、日志应用进度和请求统计。
+/// 请求方须持有集群管理员权限
+pub async fn local_metrics_handler(
+    State(app_state): State<AppState>,
+    auth_ctx: Option<Extension<AuthContext>>,
+) -> Result<Json<NodeMetricsSummary>, StatusCode> {
+    require_cluster_admin(&app_state, auth_ctx.as_deref()).await?;
+
+    let raft_node = app_state
+        .core_handle
+        .raft_client()
+        .raft_node()
+        .ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
+    let node = raft_node.read().await;
+
+    node.local_metrics_summary().await.map(Json).map_err(|e| {
+        error!("Failed to collect local metrics: {}", e);
+        e.status_code()
+    })
+}
+
+/// 节点间连接心跳处理器
+/// HEAD /_cluster/ping
+///
+/// 由其他节点的网络工厂定期调用，用于判断本节点是否存活
+pub async fn ping_handler() -> StatusCode {
+    StatusCode::OK
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::http::BatchFetchItem;
+    use crate::protocol::http::test_support::{
+        authz_with_writer, create_config_in, create_store_with_configs, local_app_state,
+    };
+    use crate::error::RaftError;
+    use crate::auth::AuthzService;
+    use std::sync::Arc;
+    use tempfile::TempDir;
+
+    async fn create_app_state(temp_dir: &TempDir) -> AppState {
+        let store = create_store_with_configs(temp_dir, &["app.json"]).await;
+        local_app_state(store, Arc::new(AuthzService::new_in_memory().await.unwrap()))
+    }
+
+    fn version_request(content: &str) -> (HeaderMap, Bytes) {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            axum::http::header::CONTENT_TYPE,
+            axum::http::HeaderValue::from_static("application/json"),
+        );
+        (headers, Bytes::from(json!({ "content": content }).to_string()))
+    }
+
+    fn path() -> Path<(String, String, String, String)> {
+        Path(("acme".to_string(), "app".to_string(), "prod".to_string(), "app.json".to_string()))
+    }
+
+    #[tokio::test]
+    async fn test_create_version_maps_limit_errors() {
+        let temp_dir = TempDir::new().unwrap();
+        let app_state = create_app_state(&temp_dir).await;
+        app_state
+            .core_handle
+            .store()
+            .apply_command(&RaftCommand::SetTenantLimits {
+                tenant: "acme".to_string(),
+                max_config_content_bytes: Some(8),
+                max_version_history: Some(1),
+            })
+            .await
+            .unwrap();
+
+        let (headers, body) = version_request("0123456789");
+        let status = create_version_handler(path(), Query(DryRunQuery::default()), State(app_state.clone()), None, IdempotencyKey::default(), headers, body)
+            .await
+            .unwrap_err();
+        assert_eq!(status, StatusCode::PAYLOAD_TOO_LARGE);
+
+        let (headers, body) = version_request("{}");
+        let status = create_version_handler(path(), Query(DryRunQuery::default()), State(app_state), None, IdempotencyKey::default(), headers, body)
+            .await
+            .unwrap_err();
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn test_create_version_dry_run_does_not_write() {
+        let temp_dir = TempDir::new().unwrap();
+        let app_state = create_app_state(&temp_dir).await;
+        let dry_run = || Query(DryRunQuery { dry_run: true });
+
+        let (headers, body) = version_request(r#"{"v":2}"#);
+        let Json(result) = create_version_handler(path(), dry_run(), State(app_state.clone()), None, IdempotencyKey::default(), headers, body)
+            .await
+            .unwrap();
+        assert_eq!(result["success"], json!(true));
+        assert_eq!(result["dry_run"], json!(true));
+        assert_eq!(result["data"]["version_id"], json!(2));
+
+        app_state
+            .core_handle
+            .store()
+            .apply_command(&RaftCommand::SetTenantLimits {
+                tenant: "acme".to_string(),
+                max_config_content_bytes: Some(8),
+                max_version_history: None,
+            })
+            .await
+            .unwrap();
+        let (headers, body) = version_request("0123456789");
+        let Json(result) = create_version_handler(path(), dry_run(), State(app_state.clone()), None, IdempotencyKey::default(), headers, body)
+            .await
+            .unwrap();
+        assert_eq!(result["success"], json!(false));
+        assert!(result["message"].as_str().unwrap().contains(CONTENT_TOO_LARGE));
+
+        let config = app_state.core_handle.store().get_config_meta(1).await.unwrap();
+        assert_eq!(config.latest_version_id, 1);
+    }
+
+    #[test]
+    fn test_split_template_params() {
+        let params = BTreeMap::from([
+            ("region".to_string(), "eu".to_string()),
+            ("vars[DB_HOST]".to_string(), "localhost".to_string()),
+            ("vars[PORT]".to_string(), "5432".to_string()),
+            ("vars_strict".to_string(), "true".to_string()),
+        ]);
+
+        let (labels, variables, strict) = split_template_params(params);
+        assert_eq!(labels, BTreeMap::from([("region".to_string(), "eu".to_string())]));
+        assert_eq!(variables.get("DB_HOST").map(String::as_str), Some("localhost"));
+        assert_eq!(variables.get("PORT").map(String::as_str), Some("5432"));
+        assert!(strict);
+    }
+
+    async fn fetched(response: Response) -> FetchConfigResponse {
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    fn fetch_params(wait: &str, current_version: u64) -> Query<BTreeMap<String, String>> {
+        Query(BTreeMap::from([
+            ("wait".to_string(), wait.to_string()),
+            ("current_version".to_string(), current_version.to_string()),
+        ]))
+    }
+
+    #[tokio::test]
+    async fn test_long_poll_times_out_with_not_modified() {
+        let temp_dir = TempDir::new().unwrap();
+        let app_state = create_app_state(&temp_dir).await;
+
+        let response = fetch_config_handler(path(), fetch_params("50ms", 1), State(app_state.clone()), HeaderMap::new())
+            .await
+            .unwrap_err();
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+
+        // 客户端版本已过期时立即返回当前配置
+        let response = fetch_config_handler(path(), fetch_params("30s", 7), State(app_state), HeaderMap::new())
+            .await
+            .unwrap();
+        assert_eq!(fetched(response).await.version_id, 1);
+    }
+
+    #[tokio::test]
+    async fn test_long_poll_returns_newly_released_version() {
+        let temp_dir = TempDir::new().unwrap();
+        let app_state = create_app_state(&temp_dir).await;
+        let store = app_state.core_handle.store();
+        store
+            .apply_command(&RaftCommand::CreateVersion {
+                config_id: 1,
+                content: b"{\"v\":2}".to_vec(),
+                format: None,
+                creator_id: 1,
+                description: "v2".to_string(),
+            })
+            .await
+            .unwrap();
+
+        let poll = tokio::spawn(fetch_config_handler(
+            path(),
+            fetch_params("10s", 1),
+            State(app_state.clone()),
+            HeaderMap::new(),
+        ));
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(!poll.is_finished());
+
+        let response = store
+            .apply_command(&RaftCommand::UpdateReleaseRules {
+                config_id: 1,
+                releases: vec![Release::new(BTreeMap::new(), 2, 0)],
+            })
+            .await
+            .unwrap();
+        assert!(response.success, "{}", response.message);
+
+        let response = poll.await.unwrap().unwrap();
+        assert_eq!(fetched(response).await.version_id, 2);
+    }
+
+    fn change_event(name: &str, version_id: u64) -> ConfigChangeEvent {
+        ConfigChangeEvent {
+            config_id: 1,
+            namespace: ConfigNamespace {
+                tenant: "acme".to_string(),
+                app: "app".to_string(),
+                env: "prod".to_string(),
+            },
+            name: name.to_string(),
+            version_id,
+            change_type: crate::raft::store::ConfigChangeType::Updated,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_watch_streams_changes_of_the_config() {
+        let (sender, receiver) = broadcast::channel(16);
+        let Path((tenant, app, env, name)) = path();
+        let stream = watch_config_changes(receiver, ConfigNamespace { tenant, app, env }, name);
+
+        sender.send(change_event("app.json", 2)).unwrap();
+        sender.send(change_event("other.json", 3)).unwrap();
+        sender.send(change_event("app.json", 4)).unwrap();
+        drop(sender);
+
+        let events: Vec<WatchEvent> = stream.collect().await;
+        assert_eq!(events.len(), 3);
+        let versions: Vec<u64> = events
+            .iter()
+            .filter_map(|event| match event {
+                WatchEvent::Change(change) => Some(change.version_id),
+                WatchEvent::Reconnect => None,
+            })
+            .collect();
+        assert_eq!(versions, vec![2, 4]);
+        assert!(matches!(events[2], WatchEvent::Reconnect));
+    }
+
+    #[tokio::test]
+    async fn test_lagged_watch_asks_client_to_reconnect() {
+        let (sender, receiver) = broadcast::channel(1);
+        let Path((tenant, app, env, name)) = path();
+        let mut stream =
+            Box::pin(watch_config_changes(receiver, ConfigNamespace { tenant, app, env }, name));
+
+        for version_id in 2..5 {
+            sender.send(change_event("app.json", version_id)).unwrap();
+        }
+
+        assert!(matches!(stream.next().await, Some(WatchEvent::Reconnect)));
+        assert!(stream.next().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_batch_fetch_reports_failures_per_config() {
+        let temp_dir = TempDir::new().unwrap();
+        let app_state = create_app_state(&temp_dir).await;
+        let item = |tenant: &str, name: &str| BatchFetchItem {
+            tenant: tenant.to_string(),
+            app: "app".to_string(),
+            env: "prod".to_string(),
+            name: name.to_string(),
+            labels: BTreeMap::new(),
+        };
+        let request = BatchFetchRequest {
+            configs: vec![
+                item("acme", "app.json"),
+                item("acme", "missing.json"),
+                item("globex", "app.json"),
+            ],
+        };
+        let auth_ctx = Some(Extension(AuthContext::new("alice".to_string(), "acme".to_string())));
+
+        let Json(response) = batch_fetch_handler(State(app_state.clone()), auth_ctx.clone(), Json(request.clone()))
+            .await
+            .unwrap();
+        assert_eq!((response.succeeded, response.failed), (1, 2));
+        let fetched = response.results[0].config.as_ref().unwrap();
+        assert_eq!((fetched.version_id, fetched.content.as_str()), (1, "{}"));
+        let codes: Vec<Option<&str>> = response
+            .results
+            .iter()
+            .map(|entry| entry.error.as_ref().map(|error| error.code.as_str()))
+            .collect();
+        assert_eq!(codes, vec![None, Some("not_found"), Some("forbidden")]);
+
+        let status = batch_fetch_handler(State(app_state.clone()), None, Json(request))
+            .await
+            .unwrap_err();
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+        let oversized = BatchFetchRequest {
+            configs: vec![item("acme", "app.json"); MAX_BATCH_FETCH_ITEMS + 1],
+        };
+        let status = batch_fetch_handler(State(app_state), auth_ctx, Json(oversized))
+            .await
+            .unwrap_err();
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_batch_fetch_does_not_return_other_tenant_configs() {
+        let temp_dir = TempDir::new().unwrap();
+        let store = create_store_with_configs(&temp_dir, &["app.json"]).await;
+        create_config_in(&store, "globex", "app.json").await;
+        let app_state = local_app_state(store, authz_with_writer(&["acme", "globex"]).await);
+        let request = BatchFetchRequest {
+            configs: ["globex", "acme"]
+                .map(|tenant| BatchFetchItem {
+                    tenant: tenant.to_string(),
+                    app: "app".to_string(),
+                    env: "prod".to_string(),
+                    name: "app.json".to_string(),
+                    labels: BTreeMap::new(),
+                })
+                .to_vec(),
+        };
+        let auth_ctx = Some(Extension(AuthContext::new("writer".to_string(), "acme".to_string())));
+
+        // 其他租户的配置存在且用户在该租户中也有权限，仍只能获取本租户的配置
+        let Json(response) = batch_fetch_handler(State(app_state), auth_ctx, Json(request))
+            .await
+            .unwrap();
+        assert_eq!((response.succeeded, response.failed), (1, 1));
+        let other = &response.results[0];
+        assert!(other.config.is_none());
+        assert_eq!(other.error.as_ref().unwrap().code, "forbidden");
+        assert!(response.results[1].config.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_fetch_config_value_returns_typed_value() {
+        let temp_dir = TempDir::new().unwrap();
+        let app_state = create_app_state(&temp_dir).await;
+        let store = app_state.core_handle.store();
+        store
+            .apply_command(&RaftCommand::CreateVersion {
+                config_id: 1,
+                content: br#"{"pool":{"size":8,"hosts":["a","b"]}}"#.to_vec(),
+                format: None,
+                creator_id: 1,
+                description: "v2".to_string(),
+            })
+            .await
+            .unwrap();
+        store
+            .apply_command(&RaftCommand::UpdateReleaseRules {
+                config_id: 1,
+                releases: vec![Release::new(BTreeMap::new(), 2, 0)],
+            })
+            .await
+            .unwrap();
+
+        let key_path = |key: &str| {
+            Path((
+                "acme".to_string(),
+                "app".to_string(),
+                "prod".to_string(),
+                "app.json".to_string(),
+                key.to_string(),
+            ))
+        };
+        let fetch = |key: &str| {
+            fetch_config_value_handler(key_path(key), Query(BTreeMap::new()), State(app_state.clone()))
+        };
+        assert_eq!(fetch("pool.size").await.unwrap().0, json!(8));
+        assert_eq!(fetch("pool.hosts.1").await.unwrap().0, json!("b"));
+        assert_eq!(fetch("pool").await.unwrap().0, json!({"size": 8, "hosts": ["a", "b"]}));
+        assert!(matches!(fetch("pool.timeout").await, Err(ConfluxError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_missing_configs_return_not_found() {
+        let temp_dir = TempDir::new().unwrap();
+        let app_state = create_app_state(&temp_dir).await;
+
+        // 租户、应用或配置名不存在
+        for (tenant, app, name) in [
+            ("other", "app", "app.json"),
+            ("acme", "other", "app.json"),
+            ("acme", "app", "other.json"),
+        ] {
+            let path = || Path((tenant.to_string(), app.to_string(), "prod".to_string(), name.to_string()));
+            let err = get_config_handler(path(), State(app_state.clone())).await.unwrap_err();
+            assert!(matches!(err, ConfluxError::NotFound(_)), "{}", err);
+            let err = list_versions_handler(path(), State(app_state.clone())).await.unwrap_err();
+            assert_eq!(err.status_code(), StatusCode::NOT_FOUND);
+
+            let response = fetch_config_handler(path(), Query(BTreeMap::new()), State(app_state.clone()), HeaderMap::new())
+                .await
+                .unwrap_err();
+            assert_eq!(response.status(), StatusCode::NOT_FOUND);
+            let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+            let body: Value = serde_json::from_slice(&body).unwrap();
+            assert_eq!(body["error"]["code"], "not_found");
+            assert!(body["error"]["message"].as_str().unwrap().contains("not found"), "{}", body);
+        }
+
+        // 配置存在但发布的版本已不存在
+        app_state
+            .core_handle
+            .store()
+            .versions
+            .write()
+            .await
+            .get_mut(&1)
+            .unwrap()
+            .clear();
+        assert!(get_config_handler(path(), State(app_state.clone())).await.is_ok());
+        let response = fetch_config_handler(path(), Query(BTreeMap::new()), State(app_state), HeaderMap::new())
+            .await
+            .unwrap_err();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: Value = serde_json::from_slice(&body).unwrap();
+        assert!(body["error"]["message"].as_str().unwrap().contains("No version"), "{}", body);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_etag_matches_served_version() {
+        let temp_dir = TempDir::new().unwrap();
+        let app_state = create_app_state(&temp_dir).await;
+        // 新版本未发布，ETag应对应仍在发布的版本1
+        app_state
+            .core_handle
+            .store()
+            .apply_command(&RaftCommand::CreateVersion {
+                config_id: 1,
+                content: b"{\"v\":2}".to_vec(),
+                format: None,
+                creator_id: 1,
+                description: "v2".to_string(),
+            })
+            .await
+            .unwrap();
+
+        let response = fetch_config_handler(path(), Query(BTreeMap::new()), State(app_state.clone()), HeaderMap::new())
+            .await
+            .unwrap();
+        let etag = response.headers().get(header::ETAG).unwrap().clone();
+        let body = fetched(response).await;
+        assert_eq!(body.version_id, 1);
+        assert_eq!(etag.to_str().unwrap(), format!("\"{}\"", body.hash));
+
+        let mut headers = HeaderMap::new();
+        headers.insert(header::IF_NONE_MATCH, etag.clone());
+        let response = fetch_config_handler(path(), Query(BTreeMap::new()), State(app_state.clone()), headers)
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(response.headers().get(header::ETAG), Some(&etag));
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert!(body.is_empty());
+
+        let mut headers = HeaderMap::new();
+        headers.insert(header::IF_NONE_MATCH, axum::http::HeaderValue::from_static("\"stale\""));
+        let response = fetch_config_handler(path(), Query(BTreeMap::new()), State(app_state), headers)
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_binary_config_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let app_state = create_app_state(&temp_dir).await;
+        let store = app_state.core_handle.store();
+        let blob: Vec<u8> = (0..100 * 1024).map(|i| (i * 31 % 251) as u8).collect();
+
+        // 原始请求体自动识别为二进制
+        let request = parse_version_body(&HeaderMap::new(), Bytes::from(blob.clone())).unwrap();
+        assert_eq!(request.format, Some(ConfigFormat::Binary));
+        let config = store.get_config_meta(1).await.unwrap();
+        let content = decode_version_content(store, &config, request.content.clone(), request.format.as_ref())
+            .await
+            .unwrap();
+        assert_eq!(content, blob);
+        let response = store
+            .apply_command(&create_version_command(config.id, content, request))
+            .await
+            .unwrap();
+        assert!(response.success, "{}", response.message);
+        let response = store
+            .apply_command(&RaftCommand::UpdateReleaseRules {
+                config_id: 1,
+                releases: vec![Release::new(BTreeMap::new(), 2, 0)],
+            })
+            .await
+            .unwrap();
+        assert!(response.success, "{}", response.message);
+
+        let response = fetch_config_handler(path(), Query(BTreeMap::new()), State(app_state.clone()), HeaderMap::new())
+            .await
+            .unwrap();
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "application/octet-stream");
+        assert_eq!(response.headers()[CONFIG_VERSION_HEADER], "2");
+        assert!(response.headers().get(header::CONTENT_ENCODING).is_none());
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(body.as_ref(), blob.as_slice());
+
+        let mut headers = HeaderMap::new();
+        headers.insert(header::ACCEPT_ENCODING, axum::http::HeaderValue::from_static("gzip, base64"));
+        let response = fetch_config_handler(path(), Query(BTreeMap::new()), State(app_state.clone()), headers)
+            .await
+            .unwrap();
+        assert_eq!(response.headers()[header::CONTENT_ENCODING], "base64");
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(BASE64_STANDARD.decode(&body).unwrap(), blob);
+
+        // 未指定格式的JSON请求沿用最新版本的二进制格式，内容按base64解码
+        let config = store.get_config_meta(1).await.unwrap();
+        let content = decode_version_content(store, &config, BASE64_STANDARD.encode(b"\x00\x01"), None)
+            .await
+            .unwrap();
+        assert_eq!(content, b"\x00\x01");
+        let status = decode_version_content(store, &config, "not base64!".to_string(), None)
+            .await
+            .unwrap_err();
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        let query = |from, to| Query(DiffVersionsQuery { from, to });
+        let Json(diff) = diff_versions_handler(path(), query(1, 2), State(app_state.clone()))
+            .await
+            .unwrap();
+        assert_eq!(
+            diff,
+            VersionDiff::Binary {
+                changed: true,
+                size_delta_bytes: 100 * 1024 - 2,
+            }
+        );
+        let status = diff_versions_handler(path(), query(1, 3), State(app_state))
+            .await
+            .unwrap_err();
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
+    #[test]
+    fn test_accepts_encoding() {
+        let mut headers = HeaderMap::new();
+        assert!(!accepts_encoding(&headers, "base64"));
+
+        headers.insert(header::ACCEPT_ENCODING, axum::http::HeaderValue::from_static("gzip;q=1.0, BASE64;q=0.5"));
+        assert!(accepts_encoding(&headers, "base64"));
+
+        headers.insert(header::ACCEPT_ENCODING, axum::http::HeaderValue::from_static("gzip, base64;q=0"));
+        assert!(!accepts_encoding(&headers, "base64"));
+    }
+
+    #[test]
+    fn test_if_none_match() {
+        let mut headers = HeaderMap::new();
+        assert!(!if_none_match(&headers, "abc"));
+
+        headers.insert(header::IF_NONE_MATCH, axum::http::HeaderValue::from_static("\"x\", W/\"abc\""));
+        assert!(if_none_match(&headers, "abc"));
+        assert!(!if_none_match(&headers, "abcd"));
+
+        headers.insert(header::IF_NONE_MATCH, axum::http::HeaderValue::from_static("*"));
+        assert!(if_none_match(&headers, "anything"));
+    }
+
+    #[test]
+    fn test_parse_long_poll_params() {
+        let mut params = BTreeMap::from([
+            ("wait".to_string(), "10m".to_string()),
+            ("current_version".to_string(), "5".to_string()),
+            ("region".to_string(), "eu".to_string()),
+        ]);
+        assert_eq!(
+            parse_long_poll_params(&mut params).unwrap(),
+            Some((MAX_LONG_POLL_WAIT, 5))
+        );
+        assert_eq!(params.len(), 1);
+
+        let mut params = BTreeMap::from([("wait".to_string(), "30s".to_string())]);
+        assert_eq!(parse_long_poll_params(&mut params).unwrap_err(), StatusCode::BAD_REQUEST);
+        assert_eq!(parse_long_poll_params(&mut BTreeMap::new()).unwrap(), None);
+
+        assert_eq!(parse_wait_duration("500ms"), Some(Duration::from_millis(500)));
+        assert_eq!(parse_wait_duration("30s"), Some(Duration::from_secs(30)));
+        assert_eq!(parse_wait_duration("2m"), Some(Duration::from_secs(120)));
+        assert_eq!(parse_wait_duration("15"), Some(Duration::from_secs(15)));
+        assert_eq!(parse_wait_duration("soon"), None);
+    }
+
+    #[test]
+    fn test_content_limit_status() {
+        assert_eq!(
+            content_limit_status("Validation error: content exceeds maximum size (9 > 8 bytes)"),
+            Some(StatusCode::PAYLOAD_TOO_LARGE)
+        );
+        assert_eq!(
+            content_limit_status("Error applying command: Validation error: version history limit reached"),
+            Some(StatusCode::UNPROCESSABLE_ENTITY)
+        );
+        assert_eq!(
+            content_limit_status("Validation error: schema validation failed against schema 'port': $.port: expected integer"),
+            Some(StatusCode::UNPROCESSABLE_ENTITY)
+        );
+        assert_eq!(
+            content_limit_status("Validation error: tenant quota exceeded (tenant acme has 2 configs, limit 2)"),
+            Some(StatusCode::UNPROCESSABLE_ENTITY)
+        );
+        assert_eq!(content_limit_status("Configuration with ID 1 not found"), None);
+    }
+
+    #[tokio::test]
+    async fn test_list_configs_filters_by_label_query() {
+        let temp_dir = TempDir::new().unwrap();
+        let app_state = create_app_state(&temp_dir).await;
+        let store = app_state.core_handle.store();
+        let releases = vec![Release::new(
+            BTreeMap::from([
+                ("env".to_string(), "prod".to_string()),
+                ("region".to_string(), "us-east-1".to_string()),
+            ]),
+            1,
+            10,
+        )];
+        let response = store
+            .apply_command(&RaftCommand::UpdateReleaseRules { config_id: 1, releases })
+            .await
+            .unwrap();
+        assert!(response.success);
+
+        let query = |label_query: &str| {
+            Query(ListConfigsQuery {
+                tenant: "acme".to_string(),
+                app: "app".to_string(),
+                env: "prod".to_string(),
+                prefix: None,
+                label_query: Some(label_query.to_string()),
+            })
+        };
+
+        let Json(result) = list_configs_handler(
+            query("env=prod AND region IN us-east-1"),
+            State(app_state.clone()),
+        )
+        .await
+        .unwrap();
+        assert_eq!(result["count"], json!(1));
+        assert_eq!(result["configs"][0]["name"], json!("app.json"));
+
+        let Json(result) = list_configs_handler(
+            query("region IN [us-east-1, eu-west-1]"),
+            State(app_state.clone()),
+        )
+        .await
+        .unwrap();
+        assert_eq!(result["count"], json!(1));
+
+        let Json(result) = list_configs_handler(query("env=staging"), State(app_state.clone()))
+            .await
+            .unwrap();
+        assert_eq!(result["count"], json!(0));
+
+        for malformed in ["env=prod AND", "region IN [us-east-1, eu-west-1"] {
+            let err = list_configs_handler(query(malformed), State(app_state.clone()))
+                .await
+                .unwrap_err();
+            assert_eq!(err.status_code(), StatusCode::BAD_REQUEST);
+        }
+    }
+
+    #[test]
+    fn test_write_error_status() {
+        let status = |error: RaftError| write_error_status(&ConfluxError::Raft(error));
+
+        assert_eq!(status(RaftError::NoLeader), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(
+            status(RaftError::NotLeader { node_id: 1, leader: Some(2) }),
+            StatusCode::SERVICE_UNAVAILABLE
+        );
+        assert_eq!(
+            status(RaftError::RequestTooLarge { size: 10, limit: 5 }),
+            StatusCode::PAYLOAD_TOO_LARGE
+        );
+        assert_eq!(
+            status(RaftError::RateLimited { client: "alice".to_string(), requests: 2 }),
+            StatusCode::TOO_MANY_REQUESTS
+        );
+        assert_eq!(
+            status(RaftError::Timeout { timeout_ms: 5000 }),
+            StatusCode::GATEWAY_TIMEOUT
+        );
+        assert_eq!(
+            status(RaftError::Other("Raft write failed".to_string())),
+            StatusCode::INTERNAL_SERVER_ERROR
+        );
+        assert_eq!(
+            write_error_status(&ConfluxError::validation("bad input")),
+            StatusCode::BAD_REQUEST
+        );
+
+        // 显示信息与原先的字符串错误保持一致
+        assert_eq!(
+            RaftError::RateLimited { client: "alice".to_string(), requests: 2 }.to_string(),
+            "Rate limit exceeded for client alice: 2 requests/second"
+        );
+        assert_eq!(
+            ConfluxError::Raft(RaftError::NotInitialized).to_string(),
+            "Raft error: Raft not initialized"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_node_rpc_endpoints_require_cluster_admin() {
+        let temp_dir = TempDir::new().unwrap();
+        let app_state = create_app_state(&temp_dir).await;
+        let request = PreVoteRequest {
+            candidate_id: 2,
+            last_log_index: 10,
+            last_log_term: 1,
+        };
+
+        let result = pre_vote_handler(State(app_state.clone()), None, Json(request.clone())).await;
+        assert_eq!(result.unwrap_err(), StatusCode::UNAUTHORIZED);
+        let result = local_metrics_handler(State(app_state.clone()), None).await;
+        assert_eq!(result.unwrap_err(), StatusCode::UNAUTHORIZED);
+
+        // 普通用户没有集群管理员权限
+        let user = AuthContext::new("alice".to_string(), "acme".to_string());
+        let result = pre_vote_handler(
+            State(app_state.clone()),
+            Some(Extension(user.clone())),
+            Json(request),
+        )
+        .await;
+        assert_eq!(result.unwrap_err(), StatusCode::FORBIDDEN);
+        let result = local_metrics_handler(State(app_state), Some(Extension(user))).await;
+        assert_eq!(result.unwrap_err(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_search_is_limited_to_the_caller_tenant() {
+        let temp_dir = TempDir::new().unwrap();
+        let store = create_store_with_configs(&temp_dir, &["app.json"]).await;
+        create_config_in(&store, "other", "app.json").await;
+        let app_state =
+            local_app_state(store, Arc::new(AuthzService::new_in_memory().await.unwrap()));
+        let caller = || Some(Extension(AuthContext::new("alice".to_string(), "acme".to_string())));
+
+        let result =
+            search_configs_handler(Query(SearchConfigsQuery::default()), State(app_state.clone()), None)
+                .await;
+        assert_eq!(result.unwrap_err(), StatusCode::UNAUTHORIZED);
+
+        let Json(body) = search_configs_handler(
+            Query(SearchConfigsQuery::default()),
+            State(app_state.clone()),
+            caller(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(body["count"], 1);
+        assert_eq!(body["configs"][0]["namespace"]["tenant"], "acme");
+
+        let query = SearchConfigsQuery {
+            namespace: Some("other/app/prod".to_string()),
+            ..Default::default()
+        };
+        let result = search_configs_handler(Query(query), State(app_state), caller()).await;
+        assert_eq!(result.unwrap_err(), StatusCode::FORBIDDEN);
+    }
+}
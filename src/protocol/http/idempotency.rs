@@ -0,0 +1,81 @@
+//! 写请求的幂等键
+//!
+//! 客户端在写请求上携带 `Idempotency-Key` 头，网络中断后使用同一个键重试时，
+//! 状态机返回首次应用时记录的响应而不会再次应用命令；同一个键用于不同请求时返回409。
+//! 幂等键按租户和请求者区分，匿名请求的幂等键被忽略。目前用于创建版本和修改发布规则的配置写入端点
+
+use crate::error::ConfluxError;
+use axum::{
+    extract::FromRequestParts,
+    http::{request::Parts, HeaderName},
+};
+
+/// 幂等键请求头
+pub const IDEMPOTENCY_KEY_HEADER: HeaderName = HeaderName::from_static("idempotency-key");
+
+/// 幂等键的最大长度（字节）
+pub const MAX_IDEMPOTENCY_KEY_LEN: usize = 255;
+
+/// 从 `Idempotency-Key` 请求头提取的幂等键，请求未携带时为None
+///
+/// 键为空、超过 [`MAX_IDEMPOTENCY_KEY_LEN`] 或不是可见ASCII字符时以400拒绝请求
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IdempotencyKey(pub Option<String>);
+
+impl IdempotencyKey {
+    /// 幂等键
+    pub fn as_deref(&self) -> Option<&str> {
+        self.0.as_deref()
+    }
+}
+
+impl<S: Send + Sync> FromRequestParts<S> for IdempotencyKey {
+    type Rejection = ConfluxError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let Some(value) = parts.headers.get(IDEMPOTENCY_KEY_HEADER) else {
+            return Ok(Self(None));
+        };
+        let key = value
+            .to_str()
+            .ok()
+            .filter(|key| !key.is_empty() && key.len() <= MAX_IDEMPOTENCY_KEY_LEN)
+            .filter(|key| key.bytes().all(|b| b.is_ascii_graphic()))
+            .ok_or_else(|| {
+                ConfluxError::validation(format!(
+                    "Idempotency-Key must be 1 to {} visible ASCII characters",
+                    MAX_IDEMPOTENCY_KEY_LEN
+                ))
+            })?;
+        Ok(Self(Some(key.to_string())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::Request;
+
+    async fn extract(value: Option<&str>) -> Result<IdempotencyKey, ConfluxError> {
+        let mut request = Request::builder();
+        if let Some(value) = value {
+            request = request.header(IDEMPOTENCY_KEY_HEADER, value);
+        }
+        let (mut parts, _) = request.body(()).unwrap().into_parts();
+        IdempotencyKey::from_request_parts(&mut parts, &()).await
+    }
+
+    #[tokio::test]
+    async fn test_extract_idempotency_key() {
+        assert_eq!(extract(None).await.unwrap(), IdempotencyKey(None));
+        assert_eq!(
+            extract(Some("retry-42")).await.unwrap().as_deref(),
+            Some("retry-42")
+        );
+
+        for invalid in ["", "has space", &"k".repeat(MAX_IDEMPOTENCY_KEY_LEN + 1)] {
+            let err = extract(Some(invalid)).await.unwrap_err();
+            assert!(matches!(err, ConfluxError::Validation(_)), "{:?}", invalid);
+        }
+    }
+}
@@ -0,0 +1,286 @@
+//! 配置文件导入解析
+//!
+//! 将上传的 `.env` 或 docker-compose 风格的YAML文件转换为单个配置版本的内容：
+//! `.env` 文件解析为键值对后以properties格式（[`ConfigFormat::Properties`]）保存，
+//! YAML文件校验后按原文以 [`ConfigFormat::Yaml`] 保存
+
+use crate::raft::types::{format::is_yaml_document, ConfigFormat};
+
+/// 解析后的导入文件
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct ImportedFile {
+    /// 配置格式
+    pub format: ConfigFormat,
+    /// 配置内容
+    pub content: Vec<u8>,
+}
+
+/// 导入文件的格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ImportKind {
+    Env,
+    Yaml,
+}
+
+/// 解析上传的导入文件
+///
+/// 格式优先由文件名决定（`.env`、`.env.*`、`*.env` 为环境变量文件，`*.yaml`、`*.yml` 为YAML），
+/// 否则根据内容检测：根节点为映射或序列的YAML文档视为YAML，其余文本按 `.env` 解析
+///
+/// # Arguments
+/// * `file_name` - 上传文件名（可选）
+/// * `data` - 文件内容
+///
+/// # Returns
+/// 配置格式和内容，文件不是UTF-8、无法解析或没有任何条目时返回错误说明
+pub(super) fn parse_import_file(file_name: Option<&str>, data: &[u8]) -> Result<ImportedFile, String> {
+    let text = std::str::from_utf8(data).map_err(|_| "file is not valid UTF-8".to_string())?;
+    if text.trim().is_empty() {
+        return Err("file is empty".to_string());
+    }
+
+    let kind = match file_name.and_then(kind_from_file_name) {
+        Some(kind) => kind,
+        None if is_yaml_document(text) => ImportKind::Yaml,
+        None => ImportKind::Env,
+    };
+
+    match kind {
+        ImportKind::Yaml => {
+            if !is_yaml_document(text) {
+                return Err("file is not a single YAML document with a mapping or sequence root".to_string());
+            }
+            Ok(ImportedFile {
+                format: ConfigFormat::Yaml,
+                content: data.to_vec(),
+            })
+        }
+        ImportKind::Env => {
+            let entries = parse_env(text)?;
+            if entries.is_empty() {
+                return Err("file contains no variables".to_string());
+            }
+            Ok(ImportedFile {
+                format: ConfigFormat::Properties,
+                content: render_properties(&entries).into_bytes(),
+            })
+        }
+    }
+}
+
+/// 根据文件名判断导入格式，无法判断时返回None
+fn kind_from_file_name(file_name: &str) -> Option<ImportKind> {
+    let base = file_name.rsplit(['/', '\\']).next().unwrap_or(file_name).to_ascii_lowercase();
+    if base == ".env" || base.starts_with(".env.") || base.ends_with(".env") {
+        Some(ImportKind::Env)
+    } else if base.ends_with(".yaml") || base.ends_with(".yml") {
+        Some(ImportKind::Yaml)
+    } else {
+        None
+    }
+}
+
+/// 解析 `.env` 文件为有序的键值对
+///
+/// 忽略空行和 `#` 注释行，支持 `export` 前缀。未加引号的值去除首尾空白和
+/// 以空白开头的行尾注释；双引号值支持 `\n`、`\r`、`\t`、`\"`、`\\` 转义并可跨行，
+/// 单引号值按原文保留。重复的键以最后一次出现的值为准
+fn parse_env(text: &str) -> Result<Vec<(String, String)>, String> {
+    let mut entries: Vec<(String, String)> = Vec::new();
+    let mut lines = text.lines().enumerate();
+
+    while let Some((index, line)) = lines.next() {
+        let line_no = index + 1;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let line = line.strip_prefix("export ").map_or(line, str::trim_start);
+        let (key, raw) = line
+            .split_once('=')
+            .ok_or_else(|| format!("line {}: expected KEY=VALUE", line_no))?;
+        let key = key.trim();
+        if !is_env_key(key) {
+            return Err(format!("line {}: invalid variable name '{}'", line_no, key));
+        }
+
+        let value = match raw.trim_start().chars().next() {
+            Some(quote @ ('"' | '\'')) => {
+                let mut quoted = raw.trim_start()[1..].to_string();
+                loop {
+                    if let Some((value, rest)) = split_closing_quote(&quoted, quote) {
+                        let rest = rest.trim_start();
+                        if !rest.is_empty() && !rest.starts_with('#') {
+                            return Err(format!("line {}: unexpected text after closing quote", line_no));
+                        }
+                        break if quote == '"' { unescape(value) } else { value.to_string() };
+                    }
+                    match lines.next() {
+                        Some((_, next)) => {
+                            quoted.push('\n');
+                            quoted.push_str(next);
+                        }
+                        None => return Err(format!("line {}: unterminated quoted value", line_no)),
+                    }
+                }
+            }
+            _ => strip_inline_comment(raw).trim().to_string(),
+        };
+
+        match entries.iter_mut().find(|(existing, _)| existing == key) {
+            Some(entry) => entry.1 = value,
+            None => entries.push((key.to_string(), value)),
+        }
+    }
+    Ok(entries)
+}
+
+/// 变量名只能包含ASCII字母、数字、`_`、`.` 和 `-`
+fn is_env_key(key: &str) -> bool {
+    !key.is_empty()
+        && key
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'_' | b'.' | b'-'))
+}
+
+/// 在引号内容中查找闭合引号，返回引号内的值和引号之后的文本
+///
+/// 双引号值中被反斜杠转义的引号不算闭合引号
+fn split_closing_quote(text: &str, quote: char) -> Option<(&str, &str)> {
+    let mut chars = text.char_indices();
+    while let Some((i, c)) = chars.next() {
+        if c == '\\' && quote == '"' {
+            chars.next();
+        } else if c == quote {
+            return Some((&text[..i], &text[i + 1..]));
+        }
+    }
+    None
+}
+
+/// 处理双引号值中的转义序列，未知的转义按原文保留
+fn unescape(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => result.push('\n'),
+            Some('r') => result.push('\r'),
+            Some('t') => result.push('\t'),
+            Some(escaped @ ('"' | '\\')) => result.push(escaped),
+            Some(other) => {
+                result.push('\\');
+                result.push(other);
+            }
+            None => result.push('\\'),
+        }
+    }
+    result
+}
+
+/// 去除未加引号的值中以空白开头的行尾注释
+fn strip_inline_comment(value: &str) -> &str {
+    value
+        .char_indices()
+        .find(|(i, c)| *c == '#' && value[..*i].ends_with([' ', '\t']))
+        .map_or(value, |(i, _)| &value[..i])
+}
+
+/// 将键值对输出为properties格式，值中的反斜杠和控制字符被转义
+fn render_properties(entries: &[(String, String)]) -> String {
+    let mut output = String::new();
+    for (key, value) in entries {
+        output.push_str(key);
+        output.push('=');
+        for c in value.chars() {
+            match c {
+                '\\' => output.push_str("\\\\"),
+                '\n' => output.push_str("\\n"),
+                '\r' => output.push_str("\\r"),
+                '\t' => output.push_str("\\t"),
+                c => output.push(c),
+            }
+        }
+        output.push('\n');
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn env(text: &str) -> Vec<(String, String)> {
+        parse_env(text).unwrap()
+    }
+
+    fn pair(key: &str, value: &str) -> (String, String) {
+        (key.to_string(), value.to_string())
+    }
+
+    #[test]
+    fn test_env_comments_and_quotes() {
+        let entries = env(concat!(
+            "# database\n",
+            "\n",
+            "DB_HOST=localhost # inline comment\n",
+            "export DB_PORT = 5432\n",
+            "DB_PASS=\"p#ss \\\"word\\\"\"  # quoted\n",
+            "GREETING='hello\\n$USER'\n",
+            "COLOR=#fff\n",
+            "EMPTY=\n",
+            "DB_HOST=db.internal\n",
+        ));
+        assert_eq!(
+            entries,
+            vec![
+                pair("DB_HOST", "db.internal"),
+                pair("DB_PORT", "5432"),
+                pair("DB_PASS", "p#ss \"word\""),
+                pair("GREETING", "hello\\n$USER"),
+                pair("COLOR", "#fff"),
+                pair("EMPTY", ""),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_env_multiline_double_quoted_value() {
+        let entries = env("CERT=\"line1\nline2\"\nNEXT=1\n");
+        assert_eq!(entries, vec![pair("CERT", "line1\nline2"), pair("NEXT", "1")]);
+        assert_eq!(render_properties(&entries), "CERT=line1\\nline2\nNEXT=1\n");
+    }
+
+    #[test]
+    fn test_env_malformed() {
+        assert!(parse_env("NOT_AN_ASSIGNMENT\n").unwrap_err().starts_with("line 1"));
+        assert!(parse_env("A=1\nBAD KEY=2\n").unwrap_err().starts_with("line 2"));
+        assert!(parse_env("A=\"unterminated\n").is_err());
+        assert!(parse_env("A=\"quoted\" trailing\n").is_err());
+    }
+
+    #[test]
+    fn test_import_file_format_detection() {
+        let imported = parse_import_file(Some("config/.env.production"), b"A=1\nB='two'\n").unwrap();
+        assert_eq!(imported.format, ConfigFormat::Properties);
+        assert_eq!(imported.content, b"A=1\nB=two\n");
+
+        let compose = b"services:\n  web:\n    image: nginx\n    ports:\n      - \"80:80\"\n";
+        let imported = parse_import_file(Some("docker-compose.yml"), compose).unwrap();
+        assert_eq!(imported.format, ConfigFormat::Yaml);
+        assert_eq!(imported.content, compose);
+
+        // 没有可识别的文件名时根据内容检测
+        assert_eq!(parse_import_file(None, compose).unwrap().format, ConfigFormat::Yaml);
+        assert_eq!(parse_import_file(Some("upload"), b"A=1").unwrap().format, ConfigFormat::Properties);
+
+        assert!(parse_import_file(Some("values.yaml"), b"A=1").is_err());
+        assert!(parse_import_file(Some(".env"), b"# only comments\n").is_err());
+        assert!(parse_import_file(None, b"\xff\xfe").is_err());
+        assert!(parse_import_file(None, b"   ").is_err());
+    }
+}
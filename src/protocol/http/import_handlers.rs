@@ -0,0 +1,140 @@
+//! 配置文件导入HTTP处理器
+//!
+//! 上传现有项目的 `.env` 或 docker-compose 风格的YAML文件，自动创建配置或为已有配置创建新版本，
+//! `.env` 文件以properties格式保存，YAML文件按原文保存
+
+use super::import_body::parse_import_file;
+use super::namespace_handlers::require_namespace_permission;
+use super::{content_limit_status, write_error_status, AppState};
+use crate::auth::{actions, AuthContext};
+use crate::raft::types::{ConfigNamespace, RaftCommand};
+use axum::{
+    extract::{Multipart, Path, State},
+    http::StatusCode,
+    response::Json,
+    Extension,
+};
+use serde_json::{json, Value};
+use tracing::{error, info, warn};
+
+/// 导入文件的表单字段名
+pub const IMPORT_FILE_FIELD: &str = "file";
+
+/// 导入说明的表单字段名（可选）
+pub const IMPORT_DESCRIPTION_FIELD: &str = "description";
+
+/// 导入配置文件处理器
+/// POST /api/v1/configs/{tenant}/{app}/{env}/{name}/import
+///
+/// 请求体为 `multipart/form-data`，[`IMPORT_FILE_FIELD`] 字段为上传的文件，
+/// [`IMPORT_DESCRIPTION_FIELD`] 字段为版本说明。配置不存在时创建配置，否则创建新版本，
+/// 需要命名空间的写权限。文件缺失或无法解析返回400，响应数据包含配置ID、版本ID和识别出的格式
+pub async fn import_config_handler(
+    Path((tenant, app, env, name)): Path<(String, String, String, String)>,
+    State(app_state): State<AppState>,
+    auth_ctx: Option<Extension<AuthContext>>,
+    mut multipart: Multipart,
+) -> Result<Json<Value>, StatusCode> {
+    let auth_ctx = auth_ctx.ok_or(StatusCode::UNAUTHORIZED)?;
+    let namespace = ConfigNamespace { tenant, app, env };
+    require_namespace_permission(&app_state, &auth_ctx, &namespace, actions::WRITE).await?;
+
+    let mut file = None;
+    let mut description = None;
+    while let Some(field) = multipart.next_field().await.map_err(|e| {
+        warn!("Invalid multipart import body: {}", e);
+        StatusCode::BAD_REQUEST
+    })? {
+        match field.name() {
+            Some(IMPORT_FILE_FIELD) => {
+                let file_name = field.file_name().map(str::to_string);
+                let data = field.bytes().await.map_err(|e| {
+                    warn!("Failed to read import file: {}", e);
+                    StatusCode::BAD_REQUEST
+                })?;
+                file = Some((file_name, data));
+            }
+            Some(IMPORT_DESCRIPTION_FIELD) => {
+                description = Some(field.text().await.map_err(|e| {
+                    warn!("Failed to read import description: {}", e);
+                    StatusCode::BAD_REQUEST
+                })?);
+            }
+            _ => {}
+        }
+    }
+    let (file_name, data) = file.ok_or_else(|| {
+        warn!("Import request has no '{}' field", IMPORT_FILE_FIELD);
+        StatusCode::BAD_REQUEST
+    })?;
+
+    let imported = parse_import_file(file_name.as_deref(), &data).map_err(|e| {
+        warn!(
+            "Cannot import {:?} into {}/{}: {}",
+            file_name.as_deref().unwrap_or("upload"), namespace, name, e
+        );
+        StatusCode::BAD_REQUEST
+    })?;
+    info!(
+        "User {} imports {:?} into {}/{} as {:?}",
+        auth_ctx.user_id, file_name.as_deref().unwrap_or("upload"), namespace, name, imported.format
+    );
+
+    let store = app_state.core_handle.store();
+    let existing = store.get_config(&namespace, &name).await;
+    let limit_check = match (store.check_content_size(&namespace.tenant, imported.content.len()), &existing) {
+        (Ok(()), Some(config)) => store.check_version_history(&namespace.tenant, config.id).await,
+        (result, _) => result,
+    };
+    if let Err(e) = limit_check {
+        warn!("Rejected import into {}/{}: {}", namespace, name, e);
+        return Err(content_limit_status(&e.to_string()).unwrap_or(StatusCode::BAD_REQUEST));
+    }
+
+    let creator_id = auth_ctx.user_id.parse().unwrap_or(0);
+    let description = description.unwrap_or_else(|| {
+        format!("Imported from {}", file_name.as_deref().unwrap_or("upload"))
+    });
+    let format = imported.format.clone();
+    let command = match existing {
+        Some(config) => RaftCommand::CreateVersion {
+            config_id: config.id,
+            content: imported.content,
+            format: Some(imported.format),
+            creator_id,
+            description,
+        },
+        None => RaftCommand::CreateConfig {
+            namespace: namespace.clone(),
+            name: name.clone(),
+            content: imported.content,
+            format: imported.format,
+            schema: None,
+            creator_id,
+            description,
+        },
+    };
+
+    match app_state.core_handle.write(command, Some(&auth_ctx)).await {
+        Ok(response) if response.success => {
+            let version_id = response.data.as_ref().and_then(|data| data.get("version_id")).cloned();
+            Ok(Json(json!({
+                "success": true,
+                "data": {
+                    "config_id": response.config_id,
+                    "version_id": version_id,
+                    "format": format
+                },
+                "message": response.message
+            })))
+        }
+        Ok(response) => {
+            warn!("Import into {}/{} rejected: {}", namespace, name, response.message);
+            Err(content_limit_status(&response.message).unwrap_or(StatusCode::BAD_REQUEST))
+        }
+        Err(e) => {
+            error!("Failed to import into {}/{}: {}", namespace, name, e);
+            Err(write_error_status(&e))
+        }
+    }
+}
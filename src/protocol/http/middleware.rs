@@ -1,54 +1,152 @@
+use crate::auth::AuthContext;
 use axum::{
-    extract::Request,
-    http::{HeaderMap, StatusCode},
+    body::HttpBody,
+    extract::{FromRequestParts, RawPathParams, Request},
+    http::{header::CONTENT_LENGTH, HeaderMap, HeaderName, HeaderValue, StatusCode},
     middleware::Next,
     response::Response,
 };
 use std::time::Instant;
-use tracing::{debug, info, warn};
+use tracing::{debug, field, info_span, warn, Instrument, Level, Span};
+use uuid::Uuid;
+
+/// 请求ID响应头
+pub const REQUEST_ID_HEADER: HeaderName = HeaderName::from_static("x-request-id");
+
+/// 请求日志中间件注入到请求扩展中的请求ID
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RequestId(pub Uuid);
 
 /// 请求日志中间件
-pub async fn logging_middleware(request: Request, next: Next) -> Response {
+///
+/// 为每个请求生成请求ID（写入请求扩展和`X-Request-Id`响应头），并在请求完成后输出一条结构化日志，
+/// 字段包括 method、path、status、latency_ms、request_id、user_id、tenant_id 和 content_length。
+/// 字段同时记录在`http_request` span上，JSON格式的订阅者会将其作为独立字段输出。
+/// 只读取响应头部的状态码和大小，不缓冲响应体
+pub async fn logging_middleware(mut request: Request, next: Next) -> Response {
     let start = Instant::now();
+    let request_id = Uuid::new_v4();
     let method = request.method().clone();
-    let uri = request.uri().clone();
-    let headers = request.headers().clone();
+    let path = request.uri().path().to_string();
+    request.extensions_mut().insert(RequestId(request_id));
 
-    // 提取客户端IP（如果有的话）
-    let client_ip = extract_client_ip(&headers);
+    // 认证中间件位于内层时，认证信息通过响应扩展回传
+    let request_auth = request.extensions().get::<AuthContext>().cloned();
 
     debug!(
         "Incoming request: {} {} from {}",
         method,
-        uri,
-        client_ip.unwrap_or_else(|| "unknown".to_string())
+        path,
+        extract_client_ip(request.headers()).unwrap_or_else(|| "unknown".to_string())
+    );
+
+    let span = info_span!(
+        "http_request",
+        method = %method,
+        path = %path,
+        request_id = %request_id,
+        status = field::Empty,
+        latency_ms = field::Empty,
+        user_id = field::Empty,
+        tenant_id = field::Empty,
+        content_length = field::Empty,
     );
 
-    // 处理请求
-    let response = next.run(request).await;
+    let mut response = next.run(request).instrument(span.clone()).await;
 
-    let duration = start.elapsed();
+    let latency_ms = start.elapsed().as_millis() as u64;
     let status = response.status();
+    let auth_ctx = request_auth.or_else(|| response.extensions().get::<AuthContext>().cloned());
+    let content_length = response_content_length(&response);
 
-    // 记录请求完成日志
-    if status.is_success() {
-        info!(
-            "Request completed: {} {} -> {} in {:?}",
-            method, uri, status, duration
-        );
-    } else if status.is_client_error() {
-        warn!(
-            "Client error: {} {} -> {} in {:?}",
-            method, uri, status, duration
-        );
+    if let Ok(value) = HeaderValue::from_str(&request_id.to_string()) {
+        response.headers_mut().insert(REQUEST_ID_HEADER, value);
+    }
+
+    span.record("status", status.as_u16());
+    span.record("latency_ms", latency_ms);
+    if let Some(ctx) = &auth_ctx {
+        span.record("user_id", ctx.user_id.as_str());
+        span.record("tenant_id", ctx.tenant_id.as_str());
+    }
+    if let Some(length) = content_length {
+        span.record("content_length", length);
+    }
+
+    let level = if status.is_success() || status.is_redirection() || status.is_informational() {
+        Level::INFO
     } else {
-        warn!(
-            "Server error: {} {} -> {} in {:?}",
-            method, uri, status, duration
-        );
+        Level::WARN
+    };
+    log_request(
+        &span,
+        level,
+        RequestLog {
+            method: method.as_str(),
+            path: &path,
+            status: status.as_u16(),
+            latency_ms,
+            request_id,
+            user_id: auth_ctx.as_ref().map(|ctx| ctx.user_id.as_str()),
+            tenant_id: auth_ctx.as_ref().map(|ctx| ctx.tenant_id.as_str()),
+            content_length,
+        },
+    );
+
+    response
+}
+
+/// 单个请求的日志字段
+struct RequestLog<'a> {
+    method: &'a str,
+    path: &'a str,
+    status: u16,
+    latency_ms: u64,
+    request_id: Uuid,
+    user_id: Option<&'a str>,
+    tenant_id: Option<&'a str>,
+    content_length: Option<u64>,
+}
+
+/// 在请求span下输出请求完成事件
+///
+/// 未认证请求的 user_id/tenant_id 以及大小未知的 content_length 不输出
+fn log_request(span: &Span, level: Level, log: RequestLog<'_>) {
+    macro_rules! emit {
+        ($level:expr) => {
+            tracing::event!(
+                parent: span,
+                $level,
+                method = log.method,
+                path = log.path,
+                status = log.status,
+                latency_ms = log.latency_ms,
+                request_id = %log.request_id,
+                user_id = log.user_id,
+                tenant_id = log.tenant_id,
+                content_length = log.content_length,
+                "Request completed"
+            )
+        };
     }
 
+    if level == Level::INFO {
+        emit!(Level::INFO);
+    } else {
+        emit!(Level::WARN);
+    }
+}
+
+/// 从响应头部获取响应体大小
+///
+/// 优先使用`Content-Length`头，其次使用响应体的精确大小提示；流式响应返回None
+fn response_content_length(response: &Response) -> Option<u64> {
     response
+        .headers()
+        .get(CONTENT_LENGTH)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse().ok())
+        .or_else(|| response.body().size_hint().exact())
 }
 
 /// 认证中间件（占位符实现）
@@ -123,6 +221,57 @@ pub async fn request_id_middleware(mut request: Request, next: Next) -> Response
     response
 }
 
+/// 请求路径中标识租户的参数名
+///
+/// 包括租户级API的 `{tenant_id}`、配置和命名空间路由的 `{tenant}`，以及命名空间克隆的源/目标租户
+const TENANT_PATH_PARAMS: [&str; 4] = ["tenant_id", "tenant", "src_tenant", "dst_tenant"];
+
+/// 租户隔离中间件
+///
+/// 比较认证上下文中的租户与请求路径中的租户参数，不一致时直接返回403，
+/// 即使用户在自己的租户中拥有集群管理员等角色也不能访问其他租户的资源；
+/// 路径中有租户参数但没有认证上下文时返回401，没有租户参数的路由不受影响。
+/// 命名空间在请求体中的路由（事务、批量创建、批量获取）由各自的处理器检查租户。
+/// 需要通过 `route_layer` 添加，路由匹配后才能读取路径参数
+pub async fn tenant_isolation_middleware(
+    request: Request,
+    next: Next,
+) -> Result<Response, StatusCode> {
+    let (mut parts, body) = request.into_parts();
+    let path_tenants: Vec<String> = RawPathParams::from_request_parts(&mut parts, &())
+        .await
+        .map(|params| {
+            params
+                .iter()
+                .filter(|(name, _)| TENANT_PATH_PARAMS.contains(name))
+                .map(|(_, value)| value.to_string())
+                .collect()
+        })
+        .unwrap_or_default();
+    let request = Request::from_parts(parts, body);
+
+    if path_tenants.is_empty() {
+        return Ok(next.run(request).await);
+    }
+
+    let auth_ctx = request
+        .extensions()
+        .get::<AuthContext>()
+        .ok_or(StatusCode::UNAUTHORIZED)?;
+    if let Some(tenant) = path_tenants.iter().find(|tenant| **tenant != auth_ctx.tenant_id) {
+        warn!(
+            "Tenant isolation violation: user {} of tenant {} requested {} of tenant {}",
+            auth_ctx.user_id,
+            auth_ctx.tenant_id,
+            request.uri().path(),
+            tenant
+        );
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    Ok(next.run(request).await)
+}
+
 /// 提取客户端IP地址
 fn extract_client_ip(headers: &HeaderMap) -> Option<String> {
     // 尝试从各种可能的头部提取客户端IP
@@ -154,6 +303,7 @@ fn is_public_endpoint(path: &str) -> bool {
         "/health",
         "/ready",
         "/_cluster/status",
+        "/_cluster/ping",     // 节点间连接心跳
         "/api/v1/fetch/configs", // 配置获取端点允许匿名访问
     ];
 
@@ -177,7 +327,133 @@ fn generate_request_id() -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use axum::http::{HeaderValue};
+    use axum::{
+        body::{to_bytes, Body},
+        middleware::from_fn,
+        routing::get,
+        Extension, Router,
+    };
+    use serde_json::Value;
+    use std::sync::{Arc, Mutex};
+    use tower::ServiceExt;
+    use tracing_subscriber::fmt::MakeWriter;
+
+    /// 收集JSON格式日志输出的写入器
+    #[derive(Clone, Default)]
+    struct CapturedLogs(Arc<Mutex<Vec<u8>>>);
+
+    impl std::io::Write for CapturedLogs {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl<'a> MakeWriter<'a> for CapturedLogs {
+        type Writer = Self;
+
+        fn make_writer(&'a self) -> Self::Writer {
+            self.clone()
+        }
+    }
+
+    impl CapturedLogs {
+        /// 解析出所有请求完成日志
+        fn request_logs(&self) -> Vec<Value> {
+            let output = String::from_utf8(self.0.lock().unwrap().clone()).unwrap();
+            output
+                .lines()
+                .map(|line| serde_json::from_str::<Value>(line).unwrap())
+                .filter(|entry| entry["message"] == "Request completed")
+                .collect()
+        }
+    }
+
+    /// 模拟内层认证中间件：注入认证信息并通过响应扩展回传
+    async fn fake_auth(mut request: Request, next: Next) -> Response {
+        let auth_ctx = AuthContext::new("user1".to_string(), "tenant1".to_string());
+        request.extensions_mut().insert(auth_ctx.clone());
+        let mut response = next.run(request).await;
+        response.extensions_mut().insert(auth_ctx);
+        response
+    }
+
+    fn app() -> Router {
+        Router::new()
+            .route(
+                "/ok",
+                get(|Extension(RequestId(id)): Extension<RequestId>| async move { id.to_string() }),
+            )
+            .route("/missing", get(|| async { StatusCode::NOT_FOUND }))
+            .layer(from_fn(fake_auth))
+            .layer(from_fn(logging_middleware))
+    }
+
+    async fn call(path: &str) -> (Response, Vec<Value>) {
+        let logs = CapturedLogs::default();
+        let subscriber = tracing_subscriber::fmt()
+            .json()
+            .flatten_event(true)
+            .with_writer(logs.clone())
+            .finish();
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        let response = app()
+            .oneshot(Request::builder().uri(path).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        (response, logs.request_logs())
+    }
+
+    #[tokio::test]
+    async fn test_logging_middleware_logs_success_fields() {
+        let (response, logs) = call("/ok").await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let request_id = response.headers()[REQUEST_ID_HEADER]
+            .to_str()
+            .unwrap()
+            .to_string();
+        Uuid::parse_str(&request_id).unwrap();
+
+        // 处理器从请求扩展中读取到的请求ID与响应头一致
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(body, request_id.as_bytes());
+
+        assert_eq!(logs.len(), 1);
+        let entry = &logs[0];
+        assert_eq!(entry["level"], "INFO");
+        assert_eq!(entry["method"], "GET");
+        assert_eq!(entry["path"], "/ok");
+        assert_eq!(entry["status"], 200);
+        assert!(entry["latency_ms"].is_u64());
+        assert_eq!(entry["request_id"], request_id);
+        assert_eq!(entry["user_id"], "user1");
+        assert_eq!(entry["tenant_id"], "tenant1");
+        assert_eq!(entry["content_length"], request_id.len() as u64);
+    }
+
+    #[tokio::test]
+    async fn test_logging_middleware_logs_client_error_fields() {
+        let (response, logs) = call("/missing").await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        let request_id = response.headers()[REQUEST_ID_HEADER].to_str().unwrap();
+
+        assert_eq!(logs.len(), 1);
+        let entry = &logs[0];
+        assert_eq!(entry["level"], "WARN");
+        assert_eq!(entry["method"], "GET");
+        assert_eq!(entry["path"], "/missing");
+        assert_eq!(entry["status"], 404);
+        assert!(entry["latency_ms"].is_u64());
+        assert_eq!(entry["request_id"], request_id);
+        assert_eq!(entry["user_id"], "user1");
+        assert_eq!(entry["tenant_id"], "tenant1");
+        assert_eq!(entry["content_length"], 0);
+    }
 
     #[test]
     fn test_extract_client_ip() {
@@ -201,11 +477,15 @@ mod tests {
         assert!(is_public_endpoint("/health"));
         assert!(is_public_endpoint("/ready"));
         assert!(is_public_endpoint("/_cluster/status"));
+        assert!(is_public_endpoint("/_cluster/ping"));
         assert!(is_public_endpoint("/api/v1/fetch/configs/tenant/app/env/config"));
         
         assert!(!is_public_endpoint("/api/v1/configs/tenant/app/env/config/versions"));
         assert!(!is_public_endpoint("/api/v1/configs/tenant/app/env/config/releases"));
         assert!(!is_public_endpoint("/_cluster/nodes"));
+        assert!(!is_public_endpoint("/_cluster/metrics"));
+        assert!(!is_public_endpoint("/_cluster/metrics/local"));
+        assert!(!is_public_endpoint("/_cluster/pre-vote"));
     }
 
     #[test]
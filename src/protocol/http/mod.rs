@@ -1,12 +1,14 @@
 use crate::app::CoreAppHandle;
+use crate::auth::jwt_auth_middleware;
 use crate::protocol::{ProtocolConfig, ProtocolPlugin};
+use crate::raft::metrics::HealthStatus;
 use async_trait::async_trait;
 use axum::{
-    extract::State,
+    extract::{DefaultBodyLimit, State},
     http::StatusCode,
-    middleware::from_fn,
+    middleware::{from_fn, from_fn_with_state},
     response::Json,
-    routing::{get, post, put},
+    routing::{get, head, post, put},
     Router,
 };
 use serde_json::{json, Value};
@@ -15,13 +17,47 @@ use tower::ServiceBuilder;
 use tower_http::{cors::CorsLayer, trace::TraceLayer};
 use tracing::{info, warn};
 
+pub mod approval_handlers;
+pub mod bulk_handlers;
+pub mod cluster_handlers;
+pub mod dependency_handlers;
 pub mod handlers;
+mod idempotency;
+mod import_body;
+pub mod import_handlers;
 pub mod middleware;
+pub mod namespace_handlers;
+pub mod permission_handlers;
+pub mod prune_handlers;
+pub mod quota_handlers;
+pub mod schema_handlers;
 pub mod schemas;
-
+pub mod tenant_cluster_handlers;
+#[cfg(test)]
+mod test_support;
+pub mod transaction_handlers;
+mod version_body;
+pub mod webhook_handlers;
+
+pub use approval_handlers::*;
+pub use bulk_handlers::*;
+pub use cluster_handlers::*;
+pub use dependency_handlers::*;
 pub use handlers::*;
-pub use middleware::logging_middleware;
+pub use idempotency::{IdempotencyKey, IDEMPOTENCY_KEY_HEADER, MAX_IDEMPOTENCY_KEY_LEN};
+pub use import_handlers::*;
+pub use middleware::{
+    logging_middleware, tenant_isolation_middleware, RequestId, REQUEST_ID_HEADER,
+};
+pub use namespace_handlers::*;
+pub use permission_handlers::*;
+pub use prune_handlers::*;
+pub use quota_handlers::*;
+pub use schema_handlers::*;
 pub use schemas::*;
+pub use tenant_cluster_handlers::*;
+pub use transaction_handlers::*;
+pub use webhook_handlers::*;
 
 /// HTTP 协议插件实现
 pub struct HttpProtocol;
@@ -84,12 +120,18 @@ fn create_router(app_state: AppState) -> Router {
         .route("/health", get(health_handler))
         .route("/ready", get(readiness_handler))
 
-        // API v1 路由（暂时不添加授权中间件）
+        // API v1 路由
         .nest("/api/v1", create_v1_routes())
 
         // 集群管理路由
         .nest("/_cluster", create_cluster_routes())
 
+        // JWT认证：为非公共端点解析AuthContext，失败时返回401
+        .layer(from_fn_with_state(
+            app_state.core_handle.jwt_authenticator.clone(),
+            jwt_auth_middleware,
+        ))
+
         // 设置应用状态
         .with_state(app_state)
 
@@ -109,11 +151,129 @@ fn create_v1_routes() -> Router<AppState> {
         // 配置管理路由
         .route("/configs/{tenant}/{app}/{env}/{name}/versions", post(create_version_handler))
         .route("/configs/{tenant}/{app}/{env}/{name}/releases", put(update_releases_handler))
+        .route(
+            "/configs/{tenant}/{app}/{env}/{name}/releases/schedule",
+            post(schedule_release_handler),
+        )
+        .route(
+            "/configs/{tenant}/{app}/{env}/{name}/releases/canary",
+            put(canary_release_handler),
+        )
+        .route(
+            "/configs/{tenant}/{app}/{env}/{name}/releases/weights",
+            put(set_release_weights_handler),
+        )
+        .route(
+            "/configs/{tenant}/{app}/{env}/{name}/dependencies",
+            get(get_dependencies_handler).post(add_dependency_handler),
+        )
+        .route(
+            "/configs/{tenant}/{app}/{env}/{name}/dependencies/{depends_on}",
+            axum::routing::delete(remove_dependency_handler),
+        )
+        .route("/configs/{tenant}/{app}/{env}/{name}/import", post(import_config_handler))
+        .route("/configs/{tenant}/{app}/{env}/{name}/rollback", post(rollback_config_handler))
+        .route("/configs/{tenant}/{app}/{env}/{name}/prune-policy", put(set_prune_policy_handler))
+        .route("/configs/{tenant}/{app}/{env}/{name}/prune", post(prune_versions_handler))
+        .route("/configs/{tenant}/{app}/{env}/{name}/approvals", get(list_approvals_handler))
+        .route(
+            "/configs/{tenant}/{app}/{env}/{name}/versions/{version_id}/request-approval",
+            post(request_approval_handler),
+        )
+        .route(
+            "/configs/{tenant}/{app}/{env}/{name}/versions/{version_id}/approve",
+            post(approve_release_handler),
+        )
+        .route(
+            "/configs/{tenant}/{app}/{env}/{name}/versions/{version_id}/reject",
+            post(reject_release_handler),
+        )
         .route("/fetch/configs/{tenant}/{app}/{env}/{name}", get(fetch_config_handler))
+        .route(
+            "/fetch/configs/{tenant}/{app}/{env}/{name}/keys/{key_path}",
+            get(fetch_config_value_handler),
+        )
+        .route("/fetch/batch", post(batch_fetch_handler))
+        .route("/watch/{tenant}/{app}/{env}/{name}", get(watch_config_handler))
 
         // 配置查询路由
         .route("/configs/{tenant}/{app}/{env}/{name}", get(get_config_handler))
         .route("/configs/{tenant}/{app}/{env}/{name}/versions", get(list_versions_handler))
+        .route("/configs/{tenant}/{app}/{env}/{name}/diff", get(diff_versions_handler))
+        .route("/configs/{tenant}/{app}/{env}/{name}/verify-chain", get(verify_chain_handler))
+        .route("/configs/{tenant}/{app}/{env}/{name}/history", get(config_history_handler))
+
+        // 命名空间克隆路由
+        .route(
+            "/namespaces/{dst_tenant}/{dst_app}/{dst_env}/clone-from/{src_tenant}/{src_app}/{src_env}",
+            post(clone_namespace_handler),
+        )
+        .route(
+            "/namespaces/{tenant}/{app}/{env}",
+            axum::routing::delete(soft_delete_namespace_handler),
+        )
+        .route("/namespaces/{tenant}/{app}/{env}/undelete", post(undelete_namespace_handler))
+        .route("/namespaces/{tenant}/{app}/{env}/compact", post(compact_namespace_handler))
+        .route(
+            "/namespaces/{tenant}/{app}/{env}/approval-policy",
+            get(get_approval_policy_handler).put(set_approval_policy_handler),
+        )
+
+        // Schema注册表路由
+        .route(
+            "/schemas/{tenant}",
+            get(list_schemas_handler).post(register_schema_handler),
+        )
+        .route("/schemas/{tenant}/{name}", get(get_schema_handler))
+        .route("/configs/{tenant}/{app}/{env}/{name}/schema", put(set_config_schema_handler))
+
+        // Webhook路由
+        .route(
+            "/webhooks/{tenant}",
+            get(list_webhooks_handler).post(create_webhook_handler),
+        )
+        .route(
+            "/webhooks/{tenant}/{id}",
+            axum::routing::delete(delete_webhook_handler),
+        )
+
+        // 多配置原子事务路由
+        .route("/transactions", post(transaction_handler))
+
+        // 批量创建配置路由
+        .route(
+            "/bulk/configs",
+            post(bulk_create_configs_handler).layer(DefaultBodyLimit::max(MAX_BULK_PAYLOAD_BYTES)),
+        )
+
+        // 配置列表路由
+        .route("/configs", get(list_configs_handler))
+
+        // 配置搜索路由
+        .route("/search", get(search_configs_handler))
+
+        // 权限查询路由
+        .route("/permissions/check-batch", post(check_batch_handler))
+
+        // 租户配额查询路由
+        .route("/tenants/{tenant_id}/quota", get(tenant_quota_usage_handler))
+
+        // 租户级集群管理路由
+        .nest("/tenants/{tenant_id}/cluster", create_tenant_cluster_routes())
+
+        // 租户隔离：路径中的租户必须与认证上下文的租户一致
+        .route_layer(from_fn(tenant_isolation_middleware))
+}
+
+/// 创建租户级集群管理路由
+fn create_tenant_cluster_routes() -> Router<AppState> {
+    Router::new()
+        .route("/status", get(tenant_cluster_status_handler))
+        .route("/nodes", post(tenant_add_node_handler))
+        .route(
+            "/nodes/{node_id}",
+            axum::routing::delete(tenant_remove_node_handler),
+        )
 }
 
 /// 创建集群管理路由
@@ -122,31 +282,102 @@ fn create_cluster_routes() -> Router<AppState> {
         .route("/status", get(cluster_status_handler))
         .route("/nodes", post(add_node_handler))
         .route("/nodes/{node_id}", axum::routing::delete(remove_node_handler))
+        .route(
+            "/nodes/{node_id}/decommission",
+            axum::routing::delete(decommission_node_handler),
+        )
+        .route("/nodes/{node_id}/priority", put(set_node_priority_handler))
+        .route("/trigger-elect", post(trigger_elect_handler))
+        .route("/node-priority", post(node_priority_handler))
+        .route("/nodes/{node_id}/address", put(update_peer_address_handler))
+        .route("/tenants/{tenant_id}/quota", put(set_tenant_quota_handler))
+        .route("/compact", post(compact_handler))
+        .route("/snapshot-info", get(snapshot_info_handler))
+        .route("/state-machine", get(state_machine_handler))
+        .route("/consistency-check", get(consistency_check_handler))
+        .route("/storage/dedup-stats", get(dedup_stats_handler))
+        .route("/storage/compact", post(compact_storage_handler))
+        .route("/storage/flush", post(flush_storage_handler))
+        .route("/events/stream", get(cluster_events_stream_handler))
+        .route("/transfer-leadership", post(transfer_leadership_handler))
+        .route("/dead-letters", get(dead_letters_handler))
+        .route("/pre-vote", post(pre_vote_handler))
+        .route("/ping", head(ping_handler))
+        .route("/peers", get(peers_handler))
+        .route("/metrics", get(cluster_metrics_handler))
+        .route("/metrics/local", get(local_metrics_handler))
 }
 
 /// 健康检查处理器
-async fn health_handler() -> Json<Value> {
-    Json(json!({
-        "status": "healthy",
-        "timestamp": chrono::Utc::now().to_rfc3339()
-    }))
+///
+/// 返回 healthy/degraded/unhealthy 及各组件（raft、storage、leader）的详细状态；
+/// 不健康时返回503，降级时仍返回200以便负载均衡器继续转发读请求
+async fn health_handler(State(app_state): State<AppState>) -> (StatusCode, Json<Value>) {
+    let report = app_state.core_handle.raft_client().health_report().await;
+    let code = match report.status {
+        HealthStatus::Unhealthy => {
+            warn!("Health check failed: {:?}", report);
+            StatusCode::SERVICE_UNAVAILABLE
+        }
+        HealthStatus::Healthy | HealthStatus::Degraded => StatusCode::OK,
+    };
+
+    (
+        code,
+        Json(json!({
+            "status": report.status,
+            "components": {
+                "raft": report.raft,
+                "storage": report.storage,
+                "leader": report.leader,
+            },
+            "timestamp": chrono::Utc::now().to_rfc3339()
+        })),
+    )
 }
 
 /// 就绪检查处理器
-async fn readiness_handler(State(app_state): State<AppState>) -> Result<Json<Value>, StatusCode> {
-    // 检查核心服务是否就绪
-    let cluster_status = app_state.core_handle.raft_client()
-        .get_cluster_status()
-        .await;
-
-    match cluster_status {
-        Ok(_) => Ok(Json(json!({
-            "status": "ready",
-            "timestamp": chrono::Utc::now().to_rfc3339()
-        }))),
+///
+/// 只有已知领导者且没有不健康组件时才就绪，否则返回503及原因
+async fn readiness_handler(State(app_state): State<AppState>) -> (StatusCode, Json<Value>) {
+    let raft_client = app_state.core_handle.raft_client();
+    let report = raft_client.health_report().await;
+
+    match raft_client.get_cluster_status().await {
+        Ok(status) if status.healthy && report.status != HealthStatus::Unhealthy => (
+            StatusCode::OK,
+            Json(json!({
+                "status": "ready",
+                "leader_id": status.leader_id,
+                "term": status.term,
+                "timestamp": chrono::Utc::now().to_rfc3339()
+            })),
+        ),
+        Ok(_) => {
+            warn!("Readiness check failed: {:?}", report);
+            (
+                StatusCode::SERVICE_UNAVAILABLE,
+                Json(json!({
+                    "status": "not_ready",
+                    "components": {
+                        "raft": report.raft,
+                        "storage": report.storage,
+                        "leader": report.leader,
+                    },
+                    "timestamp": chrono::Utc::now().to_rfc3339()
+                })),
+            )
+        }
         Err(e) => {
             warn!("Readiness check failed: {}", e);
-            Err(StatusCode::SERVICE_UNAVAILABLE)
+            (
+                StatusCode::SERVICE_UNAVAILABLE,
+                Json(json!({
+                    "status": "not_ready",
+                    "error": e.to_string(),
+                    "timestamp": chrono::Utc::now().to_rfc3339()
+                })),
+            )
         }
     }
 }
@@ -0,0 +1,343 @@
+//! 命名空间HTTP处理器
+//!
+//! 提供跨环境复制整个命名空间配置、软删除与恢复命名空间以及压缩命名空间存储的端点
+
+use super::{write_error_status, AppState, CloneNamespaceQuery, SoftDeleteNamespaceQuery};
+use crate::auth::{actions, AuthContext, ResourcePath};
+use crate::error::ConfluxError;
+use crate::raft::store::{CloneReport, CompactionStats};
+use crate::raft::types::{ClientWriteResponse, ConfigNamespace, RaftCommand};
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::Json,
+    Extension,
+};
+use chrono::Utc;
+use serde_json::{json, Value};
+use tracing::{error, info, warn};
+
+/// 检查请求者对命名空间的指定操作权限
+///
+/// # Arguments
+/// * `app_state` - 应用状态
+/// * `auth_ctx` - 认证上下文
+/// * `namespace` - 目标命名空间
+/// * `action` - 操作类型
+///
+/// # Returns
+/// 有权限时返回Ok(())，否则返回对应的HTTP状态码
+pub(super) async fn require_namespace_permission(
+    app_state: &AppState,
+    auth_ctx: &AuthContext,
+    namespace: &ConfigNamespace,
+    action: &str,
+) -> Result<(), StatusCode> {
+    let resource = ResourcePath::env(&namespace.tenant, &namespace.app, &namespace.env);
+
+    match app_state
+        .core_handle
+        .authz_service()
+        .check(&auth_ctx.user_id, &namespace.tenant, &resource, action)
+        .await
+    {
+        Ok(true) => Ok(()),
+        Ok(false) => {
+            warn!(
+                "Permission denied: user={}, resource={}, action={}",
+                auth_ctx.user_id, resource, action
+            );
+            Err(StatusCode::FORBIDDEN)
+        }
+        Err(e) => {
+            error!("Permission check failed: {}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// 命名空间克隆处理器
+/// POST /api/v1/namespaces/{dst_tenant}/{dst_app}/{dst_env}/clone-from/{src_tenant}/{src_app}/{src_env}
+///
+/// 需要目标命名空间的写权限和源命名空间的读权限，`?overwrite=true` 时覆盖已存在的配置
+pub async fn clone_namespace_handler(
+    Path((dst_tenant, dst_app, dst_env, src_tenant, src_app, src_env)): Path<(
+        String,
+        String,
+        String,
+        String,
+        String,
+        String,
+    )>,
+    Query(query): Query<CloneNamespaceQuery>,
+    State(app_state): State<AppState>,
+    auth_ctx: Option<Extension<AuthContext>>,
+) -> Result<Json<CloneReport>, StatusCode> {
+    let auth_ctx = auth_ctx.ok_or(StatusCode::UNAUTHORIZED)?;
+    let src = ConfigNamespace {
+        tenant: src_tenant,
+        app: src_app,
+        env: src_env,
+    };
+    let dst = ConfigNamespace {
+        tenant: dst_tenant,
+        app: dst_app,
+        env: dst_env,
+    };
+
+    require_namespace_permission(&app_state, &auth_ctx, &src, actions::READ).await?;
+    require_namespace_permission(&app_state, &auth_ctx, &dst, actions::WRITE).await?;
+
+    info!(
+        "User {} cloning namespace {}/{}/{} to {}/{}/{}",
+        auth_ctx.user_id, src.tenant, src.app, src.env, dst.tenant, dst.app, dst.env
+    );
+
+    match app_state
+        .core_handle
+        .store()
+        .clone_namespace(&src, &dst, query.overwrite)
+        .await
+    {
+        Ok(report) => Ok(Json(report)),
+        Err(ConfluxError::Validation(e)) => {
+            warn!("Rejected namespace clone: {}", e);
+            Err(StatusCode::BAD_REQUEST)
+        }
+        Err(e) => {
+            error!("Namespace clone failed: {}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// 通过Raft提交命名空间命令，失败的写入响应映射为HTTP状态码
+///
+/// 命名空间中没有可处理的配置时返回404
+async fn submit(
+    app_state: &AppState,
+    auth_ctx: &AuthContext,
+    command: RaftCommand,
+) -> Result<ClientWriteResponse, StatusCode> {
+    match app_state.core_handle.write(command, Some(auth_ctx)).await {
+        Ok(response) if response.success => Ok(response),
+        Ok(response) => {
+            warn!("Namespace command rejected: {}", response.message);
+            if response.message.contains("No ") && response.message.contains("found") {
+                Err(StatusCode::NOT_FOUND)
+            } else {
+                Err(StatusCode::BAD_REQUEST)
+            }
+        }
+        Err(e) => {
+            error!("Failed to submit namespace command: {}", e);
+            Err(write_error_status(&e))
+        }
+    }
+}
+
+/// 命名空间软删除处理器
+/// DELETE /api/v1/namespaces/{tenant}/{app}/{env}?after_days=7
+///
+/// 需要命名空间管理权限，`after_days` 天内可通过恢复端点还原
+pub async fn soft_delete_namespace_handler(
+    Path((tenant, app, env)): Path<(String, String, String)>,
+    Query(query): Query<SoftDeleteNamespaceQuery>,
+    State(app_state): State<AppState>,
+    auth_ctx: Option<Extension<AuthContext>>,
+) -> Result<Json<Value>, StatusCode> {
+    let auth_ctx = auth_ctx.ok_or(StatusCode::UNAUTHORIZED)?;
+    let namespace = ConfigNamespace { tenant, app, env };
+    require_namespace_permission(&app_state, &auth_ctx, &namespace, actions::ADMIN).await?;
+
+    info!(
+        "User {} soft-deletes namespace {} with a {} day recovery window",
+        auth_ctx.user_id, namespace, query.after_days
+    );
+    let response = submit(
+        &app_state,
+        &auth_ctx,
+        RaftCommand::SoftDeleteNamespace {
+            namespace,
+            delete_after_days: query.after_days,
+            deleted_at: Utc::now(),
+        },
+    )
+    .await?;
+
+    Ok(Json(json!({
+        "success": true,
+        "data": response.data,
+        "message": response.message
+    })))
+}
+
+/// 命名空间恢复处理器
+/// POST /api/v1/namespaces/{tenant}/{app}/{env}/undelete
+///
+/// 需要命名空间管理权限，只能恢复仍在可恢复窗口内的配置
+pub async fn undelete_namespace_handler(
+    Path((tenant, app, env)): Path<(String, String, String)>,
+    State(app_state): State<AppState>,
+    auth_ctx: Option<Extension<AuthContext>>,
+) -> Result<Json<Value>, StatusCode> {
+    let auth_ctx = auth_ctx.ok_or(StatusCode::UNAUTHORIZED)?;
+    let namespace = ConfigNamespace { tenant, app, env };
+    require_namespace_permission(&app_state, &auth_ctx, &namespace, actions::ADMIN).await?;
+
+    info!("User {} restores namespace {}", auth_ctx.user_id, namespace);
+    let response = submit(
+        &app_state,
+        &auth_ctx,
+        RaftCommand::UndeleteNamespace {
+            namespace,
+            now: Utc::now(),
+        },
+    )
+    .await?;
+
+    Ok(Json(json!({
+        "success": true,
+        "data": response.data,
+        "message": response.message
+    })))
+}
+
+/// 命名空间存储压缩处理器
+/// POST /api/v1/namespaces/{tenant}/{app}/{env}/compact
+///
+/// 仅租户管理员可调用。压缩本节点上该命名空间的配置和版本数据，回收已删除版本占用的磁盘空间，
+/// 不经过Raft，也不影响其他节点
+pub async fn compact_namespace_handler(
+    Path((tenant, app, env)): Path<(String, String, String)>,
+    State(app_state): State<AppState>,
+    auth_ctx: Option<Extension<AuthContext>>,
+) -> Result<Json<CompactionStats>, StatusCode> {
+    let auth_ctx = auth_ctx.ok_or(StatusCode::UNAUTHORIZED)?;
+    match app_state
+        .core_handle
+        .authz_service()
+        .is_tenant_admin(&auth_ctx.user_id, &tenant)
+        .await
+    {
+        Ok(true) => {}
+        Ok(false) => {
+            warn!("Tenant admin permission denied for user {}", auth_ctx.user_id);
+            return Err(StatusCode::FORBIDDEN);
+        }
+        Err(e) => {
+            error!("Tenant admin check failed: {}", e);
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    }
+
+    let namespace = ConfigNamespace { tenant, app, env };
+    info!("User {} compacts namespace {}", auth_ctx.user_id, namespace);
+    match app_state.core_handle.store().compact_namespace(&namespace).await {
+        Ok(stats) => Ok(Json(stats)),
+        Err(e) => {
+            error!("Failed to compact namespace {}: {}", namespace, e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::{roles, AuthzService};
+    use crate::protocol::http::test_support::local_app_state;
+    use crate::raft::store::{StateMachineManager, Store};
+    use crate::raft::types::{ConfigFormat, RaftCommand};
+    use std::sync::Arc;
+    use tempfile::TempDir;
+
+    async fn create_app_state(temp_dir: &TempDir) -> AppState {
+        let (store, rx) = Store::new(temp_dir.path()).await.unwrap();
+        let store = Arc::new(store);
+        let mut manager = StateMachineManager::new(store.clone(), rx);
+        tokio::spawn(async move { manager.run().await });
+
+        let authz_service = Arc::new(AuthzService::new_in_memory().await.unwrap());
+        authz_service
+            .add_permission_for_role(roles::VIEWER, "acme", "/tenants/acme/*", actions::READ)
+            .await
+            .unwrap();
+        authz_service
+            .add_permission_for_role(roles::DEVELOPER, "acme", "/tenants/acme/*", actions::WRITE)
+            .await
+            .unwrap();
+        authz_service
+            .assign_role_to_user("reader", roles::VIEWER, "acme")
+            .await
+            .unwrap();
+        for role in [roles::VIEWER, roles::DEVELOPER] {
+            authz_service.assign_role_to_user("writer", role, "acme").await.unwrap();
+        }
+        authz_service
+            .assign_role_to_user("admin", roles::TENANT_ADMIN, "acme")
+            .await
+            .unwrap();
+
+        local_app_state(store, authz_service)
+    }
+
+    async fn clone(app_state: &AppState, user: &str) -> Result<Json<CloneReport>, StatusCode> {
+        let path = ["acme", "web", "prod", "acme", "web", "staging"].map(String::from);
+        clone_namespace_handler(
+            Path(path.into()),
+            Query(CloneNamespaceQuery::default()),
+            State(app_state.clone()),
+            Some(Extension(AuthContext::new(user.to_string(), "acme".to_string()))),
+        )
+        .await
+    }
+
+    #[tokio::test]
+    async fn test_clone_namespace_handler_checks_permissions() {
+        let temp_dir = TempDir::new().unwrap();
+        let app_state = create_app_state(&temp_dir).await;
+        app_state
+            .core_handle
+            .store()
+            .apply_command(&RaftCommand::CreateConfig {
+                namespace: ConfigNamespace {
+                    tenant: "acme".to_string(),
+                    app: "web".to_string(),
+                    env: "staging".to_string(),
+                },
+                name: "app.json".to_string(),
+                content: b"{}".to_vec(),
+                format: ConfigFormat::Json,
+                schema: None,
+                creator_id: 1,
+                description: "initial".to_string(),
+            })
+            .await
+            .unwrap();
+
+        // 只有读权限的用户不能写入目标命名空间
+        assert_eq!(clone(&app_state, "reader").await.unwrap_err(), StatusCode::FORBIDDEN);
+
+        let Json(report) = clone(&app_state, "writer").await.unwrap();
+        assert_eq!(report.cloned, 1);
+        assert_eq!(report.failed, 0);
+    }
+
+    #[tokio::test]
+    async fn test_compact_namespace_requires_tenant_admin() {
+        let temp_dir = TempDir::new().unwrap();
+        let app_state = create_app_state(&temp_dir).await;
+        let compact = |user: &str| {
+            compact_namespace_handler(
+                Path(("acme".to_string(), "web".to_string(), "prod".to_string())),
+                State(app_state.clone()),
+                Some(Extension(AuthContext::new(user.to_string(), "acme".to_string()))),
+            )
+        };
+
+        assert_eq!(compact("writer").await.unwrap_err(), StatusCode::FORBIDDEN);
+        let Json(stats) = compact("admin").await.unwrap();
+        assert_eq!(stats.space_reclaimed_estimate_bytes, 0);
+    }
+}
@@ -0,0 +1,134 @@
+//! 权限查询HTTP处理器
+//!
+//! 供前端一次性查询当前用户对多个资源的操作权限
+
+use super::{AppState, PermissionCheckItem, PermissionCheckResult};
+use crate::auth::AuthContext;
+use axum::{extract::State, http::StatusCode, response::Json, Extension};
+use tracing::{debug, error, warn};
+
+/// 单次批量检查允许的最大条目数
+pub const MAX_PERMISSION_BATCH_SIZE: usize = 1000;
+
+/// 批量权限检查处理器
+/// POST /api/v1/permissions/check-batch
+///
+/// 请求体为 `[{"resource": ..., "action": ...}]`，以当前认证用户和租户进行检查，
+/// 返回与请求顺序一致的检查结果
+pub async fn check_batch_handler(
+    State(app_state): State<AppState>,
+    auth_ctx: Option<Extension<AuthContext>>,
+    Json(items): Json<Vec<PermissionCheckItem>>,
+) -> Result<Json<Vec<PermissionCheckResult>>, StatusCode> {
+    let auth_ctx = auth_ctx.ok_or(StatusCode::UNAUTHORIZED)?;
+
+    if items.len() > MAX_PERMISSION_BATCH_SIZE {
+        warn!(
+            "Permission batch of {} items exceeds limit {}",
+            items.len(),
+            MAX_PERMISSION_BATCH_SIZE
+        );
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    debug!(
+        "Batch checking {} permissions for user={}, tenant={}",
+        items.len(),
+        auth_ctx.user_id,
+        auth_ctx.tenant_id
+    );
+
+    let requests: Vec<(&str, &str)> = items
+        .iter()
+        .map(|item| (item.resource.as_str(), item.action.as_str()))
+        .collect();
+    let allowed = app_state
+        .core_handle
+        .authz_service()
+        .check_batch(&auth_ctx.user_id, &auth_ctx.tenant_id, &requests)
+        .await
+        .map_err(|e| {
+            error!("Batch permission check failed: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    Ok(Json(
+        items
+            .into_iter()
+            .zip(allowed)
+            .map(|(item, allowed)| PermissionCheckResult {
+                resource: item.resource,
+                action: item.action,
+                allowed,
+            })
+            .collect(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::{actions, roles, AuthzService};
+    use crate::protocol::http::test_support::{create_store_with_configs, local_app_state};
+    use std::sync::Arc;
+    use tempfile::TempDir;
+
+    async fn create_app_state(temp_dir: &TempDir) -> AppState {
+        let store = create_store_with_configs(temp_dir, &[]).await;
+        let authz_service = Arc::new(AuthzService::new_in_memory().await.unwrap());
+        authz_service
+            .add_permission_for_role(roles::VIEWER, "tenant1", "/tenants/tenant1/*", actions::READ)
+            .await
+            .unwrap();
+        authz_service
+            .assign_role_to_user("alice", roles::VIEWER, "tenant1")
+            .await
+            .unwrap();
+
+        local_app_state(store, authz_service)
+    }
+
+    fn item(resource: &str, action: &str) -> PermissionCheckItem {
+        PermissionCheckItem {
+            resource: resource.to_string(),
+            action: action.to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_check_batch_handler_preserves_order() {
+        let temp_dir = TempDir::new().unwrap();
+        let app_state = create_app_state(&temp_dir).await;
+        let ctx = AuthContext::new("alice".to_string(), "tenant1".to_string());
+
+        let Json(results) = check_batch_handler(
+            State(app_state),
+            Some(Extension(ctx)),
+            Json(vec![
+                item("/tenants/tenant1/configs", actions::WRITE),
+                item("/tenants/tenant1/configs", actions::READ),
+                item("/tenants/tenant2/configs", actions::READ),
+            ]),
+        )
+        .await
+        .unwrap();
+
+        let allowed: Vec<bool> = results.iter().map(|r| r.allowed).collect();
+        assert_eq!(allowed, vec![false, true, false]);
+        assert_eq!(results[1].action, actions::READ);
+    }
+
+    #[tokio::test]
+    async fn test_check_batch_handler_requires_auth_and_limits_size() {
+        let temp_dir = TempDir::new().unwrap();
+        let app_state = create_app_state(&temp_dir).await;
+
+        let result = check_batch_handler(State(app_state.clone()), None, Json(vec![])).await;
+        assert_eq!(result.unwrap_err(), StatusCode::UNAUTHORIZED);
+
+        let ctx = AuthContext::new("alice".to_string(), "tenant1".to_string());
+        let items = vec![item("/r", actions::READ); MAX_PERMISSION_BATCH_SIZE + 1];
+        let result = check_batch_handler(State(app_state), Some(Extension(ctx)), Json(items)).await;
+        assert_eq!(result.unwrap_err(), StatusCode::BAD_REQUEST);
+    }
+}
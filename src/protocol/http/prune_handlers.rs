@@ -0,0 +1,132 @@
+//! 配置版本清理HTTP处理器
+//!
+//! 配置可以设置保留的最大版本数，超出时自动删除最旧的版本；也可以按需清理。
+//! 被发布规则引用的版本和最新版本永远不会被删除
+
+use super::namespace_handlers::require_namespace_permission;
+use super::{write_error_status, AppState, PrunePolicyRequest, PruneVersionsRequest};
+use crate::auth::{actions, AuthContext};
+use crate::raft::types::{ClientWriteResponse, Config, ConfigNamespace, RaftCommand};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Json,
+    Extension,
+};
+use serde_json::{json, Value};
+use tracing::{error, info, warn};
+
+/// 检查命名空间管理权限并查找配置
+async fn find_config(
+    app_state: &AppState,
+    auth_ctx: &AuthContext,
+    namespace: &ConfigNamespace,
+    name: &str,
+) -> Result<Config, StatusCode> {
+    require_namespace_permission(app_state, auth_ctx, namespace, actions::ADMIN).await?;
+
+    app_state
+        .core_handle
+        .store()
+        .get_config(namespace, name)
+        .await
+        .ok_or_else(|| {
+            warn!("Config not found: {}/{}", namespace, name);
+            StatusCode::NOT_FOUND
+        })
+}
+
+/// 通过Raft提交清理命令，失败的写入响应映射为HTTP状态码
+///
+/// 配置不存在返回404，其他失败（如保留数量为0）返回400
+async fn submit(
+    app_state: &AppState,
+    auth_ctx: &AuthContext,
+    command: RaftCommand,
+) -> Result<ClientWriteResponse, StatusCode> {
+    match app_state.core_handle.write(command, Some(auth_ctx)).await {
+        Ok(response) if response.success => Ok(response),
+        Ok(response) => {
+            warn!("Prune command rejected: {}", response.message);
+            if response.message.contains("not found") {
+                Err(StatusCode::NOT_FOUND)
+            } else {
+                Err(StatusCode::BAD_REQUEST)
+            }
+        }
+        Err(e) => {
+            error!("Failed to submit prune command: {}", e);
+            Err(write_error_status(&e))
+        }
+    }
+}
+
+/// 设置配置版本清理策略处理器
+/// PUT /api/v1/configs/{tenant}/{app}/{env}/{name}/prune-policy
+///
+/// 需要命名空间管理权限，新策略会立即应用于已有版本
+pub async fn set_prune_policy_handler(
+    Path((tenant, app, env, name)): Path<(String, String, String, String)>,
+    State(app_state): State<AppState>,
+    auth_ctx: Option<Extension<AuthContext>>,
+    Json(request): Json<PrunePolicyRequest>,
+) -> Result<Json<Value>, StatusCode> {
+    let auth_ctx = auth_ctx.ok_or(StatusCode::UNAUTHORIZED)?;
+    let namespace = ConfigNamespace { tenant, app, env };
+    let config = find_config(&app_state, &auth_ctx, &namespace, &name).await?;
+
+    info!(
+        "User {} sets max versions of config {} to {:?}",
+        auth_ctx.user_id, config.id, request.max_versions
+    );
+    let response = submit(
+        &app_state,
+        &auth_ctx,
+        RaftCommand::SetPrunePolicy {
+            config_id: config.id,
+            max_versions: request.max_versions,
+        },
+    )
+    .await?;
+
+    Ok(Json(json!({
+        "success": true,
+        "data": response.data,
+        "message": response.message
+    })))
+}
+
+/// 按需清理配置版本处理器
+/// POST /api/v1/configs/{tenant}/{app}/{env}/{name}/prune
+///
+/// 需要命名空间管理权限，保留最新的 `keep_last` 个版本
+pub async fn prune_versions_handler(
+    Path((tenant, app, env, name)): Path<(String, String, String, String)>,
+    State(app_state): State<AppState>,
+    auth_ctx: Option<Extension<AuthContext>>,
+    Json(request): Json<PruneVersionsRequest>,
+) -> Result<Json<Value>, StatusCode> {
+    let auth_ctx = auth_ctx.ok_or(StatusCode::UNAUTHORIZED)?;
+    let namespace = ConfigNamespace { tenant, app, env };
+    let config = find_config(&app_state, &auth_ctx, &namespace, &name).await?;
+
+    info!(
+        "User {} prunes config {} to the last {} versions",
+        auth_ctx.user_id, config.id, request.keep_last
+    );
+    let response = submit(
+        &app_state,
+        &auth_ctx,
+        RaftCommand::PruneVersions {
+            config_id: config.id,
+            keep_last: request.keep_last,
+        },
+    )
+    .await?;
+
+    Ok(Json(json!({
+        "success": true,
+        "data": response.data,
+        "message": response.message
+    })))
+}
@@ -0,0 +1,84 @@
+//! 租户配额HTTP处理器
+//!
+//! 集群管理员通过 `/_cluster/tenants/{tenant_id}/quota` 设置租户可创建的配置数、单个配置的
+//! 版本数和内容总字节数上限；租户通过 `/api/v1/tenants/{tenant_id}/quota`
+//! 查看配额和当前用量。超出配额的写入返回422
+
+use super::{require_cluster_admin, write_error_status, AppState, SetTenantQuotaRequest};
+use crate::auth::AuthContext;
+use crate::raft::store::TenantQuotaUsage;
+use crate::raft::types::RaftCommand;
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Json,
+    Extension,
+};
+use serde_json::{json, Value};
+use tracing::{error, info, warn};
+
+/// 设置租户配额处理器
+/// PUT /_cluster/tenants/{tenant_id}/quota
+///
+/// 所有限制都未提供时清除该租户的配额
+pub async fn set_tenant_quota_handler(
+    Path(tenant_id): Path<String>,
+    State(app_state): State<AppState>,
+    auth_ctx: Option<Extension<AuthContext>>,
+    Json(request): Json<SetTenantQuotaRequest>,
+) -> Result<Json<Value>, StatusCode> {
+    require_cluster_admin(&app_state, auth_ctx.as_deref()).await?;
+
+    let command = RaftCommand::SetTenantQuota {
+        tenant: tenant_id.clone(),
+        max_configs: request.max_configs,
+        max_versions_per_config: request.max_versions_per_config,
+        max_total_bytes: request.max_total_bytes,
+    };
+
+    match app_state.core_handle.write(command, auth_ctx.as_deref()).await {
+        Ok(response) if response.success => {
+            info!("Quota of tenant {} updated", tenant_id);
+            Ok(Json(json!({
+                "success": true,
+                "data": response.data,
+                "message": response.message
+            })))
+        }
+        Ok(response) => {
+            warn!("Set tenant quota rejected: {}", response.message);
+            Err(StatusCode::BAD_REQUEST)
+        }
+        Err(e) => {
+            error!("Failed to set quota of tenant {}: {}", tenant_id, e);
+            Err(write_error_status(&e))
+        }
+    }
+}
+
+/// 查询租户配额和用量处理器
+/// GET /api/v1/tenants/{tenant_id}/quota
+pub async fn tenant_quota_usage_handler(
+    Path(tenant_id): Path<String>,
+    State(app_state): State<AppState>,
+    auth_ctx: Option<Extension<AuthContext>>,
+) -> Result<Json<TenantQuotaUsage>, StatusCode> {
+    let Extension(auth_ctx) = auth_ctx.ok_or(StatusCode::UNAUTHORIZED)?;
+    if auth_ctx.tenant_id != tenant_id {
+        warn!(
+            "User {} of tenant {} denied access to quota of tenant {}",
+            auth_ctx.user_id, auth_ctx.tenant_id, tenant_id
+        );
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    app_state
+        .core_handle
+        .store()
+        .tenant_quota_usage(&tenant_id)
+        .map(Json)
+        .map_err(|e| {
+            error!("Failed to read quota of tenant {}: {}", tenant_id, e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })
+}
@@ -0,0 +1,193 @@
+//! 配置Schema注册表HTTP处理器
+//!
+//! Schema按租户注册，配置通过ID引用后，新版本内容必须符合该Schema
+
+use super::namespace_handlers::require_namespace_permission;
+use super::{write_error_status, AppState, ConfigSchemaRequest, RegisterSchemaRequest};
+use crate::auth::{actions, AuthContext, ResourcePath};
+use crate::raft::types::{ClientWriteResponse, ConfigNamespace, RaftCommand};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Json,
+    Extension,
+};
+use serde_json::{json, Value};
+use tracing::{error, info, warn};
+
+/// 检查租户Schema注册表的权限
+///
+/// 注册表属于租户级管理资源，资源路径为 `/tenants/{tenant}/admin/schemas`
+///
+/// # Arguments
+/// * `app_state` - 应用状态
+/// * `auth_ctx` - 认证上下文
+/// * `tenant` - 租户名称
+/// * `action` - 需要的操作权限
+async fn require_schema_permission(
+    app_state: &AppState,
+    auth_ctx: &AuthContext,
+    tenant: &str,
+    action: &str,
+) -> Result<(), StatusCode> {
+    let resource = ResourcePath::admin(tenant, "schemas");
+    match app_state
+        .core_handle
+        .authz_service()
+        .check(&auth_ctx.user_id, tenant, &resource, action)
+        .await
+    {
+        Ok(true) => Ok(()),
+        Ok(false) => {
+            warn!(
+                "Permission denied: user={}, resource={}, action={}",
+                auth_ctx.user_id, resource, action
+            );
+            Err(StatusCode::FORBIDDEN)
+        }
+        Err(e) => {
+            error!("Permission check failed: {}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// 通过Raft提交Schema命令，失败的写入响应映射为HTTP状态码
+///
+/// 配置或Schema不存在返回404，其他失败（如Schema文档无效）返回400
+async fn submit(
+    app_state: &AppState,
+    auth_ctx: &AuthContext,
+    command: RaftCommand,
+) -> Result<ClientWriteResponse, StatusCode> {
+    match app_state.core_handle.write(command, Some(auth_ctx)).await {
+        Ok(response) if response.success => Ok(response),
+        Ok(response) => {
+            warn!("Schema command rejected: {}", response.message);
+            if response.message.contains("not found") {
+                Err(StatusCode::NOT_FOUND)
+            } else {
+                Err(StatusCode::BAD_REQUEST)
+            }
+        }
+        Err(e) => {
+            error!("Failed to submit schema command: {}", e);
+            Err(write_error_status(&e))
+        }
+    }
+}
+
+/// 注册Schema处理器
+/// POST /api/v1/schemas/{tenant}
+///
+/// 需要租户管理权限，同名Schema会被替换并保留原ID
+pub async fn register_schema_handler(
+    Path(tenant): Path<String>,
+    State(app_state): State<AppState>,
+    auth_ctx: Option<Extension<AuthContext>>,
+    Json(request): Json<RegisterSchemaRequest>,
+) -> Result<Json<Value>, StatusCode> {
+    let auth_ctx = auth_ctx.ok_or(StatusCode::UNAUTHORIZED)?;
+    require_schema_permission(&app_state, &auth_ctx, &tenant, actions::ADMIN).await?;
+
+    info!("User {} registers schema {} for tenant {}", auth_ctx.user_id, request.name, tenant);
+    let response = submit(
+        &app_state,
+        &auth_ctx,
+        RaftCommand::RegisterSchema {
+            tenant,
+            name: request.name,
+            schema: request.schema.to_string(),
+        },
+    )
+    .await?;
+
+    Ok(Json(json!({
+        "success": true,
+        "data": response.data,
+        "message": response.message
+    })))
+}
+
+/// 查询租户Schema列表处理器
+/// GET /api/v1/schemas/{tenant}
+pub async fn list_schemas_handler(
+    Path(tenant): Path<String>,
+    State(app_state): State<AppState>,
+    auth_ctx: Option<Extension<AuthContext>>,
+) -> Result<Json<Value>, StatusCode> {
+    let auth_ctx = auth_ctx.ok_or(StatusCode::UNAUTHORIZED)?;
+    require_schema_permission(&app_state, &auth_ctx, &tenant, actions::READ).await?;
+
+    let schemas = app_state.core_handle.store().list_schemas(&tenant).await;
+    Ok(Json(json!({
+        "tenant": tenant,
+        "schemas": schemas
+    })))
+}
+
+/// 按名称查询Schema处理器
+/// GET /api/v1/schemas/{tenant}/{name}
+pub async fn get_schema_handler(
+    Path((tenant, name)): Path<(String, String)>,
+    State(app_state): State<AppState>,
+    auth_ctx: Option<Extension<AuthContext>>,
+) -> Result<Json<Value>, StatusCode> {
+    let auth_ctx = auth_ctx.ok_or(StatusCode::UNAUTHORIZED)?;
+    require_schema_permission(&app_state, &auth_ctx, &tenant, actions::READ).await?;
+
+    app_state
+        .core_handle
+        .store()
+        .list_schemas(&tenant)
+        .await
+        .into_iter()
+        .find(|schema| schema.name == name)
+        .map(|schema| Json(json!(schema)))
+        .ok_or(StatusCode::NOT_FOUND)
+}
+
+/// 设置配置引用的Schema处理器
+/// PUT /api/v1/configs/{tenant}/{app}/{env}/{name}/schema
+///
+/// 需要命名空间管理权限，只能引用同一租户注册的Schema；已有版本不会被重新校验
+pub async fn set_config_schema_handler(
+    Path((tenant, app, env, name)): Path<(String, String, String, String)>,
+    State(app_state): State<AppState>,
+    auth_ctx: Option<Extension<AuthContext>>,
+    Json(request): Json<ConfigSchemaRequest>,
+) -> Result<Json<Value>, StatusCode> {
+    let auth_ctx = auth_ctx.ok_or(StatusCode::UNAUTHORIZED)?;
+    let namespace = ConfigNamespace { tenant, app, env };
+    require_namespace_permission(&app_state, &auth_ctx, &namespace, actions::ADMIN).await?;
+
+    let config = app_state
+        .core_handle
+        .store()
+        .get_config(&namespace, &name)
+        .await
+        .ok_or_else(|| {
+            warn!("Config not found: {}/{}", namespace, name);
+            StatusCode::NOT_FOUND
+        })?;
+
+    info!(
+        "User {} sets schema of config {} to {:?}",
+        auth_ctx.user_id, config.id, request.schema_id
+    );
+    let response = submit(
+        &app_state,
+        &auth_ctx,
+        RaftCommand::SetConfigSchema {
+            config_id: config.id,
+            schema_id: request.schema_id,
+        },
+    )
+    .await?;
+
+    Ok(Json(json!({
+        "success": true,
+        "data": response.data,
+        "message": response.message
+    })))
+}
@@ -1,10 +1,12 @@
-use crate::raft::types::{ConfigFormat, ConfigNamespace, Release};
+use crate::raft::types::{ConfigFilter, ConfigFormat, ConfigNamespace, ConfigVersion, Release};
+use base64::{prelude::BASE64_STANDARD, Engine};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 /// 创建配置版本请求
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CreateVersionRequest {
-    /// 配置内容
+    /// 配置内容（二进制格式为base64编码）
     pub content: String,
     /// 配置格式（可选，如果不提供则继承配置的默认格式）
     pub format: Option<ConfigFormat>,
@@ -23,6 +25,140 @@ pub struct UpdateReleasesRequest {
     pub updater_id: Option<String>,
 }
 
+/// 定时发布请求
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduleReleaseRequest {
+    /// 要发布的版本ID
+    pub version_id: u64,
+    /// 发布规则标签（为空时为默认发布）
+    #[serde(default)]
+    pub labels: BTreeMap<String, String>,
+    /// 生效时间（RFC3339），必须晚于当前时间
+    pub effective_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// 金丝雀发布请求
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CanaryReleaseRequest {
+    /// 金丝雀版本ID
+    pub version_id: u64,
+    /// 发布规则标签（为空时作用于所有客户端）
+    #[serde(default)]
+    pub labels: BTreeMap<String, String>,
+    /// 匹配客户端中获得金丝雀版本的百分比（0-100），按客户端 `instance_id` 标签稳定分配
+    pub percent: u8,
+}
+
+/// 版本流量权重
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReleaseWeight {
+    /// 版本ID
+    pub version_id: u64,
+    /// 相对流量权重，0表示不分配流量
+    pub weight: u32,
+}
+
+/// 设置发布流量权重请求
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SetReleaseWeightsRequest {
+    /// 参与分流的版本及其权重，权重之和必须大于0
+    pub weights: Vec<ReleaseWeight>,
+}
+
+/// 设置租户配额请求，未提供的限制表示不限
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SetTenantQuotaRequest {
+    /// 最大配置数量
+    #[serde(default)]
+    pub max_configs: Option<u64>,
+    /// 单个配置的最大版本数量
+    #[serde(default)]
+    pub max_versions_per_config: Option<u64>,
+    /// 所有版本内容的最大总字节数
+    #[serde(default)]
+    pub max_total_bytes: Option<u64>,
+}
+
+/// 批量权限检查中的单个条目
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PermissionCheckItem {
+    /// 资源路径
+    pub resource: String,
+    /// 操作类型
+    pub action: String,
+}
+
+/// 批量权限检查中的单个结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PermissionCheckResult {
+    /// 资源路径
+    pub resource: String,
+    /// 操作类型
+    pub action: String,
+    /// 是否允许
+    pub allowed: bool,
+}
+
+/// 命名空间克隆查询参数
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CloneNamespaceQuery {
+    /// 目标命名空间已存在同名配置时是否覆盖
+    #[serde(default)]
+    pub overwrite: bool,
+}
+
+/// 命名空间软删除查询参数
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SoftDeleteNamespaceQuery {
+    /// 可恢复窗口天数，过期后配置被永久清除
+    #[serde(default = "default_recovery_days")]
+    pub after_days: u8,
+}
+
+impl Default for SoftDeleteNamespaceQuery {
+    fn default() -> Self {
+        Self {
+            after_days: default_recovery_days(),
+        }
+    }
+}
+
+fn default_recovery_days() -> u8 {
+    7
+}
+
+/// 一致性检查查询参数
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ConsistencyCheckQuery {
+    /// 发现不一致时从磁盘重新加载内存状态
+    #[serde(default)]
+    pub repair: bool,
+}
+
+/// 存储压缩查询参数
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CompactStorageQuery {
+    /// 只压缩该列族；为空时压缩全部列族
+    pub cf: Option<String>,
+}
+
+/// 写请求的试运行查询参数
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DryRunQuery {
+    /// 只校验请求并返回将得到的结果，不实际写入
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// 版本对比查询参数
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiffVersionsQuery {
+    /// 旧版本ID
+    pub from: u64,
+    /// 新版本ID
+    pub to: u64,
+}
+
 /// 获取配置响应
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FetchConfigResponse {
@@ -30,7 +166,7 @@ pub struct FetchConfigResponse {
     pub namespace: ConfigNamespace,
     /// 配置名称
     pub name: String,
-    /// 配置内容
+    /// 配置内容（二进制格式为base64编码）
     pub content: String,
     /// 配置格式
     pub format: ConfigFormat,
@@ -42,6 +178,141 @@ pub struct FetchConfigResponse {
     pub created_at: chrono::DateTime<chrono::Utc>,
 }
 
+impl FetchConfigResponse {
+    /// 由发布的版本构造响应，二进制内容编码为base64
+    pub fn from_version(namespace: ConfigNamespace, name: String, version: ConfigVersion) -> Self {
+        Self {
+            namespace,
+            name,
+            content: match version.format {
+                ConfigFormat::Binary => BASE64_STANDARD.encode(&version.content),
+                _ => String::from_utf8_lossy(&version.content).into_owned(),
+            },
+            format: version.format,
+            version_id: version.id,
+            hash: version.content_hash,
+            created_at: version.created_at,
+        }
+    }
+}
+
+/// 批量获取配置时单次请求最多包含的配置数
+pub const MAX_BATCH_FETCH_ITEMS: usize = 100;
+
+/// 批量获取配置请求
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchFetchRequest {
+    /// 要获取的配置
+    pub configs: Vec<BatchFetchItem>,
+}
+
+/// 批量获取中的一个配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchFetchItem {
+    pub tenant: String,
+    pub app: String,
+    pub env: String,
+    pub name: String,
+    /// 用于匹配发布规则的客户端标签
+    #[serde(default)]
+    pub labels: BTreeMap<String, String>,
+}
+
+/// 批量获取中一个配置的结果，成功时包含配置，失败时包含错误
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchFetchEntry {
+    pub namespace: ConfigNamespace,
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub config: Option<FetchConfigResponse>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<BatchFetchError>,
+}
+
+/// 批量获取中单个配置的错误
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchFetchError {
+    /// 错误代码：`not_found` 或 `forbidden`
+    pub code: String,
+    pub message: String,
+}
+
+/// 批量获取配置响应，结果顺序与请求一致
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchFetchResponse {
+    pub results: Vec<BatchFetchEntry>,
+    /// 成功获取的配置数
+    pub succeeded: usize,
+    /// 获取失败的配置数
+    pub failed: usize,
+}
+
+/// 配置搜索查询参数
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SearchConfigsQuery {
+    /// 命名空间，格式为 "tenant/app/env"
+    pub namespace: Option<String>,
+    /// 配置名称前缀
+    pub name_prefix: Option<String>,
+    /// 创建时间下限（RFC3339）
+    pub created_after: Option<chrono::DateTime<chrono::Utc>>,
+    /// 创建时间上限（RFC3339）
+    pub created_before: Option<chrono::DateTime<chrono::Utc>>,
+    /// 版本创建者ID
+    pub creator_id: Option<u64>,
+    /// 版本描述关键字
+    pub q: Option<String>,
+}
+
+impl SearchConfigsQuery {
+    /// 转换为存储层使用的过滤条件
+    ///
+    /// 命名空间格式不正确时返回 `None`
+    pub fn into_filter(self) -> Option<ConfigFilter> {
+        let namespace = match self.namespace {
+            Some(ns) => {
+                let parts: Vec<&str> = ns.split('/').collect();
+                match parts.as_slice() {
+                    [tenant, app, env] if !tenant.is_empty() && !app.is_empty() && !env.is_empty() => {
+                        Some(ConfigNamespace {
+                            tenant: tenant.to_string(),
+                            app: app.to_string(),
+                            env: env.to_string(),
+                        })
+                    }
+                    _ => return None,
+                }
+            }
+            None => None,
+        };
+
+        Some(ConfigFilter {
+            tenant: None,
+            namespace,
+            name_prefix: self.name_prefix,
+            created_after: self.created_after,
+            created_before: self.created_before,
+            creator_id: self.creator_id,
+            description_contains: self.q,
+        })
+    }
+}
+
+/// 配置列表查询参数
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ListConfigsQuery {
+    /// 租户
+    pub tenant: String,
+    /// 应用
+    pub app: String,
+    /// 环境
+    pub env: String,
+    /// 配置名称前缀
+    pub prefix: Option<String>,
+    /// 标签查询表达式，匹配发布规则的标签，例如 `env=prod AND region IN us-east-1`
+    pub label_query: Option<String>,
+}
+
 /// 通用API响应
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ApiResponse<T> {
@@ -202,10 +473,173 @@ pub struct RemoveNodeRequest {
     pub force: Option<bool>,
 }
 
+/// 领导权移交请求
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TransferLeadershipRequest {
+    /// 目标节点ID（可选，不提供时自动选择）
+    pub target: Option<u64>,
+}
+
+/// 节点选举优先级设置请求
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SetNodePriorityRequest {
+    /// 选举优先级（0-255），数值越高越优先成为领导者
+    pub priority: u8,
+}
+
+/// 对等节点地址更新请求
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdatePeerAddressRequest {
+    /// 新地址，格式为 `ip:port`
+    pub address: String,
+}
+
+/// 添加配置依赖请求
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AddDependencyRequest {
+    /// 被依赖的配置ID
+    pub depends_on: u64,
+}
+
+/// 配置回滚请求
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RollbackRequest {
+    /// 回滚到的版本ID（可选，不提供时回滚到当前发布版本的上一个版本）
+    pub version_id: Option<u64>,
+    /// 是否按逆拓扑顺序级联回滚依赖此配置的其他配置
+    #[serde(default)]
+    pub cascade: bool,
+}
+
+/// 命名空间发布审批策略请求
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApprovalPolicyRequest {
+    /// 发布版本前是否需要审批
+    pub requires_approval: bool,
+}
+
+/// 注册配置Schema请求
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegisterSchemaRequest {
+    /// Schema名称，同一租户内唯一，重复注册会替换原有内容
+    pub name: String,
+    /// JSON Schema文档
+    pub schema: serde_json::Value,
+}
+
+/// 设置配置引用的Schema请求
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigSchemaRequest {
+    /// 引用的Schema ID，为空时取消引用
+    pub schema_id: Option<u64>,
+}
+
+/// 设置配置版本清理策略请求
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrunePolicyRequest {
+    /// 保留的最大版本数，为空时使用命名空间默认值
+    #[serde(default)]
+    pub max_versions: Option<u32>,
+}
+
+/// 按需清理配置版本请求
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PruneVersionsRequest {
+    /// 保留的最新版本数
+    pub keep_last: u32,
+}
+
+/// 事务中的单个操作，作用于 `tenant/app/env/name` 指定的已有配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransactionOperation {
+    /// 租户
+    pub tenant: String,
+    /// 应用
+    pub app: String,
+    /// 环境
+    pub env: String,
+    /// 配置名称
+    pub name: String,
+    /// 操作内容，由 `op` 字段区分类型
+    #[serde(flatten)]
+    pub action: TransactionAction,
+}
+
+/// 事务操作类型
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum TransactionAction {
+    /// 创建新版本
+    CreateVersion {
+        /// 配置内容（二进制格式为base64编码）
+        content: String,
+        /// 配置格式（可选，默认继承最新版本的格式）
+        #[serde(default)]
+        format: Option<ConfigFormat>,
+        /// 版本描述（可选）
+        #[serde(default)]
+        description: Option<String>,
+    },
+    /// 替换发布规则
+    UpdateReleases {
+        /// 新的发布规则列表
+        releases: Vec<Release>,
+    },
+    /// 将指定版本设为默认发布
+    ReleaseVersion {
+        /// 版本ID
+        version_id: u64,
+    },
+}
+
+/// 批量创建配置时单次请求最多包含的配置数
+pub const MAX_BULK_CONFIG_ENTRIES: usize = 1000;
+
+/// 批量创建配置请求体的最大字节数
+pub const MAX_BULK_PAYLOAD_BYTES: usize = 10 * 1024 * 1024;
+
+/// 批量创建中的单个配置，`tenant/app/env` 可以各不相同
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BulkCreateConfigItem {
+    /// 租户
+    pub tenant: String,
+    /// 应用
+    pub app: String,
+    /// 环境
+    pub env: String,
+    /// 配置名称
+    pub name: String,
+    /// 配置内容（二进制格式为base64编码）
+    pub content: String,
+    /// 配置格式
+    pub format: ConfigFormat,
+    /// JSON Schema（可选）
+    #[serde(default)]
+    pub schema: Option<String>,
+    /// 配置描述（可选）
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+/// 注册Webhook请求
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateWebhookRequest {
+    /// 接收变更事件的http或https地址
+    pub url: String,
+    /// 只通知该应用的变更，为空时通知租户下所有应用
+    #[serde(default)]
+    pub app: Option<String>,
+    /// 只通知该环境的变更，为空时通知所有环境
+    #[serde(default)]
+    pub env: Option<String>,
+    /// HMAC签名密钥，设置后请求携带 `X-Conflux-Signature` 头
+    #[serde(default)]
+    pub secret: Option<String>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::collections::BTreeMap;
 
     #[test]
     fn test_create_version_request_serialization() {
@@ -262,4 +696,60 @@ mod tests {
         assert_eq!(request.releases.len(), deserialized.releases.len());
         assert_eq!(request.updater_id, deserialized.updater_id);
     }
+
+    #[test]
+    fn test_update_releases_request_accepts_rollout_percentage() {
+        let request: UpdateReleasesRequest = serde_json::from_str(
+            r#"{"releases": [
+                {"labels": {}, "version_id": 1, "priority": 0},
+                {"labels": {}, "version_id": 2, "priority": 1, "rollout_percentage": 20}
+            ]}"#,
+        )
+        .unwrap();
+        assert_eq!(request.releases[0].canary_percent, None);
+        assert_eq!(request.releases[1].canary_percent, Some(20));
+    }
+
+    #[test]
+    fn test_schedule_release_request_defaults_labels() {
+        let request: ScheduleReleaseRequest = serde_json::from_str(
+            r#"{"version_id": 2, "effective_at": "2030-01-01T00:00:00Z"}"#,
+        )
+        .unwrap();
+        assert_eq!(request.version_id, 2);
+        assert!(request.labels.is_empty());
+        assert_eq!(request.effective_at.to_rfc3339(), "2030-01-01T00:00:00+00:00");
+    }
+
+    #[test]
+    fn test_search_configs_query_into_filter() {
+        let query = SearchConfigsQuery {
+            namespace: Some("tenant1/app1/prod".to_string()),
+            creator_id: Some(7),
+            q: Some("database".to_string()),
+            ..Default::default()
+        };
+        let filter = query.into_filter().unwrap();
+        assert_eq!(filter.namespace.unwrap().env, "prod");
+        assert_eq!(filter.creator_id, Some(7));
+        assert_eq!(filter.description_contains, Some("database".to_string()));
+
+        let invalid = SearchConfigsQuery {
+            namespace: Some("tenant1/app1".to_string()),
+            ..Default::default()
+        };
+        assert!(invalid.into_filter().is_none());
+
+        assert_eq!(SearchConfigsQuery::default().into_filter(), Some(ConfigFilter::default()));
+    }
+
+    #[test]
+    fn test_transfer_leadership_request_target_is_optional() {
+        let request: TransferLeadershipRequest = serde_json::from_str("{}").unwrap();
+        assert_eq!(request.target, None);
+
+        let request: TransferLeadershipRequest =
+            serde_json::from_str(r#"{"target": 3}"#).unwrap();
+        assert_eq!(request.target, Some(3));
+    }
 }
@@ -0,0 +1,175 @@
+//! 租户级集群管理HTTP处理器
+//!
+//! `/api/v1/tenants/{tenant_id}/cluster` 下的集群状态查询和节点增删端点。
+//! 路径中的租户必须与认证上下文的租户一致（由 [`tenant_isolation_middleware`] 保证），
+//! 并按租户检查 `CLUSTER_VIEW_METRICS`、`CLUSTER_ADD_NODE`、`CLUSTER_REMOVE_NODE` 权限
+//!
+//! [`tenant_isolation_middleware`]: super::tenant_isolation_middleware
+
+use super::{AddNodeRequest, AppState};
+use crate::auth::{actions, AuthContext, ResourcePath};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Json,
+    Extension,
+};
+use serde_json::{json, Value};
+use tracing::{error, info, warn};
+
+/// 检查请求者在路径租户中是否拥有指定操作的权限
+///
+/// # Arguments
+/// * `app_state` - 应用状态
+/// * `auth_ctx` - 认证上下文
+/// * `tenant_id` - 路径中的租户
+/// * `resource` - 检查的资源路径
+/// * `action` - 检查的操作
+///
+/// # Returns
+/// 有权限时返回认证上下文；未认证返回401，租户不一致或无权限返回403
+async fn require_tenant_permission(
+    app_state: &AppState,
+    auth_ctx: Option<Extension<AuthContext>>,
+    tenant_id: &str,
+    resource: &str,
+    action: &str,
+) -> Result<AuthContext, StatusCode> {
+    let Extension(auth_ctx) = auth_ctx.ok_or(StatusCode::UNAUTHORIZED)?;
+    // 中间件之外再检查一次，处理器不会被用于其他租户的资源
+    if auth_ctx.tenant_id != tenant_id {
+        warn!(
+            "User {} of tenant {} denied access to tenant {}",
+            auth_ctx.user_id, auth_ctx.tenant_id, tenant_id
+        );
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    match app_state
+        .core_handle
+        .authz_service()
+        .check(&auth_ctx.user_id, tenant_id, resource, action)
+        .await
+    {
+        Ok(true) => Ok(auth_ctx),
+        Ok(false) => {
+            warn!(
+                "Permission {} denied for user {} in tenant {}",
+                action, auth_ctx.user_id, tenant_id
+            );
+            Err(StatusCode::FORBIDDEN)
+        }
+        Err(e) => {
+            error!("Permission check failed: {}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// 租户集群状态处理器
+/// GET /api/v1/tenants/{tenant_id}/cluster/status
+pub async fn tenant_cluster_status_handler(
+    Path(tenant_id): Path<String>,
+    State(app_state): State<AppState>,
+    auth_ctx: Option<Extension<AuthContext>>,
+) -> Result<Json<Value>, StatusCode> {
+    require_tenant_permission(
+        &app_state,
+        auth_ctx,
+        &tenant_id,
+        &ResourcePath::cluster_metrics(&tenant_id),
+        actions::CLUSTER_VIEW_METRICS,
+    )
+    .await?;
+
+    match app_state.core_handle.raft_client().get_cluster_status().await {
+        Ok(status) => Ok(Json(json!(status))),
+        Err(e) => {
+            error!("Failed to get cluster status: {}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// 租户添加节点处理器
+/// POST /api/v1/tenants/{tenant_id}/cluster/nodes
+pub async fn tenant_add_node_handler(
+    Path(tenant_id): Path<String>,
+    State(app_state): State<AppState>,
+    auth_ctx: Option<Extension<AuthContext>>,
+    Json(request): Json<AddNodeRequest>,
+) -> Result<Json<Value>, StatusCode> {
+    let auth_ctx = require_tenant_permission(
+        &app_state,
+        auth_ctx,
+        &tenant_id,
+        &ResourcePath::cluster_node(&tenant_id, request.node_id),
+        actions::CLUSTER_ADD_NODE,
+    )
+    .await?;
+
+    let raft_node = app_state
+        .core_handle
+        .raft_client()
+        .raft_node()
+        .ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
+    let node = raft_node.read().await;
+
+    node.add_node_with_auth(request.node_id, request.address.clone(), Some(auth_ctx))
+        .await
+        .map_err(|e| {
+            error!("Failed to add node {}: {}", request.node_id, e);
+            e.status_code()
+        })?;
+
+    info!(
+        "Node {} at {} added by tenant {}",
+        request.node_id, request.address, tenant_id
+    );
+    Ok(Json(json!({
+        "success": true,
+        "node_id": request.node_id,
+        "address": request.address
+    })))
+}
+
+/// 租户移除节点处理器
+/// DELETE /api/v1/tenants/{tenant_id}/cluster/nodes/{node_id}
+pub async fn tenant_remove_node_handler(
+    Path((tenant_id, node_id)): Path<(String, u64)>,
+    State(app_state): State<AppState>,
+    auth_ctx: Option<Extension<AuthContext>>,
+) -> Result<Json<Value>, StatusCode> {
+    let auth_ctx = require_tenant_permission(
+        &app_state,
+        auth_ctx,
+        &tenant_id,
+        &ResourcePath::cluster_node(&tenant_id, node_id),
+        actions::CLUSTER_REMOVE_NODE,
+    )
+    .await?;
+
+    let raft_node = app_state
+        .core_handle
+        .raft_client()
+        .raft_node()
+        .ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
+    let node = raft_node.read().await;
+
+    node.remove_node_with_auth(node_id, Some(auth_ctx))
+        .await
+        .map_err(|e| {
+            error!("Failed to remove node {}: {}", node_id, e);
+            e.status_code()
+        })?;
+
+    info!("Node {} removed by tenant {}", node_id, tenant_id);
+    Ok(Json(json!({
+        "success": true,
+        "node_id": node_id
+    })))
+}
+
+#[cfg(test)]
+#[path = "tenant_cluster_handlers_tests.rs"]
+mod tests;
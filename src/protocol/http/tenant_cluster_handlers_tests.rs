@@ -0,0 +1,167 @@
+use super::*;
+use crate::auth::{roles, AuthzService};
+use crate::protocol::http::create_v1_routes;
+use crate::protocol::http::test_support::{create_store_with_configs, local_app_state};
+use axum::{
+    body::Body,
+    http::{header::CONTENT_TYPE, Method, Request},
+    Router,
+};
+use std::sync::Arc;
+use tempfile::TempDir;
+use tower::ServiceExt;
+
+/// 创建包含 acme 租户配置的应用状态
+///
+/// 用户 `ops` 是 globex 租户的集群管理员，用户 `viewer` 是 acme 租户的集群查看者
+async fn create_app_state(temp_dir: &TempDir) -> AppState {
+    let store = create_store_with_configs(temp_dir, &["app.json"]).await;
+
+    let authz_service = Arc::new(AuthzService::new_in_memory().await.unwrap());
+    for action in [
+        actions::CLUSTER_ADMIN,
+        actions::CLUSTER_ADD_NODE,
+        actions::CLUSTER_REMOVE_NODE,
+        actions::CLUSTER_VIEW_METRICS,
+        actions::READ,
+    ] {
+        authz_service
+            .add_permission_for_role(roles::CLUSTER_ADMIN, "globex", "/tenants/globex/*", action)
+            .await
+            .unwrap();
+    }
+    authz_service
+        .add_permission_for_role(
+            roles::CLUSTER_VIEWER,
+            "acme",
+            "/tenants/acme/*",
+            actions::CLUSTER_VIEW_METRICS,
+        )
+        .await
+        .unwrap();
+    authz_service
+        .assign_role_to_user("ops", roles::CLUSTER_ADMIN, "globex")
+        .await
+        .unwrap();
+    authz_service
+        .assign_role_to_user("viewer", roles::CLUSTER_VIEWER, "acme")
+        .await
+        .unwrap();
+
+    local_app_state(store, authz_service)
+}
+
+fn user(user_id: &str, tenant_id: &str) -> Option<AuthContext> {
+    Some(AuthContext::new(user_id.to_string(), tenant_id.to_string()))
+}
+
+/// 通过v1路由（含租户隔离中间件）发送请求，返回响应状态码
+async fn send(
+    app_state: &AppState,
+    auth_ctx: Option<AuthContext>,
+    method: Method,
+    uri: &str,
+    body: Option<Value>,
+) -> StatusCode {
+    let mut router = Router::new()
+        .nest("/api/v1", create_v1_routes())
+        .with_state(app_state.clone());
+    if let Some(auth_ctx) = auth_ctx {
+        router = router.layer(Extension(auth_ctx));
+    }
+
+    let builder = Request::builder().method(method).uri(uri);
+    let request = match body {
+        Some(body) => builder
+            .header(CONTENT_TYPE, "application/json")
+            .body(Body::from(body.to_string())),
+        None => builder.body(Body::empty()),
+    };
+    router.oneshot(request.unwrap()).await.unwrap().status()
+}
+
+#[tokio::test]
+async fn test_cluster_admin_of_other_tenant_is_blocked() {
+    let temp_dir = TempDir::new().unwrap();
+    let app_state = create_app_state(&temp_dir).await;
+    let ops = || user("ops", "globex");
+    let node = json!({ "node_id": 2, "address": "127.0.0.1:8081" });
+
+    // 在自己的租户中可以查看集群状态
+    let status = send(&app_state, ops(), Method::GET, "/api/v1/tenants/globex/cluster/status", None).await;
+    assert_eq!(status, StatusCode::OK);
+
+    // 集群管理员角色不能跨租户使用
+    let status = send(&app_state, ops(), Method::GET, "/api/v1/tenants/acme/cluster/status", None).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let status = send(&app_state, ops(), Method::POST, "/api/v1/tenants/acme/cluster/nodes", Some(node)).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let status = send(&app_state, ops(), Method::DELETE, "/api/v1/tenants/acme/cluster/nodes/2", None).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    // 配置和版本等存储操作同样按路径租户隔离
+    let status = send(&app_state, ops(), Method::GET, "/api/v1/configs/acme/app/prod/app.json", None).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let status = send(&app_state, ops(), Method::GET, "/api/v1/configs/acme/app/prod/app.json/versions", None).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn test_tenant_cluster_permissions() {
+    let temp_dir = TempDir::new().unwrap();
+    let app_state = create_app_state(&temp_dir).await;
+    let viewer = || user("viewer", "acme");
+
+    let status = send(&app_state, viewer(), Method::GET, "/api/v1/tenants/acme/cluster/status", None).await;
+    assert_eq!(status, StatusCode::OK);
+    let status = send(&app_state, viewer(), Method::GET, "/api/v1/configs/acme/app/prod/app.json", None).await;
+    assert_eq!(status, StatusCode::OK);
+
+    // 查看者没有增删节点的权限
+    let node = json!({ "node_id": 2, "address": "127.0.0.1:8081" });
+    let status = send(&app_state, viewer(), Method::POST, "/api/v1/tenants/acme/cluster/nodes", Some(node)).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let status = send(&app_state, viewer(), Method::DELETE, "/api/v1/tenants/acme/cluster/nodes/2", None).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    // 同租户其他没有角色的用户也被拒绝
+    let status = send(&app_state, user("guest", "acme"), Method::GET, "/api/v1/tenants/acme/cluster/status", None).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn test_tenant_isolation_middleware() {
+    let temp_dir = TempDir::new().unwrap();
+    let app_state = create_app_state(&temp_dir).await;
+
+    // 路径中有租户参数但未认证
+    let status = send(&app_state, None, Method::GET, "/api/v1/tenants/acme/cluster/status", None).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    // 命名空间克隆的源租户也必须是自己的租户
+    let status = send(
+        &app_state,
+        user("viewer", "acme"),
+        Method::POST,
+        "/api/v1/namespaces/acme/app/staging/clone-from/globex/app/prod",
+        Some(json!({})),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn test_handler_rejects_mismatched_tenant() {
+    let temp_dir = TempDir::new().unwrap();
+    let app_state = create_app_state(&temp_dir).await;
+
+    // 不经过中间件直接调用处理器时同样拒绝
+    let status = tenant_cluster_status_handler(
+        Path("acme".to_string()),
+        State(app_state),
+        user("ops", "globex").map(Extension),
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(status, StatusCode::FORBIDDEN);
+}
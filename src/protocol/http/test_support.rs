@@ -0,0 +1,79 @@
+//! HTTP处理器测试共用的辅助函数
+
+use super::AppState;
+use crate::app::CoreAppHandle;
+use crate::auth::{actions, roles, AuthzService, JwtAuthenticator};
+use crate::raft::client::RaftClient;
+use crate::raft::store::Store;
+use crate::raft::types::{ConfigFormat, ConfigNamespace, RaftCommand};
+use std::sync::Arc;
+use tempfile::TempDir;
+
+/// 以给定的Raft客户端、存储和权限服务创建应用状态
+pub(crate) fn app_state(
+    raft_client: RaftClient,
+    store: Arc<Store>,
+    authz_service: Arc<AuthzService>,
+) -> AppState {
+    AppState::new(CoreAppHandle::new(
+        Arc::new(raft_client),
+        store,
+        authz_service,
+        Arc::new(JwtAuthenticator::new("test-secret", 1)),
+    ))
+}
+
+/// 创建写请求直接应用到本地存储的应用状态
+pub(crate) fn local_app_state(store: Arc<Store>, authz_service: Arc<AuthzService>) -> AppState {
+    app_state(RaftClient::new(store.clone()), store, authz_service)
+}
+
+/// 在临时目录中创建存储，并在 acme/app/prod 命名空间下创建给定名称的空JSON配置
+pub(crate) async fn create_store_with_configs(temp_dir: &TempDir, names: &[&str]) -> Arc<Store> {
+    let (store, _rx) = Store::new(temp_dir.path()).await.unwrap();
+    for name in names {
+        create_config_in(&store, "acme", name).await;
+    }
+    Arc::new(store)
+}
+
+/// 在给定租户的 app/prod 命名空间下创建空JSON配置
+pub(crate) async fn create_config_in(store: &Store, tenant: &str, name: &str) {
+    store
+        .apply_command(&RaftCommand::CreateConfig {
+            namespace: ConfigNamespace {
+                tenant: tenant.to_string(),
+                app: "app".to_string(),
+                env: "prod".to_string(),
+            },
+            name: name.to_string(),
+            content: b"{}".to_vec(),
+            format: ConfigFormat::Json,
+            schema: None,
+            creator_id: 1,
+            description: "initial".to_string(),
+        })
+        .await
+        .unwrap();
+}
+
+/// 创建权限服务，用户 `writer` 在给定的每个租户中都有读写权限
+///
+/// 用于验证跨租户的请求被租户隔离拒绝，而不是因为缺少权限
+pub(crate) async fn authz_with_writer(tenants: &[&str]) -> Arc<AuthzService> {
+    let authz_service = Arc::new(AuthzService::new_in_memory().await.unwrap());
+    for tenant in tenants {
+        let resource = format!("/tenants/{}/*", tenant);
+        for action in [actions::READ, actions::WRITE] {
+            authz_service
+                .add_permission_for_role(roles::DEVELOPER, tenant, &resource, action)
+                .await
+                .unwrap();
+        }
+        authz_service
+            .assign_role_to_user("writer", roles::DEVELOPER, tenant)
+            .await
+            .unwrap();
+    }
+    authz_service
+}
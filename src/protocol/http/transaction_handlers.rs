@@ -0,0 +1,143 @@
+//! 多配置原子事务HTTP处理器
+//!
+//! 多个配置需要一起变更时，所有操作作为一条Raft日志提交，要么全部生效，要么全部回滚
+
+use super::namespace_handlers::require_namespace_permission;
+use super::version_body::decode_version_content;
+use super::{write_error_status, AppState, TransactionAction, TransactionOperation};
+use crate::auth::{actions, AuthContext};
+use crate::raft::types::{ConfigNamespace, RaftCommand};
+use axum::{extract::State, http::StatusCode, response::Json, Extension};
+use serde_json::{json, Value};
+use tracing::{error, info, warn};
+
+/// 事务处理器
+/// POST /api/v1/transactions
+///
+/// 请求体为操作数组。调用者只能修改本租户的配置，并需要每个操作对应命名空间的写权限，
+/// 否则整个请求返回403。
+/// 任一操作失败时整个事务回滚并返回400，引用的配置不存在时返回404
+pub async fn transaction_handler(
+    State(app_state): State<AppState>,
+    auth_ctx: Option<Extension<AuthContext>>,
+    Json(operations): Json<Vec<TransactionOperation>>,
+) -> Result<Json<Value>, StatusCode> {
+    let auth_ctx = auth_ctx.ok_or(StatusCode::UNAUTHORIZED)?;
+    if operations.is_empty() {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let mut commands = Vec::with_capacity(operations.len());
+    for operation in operations {
+        let namespace = ConfigNamespace {
+            tenant: operation.tenant,
+            app: operation.app,
+            env: operation.env,
+        };
+        if namespace.tenant != auth_ctx.tenant_id {
+            warn!(
+                "Tenant isolation violation: user {} of tenant {} transacted on {}/{}",
+                auth_ctx.user_id, auth_ctx.tenant_id, namespace, operation.name
+            );
+            return Err(StatusCode::FORBIDDEN);
+        }
+        require_namespace_permission(&app_state, &auth_ctx, &namespace, actions::WRITE).await?;
+
+        let config = app_state
+            .core_handle
+            .store()
+            .get_config(&namespace, &operation.name)
+            .await
+            .ok_or_else(|| {
+                warn!("Config not found: {}/{}", namespace, operation.name);
+                StatusCode::NOT_FOUND
+            })?;
+
+        commands.push(match operation.action {
+            TransactionAction::CreateVersion {
+                content,
+                format,
+                description,
+            } => RaftCommand::CreateVersion {
+                config_id: config.id,
+                content: decode_version_content(
+                    app_state.core_handle.store(),
+                    &config,
+                    content,
+                    format.as_ref(),
+                )
+                .await?,
+                format,
+                creator_id: auth_ctx.user_id.parse().unwrap_or(0),
+                description: description.unwrap_or_else(|| "Created via transaction".to_string()),
+            },
+            TransactionAction::UpdateReleases { releases } => RaftCommand::UpdateReleaseRules {
+                config_id: config.id,
+                releases,
+            },
+            TransactionAction::ReleaseVersion { version_id } => RaftCommand::ReleaseVersion {
+                config_id: config.id,
+                version_id,
+            },
+        });
+    }
+
+    info!("User {} submits a transaction of {} operations", auth_ctx.user_id, commands.len());
+    match app_state
+        .core_handle
+        .write(RaftCommand::Transaction { commands }, Some(&auth_ctx))
+        .await
+    {
+        Ok(response) if response.success => Ok(Json(json!({
+            "success": true,
+            "data": response.data,
+            "message": response.message
+        }))),
+        Ok(response) => {
+            warn!("Transaction rejected: {}", response.message);
+            Err(StatusCode::BAD_REQUEST)
+        }
+        Err(e) => {
+            error!("Failed to submit transaction: {}", e);
+            Err(write_error_status(&e))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::http::test_support::{
+        authz_with_writer, create_config_in, create_store_with_configs, local_app_state,
+    };
+    use tempfile::TempDir;
+
+    fn release(tenant: &str) -> TransactionOperation {
+        TransactionOperation {
+            tenant: tenant.to_string(),
+            app: "app".to_string(),
+            env: "prod".to_string(),
+            name: "app.json".to_string(),
+            action: TransactionAction::ReleaseVersion { version_id: 1 },
+        }
+    }
+
+    #[tokio::test]
+    async fn test_transaction_rejects_other_tenant_operations() {
+        let temp_dir = TempDir::new().unwrap();
+        let store = create_store_with_configs(&temp_dir, &["app.json"]).await;
+        create_config_in(&store, "other", "app.json").await;
+        let app_state = local_app_state(store, authz_with_writer(&["acme", "other"]).await);
+        let auth_ctx = AuthContext::new("writer".to_string(), "acme".to_string());
+
+        // 即使用户在另一个租户中也有写权限，请求体中的其他租户命名空间仍被拒绝
+        let status = transaction_handler(
+            State(app_state),
+            Some(Extension(auth_ctx)),
+            Json(vec![release("acme"), release("other")]),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(status, StatusCode::FORBIDDEN);
+    }
+}
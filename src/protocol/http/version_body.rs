@@ -0,0 +1,193 @@
+//! 创建版本请求体解析
+//!
+//! `application/json` 请求体为 [`CreateVersionRequest`]；其他类型的请求体即为配置内容本身，
+//! 格式由 `Content-Type` 决定，未提供或为 `application/octet-stream` 时根据内容自动检测。
+//! 二进制配置（[`ConfigFormat::Binary`]）的内容在 [`CreateVersionRequest`] 中以base64编码
+
+use super::CreateVersionRequest;
+use crate::raft::store::Store;
+use crate::raft::types::{Config, ConfigFormat};
+use axum::{
+    body::Bytes,
+    http::{header::CONTENT_TYPE, HeaderMap, StatusCode},
+};
+use base64::{prelude::BASE64_STANDARD, Engine};
+use tracing::warn;
+
+/// 根据 `Content-Type` 解析创建版本的请求体
+///
+/// # Arguments
+/// * `headers` - 请求头
+/// * `body` - 原始请求体
+///
+/// # Returns
+/// 格式已确定的创建版本请求（JSON请求未指定格式时为None，沿用配置最新版本的格式）。
+/// 请求体无法解析返回400，格式无法唯一识别返回400，不支持的 `Content-Type` 返回415
+pub(super) fn parse_version_body(
+    headers: &HeaderMap,
+    body: Bytes,
+) -> Result<CreateVersionRequest, StatusCode> {
+    let media_type = headers
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.split(';').next())
+        .map(|value| value.trim().to_ascii_lowercase());
+
+    let mut request = match media_type.as_deref() {
+        Some(media) if media == "application/json" || media.ends_with("+json") => {
+            serde_json::from_slice(&body).map_err(|e| {
+                warn!("Invalid create version request: {}", e);
+                StatusCode::BAD_REQUEST
+            })?
+        }
+        media => {
+            let format = match media {
+                None | Some("application/octet-stream") => detect_format(&body)?,
+                Some(media) => media_type_format(media).ok_or_else(|| {
+                    warn!("Unsupported content type for config version: {}", media);
+                    StatusCode::UNSUPPORTED_MEDIA_TYPE
+                })?,
+            };
+            let content = if format == ConfigFormat::Binary {
+                BASE64_STANDARD.encode(&body)
+            } else {
+                String::from_utf8(body.to_vec()).map_err(|_| {
+                    warn!("Config version content is not valid UTF-8");
+                    StatusCode::BAD_REQUEST
+                })?
+            };
+            CreateVersionRequest {
+                content,
+                format: Some(format),
+                creator_id: None,
+                description: None,
+            }
+        }
+    };
+
+    if request.format == Some(ConfigFormat::Unknown) {
+        let detected = detect_format(request.content.as_bytes())?;
+        if detected == ConfigFormat::Binary {
+            request.content = BASE64_STANDARD.encode(&request.content);
+        }
+        request.format = Some(detected);
+    }
+    Ok(request)
+}
+
+/// 取出版本请求中的配置内容
+///
+/// 二进制配置的内容以base64传输，在此解码；未指定格式的请求沿用配置最新版本的格式。
+///
+/// # Returns
+/// 要写入的配置内容，base64无效时返回400
+pub(super) async fn decode_version_content(
+    store: &Store,
+    config: &Config,
+    content: String,
+    format: Option<&ConfigFormat>,
+) -> Result<Vec<u8>, StatusCode> {
+    let binary = match format {
+        Some(format) => *format == ConfigFormat::Binary,
+        None => store
+            .get_config_version(config.id, config.latest_version_id)
+            .await
+            .is_some_and(|latest| latest.format == ConfigFormat::Binary),
+    };
+    if !binary {
+        return Ok(content.into_bytes());
+    }
+    BASE64_STANDARD.decode(content.trim()).map_err(|e| {
+        warn!("Binary config version content is not valid base64: {}", e);
+        StatusCode::BAD_REQUEST
+    })
+}
+
+/// 自动检测配置内容的格式，无法唯一识别时返回400
+fn detect_format(content: &[u8]) -> Result<ConfigFormat, StatusCode> {
+    ConfigFormat::detect(content).ok_or_else(|| {
+        warn!("Could not detect the format of config version content");
+        StatusCode::BAD_REQUEST
+    })
+}
+
+/// 将配置内容的媒体类型映射为配置格式
+fn media_type_format(media_type: &str) -> Option<ConfigFormat> {
+    match media_type {
+        "application/yaml" | "application/x-yaml" | "text/yaml" | "text/x-yaml" => {
+            Some(ConfigFormat::Yaml)
+        }
+        "application/toml" | "text/x-toml" => Some(ConfigFormat::Toml),
+        "text/x-java-properties" => Some(ConfigFormat::Properties),
+        "application/xml" | "text/xml" => Some(ConfigFormat::Xml),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    fn headers(content_type: Option<&'static str>) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        if let Some(content_type) = content_type {
+            headers.insert(CONTENT_TYPE, HeaderValue::from_static(content_type));
+        }
+        headers
+    }
+
+    fn format_of(content_type: Option<&'static str>, body: &'static [u8]) -> Result<Option<ConfigFormat>, StatusCode> {
+        parse_version_body(&headers(content_type), Bytes::from_static(body)).map(|r| r.format)
+    }
+
+    #[test]
+    fn test_json_request_keeps_explicit_or_inherited_format() {
+        let request = parse_version_body(
+            &headers(Some("application/json; charset=utf-8")),
+            Bytes::from_static(br#"{"content": "a: 1", "description": "yaml"}"#),
+        )
+        .unwrap();
+        assert_eq!(request.content, "a: 1");
+        assert_eq!(request.format, None);
+        assert_eq!(request.description.as_deref(), Some("yaml"));
+
+        let format = format_of(
+            Some("application/json"),
+            br#"{"content": "a = 1", "format": "Unknown"}"#,
+        );
+        assert_eq!(format, Ok(Some(ConfigFormat::Toml)));
+    }
+
+    #[test]
+    fn test_content_type_takes_precedence_over_detection() {
+        assert_eq!(format_of(Some("application/yaml"), b"a = 1"), Ok(Some(ConfigFormat::Yaml)));
+        assert_eq!(format_of(Some("text/plain"), b"a = 1"), Err(StatusCode::UNSUPPORTED_MEDIA_TYPE));
+    }
+
+    #[test]
+    fn test_raw_content_is_detected() {
+        assert_eq!(format_of(None, br#"{"a": 1}"#), Ok(Some(ConfigFormat::Json)));
+        assert_eq!(format_of(Some("application/octet-stream"), b"a = 1"), Ok(Some(ConfigFormat::Toml)));
+        assert_eq!(format_of(None, b"a:\n  - 1\n"), Ok(Some(ConfigFormat::Yaml)));
+        assert_eq!(format_of(None, b"plain text"), Err(StatusCode::BAD_REQUEST));
+    }
+
+    #[test]
+    fn test_binary_content_is_base64_encoded() {
+        let request = parse_version_body(&headers(None), Bytes::from_static(b"\xff\xfe\x00")).unwrap();
+        assert_eq!(request.format, Some(ConfigFormat::Binary));
+        assert_eq!(request.content, "//4A");
+
+        // 指定了文本格式的内容必须是UTF-8
+        assert_eq!(format_of(Some("application/yaml"), b"\xff\xfe"), Err(StatusCode::BAD_REQUEST));
+
+        let request = parse_version_body(
+            &headers(Some("application/json")),
+            Bytes::from_static(br#"{"content": "//4A", "format": "Binary"}"#),
+        )
+        .unwrap();
+        assert_eq!(request.format, Some(ConfigFormat::Binary));
+        assert_eq!(request.content, "//4A");
+    }
+}
@@ -0,0 +1,173 @@
+//! Webhook注册HTTP处理器
+//!
+//! Webhook按租户注册，配置变更事件由领导者节点以JSON格式POST到匹配的地址
+
+use super::{write_error_status, AppState, CreateWebhookRequest};
+use crate::auth::{actions, AuthContext, ResourcePath};
+use crate::raft::types::{ClientWriteResponse, RaftCommand, Webhook};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Json,
+    Extension,
+};
+use serde_json::{json, Value};
+use tracing::{error, info, warn};
+
+/// 检查租户Webhook的权限
+///
+/// Webhook属于租户级管理资源，资源路径为 `/tenants/{tenant}/admin/webhooks`
+///
+/// # Arguments
+/// * `app_state` - 应用状态
+/// * `auth_ctx` - 认证上下文
+/// * `tenant` - 租户名称
+/// * `action` - 需要的操作权限
+async fn require_webhook_permission(
+    app_state: &AppState,
+    auth_ctx: &AuthContext,
+    tenant: &str,
+    action: &str,
+) -> Result<(), StatusCode> {
+    let resource = ResourcePath::admin(tenant, "webhooks");
+    match app_state
+        .core_handle
+        .authz_service()
+        .check(&auth_ctx.user_id, tenant, &resource, action)
+        .await
+    {
+        Ok(true) => Ok(()),
+        Ok(false) => {
+            warn!(
+                "Permission denied: user={}, resource={}, action={}",
+                auth_ctx.user_id, resource, action
+            );
+            Err(StatusCode::FORBIDDEN)
+        }
+        Err(e) => {
+            error!("Permission check failed: {}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// 通过Raft提交Webhook命令，失败的写入响应映射为HTTP状态码
+///
+/// Webhook不存在返回404，其他失败（如地址无效）返回400
+async fn submit(
+    app_state: &AppState,
+    auth_ctx: &AuthContext,
+    command: RaftCommand,
+) -> Result<ClientWriteResponse, StatusCode> {
+    match app_state.core_handle.write(command, Some(auth_ctx)).await {
+        Ok(response) if response.success => Ok(response),
+        Ok(response) => {
+            warn!("Webhook command rejected: {}", response.message);
+            if response.message.contains("not found") {
+                Err(StatusCode::NOT_FOUND)
+            } else {
+                Err(StatusCode::BAD_REQUEST)
+            }
+        }
+        Err(e) => {
+            error!("Failed to submit webhook command: {}", e);
+            Err(write_error_status(&e))
+        }
+    }
+}
+
+/// Webhook的响应表示，不返回签名密钥
+fn webhook_json(webhook: &Webhook) -> Value {
+    json!({
+        "id": webhook.id,
+        "tenant": webhook.tenant,
+        "url": webhook.url,
+        "app": webhook.app,
+        "env": webhook.env,
+        "signed": webhook.secret.is_some(),
+        "created_at": webhook.created_at
+    })
+}
+
+/// 注册Webhook处理器
+/// POST /api/v1/webhooks/{tenant}
+///
+/// 需要租户管理权限，每次注册分配新的ID
+pub async fn create_webhook_handler(
+    Path(tenant): Path<String>,
+    State(app_state): State<AppState>,
+    auth_ctx: Option<Extension<AuthContext>>,
+    Json(request): Json<CreateWebhookRequest>,
+) -> Result<Json<Value>, StatusCode> {
+    let auth_ctx = auth_ctx.ok_or(StatusCode::UNAUTHORIZED)?;
+    require_webhook_permission(&app_state, &auth_ctx, &tenant, actions::ADMIN).await?;
+
+    info!("User {} registers webhook {} for tenant {}", auth_ctx.user_id, request.url, tenant);
+    let response = submit(
+        &app_state,
+        &auth_ctx,
+        RaftCommand::RegisterWebhook {
+            tenant,
+            url: request.url,
+            app: request.app,
+            env: request.env,
+            secret: request.secret,
+        },
+    )
+    .await?;
+
+    Ok(Json(json!({
+        "success": true,
+        "data": response.data,
+        "message": response.message
+    })))
+}
+
+/// 查询租户Webhook列表处理器
+/// GET /api/v1/webhooks/{tenant}
+pub async fn list_webhooks_handler(
+    Path(tenant): Path<String>,
+    State(app_state): State<AppState>,
+    auth_ctx: Option<Extension<AuthContext>>,
+) -> Result<Json<Value>, StatusCode> {
+    let auth_ctx = auth_ctx.ok_or(StatusCode::UNAUTHORIZED)?;
+    require_webhook_permission(&app_state, &auth_ctx, &tenant, actions::READ).await?;
+
+    let webhooks: Vec<Value> = app_state
+        .core_handle
+        .store()
+        .list_webhooks(&tenant)
+        .await
+        .iter()
+        .map(webhook_json)
+        .collect();
+    Ok(Json(json!({
+        "tenant": tenant,
+        "webhooks": webhooks
+    })))
+}
+
+/// 删除Webhook处理器
+/// DELETE /api/v1/webhooks/{tenant}/{id}
+pub async fn delete_webhook_handler(
+    Path((tenant, webhook_id)): Path<(String, u64)>,
+    State(app_state): State<AppState>,
+    auth_ctx: Option<Extension<AuthContext>>,
+) -> Result<Json<Value>, StatusCode> {
+    let auth_ctx = auth_ctx.ok_or(StatusCode::UNAUTHORIZED)?;
+    require_webhook_permission(&app_state, &auth_ctx, &tenant, actions::ADMIN).await?;
+
+    info!("User {} deletes webhook {} of tenant {}", auth_ctx.user_id, webhook_id, tenant);
+    let response = submit(
+        &app_state,
+        &auth_ctx,
+        RaftCommand::DeleteWebhook { tenant, webhook_id },
+    )
+    .await?;
+
+    Ok(Json(json!({
+        "success": true,
+        "data": response.data,
+        "message": response.message
+    })))
+}
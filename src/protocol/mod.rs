@@ -2,6 +2,13 @@ use crate::app::CoreAppHandle;
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+use tokio::task::{JoinError, JoinHandle};
+use tokio::time::Instant;
+use tokio_util::sync::CancellationToken;
+use tracing::{error, info, warn};
 
 pub mod http;
 
@@ -63,10 +70,14 @@ pub trait ProtocolPlugin: Send + Sync {
 
 /// 协议插件管理器
 /// 
-/// 负责管理和启动所有已注册的协议插件
+/// 负责管理、启动和关闭所有已注册的协议插件
 pub struct ProtocolManager {
-    plugins: Vec<Box<dyn ProtocolPlugin>>,
+    plugins: Vec<Arc<dyn ProtocolPlugin>>,
     configs: HashMap<String, ProtocolConfig>,
+    /// 关闭信号，每个插件任务持有其子令牌
+    cancel_token: CancellationToken,
+    /// 已启动的插件任务
+    handles: Mutex<Vec<(String, JoinHandle<()>)>>,
 }
 
 impl ProtocolManager {
@@ -75,12 +86,14 @@ impl ProtocolManager {
         Self {
             plugins: Vec::new(),
             configs: HashMap::new(),
+            cancel_token: CancellationToken::new(),
+            handles: Mutex::new(Vec::new()),
         }
     }
     
     /// 注册协议插件
     pub fn register_plugin(&mut self, plugin: Box<dyn ProtocolPlugin>) {
-        self.plugins.push(plugin);
+        self.plugins.push(Arc::from(plugin));
     }
     
     /// 设置协议配置
@@ -89,35 +102,103 @@ impl ProtocolManager {
     }
     
     /// 启动所有已注册的协议插件
-    pub async fn start_all(&self, core_handle: CoreAppHandle) -> anyhow::Result<Vec<tokio::task::JoinHandle<()>>> {
-        let mut handles = Vec::new();
-        
+    ///
+    /// 每个插件运行在独立的任务中，直到插件的`start`返回或收到关闭信号；
+    /// 任务退出前会调用插件的`shutdown`
+    pub async fn start_all(&self, core_handle: CoreAppHandle) -> anyhow::Result<()> {
+        let mut handles = self.handles.lock().await;
+
         for plugin in &self.plugins {
             let plugin_name = plugin.name();
-            let _config = self.configs.get(plugin_name)
+            let config = self.configs.get(plugin_name)
                 .cloned()
                 .unwrap_or_default();
 
-            let _core_handle_clone = core_handle.clone();
-            let plugin_name_owned = plugin_name.to_string();
-            
+            let plugin = plugin.clone();
+            let core_handle = core_handle.clone();
+            let cancel_token = self.cancel_token.child_token();
+
             // 为每个插件创建一个独立的任务
             let handle = tokio::spawn(async move {
-                // 注意：这里我们无法直接使用 plugin，因为它不是 Clone 的
-                // 在实际实现中，我们需要重新设计这个部分
-                tracing::info!("Starting protocol plugin: {}", plugin_name_owned);
-                
-                // TODO: 实际启动插件的逻辑需要在具体的插件实现中处理
-                // 这里只是一个占位符
-                loop {
-                    tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+                let name = plugin.name();
+                info!("Starting protocol plugin: {}", name);
+
+                tokio::select! {
+                    result = plugin.start(core_handle, config) => {
+                        if let Err(e) = result {
+                            error!("Protocol plugin {} failed: {}", name, e);
+                        }
+                    }
+                    _ = cancel_token.cancelled() => {
+                        info!("Protocol plugin {} received shutdown signal", name);
+                    }
+                }
+
+                if let Err(e) = plugin.shutdown().await {
+                    warn!("Protocol plugin {} failed to shut down cleanly: {}", name, e);
                 }
             });
-            
-            handles.push(handle);
+
+            handles.push((plugin_name.to_string(), handle));
         }
-        
-        Ok(handles)
+
+        Ok(())
+    }
+
+    /// 关闭所有协议插件
+    ///
+    /// 发出关闭信号后在超时时间内等待所有插件任务结束，超时仍未结束的任务会被强制中止
+    ///
+    /// # Arguments
+    /// * `timeout` - 等待所有插件任务结束的总时长
+    ///
+    /// # Returns
+    /// 按启动顺序返回每个插件任务的结束结果，被强制中止的任务返回取消错误
+    pub async fn shutdown_all(&self, timeout: Duration) -> Vec<Result<(), JoinError>> {
+        self.cancel_token.cancel();
+
+        let handles = std::mem::take(&mut *self.handles.lock().await);
+        let deadline = Instant::now() + timeout;
+        let mut results = Vec::with_capacity(handles.len());
+
+        for (name, mut handle) in handles {
+            let result = match tokio::time::timeout_at(deadline, &mut handle).await {
+                Ok(result) => result,
+                Err(_) => {
+                    warn!("Protocol plugin {} did not stop within {:?}, aborting", name, timeout);
+                    handle.abort();
+                    handle.await
+                }
+            };
+            results.push(result);
+        }
+
+        info!("All protocol plugins stopped");
+        results
+    }
+
+    /// 并发检查所有插件的健康状态
+    ///
+    /// # Returns
+    /// 按注册顺序返回`(插件名称, 是否健康)`，健康检查异常的插件视为不健康
+    pub async fn is_healthy(&self) -> Vec<(String, bool)> {
+        let checks: Vec<_> = self
+            .plugins
+            .iter()
+            .map(|plugin| {
+                let plugin = plugin.clone();
+                (
+                    plugin.name().to_string(),
+                    tokio::spawn(async move { plugin.health_check().await }),
+                )
+            })
+            .collect();
+
+        let mut results = Vec::with_capacity(checks.len());
+        for (name, check) in checks {
+            results.push((name, check.await.unwrap_or(false)));
+        }
+        results
     }
     
     /// 获取已注册的插件数量
@@ -140,9 +221,11 @@ impl Default for ProtocolManager {
 #[cfg(test)]
 mod tests {
     use super::*;
-    // use crate::raft::{RaftClient, Store}; // Commented out until needed
-    // use std::sync::Arc; // Commented out until needed
-    // use tempfile::TempDir; // Commented out until needed
+    use crate::auth::{AuthzService, JwtAuthenticator};
+    use crate::raft::client::RaftClient;
+    use crate::raft::store::Store;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use tempfile::TempDir;
 
     // 测试用的协议插件实现
     struct TestProtocol {
@@ -161,6 +244,60 @@ mod tests {
         }
     }
 
+    /// 一直运行到收到关闭信号的测试插件，记录shutdown是否被调用
+    struct LongRunningProtocol {
+        name: &'static str,
+        shut_down: Arc<AtomicBool>,
+        hang_on_shutdown: bool,
+    }
+
+    impl LongRunningProtocol {
+        fn new(name: &'static str, hang_on_shutdown: bool) -> (Self, Arc<AtomicBool>) {
+            let shut_down = Arc::new(AtomicBool::new(false));
+            let plugin = Self {
+                name,
+                shut_down: shut_down.clone(),
+                hang_on_shutdown,
+            };
+            (plugin, shut_down)
+        }
+    }
+
+    #[async_trait]
+    impl ProtocolPlugin for LongRunningProtocol {
+        fn name(&self) -> &'static str {
+            self.name
+        }
+
+        async fn start(&self, _core_handle: CoreAppHandle, _config: ProtocolConfig) -> anyhow::Result<()> {
+            std::future::pending::<()>().await;
+            Ok(())
+        }
+
+        async fn health_check(&self) -> bool {
+            !self.hang_on_shutdown
+        }
+
+        async fn shutdown(&self) -> anyhow::Result<()> {
+            self.shut_down.store(true, Ordering::SeqCst);
+            if self.hang_on_shutdown {
+                std::future::pending::<()>().await;
+            }
+            Ok(())
+        }
+    }
+
+    async fn create_core_handle(temp_dir: &TempDir) -> CoreAppHandle {
+        let (store, _rx) = Store::new(temp_dir.path()).await.unwrap();
+        let store = Arc::new(store);
+        CoreAppHandle::new(
+            Arc::new(RaftClient::new(store.clone())),
+            store,
+            Arc::new(AuthzService::new_in_memory().await.unwrap()),
+            Arc::new(JwtAuthenticator::new("test-secret", 1)),
+        )
+    }
+
     #[tokio::test]
     async fn test_protocol_config_default() {
         let config = ProtocolConfig::default();
@@ -188,6 +325,59 @@ mod tests {
         manager.set_config("test-http".to_string(), config);
     }
 
+    #[tokio::test]
+    async fn test_shutdown_all_stops_plugins_gracefully() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut manager = ProtocolManager::new();
+        let (first, first_shut_down) = LongRunningProtocol::new("first", false);
+        let (second, second_shut_down) = LongRunningProtocol::new("second", false);
+        manager.register_plugin(Box::new(first));
+        manager.register_plugin(Box::new(second));
+
+        manager.start_all(create_core_handle(&temp_dir).await).await.unwrap();
+        let results = manager.shutdown_all(Duration::from_secs(5)).await;
+
+        assert_eq!(results.len(), 2);
+        assert!(results.iter().all(|result| result.is_ok()));
+        assert!(first_shut_down.load(Ordering::SeqCst));
+        assert!(second_shut_down.load(Ordering::SeqCst));
+
+        // 任务已全部回收，再次关闭不会等待
+        assert!(manager.shutdown_all(Duration::from_secs(5)).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_all_aborts_hanging_plugin() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut manager = ProtocolManager::new();
+        let (graceful, graceful_shut_down) = LongRunningProtocol::new("graceful", false);
+        let (hanging, hanging_shut_down) = LongRunningProtocol::new("hanging", true);
+        manager.register_plugin(Box::new(graceful));
+        manager.register_plugin(Box::new(hanging));
+
+        manager.start_all(create_core_handle(&temp_dir).await).await.unwrap();
+        let start = Instant::now();
+        let results = manager.shutdown_all(Duration::from_millis(200)).await;
+
+        assert!(start.elapsed() < Duration::from_secs(2));
+        assert!(results[0].is_ok());
+        assert!(results[1].as_ref().unwrap_err().is_cancelled());
+        assert!(graceful_shut_down.load(Ordering::SeqCst));
+        assert!(hanging_shut_down.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn test_is_healthy_reports_each_plugin() {
+        let mut manager = ProtocolManager::new();
+        manager.register_plugin(Box::new(TestProtocol { name: "default" }));
+        manager.register_plugin(Box::new(LongRunningProtocol::new("unhealthy", true).0));
+
+        assert_eq!(
+            manager.is_healthy().await,
+            vec![("default".to_string(), true), ("unhealthy".to_string(), false)]
+        );
+    }
+
     // TODO: 修复这个测试以包含AuthzService
     // #[tokio::test]
     // async fn test_core_app_handle_integration() {
@@ -0,0 +1,148 @@
+use crate::error::ConfluxError;
+use super::types::ClientWriteRequest;
+use prometheus::IntCounter;
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::sync::{Arc, LazyLock};
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+use tracing::{debug, warn};
+
+/// Maximum number of failed writes kept before the oldest are dropped
+pub const DEAD_LETTER_CAPACITY: usize = 1_000;
+
+/// How often the background monitor reports a non-empty queue
+pub const DEAD_LETTER_WARN_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Total number of write requests moved to the dead-letter queue
+static DEAD_LETTER_TOTAL: LazyLock<IntCounter> = LazyLock::new(|| {
+    prometheus::register_int_counter!(
+        "raft_dead_letter_total",
+        "Total number of Raft write requests moved to the dead-letter queue"
+    )
+    .expect("raft_dead_letter_total is registered once")
+});
+
+/// A write request that could not be submitted to the cluster
+#[derive(Debug)]
+pub struct DeadLetterEntry {
+    /// The request that failed
+    pub request: ClientWriteRequest,
+    /// The error returned by the last submission attempt
+    pub error: ConfluxError,
+    /// When the request was moved to the queue
+    pub failed_at: Instant,
+}
+
+/// Serializable view of a dead-letter entry
+#[derive(Debug, Clone, Serialize)]
+pub struct DeadLetterSummary {
+    pub request: ClientWriteRequest,
+    pub error: String,
+    pub age_ms: u64,
+}
+
+/// Bounded queue of write requests that failed to reach consensus
+///
+/// When full, the oldest entry is dropped to make room for the newest.
+#[derive(Debug)]
+pub struct DeadLetterQueue {
+    entries: Mutex<VecDeque<DeadLetterEntry>>,
+    capacity: usize,
+}
+
+impl Default for DeadLetterQueue {
+    fn default() -> Self {
+        Self::new(DEAD_LETTER_CAPACITY)
+    }
+}
+
+impl DeadLetterQueue {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            entries: Mutex::new(VecDeque::with_capacity(capacity.min(DEAD_LETTER_CAPACITY))),
+            capacity: capacity.max(1),
+        }
+    }
+
+    /// Record a failed write request
+    pub async fn push(&self, request: ClientWriteRequest, error: ConfluxError) {
+        let mut entries = self.entries.lock().await;
+        if entries.len() >= self.capacity {
+            if let Some(dropped) = entries.pop_front() {
+                warn!(
+                    "Dead-letter queue full, dropping oldest request: {:?}",
+                    dropped.request.command
+                );
+            }
+        }
+        entries.push_back(DeadLetterEntry {
+            request,
+            error,
+            failed_at: Instant::now(),
+        });
+        DEAD_LETTER_TOTAL.inc();
+    }
+
+    /// Remove and return all queued entries, oldest first
+    pub async fn drain(&self) -> Vec<DeadLetterEntry> {
+        self.entries.lock().await.drain(..).collect()
+    }
+
+    /// Describe the queued entries without removing them
+    pub async fn summaries(&self) -> Vec<DeadLetterSummary> {
+        self.entries
+            .lock()
+            .await
+            .iter()
+            .map(|entry| DeadLetterSummary {
+                request: entry.request.clone(),
+                error: entry.error.to_string(),
+                age_ms: entry.failed_at.elapsed().as_millis() as u64,
+            })
+            .collect()
+    }
+
+    pub async fn len(&self) -> usize {
+        self.entries.lock().await.len()
+    }
+
+    pub async fn is_empty(&self) -> bool {
+        self.entries.lock().await.is_empty()
+    }
+
+    /// Total number of requests ever moved to a dead-letter queue in this process
+    pub fn total_count() -> u64 {
+        DEAD_LETTER_TOTAL.get()
+    }
+
+    /// Start the background task that warns while the queue is non-empty
+    ///
+    /// The task only holds a weak reference and stops once the queue is dropped.
+    /// Does nothing when called outside a Tokio runtime.
+    pub fn spawn_monitor(self: &Arc<Self>, interval: Duration) {
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            return;
+        };
+        let queue = Arc::downgrade(self);
+        runtime.spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            // The first tick completes immediately
+            ticker.tick().await;
+            loop {
+                ticker.tick().await;
+                let Some(queue) = queue.upgrade() else {
+                    debug!("Dead-letter queue dropped, stopping monitor");
+                    break;
+                };
+                let pending = queue.len().await;
+                if pending > 0 {
+                    warn!(
+                        "{} failed write request(s) waiting in the dead-letter queue",
+                        pending
+                    );
+                }
+            }
+        });
+    }
+}
@@ -0,0 +1,39 @@
+use super::types::{ClientReadRequest, ClientReadResponse, ClientWriteRequest};
+use crate::error::{ConfluxError, RaftError, Result};
+use crate::raft::types::{ClientWriteResponse, NodeId};
+use async_trait::async_trait;
+
+/// Sends client requests to the current leader when the local node is not it
+///
+/// The receiving side must serve forwarded requests locally rather than
+/// forwarding them again, so requests cannot bounce between nodes while
+/// leadership is moving.
+#[async_trait]
+pub trait LeaderForwarder: Send + Sync {
+    /// Submit a write request to `leader`
+    async fn forward_write(
+        &self,
+        leader: NodeId,
+        request: ClientWriteRequest,
+    ) -> Result<ClientWriteResponse>;
+
+    /// Serve a read request on `leader`
+    async fn forward_read(
+        &self,
+        leader: NodeId,
+        request: ClientReadRequest,
+    ) -> Result<ClientReadResponse>;
+}
+
+/// Whether the error means the local node is not (or no longer) the leader
+pub(crate) fn is_leadership_loss(error: &ConfluxError) -> bool {
+    matches!(error.root_cause(), ConfluxError::Raft(RaftError::NotLeader { .. }))
+}
+
+/// The leader named by a not-leader error, if it is known
+pub(crate) fn leader_hint(error: &ConfluxError) -> Option<NodeId> {
+    match error.root_cause() {
+        ConfluxError::Raft(RaftError::NotLeader { leader, .. }) => *leader,
+        _ => None,
+    }
+}
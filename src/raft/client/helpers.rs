@@ -1,12 +1,30 @@
 use crate::raft::types::*;
 use super::types::*;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap};
 
 /// Helper function to create a write request
 pub fn create_write_request(command: RaftCommand) -> ClientWriteRequest {
     ClientWriteRequest {
         command,
         request_id: None,
+        client_id: None,
+        validate_only: false,
+    }
+}
+
+/// Helper function to create a write request rate limited as `client_id`
+pub fn create_client_write_request(command: RaftCommand, client_id: &str) -> ClientWriteRequest {
+    ClientWriteRequest {
+        client_id: Some(client_id.to_string()),
+        ..create_write_request(command)
+    }
+}
+
+/// Helper function to create a validate-only write request
+pub fn create_validate_request(command: RaftCommand, client_id: &str) -> ClientWriteRequest {
+    ClientWriteRequest {
+        validate_only: true,
+        ..create_client_write_request(command, client_id)
     }
 }
 
@@ -15,6 +33,7 @@ pub fn create_read_request(operation: ReadOperation) -> ClientReadRequest {
     ClientReadRequest {
         operation,
         consistency: Some(ReadConsistency::default()),
+        min_index: None,
     }
 }
 
@@ -23,11 +42,39 @@ pub fn create_get_config_request(
     namespace: ConfigNamespace,
     name: String,
     client_labels: BTreeMap<String, String>,
+) -> ClientReadRequest {
+    create_render_config_request(namespace, name, client_labels, HashMap::new(), false)
+}
+
+/// Helper function to create a get config request with template variables
+pub fn create_render_config_request(
+    namespace: ConfigNamespace,
+    name: String,
+    client_labels: BTreeMap<String, String>,
+    template_variables: HashMap<String, String>,
+    strict: bool,
 ) -> ClientReadRequest {
     create_read_request(ReadOperation::GetConfig {
         namespace,
         name,
         client_labels,
+        template_variables,
+        strict,
+    })
+}
+
+/// Helper function to create a request for the config published at `at`
+pub fn create_get_config_at_request(
+    namespace: ConfigNamespace,
+    name: String,
+    at: chrono::DateTime<chrono::Utc>,
+    client_labels: BTreeMap<String, String>,
+) -> ClientReadRequest {
+    create_read_request(ReadOperation::GetConfigAt {
+        namespace,
+        name,
+        at,
+        client_labels,
     })
 }
 
@@ -35,6 +82,16 @@ pub fn create_get_config_request(
 pub fn create_list_configs_request(
     namespace: ConfigNamespace,
     prefix: Option<String>,
+    label_query: Option<String>,
 ) -> ClientReadRequest {
-    create_read_request(ReadOperation::ListConfigs { namespace, prefix })
+    create_read_request(ReadOperation::ListConfigs {
+        namespace,
+        prefix,
+        label_query,
+    })
+}
+
+/// Helper function to create a search configs request
+pub fn create_search_configs_request(filter: ConfigFilter) -> ClientReadRequest {
+    create_read_request(ReadOperation::SearchConfigs { filter })
 }
@@ -0,0 +1,58 @@
+use crate::error::{ConfluxError, Result};
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+
+/// Error message returned when a stale read is rejected
+pub const FOLLOWER_LAG_EXCEEDED: &str = "follower lag exceeds threshold";
+
+/// Tracks how far the local node's applied index trails the leader's commit index in time
+///
+/// Every commit index observed from the leader is recorded with the time it
+/// was first seen. The lag is the age of the oldest observed commit index the
+/// local node has not applied yet, or zero when it has caught up.
+#[derive(Debug, Default)]
+pub struct ReplicationLagTracker {
+    observed: Mutex<BTreeMap<u64, Instant>>,
+}
+
+impl ReplicationLagTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record that the leader's commit index was `commit_index` at `at`
+    ///
+    /// Only the first observation of each index is kept.
+    pub async fn observe_commit(&self, commit_index: u64, at: Instant) {
+        let mut observed = self.observed.lock().await;
+        let newest = observed.last_key_value().map_or(0, |(&index, _)| index);
+        if commit_index > newest {
+            observed.insert(commit_index, at);
+        }
+    }
+
+    /// Time the local node has been behind the leader, given its applied index
+    pub async fn lag(&self, last_applied: u64, now: Instant) -> Duration {
+        let mut observed = self.observed.lock().await;
+        // Indexes already applied no longer contribute to the lag
+        *observed = observed.split_off(&(last_applied + 1));
+        observed
+            .first_key_value()
+            .map_or(Duration::ZERO, |(_, &seen)| now.saturating_duration_since(seen))
+    }
+
+    /// Reject the read if the local node lags more than `max_lag_ms` behind
+    pub async fn check(&self, last_applied: u64, max_lag_ms: u64, now: Instant) -> Result<()> {
+        let lag = self.lag(last_applied, now).await;
+        if lag > Duration::from_millis(max_lag_ms) {
+            return Err(ConfluxError::raft(format!(
+                "{} ({}ms > {}ms)",
+                FOLLOWER_LAG_EXCEEDED,
+                lag.as_millis(),
+                max_lag_ms
+            )));
+        }
+        Ok(())
+    }
+}
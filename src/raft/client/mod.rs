@@ -1,15 +1,36 @@
-use crate::error::Result;
+use crate::error::{ConfluxError, RaftError, Result};
+use crate::raft::metrics::{ComponentHealth, HealthReport, HealthStatus};
+use crate::raft::node::ResourceLimits;
 use crate::raft::types::*;
+use std::future::Future;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::RwLock;
-use tracing::{debug, error, info};
+use tracing::{debug, error, info, warn};
 
 // 重新导出模块内容
+mod dead_letter;
+mod forward;
 pub mod helpers;
+mod lag;
+mod read_index;
+mod retry;
+mod timeout;
 #[cfg(test)]
 mod tests;
 pub mod types;
 
+pub use dead_letter::{
+    DeadLetterEntry, DeadLetterQueue, DeadLetterSummary, DEAD_LETTER_CAPACITY,
+    DEAD_LETTER_WARN_INTERVAL,
+};
+pub use forward::LeaderForwarder;
+pub use lag::{ReplicationLagTracker, FOLLOWER_LAG_EXCEEDED};
+pub use read_index::{
+    ReadIndexTracker, MAX_READ_BATCH_OPERATIONS, READ_INDEX_NOT_APPLIED, READ_INDEX_WAIT_TIMEOUT,
+};
+pub use retry::{is_retryable, RetryPolicy};
+pub use timeout::{AdaptiveTimeout, ADAPTIVE_TIMEOUT_ALPHA, MIN_ADAPTIVE_TIMEOUT, WRITE_TIMED_OUT};
 pub use types::*;
 // pub use helpers::*; // Commented out until needed
 
@@ -22,6 +43,18 @@ pub struct RaftClient {
     raft_node: Option<Arc<RwLock<crate::raft::node::RaftNode>>>,
     /// Current leader node (for routing requests)
     current_leader: Arc<RwLock<Option<NodeId>>>,
+    /// Write requests that failed to reach consensus
+    dead_letters: Arc<DeadLetterQueue>,
+    /// How far this node's applied index trails the leader, for stale reads
+    replication_lag: Arc<ReplicationLagTracker>,
+    /// Last committed write index, required by subsequent reads
+    read_index: Arc<ReadIndexTracker>,
+    /// Backoff for requests that fail while no leader is available
+    retry_policy: RetryPolicy,
+    /// Sends requests to the leader when this node is not it
+    forwarder: Option<Arc<dyn LeaderForwarder>>,
+    /// Write timeout derived from observed write latency
+    adaptive_timeout: Arc<AdaptiveTimeout>,
 }
 
 impl RaftClient {
@@ -31,6 +64,12 @@ impl RaftClient {
             store,
             raft_node: None,
             current_leader: Arc::new(RwLock::new(Some(1))), // Default to node 1 as leader
+            dead_letters: Self::new_dead_letter_queue(),
+            replication_lag: Arc::new(ReplicationLagTracker::new()),
+            read_index: Arc::new(ReadIndexTracker::new()),
+            retry_policy: RetryPolicy::default(),
+            forwarder: None,
+            adaptive_timeout: Arc::new(AdaptiveTimeout::new()),
         }
     }
 
@@ -43,11 +82,151 @@ impl RaftClient {
             store,
             raft_node: Some(raft_node),
             current_leader: Arc::new(RwLock::new(Some(1))), // Default to node 1 as leader
+            dead_letters: Self::new_dead_letter_queue(),
+            replication_lag: Arc::new(ReplicationLagTracker::new()),
+            read_index: Arc::new(ReadIndexTracker::new()),
+            retry_policy: RetryPolicy::default(),
+            forwarder: None,
+            adaptive_timeout: Arc::new(AdaptiveTimeout::new()),
+        }
+    }
+
+    /// Retry transient failures with `retry_policy` instead of the default
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// The backoff applied to requests that fail transiently
+    pub fn retry_policy(&self) -> &RetryPolicy {
+        &self.retry_policy
+    }
+
+    /// Forward requests this node cannot serve to the current leader
+    ///
+    /// Without a forwarder such requests fail with [`RaftError::NotLeader`].
+    pub fn with_leader_forwarder(mut self, forwarder: Arc<dyn LeaderForwarder>) -> Self {
+        self.forwarder = Some(forwarder);
+        self
+    }
+
+    /// Derive the write timeout from observed write latency instead of
+    /// using `ResourceLimits::request_timeout_ms` as is
+    ///
+    /// See [`AdaptiveTimeout`]; the static timeout stays the upper bound.
+    /// While enabled, write latencies are also recorded in the node's
+    /// client latency histogram.
+    pub fn set_adaptive_timeout_enabled(&self, enabled: bool) {
+        info!("Adaptive write timeout {}", if enabled { "enabled" } else { "disabled" });
+        self.adaptive_timeout.set_enabled(enabled);
+    }
+
+    /// Moving average of the P99 write latency in milliseconds
+    pub fn get_p99_ema_ms(&self) -> f64 {
+        self.adaptive_timeout.p99_ema_ms()
+    }
+
+    /// Share an existing dead-letter queue instead of the client's own
+    pub fn with_dead_letter_queue(mut self, dead_letters: Arc<DeadLetterQueue>) -> Self {
+        self.dead_letters = dead_letters;
+        self
+    }
+
+    fn new_dead_letter_queue() -> Arc<DeadLetterQueue> {
+        let queue = Arc::new(DeadLetterQueue::default());
+        queue.spawn_monitor(DEAD_LETTER_WARN_INTERVAL);
+        queue
+    }
+
+    /// The queue holding write requests that failed to reach consensus
+    pub fn dead_letters(&self) -> Arc<DeadLetterQueue> {
+        self.dead_letters.clone()
+    }
+
+    /// Remove and return all failed write requests for inspection or retry
+    pub async fn drain_dead_letters(&self) -> Vec<DeadLetterEntry> {
+        self.dead_letters.drain().await
+    }
+
+    /// Resubmit all failed write requests
+    ///
+    /// Requests that fail again are returned to the dead-letter queue.
+    /// Returns the number of requests that succeeded.
+    pub async fn retry_dead_letters(&self) -> usize {
+        let mut succeeded = 0;
+        for entry in self.drain_dead_letters().await {
+            if self.write(entry.request).await.is_ok() {
+                succeeded += 1;
+            }
+        }
+        succeeded
+    }
+
+    /// Get the Raft node backing this client, if running in consensus mode
+    pub fn raft_node(&self) -> Option<Arc<RwLock<crate::raft::node::RaftNode>>> {
+        self.raft_node.clone()
+    }
+
+    /// Health of the backing node by component
+    ///
+    /// Without a Raft node only the store is checked and Raft is reported unhealthy.
+    pub async fn health_report(&self) -> HealthReport {
+        match self.raft_node {
+            Some(ref raft_node) => raft_node.read().await.health_report().await,
+            None => HealthReport::new(
+                ComponentHealth::new(HealthStatus::Unhealthy, "no Raft node attached"),
+                crate::raft::node::storage_health(&self.store),
+                crate::raft::node::no_leader(),
+            ),
         }
     }
 
     /// Submit a write request to the cluster
+    ///
+    /// The response carries the log index the write committed at; subsequent
+    /// reads through this client wait until the serving node applied it.
+    /// Transient failures are retried according to the retry policy; requests
+    /// that still fail to reach consensus are moved to the dead-letter queue.
+    ///
+    /// Requests with `validate_only` set are only checked against a scratch
+    /// copy of the local store; see [`Store::dry_run`](crate::raft::store::Store::dry_run).
     pub async fn write(&self, request: ClientWriteRequest) -> Result<ClientWriteResponse> {
+        if request.validate_only {
+            debug!("Validating write request without applying it: {:?}", request.command);
+            return self.store.dry_run(&request.command).await;
+        }
+        let result = self
+            .with_retry("write", || self.with_write_timeout(self.submit_write(&request)))
+            .await;
+        self.finish_write(request, result).await
+    }
+
+    /// Record the outcome of a write, dead-lettering requests that failed
+    async fn finish_write(
+        &self,
+        request: ClientWriteRequest,
+        result: Result<ClientWriteResponse>,
+    ) -> Result<ClientWriteResponse> {
+        match result {
+            Ok(response) => {
+                if let Some(index) = response.log_index {
+                    self.read_index.observe(index);
+                }
+                Ok(response)
+            }
+            // Retrying a rate-limited write later would bypass the limit
+            Err(e @ ConfluxError::Raft(RaftError::RateLimited { .. })) => Err(e),
+            Err(e) => {
+                warn!("Moving failed write request to the dead-letter queue: {}", e);
+                self.dead_letters
+                    .push(request, crate::error::ConfluxError::raft(e.to_string()))
+                    .await;
+                Err(e)
+            }
+        }
+    }
+
+    async fn submit_write(&self, request: &ClientWriteRequest) -> Result<ClientWriteResponse> {
         info!("Processing client write request: {:?}", request.command);
 
         // Always use Raft consensus - no fallback to direct store access
@@ -55,17 +234,31 @@ impl RaftClient {
             debug!("Routing write request through Raft consensus");
             let node = raft_node.read().await;
 
-            // Convert ClientWriteRequest to ClientRequest
+            // Convert ClientWriteRequest to ClientRequest, stamping the
+            // idempotency key with the time it is proposed
             let client_request = ClientRequest {
                 command: request.command.clone(),
+                idempotency_key: request.request_id.clone().map(StampedIdempotencyKey::now),
             };
 
-            match node.client_write(client_request).await {
+            match node
+                .client_write_as(client_request, request.client_id.as_deref())
+                .await
+            {
                 Ok(response) => {
                     debug!("Raft write completed successfully");
                     return Ok(response);
                 }
                 Err(e) => {
+                    let leader = forward::leader_hint(&e);
+                    if let (Some(forwarder), Some(leader)) = (&self.forwarder, leader) {
+                        info!(
+                            "Node {} is not the leader, forwarding write to {}",
+                            node.node_id(),
+                            leader
+                        );
+                        return forwarder.forward_write(leader, request.clone()).await;
+                    }
                     error!("Raft write failed: {}", e);
                     return Err(e);
                 }
@@ -73,26 +266,115 @@ impl RaftClient {
         }
 
         // Return error if no Raft node available instead of fallback
-        Err(crate::error::ConfluxError::raft(
-            "No Raft node available - cannot process write requests",
-        ))
+        Err(RaftError::Unavailable(
+            "No Raft node available - cannot process write requests".to_string(),
+        )
+        .into())
     }
 
     /// Submit a write request with automatic leader detection
+    ///
+    /// While no leader is known the request is retried according to the
+    /// retry policy, so writes issued during an election succeed once the
+    /// new leader is set.
     pub async fn write_with_leader_detection(
         &self,
         request: ClientWriteRequest,
     ) -> Result<ClientWriteResponse> {
-        // Check if we know the current leader
-        let leader_id = self.current_leader.read().await;
+        // TODO: Implement actual leader detection and request forwarding
+        let result = self
+            .with_retry("write", || async {
+                if self.current_leader.read().await.is_none() {
+                    return Err(RaftError::NoLeader.into());
+                }
+                self.with_write_timeout(self.submit_write(&request)).await
+            })
+            .await;
+        self.finish_write(request, result).await
+    }
 
-        if leader_id.is_none() {
-            return Err(crate::error::ConfluxError::raft("No leader available"));
+    /// Run one write attempt under the current write timeout
+    ///
+    /// Successful attempts are recorded in the adaptive timeout. Timed-out
+    /// attempts count as twice the timeout, so the timeout backs off when the
+    /// cluster slows down instead of failing every write at the old latency.
+    /// Attempts that fail early do not reflect the write latency and are not
+    /// recorded.
+    async fn with_write_timeout<T>(&self, write: impl Future<Output = Result<T>>) -> Result<T> {
+        let timeout = self.adaptive_timeout.timeout(self.request_timeout().await);
+        let start = tokio::time::Instant::now();
+        let result = tokio::time::timeout(timeout, write).await;
+        let latency = start.elapsed();
+
+        match result {
+            Ok(Err(e)) => Err(e),
+            Ok(Ok(response)) => {
+                self.adaptive_timeout.record(latency);
+                self.record_latency_histogram(latency).await;
+                Ok(response)
+            }
+            Err(_) => {
+                warn!("Write did not complete within {}ms", timeout.as_millis());
+                self.adaptive_timeout.record(timeout * 2);
+                self.record_latency_histogram(latency).await;
+                Err(RaftError::Other(format!(
+                    "{} after {}ms",
+                    WRITE_TIMED_OUT,
+                    timeout.as_millis()
+                ))
+                .into())
+            }
         }
+    }
 
-        // For now, just use the local store (same as write method)
-        // TODO: Implement actual leader detection and request forwarding
-        self.write(request).await
+    /// The static request timeout of the backing node, the default without one
+    async fn request_timeout(&self) -> Duration {
+        let timeout_ms = match self.raft_node {
+            Some(ref raft_node) => {
+                raft_node.read().await.config().resource_limits.request_timeout_ms
+            }
+            None => ResourceLimits::default().request_timeout_ms,
+        };
+        Duration::from_millis(timeout_ms)
+    }
+
+    /// Record a write latency in the node's histogram while the adaptive timeout is on
+    async fn record_latency_histogram(&self, latency: Duration) {
+        if !self.adaptive_timeout.is_enabled() {
+            return;
+        }
+        if let Some(ref raft_node) = self.raft_node {
+            raft_node
+                .read()
+                .await
+                .metrics_collector()
+                .record_client_latency(latency)
+                .await;
+        }
+    }
+
+    /// Run `operation` until it succeeds, fails with a non-retryable error or
+    /// the retry policy runs out of attempts
+    async fn with_retry<T, F, Fut>(&self, name: &str, mut operation: F) -> Result<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let mut attempt = 1;
+        loop {
+            match operation().await {
+                Err(e) if attempt < self.retry_policy.max_attempts && is_retryable(&e) => {
+                    let delay = self.retry_policy.delay(attempt);
+                    warn!(
+                        "Client {} attempt {}/{} failed, retrying in {:?}: {}",
+                        name, attempt, self.retry_policy.max_attempts, delay, e
+                    );
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
     }
 
     /// Batch write multiple requests
@@ -120,49 +402,192 @@ impl RaftClient {
     }
 
     /// Submit a read request to the cluster with linearizability through Raft
+    ///
+    /// Before reading, the serving node must have applied `request.min_index`
+    /// and this client's last committed write, waiting up to
+    /// `READ_INDEX_WAIT_TIMEOUT` for it to catch up. A read that fails because
+    /// this node lost leadership fails over to the new leader once, through
+    /// the [`LeaderForwarder`] if one is set; reads that fail because no leader
+    /// can confirm linearizability are retried per the retry policy.
     pub async fn read(&self, request: ClientReadRequest) -> Result<ClientReadResponse> {
+        self.with_retry("read", || self.read_with_failover(request.clone()))
+            .await
+    }
+
+    /// Serve a read, failing over once if this node lost leadership
+    ///
+    /// The leader is re-resolved from the node's Raft metrics. If leadership
+    /// came back to this node the read is retried locally, otherwise it is
+    /// sent to the new leader through the configured [`LeaderForwarder`]
+    /// together with this client's read index. Without a forwarder, or while
+    /// no leader is known, the original error is returned.
+    async fn read_with_failover(&self, request: ClientReadRequest) -> Result<ClientReadResponse> {
+        let error = match self.read_once(request.clone()).await {
+            Err(e) if forward::is_leadership_loss(&e) => e,
+            result => return result,
+        };
+
+        let Some(leader) = self.resolve_leader().await else {
+            return Err(error);
+        };
+        let local_node_id = match self.raft_node {
+            Some(ref raft_node) => Some(raft_node.read().await.node_id()),
+            None => None,
+        };
+
+        if local_node_id == Some(leader) {
+            info!("Leadership returned to node {}, retrying read locally", leader);
+            return self.read_once(request).await;
+        }
+        match self.forwarder {
+            Some(ref forwarder) => {
+                info!("Leadership moved to node {} during read, forwarding", leader);
+                let mut request = request;
+                request.min_index = Some(self.read_index.required(request.min_index));
+                forwarder.forward_read(leader, request).await
+            }
+            None => Err(error),
+        }
+    }
+
+    /// Re-resolve the current leader from the local node's Raft metrics
+    ///
+    /// The client's cached leader is updated when one is known.
+    async fn resolve_leader(&self) -> Option<NodeId> {
+        let raft_node = self.raft_node.as_ref()?;
+        let leader = raft_node.read().await.get_leader().await?;
+        *self.current_leader.write().await = Some(leader);
+        Some(leader)
+    }
+
+    /// The leader this client last resolved, if any
+    pub async fn current_leader(&self) -> Option<NodeId> {
+        *self.current_leader.read().await
+    }
+
+    async fn read_once(&self, request: ClientReadRequest) -> Result<ClientReadResponse> {
         debug!("Processing client read request: {:?}", request.operation);
 
-        // Ensure linearizable reads through Raft consensus
-        if let Some(ref raft_node) = self.raft_node {
-            let node = raft_node.read().await;
+        self.wait_for_applied(self.read_index.required(request.min_index))
+            .await?;
 
-            // Ensure we can provide linearizable reads (only leaders can guarantee this)
-            if let Some(ref raft) = node.get_raft() {
-                // Use ensure_linearizable to make sure we can provide consistent reads
-                match raft.ensure_linearizable().await {
-                    Ok(_) => {
-                        // We're the leader or can provide linearizable reads
-                        debug!("Linearizable read confirmed, proceeding with read operation");
-                    }
-                    Err(e) => {
-                        return Err(crate::error::ConfluxError::raft(format!(
-                            "Cannot provide linearizable read: {}",
-                            e
-                        )));
-                    }
+        match request.consistency {
+            Some(ReadConsistency::Stale { max_lag_ms }) => {
+                // A leader holding a valid lease is up to date by definition;
+                // other nodes serve stale reads if they are not too far behind
+                if !self.holds_leader_lease().await {
+                    self.check_replication_lag(max_lag_ms).await?;
                 }
-            } else {
-                return Err(crate::error::ConfluxError::raft(
-                    "Raft instance not available",
-                ));
             }
-        } else {
-            return Err(crate::error::ConfluxError::raft(
-                "No Raft node available for reads",
-            ));
+            Some(ReadConsistency::Leased) => self.confirm_leadership(true).await?,
+            _ => self.confirm_leadership(false).await?,
+        }
+
+        let data = self.execute_read(request.operation).await?;
+
+        let response = ClientReadResponse {
+            success: true,
+            data,
+            leader_id: *self.current_leader.read().await,
+            consistency_level: request.consistency.unwrap_or_default(),
+        };
+
+        debug!("Client read completed successfully");
+        Ok(response)
+    }
+
+    /// Read several operations against one linearizable view of the state
+    ///
+    /// Leadership is confirmed with a quorum once through
+    /// [`RaftNode::read_index`](crate::raft::node::RaftNode::read_index)
+    /// instead of once per operation. The operations are then served locally
+    /// while log application is held off, so:
+    /// - every operation observes all writes committed before this call
+    ///   started, including this client's own writes;
+    /// - all operations observe the same state, the one at `applied_index`,
+    ///   which may include writes committed after this call started;
+    /// - writes made to the store outside the Raft log, such as background
+    ///   garbage collection, are not held off.
+    ///
+    /// Fails without reading if the batch is larger than
+    /// [`MAX_READ_BATCH_OPERATIONS`], if no Raft node backs this client, or if
+    /// this node is not the leader. Unlike [`Self::read`], the batch is
+    /// neither retried nor forwarded.
+    pub async fn read_batch(&self, operations: Vec<ReadOperation>) -> Result<ReadBatchResponse> {
+        if operations.len() > MAX_READ_BATCH_OPERATIONS {
+            return Err(ConfluxError::Validation(format!(
+                "Batch of {} reads exceeds the limit of {}",
+                operations.len(),
+                MAX_READ_BATCH_OPERATIONS
+            )));
+        }
+        let Some(ref raft_node) = self.raft_node else {
+            return Err(
+                RaftError::Unavailable("No Raft node available for reads".to_string()).into(),
+            );
+        };
+
+        self.wait_for_applied(self.read_index.last_seen()).await?;
+        let (read_index, state) = {
+            let node = raft_node.read().await;
+            let read_index = node.read_index().await?;
+            let Some(state_machine) = node.state_machine() else {
+                return Err(RaftError::NotInitialized.into());
+            };
+            (read_index, state_machine.lock_applied_state().await)
+        };
+        debug!(
+            "Serving {} reads at read index {} (applied {})",
+            operations.len(),
+            read_index,
+            state.applied_index()
+        );
+
+        let mut results = Vec::with_capacity(operations.len());
+        for operation in operations {
+            results.push(self.execute_read(operation).await?);
         }
+        let applied_index = state.applied_index();
+        drop(state);
+
+        Ok(ReadBatchResponse {
+            read_index,
+            applied_index,
+            results,
+            leader_id: *self.current_leader.read().await,
+        })
+    }
 
-        // Now perform the actual read operation
-        let data = match request.operation {
+    /// Perform a read operation against the local store
+    async fn execute_read(&self, operation: ReadOperation) -> Result<Option<serde_json::Value>> {
+        let data = match operation {
             ReadOperation::GetConfig {
                 namespace,
                 name,
                 client_labels,
+                template_variables,
+                strict,
+            } => {
+                let result = self
+                    .store
+                    .get_rendered_config(&namespace, &name, &client_labels, &template_variables, strict)
+                    .await?;
+                result.map(|(config, version)| {
+                    serde_json::json!({
+                        "config": config,
+                        "version": version
+                    })
+                })
+            }
+            ReadOperation::GetConfigAt {
+                namespace,
+                name,
+                at,
+                client_labels,
             } => {
                 let result = self
                     .store
-                    .get_published_config(&namespace, &name, &client_labels)
+                    .get_config_at(&namespace, &name, at, &client_labels)
                     .await;
                 result.map(|(config, version)| {
                     serde_json::json!({
@@ -178,39 +603,176 @@ impl RaftClient {
                 let result = self.store.get_config_version(config_id, version_id).await;
                 result.map(|version| serde_json::json!(version))
             }
-            ReadOperation::ListConfigs { namespace, prefix } => {
-                // For MVP, return empty list
-                // In a real implementation, this would query the store
-                let _ = (namespace, prefix);
-                Some(serde_json::json!([]))
+            ReadOperation::ListConfigs {
+                namespace,
+                prefix,
+                label_query,
+            } => {
+                let label_query = label_query
+                    .as_deref()
+                    .map(LabelQuery::parse)
+                    .transpose()
+                    .map_err(|e| ConfluxError::Validation(e.to_string()))?;
+                let configs = self
+                    .store
+                    .list_configs(&namespace, prefix.as_deref(), label_query.as_ref())
+                    .await;
+                Some(serde_json::json!(configs))
+            }
+            ReadOperation::SearchConfigs { filter } => {
+                let configs = self.store.search_configs(&filter).await;
+                Some(serde_json::json!(configs))
             }
         };
+        Ok(data)
+    }
 
-        let response = ClientReadResponse {
-            success: true,
-            data,
-            leader_id: *self.current_leader.read().await,
-            consistency_level: request.consistency.unwrap_or_default(),
+    /// Whether the local node is the leader and holds a valid leader lease
+    ///
+    /// Counts a lease hit when it does.
+    async fn holds_leader_lease(&self) -> bool {
+        let Some(ref raft_node) = self.raft_node else {
+            return false;
+        };
+        let node = raft_node.read().await;
+        let held = node.holds_leader_lease();
+        if held {
+            node.metrics_collector().record_lease_read(true).await;
+        }
+        held
+    }
+
+    /// Make sure the local node can serve linearizable reads
+    ///
+    /// With `leased`, a valid leader lease is enough; otherwise, and once the
+    /// lease expired, leadership is confirmed with a quorum through
+    /// [`RaftNode::read_index`](crate::raft::node::RaftNode::read_index),
+    /// which also renews the lease.
+    async fn confirm_leadership(&self, leased: bool) -> Result<()> {
+        let Some(ref raft_node) = self.raft_node else {
+            return Err(
+                RaftError::Unavailable("No Raft node available for reads".to_string()).into(),
+            );
         };
+        let node = raft_node.read().await;
+        if node.get_raft().is_none() {
+            return Err(RaftError::NotInitialized.into());
+        }
 
-        debug!("Client read completed successfully");
-        Ok(response)
+        if leased {
+            let hit = node.holds_leader_lease();
+            node.metrics_collector().record_lease_read(hit).await;
+            if hit {
+                debug!("Leader lease valid, serving read without quorum check");
+                return Ok(());
+            }
+        }
+
+        node.read_index().await?;
+        debug!("Linearizable read confirmed, proceeding with read operation");
+        Ok(())
+    }
+
+    /// Highest log index a write through this client committed at
+    pub fn last_write_index(&self) -> u64 {
+        self.read_index.last_seen()
+    }
+
+    /// Wait until the local node has applied `index`
+    ///
+    /// Fails if the node does not catch up within `READ_INDEX_WAIT_TIMEOUT`,
+    /// or if an index is required but no Raft node backs this client.
+    async fn wait_for_applied(&self, index: u64) -> Result<()> {
+        if index == 0 {
+            return Ok(());
+        }
+        let raft = match self.raft_node {
+            Some(ref raft_node) => raft_node.read().await.get_raft().cloned(),
+            None => None,
+        };
+        let Some(raft) = raft else {
+            return Err(RaftError::Unavailable(format!(
+                "{}: no Raft node available to serve index {}",
+                READ_INDEX_NOT_APPLIED, index
+            ))
+            .into());
+        };
+
+        raft.wait(Some(READ_INDEX_WAIT_TIMEOUT))
+            .applied_index_at_least(Some(index), "read-your-writes")
+            .await
+            .map(|_| ())
+            .map_err(|e| {
+                warn!("Read index {} not applied in time: {}", index, e);
+                crate::error::ConfluxError::raft(format!(
+                    "{} (index {}): {}",
+                    READ_INDEX_NOT_APPLIED, index, e
+                ))
+            })
+    }
+
+    /// Tracker of how far this node's applied index trails the leader's commit index
+    pub fn replication_lag(&self) -> Arc<ReplicationLagTracker> {
+        self.replication_lag.clone()
+    }
+
+    /// Fail with a lag error if this node applies committed entries more than
+    /// `max_lag_ms` behind the leader, recording the outcome in the node metrics
+    async fn check_replication_lag(&self, max_lag_ms: u64) -> Result<()> {
+        let status = self.get_cluster_status().await?;
+        let now = std::time::Instant::now();
+        self.replication_lag.observe_commit(status.commit_index, now).await;
+
+        let node = match self.raft_node {
+            Some(ref raft_node) => Some(raft_node.read().await),
+            None => None,
+        };
+        let last_applied = match node {
+            Some(ref node) => node
+                .get_metrics()
+                .await
+                .map_or(status.applied_index, |metrics| metrics.last_applied),
+            None => status.applied_index,
+        };
+
+        let result = self.replication_lag.check(last_applied, max_lag_ms, now).await;
+        if let Some(node) = node {
+            node.metrics_collector().record_stale_read(result.is_ok()).await;
+        }
+        if let Err(ref e) = result {
+            warn!("Rejecting stale read: {}", e);
+        }
+        result
     }
 
-    /// Get current cluster status
+    /// Get current cluster status from the attached Raft node's metrics
+    ///
+    /// Without a running Raft node the status is [`ClusterStatus::degraded`].
     pub async fn get_cluster_status(&self) -> Result<ClusterStatus> {
         debug!("Getting cluster status");
 
-        let status = ClusterStatus {
-            leader_id: *self.current_leader.read().await,
-            members: vec![1], // For MVP, single node cluster
-            term: 1,
-            last_log_index: 0,
-            commit_index: 0,
-            applied_index: 0,
+        let Some(ref raft_node) = self.raft_node else {
+            return Ok(ClusterStatus::degraded());
+        };
+        let node = raft_node.read().await;
+        let metrics = match node.get_metrics().await {
+            Ok(metrics) => metrics,
+            Err(e) => {
+                warn!("Cluster status unavailable: {}", e);
+                return Ok(ClusterStatus::degraded());
+            }
         };
 
-        Ok(status)
+        Ok(ClusterStatus {
+            leader_id: metrics.leader_id,
+            members: metrics.membership.into_iter().collect(),
+            nodes: node.get_member_details().await,
+            term: metrics.current_term,
+            last_log_index: metrics.last_log_index,
+            commit_index: metrics.commit_index,
+            applied_index: metrics.last_applied,
+            healthy: metrics.leader_id.is_some(),
+        })
     }
 
     /// Set the current leader (for testing and manual control)
@@ -0,0 +1,42 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+/// Error message returned when the serving node has not applied a read's minimum index
+pub const READ_INDEX_NOT_APPLIED: &str = "minimum read index not applied";
+
+/// How long a read waits for the serving node to apply its minimum index
+pub const READ_INDEX_WAIT_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Most operations a batch read may contain, as the batch blocks log
+/// application while it runs
+pub const MAX_READ_BATCH_OPERATIONS: usize = 100;
+
+/// Highest log index this client has seen a write commit at
+///
+/// Reads carry the index so the serving node must have applied the client's
+/// own writes before answering, giving read-your-writes semantics.
+#[derive(Debug, Default)]
+pub struct ReadIndexTracker {
+    last_seen: AtomicU64,
+}
+
+impl ReadIndexTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record that a write committed at `index`
+    pub fn observe(&self, index: u64) {
+        self.last_seen.fetch_max(index, Ordering::AcqRel);
+    }
+
+    /// Highest committed write index seen so far, 0 before any write
+    pub fn last_seen(&self) -> u64 {
+        self.last_seen.load(Ordering::Acquire)
+    }
+
+    /// Index a read must wait for: the larger of the requested and last seen index
+    pub fn required(&self, min_index: Option<u64>) -> u64 {
+        min_index.unwrap_or(0).max(self.last_seen())
+    }
+}
@@ -0,0 +1,75 @@
+use crate::error::{ConfluxError, RaftError};
+use std::time::Duration;
+
+/// Error messages of transient conditions that clear up once a leader is
+/// elected or reachable again
+const RETRYABLE_MARKERS: &[&str] = &[
+    "No leader available",
+    "has to forward request to",
+    "not enough for a quorum",
+    "Unreachable node",
+    "timeout after",
+];
+
+/// Exponential backoff for client requests that fail transiently
+///
+/// The n-th retry waits `base_delay * 2^(n-1)`, capped at `max_delay`, plus
+/// a random jitter of up to `jitter` so clients do not retry in lockstep.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryPolicy {
+    /// Total attempts including the first one; 1 disables retries
+    pub max_attempts: u32,
+    /// Delay before the first retry
+    pub base_delay: Duration,
+    /// Upper bound of the backoff delay, excluding jitter
+    pub max_delay: Duration,
+    /// Maximum random delay added to each backoff
+    pub jitter: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 5,
+            base_delay: Duration::from_millis(50),
+            max_delay: Duration::from_secs(1),
+            jitter: Duration::from_millis(25),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Policy that never retries
+    pub fn none() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Self::default()
+        }
+    }
+
+    /// Backoff before retry number `retry` (1 for the first retry), without jitter
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let factor = 2u32.saturating_pow(retry.saturating_sub(1));
+        self.base_delay.saturating_mul(factor).min(self.max_delay)
+    }
+
+    /// Backoff before retry number `retry` with random jitter added
+    pub fn delay(&self, retry: u32) -> Duration {
+        let jitter_ms = self.jitter.as_millis() as u64;
+        self.backoff(retry) + Duration::from_millis(fastrand::u64(0..=jitter_ms))
+    }
+}
+
+/// Whether an error is transient and the request may succeed when retried
+///
+/// Only Raft errors caused by a missing or unreachable leader are retried;
+/// validation failures, rate limiting and other errors are returned at once.
+pub fn is_retryable(error: &ConfluxError) -> bool {
+    match error.root_cause() {
+        ConfluxError::Raft(RaftError::NoLeader | RaftError::NotLeader { .. }) => true,
+        ConfluxError::Raft(RaftError::Other(message)) => RETRYABLE_MARKERS
+            .iter()
+            .any(|marker| message.contains(marker)),
+        _ => false,
+    }
+}
@@ -38,7 +38,7 @@ mod tests {
 
         // Verify the error message
         match result {
-            Err(crate::error::ConfluxError::Raft(msg)) => {
+            Err(crate::error::ConfluxError::Raft(RaftError::Unavailable(msg))) => {
                 assert!(msg.contains("No Raft node available"));
             }
             _ => panic!("Expected Raft error"),
@@ -65,7 +65,7 @@ mod tests {
 
         // Verify the error message
         match result {
-            Err(crate::error::ConfluxError::Raft(msg)) => {
+            Err(crate::error::ConfluxError::Raft(RaftError::Unavailable(msg))) => {
                 assert!(msg.contains("No Raft node available"));
             }
             _ => panic!("Expected Raft error"),
@@ -76,12 +76,735 @@ mod tests {
     async fn test_cluster_status() {
         let (client, _temp_dir) = create_test_client().await;
 
+        // Without a Raft node the status is degraded
         let status = client.get_cluster_status().await.unwrap();
-        // The client returns a hardcoded status for MVP
-        // According to the implementation, it returns vec![1] as members
-        assert_eq!(status.members, vec![1]);
-        assert_eq!(status.leader_id, Some(1));
-        assert_eq!(status.term, 1);
+        assert!(!status.healthy);
+        assert!(status.members.is_empty());
+        assert_eq!(status.leader_id, None);
+        assert_eq!(status.term, 0);
     }
     
+
+    fn create_config_command(name: &str) -> RaftCommand {
+        RaftCommand::CreateConfig {
+            namespace: ConfigNamespace {
+                tenant: "test".to_string(),
+                app: "app".to_string(),
+                env: "dev".to_string(),
+            },
+            name: name.to_string(),
+            content: b"{}".to_vec(),
+            format: ConfigFormat::Json,
+            schema: None,
+            creator_id: 1,
+            description: "Test configuration".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_failed_writes_land_in_dead_letter_queue() {
+        let (client, _temp_dir) = create_test_client().await;
+        let total_before = DeadLetterQueue::total_count();
+
+        for name in ["a.json", "b.json"] {
+            assert!(client.write(create_write_request(create_config_command(name))).await.is_err());
+        }
+
+        let summaries = client.dead_letters().summaries().await;
+        assert_eq!(summaries.len(), 2);
+        assert!(summaries[0].error.contains("No Raft node available"));
+        assert!(DeadLetterQueue::total_count() >= total_before + 2);
+
+        // Retrying without a Raft node fails again and keeps the requests queued
+        assert_eq!(client.retry_dead_letters().await, 0);
+        assert_eq!(client.dead_letters().len().await, 2);
+
+        let drained = client.drain_dead_letters().await;
+        assert_eq!(drained.len(), 2);
+        assert!(client.dead_letters().is_empty().await);
+    }
+
+    #[tokio::test]
+    async fn test_dead_letter_queue_drops_oldest_when_full() {
+        let queue = DeadLetterQueue::new(2);
+        for name in ["a.json", "b.json", "c.json"] {
+            queue
+                .push(
+                    create_write_request(create_config_command(name)),
+                    crate::error::ConfluxError::raft("unavailable"),
+                )
+                .await;
+        }
+
+        let entries = queue.drain().await;
+        assert_eq!(entries.len(), 2);
+        assert!(matches!(
+            &entries[0].request.command,
+            RaftCommand::CreateConfig { name, .. } if name == "b.json"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_successful_retry_removes_dead_letters() {
+        use crate::config::{AppConfig, StorageConfig};
+        use crate::raft::node::{NodeConfig, RaftNode};
+        use tokio::sync::RwLock;
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let app_config = AppConfig {
+            storage: StorageConfig {
+                data_dir: temp_dir.path().to_string_lossy().to_string(),
+                cache_size_mb: 64,
+                max_write_buffer_number: 2,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let node_config = NodeConfig {
+            node_id: 1,
+            address: "127.0.0.1:18095".to_string(),
+            ..Default::default()
+        };
+        let mut node = RaftNode::new(node_config, &app_config).await.unwrap();
+        node.start().await.unwrap();
+        node.wait_for_leadership(std::time::Duration::from_secs(5)).await.unwrap();
+        let store = node.store();
+
+        // A client without a Raft node fails and queues the request
+        let offline = RaftClient::new(store.clone());
+        assert!(offline.write(create_write_request(create_config_command("retry.json"))).await.is_err());
+        let queue = offline.dead_letters();
+        assert_eq!(queue.len().await, 1);
+
+        // Once consensus is available the retry succeeds and empties the queue
+        let online = RaftClient::new_with_raft_node(store, Arc::new(RwLock::new(node)))
+            .with_dead_letter_queue(queue.clone());
+        assert_eq!(online.retry_dead_letters().await, 1);
+        assert!(queue.is_empty().await);
+    }
+
+    #[tokio::test]
+    async fn test_replication_lag_tracker() {
+        use std::time::{Duration, Instant};
+
+        let tracker = ReplicationLagTracker::new();
+        let seen = Instant::now();
+        tracker.observe_commit(10, seen).await;
+        tracker.observe_commit(12, seen + Duration::from_secs(1)).await;
+
+        let now = seen + Duration::from_secs(3);
+        assert_eq!(tracker.lag(5, now).await, Duration::from_secs(3));
+        assert!(tracker.check(5, 3_000, now).await.is_ok());
+        let err = tracker.check(5, 2_000, now).await.unwrap_err();
+        assert!(err.to_string().contains(FOLLOWER_LAG_EXCEEDED));
+
+        // Applying index 10 leaves only index 12, first seen one second later
+        assert_eq!(tracker.lag(10, now).await, Duration::from_secs(2));
+        assert_eq!(tracker.lag(12, now).await, Duration::ZERO);
+    }
+
+    #[tokio::test]
+    async fn test_stale_read_served_from_local_store() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let (store, _) = Store::new(temp_dir.path()).await.unwrap();
+        let store = Arc::new(store);
+        store.apply_command(&create_config_command("stale.json")).await.unwrap();
+        let client = RaftClient::new(store);
+
+        let mut request = create_get_config_request(
+            ConfigNamespace {
+                tenant: "test".to_string(),
+                app: "app".to_string(),
+                env: "dev".to_string(),
+            },
+            "stale.json".to_string(),
+            BTreeMap::new(),
+        );
+        request.consistency = Some(ReadConsistency::Stale { max_lag_ms: 1_000 });
+
+        // No linearizability check is needed, so the read succeeds without a Raft node
+        let response = client.read(request.clone()).await.unwrap();
+        assert!(response.data.is_some());
+        assert!(matches!(response.consistency_level, ReadConsistency::Stale { max_lag_ms: 1_000 }));
+
+        // A commit index observed two seconds ago that has not been applied locally
+        let seen = std::time::Instant::now()
+            .checked_sub(std::time::Duration::from_secs(2))
+            .unwrap();
+        client.replication_lag().observe_commit(5, seen).await;
+        match client.read(request).await {
+            Err(crate::error::ConfluxError::Raft(RaftError::Other(msg))) => {
+                assert!(msg.contains(FOLLOWER_LAG_EXCEEDED));
+            }
+            other => panic!("Expected lag error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_read_index_tracker_keeps_highest_index() {
+        let tracker = ReadIndexTracker::new();
+        assert_eq!(tracker.required(None), 0);
+
+        tracker.observe(7);
+        tracker.observe(3);
+        assert_eq!(tracker.last_seen(), 7);
+        assert_eq!(tracker.required(None), 7);
+        assert_eq!(tracker.required(Some(5)), 7);
+        assert_eq!(tracker.required(Some(9)), 9);
+    }
+
+    #[tokio::test]
+    async fn test_reads_require_last_write_index() {
+        use crate::config::{AppConfig, StorageConfig};
+        use crate::raft::node::{NodeConfig, RaftNode};
+        use tokio::sync::RwLock;
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let app_config = AppConfig {
+            storage: StorageConfig {
+                data_dir: temp_dir.path().to_string_lossy().to_string(),
+                cache_size_mb: 64,
+                max_write_buffer_number: 2,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let mut node = RaftNode::new(NodeConfig::default(), &app_config).await.unwrap();
+        node.start().await.unwrap();
+        node.wait_for_leadership(std::time::Duration::from_secs(5)).await.unwrap();
+        let store = node.store();
+        let client = RaftClient::new_with_raft_node(store.clone(), Arc::new(RwLock::new(node)));
+
+        // The write reports the index it committed at and the client remembers it
+        let response = client
+            .write(create_write_request(create_config_command("ryw.json")))
+            .await
+            .unwrap();
+        let index = response.log_index.unwrap();
+        assert!(index > 0);
+        assert_eq!(client.last_write_index(), index);
+
+        let mut request = create_get_config_request(
+            ConfigNamespace {
+                tenant: "test".to_string(),
+                app: "app".to_string(),
+                env: "dev".to_string(),
+            },
+            "ryw.json".to_string(),
+            BTreeMap::new(),
+        );
+        let response = client.read(request.clone()).await.unwrap();
+        assert!(response.data.is_some());
+
+        // An index the node has not reached fails once the wait times out
+        request.min_index = Some(index + 100);
+        match client.read(request).await {
+            Err(crate::error::ConfluxError::Raft(RaftError::Other(msg))) => {
+                assert!(msg.contains(READ_INDEX_NOT_APPLIED));
+            }
+            other => panic!("Expected read index error, got {:?}", other),
+        }
+
+        // Without a Raft node the required index cannot be verified
+        let offline = RaftClient::new(store);
+        let mut request = create_get_config_request(
+            ConfigNamespace {
+                tenant: "test".to_string(),
+                app: "app".to_string(),
+                env: "dev".to_string(),
+            },
+            "ryw.json".to_string(),
+            BTreeMap::new(),
+        );
+        request.consistency = Some(ReadConsistency::Stale { max_lag_ms: 1_000 });
+        assert!(offline.read(request.clone()).await.is_ok());
+        request.min_index = Some(index);
+        assert!(offline.read(request).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_expired_lease_falls_back_to_linearizable_read() {
+        use crate::config::{AppConfig, StorageConfig};
+        use crate::raft::node::{NodeConfig, RaftNode};
+        use std::time::{Duration, Instant};
+        use tokio::sync::RwLock;
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let app_config = AppConfig {
+            storage: StorageConfig {
+                data_dir: temp_dir.path().to_string_lossy().to_string(),
+                cache_size_mb: 64,
+                max_write_buffer_number: 2,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let mut node = RaftNode::new(NodeConfig::default(), &app_config).await.unwrap();
+        node.start().await.unwrap();
+        node.wait_for_leadership(Duration::from_secs(5)).await.unwrap();
+        let store = node.store();
+        store.apply_command(&create_config_command("lease.json")).await.unwrap();
+        let lease = node.leader_lease();
+        let metrics = node.metrics_collector();
+        let client = RaftClient::new_with_raft_node(store, Arc::new(RwLock::new(node)));
+
+        let mut request = create_get_config_request(
+            ConfigNamespace {
+                tenant: "test".to_string(),
+                app: "app".to_string(),
+                env: "dev".to_string(),
+            },
+            "lease.json".to_string(),
+            BTreeMap::new(),
+        );
+        request.consistency = Some(ReadConsistency::Leased);
+
+        // Without a lease the read confirms leadership with a quorum, renewing the lease
+        lease.revoke();
+        let before = metrics.get_metrics_report().await;
+        assert!(client.read(request.clone()).await.unwrap().data.is_some());
+        let after = metrics.get_metrics_report().await;
+        assert_eq!(after.lease_misses, before.lease_misses + 1);
+        assert!(lease.expiry().is_some());
+
+        // While the lease is valid no quorum check is made
+        lease.extend(Instant::now() + Duration::from_secs(60));
+        assert!(client.read(request.clone()).await.unwrap().data.is_some());
+        let leased = metrics.get_metrics_report().await;
+        assert_eq!(leased.lease_hits, after.lease_hits + 1);
+        assert_eq!(leased.lease_misses, after.lease_misses);
+        assert!(matches!(
+            client.read(request).await.unwrap().consistency_level,
+            ReadConsistency::Leased
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_read_batch_shares_one_read_index() {
+        use crate::config::{AppConfig, StorageConfig};
+        use crate::raft::node::{NodeConfig, RaftNode};
+        use tokio::sync::RwLock;
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let app_config = AppConfig {
+            storage: StorageConfig {
+                data_dir: temp_dir.path().to_string_lossy().to_string(),
+                cache_size_mb: 64,
+                max_write_buffer_number: 2,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let mut node = RaftNode::new(NodeConfig::default(), &app_config).await.unwrap();
+        node.start().await.unwrap();
+        node.wait_for_leadership(std::time::Duration::from_secs(5)).await.unwrap();
+        let store = node.store();
+        let client = RaftClient::new_with_raft_node(store.clone(), Arc::new(RwLock::new(node)));
+
+        for name in ["a.json", "b.json"] {
+            client
+                .write(create_write_request(create_config_command(name)))
+                .await
+                .unwrap();
+        }
+        let operations: Vec<_> = ["a.json", "b.json", "missing.json"]
+            .into_iter()
+            .map(|name| {
+                create_get_config_request(
+                    ConfigNamespace {
+                        tenant: "test".to_string(),
+                        app: "app".to_string(),
+                        env: "dev".to_string(),
+                    },
+                    name.to_string(),
+                    BTreeMap::new(),
+                )
+                .operation
+            })
+            .collect();
+
+        let response = client.read_batch(operations.clone()).await.unwrap();
+        assert!(response.read_index >= client.last_write_index());
+        assert!(response.applied_index >= response.read_index);
+        assert_eq!(response.results.len(), 3);
+        assert!(response.results[0].is_some());
+        assert!(response.results[1].is_some());
+        assert!(response.results[2].is_none());
+
+        let oversized = vec![operations[0].clone(); MAX_READ_BATCH_OPERATIONS + 1];
+        assert!(matches!(
+            client.read_batch(oversized).await,
+            Err(crate::error::ConfluxError::Validation(_))
+        ));
+
+        // Without a Raft node no read index can be confirmed
+        let offline = RaftClient::new(store);
+        assert!(offline.read_batch(operations).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_cluster_status_reads_node_metrics() {
+        use crate::config::{AppConfig, StorageConfig};
+        use crate::raft::node::{NodeConfig, RaftNode};
+        use tokio::sync::RwLock;
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let app_config = AppConfig {
+            storage: StorageConfig {
+                data_dir: temp_dir.path().to_string_lossy().to_string(),
+                cache_size_mb: 64,
+                max_write_buffer_number: 2,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let node = RaftNode::new(NodeConfig::default(), &app_config).await.unwrap();
+        let raft_node = Arc::new(RwLock::new(node));
+        let client = RaftClient::new_with_raft_node(raft_node.read().await.store(), raft_node.clone());
+
+        // A node that has not started is degraded
+        assert!(!client.get_cluster_status().await.unwrap().healthy);
+
+        raft_node.write().await.start().await.unwrap();
+        raft_node
+            .read()
+            .await
+            .wait_for_leadership(std::time::Duration::from_secs(5))
+            .await
+            .unwrap();
+        let response = client
+            .write(create_write_request(create_config_command("status.json")))
+            .await
+            .unwrap();
+        let index = response.log_index.unwrap();
+
+        let status = client.get_cluster_status().await.unwrap();
+        assert!(status.healthy);
+        assert_eq!(status.leader_id, Some(1));
+        assert_eq!(status.members, vec![1]);
+        assert!(status.term >= 1);
+        assert!(status.last_log_index >= index);
+        assert!(status.commit_index >= index);
+        assert!(status.applied_index >= index);
+    }
+
+    #[test]
+    fn test_retry_policy_backoff_and_classification() {
+        use std::time::Duration;
+
+        let policy = RetryPolicy {
+            max_attempts: 5,
+            base_delay: Duration::from_millis(10),
+            max_delay: Duration::from_millis(35),
+            jitter: Duration::from_millis(5),
+        };
+        assert_eq!(policy.backoff(1), Duration::from_millis(10));
+        assert_eq!(policy.backoff(2), Duration::from_millis(20));
+        assert_eq!(policy.backoff(3), Duration::from_millis(35));
+        let delay = policy.delay(2);
+        assert!(delay >= Duration::from_millis(20) && delay <= Duration::from_millis(25));
+        assert_eq!(RetryPolicy::none().max_attempts, 1);
+
+        use crate::error::ConfluxError;
+        assert!(is_retryable(&ConfluxError::raft("No leader available")));
+        assert!(is_retryable(&ConfluxError::raft(
+            "Raft write failed: has to forward request to: Some(2), None"
+        )));
+        assert!(!is_retryable(&ConfluxError::validation("No leader available")));
+        assert!(!is_retryable(&ConfluxError::raft(
+            "Rate limit exceeded for client alice: 2 requests/second"
+        )));
+        assert!(!is_retryable(&ConfluxError::raft(
+            "No Raft node available - cannot process write requests"
+        )));
+    }
+
+    #[tokio::test]
+    async fn test_write_retries_until_leader_returns() {
+        use crate::config::{AppConfig, StorageConfig};
+        use crate::raft::node::{NodeConfig, RaftNode};
+        use std::time::Duration;
+        use tokio::sync::RwLock;
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let app_config = AppConfig {
+            storage: StorageConfig {
+                data_dir: temp_dir.path().to_string_lossy().to_string(),
+                cache_size_mb: 64,
+                max_write_buffer_number: 2,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let mut node = RaftNode::new(NodeConfig::default(), &app_config).await.unwrap();
+        node.start().await.unwrap();
+        node.wait_for_leadership(Duration::from_secs(5)).await.unwrap();
+        let raft_node = Arc::new(RwLock::new(node));
+        let store = raft_node.read().await.store();
+
+        // Without retries a leaderless write fails at once
+        let client = RaftClient::new_with_raft_node(store.clone(), raft_node.clone())
+            .with_retry_policy(RetryPolicy::none());
+        client.set_leader(None).await;
+        let result = client
+            .write_with_leader_detection(create_write_request(create_config_command("a.json")))
+            .await;
+        assert!(result.unwrap_err().to_string().contains("No leader available"));
+
+        // With retries the write succeeds once the leader comes back
+        let client = RaftClient::new_with_raft_node(store, raft_node).with_retry_policy(RetryPolicy {
+            max_attempts: 10,
+            base_delay: Duration::from_millis(20),
+            max_delay: Duration::from_millis(100),
+            jitter: Duration::ZERO,
+        });
+        client.set_leader(None).await;
+        let restorer = client.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(150)).await;
+            restorer.set_leader(Some(1)).await;
+        });
+
+        let response = client
+            .write_with_leader_detection(create_write_request(create_config_command("b.json")))
+            .await
+            .unwrap();
+        assert!(response.success);
+        assert!(client.dead_letters().is_empty().await);
+    }
+
+    /// Serves forwarded requests with the client of the target node
+    struct InProcessForwarder {
+        clients: std::collections::HashMap<NodeId, RaftClient>,
+        forwarded_reads: std::sync::atomic::AtomicUsize,
+    }
+
+    #[async_trait::async_trait]
+    impl LeaderForwarder for InProcessForwarder {
+        async fn forward_write(
+            &self,
+            leader: NodeId,
+            request: ClientWriteRequest,
+        ) -> Result<ClientWriteResponse> {
+            self.clients[&leader].write(request).await
+        }
+
+        async fn forward_read(
+            &self,
+            leader: NodeId,
+            request: ClientReadRequest,
+        ) -> Result<ClientReadResponse> {
+            self.forwarded_reads
+                .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            self.clients[&leader].read(request).await
+        }
+    }
+
+    #[tokio::test]
+    async fn test_read_fails_over_after_leadership_change() {
+        use crate::config::{AppConfig, StorageConfig};
+        use crate::raft::network::NetworkConfig;
+        use crate::raft::network_server::serve_raft_rpc;
+        use crate::raft::node::{NodeConfig, RaftNode};
+        use std::collections::HashMap;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::time::{Duration, Instant};
+        use tokio::net::TcpListener;
+        use tokio::sync::RwLock;
+
+        // Three nodes with node 1 as the initial leader
+        let temp_dirs: Vec<_> = (0..3).map(|_| tempfile::tempdir().unwrap()).collect();
+        let mut listeners = Vec::new();
+        let mut addresses = HashMap::new();
+        for node_id in 1..=3 {
+            let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            addresses.insert(node_id, listener.local_addr().unwrap().to_string());
+            listeners.push(listener);
+        }
+        let network_config = NetworkConfig::new(addresses.clone());
+
+        let mut nodes = Vec::new();
+        let mut servers = Vec::new();
+        for ((node_id, listener), temp_dir) in (1..=3).zip(listeners).zip(&temp_dirs) {
+            let app_config = AppConfig {
+                storage: StorageConfig {
+                    data_dir: temp_dir.path().to_string_lossy().to_string(),
+                    cache_size_mb: 8,
+                    write_buffer_size_mb: 8,
+                    max_write_buffer_number: 2,
+                    ..Default::default()
+                },
+                ..Default::default()
+            };
+            let config = NodeConfig {
+                node_id,
+                address: addresses[&node_id].clone(),
+                network_config: network_config.clone(),
+                ..Default::default()
+            };
+            let mut node = RaftNode::new(config, &app_config).await.unwrap();
+            if node_id == 1 {
+                node.start().await.unwrap();
+                node.wait_for_leadership(Duration::from_secs(5)).await.unwrap();
+            } else {
+                node.start_as_learner().await.unwrap();
+            }
+            servers.push(serve_raft_rpc(listener, node.get_raft().cloned().unwrap()));
+            nodes.push(node);
+        }
+        for node_id in 2..=3 {
+            nodes[0]
+                .add_learner(node_id, addresses[&node_id].clone())
+                .await
+                .unwrap();
+        }
+        nodes[0].promote_learners().await.unwrap();
+
+        let nodes: Vec<_> = nodes.into_iter().map(|node| Arc::new(RwLock::new(node))).collect();
+        let mut clients = Vec::new();
+        for node in &nodes {
+            let store = node.read().await.store();
+            clients.push(
+                RaftClient::new_with_raft_node(store, node.clone())
+                    .with_retry_policy(RetryPolicy::none()),
+            );
+        }
+        let forwarder = Arc::new(InProcessForwarder {
+            clients: HashMap::from([(2, clients[1].clone()), (3, clients[2].clone())]),
+            forwarded_reads: AtomicUsize::new(0),
+        });
+        let client = clients[0].clone().with_leader_forwarder(forwarder.clone());
+
+        let response = client
+            .write(create_write_request(create_config_command("failover.json")))
+            .await
+            .unwrap();
+        assert!(response.success);
+
+        // Leadership moves away after the client last talked to node 1 as leader
+        nodes[0].read().await.transfer_leadership(Some(2)).await.unwrap();
+        let start = Instant::now();
+        loop {
+            let mut agreed = true;
+            for node in &nodes {
+                agreed &= node.read().await.get_leader().await == Some(2);
+            }
+            if agreed {
+                break;
+            }
+            assert!(start.elapsed() < Duration::from_secs(10), "leadership transfer timed out");
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+
+        let request = create_get_config_request(
+            ConfigNamespace {
+                tenant: "test".to_string(),
+                app: "app".to_string(),
+                env: "dev".to_string(),
+            },
+            "failover.json".to_string(),
+            BTreeMap::new(),
+        );
+
+        // Without a forwarder the leadership loss surfaces after re-resolving the leader
+        match clients[0].read(request.clone()).await {
+            Err(crate::error::ConfluxError::Raft(RaftError::NotLeader { node_id, leader })) => {
+                assert_eq!(node_id, 1);
+                assert_eq!(leader, Some(2));
+            }
+            other => panic!("Expected not-leader error, got {:?}", other),
+        }
+        assert_eq!(clients[0].current_leader().await, Some(2));
+
+        // With a forwarder the read is served by the new leader exactly once
+        let response = client.read(request).await.unwrap();
+        assert!(response.data.is_some());
+        assert_eq!(forwarder.forwarded_reads.load(Ordering::SeqCst), 1);
+
+        // Writes go through the same forwarder
+        let response = client
+            .write(create_write_request(create_config_command("forwarded.json")))
+            .await
+            .unwrap();
+        assert!(response.success);
+
+        for server in servers {
+            server.abort();
+        }
+    }
+
+    /// Time a write attempt that takes `latency` under the client's write timeout
+    async fn timed_write(client: &RaftClient, latency: std::time::Duration) -> Result<()> {
+        client
+            .with_write_timeout(async {
+                tokio::time::sleep(latency).await;
+                Ok(())
+            })
+            .await
+    }
+
+    #[tokio::test]
+    async fn test_adaptive_timeout_converges() {
+        use std::time::Duration;
+
+        let (client, _temp_dir) = create_test_client().await;
+        tokio::time::pause();
+        let max = Duration::from_millis(ResourceLimits::default().request_timeout_ms);
+
+        // Disabled: latencies are tracked but the static timeout applies
+        timed_write(&client, Duration::from_millis(300)).await.unwrap();
+        assert_eq!(client.adaptive_timeout.timeout(max), max);
+
+        client.set_adaptive_timeout_enabled(true);
+        for _ in 0..200 {
+            timed_write(&client, Duration::from_millis(300)).await.unwrap();
+        }
+        assert!((client.get_p99_ema_ms() - 300.0).abs() < 2.0, "{}", client.get_p99_ema_ms());
+        let slow = client.adaptive_timeout.timeout(max);
+        assert!(slow >= Duration::from_millis(300) && slow < Duration::from_millis(305), "{:?}", slow);
+
+        // The timeout tightens as the latency decreases
+        for _ in 0..200 {
+            timed_write(&client, Duration::from_millis(120)).await.unwrap();
+        }
+        assert!((client.get_p99_ema_ms() - 120.0).abs() < 2.0, "{}", client.get_p99_ema_ms());
+        let fast = client.adaptive_timeout.timeout(max);
+        assert!(fast < slow);
+        assert!(fast >= Duration::from_millis(120) && fast < Duration::from_millis(125), "{:?}", fast);
+
+        // Never below the floor
+        for _ in 0..200 {
+            timed_write(&client, Duration::from_millis(20)).await.unwrap();
+        }
+        assert_eq!(client.adaptive_timeout.timeout(max), MIN_ADAPTIVE_TIMEOUT);
+    }
+
+    #[tokio::test]
+    async fn test_adaptive_timeout_expires_slow_writes() {
+        use std::time::Duration;
+
+        let (client, _temp_dir) = create_test_client().await;
+        tokio::time::pause();
+        client.set_adaptive_timeout_enabled(true);
+        let max = Duration::from_millis(ResourceLimits::default().request_timeout_ms);
+
+        for _ in 0..100 {
+            timed_write(&client, Duration::from_millis(200)).await.unwrap();
+        }
+        let timeout = client.adaptive_timeout.timeout(max);
+        assert!(timeout < Duration::from_millis(205), "{:?}", timeout);
+
+        let start = tokio::time::Instant::now();
+        let error = timed_write(&client, Duration::from_secs(3)).await.unwrap_err();
+        assert!(error.to_string().contains(WRITE_TIMED_OUT), "{}", error);
+        assert!(start.elapsed() >= timeout);
+        assert!(!is_retryable(&error));
+
+        // Repeated timeouts back the timeout off, up to the static timeout
+        for _ in 0..100 {
+            let _ = timed_write(&client, Duration::from_secs(60)).await;
+        }
+        assert!(client.adaptive_timeout.timeout(max) > timeout);
+        assert_eq!(client.adaptive_timeout.timeout(max), max);
+    }
 }
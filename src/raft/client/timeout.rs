@@ -0,0 +1,110 @@
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Error message returned when a write does not complete within its timeout
+pub const WRITE_TIMED_OUT: &str = "write timed out";
+
+/// Smoothing factor of the latency moving averages
+pub const ADAPTIVE_TIMEOUT_ALPHA: f64 = 0.1;
+
+/// Lower bound of the adaptive timeout
+pub const MIN_ADAPTIVE_TIMEOUT: Duration = Duration::from_millis(100);
+
+/// Number of recent latencies the P99 is taken over
+const LATENCY_WINDOW: usize = 100;
+
+/// Request timeout derived from observed request latency
+///
+/// Every recorded latency updates an exponential moving average (α =
+/// [`ADAPTIVE_TIMEOUT_ALPHA`]) of the latency, its variance, and the P99 of
+/// the last [`LATENCY_WINDOW`] latencies. While enabled, the timeout is
+/// `P99_ema + 2 * std_dev`, kept between [`MIN_ADAPTIVE_TIMEOUT`] and the
+/// static request timeout; while disabled, or before any latency was
+/// recorded, the static request timeout is used as is.
+#[derive(Debug, Default)]
+pub struct AdaptiveTimeout {
+    state: Mutex<LatencyStats>,
+}
+
+#[derive(Debug, Default)]
+struct LatencyStats {
+    enabled: bool,
+    samples: u64,
+    mean_ms: f64,
+    variance_ms: f64,
+    p99_ema_ms: f64,
+    window: VecDeque<f64>,
+}
+
+impl AdaptiveTimeout {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Switch between the adaptive and the static request timeout
+    ///
+    /// Latency statistics are kept while disabled.
+    pub fn set_enabled(&self, enabled: bool) {
+        self.state.lock().unwrap().enabled = enabled;
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.state.lock().unwrap().enabled
+    }
+
+    /// Record the latency of one request
+    pub fn record(&self, latency: Duration) {
+        let latency_ms = latency.as_secs_f64() * 1000.0;
+        let mut state = self.state.lock().unwrap();
+
+        if state.window.len() == LATENCY_WINDOW {
+            state.window.pop_front();
+        }
+        state.window.push_back(latency_ms);
+        let p99_ms = percentile(&state.window, 0.99);
+
+        if state.samples == 0 {
+            state.mean_ms = latency_ms;
+            state.variance_ms = 0.0;
+            state.p99_ema_ms = p99_ms;
+        } else {
+            let diff = latency_ms - state.mean_ms;
+            state.mean_ms += ADAPTIVE_TIMEOUT_ALPHA * diff;
+            state.variance_ms = (1.0 - ADAPTIVE_TIMEOUT_ALPHA)
+                * (state.variance_ms + ADAPTIVE_TIMEOUT_ALPHA * diff * diff);
+            state.p99_ema_ms += ADAPTIVE_TIMEOUT_ALPHA * (p99_ms - state.p99_ema_ms);
+        }
+        state.samples += 1;
+    }
+
+    /// Moving average of the P99 latency in milliseconds, 0 before any request
+    pub fn p99_ema_ms(&self) -> f64 {
+        self.state.lock().unwrap().p99_ema_ms
+    }
+
+    /// Standard deviation of the latency in milliseconds
+    pub fn std_dev_ms(&self) -> f64 {
+        self.state.lock().unwrap().variance_ms.sqrt()
+    }
+
+    /// Timeout for the next request given the static request timeout `max`
+    pub fn timeout(&self, max: Duration) -> Duration {
+        let state = self.state.lock().unwrap();
+        if !state.enabled || state.samples == 0 {
+            return max;
+        }
+        let timeout_ms = state.p99_ema_ms + 2.0 * state.variance_ms.sqrt();
+        Duration::from_secs_f64(timeout_ms / 1000.0)
+            .max(MIN_ADAPTIVE_TIMEOUT)
+            .min(max)
+    }
+}
+
+/// Nearest-rank percentile of `values`
+fn percentile(values: &VecDeque<f64>, quantile: f64) -> f64 {
+    let mut sorted: Vec<f64> = values.iter().copied().collect();
+    sorted.sort_by(f64::total_cmp);
+    let rank = (quantile * sorted.len() as f64).ceil() as usize;
+    sorted[rank.saturating_sub(1).min(sorted.len() - 1)]
+}
@@ -1,14 +1,22 @@
 use crate::raft::types::*;
 use serde::{Deserialize, Serialize};
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap};
 
 /// Client write request wrapper
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ClientWriteRequest {
     /// The command to execute
     pub command: RaftCommand,
-    /// Optional request ID for idempotency
+    /// Idempotency key; retrying with the same key returns the original
+    /// response instead of applying the command again
     pub request_id: Option<String>,
+    /// Client the request is rate limited as; `None` for node-internal writes
+    #[serde(default)]
+    pub client_id: Option<String>,
+    /// Only validate the command and return the would-be response, without
+    /// persisting it or going through consensus
+    #[serde(default)]
+    pub validate_only: bool,
 }
 
 /// Client read request wrapper
@@ -18,6 +26,11 @@ pub struct ClientReadRequest {
     pub operation: ReadOperation,
     /// Optional consistency level
     pub consistency: Option<ReadConsistency>,
+    /// Log index the serving node must have applied before answering
+    ///
+    /// The client's own last committed write index is always required as well.
+    #[serde(default)]
+    pub min_index: Option<u64>,
 }
 
 /// Read operation types
@@ -29,6 +42,20 @@ pub enum ReadOperation {
         name: String,
         /// Client labels for release targeting
         client_labels: BTreeMap<String, String>,
+        /// Values substituted for `${NAME}` placeholders in the content
+        #[serde(default)]
+        template_variables: HashMap<String, String>,
+        /// Fail when the content references a variable that is not provided
+        #[serde(default)]
+        strict: bool,
+    },
+    /// Get the configuration version that was published at a past time
+    GetConfigAt {
+        namespace: ConfigNamespace,
+        name: String,
+        at: chrono::DateTime<chrono::Utc>,
+        /// Client labels for release targeting
+        client_labels: BTreeMap<String, String>,
     },
     /// Get configuration version
     GetConfigVersion { config_id: u64, version_id: u64 },
@@ -37,7 +64,12 @@ pub enum ReadOperation {
         namespace: ConfigNamespace,
         /// Optional prefix filter
         prefix: Option<String>,
+        /// Optional label query (see [`LabelQuery`]) matched against release labels
+        #[serde(default)]
+        label_query: Option<String>,
     },
+    /// Search configurations by metadata
+    SearchConfigs { filter: ConfigFilter },
 }
 
 /// Read consistency levels
@@ -49,6 +81,13 @@ pub enum ReadConsistency {
     Strong,
     /// Read with linearizable semantics
     Linearizable,
+    /// Read from the local store without linearizability checks, as long as
+    /// this node applies committed entries within `max_lag_ms` of the leader
+    /// or is the leader holding a valid lease
+    Stale { max_lag_ms: u64 },
+    /// Read from the leader, skipping the quorum check while its leader lease
+    /// is valid and falling back to a linearizable read once it expired
+    Leased,
 }
 
 impl Default for ReadConsistency {
@@ -70,6 +109,19 @@ pub struct ClientReadResponse {
     pub consistency_level: ReadConsistency,
 }
 
+/// Response to [`RaftClient::read_batch`](super::RaftClient::read_batch)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReadBatchResponse {
+    /// Read index confirmed with a quorum once for the whole batch
+    pub read_index: u64,
+    /// Applied index of the state every operation was read from, at least `read_index`
+    pub applied_index: u64,
+    /// Result of each operation, in request order
+    pub results: Vec<Option<serde_json::Value>>,
+    /// Current leader ID
+    pub leader_id: Option<NodeId>,
+}
+
 /// Cluster status information
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ClusterStatus {
@@ -77,6 +129,9 @@ pub struct ClusterStatus {
     pub leader_id: Option<NodeId>,
     /// List of cluster members
     pub members: Vec<NodeId>,
+    /// Every node in the membership with its address and role (voter/learner)
+    #[serde(default)]
+    pub nodes: Vec<MemberInfo>,
     /// Current term
     pub term: u64,
     /// Last log index
@@ -85,4 +140,22 @@ pub struct ClusterStatus {
     pub commit_index: u64,
     /// Applied index
     pub applied_index: u64,
+    /// Whether the status comes from a running Raft node that knows the leader
+    pub healthy: bool,
+}
+
+impl ClusterStatus {
+    /// Status reported when no running Raft node is available
+    pub fn degraded() -> Self {
+        Self {
+            leader_id: None,
+            members: Vec::new(),
+            nodes: Vec::new(),
+            term: 0,
+            last_log_index: 0,
+            commit_index: 0,
+            applied_index: 0,
+            healthy: false,
+        }
+    }
 }
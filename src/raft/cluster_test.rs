@@ -92,6 +92,10 @@ impl ThreeNodeCluster {
                 election_timeout_min: 300,
                 election_timeout_max: 600,
                 resource_limits: crate::raft::node::ResourceLimits::default(),
+                election_priority: DEFAULT_ELECTION_PRIORITY,
+                snapshot_stream: crate::raft::node::SnapshotStreamConfig::default(),
+                pre_vote_enabled: false,
+                compress_log_entries: false,
             };
 
             let app_config = AppConfig {
@@ -101,6 +105,7 @@ impl ThreeNodeCluster {
                     cache_size_mb: 8,
                     write_buffer_size_mb: 8,
                     max_write_buffer_number: 2,
+                    ..Default::default()
                 },
                 ..Default::default()
             };
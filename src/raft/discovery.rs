@@ -0,0 +1,208 @@
+//! DNS-based discovery of Raft peers
+//!
+//! Resolves the peers configured by [`PeerDiscovery`] into a node ID to
+//! address mapping. Lookups go through [`PeerResolver`] so tests can supply
+//! their own records.
+
+use crate::error::{ConfluxError, Result};
+use crate::raft::network::PeerDiscovery;
+use crate::raft::types::NodeId;
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::io;
+use std::net::SocketAddr;
+use std::time::Duration;
+
+/// How often DNS peers are resolved again
+pub const DNS_DISCOVERY_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Consecutive unresolvable per-node names that end the probe for more nodes
+pub const DNS_PROBE_MISSES: u64 = 3;
+
+/// Resolves host names to socket addresses
+#[async_trait]
+pub trait PeerResolver: Send + Sync {
+    /// Look up all addresses of `host`, using `port` for each of them
+    async fn lookup(&self, host: &str, port: u16) -> io::Result<Vec<SocketAddr>>;
+}
+
+/// [`PeerResolver`] backed by the system resolver
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemResolver;
+
+#[async_trait]
+impl PeerResolver for SystemResolver {
+    async fn lookup(&self, host: &str, port: u16) -> io::Result<Vec<SocketAddr>> {
+        Ok(tokio::net::lookup_host((host, port)).await?.collect())
+    }
+}
+
+/// Resolve the peers of `discovery` into a node ID to address mapping
+///
+/// Record-index IDs shift when the set of addresses changes, so they suit
+/// peers with stable addresses; per-node names keep their IDs when a peer
+/// moves to a new address.
+///
+/// # Errors
+///
+/// Returns an IO error if the lookup fails or finds no peers.
+pub async fn resolve_peers(
+    resolver: &dyn PeerResolver,
+    discovery: &PeerDiscovery,
+) -> Result<HashMap<NodeId, String>> {
+    let (hostname, port, suffix) = match discovery {
+        PeerDiscovery::Static(addresses) => return Ok(addresses.clone()),
+        PeerDiscovery::Dns {
+            hostname,
+            port,
+            node_id_suffix,
+        } => (hostname, *port, node_id_suffix),
+    };
+
+    let peers = match suffix {
+        None => {
+            let mut addresses = resolver
+                .lookup(hostname, port)
+                .await
+                .map_err(|e| lookup_error(hostname, e))?;
+            addresses.sort();
+            addresses.dedup();
+            (1..)
+                .zip(addresses)
+                .map(|(node_id, address)| (node_id, address.to_string()))
+                .collect()
+        }
+        Some(suffix) => {
+            let mut peers = HashMap::new();
+            let mut misses = 0;
+            let mut node_id = 1;
+            while misses < DNS_PROBE_MISSES {
+                let name = node_name(hostname, node_id, suffix);
+                match resolver.lookup(&name, port).await {
+                    Ok(addresses) if !addresses.is_empty() => {
+                        misses = 0;
+                        if let Some(address) = addresses.iter().min() {
+                            peers.insert(node_id, address.to_string());
+                        }
+                    }
+                    _ => misses += 1,
+                }
+                node_id += 1;
+            }
+            peers
+        }
+    };
+
+    if peers.is_empty() {
+        return Err(lookup_error(
+            hostname,
+            io::Error::new(io::ErrorKind::NotFound, "no peers found"),
+        ));
+    }
+    Ok(peers)
+}
+
+/// DNS name of a single node, e.g. `node-2.conflux.svc`
+pub fn node_name(hostname: &str, node_id: NodeId, suffix: &str) -> String {
+    format!("{}-{}.{}", hostname, node_id, suffix.trim_start_matches('.'))
+}
+
+fn lookup_error(hostname: &str, e: io::Error) -> ConfluxError {
+    ConfluxError::Io(io::Error::new(
+        e.kind(),
+        format!("Failed to resolve peers from {}: {}", hostname, e),
+    ))
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    /// Resolver answering from a fixed record table
+    #[derive(Default)]
+    pub(crate) struct MockResolver {
+        records: Mutex<HashMap<String, Vec<SocketAddr>>>,
+    }
+
+    impl MockResolver {
+        pub(crate) fn set(&self, host: &str, addresses: &[&str]) {
+            let addresses = addresses.iter().map(|a| a.parse().unwrap()).collect();
+            self.records.lock().unwrap().insert(host.to_string(), addresses);
+        }
+    }
+
+    #[async_trait]
+    impl PeerResolver for MockResolver {
+        async fn lookup(&self, host: &str, port: u16) -> io::Result<Vec<SocketAddr>> {
+            let records = self.records.lock().unwrap();
+            let addresses = records
+                .get(host)
+                .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, host.to_string()))?;
+            // Records without a port answer on the requested one
+            Ok(addresses
+                .iter()
+                .map(|a| match a.port() {
+                    0 => SocketAddr::new(a.ip(), port),
+                    _ => *a,
+                })
+                .collect())
+        }
+    }
+
+    fn dns(node_id_suffix: Option<&str>) -> PeerDiscovery {
+        PeerDiscovery::Dns {
+            hostname: "node".to_string(),
+            port: 9000,
+            node_id_suffix: node_id_suffix.map(str::to_string),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_record_index_assigns_ids_in_address_order() {
+        let resolver = MockResolver::default();
+        resolver.set("node", &["10.0.0.3:0", "10.0.0.1:0", "10.0.0.2:0", "10.0.0.1:0"]);
+
+        let peers = resolve_peers(&resolver, &dns(None)).await.unwrap();
+        assert_eq!(
+            peers,
+            HashMap::from([
+                (1, "10.0.0.1:9000".to_string()),
+                (2, "10.0.0.2:9000".to_string()),
+                (3, "10.0.0.3:9000".to_string()),
+            ])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_node_id_suffix_probes_per_node_names() {
+        let resolver = MockResolver::default();
+        resolver.set("node-1.conflux.svc", &["10.0.0.9:0"]);
+        // A missing node does not end the probe
+        resolver.set("node-3.conflux.svc", &["10.0.0.5:0"]);
+        resolver.set("node-9.conflux.svc", &["10.0.0.7:0"]);
+
+        let peers = resolve_peers(&resolver, &dns(Some(".conflux.svc"))).await.unwrap();
+        assert_eq!(
+            peers,
+            HashMap::from([(1, "10.0.0.9:9000".to_string()), (3, "10.0.0.5:9000".to_string())])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_unresolvable_peers_are_an_error() {
+        let resolver = MockResolver::default();
+        assert!(resolve_peers(&resolver, &dns(None)).await.is_err());
+        assert!(resolve_peers(&resolver, &dns(Some("conflux.svc"))).await.is_err());
+
+        resolver.set("node", &[]);
+        let err = resolve_peers(&resolver, &dns(None)).await.unwrap_err();
+        assert!(err.to_string().contains("Failed to resolve peers from node"), "{}", err);
+
+        let addresses = HashMap::from([(1, "127.0.0.1:9000".to_string())]);
+        let peers = resolve_peers(&resolver, &PeerDiscovery::Static(addresses.clone()))
+            .await
+            .unwrap();
+        assert_eq!(peers, addresses);
+    }
+}
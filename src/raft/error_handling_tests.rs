@@ -7,10 +7,10 @@ mod error_handling_tests {
 
     use crate::auth::AuthContext;
     use crate::config::AppConfig;
-    use crate::error::ConfluxError;
+    use crate::error::{ConfluxError, RaftError};
     use crate::raft::validation::{ClusterValidator, NodeValidator};
     use crate::raft::{
-        node::{NodeConfig, RaftNode, ResourceLimits},
+        node::{NodeConfig, RaftNode, ResourceLimits, SnapshotStreamConfig},
         types::*,
         validation::{RaftInputValidator, ValidationConfig},
     };
@@ -29,10 +29,8 @@ mod error_handling_tests {
             },
             storage: crate::config::StorageConfig {
                 data_dir: format!("/tmp/conflux_error_test_{}", test_id),
-                max_open_files: 1000,
                 cache_size_mb: 64,
-                write_buffer_size_mb: 64,
-                max_write_buffer_number: 3,
+                ..Default::default()
             },
             ..Default::default()
         }
@@ -49,6 +47,10 @@ mod error_handling_tests {
             election_timeout_min: 150,
             election_timeout_max: 300,
             resource_limits: ResourceLimits::default(),
+            election_priority: DEFAULT_ELECTION_PRIORITY,
+            snapshot_stream: SnapshotStreamConfig::default(),
+            pre_vote_enabled: false,
+            compress_log_entries: false,
         }
     }
 
@@ -255,6 +257,7 @@ mod error_handling_tests {
             max_request_size: 10,  // Very small
             max_memory_usage: 100, // Very small
             request_timeout_ms: 1000,
+            ..Default::default()
         };
 
         let node = RaftNode::new(node_config, &app_config).await.unwrap();
@@ -265,8 +268,8 @@ mod error_handling_tests {
         assert!(result.is_err());
 
         match result {
-            Err(ConfluxError::Raft(msg)) => {
-                assert!(msg.contains("exceeds limit"));
+            Err(ConfluxError::Raft(error @ RaftError::RequestTooLarge { .. })) => {
+                assert!(error.to_string().contains("exceeds limit"));
             }
             _ => panic!("Expected request size limit error"),
         }
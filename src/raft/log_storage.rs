@@ -4,7 +4,7 @@
 //! 将日志管理与状态机逻辑分离。
 
 use crate::raft::types::*;
-use crate::raft::store::Store;
+use crate::raft::store::{decode_log_entry, Store};
 use openraft::{
     Entry, OptionalSend, RaftLogReader, StorageError,
 };
@@ -25,6 +25,14 @@ impl ConfluxLogStorage {
     pub fn new(store: Arc<Store>) -> Self {
         Self { store }
     }
+
+    /// 日志条目的压缩统计
+    ///
+    /// 返回 `(compressed_bytes, uncompressed_bytes)`：自存储打开以来写入的
+    /// 日志条目在存储中的字节数和压缩前的字节数
+    pub fn log_compression_stats(&self) -> (u64, u64) {
+        self.store.log_compression_stats()
+    }
 }
 
 impl RaftLogReader<TypeConfig> for ConfluxLogStorage {
@@ -35,8 +43,8 @@ impl RaftLogReader<TypeConfig> for ConfluxLogStorage {
         let logs = self.store.logs.read().await;
         let mut entries = Vec::new();
 
-        for (index, entry_json) in logs.range(range) {
-            match serde_json::from_str::<Entry<TypeConfig>>(entry_json) {
+        for (index, encoded) in logs.range(range) {
+            match decode_log_entry(encoded) {
                 Ok(entry) => entries.push(entry),
                 Err(e) => {
                     error!("Failed to deserialize log entry at index {}: {}", index, e);
@@ -77,8 +85,8 @@ impl openraft::storage::RaftLogReader<TypeConfig> for ConfluxLogReader {
         let logs = self.store.logs.read().await;
         let mut entries = Vec::new();
         
-        for (index, entry_json) in logs.range(range) {
-            match serde_json::from_str::<Entry<TypeConfig>>(entry_json) {
+        for (index, encoded) in logs.range(range) {
+            match decode_log_entry(encoded) {
                 Ok(entry) => {
                     entries.push(entry);
                 }
@@ -1,10 +1,25 @@
-use crate::raft::types::NodeId;
+use crate::config::ObservabilityConfig;
+use crate::error::{ConfluxError, Result};
+use crate::raft::types::{Node, NodeId};
+use reqwest::header::{AUTHORIZATION, CONTENT_TYPE};
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::Arc;
-use std::time::{Duration, Instant};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use tokio::sync::RwLock;
+use tokio::task::JoinHandle;
 use tracing::{debug, info, warn};
 
+/// InfluxDB measurement that node metrics are written to
+pub const INFLUX_MEASUREMENT: &str = "raft_node";
+
+/// Timeout of a single InfluxDB write
+const INFLUX_WRITE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Upper bounds (milliseconds) of the client request latency histogram buckets
+pub const CLIENT_LATENCY_BUCKETS_MS: [u64; 11] =
+    [5, 10, 25, 50, 100, 250, 500, 1000, 2500, 5000, 10000];
+
 /// Comprehensive metrics collection for Raft cluster
 #[derive(Debug, Clone)]
 pub struct RaftMetricsCollector {
@@ -16,6 +31,12 @@ pub struct RaftMetricsCollector {
     performance_metrics: Arc<RwLock<PerformanceMetrics>>,
     /// Start time for uptime calculation
     start_time: Instant,
+    /// Value of the `tenant` tag of metrics written to InfluxDB
+    tenant: String,
+    /// Client used to write metrics to InfluxDB
+    http_client: reqwest::Client,
+    /// Replication lag at which the node health is downgraded
+    lag_thresholds: LagThresholds,
 }
 
 /// Node-specific metrics
@@ -39,10 +60,23 @@ pub struct NodeMetrics {
     pub votes_received: u64,
     /// Total votes granted to other nodes
     pub votes_granted: u64,
+    /// Pre-vote responses granting this node's candidacy
+    pub pre_vote_granted_count: u64,
+    /// Pre-vote responses refusing this node's candidacy
+    pub pre_vote_denied_count: u64,
+    /// Failed DNS lookups of peers
+    pub dns_resolution_failures: u64,
+    /// Successful reconnections to peers after an RPC to them failed
+    pub peer_reconnects: u64,
     /// Last heartbeat received time
     pub last_heartbeat: Option<Instant>,
+    /// Last time each peer answered a connection heartbeat
+    pub last_seen: HashMap<NodeId, Instant>,
     /// Election timeout count
     pub election_timeouts: u64,
+    /// Log entries the slowest voter trails this node's commit index; only
+    /// measured while this node is the leader
+    pub commit_lag: u64,
     /// Node uptime
     pub uptime: Duration,
 }
@@ -67,7 +101,7 @@ pub struct ClusterMetrics {
 }
 
 /// Node status in cluster
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub enum NodeStatus {
     /// Node is active and reachable
     Active,
@@ -79,6 +113,9 @@ pub enum NodeStatus {
     Joining,
     /// Node is being removed
     Leaving,
+    /// Node stopped answering connection heartbeats for longer than the
+    /// peer dead timeout
+    Dead,
 }
 
 impl Default for NodeStatus {
@@ -110,6 +147,63 @@ pub struct PerformanceMetrics {
     pub snapshot_size: u64,
     /// Last snapshot creation time
     pub last_snapshot_time: Option<Instant>,
+    /// Number of manual log compactions performed
+    pub compaction_count: u64,
+    /// Stale reads served without linearizability checks
+    pub stale_reads: u64,
+    /// Stale reads rejected because the node lagged too far behind
+    pub stale_reads_rejected: u64,
+    /// Reads served under a valid leader lease, skipping the quorum check
+    pub lease_hits: u64,
+    /// Leased reads that found no valid lease and confirmed leadership with a quorum
+    pub lease_misses: u64,
+    /// Snapshot bytes sent to followers
+    pub snapshot_bytes_sent: u64,
+    /// Snapshot bytes received and installed from the leader
+    pub snapshot_bytes_received: u64,
+    /// Bytes of Raft log entries as stored, after compression
+    pub log_bytes_compressed: u64,
+    /// Bytes of Raft log entries before compression
+    pub log_bytes_uncompressed: u64,
+    /// Latency of client requests timed by the adaptive request timeout
+    pub client_latency: LatencyHistogram,
+}
+
+/// Latency histogram over [`CLIENT_LATENCY_BUCKETS_MS`]
+///
+/// `counts[i]` holds the requests that took at most `CLIENT_LATENCY_BUCKETS_MS[i]`
+/// and more than the previous bound; the last entry counts slower requests.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LatencyHistogram {
+    pub counts: [u64; CLIENT_LATENCY_BUCKETS_MS.len() + 1],
+    /// Number of observed requests
+    pub count: u64,
+    /// Sum of all observed latencies in milliseconds
+    pub sum_ms: f64,
+}
+
+impl Default for LatencyHistogram {
+    fn default() -> Self {
+        Self {
+            counts: [0; CLIENT_LATENCY_BUCKETS_MS.len() + 1],
+            count: 0,
+            sum_ms: 0.0,
+        }
+    }
+}
+
+impl LatencyHistogram {
+    /// Count one request that took `latency`
+    pub fn observe(&mut self, latency: Duration) {
+        let latency_ms = latency.as_secs_f64() * 1000.0;
+        let bucket = CLIENT_LATENCY_BUCKETS_MS
+            .iter()
+            .position(|&bound| latency_ms <= bound as f64)
+            .unwrap_or(CLIENT_LATENCY_BUCKETS_MS.len());
+        self.counts[bucket] += 1;
+        self.count += 1;
+        self.sum_ms += latency_ms;
+    }
 }
 
 impl RaftMetricsCollector {
@@ -125,9 +219,27 @@ impl RaftMetricsCollector {
             cluster_metrics: Arc::new(RwLock::new(ClusterMetrics::default())),
             performance_metrics: Arc::new(RwLock::new(PerformanceMetrics::default())),
             start_time: Instant::now(),
+            tenant: "default".to_string(),
+            http_client: reqwest::Client::builder()
+                .timeout(INFLUX_WRITE_TIMEOUT)
+                .build()
+                .unwrap_or_default(),
+            lag_thresholds: LagThresholds::default(),
         }
     }
 
+    /// Set the `tenant` tag of the metrics written to InfluxDB
+    pub fn with_tenant(mut self, tenant: impl Into<String>) -> Self {
+        self.tenant = tenant.into();
+        self
+    }
+
+    /// Downgrade the node health once the replication lag exceeds `thresholds`
+    pub fn with_lag_thresholds(mut self, thresholds: LagThresholds) -> Self {
+        self.lag_thresholds = thresholds;
+        self
+    }
+
     /// Update node metrics
     pub async fn update_node_metrics(
         &self,
@@ -154,10 +266,20 @@ impl RaftMetricsCollector {
         metrics.leader_id = leader_id;
         metrics.is_leader = is_leader;
         metrics.uptime = self.start_time.elapsed();
+        if !is_leader {
+            // Commit lag is only measured by the leader
+            metrics.commit_lag = 0;
+        }
 
         debug!("Updated node metrics for node {}", metrics.node_id);
     }
 
+    /// Record how many log entries the slowest voter trails the leader's
+    /// commit index
+    pub async fn update_commit_lag(&self, commit_lag: u64) {
+        self.node_metrics.write().await.commit_lag = commit_lag;
+    }
+
     /// Record election timeout
     pub async fn record_election_timeout(&self) {
         let mut metrics = self.node_metrics.write().await;
@@ -182,6 +304,26 @@ impl RaftMetricsCollector {
         debug!("Vote granted by node {}", metrics.node_id);
     }
 
+    /// Record a pre-vote response to this node's candidacy
+    pub async fn record_pre_vote(&self, granted: bool) {
+        let mut metrics = self.node_metrics.write().await;
+        if granted {
+            metrics.pre_vote_granted_count += 1;
+        } else {
+            metrics.pre_vote_denied_count += 1;
+        }
+    }
+
+    /// Record a failed DNS lookup of peers
+    pub async fn record_dns_failure(&self) {
+        self.node_metrics.write().await.dns_resolution_failures += 1;
+    }
+
+    /// Record a successful reconnection to an unreachable peer
+    pub async fn record_peer_reconnect(&self) {
+        self.node_metrics.write().await.peer_reconnects += 1;
+    }
+
     /// Update heartbeat received time
     pub async fn record_heartbeat(&self) {
         let mut metrics = self.node_metrics.write().await;
@@ -189,6 +331,24 @@ impl RaftMetricsCollector {
         debug!("Heartbeat recorded for node {}", metrics.node_id);
     }
 
+    /// Record that a peer answered a connection heartbeat
+    pub async fn record_peer_seen(&self, peer_id: NodeId) {
+        self.node_metrics
+            .write()
+            .await
+            .last_seen
+            .insert(peer_id, Instant::now());
+    }
+
+    /// Set a peer's status in the cluster membership
+    pub async fn set_peer_status(&self, peer_id: NodeId, status: NodeStatus) {
+        self.cluster_metrics
+            .write()
+            .await
+            .membership
+            .insert(peer_id, status);
+    }
+
     /// Update cluster metrics
     pub async fn update_cluster_metrics(
         &self,
@@ -238,6 +398,12 @@ impl RaftMetricsCollector {
         );
     }
 
+    /// Record the latency of a client request in the client latency histogram
+    pub async fn record_client_latency(&self, latency: Duration) {
+        let mut metrics = self.performance_metrics.write().await;
+        metrics.client_latency.observe(latency);
+    }
+
     /// Record replication latency
     pub async fn record_replication_latency(&self, latency: Duration) {
         let mut metrics = self.performance_metrics.write().await;
@@ -286,6 +452,58 @@ impl RaftMetricsCollector {
         info!("Snapshot creation recorded");
     }
 
+    /// Record a completed log compaction
+    pub async fn record_compaction(&self, snapshot_size: u64) {
+        let mut metrics = self.performance_metrics.write().await;
+        metrics.compaction_count += 1;
+        metrics.snapshot_size = snapshot_size;
+        metrics.last_snapshot_time = Some(Instant::now());
+        info!(
+            "Log compaction recorded (total: {}, snapshot size: {}KB)",
+            metrics.compaction_count,
+            snapshot_size / 1024
+        );
+    }
+
+    /// Record a stale read, whether it was served or rejected for lag
+    pub async fn record_stale_read(&self, served: bool) {
+        let mut metrics = self.performance_metrics.write().await;
+        if served {
+            metrics.stale_reads += 1;
+        } else {
+            metrics.stale_reads_rejected += 1;
+        }
+    }
+
+    /// Record a read that relied on the leader lease, whether the lease was valid
+    pub async fn record_lease_read(&self, hit: bool) {
+        let mut metrics = self.performance_metrics.write().await;
+        if hit {
+            metrics.lease_hits += 1;
+        } else {
+            metrics.lease_misses += 1;
+        }
+    }
+
+    /// Record snapshot bytes sent to a follower
+    pub async fn record_snapshot_bytes_sent(&self, bytes: u64) {
+        let mut metrics = self.performance_metrics.write().await;
+        metrics.snapshot_bytes_sent += bytes;
+    }
+
+    /// Record snapshot bytes received from the leader
+    pub async fn record_snapshot_bytes_received(&self, bytes: u64) {
+        let mut metrics = self.performance_metrics.write().await;
+        metrics.snapshot_bytes_received += bytes;
+    }
+
+    /// Update the byte counts of the Raft log entries written by the store
+    pub async fn set_log_compression_stats(&self, compressed: u64, uncompressed: u64) {
+        let mut metrics = self.performance_metrics.write().await;
+        metrics.log_bytes_compressed = compressed;
+        metrics.log_bytes_uncompressed = uncompressed;
+    }
+
     /// Get all metrics as a comprehensive report
     pub async fn get_metrics_report(&self) -> MetricsReport {
         let node_metrics = self.node_metrics.read().await.clone();
@@ -293,6 +511,17 @@ impl RaftMetricsCollector {
         let performance_metrics = self.performance_metrics.read().await.clone();
 
         MetricsReport {
+            compaction_count: performance_metrics.compaction_count,
+            stale_reads: performance_metrics.stale_reads,
+            stale_reads_rejected: performance_metrics.stale_reads_rejected,
+            lease_hits: performance_metrics.lease_hits,
+            lease_misses: performance_metrics.lease_misses,
+            snapshot_bytes_sent: performance_metrics.snapshot_bytes_sent,
+            snapshot_bytes_received: performance_metrics.snapshot_bytes_received,
+            log_compression_ratio: compression_ratio(
+                performance_metrics.log_bytes_compressed,
+                performance_metrics.log_bytes_uncompressed,
+            ),
             node_metrics,
             cluster_metrics,
             performance_metrics,
@@ -300,6 +529,127 @@ impl RaftMetricsCollector {
         }
     }
 
+    /// Write the current metrics report to InfluxDB v2
+    ///
+    /// The report is serialized as one line of InfluxDB line protocol (see
+    /// [`MetricsReport::to_line_protocol`]) with a nanosecond timestamp and
+    /// `POST`ed to `{endpoint}/api/v2/write`.
+    ///
+    /// # Errors
+    /// Returns an error if InfluxDB cannot be reached or rejects the write
+    pub async fn flush_to_influx(
+        &self,
+        endpoint: &str,
+        token: Option<&str>,
+        bucket: &str,
+        org: &str,
+    ) -> Result<()> {
+        let timestamp_ns = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos();
+        let body = self
+            .get_metrics_report()
+            .await
+            .to_line_protocol(&self.tenant, timestamp_ns);
+
+        let mut request = self
+            .http_client
+            .post(format!("{}/api/v2/write", endpoint.trim_end_matches('/')))
+            .query(&[("org", org), ("bucket", bucket), ("precision", "ns")])
+            .header(CONTENT_TYPE, "text/plain; charset=utf-8")
+            .body(body);
+        if let Some(token) = token {
+            request = request.header(AUTHORIZATION, format!("Token {}", token));
+        }
+
+        let response = request.send().await?;
+        let status = response.status();
+        if !status.is_success() {
+            let message = response.text().await.unwrap_or_default();
+            return Err(ConfluxError::internal(format!(
+                "InfluxDB rejected metrics write with status {}: {}",
+                status, message
+            )));
+        }
+        Ok(())
+    }
+
+    /// Periodically write metrics to InfluxDB in a background task
+    ///
+    /// Returns `None` when no `influx_endpoint` is configured. Failed writes
+    /// are logged and the next interval is tried again.
+    pub fn spawn_influx_flush(
+        self: &Arc<Self>,
+        config: &ObservabilityConfig,
+    ) -> Option<JoinHandle<()>> {
+        let endpoint = config.influx_endpoint.clone()?;
+        let token = config.influx_token.clone();
+        let bucket = config.influx_bucket.clone();
+        let org = config.influx_org.clone();
+        let period = Duration::from_secs(config.collection_interval_secs.max(1));
+        let collector = self.clone();
+
+        info!(
+            "Writing Raft metrics to InfluxDB at {} every {:?}",
+            endpoint, period
+        );
+        Some(tokio::spawn(async move {
+            let mut interval = tokio::time::interval(period);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                interval.tick().await;
+                if let Err(e) = collector
+                    .flush_to_influx(&endpoint, token.as_deref(), &bucket, &org)
+                    .await
+                {
+                    warn!("Failed to write metrics to InfluxDB: {}", e);
+                }
+            }
+        }))
+    }
+
+    /// Update the node metrics from openraft's metrics and summarize them
+    ///
+    /// This is what a node reports when the leader pulls cluster-wide metrics.
+    pub async fn node_summary(
+        &self,
+        raft_metrics: &openraft::RaftMetrics<NodeId, Node>,
+    ) -> NodeMetricsSummary {
+        self.update_node_metrics(
+            raft_metrics.current_term,
+            raft_metrics.last_log_index.unwrap_or(0),
+            raft_metrics.last_applied.map_or(0, |log_id| log_id.index),
+            raft_metrics.current_leader,
+            raft_metrics.current_leader == Some(raft_metrics.id),
+        )
+        .await;
+
+        let node = self.node_metrics.read().await.clone();
+        let performance = self.performance_metrics.read().await;
+        let uptime = self.start_time.elapsed();
+        let request_rate = if uptime.as_secs_f64() > 0.0 {
+            performance.total_requests as f64 / uptime.as_secs_f64()
+        } else {
+            0.0
+        };
+
+        NodeMetricsSummary {
+            node_id: node.node_id,
+            current_term: node.current_term,
+            last_log_index: node.last_log_index,
+            last_applied: node.last_applied,
+            apply_lag: node.last_log_index.saturating_sub(node.last_applied),
+            leader_id: node.leader_id,
+            is_leader: node.is_leader,
+            total_requests: performance.total_requests,
+            failed_requests: performance.failed_requests,
+            request_rate,
+            error_rate: error_rate(performance.failed_requests, performance.total_requests),
+            uptime_secs: uptime.as_secs(),
+        }
+    }
+
     /// Calculate current request throughput
     pub async fn calculate_throughput(&self) -> f64 {
         let metrics = self.performance_metrics.read().await;
@@ -355,9 +705,25 @@ impl RaftMetricsCollector {
             HealthStatus::Unhealthy
         };
 
+        // A node falling behind is downgraded regardless of its score
+        let lag = ReplicationLag {
+            apply_lag: node_metrics
+                .last_log_index
+                .saturating_sub(node_metrics.last_applied),
+            commit_lag: node_metrics.commit_lag,
+        };
+        let lag_status = self.lag_thresholds.classify(lag.max());
+        if lag_status > HealthStatus::Healthy {
+            warn!(
+                "Node {} is falling behind: apply lag {}, commit lag {}",
+                node_metrics.node_id, lag.apply_lag, lag.commit_lag
+            );
+        }
+
         NodeHealth {
-            status,
+            status: status.max(lag_status),
             score: health_score.max(0.0).min(100.0),
+            lag,
             last_check: Instant::now(),
         }
     }
@@ -370,6 +736,218 @@ pub struct MetricsReport {
     pub cluster_metrics: ClusterMetrics,
     pub performance_metrics: PerformanceMetrics,
     pub collection_time: Instant,
+    /// Number of log compactions performed on this node
+    pub compaction_count: u64,
+    /// Number of stale reads served by this node
+    pub stale_reads: u64,
+    /// Number of stale reads rejected because this node lagged too far behind
+    pub stale_reads_rejected: u64,
+    /// Number of reads served under a valid leader lease
+    pub lease_hits: u64,
+    /// Number of leased reads that had to confirm leadership with a quorum
+    pub lease_misses: u64,
+    /// Total snapshot bytes sent to followers
+    pub snapshot_bytes_sent: u64,
+    /// Total snapshot bytes received from the leader
+    pub snapshot_bytes_received: u64,
+    /// Stored size of the Raft log entries relative to their uncompressed
+    /// size; 1.0 when nothing was compressed
+    pub log_compression_ratio: f64,
+}
+
+/// `compressed / uncompressed`, or 1.0 before any bytes were written
+fn compression_ratio(compressed: u64, uncompressed: u64) -> f64 {
+    if uncompressed == 0 {
+        1.0
+    } else {
+        compressed as f64 / uncompressed as f64
+    }
+}
+
+/// Metrics of one node, as pulled by the leader for cluster-wide aggregation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NodeMetricsSummary {
+    pub node_id: NodeId,
+    pub current_term: u64,
+    pub last_log_index: u64,
+    pub last_applied: u64,
+    /// Log entries the node has but not yet applied
+    pub apply_lag: u64,
+    pub leader_id: Option<NodeId>,
+    pub is_leader: bool,
+    /// Client requests handled by the node
+    pub total_requests: u64,
+    pub failed_requests: u64,
+    /// Client requests per second since the node started
+    pub request_rate: f64,
+    /// Fraction of client requests that failed
+    pub error_rate: f64,
+    pub uptime_secs: u64,
+}
+
+/// Whether a node answered the leader's metrics pull
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NodeMetricsState {
+    Reported,
+    /// The node could not be reached or did not answer in time
+    Unknown,
+}
+
+/// One node's entry in the cluster-wide metrics
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ClusterNodeMetrics {
+    pub node_id: NodeId,
+    pub state: NodeMetricsState,
+    /// Metrics reported by the node; `None` while its state is unknown
+    pub metrics: Option<NodeMetricsSummary>,
+    /// Why the node's metrics could not be pulled
+    pub error: Option<String>,
+}
+
+impl ClusterNodeMetrics {
+    /// Entry of a node that reported its metrics
+    pub fn reported(metrics: NodeMetricsSummary) -> Self {
+        Self {
+            node_id: metrics.node_id,
+            state: NodeMetricsState::Reported,
+            metrics: Some(metrics),
+            error: None,
+        }
+    }
+
+    /// Entry of a node whose metrics could not be pulled
+    pub fn unknown(node_id: NodeId, error: impl Into<String>) -> Self {
+        Self {
+            node_id,
+            state: NodeMetricsState::Unknown,
+            metrics: None,
+            error: Some(error.into()),
+        }
+    }
+}
+
+/// Cluster-wide totals over the nodes that reported metrics
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ClusterMetricsTotals {
+    pub node_count: usize,
+    pub reporting_nodes: usize,
+    pub unknown_nodes: usize,
+    pub total_requests: u64,
+    pub failed_requests: u64,
+    /// Sum of the request rates of the reporting nodes
+    pub request_rate: f64,
+    /// `failed_requests / total_requests` over the reporting nodes
+    pub error_rate: f64,
+    /// Largest apply lag of any reporting node
+    pub max_apply_lag: u64,
+}
+
+/// Metrics of every cluster member, collected by the leader
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AggregatedClusterMetrics {
+    /// Node that collected the metrics
+    pub leader_id: NodeId,
+    /// One entry per member, sorted by node ID
+    pub nodes: Vec<ClusterNodeMetrics>,
+    pub totals: ClusterMetricsTotals,
+}
+
+impl AggregatedClusterMetrics {
+    /// Compute the totals over the given node entries
+    pub fn new(leader_id: NodeId, mut nodes: Vec<ClusterNodeMetrics>) -> Self {
+        nodes.sort_by_key(|node| node.node_id);
+
+        let mut totals = ClusterMetricsTotals {
+            node_count: nodes.len(),
+            ..Default::default()
+        };
+        for node in &nodes {
+            let Some(metrics) = &node.metrics else {
+                totals.unknown_nodes += 1;
+                continue;
+            };
+            totals.reporting_nodes += 1;
+            totals.total_requests += metrics.total_requests;
+            totals.failed_requests += metrics.failed_requests;
+            totals.request_rate += metrics.request_rate;
+            totals.max_apply_lag = totals.max_apply_lag.max(metrics.apply_lag);
+        }
+        totals.error_rate = error_rate(totals.failed_requests, totals.total_requests);
+
+        Self {
+            leader_id,
+            nodes,
+            totals,
+        }
+    }
+}
+
+fn error_rate(failed: u64, total: u64) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        failed as f64 / total as f64
+    }
+}
+
+impl MetricsReport {
+    /// Serialize the node metrics as one line of InfluxDB line protocol
+    ///
+    /// The line has measurement [`INFLUX_MEASUREMENT`], tags `node_id` and
+    /// `tenant`, an integer field for every numeric [`NodeMetrics`] field and
+    /// the boolean `is_leader`; `leader_id` is omitted while no leader is
+    /// known and the uptime is written in whole seconds.
+    ///
+    /// # Arguments
+    /// * `tenant` - value of the `tenant` tag
+    /// * `timestamp_ns` - Unix timestamp of the point in nanoseconds
+    pub fn to_line_protocol(&self, tenant: &str, timestamp_ns: u128) -> String {
+        let node = &self.node_metrics;
+        let mut fields = vec![
+            ("current_term", node.current_term),
+            ("last_log_index", node.last_log_index),
+            ("last_applied", node.last_applied),
+            ("leadership_changes", node.leadership_changes),
+            ("votes_received", node.votes_received),
+            ("votes_granted", node.votes_granted),
+            ("pre_vote_granted_count", node.pre_vote_granted_count),
+            ("pre_vote_denied_count", node.pre_vote_denied_count),
+            ("dns_resolution_failures", node.dns_resolution_failures),
+            ("peer_reconnects", node.peer_reconnects),
+            ("election_timeouts", node.election_timeouts),
+            ("uptime_secs", node.uptime.as_secs()),
+        ];
+        if let Some(leader_id) = node.leader_id {
+            fields.push(("leader_id", leader_id));
+        }
+
+        let fields: Vec<String> = fields
+            .into_iter()
+            .map(|(name, value)| format!("{}={}i", name, value))
+            .chain(std::iter::once(format!("is_leader={}", node.is_leader)))
+            .collect();
+        format!(
+            "{},node_id={},tenant={} {} {}",
+            INFLUX_MEASUREMENT,
+            node.node_id,
+            escape_tag_value(tenant),
+            fields.join(","),
+            timestamp_ns
+        )
+    }
+}
+
+/// Escape the characters with special meaning in a line protocol tag value
+fn escape_tag_value(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        if matches!(c, ',' | '=' | ' ') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
 }
 
 /// Node health status
@@ -377,13 +955,121 @@ pub struct MetricsReport {
 pub struct NodeHealth {
     pub status: HealthStatus,
     pub score: f64, // 0-100
+    /// Replication lag the status accounts for
+    pub lag: ReplicationLag,
     pub last_check: Instant,
 }
 
-/// Health status levels
-#[derive(Debug, Clone, PartialEq)]
+/// Log entries a node may trail before its health is downgraded
+///
+/// A lag above `degraded` makes the node degraded, above `unhealthy` unhealthy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LagThresholds {
+    pub degraded: u64,
+    pub unhealthy: u64,
+}
+
+impl Default for LagThresholds {
+    fn default() -> Self {
+        Self {
+            degraded: 1000,
+            unhealthy: 10000,
+        }
+    }
+}
+
+impl LagThresholds {
+    /// Health status of a node trailing by `lag` log entries
+    pub fn classify(&self, lag: u64) -> HealthStatus {
+        if lag > self.unhealthy {
+            HealthStatus::Unhealthy
+        } else if lag > self.degraded {
+            HealthStatus::Degraded
+        } else {
+            HealthStatus::Healthy
+        }
+    }
+}
+
+/// How far a node trails, in log entries
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct ReplicationLag {
+    /// Entries in the node's log not yet applied to the state machine
+    pub apply_lag: u64,
+    /// Entries the slowest voter trails the leader's commit index; only
+    /// measured on the leader, 0 elsewhere
+    pub commit_lag: u64,
+}
+
+impl ReplicationLag {
+    /// The larger of the two lags
+    pub fn max(&self) -> u64 {
+        self.apply_lag.max(self.commit_lag)
+    }
+}
+
+/// Health status levels, ordered from best to worst
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
 pub enum HealthStatus {
     Healthy,
     Degraded,
     Unhealthy,
-}
\ No newline at end of file
+}
+
+/// Health of a single component with a human-readable explanation
+#[derive(Debug, Clone, Serialize)]
+pub struct ComponentHealth {
+    pub status: HealthStatus,
+    pub detail: String,
+}
+
+impl ComponentHealth {
+    pub fn new(status: HealthStatus, detail: impl Into<String>) -> Self {
+        Self {
+            status,
+            detail: detail.into(),
+        }
+    }
+}
+
+/// Node health broken down by component
+///
+/// The overall status is the worst status of any component.
+#[derive(Debug, Clone, Serialize)]
+pub struct HealthReport {
+    pub status: HealthStatus,
+    /// Raft instance and state machine task
+    pub raft: ComponentHealth,
+    /// RocksDB storage backend
+    pub storage: ComponentHealth,
+    /// Whether the cluster has an elected leader
+    pub leader: ComponentHealth,
+    /// Replication lag of the node, when Raft is running
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lag: Option<ReplicationLag>,
+}
+
+impl HealthReport {
+    /// Combine component health into a report
+    pub fn new(raft: ComponentHealth, storage: ComponentHealth, leader: ComponentHealth) -> Self {
+        let status = raft.status.max(storage.status).max(leader.status);
+        Self {
+            status,
+            raft,
+            storage,
+            leader,
+            lag: None,
+        }
+    }
+
+    /// Attach the replication lag the Raft component health accounts for
+    pub fn with_lag(mut self, lag: ReplicationLag) -> Self {
+        self.lag = Some(lag);
+        self
+    }
+}
+
+#[cfg(test)]
+#[path = "metrics_tests.rs"]
+mod tests;
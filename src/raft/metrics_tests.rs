@@ -0,0 +1,261 @@
+use super::*;
+use axum::extract::{Query, State};
+use axum::http::{HeaderMap, StatusCode};
+use axum::routing::post;
+use axum::Router;
+use std::sync::Mutex;
+use tokio::net::TcpListener;
+
+/// A write received by the mock InfluxDB server
+#[derive(Debug, Clone)]
+struct Write {
+    query: HashMap<String, String>,
+    authorization: Option<String>,
+    body: String,
+}
+
+#[derive(Default)]
+struct MockInflux {
+    status: Option<StatusCode>,
+    writes: Mutex<Vec<Write>>,
+}
+
+async fn write(
+    State(influx): State<Arc<MockInflux>>,
+    Query(query): Query<HashMap<String, String>>,
+    headers: HeaderMap,
+    body: String,
+) -> StatusCode {
+    let authorization = headers
+        .get(AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string);
+    influx.writes.lock().unwrap().push(Write {
+        query,
+        authorization,
+        body,
+    });
+    influx.status.unwrap_or(StatusCode::NO_CONTENT)
+}
+
+async fn serve(influx: Arc<MockInflux>) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let endpoint = format!("http://{}", listener.local_addr().unwrap());
+    let app = Router::new()
+        .route("/api/v2/write", post(write))
+        .with_state(influx);
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    endpoint
+}
+
+fn unix_nanos() -> u128 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_nanos()
+}
+
+#[tokio::test]
+async fn test_line_protocol_format() {
+    let collector = RaftMetricsCollector::new(3);
+    collector
+        .update_node_metrics(7, 42, 40, Some(3), true)
+        .await;
+    collector.record_vote_received().await;
+    collector.record_pre_vote(false).await;
+
+    let line = collector
+        .get_metrics_report()
+        .await
+        .to_line_protocol("acme corp,eu", 1_700_000_000_123_456_789);
+
+    let (series, rest) = line.split_once(' ').unwrap();
+    // Tag values escape spaces, commas and equals signs
+    assert_eq!(series, r"raft_node,node_id=3,tenant=acme\ corp\,eu");
+
+    let (fields, timestamp) = rest.rsplit_once(' ').unwrap();
+    assert_eq!(timestamp, "1700000000123456789");
+    let fields: HashMap<&str, &str> = fields
+        .split(',')
+        .map(|field| field.split_once('=').unwrap())
+        .collect();
+    assert_eq!(fields["current_term"], "7i");
+    assert_eq!(fields["last_log_index"], "42i");
+    assert_eq!(fields["last_applied"], "40i");
+    assert_eq!(fields["leader_id"], "3i");
+    assert_eq!(fields["leadership_changes"], "1i");
+    assert_eq!(fields["votes_received"], "1i");
+    assert_eq!(fields["pre_vote_denied_count"], "1i");
+    assert_eq!(fields["election_timeouts"], "0i");
+    assert_eq!(fields["is_leader"], "true");
+    assert!(fields.contains_key("uptime_secs"));
+}
+
+#[tokio::test]
+async fn test_line_protocol_without_leader() {
+    let line = RaftMetricsCollector::new(1)
+        .get_metrics_report()
+        .await
+        .to_line_protocol("default", 1);
+    assert!(!line.contains("leader_id="), "{}", line);
+    assert!(line.contains("is_leader=false"), "{}", line);
+}
+
+#[tokio::test]
+async fn test_flush_to_influx() {
+    let influx = Arc::new(MockInflux::default());
+    let endpoint = serve(influx.clone()).await;
+    let collector = RaftMetricsCollector::new(2).with_tenant("conflux-cluster");
+    collector.update_node_metrics(5, 10, 10, Some(1), false).await;
+
+    let before = unix_nanos();
+    collector
+        .flush_to_influx(&format!("{}/", endpoint), Some("secret"), "metrics", "acme")
+        .await
+        .unwrap();
+    let after = unix_nanos();
+
+    let writes = influx.writes.lock().unwrap().clone();
+    assert_eq!(writes.len(), 1);
+    let write = &writes[0];
+    assert_eq!(write.query["org"], "acme");
+    assert_eq!(write.query["bucket"], "metrics");
+    assert_eq!(write.query["precision"], "ns");
+    assert_eq!(write.authorization.as_deref(), Some("Token secret"));
+
+    assert!(write
+        .body
+        .starts_with("raft_node,node_id=2,tenant=conflux-cluster current_term=5i,"));
+    // The timestamp is in nanoseconds, matching the requested precision
+    let timestamp: u128 = write.body.rsplit(' ').next().unwrap().parse().unwrap();
+    assert!((before..=after).contains(&timestamp), "{}", write.body);
+}
+
+#[tokio::test]
+async fn test_flush_to_influx_rejected() {
+    let influx = Arc::new(MockInflux {
+        status: Some(StatusCode::UNAUTHORIZED),
+        ..Default::default()
+    });
+    let endpoint = serve(influx.clone()).await;
+
+    let error = RaftMetricsCollector::new(1)
+        .flush_to_influx(&endpoint, None, "metrics", "acme")
+        .await
+        .unwrap_err();
+    assert!(error.to_string().contains("401"), "{}", error);
+    assert_eq!(influx.writes.lock().unwrap()[0].authorization, None);
+}
+
+#[tokio::test]
+async fn test_spawn_influx_flush() {
+    let influx = Arc::new(MockInflux::default());
+    let mut config = crate::config::AppConfig::default().observability;
+    assert!(Arc::new(RaftMetricsCollector::new(1))
+        .spawn_influx_flush(&config)
+        .is_none());
+
+    config.influx_endpoint = Some(serve(influx.clone()).await);
+    config.collection_interval_secs = 1;
+    let handle = Arc::new(RaftMetricsCollector::new(1))
+        .spawn_influx_flush(&config)
+        .unwrap();
+
+    // The first write happens immediately, the next after one interval
+    for _ in 0..50 {
+        if influx.writes.lock().unwrap().len() >= 2 {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    handle.abort();
+    assert!(influx.writes.lock().unwrap().len() >= 2);
+}
+
+#[tokio::test]
+async fn test_aggregated_cluster_metrics_totals() {
+    let summary = |node_id, last_applied, total_requests, failed_requests| NodeMetricsSummary {
+        node_id,
+        current_term: 2,
+        last_log_index: 10,
+        last_applied,
+        apply_lag: 10 - last_applied,
+        leader_id: Some(1),
+        is_leader: node_id == 1,
+        total_requests,
+        failed_requests,
+        request_rate: total_requests as f64,
+        error_rate: 0.0,
+        uptime_secs: 1,
+    };
+
+    let metrics = AggregatedClusterMetrics::new(
+        1,
+        vec![
+            ClusterNodeMetrics::unknown(3, "connection refused"),
+            ClusterNodeMetrics::reported(summary(2, 7, 4, 3)),
+            ClusterNodeMetrics::reported(summary(1, 10, 6, 1)),
+        ],
+    );
+
+    let node_ids: Vec<NodeId> = metrics.nodes.iter().map(|node| node.node_id).collect();
+    assert_eq!(node_ids, vec![1, 2, 3]);
+    assert_eq!(
+        metrics.totals,
+        ClusterMetricsTotals {
+            node_count: 3,
+            reporting_nodes: 2,
+            unknown_nodes: 1,
+            total_requests: 10,
+            failed_requests: 4,
+            request_rate: 10.0,
+            error_rate: 0.4,
+            max_apply_lag: 3,
+        }
+    );
+
+    // Unknown nodes serialize with their state and error
+    let json = serde_json::to_value(&metrics.nodes[2]).unwrap();
+    assert_eq!(json["state"], "unknown");
+    assert_eq!(json["error"], "connection refused");
+}
+
+#[tokio::test]
+async fn test_replication_lag_downgrades_health() {
+    let collector = RaftMetricsCollector::new(1).with_lag_thresholds(LagThresholds {
+        degraded: 10,
+        unhealthy: 100,
+    });
+
+    collector.update_node_metrics(1, 50, 45, Some(1), true).await;
+    let health = collector.get_node_health().await;
+    assert_eq!(health.status, HealthStatus::Healthy);
+    assert_eq!(health.lag, ReplicationLag { apply_lag: 5, commit_lag: 0 });
+
+    // Unapplied entries above the degraded threshold
+    collector.update_node_metrics(1, 80, 45, Some(1), true).await;
+    let health = collector.get_node_health().await;
+    assert_eq!(health.status, HealthStatus::Degraded);
+    assert_eq!(health.lag.apply_lag, 35);
+    assert_eq!(health.score, 100.0);
+
+    // A voter far behind the leader's commit index
+    collector.update_commit_lag(500).await;
+    let health = collector.get_node_health().await;
+    assert_eq!(health.status, HealthStatus::Unhealthy);
+    assert_eq!(health.lag.commit_lag, 500);
+
+    // Commit lag is only measured while leading
+    collector.update_node_metrics(2, 80, 80, Some(2), false).await;
+    assert_eq!(collector.get_node_health().await.lag, ReplicationLag::default());
+}
+
+#[test]
+fn test_lag_thresholds_classify() {
+    let thresholds = LagThresholds::default();
+    assert_eq!(thresholds.classify(0), HealthStatus::Healthy);
+    assert_eq!(thresholds.classify(thresholds.degraded), HealthStatus::Healthy);
+    assert_eq!(thresholds.classify(thresholds.degraded + 1), HealthStatus::Degraded);
+    assert_eq!(thresholds.classify(thresholds.unhealthy), HealthStatus::Degraded);
+    assert_eq!(thresholds.classify(thresholds.unhealthy + 1), HealthStatus::Unhealthy);
+}
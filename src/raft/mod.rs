@@ -1,8 +1,10 @@
 pub mod auth;
 pub mod client;
+pub mod discovery;
 pub mod log_storage;
 pub mod metrics;
 pub mod network;
+pub mod network_server;
 pub mod node;
 pub mod state_machine;
 pub mod store;
@@ -28,9 +30,17 @@ pub mod error_handling_tests;
 pub use auth::{RaftAuthzService, AuthorizedRaftOperation};
 pub use client::{RaftClient, ClientWriteRequest, ClientReadRequest, ClientReadResponse, ClusterStatus};
 pub use log_storage::{ConfluxLogStorage, ConfluxLogReader};
-pub use metrics::{RaftMetricsCollector, NodeMetrics, ClusterMetrics, PerformanceMetrics, MetricsReport, NodeHealth, HealthStatus, NodeStatus};
-pub use network::{ConfluxNetwork, ConfluxNetworkFactory, NetworkConfig};
-pub use node::{create_node_config, create_node_config_with_timeouts, create_node_config_with_limits, NodeConfig, RaftNode, ResourceLimits, ResourceStats};
-pub use state_machine::{ConfluxStateMachine, ConfluxStateMachineWrapper, ConfluxSnapshotBuilder};
+pub use metrics::{RaftMetricsCollector, NodeMetrics, ClusterMetrics, PerformanceMetrics, LatencyHistogram, CLIENT_LATENCY_BUCKETS_MS, MetricsReport, NodeHealth, LagThresholds, ReplicationLag, HealthStatus, NodeStatus, ComponentHealth, HealthReport, NodeMetricsSummary, NodeMetricsState, ClusterNodeMetrics, ClusterMetricsTotals, AggregatedClusterMetrics};
+pub use discovery::{PeerResolver, SystemResolver};
+pub use network::{
+    AddressBook, ConfluxNetwork, ConfluxNetworkFactory, NetworkConfig, PeerConnectionState,
+    PeerDiscovery, PreVoteRequest, PreVoteResponse, PEER_HEARTBEAT_INTERVAL,
+    RECONNECT_INITIAL_BACKOFF, RECONNECT_MAX_BACKOFF,
+};
+pub use node::{create_node_config, create_node_config_with_timeouts, create_node_config_with_limits, ClusterEvent, ClusterEventKind, NodeConfig, NodeConfigBuilder, RaftNode, ResourceLimits, ResourceStats, SnapshotStreamConfig};
+pub use state_machine::{
+    AppliedStateGuard, ConfluxSnapshotBuilder, ConfluxStateMachine, ConfluxStateMachineWrapper,
+    StateSummary,
+};
 pub use store::Store;
 pub use validation::{RaftInputValidator, ValidationConfig};
@@ -1,3 +1,6 @@
+use crate::raft::discovery::{resolve_peers, PeerResolver};
+use crate::raft::metrics::{NodeMetricsSummary, NodeStatus, RaftMetricsCollector};
+use crate::raft::store::read_snapshot_chunk;
 use crate::raft::types::*;
 use openraft::{
     error::{
@@ -10,15 +13,98 @@ use openraft::{
         InstallSnapshotResponse, SnapshotResponse, VoteRequest, VoteResponse,
     },
     storage::Snapshot,
-    BasicNode, Vote,
+    StorageError, StorageIOError, Vote,
 };
+use reqwest::header::{HeaderMap, HeaderValue, AUTHORIZATION};
 use reqwest::Client;
 
-use std::collections::HashMap;
-use std::sync::Arc;
-use std::time::Duration;
-use tokio::sync::RwLock;
-use tracing::{debug, error};
+use std::collections::{BTreeSet, HashMap};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError, Weak};
+use std::time::{Duration, Instant};
+use tokio::io::AsyncSeekExt;
+use tokio::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+use tokio::task::{JoinHandle, JoinSet};
+use tracing::{debug, error, info, warn};
+
+/// How often [`ConfluxNetworkFactory::spawn_peer_heartbeat`] pings each peer
+pub const PEER_HEARTBEAT_INTERVAL: Duration = Duration::from_secs(10);
+
+/// First delay before a peer whose RPC failed is pinged again
+pub const RECONNECT_INITIAL_BACKOFF: Duration = Duration::from_millis(100);
+
+/// Longest delay between two reconnection attempts to an unreachable peer
+pub const RECONNECT_MAX_BACKOFF: Duration = Duration::from_secs(5);
+
+/// Snapshot chunk size used when the replication options do not set one
+const DEFAULT_SNAPSHOT_CHUNK_BYTES: usize = 4 * 1024 * 1024;
+
+/// How a node finds the addresses of its peers
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PeerDiscovery {
+    /// Fixed node ID to address mapping
+    Static(HashMap<NodeId, String>),
+    /// Peers resolved from DNS every [`DNS_DISCOVERY_INTERVAL`]
+    ///
+    /// Without `node_id_suffix`, every address `hostname` resolves to is a
+    /// peer and node IDs follow the sorted record order (first record =
+    /// node 1). With a suffix, the per-node names `{hostname}-{id}.{suffix}`
+    /// are resolved instead, so `node-2.conflux.svc` is node 2.
+    ///
+    /// [`DNS_DISCOVERY_INTERVAL`]: crate::raft::discovery::DNS_DISCOVERY_INTERVAL
+    Dns {
+        hostname: String,
+        port: u16,
+        node_id_suffix: Option<String>,
+    },
+}
+
+impl Default for PeerDiscovery {
+    fn default() -> Self {
+        Self::Static(HashMap::new())
+    }
+}
+
+/// Node ID to address mapping shared by a node's network clients
+///
+/// Clients look the address up for every RPC, so an update takes effect on
+/// the next RPC to that peer.
+#[derive(Debug, Clone, Default)]
+pub struct AddressBook {
+    addresses: Arc<RwLock<HashMap<NodeId, String>>>,
+}
+
+impl AddressBook {
+    pub fn new(addresses: HashMap<NodeId, String>) -> Self {
+        Self {
+            addresses: Arc::new(RwLock::new(addresses)),
+        }
+    }
+
+    /// Address of `node_id`
+    pub async fn get(&self, node_id: NodeId) -> Option<String> {
+        self.addresses.read().await.get(&node_id).cloned()
+    }
+
+    /// Set the address of `node_id`, returning its previous address
+    pub async fn insert(&self, node_id: NodeId, address: String) -> Option<String> {
+        self.addresses.write().await.insert(node_id, address)
+    }
+
+    /// Lock the mapping for reading
+    pub async fn read(&self) -> RwLockReadGuard<'_, HashMap<NodeId, String>> {
+        self.addresses.read().await
+    }
+
+    /// Lock the mapping for writing
+    pub async fn write(&self) -> RwLockWriteGuard<'_, HashMap<NodeId, String>> {
+        self.addresses.write().await
+    }
+
+    /// Copy of the mapping, for inclusion in snapshots
+    pub async fn serialize(&self) -> HashMap<NodeId, String> {
+        self.addresses.read().await.clone()
+    }
+}
 
 /// Network configuration for Raft communication
 #[derive(Debug, Clone)]
@@ -26,14 +112,30 @@ pub struct NetworkConfig {
     /// HTTP client timeout in seconds
     pub timeout_secs: u64,
     /// Node ID to address mapping
-    pub node_addresses: Arc<RwLock<HashMap<NodeId, String>>>,
+    pub node_addresses: AddressBook,
+    /// Where the node ID to address mapping comes from
+    pub peer_discovery: PeerDiscovery,
+    /// Idle connections kept open to each peer
+    pub connections_per_peer: usize,
+    /// Peers that have not answered a heartbeat for this long are marked
+    /// [`NodeStatus::Dead`]
+    pub peer_dead_timeout: Duration,
+    /// Bearer token sent with every request to a peer
+    ///
+    /// Peers only accept election triggers, priority changes, pre-votes and
+    /// metrics pulls from an identity holding the cluster admin permission.
+    pub auth_token: Option<String>,
 }
 
 impl Default for NetworkConfig {
     fn default() -> Self {
         Self {
             timeout_secs: 10,
-            node_addresses: Arc::new(RwLock::new(HashMap::new())),
+            node_addresses: AddressBook::default(),
+            peer_discovery: PeerDiscovery::default(),
+            connections_per_peer: 4,
+            peer_dead_timeout: Duration::from_secs(30),
+            auth_token: None,
         }
     }
 }
@@ -42,19 +144,60 @@ impl NetworkConfig {
     /// Create a new network config with node addresses
     pub fn new(node_addresses: HashMap<NodeId, String>) -> Self {
         Self {
-            timeout_secs: 10,
-            node_addresses: Arc::new(RwLock::new(node_addresses)),
+            node_addresses: AddressBook::new(node_addresses.clone()),
+            peer_discovery: PeerDiscovery::Static(node_addresses),
+            ..Self::default()
+        }
+    }
+
+    /// Use `peer_discovery` to find peers
+    ///
+    /// Static addresses become the node address table right away; DNS peers
+    /// are added to it as they are resolved.
+    pub fn with_peer_discovery(mut self, peer_discovery: PeerDiscovery) -> Self {
+        if let PeerDiscovery::Static(addresses) = &peer_discovery {
+            self.node_addresses = AddressBook::new(addresses.clone());
         }
+        self.peer_discovery = peer_discovery;
+        self
+    }
+
+    /// Authenticate requests to peers with `token`
+    pub fn with_auth_token(mut self, token: impl Into<String>) -> Self {
+        self.auth_token = Some(token.into());
+        self
+    }
+
+    /// Headers sent with every request to a peer
+    fn default_headers(&self) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        if let Some(token) = &self.auth_token {
+            match HeaderValue::try_from(format!("Bearer {}", token)) {
+                Ok(mut value) => {
+                    value.set_sensitive(true);
+                    headers.insert(AUTHORIZATION, value);
+                }
+                Err(e) => warn!("Ignoring invalid peer auth token: {}", e),
+            }
+        }
+        headers
     }
 
     /// Add a node address
     pub async fn add_node(&self, node_id: NodeId, address: String) {
-        self.node_addresses.write().await.insert(node_id, address);
+        self.node_addresses.insert(node_id, address).await;
+    }
+
+    /// Point `node_id` at `new_address`, returning its previous address
+    ///
+    /// The next RPC to the peer goes to the new address.
+    pub async fn update_peer_address(&self, node_id: NodeId, new_address: String) -> Option<String> {
+        self.node_addresses.insert(node_id, new_address).await
     }
 
     /// Get node address
     pub async fn get_node_address(&self, node_id: NodeId) -> Option<String> {
-        self.node_addresses.read().await.get(&node_id).cloned()
+        self.node_addresses.get(node_id).await
     }
 }
 
@@ -67,6 +210,10 @@ pub struct ConfluxNetwork {
     client: Client,
     /// Target node ID
     target_node_id: NodeId,
+    /// Optional collector recording snapshot bytes sent
+    metrics: Option<Arc<RaftMetricsCollector>>,
+    /// Pool the client was taken from, counting requests per peer
+    pool: Option<Arc<PeerPool>>,
 }
 
 impl ConfluxNetwork {
@@ -74,6 +221,7 @@ impl ConfluxNetwork {
     pub fn new(config: NetworkConfig, target_node_id: NodeId) -> Self {
         let client = Client::builder()
             .timeout(Duration::from_secs(config.timeout_secs))
+            .default_headers(config.default_headers())
             .build()
             .expect("Failed to create HTTP client");
 
@@ -81,6 +229,61 @@ impl ConfluxNetwork {
             config,
             client,
             target_node_id,
+            metrics: None,
+            pool: None,
+        }
+    }
+
+    /// HTTP client of the target node
+    ///
+    /// Taken from the pool on every request so that a client replaced after
+    /// reconnecting is used by network instances created before.
+    fn http_client(&self) -> Client {
+        match &self.pool {
+            Some(pool) => pool.client(self.target_node_id),
+            None => self.client.clone(),
+        }
+    }
+
+    /// Count a Raft RPC in the pool statistics of the target node
+    ///
+    /// A failed RPC marks the target unreachable and starts reconnecting to
+    /// it in the background; a successful one marks it reachable.
+    fn record_request(&self, success: bool) {
+        let Some(pool) = &self.pool else {
+            return;
+        };
+        pool.record_request(self.target_node_id, success);
+        if success {
+            if pool.mark_reachable(self.target_node_id) {
+                info!("Peer {} is reachable", self.target_node_id);
+                self.report_peer_status(NodeStatus::Active, true);
+            }
+        } else if let Some(status) = pool.mark_unreachable(self.target_node_id) {
+            warn!(
+                "RPC to peer {} failed, reconnecting in the background",
+                self.target_node_id
+            );
+            self.report_peer_status(status, false);
+            spawn_reconnect(
+                Arc::downgrade(pool),
+                self.config.clone(),
+                self.target_node_id,
+                self.metrics.clone(),
+            );
+        }
+    }
+
+    /// Publish the target's connectivity to the metrics collector
+    fn report_peer_status(&self, status: NodeStatus, seen: bool) {
+        if let Some(metrics) = self.metrics.clone() {
+            let target = self.target_node_id;
+            tokio::spawn(async move {
+                if seen {
+                    metrics.record_peer_seen(target).await;
+                }
+                metrics.set_peer_status(target, status).await;
+            });
         }
     }
 
@@ -108,7 +311,7 @@ impl ConfluxNetwork {
         let mut delay = Duration::from_millis(100);
 
         for attempt in 1..=max_attempts {
-            match self.client.post(url).json(request).send().await {
+            match self.http_client().post(url).json(request).send().await {
                 Ok(response) => match response.json::<R>().await {
                     Ok(data) => return Ok(data),
                     Err(e) => {
@@ -138,7 +341,7 @@ impl ConfluxNetwork {
     pub async fn is_reachable(&self) -> bool {
         if let Ok(address) = self.get_target_address().await {
             let url = format!("http://{}/health", address);
-            match self.client.get(&url).send().await {
+            match self.http_client().get(&url).send().await {
                 Ok(response) => response.status().is_success(),
                 Err(_) => false,
             }
@@ -147,6 +350,160 @@ impl ConfluxNetwork {
         }
     }
 
+    /// Ask the target node to start an election immediately
+    ///
+    /// Used for leadership transfer: the target campaigns with a higher term,
+    /// which makes the current leader step down once it sees the new vote.
+    pub async fn trigger_elect(&self) -> Result<(), NetworkError> {
+        debug!("Triggering election on node {}", self.target_node_id);
+
+        let address = self.get_target_address().await?;
+        let url = format!("http://{}/_cluster/trigger-elect", address);
+
+        let response = self.http_client().post(&url).send().await.map_err(|e| {
+            error!("Failed to trigger election on node {}: {}", self.target_node_id, e);
+            NetworkError::new(&e)
+        })?;
+
+        if response.status().is_success() {
+            Ok(())
+        } else {
+            let err = std::io::Error::other(format!(
+                "Node {} rejected election trigger with status {}",
+                self.target_node_id,
+                response.status()
+            ));
+            Err(NetworkError::new(&err))
+        }
+    }
+
+    /// Ask the target node, which must be the leader, to change the voter set
+    ///
+    /// Used when the local node has handed off leadership but still needs a
+    /// membership change committed, e.g. while decommissioning itself.
+    pub async fn change_membership(&self, members: &BTreeSet<NodeId>) -> Result<(), NetworkError> {
+        debug!(
+            "Requesting membership change {:?} on node {}",
+            members, self.target_node_id
+        );
+
+        let address = self.get_target_address().await?;
+        let url = format!("http://{}/raft/change_membership", address);
+
+        let response = self.http_client().post(&url).json(members).send().await.map_err(|e| {
+            error!(
+                "Failed to request membership change on node {}: {}",
+                self.target_node_id, e
+            );
+            NetworkError::new(&e)
+        })?;
+
+        if response.status().is_success() {
+            Ok(())
+        } else {
+            let err = std::io::Error::other(format!(
+                "Node {} rejected membership change with status {}",
+                self.target_node_id,
+                response.status()
+            ));
+            Err(NetworkError::new(&err))
+        }
+    }
+
+    /// Ask the target node, which must be the leader, to set a node's election priority
+    pub async fn set_node_priority(&self, node_id: NodeId, priority: u8) -> Result<(), NetworkError> {
+        debug!(
+            "Requesting election priority {} for node {} on node {}",
+            priority, node_id, self.target_node_id
+        );
+
+        let address = self.get_target_address().await?;
+        let url = format!("http://{}/_cluster/node-priority", address);
+
+        let response = self
+            .http_client()
+            .post(&url)
+            .json(&NodePriorityUpdate { node_id, priority })
+            .send()
+            .await
+            .map_err(|e| {
+                error!(
+                    "Failed to request priority change on node {}: {}",
+                    self.target_node_id, e
+                );
+                NetworkError::new(&e)
+            })?;
+
+        if response.status().is_success() {
+            Ok(())
+        } else {
+            let err = std::io::Error::other(format!(
+                "Node {} rejected priority change with status {}",
+                self.target_node_id,
+                response.status()
+            ));
+            Err(NetworkError::new(&err))
+        }
+    }
+
+    /// Ask the target node whether it would vote for the local node
+    ///
+    /// Sent before a real election when pre-vote is enabled. The target's
+    /// term and vote are left untouched whatever the answer.
+    pub async fn pre_vote(&self, request: &PreVoteRequest) -> Result<PreVoteResponse, NetworkError> {
+        debug!(
+            "Sending PreVote from node {} to node {}",
+            request.candidate_id, self.target_node_id
+        );
+
+        let address = self.get_target_address().await?;
+        let url = format!("http://{}/_cluster/pre-vote", address);
+
+        let response = self.http_client().post(&url).json(request).send().await.map_err(|e| {
+            error!("Failed to send PreVote to node {}: {}", self.target_node_id, e);
+            NetworkError::new(&e)
+        })?;
+
+        if !response.status().is_success() {
+            let err = std::io::Error::other(format!(
+                "Node {} rejected PreVote with status {}",
+                self.target_node_id,
+                response.status()
+            ));
+            return Err(NetworkError::new(&err));
+        }
+        response.json::<PreVoteResponse>().await.map_err(|e| {
+            error!("Failed to parse PreVote response: {}", e);
+            NetworkError::new(&e)
+        })
+    }
+
+    /// Pull the target node's metrics summary for cluster-wide aggregation
+    pub async fn pull_metrics(&self) -> Result<NodeMetricsSummary, NetworkError> {
+        debug!("Pulling metrics from node {}", self.target_node_id);
+
+        let address = self.get_target_address().await?;
+        let url = format!("http://{}/_cluster/metrics/local", address);
+
+        let response = self.http_client().get(&url).send().await.map_err(|e| {
+            debug!("Failed to pull metrics from node {}: {}", self.target_node_id, e);
+            NetworkError::new(&e)
+        })?;
+
+        if !response.status().is_success() {
+            let err = std::io::Error::other(format!(
+                "Node {} rejected metrics pull with status {}",
+                self.target_node_id,
+                response.status()
+            ));
+            return Err(NetworkError::new(&err));
+        }
+        response.json::<NodeMetricsSummary>().await.map_err(|e| {
+            error!("Failed to parse metrics of node {}: {}", self.target_node_id, e);
+            NetworkError::new(&e)
+        })
+    }
+
     /// Get connection statistics
     pub async fn get_connection_stats(&self) -> ConnectionStats {
         ConnectionStats {
@@ -157,6 +514,33 @@ impl ConfluxNetwork {
     }
 }
 
+/// Body of a request to change a node's election priority
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct NodePriorityUpdate {
+    pub node_id: NodeId,
+    pub priority: u8,
+}
+
+/// Body of a pre-vote request sent before starting an election
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct PreVoteRequest {
+    /// Node that wants to start an election
+    pub candidate_id: NodeId,
+    /// Index of the candidate's last log entry
+    pub last_log_index: u64,
+    /// Term of the candidate's last log entry
+    pub last_log_term: u64,
+}
+
+/// Answer to a pre-vote request
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct PreVoteResponse {
+    /// Current term of the responding node
+    pub term: u64,
+    /// Whether the responding node would vote for the candidate
+    pub granted: bool,
+}
+
 /// Connection statistics
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct ConnectionStats {
@@ -177,17 +561,19 @@ impl RaftNetwork<TypeConfig> for ConfluxNetwork {
 
         let url = format!("http://{}/raft/append_entries", address);
 
-        match self.client.post(&url).json(&rpc).send().await {
+        match self.http_client().post(&url).json(&rpc).send().await {
             Ok(response) => match response.json::<AppendEntriesResponse<NodeId>>().await {
                 Ok(resp) => {
                     debug!(
                         "AppendEntries response received from node {}",
                         self.target_node_id
                     );
+                    self.record_request(true);
                     Ok(resp)
                 }
                 Err(e) => {
                     error!("Failed to parse AppendEntries response: {}", e);
+                    self.record_request(false);
                     Err(RPCError::Network(NetworkError::new(&e)))
                 }
             },
@@ -196,6 +582,7 @@ impl RaftNetwork<TypeConfig> for ConfluxNetwork {
                     "Failed to send AppendEntries to node {}: {}",
                     self.target_node_id, e
                 );
+                self.record_request(false);
                 Err(RPCError::Network(NetworkError::new(&e)))
             }
         }
@@ -212,19 +599,22 @@ impl RaftNetwork<TypeConfig> for ConfluxNetwork {
 
         let url = format!("http://{}/raft/vote", address);
 
-        match self.client.post(&url).json(&rpc).send().await {
+        match self.http_client().post(&url).json(&rpc).send().await {
             Ok(response) => match response.json::<VoteResponse<NodeId>>().await {
                 Ok(resp) => {
                     debug!("Vote response received from node {}", self.target_node_id);
+                    self.record_request(true);
                     Ok(resp)
                 }
                 Err(e) => {
                     error!("Failed to parse Vote response: {}", e);
+                    self.record_request(false);
                     Err(RPCError::Network(NetworkError::new(&e)))
                 }
             },
             Err(e) => {
                 error!("Failed to send Vote to node {}: {}", self.target_node_id, e);
+                self.record_request(false);
                 Err(RPCError::Network(NetworkError::new(&e)))
             }
         }
@@ -244,19 +634,22 @@ impl RaftNetwork<TypeConfig> for ConfluxNetwork {
         let url = format!("http://{}/raft/install_snapshot", address);
 
         // Send the snapshot installation request
-        match self.client.post(&url).json(&rpc).send().await {
+        match self.http_client().post(&url).json(&rpc).send().await {
             Ok(response) => match response.json::<InstallSnapshotResponse<NodeId>>().await {
                 Ok(resp) => {
                     debug!("InstallSnapshot response received from node {}", self.target_node_id);
+                    self.record_request(true);
                     Ok(resp)
                 }
                 Err(e) => {
                     error!("Failed to parse InstallSnapshot response: {}", e);
+                    self.record_request(false);
                     Err(RPCError::Network(NetworkError::new(&e)))
                 }
             },
             Err(e) => {
                 error!("Failed to send InstallSnapshot to node {}: {}", self.target_node_id, e);
+                self.record_request(false);
                 Err(RPCError::Network(NetworkError::new(&e)))
             }
         }
@@ -267,7 +660,7 @@ impl RaftNetwork<TypeConfig> for ConfluxNetwork {
         vote: Vote<NodeId>,
         snapshot: Snapshot<TypeConfig>,
         cancel: impl std::future::Future<Output = ReplicationClosed> + Send + 'static,
-        _option: RPCOption,
+        option: RPCOption,
     ) -> Result<SnapshotResponse<NodeId>, StreamingError<TypeConfig, Fatal<NodeId>>> {
         debug!("Sending full snapshot to node {}", self.target_node_id);
 
@@ -282,20 +675,54 @@ impl RaftNetwork<TypeConfig> for ConfluxNetwork {
                 StreamingError::Network(NetworkError::new(&err))
             })?;
 
-        let url = format!("{}/raft/install_snapshot", target_address);
+        let url = format!("http://{}/raft/install_snapshot", target_address);
+        drop(node_addresses);
+
+        // Send the snapshot in chunks read from its file so each request stays bounded
+        let meta = snapshot.meta.clone();
+        let mut data = snapshot.snapshot;
+        let chunk_size = option
+            .snapshot_chunk_size()
+            .unwrap_or(DEFAULT_SNAPSHOT_CHUNK_BYTES)
+            .max(1);
+        let read_error = |e: std::io::Error| -> StreamingError<TypeConfig, Fatal<NodeId>> {
+            StorageError::from(StorageIOError::read_snapshot(Some(meta.signature()), &e)).into()
+        };
 
-        // Create install snapshot request
-        let request = InstallSnapshotRequest {
-            vote,
-            meta: snapshot.meta.clone(),
-            offset: 0,
-            data: snapshot.snapshot.into_inner(),
-            done: true,
+        let send_chunks = async {
+            let size = data.metadata().await.map_err(read_error)?.len();
+            data.rewind().await.map_err(read_error)?;
+            let mut offset = 0;
+            loop {
+                let chunk = read_snapshot_chunk(&mut *data, chunk_size)
+                    .await
+                    .map_err(read_error)?;
+                let sent = chunk.len() as u64;
+                let request = InstallSnapshotRequest {
+                    vote,
+                    meta: meta.clone(),
+                    offset,
+                    data: chunk,
+                    done: offset + sent >= size,
+                };
+                let response = self
+                    .send_snapshot_with_retry(&url, &request)
+                    .await
+                    .map_err(StreamingError::Network)?;
+                if let Some(metrics) = &self.metrics {
+                    metrics.record_snapshot_bytes_sent(sent).await;
+                }
+                // A higher vote means the target no longer accepts this leader
+                if request.done || response.vote > vote {
+                    return Ok(response);
+                }
+                offset += sent;
+            }
         };
 
         // Use tokio::select to handle cancellation
         tokio::select! {
-            result = self.send_snapshot_with_retry(&url, &request) => {
+            result = send_chunks => {
                 match result {
                     Ok(response) => {
                         debug!("Successfully sent snapshot to node {}", self.target_node_id);
@@ -305,7 +732,7 @@ impl RaftNetwork<TypeConfig> for ConfluxNetwork {
                     }
                     Err(e) => {
                         error!("Failed to send snapshot to node {}: {}", self.target_node_id, e);
-                        Err(StreamingError::Network(e))
+                        Err(e)
                     }
                 }
             }
@@ -330,7 +757,7 @@ impl ConfluxNetwork {
         for attempt in 1..=max_attempts {
             debug!("Sending snapshot (attempt {}/{})", attempt, max_attempts);
 
-            match self.client
+            match self.http_client()
                 .post(url)
                 .timeout(Duration::from_secs(60)) // Longer timeout for snapshots
                 .json(request)
@@ -381,23 +808,460 @@ impl ConfluxNetwork {
     }
 }
 
+/// Connection pool state of one peer
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct PeerConnectionState {
+    pub node_id: NodeId,
+    pub address: Option<String>,
+    /// Idle connections the pool keeps open to the peer
+    pub max_idle_connections: usize,
+    /// Raft RPCs and heartbeats sent to the peer
+    pub requests_sent: u64,
+    /// Requests that got no successful response
+    pub requests_failed: u64,
+    pub status: NodeStatus,
+    /// Milliseconds since the peer last answered a heartbeat or RPC
+    pub last_seen_ms_ago: Option<u64>,
+    /// Failed requests since the peer last answered
+    pub consecutive_failures: u32,
+    /// Whether the peer is being reconnected to in the background
+    pub reconnecting: bool,
+}
+
+/// Pooled client and health of one peer
+#[derive(Debug)]
+struct PeerConnection {
+    client: Client,
+    requests_sent: u64,
+    requests_failed: u64,
+    /// When the pool first connected to the peer, the reference for
+    /// peers that never answered
+    tracked_since: Instant,
+    last_seen: Option<Instant>,
+    status: NodeStatus,
+    /// Failed requests since the peer last answered
+    consecutive_failures: u32,
+    /// Whether a background task is trying to reconnect to the peer
+    reconnecting: bool,
+}
+
+/// One HTTP client per peer, shared by every network instance of a factory
+///
+/// `reqwest::Client` keeps idle connections alive internally, so reusing the
+/// client lets Raft RPCs to a peer share connections instead of each
+/// network instance opening its own.
+#[derive(Debug)]
+struct PeerPool {
+    timeout: Duration,
+    connections_per_peer: usize,
+    headers: HeaderMap,
+    peers: Mutex<HashMap<NodeId, PeerConnection>>,
+}
+
+impl PeerPool {
+    fn new(config: &NetworkConfig) -> Self {
+        Self {
+            timeout: Duration::from_secs(config.timeout_secs),
+            connections_per_peer: config.connections_per_peer,
+            headers: config.default_headers(),
+            peers: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn peers(&self) -> MutexGuard<'_, HashMap<NodeId, PeerConnection>> {
+        self.peers.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// A new HTTP client with the pool's timeout, idle connection limit and
+    /// auth headers
+    fn new_client(&self) -> Client {
+        Client::builder()
+            .timeout(self.timeout)
+            .pool_max_idle_per_host(self.connections_per_peer)
+            .default_headers(self.headers.clone())
+            .build()
+            .expect("Failed to create HTTP client")
+    }
+
+    /// The client of `target`, created on first use
+    fn client(&self, target: NodeId) -> Client {
+        let mut peers = self.peers();
+        peers
+            .entry(target)
+            .or_insert_with(|| PeerConnection {
+                client: self.new_client(),
+                requests_sent: 0,
+                requests_failed: 0,
+                tracked_since: Instant::now(),
+                last_seen: None,
+                status: NodeStatus::Joining,
+                consecutive_failures: 0,
+                reconnecting: false,
+            })
+            .client
+            .clone()
+    }
+
+    fn record_request(&self, target: NodeId, success: bool) {
+        if let Some(peer) = self.peers().get_mut(&target) {
+            peer.requests_sent += 1;
+            if !success {
+                peer.requests_failed += 1;
+            }
+        }
+    }
+
+    /// Mark `target` reachable after it answered a request
+    ///
+    /// Stops any reconnection in progress. Returns whether the peer was not
+    /// already considered reachable.
+    fn mark_reachable(&self, target: NodeId) -> bool {
+        let mut peers = self.peers();
+        let Some(peer) = peers.get_mut(&target) else {
+            return false;
+        };
+        peer.last_seen = Some(Instant::now());
+        peer.consecutive_failures = 0;
+        peer.reconnecting = false;
+        std::mem::replace(&mut peer.status, NodeStatus::Active) != NodeStatus::Active
+    }
+
+    /// Mark `target` unreachable after a failed request
+    ///
+    /// Returns the peer's new status if no reconnection was in progress, in
+    /// which case the caller starts one.
+    fn mark_unreachable(&self, target: NodeId) -> Option<NodeStatus> {
+        let mut peers = self.peers();
+        let peer = peers.get_mut(&target)?;
+        peer.consecutive_failures += 1;
+        if peer.status != NodeStatus::Dead {
+            peer.status = NodeStatus::Suspected;
+        }
+        if peer.reconnecting {
+            return None;
+        }
+        peer.reconnecting = true;
+        Some(peer.status.clone())
+    }
+
+    /// Whether a reconnection to `target` is still wanted
+    fn is_reconnecting(&self, target: NodeId) -> bool {
+        self.peers().get(&target).is_some_and(|peer| peer.reconnecting)
+    }
+
+    /// Give up reconnecting to `target`
+    fn stop_reconnecting(&self, target: NodeId) {
+        if let Some(peer) = self.peers().get_mut(&target) {
+            peer.reconnecting = false;
+        }
+    }
+
+    /// Replace the client of `target` with one that reached it
+    ///
+    /// Idle connections of the old client may be broken after the peer
+    /// went down, so they are dropped together with it.
+    fn reconnected(&self, target: NodeId, client: Client) {
+        if let Some(peer) = self.peers().get_mut(&target) {
+            peer.client = client;
+            peer.last_seen = Some(Instant::now());
+            peer.consecutive_failures = 0;
+            peer.reconnecting = false;
+            peer.status = NodeStatus::Active;
+        }
+    }
+
+    /// Forget the connections and health of `target` after its address changed
+    ///
+    /// Idle connections of the old client lead to the old address, and the
+    /// peer's status says nothing about the new one.
+    fn address_changed(&self, target: NodeId) {
+        if let Some(peer) = self.peers().get_mut(&target) {
+            peer.client = self.new_client();
+            peer.tracked_since = Instant::now();
+            peer.last_seen = None;
+            peer.status = NodeStatus::Joining;
+            peer.consecutive_failures = 0;
+            peer.reconnecting = false;
+        }
+    }
+
+    /// Update a peer's status after a heartbeat, returning the previous and
+    /// the new status
+    fn record_heartbeat(
+        &self,
+        target: NodeId,
+        answered: bool,
+        dead_timeout: Duration,
+    ) -> Option<(NodeStatus, NodeStatus)> {
+        let mut peers = self.peers();
+        let peer = peers.get_mut(&target)?;
+        let now = Instant::now();
+        let status = if answered {
+            peer.last_seen = Some(now);
+            peer.consecutive_failures = 0;
+            peer.reconnecting = false;
+            NodeStatus::Active
+        } else if now.duration_since(peer.last_seen.unwrap_or(peer.tracked_since)) >= dead_timeout {
+            NodeStatus::Dead
+        } else {
+            NodeStatus::Suspected
+        };
+        let previous = std::mem::replace(&mut peer.status, status.clone());
+        Some((previous, status))
+    }
+
+    fn states(&self, addresses: &HashMap<NodeId, String>) -> Vec<PeerConnectionState> {
+        let mut states: Vec<_> = self
+            .peers()
+            .iter()
+            .map(|(node_id, peer)| PeerConnectionState {
+                node_id: *node_id,
+                address: addresses.get(node_id).cloned(),
+                max_idle_connections: self.connections_per_peer,
+                requests_sent: peer.requests_sent,
+                requests_failed: peer.requests_failed,
+                status: peer.status.clone(),
+                last_seen_ms_ago: peer
+                    .last_seen
+                    .map(|seen| seen.elapsed().as_millis() as u64),
+                consecutive_failures: peer.consecutive_failures,
+                reconnecting: peer.reconnecting,
+            })
+            .collect();
+        states.sort_by_key(|state| state.node_id);
+        states
+    }
+}
+
+/// Ping `target` with exponential backoff until it answers
+///
+/// Each attempt uses a fresh client; the one that reaches the peer replaces
+/// the pooled client. The task ends when the peer answers, when a heartbeat
+/// or RPC has already found it reachable, when its address is removed, or
+/// when the pool is dropped.
+fn spawn_reconnect(
+    pool: Weak<PeerPool>,
+    config: NetworkConfig,
+    target: NodeId,
+    metrics: Option<Arc<RaftMetricsCollector>>,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut backoff = RECONNECT_INITIAL_BACKOFF;
+        loop {
+            tokio::time::sleep(backoff).await;
+            let Some(pool) = pool.upgrade() else {
+                return;
+            };
+            if !pool.is_reconnecting(target) {
+                return;
+            }
+            let Some(address) = config.get_node_address(target).await else {
+                debug!("Stopped reconnecting to node {} without an address", target);
+                pool.stop_reconnecting(target);
+                return;
+            };
+
+            let client = pool.new_client();
+            let url = format!("http://{}/_cluster/ping", address);
+            if matches!(
+                client.head(&url).send().await,
+                Ok(response) if response.status().is_success()
+            ) {
+                pool.reconnected(target, client);
+                info!("Reconnected to peer {}", target);
+                if let Some(metrics) = &metrics {
+                    metrics.record_peer_seen(target).await;
+                    metrics.set_peer_status(target, NodeStatus::Active).await;
+                    metrics.record_peer_reconnect().await;
+                }
+                return;
+            }
+
+            backoff = (backoff * 2).min(RECONNECT_MAX_BACKOFF);
+            debug!("Peer {} still unreachable, retrying in {:?}", target, backoff);
+        }
+    })
+}
+
 /// Network factory for creating network instances
 #[derive(Clone)]
 pub struct ConfluxNetworkFactory {
     config: NetworkConfig,
+    metrics: Option<Arc<RaftMetricsCollector>>,
+    /// Per-peer clients, shared by clones of the factory
+    pool: Arc<PeerPool>,
 }
 
 impl ConfluxNetworkFactory {
     pub fn new(config: NetworkConfig) -> Self {
-        Self { config }
+        Self {
+            pool: Arc::new(PeerPool::new(&config)),
+            config,
+            metrics: None,
+        }
+    }
+
+    /// Record snapshot bytes sent by the created clients and peer health in `metrics`
+    pub fn with_metrics(mut self, metrics: Arc<RaftMetricsCollector>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// Create a network client for a specific node outside of openraft
+    pub fn client_for(&self, target: NodeId) -> ConfluxNetwork {
+        ConfluxNetwork {
+            config: self.config.clone(),
+            client: self.pool.client(target),
+            target_node_id: target,
+            metrics: self.metrics.clone(),
+            pool: Some(self.pool.clone()),
+        }
+    }
+
+    /// Point `node_id` at `address` for the next RPC sent to it
+    ///
+    /// Pooled connections to the old address are dropped and the peer's
+    /// health starts over. Returns the previous address.
+    pub async fn update_peer_address(&self, node_id: NodeId, address: String) -> Option<String> {
+        let previous = self.config.update_peer_address(node_id, address.clone()).await;
+        if previous.as_deref() != Some(address.as_str()) {
+            self.pool.address_changed(node_id);
+            info!(
+                "Address of node {} changed from {} to {}",
+                node_id,
+                previous.as_deref().unwrap_or("<none>"),
+                address
+            );
+        }
+        previous
+    }
+
+    /// Connection pool state of every peer the factory has connected to
+    pub async fn peer_connections(&self) -> Vec<PeerConnectionState> {
+        let addresses = self.config.node_addresses.read().await;
+        self.pool.states(&addresses)
+    }
+
+    /// Send `HEAD /_cluster/ping` to every peer once and update its status
+    ///
+    /// A peer that answers becomes [`NodeStatus::Active`]. One that does not
+    /// is [`NodeStatus::Suspected`] until it has been silent for
+    /// `peer_dead_timeout`, then [`NodeStatus::Dead`]. Returns the new status
+    /// of each pinged peer.
+    pub async fn heartbeat_peers(&self, local_node_id: NodeId) -> HashMap<NodeId, NodeStatus> {
+        let peers: Vec<(NodeId, String)> = self
+            .config
+            .node_addresses
+            .read()
+            .await
+            .iter()
+            .filter(|(node_id, _)| **node_id != local_node_id)
+            .map(|(node_id, address)| (*node_id, address.clone()))
+            .collect();
+
+        let mut pings = JoinSet::new();
+        for (node_id, address) in peers {
+            let client = self.pool.client(node_id);
+            pings.spawn(async move {
+                let url = format!("http://{}/_cluster/ping", address);
+                let answered = matches!(
+                    client.head(&url).send().await,
+                    Ok(response) if response.status().is_success()
+                );
+                (node_id, answered)
+            });
+        }
+
+        let mut statuses = HashMap::new();
+        while let Some(result) = pings.join_next().await {
+            let Ok((node_id, answered)) = result else {
+                continue;
+            };
+            self.pool.record_request(node_id, answered);
+            let Some((previous, status)) =
+                self.pool
+                    .record_heartbeat(node_id, answered, self.config.peer_dead_timeout)
+            else {
+                continue;
+            };
+
+            if previous != status {
+                match status {
+                    NodeStatus::Active => info!("Peer {} is reachable", node_id),
+                    NodeStatus::Dead => warn!(
+                        "Peer {} has not answered heartbeats for {:?}, marking it dead",
+                        node_id, self.config.peer_dead_timeout
+                    ),
+                    _ => warn!("Peer {} did not answer heartbeat", node_id),
+                }
+            }
+            if let Some(metrics) = &self.metrics {
+                if answered {
+                    metrics.record_peer_seen(node_id).await;
+                }
+                metrics.set_peer_status(node_id, status.clone()).await;
+            }
+            statuses.insert(node_id, status);
+        }
+        statuses
+    }
+
+    /// Run [`Self::heartbeat_peers`] every `period` in a background task
+    pub fn spawn_peer_heartbeat(&self, local_node_id: NodeId, period: Duration) -> JoinHandle<()> {
+        let factory = self.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(period);
+            loop {
+                interval.tick().await;
+                factory.heartbeat_peers(local_node_id).await;
+            }
+        })
+    }
+
+    /// Resolve the configured peers and record their addresses
+    ///
+    /// Resolved peers are added to, or have their address updated in, the
+    /// shared node address table. Peers that are no longer resolved keep
+    /// their entry; removing them from the cluster is a membership decision.
+    ///
+    /// # Errors
+    ///
+    /// Returns an IO error if the peers cannot be resolved; the address table
+    /// is left unchanged.
+    pub async fn refresh_peers(
+        &self,
+        resolver: &dyn PeerResolver,
+    ) -> crate::error::Result<HashMap<NodeId, String>> {
+        let peers = resolve_peers(resolver, &self.config.peer_discovery).await?;
+        let mut node_addresses = self.config.node_addresses.write().await;
+        for (node_id, address) in &peers {
+            if node_addresses.get(node_id) != Some(address) {
+                debug!("Discovered node {} at {}", node_id, address);
+                node_addresses.insert(*node_id, address.clone());
+            }
+        }
+        Ok(peers)
     }
 }
 
 impl RaftNetworkFactory<TypeConfig> for ConfluxNetworkFactory {
     type Network = ConfluxNetwork;
 
-    async fn new_client(&mut self, target: NodeId, _node: &BasicNode) -> Self::Network {
-        ConfluxNetwork::new(self.config.clone(), target)
+    async fn new_client(&mut self, target: NodeId, node: &Node) -> Self::Network {
+        // Members added by another node may not be in the address book yet;
+        // their RPCs fail until an address is known, instead of panicking
+        if self.config.get_node_address(target).await.is_none() {
+            if node.addr.is_empty() {
+                warn!("Node {} is a member but has no known address", target);
+            } else {
+                warn!(
+                    "Node {} is not in the address book, using its membership address {}",
+                    target, node.addr
+                );
+                self.config.add_node(target, node.addr.clone()).await;
+            }
+        }
+        self.client_for(target)
     }
 }
 
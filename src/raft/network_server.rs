@@ -0,0 +1,131 @@
+use crate::raft::metrics::{NodeMetricsSummary, RaftMetricsCollector};
+use crate::raft::network::{NodePriorityUpdate, PreVoteRequest, PreVoteResponse};
+use crate::raft::node::{handle_pre_vote, update_node_priority};
+use crate::raft::types::*;
+use axum::{
+    extract::{DefaultBodyLimit, State},
+    http::StatusCode,
+    response::Json,
+    routing::{get, head, post},
+    Router,
+};
+use openraft::raft::{
+    AppendEntriesRequest, AppendEntriesResponse, InstallSnapshotRequest, InstallSnapshotResponse,
+    VoteRequest, VoteResponse,
+};
+use std::sync::Arc;
+use tokio::net::TcpListener;
+use tokio::task::JoinHandle;
+use tracing::{debug, error, info};
+
+/// Largest accepted InstallSnapshot request body
+///
+/// Snapshot chunks are JSON byte arrays, up to four times the chunk size.
+const MAX_SNAPSHOT_REQUEST_BYTES: usize = 64 * 1024 * 1024;
+
+/// Routes that receive the Raft RPCs sent by `ConfluxNetwork`
+///
+/// Successful responses are returned as the bare openraft response type,
+/// which is what the sending side deserializes; failures become a 500.
+///
+/// No request is authenticated, so this is only meant for tests and
+/// benchmarks. A node serves the same cluster routes from its HTTP API,
+/// where they require the cluster admin permission.
+pub fn raft_rpc_routes(raft: ConfluxRaft) -> Router {
+    Router::new()
+        .route("/health", get(|| async { StatusCode::OK }))
+        .route("/raft/append_entries", post(append_entries))
+        .route("/raft/vote", post(vote))
+        .route(
+            "/raft/install_snapshot",
+            post(install_snapshot).layer(DefaultBodyLimit::max(MAX_SNAPSHOT_REQUEST_BYTES)),
+        )
+        .route("/_cluster/trigger-elect", post(trigger_elect))
+        .route("/_cluster/node-priority", post(node_priority))
+        .route("/_cluster/pre-vote", post(pre_vote))
+        .route("/_cluster/ping", head(|| async { StatusCode::OK }))
+        .with_state(raft)
+}
+
+/// Route answering the leader's metrics pull (`GET /_cluster/metrics/local`)
+///
+/// Kept apart from [`raft_rpc_routes`] because it also needs the node's
+/// metrics collector; merge the two routers to serve both.
+pub fn metrics_rpc_routes(raft: ConfluxRaft, metrics: Arc<RaftMetricsCollector>) -> Router {
+    Router::new()
+        .route("/_cluster/metrics/local", get(local_metrics))
+        .with_state((raft, metrics))
+}
+
+/// Serve the Raft RPC routes on `listener` in a background task
+pub fn serve_raft_rpc(listener: TcpListener, raft: ConfluxRaft) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        if let Ok(addr) = listener.local_addr() {
+            info!("Raft RPC server listening on {}", addr);
+        }
+        if let Err(e) = axum::serve(listener, raft_rpc_routes(raft)).await {
+            error!("Raft RPC server stopped: {}", e);
+        }
+    })
+}
+
+async fn append_entries(
+    State(raft): State<ConfluxRaft>,
+    Json(rpc): Json<AppendEntriesRequest<TypeConfig>>,
+) -> Result<Json<AppendEntriesResponse<NodeId>>, (StatusCode, String)> {
+    debug!("Received AppendEntries with {} entries", rpc.entries.len());
+    raft.append_entries(rpc).await.map(Json).map_err(internal_error)
+}
+
+async fn vote(
+    State(raft): State<ConfluxRaft>,
+    Json(rpc): Json<VoteRequest<NodeId>>,
+) -> Result<Json<VoteResponse<NodeId>>, (StatusCode, String)> {
+    debug!("Received Vote from {}", rpc.vote.leader_id().node_id);
+    raft.vote(rpc).await.map(Json).map_err(internal_error)
+}
+
+async fn install_snapshot(
+    State(raft): State<ConfluxRaft>,
+    Json(rpc): Json<InstallSnapshotRequest<TypeConfig>>,
+) -> Result<Json<InstallSnapshotResponse<NodeId>>, (StatusCode, String)> {
+    debug!("Received InstallSnapshot at offset {}", rpc.offset);
+    raft.install_snapshot(rpc).await.map(Json).map_err(internal_error)
+}
+
+async fn trigger_elect(State(raft): State<ConfluxRaft>) -> Result<StatusCode, (StatusCode, String)> {
+    debug!("Received election trigger");
+    raft.trigger().elect().await.map_err(internal_error)?;
+    Ok(StatusCode::OK)
+}
+
+async fn node_priority(
+    State(raft): State<ConfluxRaft>,
+    Json(update): Json<NodePriorityUpdate>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    debug!("Received priority {} for node {}", update.priority, update.node_id);
+    update_node_priority(&raft, update.node_id, update.priority)
+        .await
+        .map_err(internal_error)?;
+    Ok(StatusCode::OK)
+}
+
+async fn pre_vote(
+    State(raft): State<ConfluxRaft>,
+    Json(request): Json<PreVoteRequest>,
+) -> Result<Json<PreVoteResponse>, (StatusCode, String)> {
+    debug!("Received PreVote from {}", request.candidate_id);
+    handle_pre_vote(&raft, &request).await.map(Json).map_err(internal_error)
+}
+
+async fn local_metrics(
+    State((raft, metrics)): State<(ConfluxRaft, Arc<RaftMetricsCollector>)>,
+) -> Json<NodeMetricsSummary> {
+    let raft_metrics = raft.metrics().borrow().clone();
+    Json(metrics.node_summary(&raft_metrics).await)
+}
+
+fn internal_error(e: impl std::fmt::Display) -> (StatusCode, String) {
+    error!("Raft RPC failed: {}", e);
+    (StatusCode::INTERNAL_SERVER_ERROR, e.to_string())
+}
@@ -1,10 +1,9 @@
 #[cfg(test)]
 mod tests {
+    use crate::raft::metrics::{NodeStatus, RaftMetricsCollector};
     use crate::raft::network::{NetworkConfig, ConfluxNetwork, ConfluxNetworkFactory};
-    use openraft::{
-        network::RaftNetworkFactory,
-        BasicNode,
-    };
+    use crate::raft::types::Node;
+    use openraft::network::RaftNetworkFactory;
     use std::collections::HashMap;
 
     /// Create a test network config
@@ -63,7 +62,7 @@ mod tests {
         let mut factory = ConfluxNetworkFactory::new(config);
 
         // Test that we can create a network instance
-        let network = factory.new_client(1, &BasicNode::default()).await;
+        let network = factory.new_client(1, &Node::default()).await;
         assert_eq!(network.target_node_id, 1);
     }
 
@@ -114,13 +113,352 @@ mod tests {
         let mut factory = ConfluxNetworkFactory::new(config);
 
         // Create multiple network instances for different nodes
-        let network1 = factory.new_client(1, &BasicNode::default()).await;
-        let network2 = factory.new_client(2, &BasicNode::default()).await;
-        let network3 = factory.new_client(3, &BasicNode::default()).await;
+        let network1 = factory.new_client(1, &Node::default()).await;
+        let network2 = factory.new_client(2, &Node::default()).await;
+        let network3 = factory.new_client(3, &Node::default()).await;
 
         // Verify they target different nodes
         assert_eq!(network1.target_node_id, 1);
         assert_eq!(network2.target_node_id, 2);
         assert_eq!(network3.target_node_id, 3);
     }
+
+    #[tokio::test]
+    async fn test_trigger_elect_posts_to_target_node() {
+        use axum::{routing::post, Router};
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = calls.clone();
+        let app = Router::new().route(
+            "/_cluster/trigger-elect",
+            post(move || async move {
+                counter.fetch_add(1, Ordering::SeqCst);
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+        tokio::spawn(async move { axum::serve(listener, app).await });
+
+        let config = NetworkConfig::new(HashMap::from([(2, address)]));
+        let factory = ConfluxNetworkFactory::new(config);
+
+        factory.client_for(2).trigger_elect().await.unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        // Unknown nodes fail without sending a request
+        assert!(factory.client_for(3).trigger_elect().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_cluster_rpcs_carry_the_auth_token() {
+        use axum::{http::HeaderMap, routing::post, Router};
+        use std::sync::Arc;
+        use tokio::sync::Mutex;
+
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let sink = received.clone();
+        let priority_sink = received.clone();
+        let app = Router::new()
+            .route(
+                "/_cluster/trigger-elect",
+                post(move |headers: HeaderMap| async move {
+                    sink.lock().await.push(headers.get("authorization").cloned());
+                }),
+            )
+            .route(
+                "/_cluster/node-priority",
+                post(move |headers: HeaderMap| async move {
+                    priority_sink.lock().await.push(headers.get("authorization").cloned());
+                }),
+            );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+        tokio::spawn(async move { axum::serve(listener, app).await });
+
+        let config = NetworkConfig::new(HashMap::from([(2, address)])).with_auth_token("node-token");
+        let factory = ConfluxNetworkFactory::new(config);
+        factory.client_for(2).trigger_elect().await.unwrap();
+        factory.client_for(2).set_node_priority(2, 200).await.unwrap();
+
+        let received = received.lock().await;
+        assert_eq!(received.len(), 2);
+        for header in received.iter() {
+            assert_eq!(header.as_ref().unwrap(), "Bearer node-token");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_change_membership_posts_members_to_target_node() {
+        use axum::{routing::post, Json, Router};
+        use std::collections::BTreeSet;
+        use std::sync::Arc;
+        use tokio::sync::Mutex;
+
+        let received = Arc::new(Mutex::new(None));
+        let sink = received.clone();
+        let app = Router::new().route(
+            "/raft/change_membership",
+            post(move |Json(members): Json<BTreeSet<crate::raft::types::NodeId>>| async move {
+                *sink.lock().await = Some(members);
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+        tokio::spawn(async move { axum::serve(listener, app).await });
+
+        let config = NetworkConfig::new(HashMap::from([(2, address)]));
+        let factory = ConfluxNetworkFactory::new(config);
+        let members = BTreeSet::from([2, 3]);
+
+        factory.client_for(2).change_membership(&members).await.unwrap();
+        assert_eq!(*received.lock().await, Some(members.clone()));
+
+        assert!(factory.client_for(3).change_membership(&members).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_rpc_after_address_update_goes_to_new_address() {
+        use axum::{routing::post, Router};
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        async fn serve_counter() -> (String, Arc<AtomicUsize>) {
+            let calls = Arc::new(AtomicUsize::new(0));
+            let counter = calls.clone();
+            let app = Router::new().route(
+                "/_cluster/trigger-elect",
+                post(move || async move {
+                    counter.fetch_add(1, Ordering::SeqCst);
+                }),
+            );
+            let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let address = listener.local_addr().unwrap().to_string();
+            tokio::spawn(async move { axum::serve(listener, app).await });
+            (address, calls)
+        }
+
+        let (old_address, old_calls) = serve_counter().await;
+        let (new_address, new_calls) = serve_counter().await;
+        let config = NetworkConfig::new(HashMap::from([(2, old_address.clone())]));
+        let factory = ConfluxNetworkFactory::new(config);
+        let network = factory.client_for(2);
+
+        network.trigger_elect().await.unwrap();
+        assert_eq!(old_calls.load(Ordering::SeqCst), 1);
+
+        let previous = factory.update_peer_address(2, new_address.clone()).await;
+        assert_eq!(previous, Some(old_address));
+        let peers = factory.peer_connections().await;
+        assert_eq!(peers[0].address, Some(new_address.clone()));
+        assert_eq!(peers[0].status, NodeStatus::Joining);
+
+        // Already created network instances use the new address too
+        network.trigger_elect().await.unwrap();
+        assert_eq!(old_calls.load(Ordering::SeqCst), 1);
+        assert_eq!(new_calls.load(Ordering::SeqCst), 1);
+        assert_eq!(
+            factory.config.node_addresses.serialize().await,
+            HashMap::from([(2, new_address)])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_new_client_without_address_uses_membership_address() {
+        let mut factory = ConfluxNetworkFactory::new(NetworkConfig::default());
+
+        // Neither known nor in the membership: the client is created anyway
+        let network = factory.new_client(4, &Node::default()).await;
+        assert_eq!(network.target_node_id, 4);
+        assert!(factory.config.get_node_address(4).await.is_none());
+
+        factory.new_client(5, &Node::new("127.0.0.1:8005")).await;
+        assert_eq!(
+            factory.config.get_node_address(5).await,
+            Some("127.0.0.1:8005".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_factory_reuses_pooled_client_per_peer() {
+        let mut config = create_test_network_config();
+        config.connections_per_peer = 2;
+        let factory = ConfluxNetworkFactory::new(config);
+
+        factory.client_for(2);
+        factory.clone().client_for(2);
+        factory.client_for(3);
+
+        let peers = factory.peer_connections().await;
+        assert_eq!(
+            peers.iter().map(|peer| peer.node_id).collect::<Vec<_>>(),
+            vec![2, 3]
+        );
+        assert_eq!(peers[0].address, Some("127.0.0.1:8002".to_string()));
+        assert_eq!(peers[0].max_idle_connections, 2);
+        assert_eq!(peers[0].status, NodeStatus::Joining);
+    }
+
+    #[tokio::test]
+    async fn test_heartbeat_marks_silent_peer_dead() {
+        use axum::{routing::head, Router};
+        use std::sync::atomic::{AtomicBool, Ordering};
+        use std::sync::Arc;
+        use std::time::Duration;
+
+        // The peer answers pings until it goes silent, then never responds
+        let silent = Arc::new(AtomicBool::new(false));
+        let flag = silent.clone();
+        let app = Router::new().route(
+            "/_cluster/ping",
+            head(move || async move {
+                if flag.load(Ordering::SeqCst) {
+                    std::future::pending::<()>().await;
+                }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+        tokio::spawn(async move { axum::serve(listener, app).await });
+
+        let mut config = NetworkConfig::new(HashMap::from([
+            (1, "127.0.0.1:1".to_string()),
+            (2, address),
+        ]));
+        config.timeout_secs = 1;
+        config.peer_dead_timeout = Duration::from_millis(1500);
+        let metrics = Arc::new(RaftMetricsCollector::new(1));
+        let factory = ConfluxNetworkFactory::new(config).with_metrics(metrics.clone());
+
+        // Node 1 is the local node and is never pinged
+        let statuses = factory.heartbeat_peers(1).await;
+        assert_eq!(statuses, HashMap::from([(2, NodeStatus::Active)]));
+        let report = metrics.get_metrics_report().await;
+        assert!(report.node_metrics.last_seen.contains_key(&2));
+        assert_eq!(report.cluster_metrics.membership.get(&2), Some(&NodeStatus::Active));
+
+        silent.store(true, Ordering::SeqCst);
+        let statuses = factory.heartbeat_peers(1).await;
+        assert_eq!(statuses.get(&2), Some(&NodeStatus::Suspected));
+
+        let statuses = factory.heartbeat_peers(1).await;
+        assert_eq!(statuses.get(&2), Some(&NodeStatus::Dead));
+        let report = metrics.get_metrics_report().await;
+        assert_eq!(report.cluster_metrics.membership.get(&2), Some(&NodeStatus::Dead));
+
+        let peers = factory.peer_connections().await;
+        assert_eq!(peers.len(), 1);
+        assert_eq!(peers[0].status, NodeStatus::Dead);
+        assert_eq!(peers[0].requests_sent, 3);
+        assert_eq!(peers[0].requests_failed, 2);
+        assert!(peers[0].last_seen_ms_ago.unwrap() >= 1500);
+
+        // The peer is back as soon as it answers again
+        silent.store(false, Ordering::SeqCst);
+        let statuses = factory.heartbeat_peers(1).await;
+        assert_eq!(statuses.get(&2), Some(&NodeStatus::Active));
+    }
+
+    #[tokio::test]
+    async fn test_heartbeat_marks_never_seen_peer_dead() {
+        use std::time::Duration;
+
+        // Nothing listens on the peer's address
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+        drop(listener);
+
+        let mut config = NetworkConfig::new(HashMap::from([(2, address)]));
+        config.peer_dead_timeout = Duration::from_millis(200);
+        let factory = ConfluxNetworkFactory::new(config);
+
+        let statuses = factory.heartbeat_peers(1).await;
+        assert_eq!(statuses.get(&2), Some(&NodeStatus::Suspected));
+
+        tokio::time::sleep(Duration::from_millis(250)).await;
+        let statuses = factory.heartbeat_peers(1).await;
+        assert_eq!(statuses.get(&2), Some(&NodeStatus::Dead));
+        assert_eq!(factory.peer_connections().await[0].last_seen_ms_ago, None);
+    }
+
+    #[tokio::test]
+    async fn test_failed_rpc_reconnects_to_recovered_peer() {
+        use axum::{
+            routing::{head, post},
+            Json, Router,
+        };
+        use openraft::network::{RPCOption, RaftNetwork};
+        use openraft::raft::{VoteRequest, VoteResponse};
+        use openraft::Vote;
+        use std::sync::Arc;
+        use std::time::Duration;
+
+        fn serve(
+            listener: tokio::net::TcpListener,
+        ) -> (tokio::sync::oneshot::Sender<()>, tokio::task::JoinHandle<()>) {
+            let app = Router::new()
+                .route("/_cluster/ping", head(|| async {}))
+                .route(
+                    "/raft/vote",
+                    post(|Json(rpc): Json<VoteRequest<crate::raft::types::NodeId>>| async move {
+                        Json(VoteResponse::new(rpc.vote, None, true))
+                    }),
+                );
+            let (stop, stopped) = tokio::sync::oneshot::channel::<()>();
+            let server = tokio::spawn(async move {
+                axum::serve(listener, app)
+                    .with_graceful_shutdown(async move {
+                        let _ = stopped.await;
+                    })
+                    .await
+                    .unwrap();
+            });
+            (stop, server)
+        }
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let (stop, server) = serve(listener);
+
+        let mut config = NetworkConfig::new(HashMap::from([(2, address.to_string())]));
+        config.timeout_secs = 1;
+        let metrics = Arc::new(RaftMetricsCollector::new(1));
+        let factory = ConfluxNetworkFactory::new(config).with_metrics(metrics.clone());
+        let mut network = factory.client_for(2);
+        let vote = || VoteRequest::new(Vote::new(1, 1), None);
+        let option = || RPCOption::new(Duration::from_secs(1));
+
+        network.vote(vote(), option()).await.unwrap();
+        assert_eq!(factory.peer_connections().await[0].status, NodeStatus::Active);
+
+        // The peer goes down: the failed RPC marks it unreachable
+        stop.send(()).unwrap();
+        server.await.unwrap();
+        assert!(network.vote(vote(), option()).await.is_err());
+        let peers = factory.peer_connections().await;
+        assert_eq!(peers[0].status, NodeStatus::Suspected);
+        assert!(peers[0].reconnecting);
+        assert!(peers[0].consecutive_failures >= 1);
+
+        // The peer comes back on the same address and is reconnected to
+        let (_stop, _server) = serve(tokio::net::TcpListener::bind(address).await.unwrap());
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while metrics.get_metrics_report().await.node_metrics.peer_reconnects == 0 {
+                tokio::time::sleep(Duration::from_millis(50)).await;
+            }
+        })
+        .await
+        .expect("peer was not reconnected");
+
+        let peers = factory.peer_connections().await;
+        assert_eq!(peers[0].status, NodeStatus::Active);
+        assert!(!peers[0].reconnecting);
+        assert_eq!(peers[0].consecutive_failures, 0);
+        let report = metrics.get_metrics_report().await;
+        assert_eq!(report.node_metrics.peer_reconnects, 1);
+        assert_eq!(report.cluster_metrics.membership.get(&2), Some(&NodeStatus::Active));
+
+        network.vote(vote(), option()).await.unwrap();
+    }
 }
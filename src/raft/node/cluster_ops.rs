@@ -3,11 +3,27 @@
 //! 提供Raft集群的成员管理和配置更新功能
 
 use super::core::RaftNode;
+use super::event_ops::ClusterEventKind;
 use crate::auth::{AuthContext, PermissionResult};
-use crate::error::Result;
-use crate::raft::{auth::AuthorizedRaftOperation, types::NodeId};
+use crate::error::{ConfluxError, RaftError, Result};
+use crate::raft::{
+    auth::AuthorizedRaftOperation,
+    metrics::RaftMetricsCollector,
+    network::{ConfluxNetworkFactory, PreVoteRequest},
+    store::Store,
+    types::{ConfluxRaft, NodeId},
+};
+use openraft::storage::RaftLogStorage;
+use openraft::ChangeMembers;
+use serde_json::json;
 use std::collections::BTreeSet;
-use tracing::{info, warn};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::task::JoinSet;
+use tracing::{debug, info, warn};
+
+/// 新节点作为学习者加入后等待其日志追上领导者的最长时间
+const LEARNER_CATCH_UP_TIMEOUT: Duration = Duration::from_secs(5);
 
 impl RaftNode {
     /// 向集群添加新节点（使用Raft共识和授权）
@@ -73,16 +89,8 @@ impl RaftNode {
             node_id, address
         );
 
-        // 获取现有节点用于验证
-        let existing_nodes: Vec<(NodeId, String)> = {
-            let members = self.get_members().await;
-            // 在真实实现中，我们会有获取所有成员地址的方法
-            // 现在我们创建一个简化的列表用于验证
-            members
-                .iter()
-                .map(|&id| (id, format!("node-{}", id)))
-                .collect()
-        };
+        // 获取现有节点及其真实地址用于验证
+        let existing_nodes = self.get_members_with_addresses().await;
 
         // 验证节点添加请求
         let _validated_address = self
@@ -126,10 +134,26 @@ impl RaftNode {
         }
 
         if let Some(ref raft) = self.get_raft() {
-            // 获取当前成员并添加新节点
-            let current_members = self.get_members().await;
+            // 先以学习者身份加入：节点地址随成员配置持久化，日志追上后才提升为投票成员，
+            // 避免不可达的节点成为投票成员后使集群失去多数派
+            self.add_learner(node_id, address.clone()).await?;
+            if !self
+                .wait_for_learner_catch_up(node_id, LEARNER_CATCH_UP_TIMEOUT)
+                .await
+            {
+                if let Err(e) = raft
+                    .change_membership(ChangeMembers::RemoveNodes(BTreeSet::from([node_id])), false)
+                    .await
+                {
+                    warn!("Failed to remove lagging learner {}: {}", node_id, e);
+                }
+                return Err(ConfluxError::raft(format!(
+                    "Node {} at {} did not catch up with the leader's log",
+                    node_id, address
+                )));
+            }
 
-            let mut new_members = current_members;
+            let mut new_members = self.get_members().await;
             new_members.insert(node_id);
 
             // 使用Raft的change_membership通过共识添加节点
@@ -139,15 +163,16 @@ impl RaftNode {
                     crate::error::ConfluxError::raft(format!("Failed to add node via Raft: {}", e))
                 })?;
 
-            // 注意：在实际实现中，成员更新应该通过Raft状态机处理
-            // 这里我们暂时跳过本地成员更新，因为它应该通过共识机制自动处理
-
             info!(
                 "Node {} added to cluster successfully via Raft consensus",
                 node_id
             );
+            self.publish_event(
+                ClusterEventKind::NodeAdded,
+                json!({ "node_id": node_id, "address": address }),
+            );
         } else {
-            return Err(crate::error::ConfluxError::raft("Raft not initialized"));
+            return Err(RaftError::NotInitialized.into());
         }
 
         Ok(())
@@ -211,16 +236,8 @@ impl RaftNode {
     ) -> Result<()> {
         info!("Removing node {} from cluster via Raft consensus", node_id);
 
-        // 获取现有节点用于验证
-        let existing_nodes: Vec<(NodeId, String)> = {
-            let members = self.get_members().await;
-            // 在真实实现中，我们会有获取所有成员地址的方法
-            // 现在我们创建一个简化的列表用于验证
-            members
-                .iter()
-                .map(|&id| (id, format!("node-{}", id)))
-                .collect()
-        };
+        // 获取现有节点及其真实地址用于验证
+        let existing_nodes = self.get_members_with_addresses().await;
 
         // 验证节点移除请求
         self.input_validator()
@@ -284,15 +301,13 @@ impl RaftNode {
                     ))
                 })?;
 
-            // 注意：在实际实现中，成员更新应该通过Raft状态机处理
-            // 这里我们暂时跳过本地成员更新，因为它应该通过共识机制自动处理
-
             info!(
                 "Node {} removed from cluster successfully via Raft consensus",
                 node_id
             );
+            self.publish_event(ClusterEventKind::NodeRemoved, json!({ "node_id": node_id }));
         } else {
-            return Err(crate::error::ConfluxError::raft("Raft not initialized"));
+            return Err(RaftError::NotInitialized.into());
         }
 
         Ok(())
@@ -352,12 +367,9 @@ impl RaftNode {
                     ))
                 })?;
 
-            // 注意：在实际实现中，成员更新应该通过Raft状态机处理
-            // 这里我们暂时跳过本地成员更新，因为它应该通过共识机制自动处理
-
             info!("Membership change completed via Raft consensus");
         } else {
-            return Err(crate::error::ConfluxError::raft("Raft not initialized"));
+            return Err(RaftError::NotInitialized.into());
         }
 
         Ok(())
@@ -416,7 +428,10 @@ impl RaftNode {
             }
         }
 
-        Ok(self.metrics_collector().get_metrics_report().await)
+        let (compressed, uncompressed) = self.store().log_compression_stats();
+        let metrics = self.metrics_collector();
+        metrics.set_log_compression_stats(compressed, uncompressed).await;
+        Ok(metrics.get_metrics_report().await)
     }
 
     /// 动态更新超时配置（带授权）
@@ -532,6 +547,156 @@ impl RaftNode {
 
         Ok(())
     }
+
+    /// 向其他投票成员发起预投票
+    ///
+    /// 预投票不会改变任何节点的任期；只有多数投票成员（包括本节点）同意时，
+    /// 真正发起选举才不会打断仍然存活的领导者
+    ///
+    /// # Returns
+    ///
+    /// 获得多数同意时返回true
+    ///
+    /// # Errors
+    ///
+    /// 如果Raft未初始化或读取本地日志状态失败，返回错误
+    pub async fn pre_vote(&self) -> Result<bool> {
+        let raft = self
+            .get_raft()
+            .ok_or(RaftError::NotInitialized)?;
+        let network_factory = self.network_factory().read().await.clone();
+        collect_pre_votes(
+            self.node_id(),
+            raft,
+            &self.store(),
+            &network_factory,
+            &self.metrics_collector(),
+        )
+        .await
+    }
+
+    /// 更新对等节点在本节点地址表中的地址
+    ///
+    /// 发往该节点的下一次RPC即使用新地址，到旧地址的连接被丢弃。只修改本节点的地址表，
+    /// 不改变集群成员配置，其他节点需要分别更新
+    ///
+    /// # Arguments
+    ///
+    /// * `node_id` - 对等节点ID
+    /// * `address` - 新地址，格式为 `ip:port`
+    ///
+    /// # Errors
+    ///
+    /// 如果地址格式无效或 `node_id` 是本节点，返回验证错误
+    pub async fn update_peer_address(&self, node_id: NodeId, address: String) -> Result<()> {
+        self.input_validator().validate_node_address(&address)?;
+        if node_id == self.node_id() {
+            return Err(ConfluxError::validation(
+                "Cannot change the address of the local node".to_string(),
+            ));
+        }
+
+        self.network_factory()
+            .read()
+            .await
+            .update_peer_address(node_id, address)
+            .await;
+        Ok(())
+    }
+}
+
+
+/// 向所有其他投票成员并发发送预投票请求并统计结果
+///
+/// 每个请求最多等待一个最小选举超时；无法连接或超时的节点视为未同意。
+/// 收到的每个响应都会记录到指标收集器中
+///
+/// # Arguments
+///
+/// * `node_id` - 本节点ID（候选者）
+/// * `raft` - 本节点的Raft实例
+/// * `store` - 本节点的存储，用于读取最后一条日志
+/// * `network_factory` - 网络客户端工厂
+/// * `metrics` - 指标收集器
+///
+/// # Returns
+///
+/// 本节点不是投票成员时返回false；否则在同意票（包括本节点）超过半数时返回true
+///
+/// # Errors
+///
+/// 如果读取本地日志状态失败，返回错误
+pub(crate) async fn collect_pre_votes(
+    node_id: NodeId,
+    raft: &ConfluxRaft,
+    store: &Arc<Store>,
+    network_factory: &ConfluxNetworkFactory,
+    metrics: &RaftMetricsCollector,
+) -> Result<bool> {
+    let voters: BTreeSet<NodeId> = raft
+        .metrics()
+        .borrow()
+        .membership_config
+        .membership()
+        .voter_ids()
+        .collect();
+    if !voters.contains(&node_id) {
+        return Ok(false);
+    }
+
+    let log_state = store
+        .clone()
+        .get_log_state()
+        .await
+        .map_err(|e| ConfluxError::raft(format!("Failed to read log state: {}", e)))?;
+    let last_log_id = log_state.last_log_id.or(log_state.last_purged_log_id);
+    let request = PreVoteRequest {
+        candidate_id: node_id,
+        last_log_index: last_log_id.map_or(0, |log_id| log_id.index),
+        last_log_term: last_log_id.map_or(0, |log_id| log_id.leader_id.term),
+    };
+    let timeout = Duration::from_millis(raft.config().election_timeout_min);
+
+    let mut requests = JoinSet::new();
+    for peer in voters.iter().copied().filter(|id| *id != node_id) {
+        let client = network_factory.client_for(peer);
+        let request = request.clone();
+        requests.spawn(async move {
+            (peer, tokio::time::timeout(timeout, client.pre_vote(&request)).await)
+        });
+    }
+
+    let mut granted = 1;
+    while let Some(result) = requests.join_next().await {
+        match result {
+            Ok((peer, Ok(Ok(response)))) => {
+                debug!(
+                    "Node {} {} pre-vote of node {} (term {})",
+                    peer,
+                    if response.granted { "granted" } else { "denied" },
+                    node_id,
+                    response.term
+                );
+                metrics.record_pre_vote(response.granted).await;
+                if response.granted {
+                    granted += 1;
+                }
+            }
+            Ok((peer, Ok(Err(e)))) => debug!("Pre-vote to node {} failed: {}", peer, e),
+            Ok((peer, Err(_))) => debug!("Pre-vote to node {} timed out", peer),
+            Err(e) => warn!("Pre-vote task failed: {}", e),
+        }
+    }
+
+    let won = granted * 2 > voters.len();
+    info!(
+        "Node {} received {}/{} pre-votes, {}",
+        node_id,
+        granted,
+        voters.len(),
+        if won { "starting election" } else { "staying follower" }
+    );
+    Ok(won)
 }
 
 #[cfg(test)]
@@ -546,10 +711,7 @@ mod tests {
         let app_config = AppConfig {
             storage: StorageConfig {
                 data_dir: temp_dir.path().to_string_lossy().to_string(),
-                max_open_files: 1000,
-                cache_size_mb: 256,
-                write_buffer_size_mb: 64,
-                max_write_buffer_number: 3,
+                ..Default::default()
             },
             ..Default::default()
         };
@@ -2,8 +2,17 @@
 //!
 //! 定义节点配置和资源限制相关的数据结构
 
-use crate::raft::{network::NetworkConfig, types::NodeId};
+use crate::error::ConfluxError;
+use crate::raft::store::{ContentLimits, DEFAULT_MAX_CONFIG_CONTENT_BYTES, DEFAULT_MAX_VERSION_HISTORY};
+use crate::raft::{
+    network::NetworkConfig,
+    types::{NodeId, DEFAULT_ELECTION_PRIORITY},
+    validation::RaftInputValidator,
+};
 use openraft::Config as RaftConfig;
+use serde::{Deserialize, Serialize};
+use std::net::SocketAddr;
+use std::time::Duration;
 
 /// Raft节点配置
 /// 
@@ -41,6 +50,19 @@ pub struct NodeConfig {
     pub election_timeout_max: u64,
     /// 资源限制配置
     pub resource_limits: ResourceLimits,
+    /// 选举优先级（0-255），默认128，数值越高越优先成为领导者
+    pub election_priority: u8,
+    /// 快照流式传输配置
+    pub snapshot_stream: SnapshotStreamConfig,
+    /// 是否启用预投票（Pre-Vote），默认关闭
+    ///
+    /// 启用后由应用层在选举超时后先发起预投票，获得多数同意才真正发起选举，
+    /// 避免网络分区恢复后的节点以更高任期打断现有领导者
+    pub pre_vote_enabled: bool,
+    /// 是否用zstd压缩携带客户端请求的Raft日志条目，默认关闭
+    ///
+    /// 大的配置内容会使日志膨胀，拖慢快照传输和日志重放
+    pub compress_log_entries: bool,
 }
 
 impl Default for NodeConfig {
@@ -54,6 +76,42 @@ impl Default for NodeConfig {
             election_timeout_min: 300,
             election_timeout_max: 600,
             resource_limits: ResourceLimits::default(),
+            election_priority: DEFAULT_ELECTION_PRIORITY,
+            snapshot_stream: SnapshotStreamConfig::default(),
+            pre_vote_enabled: false,
+            compress_log_entries: false,
+        }
+    }
+}
+
+/// 快照流式传输配置
+///
+/// 快照按固定大小的分块生成、发送和安装，避免大状态时一次性占用内存
+///
+/// # Examples
+///
+/// ```rust
+/// use conflux::raft::node::SnapshotStreamConfig;
+///
+/// let config = SnapshotStreamConfig {
+///     chunk_size_bytes: 1024 * 1024, // 1MB
+///     compression: true,
+/// };
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SnapshotStreamConfig {
+    /// 快照分块大小（字节），默认4MB
+    pub chunk_size_bytes: usize,
+    /// 是否使用deflate压缩快照数据
+    pub compression: bool,
+}
+
+impl Default for SnapshotStreamConfig {
+    fn default() -> Self {
+        Self {
+            chunk_size_bytes: 4 * 1024 * 1024, // 4MB
+            compression: false,
         }
     }
 }
@@ -73,9 +131,13 @@ impl Default for NodeConfig {
 ///     max_request_size: 2 * 1024 * 1024, // 2MB
 ///     max_memory_usage: 100 * 1024 * 1024, // 100MB
 ///     request_timeout_ms: 10000, // 10 seconds
+///     max_config_content_bytes: 1024 * 1024, // 1MB
+///     max_version_history: 200,
+///     rate_limit_window_ms: 1000,
 /// };
 /// ```
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct ResourceLimits {
     /// 每个客户端每秒最大请求数
     pub max_requests_per_second: u32,
@@ -87,6 +149,12 @@ pub struct ResourceLimits {
     pub max_memory_usage: usize,
     /// 请求超时时间（毫秒）
     pub request_timeout_ms: u64,
+    /// 单个配置版本内容的最大大小（字节），可按租户覆盖
+    pub max_config_content_bytes: usize,
+    /// 每个配置最多保留的版本数，可按租户覆盖
+    pub max_version_history: usize,
+    /// 速率限制的滑动窗口长度（毫秒），窗口内的请求数不超过 `max_requests_per_second`
+    pub rate_limit_window_ms: u64,
 }
 
 impl Default for ResourceLimits {
@@ -97,6 +165,9 @@ impl Default for ResourceLimits {
             max_request_size: 1024 * 1024, // 1MB
             max_memory_usage: 50 * 1024 * 1024, // 50MB
             request_timeout_ms: 5000, // 5 seconds
+            max_config_content_bytes: DEFAULT_MAX_CONFIG_CONTENT_BYTES, // 512KB
+            max_version_history: DEFAULT_MAX_VERSION_HISTORY,
+            rate_limit_window_ms: 1000, // 1 second
         }
     }
 }
@@ -132,6 +203,26 @@ impl ResourceLimits {
             max_request_size,
             max_memory_usage,
             request_timeout_ms,
+            max_config_content_bytes: DEFAULT_MAX_CONFIG_CONTENT_BYTES,
+            max_version_history: DEFAULT_MAX_VERSION_HISTORY,
+            rate_limit_window_ms: 1000,
+        }
+    }
+
+    /// 速率限制的滑动窗口长度
+    pub fn rate_limit_window(&self) -> Duration {
+        Duration::from_millis(self.rate_limit_window_ms)
+    }
+
+    /// 获取配置内容相关的限制
+    ///
+    /// # Returns
+    ///
+    /// 返回内容大小和版本历史限制
+    pub fn content_limits(&self) -> ContentLimits {
+        ContentLimits {
+            max_config_content_bytes: self.max_config_content_bytes,
+            max_version_history: self.max_version_history,
         }
     }
 
@@ -169,6 +260,18 @@ impl ResourceLimits {
         if self.request_timeout_ms == 0 {
             return Err("request_timeout_ms must be greater than 0".to_string());
         }
+
+        if self.max_config_content_bytes == 0 {
+            return Err("max_config_content_bytes must be greater than 0".to_string());
+        }
+
+        if self.max_version_history == 0 {
+            return Err("max_version_history must be greater than 0".to_string());
+        }
+
+        if self.rate_limit_window_ms == 0 {
+            return Err("rate_limit_window_ms must be greater than 0".to_string());
+        }
         
         // 检查内存使用量是否合理（至少能容纳一个最大请求）
         if self.max_memory_usage < self.max_request_size {
@@ -243,56 +346,162 @@ impl NodeConfig {
         self.resource_limits = resource_limits;
     }
 
+    /// 创建节点配置构建器
+    ///
+    /// 节点ID和地址为必填项，其余字段使用默认值，可通过链式调用设置
+    ///
+    /// # Arguments
+    ///
+    /// * `node_id` - 节点ID
+    /// * `address` - 节点地址
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use conflux::raft::node::NodeConfig;
+    ///
+    /// let config = NodeConfig::builder(1, "127.0.0.1:8080")
+    ///     .timeouts(100, 300, 600)
+    ///     .pre_vote_enabled(true)
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(config.heartbeat_interval, 100);
+    ///
+    /// // 无效配置在构建时即被拒绝
+    /// assert!(NodeConfig::builder(0, "127.0.0.1:8080").build().is_err());
+    /// ```
+    pub fn builder(node_id: NodeId, address: impl Into<String>) -> NodeConfigBuilder {
+        NodeConfigBuilder {
+            config: Self::new(node_id, address.into()),
+        }
+    }
+
     /// 验证节点配置的合理性
-    /// 
+    ///
+    /// 检查节点ID非零、地址可解析为 `SocketAddr`、超时配置满足
+    /// [`RaftInputValidator::validate_timeout_config`] 的要求以及资源限制合理
+    ///
     /// # Returns
-    /// 
-    /// 如果配置合理返回Ok(())，否则返回错误信息
-    /// 
+    ///
+    /// 如果配置合理返回Ok(())，否则返回验证错误
+    ///
     /// # Examples
-    /// 
+    ///
     /// ```rust
     /// use conflux::raft::node::NodeConfig;
-    /// 
+    ///
     /// let config = NodeConfig::default();
     /// assert!(config.validate().is_ok());
     /// ```
-    pub fn validate(&self) -> Result<(), String> {
+    pub fn validate(&self) -> crate::error::Result<()> {
         if self.node_id == 0 {
-            return Err("node_id must be greater than 0".to_string());
-        }
-        
-        if self.address.is_empty() {
-            return Err("address cannot be empty".to_string());
+            return Err(ConfluxError::validation("node_id must be greater than 0"));
         }
-        
-        if self.heartbeat_interval == 0 {
-            return Err("heartbeat_interval must be greater than 0".to_string());
-        }
-        
-        if self.election_timeout_min == 0 {
-            return Err("election_timeout_min must be greater than 0".to_string());
-        }
-        
-        if self.election_timeout_max == 0 {
-            return Err("election_timeout_max must be greater than 0".to_string());
-        }
-        
-        if self.election_timeout_min >= self.election_timeout_max {
-            return Err("election_timeout_min must be less than election_timeout_max".to_string());
-        }
-        
-        if self.heartbeat_interval >= self.election_timeout_min {
-            return Err("heartbeat_interval must be less than election_timeout_min".to_string());
+
+        if self.address.parse::<SocketAddr>().is_err() {
+            return Err(ConfluxError::validation(format!(
+                "address '{}' is not a valid socket address (expected ip:port)",
+                self.address
+            )));
         }
-        
+
+        RaftInputValidator::new().validate_timeout_config(
+            Some(self.heartbeat_interval),
+            Some(self.election_timeout_min),
+            Some(self.election_timeout_max),
+        )?;
+
         // 验证资源限制
-        self.resource_limits.validate()?;
-        
+        self.resource_limits
+            .validate()
+            .map_err(ConfluxError::validation)?;
+
         Ok(())
     }
 }
 
+/// 节点配置构建器
+///
+/// 由 [`NodeConfig::builder`] 创建，[`build`](Self::build) 时验证配置，
+/// 使无效配置在构造阶段而不是节点启动时被发现
+#[derive(Debug, Clone)]
+pub struct NodeConfigBuilder {
+    config: NodeConfig,
+}
+
+impl NodeConfigBuilder {
+    /// 设置Raft算法配置
+    pub fn raft_config(mut self, raft_config: RaftConfig) -> Self {
+        self.config.raft_config = raft_config;
+        self
+    }
+
+    /// 设置网络配置
+    pub fn network_config(mut self, network_config: NetworkConfig) -> Self {
+        self.config.network_config = network_config;
+        self
+    }
+
+    /// 设置心跳间隔和选举超时（毫秒）
+    pub fn timeouts(
+        mut self,
+        heartbeat_interval: u64,
+        election_timeout_min: u64,
+        election_timeout_max: u64,
+    ) -> Self {
+        self.config
+            .set_timeouts(heartbeat_interval, election_timeout_min, election_timeout_max);
+        self
+    }
+
+    /// 设置资源限制
+    pub fn resource_limits(mut self, resource_limits: ResourceLimits) -> Self {
+        self.config.resource_limits = resource_limits;
+        self
+    }
+
+    /// 设置选举优先级
+    pub fn election_priority(mut self, election_priority: u8) -> Self {
+        self.config.election_priority = election_priority;
+        self
+    }
+
+    /// 设置快照流式传输配置
+    pub fn snapshot_stream(mut self, snapshot_stream: SnapshotStreamConfig) -> Self {
+        self.config.snapshot_stream = snapshot_stream;
+        self
+    }
+
+    /// 设置是否启用预投票
+    pub fn pre_vote_enabled(mut self, pre_vote_enabled: bool) -> Self {
+        self.config.pre_vote_enabled = pre_vote_enabled;
+        self
+    }
+
+    /// 设置是否压缩Raft日志条目
+    pub fn compress_log_entries(mut self, compress_log_entries: bool) -> Self {
+        self.config.compress_log_entries = compress_log_entries;
+        self
+    }
+
+    /// 验证并返回节点配置
+    ///
+    /// # Errors
+    ///
+    /// 配置未通过 [`NodeConfig::validate`] 时返回验证错误
+    pub fn build(self) -> crate::error::Result<NodeConfig> {
+        self.config.validate()?;
+        Ok(self.config)
+    }
+
+    /// 不做验证直接返回节点配置，供保持原有签名的辅助函数使用
+    ///
+    /// 未验证的配置会在 `RaftNode::new` 中被拒绝
+    pub(crate) fn build_unchecked(self) -> NodeConfig {
+        self.config
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -305,6 +514,8 @@ mod tests {
         assert_eq!(limits.max_request_size, 1024 * 1024);
         assert_eq!(limits.max_memory_usage, 50 * 1024 * 1024);
         assert_eq!(limits.request_timeout_ms, 5000);
+        assert_eq!(limits.max_config_content_bytes, 512 * 1024);
+        assert_eq!(limits.max_version_history, 100);
     }
 
     #[test]
@@ -347,4 +558,65 @@ mod tests {
         config.election_timeout_max = 300;
         assert!(config.validate().is_err());
     }
+
+    #[test]
+    fn test_node_config_validation_checks_address_and_timeouts() {
+        let mut config = NodeConfig::default();
+        config.address = "localhost".to_string();
+        assert!(config.validate().is_err());
+
+        config.address = "node-1.conflux.svc:8080".to_string();
+        assert!(config.validate().is_err());
+
+        config.address = "[::1]:8080".to_string();
+        assert!(config.validate().is_ok());
+
+        // The default heartbeat would not be shorter than this election timeout
+        config.election_timeout_min = 150;
+        config.election_timeout_max = 300;
+        config.heartbeat_interval = 150;
+        let err = config.validate().unwrap_err();
+        assert!(matches!(err, ConfluxError::Validation(_)));
+
+        // Below the validator's 10ms floor
+        config.heartbeat_interval = 5;
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_node_config_builder() {
+        let config = NodeConfig::builder(3, "10.0.0.3:9000")
+            .timeouts(50, 200, 400)
+            .resource_limits(ResourceLimits::new(10, 5, 1024, 4096, 1000))
+            .election_priority(200)
+            .pre_vote_enabled(true)
+            .compress_log_entries(true)
+            .build()
+            .unwrap();
+
+        assert_eq!(config.node_id, 3);
+        assert_eq!(config.address, "10.0.0.3:9000");
+        assert_eq!(
+            (config.heartbeat_interval, config.election_timeout_min, config.election_timeout_max),
+            (50, 200, 400)
+        );
+        assert_eq!(config.resource_limits.max_requests_per_second, 10);
+        assert_eq!(config.election_priority, 200);
+        assert!(config.pre_vote_enabled);
+        assert!(config.compress_log_entries);
+    }
+
+    #[test]
+    fn test_node_config_builder_rejects_invalid_config() {
+        assert!(NodeConfig::builder(0, "127.0.0.1:8080").build().is_err());
+        assert!(NodeConfig::builder(1, "not-an-address").build().is_err());
+        assert!(NodeConfig::builder(1, "127.0.0.1:8080")
+            .timeouts(600, 300, 900)
+            .build()
+            .is_err());
+        assert!(NodeConfig::builder(1, "127.0.0.1:8080")
+            .resource_limits(ResourceLimits::new(0, 5, 1024, 4096, 1000))
+            .build()
+            .is_err());
+    }
 }
@@ -3,23 +3,30 @@
 //! 包含RaftNode的主要实现，负责节点的创建、启动、停止和基本操作
 
 use super::config::NodeConfig;
+use super::event_ops::ClusterEventBus;
+use super::lease_ops::LeaderLease;
 use super::resource_limiter::{ResourceLimiter, ResourceStats};
+use crate::app::connect_event_sink;
 use crate::config::AppConfig;
-use crate::error::Result;
+use crate::error::{ConfluxError, RaftError, Result, ResultExt};
 use crate::raft::{
     auth::RaftAuthzService,
-    metrics::RaftMetricsCollector,
-    network::ConfluxNetworkFactory,
+    metrics::{LagThresholds, RaftMetricsCollector},
+    network::{
+        ConfluxNetwork, ConfluxNetworkFactory, NetworkConfig, PeerConnectionState,
+        PEER_HEARTBEAT_INTERVAL,
+    },
+    state_machine::ConfluxStateMachineWrapper,
     store::{StateMachineManager, Store},
     types::*,
     validation::RaftInputValidator,
 };
-use openraft::Raft;
-use std::collections::BTreeSet;
+use openraft::{Raft, SnapshotPolicy};
+use std::collections::{BTreeMap, BTreeSet};
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::RwLock;
-use tracing::{debug, error, info};
+use tracing::{debug, error, info, warn};
 
 /// Raft节点核心实现
 ///
@@ -55,16 +62,34 @@ pub struct RaftNode {
     members: Arc<RwLock<BTreeSet<NodeId>>>,
     /// 实际的Raft实例
     raft: Option<ConfluxRaft>,
+    /// Raft实例使用的状态机，与Raft实例共享已应用状态
+    state_machine: Option<ConfluxStateMachineWrapper>,
     /// 状态机管理器句柄
     state_machine_handle: Option<tokio::task::JoinHandle<()>>,
+    /// 预投票选举监控任务句柄（启用预投票时存在）
+    pre_vote_handle: Option<tokio::task::JoinHandle<()>>,
+    /// 领导者定时维护任务句柄（Raft启动后存在）
+    housekeeping_handle: Option<tokio::task::JoinHandle<()>>,
+    /// 对等节点连接心跳任务句柄
+    peer_heartbeat_handle: Option<tokio::task::JoinHandle<()>>,
     /// 指标收集器
     metrics_collector: Arc<RaftMetricsCollector>,
+    /// 指标写入InfluxDB的后台任务句柄（配置了influx_endpoint时存在）
+    influx_flush_handle: Option<tokio::task::JoinHandle<()>>,
+    /// 自上次快照以来应用多少条日志后自动生成快照
+    snapshot_threshold: u64,
+    /// 快照之后保留的已应用日志条数，更早的日志被清理
+    max_applied_log_to_keep: u64,
     /// 客户端请求资源限制器
     resource_limiter: Arc<ResourceLimiter>,
     /// 可选的集群操作授权服务
     authz_service: Option<Arc<RaftAuthzService>>,
     /// 集群操作输入验证器
     input_validator: Arc<RaftInputValidator>,
+    /// 集群事件总线
+    event_bus: ClusterEventBus,
+    /// 领导者租约，有效期内的读请求无需确认领导权
+    leader_lease: Arc<LeaderLease>,
 }
 
 impl RaftNode {
@@ -81,6 +106,7 @@ impl RaftNode {
     ///
     /// # Errors
     ///
+    /// 如果节点配置未通过 [`NodeConfig::validate`]，在进行任何I/O之前返回验证错误；
     /// 如果存储初始化失败或其他组件创建失败，返回错误
     ///
     /// # Examples
@@ -96,38 +122,69 @@ impl RaftNode {
     /// # });
     /// ```
     pub async fn new(config: NodeConfig, app_config: &AppConfig) -> Result<Self> {
+        config.validate()?;
+
         info!(
             "Creating Raft node {} at {}",
             config.node_id, config.address
         );
 
         // 创建存储并获取事件接收器
-        let (store, event_receiver) = Store::new(&app_config.storage.data_dir).await?;
+        let (store, event_receiver) = Store::open(&app_config.storage.data_dir, &app_config.storage)
+            .await
+            .with_context("Failed to open store")?;
         let store = Arc::new(store);
+        store
+            .published_cache()
+            .set_ttl(Duration::from_secs(app_config.storage.cache_ttl_secs));
+        store.set_log_compression(config.compress_log_entries);
+        for rule in &app_config.env_inheritance {
+            store
+                .set_env_inheritance(&rule.namespace(), Some(&rule.inherits))
+                .with_context("Invalid environment inheritance")?;
+        }
 
         // 启动状态机管理器
         let mut state_machine_manager = StateMachineManager::new(store.clone(), event_receiver);
+        if let Some(sink_config) = &app_config.event_sink {
+            state_machine_manager =
+                state_machine_manager.with_event_sink(connect_event_sink(sink_config)?);
+        }
         let state_machine_handle = tokio::spawn(async move {
             state_machine_manager.run().await;
         });
 
+        // 创建指标收集器，写入InfluxDB的指标以集群名作为tenant标签
+        let metrics_collector = Arc::new(
+            RaftMetricsCollector::new(config.node_id)
+                .with_tenant(app_config.raft.cluster_name.clone())
+                .with_lag_thresholds(LagThresholds {
+                    degraded: app_config.raft.lag_degraded_threshold,
+                    unhealthy: app_config.raft.lag_unhealthy_threshold,
+                }),
+        );
+        let influx_flush_handle = metrics_collector.spawn_influx_flush(&app_config.observability);
+
         // 创建网络工厂
-        let network_factory = Arc::new(RwLock::new(ConfluxNetworkFactory::new(
-            config.network_config.clone(),
-        )));
+        let network_factory = Arc::new(RwLock::new(
+            ConfluxNetworkFactory::new(config.network_config.clone())
+                .with_metrics(metrics_collector.clone()),
+        ));
 
         // 初始化成员列表（包含自己）
         let mut members = BTreeSet::new();
         members.insert(config.node_id);
 
-        // 创建指标收集器
-        let metrics_collector = Arc::new(RaftMetricsCollector::new(config.node_id));
-
         // 创建资源限制器
-        let resource_limiter = Arc::new(ResourceLimiter::new(config.resource_limits.clone()));
+        // 内容限制与存储共享，使按租户覆盖的限制在写入时生效
+        let resource_limiter = Arc::new(ResourceLimiter::with_content_limits(
+            config.resource_limits.clone(),
+            store.content_limits(),
+        ));
 
         // 创建输入验证器
         let input_validator = Arc::new(RaftInputValidator::new());
+        let leader_lease = Arc::new(LeaderLease::new(config.heartbeat_interval));
 
         Ok(Self {
             config,
@@ -135,11 +192,20 @@ impl RaftNode {
             network_factory,
             members: Arc::new(RwLock::new(members)),
             raft: None, // 将在start()中初始化
+            state_machine: None,
             state_machine_handle: Some(state_machine_handle),
+            pre_vote_handle: None,
+            housekeeping_handle: None,
+            peer_heartbeat_handle: None,
             metrics_collector,
+            influx_flush_handle,
+            snapshot_threshold: app_config.raft.snapshot_threshold,
+            max_applied_log_to_keep: app_config.raft.max_applied_log_to_keep,
             resource_limiter,
             authz_service: None, // 可以稍后通过set_authz_service()设置
             input_validator,
+            event_bus: ClusterEventBus::default(),
+            leader_lease,
         })
     }
 
@@ -179,6 +245,11 @@ impl RaftNode {
         self.metrics_collector.clone()
     }
 
+    /// 本节点的领导者租约
+    pub fn leader_lease(&self) -> Arc<LeaderLease> {
+        self.leader_lease.clone()
+    }
+
     /// 获取资源限制器
     ///
     /// # Returns
@@ -188,6 +259,40 @@ impl RaftNode {
         self.resource_limiter.clone()
     }
 
+    /// 获取节点配置
+    pub fn config(&self) -> &NodeConfig {
+        &self.config
+    }
+
+    /// 获取可修改的节点配置
+    pub(super) fn config_mut(&mut self) -> &mut NodeConfig {
+        &mut self.config
+    }
+
+    /// 获取节点网络配置，节点地址表在集群内各网络客户端之间共享
+    pub fn network_config(&self) -> &NetworkConfig {
+        &self.config.network_config
+    }
+
+    /// 创建到指定节点的网络客户端
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - 目标节点ID
+    pub(crate) async fn network_client(&self, target: NodeId) -> ConfluxNetwork {
+        self.network_factory.read().await.client_for(target)
+    }
+
+    /// 获取到各对等节点的连接池状态
+    pub async fn peer_connections(&self) -> Vec<PeerConnectionState> {
+        self.network_factory.read().await.peer_connections().await
+    }
+
+    /// 获取共享的网络客户端工厂
+    pub(super) fn network_factory(&self) -> Arc<RwLock<ConfluxNetworkFactory>> {
+        self.network_factory.clone()
+    }
+
     /// 设置集群操作授权服务
     ///
     /// # Arguments
@@ -219,6 +324,15 @@ impl RaftNode {
         self.input_validator.clone()
     }
 
+    /// 获取集群事件总线
+    ///
+    /// # Returns
+    ///
+    /// 返回与本节点共享广播通道的事件总线
+    pub fn event_bus(&self) -> ClusterEventBus {
+        self.event_bus.clone()
+    }
+
     /// 启动节点并初始化Raft实例
     ///
     /// # Returns
@@ -244,6 +358,33 @@ impl RaftNode {
     /// # });
     /// ```
     pub async fn start(&mut self) -> Result<()> {
+        self.start_raft().await?;
+
+        // 如果需要，初始化单节点集群
+        if self.is_single_node_cluster().await {
+            self.initialize_cluster().await?;
+        }
+
+        info!("Raft node {} started successfully", self.config.node_id);
+        Ok(())
+    }
+
+    /// 启动节点但不初始化集群，等待领导者将其作为学习者加入已有集群
+    ///
+    /// # Returns
+    ///
+    /// 如果Raft实例创建成功返回Ok(())，否则返回错误
+    pub async fn start_as_learner(&mut self) -> Result<()> {
+        self.start_raft().await?;
+        info!(
+            "Raft node {} started, waiting to join an existing cluster",
+            self.config.node_id
+        );
+        Ok(())
+    }
+
+    /// 创建Raft实例
+    async fn start_raft(&mut self) -> Result<()> {
         info!("Starting Raft node {}", self.config.node_id);
 
         // openraft 0.9 正确初始化方式：直接用 Arc<Store> 作为 storage
@@ -251,17 +392,32 @@ impl RaftNode {
             let factory = self.network_factory.read().await;
             factory.clone()
         };
+        if let Some(handle) = self.peer_heartbeat_handle.replace(
+            network_factory.spawn_peer_heartbeat(self.config.node_id, PEER_HEARTBEAT_INTERVAL),
+        ) {
+            handle.abort();
+        }
 
         let mut raft_config = self.config.raft_config.clone();
         raft_config.heartbeat_interval = self.config.heartbeat_interval;
         raft_config.election_timeout_min = self.config.election_timeout_min;
         raft_config.election_timeout_max = self.config.election_timeout_max;
+        raft_config.snapshot_max_chunk_size = self.config.snapshot_stream.chunk_size_bytes as u64;
+        // 按已应用日志数自动生成快照并清理已快照的日志，避免日志无限增长
+        raft_config.snapshot_policy = SnapshotPolicy::LogsSinceLast(self.snapshot_threshold);
+        raft_config.max_in_snapshot_log_to_keep = self.max_applied_log_to_keep;
+        // 启用预投票时由预投票监控任务决定何时发起选举
+        if self.config.pre_vote_enabled {
+            raft_config.enable_elect = false;
+        }
 
         // openraft 0.9 storage v2 不再使用 Adaptor
         // 直接使用 Store 作为 RaftLogStorage 和创建 ConfluxStateMachineWrapper
         let log_storage = self.store.clone();
-        let state_machine =
-            crate::raft::state_machine::ConfluxStateMachineWrapper::new(self.store.clone());
+        let state_machine = ConfluxStateMachineWrapper::new(self.store.clone())
+            .with_stream_config(self.config.snapshot_stream.clone())
+            .with_metrics(self.metrics_collector.clone());
+        let applied_state = state_machine.clone();
 
         // openraft 0.9 Raft::new 需要5个参数：node_id, config, network_factory, log_storage, state_machine
         match Raft::new(
@@ -274,7 +430,46 @@ impl RaftNode {
         .await
         {
             Ok(raft) => {
+                super::priority_ops::spawn_priority_monitor(
+                    self.config.node_id,
+                    raft.metrics(),
+                    self.network_factory.clone(),
+                );
+                super::event_ops::spawn_leader_monitor(
+                    self.config.node_id,
+                    raft.metrics(),
+                    self.event_bus.clone(),
+                );
+                super::event_ops::spawn_membership_monitor(
+                    self.config.node_id,
+                    raft.metrics(),
+                    self.event_bus.clone(),
+                );
+                super::lease_ops::spawn_lease_monitor(
+                    self.config.node_id,
+                    raft.metrics(),
+                    self.leader_lease.clone(),
+                );
+                if self.config.pre_vote_enabled {
+                    self.pre_vote_handle = Some(super::pre_vote_ops::spawn_pre_vote_monitor(
+                        self.config.node_id,
+                        raft.clone(),
+                        self.store.clone(),
+                        self.network_factory.clone(),
+                        self.metrics_collector.clone(),
+                    ));
+                }
+                if let Some(handle) = self.housekeeping_handle.replace(
+                    super::housekeeping_ops::spawn_housekeeping_monitor(
+                        self.config.node_id,
+                        raft.clone(),
+                        self.store.clone(),
+                    ),
+                ) {
+                    handle.abort();
+                }
                 self.raft = Some(raft);
+                self.state_machine = Some(applied_state);
                 info!(
                     "Raft instance initialized successfully for node {}",
                     self.config.node_id
@@ -282,19 +477,10 @@ impl RaftNode {
             }
             Err(e) => {
                 error!("Failed to initialize Raft instance: {}", e);
-                return Err(crate::error::ConfluxError::raft(format!(
-                    "Raft initialization failed: {}",
-                    e
-                )));
+                return Err(ConfluxError::from(e).with_context("Raft initialization failed"));
             }
         }
 
-        // 如果需要，初始化单节点集群
-        if self.is_single_node_cluster().await {
-            self.initialize_cluster().await?;
-        }
-
-        info!("Raft node {} started successfully", self.config.node_id);
         Ok(())
     }
 
@@ -307,7 +493,14 @@ impl RaftNode {
         self.raft.as_ref()
     }
 
-    /// 通过Raft共识提交客户端写请求（带资源限制）
+    /// 获取Raft实例使用的状态机，用于检查已应用的状态
+    ///
+    /// Raft未启动时返回None
+    pub fn state_machine(&self) -> Option<&ConfluxStateMachineWrapper> {
+        self.state_machine.as_ref()
+    }
+
+    /// 通过Raft共识提交节点内部的写请求（带资源限制，不做客户端速率限制）
     ///
     /// # Arguments
     ///
@@ -321,6 +514,32 @@ impl RaftNode {
     ///
     /// 如果资源限制检查失败、Raft未初始化或写操作失败，返回错误
     pub async fn client_write(&self, request: ClientRequest) -> Result<ClientWriteResponse> {
+        self.client_write_as(request, None).await
+    }
+
+    /// 以指定客户端身份通过Raft共识提交写请求（带资源限制）
+    ///
+    /// 每个客户端有独立的速率限制窗口，未认证的请求应使用 `ANONYMOUS_CLIENT_ID` 共享同一窗口。
+    /// 超过 `ResourceLimits::request_timeout_ms` 仍未提交时返回超时错误并释放资源许可，
+    /// 但已追加到日志的写入之后仍可能被提交
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - 客户端请求
+    /// * `client_id` - 用于速率限制的客户端ID，为None时不做速率限制
+    ///
+    /// # Returns
+    ///
+    /// 返回客户端写响应
+    ///
+    /// # Errors
+    ///
+    /// 如果资源限制检查失败（包括超出客户端速率限制）、Raft未初始化、写操作超时或失败，返回错误
+    pub async fn client_write_as(
+        &self,
+        request: ClientRequest,
+        client_id: Option<&str>,
+    ) -> Result<ClientWriteResponse> {
         let start_time = std::time::Instant::now();
 
         info!(
@@ -328,35 +547,52 @@ impl RaftNode {
             self.config.node_id
         );
 
-        // 计算请求大小（简化版 - 实际实现中会序列化）
-        let request_size = std::mem::size_of_val(&request) + request.command.estimate_size();
+        // 按序列化后的实际负载计算请求大小
+        let request_size = request.payload_size();
 
         // 首先检查资源限制
         let _permit = self
             .resource_limiter
-            .check_request_allowed(request_size, None) // TODO: 可用时添加客户端ID
+            .check_request_allowed(request_size, client_id)
             .await?;
 
         let result = if let Some(ref raft) = self.raft {
             // 始终通过Raft共识路由 - 无回退
-            match raft.client_write(request).await {
-                Ok(raft_response) => {
-                    // raft_response.data 包含我们的 ClientWriteResponse
-                    Ok(raft_response.data)
+            // 共识阻塞时不让调用方无限等待，超时使用限制器中可热更新的当前值
+            let timeout_ms = self.resource_limiter.get_limits().request_timeout_ms;
+            match tokio::time::timeout(Duration::from_millis(timeout_ms), raft.client_write(request))
+                .await
+            {
+                Err(_) => {
+                    warn!(
+                        "Raft client write on node {} did not complete within {}ms",
+                        self.config.node_id, timeout_ms
+                    );
+                    Err(RaftError::Timeout { timeout_ms }.into())
                 }
-                Err(e) => {
+                Ok(Ok(raft_response)) => {
+                    // raft_response.data 包含我们的 ClientWriteResponse，补充提交时的日志索引
+                    Ok(ClientWriteResponse {
+                        log_index: Some(raft_response.log_id.index),
+                        ..raft_response.data
+                    })
+                }
+                Ok(Err(e)) => {
                     error!("Raft client write failed: {}", e);
-                    Err(crate::error::ConfluxError::raft(format!(
-                        "Raft write failed: {}",
-                        e
-                    )))
+                    // 需要转发给领导者时返回结构化错误，调用方可据此重试或重定向
+                    match e.forward_to_leader::<Node>() {
+                        Some(forward) => Err(RaftError::NotLeader {
+                            node_id: self.config.node_id,
+                            leader: forward.leader_id,
+                        }
+                        .into()),
+                        None => Err(ConfluxError::from(e).with_context("Raft write failed")),
+                    }
                 }
             }
         } else {
             // 如果Raft未初始化则返回错误而不是回退
-            Err(crate::error::ConfluxError::raft(
-                "Raft not initialized - cannot process write requests",
-            ))
+            Err(RaftError::NotInitialized.into())
         };
 
         // 记录请求指标
@@ -369,26 +605,116 @@ impl RaftNode {
         result
     }
 
-    /// 停止节点（占位符实现）
+    /// 停止节点
+    ///
+    /// 如果当前节点是领导者，先尝试将领导权移交给其他节点；
+    /// 移交失败不会阻止节点停止
     ///
     /// # Returns
     ///
     /// 如果停止成功返回Ok(())，否则返回错误
     pub async fn stop(&self) -> Result<()> {
         info!("Stopping Raft node {}", self.config.node_id);
+
+        if self.get_leader().await == Some(self.config.node_id) {
+            if let Err(e) = self.transfer_leadership(None).await {
+                warn!(
+                    "Failed to transfer leadership before stopping node {}: {}",
+                    self.config.node_id, e
+                );
+            }
+        }
+
         debug!("Raft node {} stopped successfully", self.config.node_id);
         Ok(())
     }
 
     /// 获取当前集群成员
     ///
+    /// Raft实例已初始化时返回其成员配置中的投票成员，否则返回本地记录的成员
+    ///
     /// # Returns
     ///
     /// 返回当前集群成员的集合
     pub async fn get_members(&self) -> BTreeSet<NodeId> {
+        if let Some(raft) = &self.raft {
+            let voters: BTreeSet<NodeId> = raft
+                .metrics()
+                .borrow()
+                .membership_config
+                .membership()
+                .voter_ids()
+                .collect();
+            if !voters.is_empty() {
+                return voters;
+            }
+        }
         self.members.read().await.clone()
     }
 
+    /// 获取当前集群成员及其地址
+    ///
+    /// 包含投票成员和学习者，地址取自Raft成员配置
+    ///
+    /// # Returns
+    ///
+    /// 返回`(节点ID, 地址)`列表
+    pub async fn get_members_with_addresses(&self) -> Vec<(NodeId, String)> {
+        self.get_member_details()
+            .await
+            .into_iter()
+            .map(|member| (member.node_id, member.address))
+            .collect()
+    }
+
+    /// 获取当前集群成员的地址和角色
+    ///
+    /// Raft实例已初始化时返回成员配置中的所有节点（投票成员和学习者），
+    /// 否则返回本地记录的成员，地址取自节点配置和网络地址表
+    ///
+    /// # Returns
+    ///
+    /// 返回按节点ID排序的成员信息列表
+    pub async fn get_member_details(&self) -> Vec<MemberInfo> {
+        if let Some(raft) = &self.raft {
+            let membership = raft.metrics().borrow().membership_config.membership().clone();
+            let voters: BTreeSet<NodeId> = membership.voter_ids().collect();
+            if !voters.is_empty() {
+                return membership
+                    .nodes()
+                    .map(|(node_id, node)| MemberInfo {
+                        node_id: *node_id,
+                        address: node.addr.clone(),
+                        role: if voters.contains(node_id) {
+                            MemberRole::Voter
+                        } else {
+                            MemberRole::Learner
+                        },
+                    })
+                    .collect();
+            }
+        }
+
+        let members = self.members.read().await.clone();
+        let mut details = Vec::with_capacity(members.len());
+        for node_id in members {
+            let address = if node_id == self.config.node_id {
+                self.config.address.clone()
+            } else {
+                self.network_config()
+                    .get_node_address(node_id)
+                    .await
+                    .unwrap_or_default()
+            };
+            details.push(MemberInfo {
+                node_id,
+                address,
+                role: MemberRole::Voter,
+            });
+        }
+        details
+    }
+
     /// 获取资源使用统计信息
     ///
     /// # Returns
@@ -422,6 +748,32 @@ impl RaftNode {
         ))
     }
 
+    /// 等待本地状态机应用到指定日志索引
+    ///
+    /// 订阅Raft指标，直到 `last_applied >= index` 或超时，不轮询
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - 需要应用到的日志索引
+    /// * `timeout` - 等待超时时间
+    ///
+    /// # Errors
+    ///
+    /// 如果Raft未初始化、超时或Raft已关闭，返回错误
+    pub async fn wait_for_applied(&self, index: u64, timeout: Duration) -> Result<()> {
+        let raft = self.raft.as_ref().ok_or(RaftError::NotInitialized)?;
+        raft.wait(Some(timeout))
+            .applied_index_at_least(Some(index), "wait_for_applied")
+            .await
+            .map(|_| ())
+            .map_err(|e| {
+                ConfluxError::raft(format!(
+                    "Timeout waiting for node {} to apply index {}: {}",
+                    self.config.node_id, index, e
+                ))
+            })
+    }
+
     /// 检查这是否是单节点集群
     async fn is_single_node_cluster(&self) -> bool {
         let members = self.members.read().await;
@@ -490,17 +842,28 @@ impl RaftNode {
                 )
                 .await;
 
+            let last_applied = raft_metrics.last_applied.map(|id| id.index).unwrap_or(0);
+            let commit_index = raft_metrics
+                .replication
+                .as_ref()
+                .map(|replication| {
+                    quorum_matched_index(raft_metrics.membership_config.membership(), replication)
+                })
+                .unwrap_or(0)
+                .max(last_applied);
+
             Ok(RaftMetrics {
                 node_id: self.config.node_id,
                 current_term: raft_metrics.current_term,
                 last_log_index: raft_metrics.last_log_index.unwrap_or(0),
-                last_applied: raft_metrics.last_applied.map(|id| id.index).unwrap_or(0),
+                last_applied,
+                commit_index,
                 leader_id: raft_metrics.current_leader,
                 membership,
                 is_leader: self.is_leader().await,
             })
         } else {
-            Err(crate::error::ConfluxError::raft("Raft not initialized"))
+            Err(RaftError::NotInitialized.into())
         }
     }
 
@@ -513,6 +876,17 @@ impl RaftNode {
         Ok(self.metrics_collector.get_node_health().await)
     }
 
+    /// 状态机管理器任务是否仍在运行
+    ///
+    /// # Returns
+    ///
+    /// 任务已退出（例如发生panic）时返回false
+    pub fn is_state_machine_running(&self) -> bool {
+        self.state_machine_handle
+            .as_ref()
+            .is_some_and(|handle| !handle.is_finished())
+    }
+
     /// 获取当前超时配置
     ///
     /// # Returns
@@ -578,12 +952,14 @@ impl RaftNode {
                 self.config.node_id
             );
 
-            let mut members = BTreeSet::new();
-            members.insert(self.config.node_id);
+            // 本节点的地址和选举优先级随成员配置一起持久化
+            let node = Node::new(self.config.address.clone())
+                .with_election_priority(self.config.election_priority);
+            let members = BTreeMap::from([(self.config.node_id, node)]);
 
-            raft.initialize(members).await.map_err(|e| {
-                crate::error::ConfluxError::raft(format!("Failed to initialize cluster: {}", e))
-            })?;
+            raft.initialize(members)
+                .await
+                .with_context("Failed to initialize cluster")?;
 
             info!("Single-node cluster initialized successfully");
         }
@@ -591,6 +967,58 @@ impl RaftNode {
     }
 }
 
+impl Drop for RaftNode {
+    fn drop(&mut self) {
+        // 监控任务持有Raft实例，需要随节点一起结束
+        if let Some(handle) = self.pre_vote_handle.take() {
+            handle.abort();
+        }
+        if let Some(handle) = self.housekeeping_handle.take() {
+            handle.abort();
+        }
+        if let Some(handle) = self.peer_heartbeat_handle.take() {
+            handle.abort();
+        }
+        if let Some(handle) = self.influx_flush_handle.take() {
+            handle.abort();
+        }
+    }
+}
+
+/// 计算多数派已复制的最高日志索引（领导者的提交索引）
+///
+/// openraft 0.9 的指标不包含提交索引，领导者根据各投票节点的复制进度计算；
+/// 联合成员配置下取各配置多数派索引的最小值
+///
+/// # Arguments
+///
+/// * `membership` - 当前成员配置
+/// * `replication` - 各节点已匹配的日志ID
+fn quorum_matched_index(
+    membership: &openraft::Membership<NodeId, Node>,
+    replication: &BTreeMap<NodeId, Option<openraft::LogId<NodeId>>>,
+) -> u64 {
+    membership
+        .get_joint_config()
+        .iter()
+        .map(|voters| {
+            let mut matched: Vec<u64> = voters
+                .iter()
+                .map(|id| {
+                    replication
+                        .get(id)
+                        .copied()
+                        .flatten()
+                        .map_or(0, |log_id| log_id.index)
+                })
+                .collect();
+            matched.sort_unstable_by(|a, b| b.cmp(a));
+            matched.get(voters.len() / 2).copied().unwrap_or(0)
+        })
+        .min()
+        .unwrap_or(0)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -602,10 +1030,7 @@ mod tests {
         AppConfig {
             storage: StorageConfig {
                 data_dir: temp_dir.path().to_string_lossy().to_string(),
-                max_open_files: 1000,
-                cache_size_mb: 256,
-                write_buffer_size_mb: 64,
-                max_write_buffer_number: 3,
+                ..Default::default()
             },
             ..Default::default()
         }
@@ -623,6 +1048,32 @@ mod tests {
         assert!(node.get_raft().is_none()); // Raft未启动
     }
 
+    #[tokio::test]
+    async fn test_raft_node_rejects_invalid_config_before_io() {
+        let app_config = create_test_app_config();
+        let invalid_configs = [
+            NodeConfig {
+                node_id: 0,
+                ..Default::default()
+            },
+            NodeConfig {
+                address: "localhost".to_string(),
+                ..Default::default()
+            },
+            NodeConfig {
+                heartbeat_interval: 300,
+                ..Default::default()
+            },
+        ];
+
+        for config in invalid_configs {
+            let err = RaftNode::new(config, &app_config).await.err().unwrap();
+            assert!(matches!(err, ConfluxError::Validation(_)), "{}", err);
+        }
+        // The store was never opened
+        assert!(!std::path::Path::new(&app_config.storage.data_dir).exists());
+    }
+
     #[tokio::test]
     async fn test_raft_node_start() {
         let config = NodeConfig::default();
@@ -636,6 +1087,53 @@ mod tests {
         assert!(node.get_raft().is_some()); // Raft已启动
     }
 
+    #[tokio::test]
+    async fn test_wait_for_applied() {
+        let temp_dir = TempDir::new().unwrap();
+        let app_config = AppConfig {
+            storage: StorageConfig {
+                data_dir: temp_dir.path().to_string_lossy().to_string(),
+                ..create_test_app_config().storage
+            },
+            ..Default::default()
+        };
+        let mut node = RaftNode::new(NodeConfig::default(), &app_config).await.unwrap();
+        assert!(node.wait_for_applied(1, Duration::from_millis(10)).await.is_err());
+
+        node.start().await.unwrap();
+        node.wait_for_leadership(Duration::from_secs(5)).await.unwrap();
+        let response = node
+            .client_write(ClientRequest {
+                command: crate::raft::types::RaftCommand::CreateConfig {
+                    namespace: ConfigNamespace {
+                        tenant: "tenant".to_string(),
+                        app: "app".to_string(),
+                        env: "test".to_string(),
+                    },
+                    name: "applied.json".to_string(),
+                    content: b"{}".to_vec(),
+                    format: ConfigFormat::Json,
+                    schema: None,
+                    creator_id: 1,
+                    description: "wait for applied".to_string(),
+                },
+                idempotency_key: None,
+            })
+            .await
+            .unwrap();
+        let index = response.log_index.unwrap();
+
+        node.wait_for_applied(index, Duration::from_secs(5)).await.unwrap();
+        assert!(node.get_metrics().await.unwrap().last_applied >= index);
+
+        // 尚未写入的索引在超时后返回错误
+        let err = node
+            .wait_for_applied(index + 100, Duration::from_millis(100))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("Timeout waiting for node 1"), "{}", err);
+    }
+
     #[tokio::test]
     async fn test_resource_stats() {
         let config = NodeConfig::default();
@@ -647,4 +1145,129 @@ mod tests {
         assert_eq!(stats.total_requests, 0);
         assert_eq!(stats.rejected_requests, 0);
     }
+
+    #[tokio::test]
+    async fn test_client_write_rejects_oversized_payload() {
+        let config = NodeConfig::default();
+        assert_eq!(config.resource_limits.max_request_size, 1024 * 1024);
+        let app_config = create_test_app_config();
+        let node = RaftNode::new(config, &app_config).await.unwrap();
+
+        let request = ClientRequest {
+            command: crate::raft::types::RaftCommand::CreateVersion {
+                config_id: 1,
+                content: vec![b'x'; 2 * 1024 * 1024],
+                format: None,
+                creator_id: 1,
+                description: "oversized".to_string(),
+            },
+            idempotency_key: None,
+        };
+        let err = node.client_write(request).await.unwrap_err();
+        assert!(err.to_string().contains("exceeds limit"));
+        assert_eq!(node.get_resource_stats().rejected_requests, 1);
+    }
+
+    #[tokio::test]
+    async fn test_client_write_times_out_when_consensus_stalls() {
+        let config = NodeConfig {
+            resource_limits: crate::raft::node::ResourceLimits {
+                request_timeout_ms: 200,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let app_config = create_test_app_config();
+        let mut node = RaftNode::new(config, &app_config).await.unwrap();
+        node.start().await.unwrap();
+        node.wait_for_leadership(Duration::from_secs(5)).await.unwrap();
+
+        // 持有日志写锁使新条目无法追加，写入因此无法提交
+        let store = node.store();
+        let stall = store.logs.write().await;
+
+        let request = ClientRequest {
+            command: crate::raft::types::RaftCommand::CreateVersion {
+                config_id: 1,
+                content: vec![b'x'; 1024],
+                format: None,
+                creator_id: 1,
+                description: "stalled".to_string(),
+            },
+            idempotency_key: None,
+        };
+        let start = std::time::Instant::now();
+        let err = node.client_write(request).await.unwrap_err();
+        assert!(start.elapsed() < Duration::from_secs(2), "{:?}", start.elapsed());
+        assert!(
+            matches!(err, ConfluxError::Raft(RaftError::Timeout { timeout_ms: 200 })),
+            "{}",
+            err
+        );
+
+        // 超时后资源许可被释放
+        let stats = node.get_resource_stats();
+        assert_eq!(stats.current_memory_usage, 0);
+        assert_eq!(stats.available_permits, stats.max_concurrent_requests);
+        drop(stall);
+    }
+
+    #[tokio::test]
+    async fn test_state_machine_inspection() {
+        let app_config = create_test_app_config();
+        let mut node = RaftNode::new(NodeConfig::default(), &app_config).await.unwrap();
+        assert!(node.state_machine().is_none());
+
+        node.start().await.unwrap();
+        node.wait_for_leadership(Duration::from_secs(5)).await.unwrap();
+
+        let mut last_index = 0;
+        for i in 0..3 {
+            let response = node
+                .client_write(ClientRequest {
+                    command: crate::raft::types::RaftCommand::CreateConfig {
+                        namespace: ConfigNamespace {
+                            tenant: "tenant".to_string(),
+                            app: "app".to_string(),
+                            env: "test".to_string(),
+                        },
+                        name: format!("inspect-{}.json", i),
+                        content: b"{}".to_vec(),
+                        format: ConfigFormat::Json,
+                        schema: None,
+                        creator_id: 1,
+                        description: "inspect".to_string(),
+                    },
+                    idempotency_key: None,
+                })
+                .await
+                .unwrap();
+            last_index = response.log_index.unwrap();
+        }
+        node.wait_for_applied(last_index, Duration::from_secs(5)).await.unwrap();
+
+        let state_machine = node.state_machine().unwrap();
+        let last_applied = node.get_metrics().await.unwrap().last_applied.unwrap();
+        assert_eq!(state_machine.get_applied_index().await, last_applied.index);
+        assert_eq!(state_machine.get_applied_index().await, last_index);
+        assert_eq!(state_machine.get_last_applied_term().await, last_applied.leader_id.term);
+
+        let summary = state_machine.dump_state_summary().await;
+        assert_eq!(summary.config_count, 3);
+        assert_eq!(summary.version_count, 3);
+        assert_eq!(summary.last_applied, last_index);
+
+        // 从第一条写入开始重放只交付业务命令
+        let mut names = Vec::new();
+        let delivered = state_machine
+            .replay_since(last_index - 2, |command| {
+                if let crate::raft::types::RaftCommand::CreateConfig { name, .. } = command {
+                    names.push(name);
+                }
+            })
+            .await
+            .unwrap();
+        assert_eq!(delivered, 3);
+        assert_eq!(names, ["inspect-0.json", "inspect-1.json", "inspect-2.json"]);
+    }
 }
@@ -0,0 +1,231 @@
+//! 节点下线模块
+//!
+//! 提供安全移除集群节点的流程：校验法定人数、必要时移交领导权、
+//! 等待待复制日志排空后再通过成员变更移除节点
+
+use super::core::RaftNode;
+use crate::error::{ConfluxError, RaftError, Result};
+use crate::raft::types::NodeId;
+use std::collections::BTreeSet;
+use std::time::Duration;
+use tracing::{info, warn};
+
+/// 等待下线节点日志排空的最长时间
+const DECOMMISSION_DRAIN_TIMEOUT: Duration = Duration::from_secs(5);
+
+impl RaftNode {
+    /// 安全地将节点从集群中下线
+    ///
+    /// 必须在领导者上调用。流程如下：
+    /// 1. 校验移除后剩余节点仍能构成原集群的多数派
+    /// 2. 如果目标是当前领导者，先将领导权移交给其他节点，
+    ///    再由新领导者提交成员变更
+    /// 3. 目标为跟随者时，等待其复制进度追上领导者的最新日志
+    /// 4. 通过Raft成员变更移除节点
+    ///
+    /// # Arguments
+    ///
+    /// * `node_id` - 要下线的节点ID
+    ///
+    /// # Returns
+    ///
+    /// 如果节点已从成员配置中移除返回Ok(())
+    ///
+    /// # Errors
+    ///
+    /// 如果当前节点不是领导者、目标不是投票成员、移除会破坏法定人数、
+    /// 领导权移交失败或成员变更失败，返回错误
+    pub async fn decommission_node(&self, node_id: NodeId) -> Result<()> {
+        let raft = self
+            .get_raft()
+            .ok_or(RaftError::NotInitialized)?;
+        let local_id = self.node_id();
+
+        let metrics = raft.metrics().borrow().clone();
+        if metrics.current_leader != Some(local_id) {
+            return Err(ConfluxError::raft("Only leader can decommission nodes"));
+        }
+
+        let voters: BTreeSet<NodeId> = metrics.membership_config.membership().voter_ids().collect();
+        if !voters.contains(&node_id) {
+            return Err(ConfluxError::raft(format!(
+                "Node {} is not a voting member of the cluster",
+                node_id
+            )));
+        }
+
+        let remaining = check_quorum_after_removal(&voters, node_id)?;
+        if remaining.len() % 2 == 0 {
+            warn!(
+                "Decommissioning node {} leaves an even number of voters ({}), fault tolerance is not improved",
+                node_id,
+                remaining.len()
+            );
+        }
+
+        info!("Decommissioning node {} (remaining voters: {:?})", node_id, remaining);
+
+        if node_id == local_id {
+            // 领导者不能在移除自己后继续协调，先移交领导权再由新领导者提交变更
+            self.transfer_leadership(None).await?;
+            let new_leader = self.wait_for_new_leader(local_id).await?;
+
+            self.network_client(new_leader)
+                .await
+                .change_membership(&remaining)
+                .await
+                .map_err(|e| {
+                    ConfluxError::raft(format!(
+                        "Failed to remove node {} via new leader {}: {}",
+                        node_id, new_leader, e
+                    ))
+                })?;
+        } else {
+            self.drain_replication(node_id).await;
+            self.change_membership(remaining).await?;
+        }
+
+        info!("Node {} decommissioned successfully", node_id);
+        Ok(())
+    }
+
+    /// 等待下线节点的复制进度追上领导者的最新日志
+    ///
+    /// 超时后仅记录警告，成员变更仍会继续
+    async fn drain_replication(&self, node_id: NodeId) {
+        let Some(raft) = self.get_raft() else {
+            return;
+        };
+
+        let start = std::time::Instant::now();
+        while start.elapsed() < DECOMMISSION_DRAIN_TIMEOUT {
+            let metrics = raft.metrics().borrow().clone();
+            let matched = metrics
+                .replication
+                .as_ref()
+                .and_then(|replication| replication.get(&node_id).copied().flatten())
+                .map(|log_id| log_id.index);
+
+            if is_drained(matched, metrics.last_log_index) {
+                info!("Pending log entries drained on node {}", node_id);
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+
+        warn!(
+            "Timed out draining log entries on node {}, continuing with removal",
+            node_id
+        );
+    }
+
+    /// 等待除 `previous_leader` 外的节点成为领导者
+    async fn wait_for_new_leader(&self, previous_leader: NodeId) -> Result<NodeId> {
+        let start = std::time::Instant::now();
+        while start.elapsed() < DECOMMISSION_DRAIN_TIMEOUT {
+            match self.get_leader().await {
+                Some(leader) if leader != previous_leader => return Ok(leader),
+                _ => tokio::time::sleep(Duration::from_millis(50)).await,
+            }
+        }
+
+        Err(ConfluxError::raft("No new leader elected after leadership transfer"))
+    }
+}
+
+/// 校验移除节点后剩余投票成员仍构成原集群的多数派
+///
+/// 剩余节点数不少于原集群法定人数时，新旧配置的联合共识可以完成，
+/// 返回移除后的投票成员集合
+fn check_quorum_after_removal(voters: &BTreeSet<NodeId>, node_id: NodeId) -> Result<BTreeSet<NodeId>> {
+    let mut remaining = voters.clone();
+    remaining.remove(&node_id);
+
+    let quorum = voters.len() / 2 + 1;
+    if remaining.is_empty() || remaining.len() < quorum {
+        return Err(ConfluxError::raft("decommission would break quorum"));
+    }
+    Ok(remaining)
+}
+
+/// 判断节点的复制进度是否已追上领导者的最新日志
+fn is_drained(matched_index: Option<u64>, last_log_index: Option<u64>) -> bool {
+    match last_log_index {
+        None => true,
+        Some(last) => matched_index.is_some_and(|matched| matched >= last),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{AppConfig, StorageConfig};
+    use crate::raft::node::NodeConfig;
+    use tempfile::TempDir;
+
+    async fn create_node(temp_dir: &TempDir) -> RaftNode {
+        let app_config = AppConfig {
+            storage: StorageConfig {
+                data_dir: temp_dir.path().to_string_lossy().to_string(),
+                cache_size_mb: 8,
+                write_buffer_size_mb: 8,
+                max_write_buffer_number: 2,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        RaftNode::new(NodeConfig::default(), &app_config).await.unwrap()
+    }
+
+    #[test]
+    fn test_quorum_check_in_three_node_cluster() {
+        let voters = BTreeSet::from([1, 2, 3]);
+
+        // 下线跟随者
+        assert_eq!(check_quorum_after_removal(&voters, 3).unwrap(), BTreeSet::from([1, 2]));
+        // 下线领导者
+        assert_eq!(check_quorum_after_removal(&voters, 1).unwrap(), BTreeSet::from([2, 3]));
+    }
+
+    #[test]
+    fn test_quorum_check_rejects_small_clusters() {
+        for voters in [BTreeSet::from([1]), BTreeSet::from([1, 2])] {
+            let err = check_quorum_after_removal(&voters, 1).unwrap_err();
+            assert!(err.to_string().contains("decommission would break quorum"));
+        }
+
+        assert!(check_quorum_after_removal(&BTreeSet::from([1, 2, 3, 4, 5]), 5).is_ok());
+    }
+
+    #[test]
+    fn test_is_drained() {
+        assert!(is_drained(None, None));
+        assert!(is_drained(Some(10), Some(10)));
+        assert!(!is_drained(Some(9), Some(10)));
+        assert!(!is_drained(None, Some(1)));
+    }
+
+    #[tokio::test]
+    async fn test_decommission_requires_leader() {
+        let temp_dir = TempDir::new().unwrap();
+        let node = create_node(&temp_dir).await;
+        assert!(node.decommission_node(2).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_decommission_last_node_breaks_quorum() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut node = create_node(&temp_dir).await;
+        node.start().await.unwrap();
+        node.wait_for_leadership(Duration::from_secs(5)).await.unwrap();
+        let node_id = node.node_id();
+
+        let err = node.decommission_node(node_id).await.unwrap_err();
+        assert!(err.to_string().contains("decommission would break quorum"));
+        assert!(node.decommission_node(node_id + 100).await.is_err());
+
+        // 失败的下线请求不会改变成员和领导权
+        assert_eq!(node.get_members().await, BTreeSet::from([node_id]));
+        assert_eq!(node.get_leader().await, Some(node_id));
+    }
+}
@@ -0,0 +1,189 @@
+//! DNS节点发现模块
+//!
+//! 按 `NetworkConfig::peer_discovery` 定期解析对等节点并更新网络地址表。领导者还会把新发现的节点
+//! 加入集群，并移除连续多轮解析都不存在的节点；一次解析失败不会改变成员
+
+use super::core::RaftNode;
+use crate::error::{ConfluxError, Result};
+use crate::raft::discovery::{PeerResolver, DNS_DISCOVERY_INTERVAL};
+use crate::raft::network::PeerDiscovery;
+use crate::raft::types::NodeId;
+use openraft::ServerState;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::{info, warn};
+
+/// 节点连续多少轮解析不存在后被移出集群
+pub const DNS_STALE_ROUNDS: u32 = 3;
+
+/// 一轮节点发现造成的成员变更
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PeerChanges {
+    /// 加入集群的节点
+    pub added: Vec<NodeId>,
+    /// 移出集群的节点
+    pub removed: Vec<NodeId>,
+}
+
+impl PeerChanges {
+    /// 是否没有成员变更
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+}
+
+/// 将DNS解析结果同步到集群成员
+///
+/// 记录每个成员连续缺席的轮数，只有连续 [`DNS_STALE_ROUNDS`] 轮都没有被解析到的节点才会被移除
+pub struct PeerReconciler {
+    resolver: Arc<dyn PeerResolver>,
+    missed_rounds: HashMap<NodeId, u32>,
+}
+
+impl PeerReconciler {
+    /// 创建使用指定解析器的调和器
+    ///
+    /// # Arguments
+    ///
+    /// * `resolver` - DNS解析器，测试中可替换为模拟实现
+    pub fn new(resolver: Arc<dyn PeerResolver>) -> Self {
+        Self {
+            resolver,
+            missed_rounds: HashMap::new(),
+        }
+    }
+
+    /// 执行一轮节点发现
+    ///
+    /// 所有节点都会更新网络地址表；只有领导者会通过 `add_learner` 和 `add_node` 加入新节点、
+    /// 通过 `remove_node` 移除过期节点。静态配置的节点不做调和
+    ///
+    /// # Arguments
+    ///
+    /// * `node` - 本地Raft节点
+    ///
+    /// # Returns
+    ///
+    /// 返回本轮的成员变更，单个节点的加入或移除失败只记录警告
+    ///
+    /// # Errors
+    ///
+    /// 如果DNS解析失败，记录到 `NodeMetrics::dns_resolution_failures` 并返回错误
+    pub async fn reconcile(&mut self, node: &RaftNode) -> Result<PeerChanges> {
+        if matches!(node.network_config().peer_discovery, PeerDiscovery::Static(_)) {
+            return Ok(PeerChanges::default());
+        }
+
+        let factory = node.network_factory().read().await.clone();
+        let peers = match factory.refresh_peers(self.resolver.as_ref()).await {
+            Ok(peers) => peers,
+            Err(e) => {
+                node.metrics_collector().record_dns_failure().await;
+                return Err(e);
+            }
+        };
+
+        let mut changes = PeerChanges::default();
+        if !node.is_leader().await {
+            return Ok(changes);
+        }
+
+        let self_id = node.node_id();
+        let mut members = node.get_members().await;
+        let mut discovered: Vec<_> = peers.iter().collect();
+        discovered.sort();
+        for (&node_id, address) in discovered {
+            if node_id == self_id || members.contains(&node_id) {
+                continue;
+            }
+            let joined = membership_change(node_id, "add", async {
+                node.add_learner(node_id, address.clone()).await?;
+                node.add_node(node_id, address.clone()).await
+            })
+            .await;
+            if joined {
+                members.insert(node_id);
+                changes.added.push(node_id);
+            }
+        }
+
+        self.missed_rounds.retain(|node_id, _| !peers.contains_key(node_id));
+        for node_id in members {
+            if node_id == self_id || peers.contains_key(&node_id) {
+                continue;
+            }
+            let missed = self.missed_rounds.entry(node_id).or_default();
+            *missed += 1;
+            if *missed < DNS_STALE_ROUNDS {
+                continue;
+            }
+            if membership_change(node_id, "remove", node.remove_node(node_id)).await {
+                self.missed_rounds.remove(&node_id);
+                changes.removed.push(node_id);
+            }
+        }
+
+        Ok(changes)
+    }
+}
+
+/// 执行一次成员变更，超过一个发现周期视为失败，避免不可达的节点阻塞后续轮次
+async fn membership_change(
+    node_id: NodeId,
+    action: &str,
+    change: impl std::future::Future<Output = Result<()>>,
+) -> bool {
+    let result = tokio::time::timeout(DNS_DISCOVERY_INTERVAL, change)
+        .await
+        .unwrap_or_else(|_| Err(ConfluxError::raft("membership change timed out")));
+    match result {
+        Ok(()) => {
+            info!("Peer discovery: {} node {}", action, node_id);
+            true
+        }
+        Err(e) => {
+            warn!("Peer discovery failed to {} node {}: {}", action, node_id, e);
+            false
+        }
+    }
+}
+
+/// 启动DNS节点发现任务
+///
+/// 每隔 [`DNS_DISCOVERY_INTERVAL`] 执行一轮 [`PeerReconciler::reconcile`]，Raft实例停止后任务自动退出
+///
+/// # Arguments
+///
+/// * `node` - 共享的Raft节点
+/// * `resolver` - DNS解析器，生产环境使用 `SystemResolver`
+pub fn spawn_peer_discovery(
+    node: Arc<RwLock<RaftNode>>,
+    resolver: Arc<dyn PeerResolver>,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut reconciler = PeerReconciler::new(resolver);
+        let mut interval = tokio::time::interval(DNS_DISCOVERY_INTERVAL);
+        loop {
+            interval.tick().await;
+            let node = node.read().await;
+            let stopped = node
+                .get_raft()
+                .is_some_and(|raft| raft.metrics().borrow().state == ServerState::Shutdown);
+            if stopped {
+                break;
+            }
+            match reconciler.reconcile(&node).await {
+                Ok(changes) if !changes.is_empty() => {
+                    info!("Peer discovery changed membership: {:?}", changes)
+                }
+                Ok(_) => {}
+                Err(e) => warn!("Peer discovery on node {} failed: {}", node.node_id(), e),
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+#[path = "discovery_ops_tests.rs"]
+mod tests;
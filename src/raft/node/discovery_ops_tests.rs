@@ -0,0 +1,104 @@
+use super::*;
+use crate::raft::discovery::tests::MockResolver;
+use crate::raft::network::NetworkConfig;
+use crate::raft::network_server::serve_raft_rpc;
+use crate::raft::node::NodeConfig;
+use crate::raft::node::test_support::app_config;
+use std::collections::BTreeSet;
+use std::time::Duration;
+use tempfile::TempDir;
+use tokio::net::TcpListener;
+use tokio::task::JoinHandle;
+
+fn dns_discovery() -> PeerDiscovery {
+    PeerDiscovery::Dns {
+        hostname: "conflux".to_string(),
+        port: 9000,
+        node_id_suffix: Some("svc".to_string()),
+    }
+}
+
+/// 启动两个使用DNS发现的节点：节点1为单节点集群的领导者，节点2等待加入
+async fn start_nodes(temp_dirs: &[TempDir]) -> (Vec<RaftNode>, Vec<JoinHandle<()>>, Vec<String>) {
+    let mut nodes = Vec::new();
+    let mut servers = Vec::new();
+    let mut addresses = Vec::new();
+    for (node_id, temp_dir) in (1..=2).zip(temp_dirs) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+        let config = NodeConfig {
+            node_id,
+            address: address.clone(),
+            network_config: NetworkConfig::default().with_peer_discovery(dns_discovery()),
+            ..Default::default()
+        };
+        let mut node = RaftNode::new(config, &app_config(temp_dir)).await.unwrap();
+        if node_id == 1 {
+            node.start().await.unwrap();
+            node.wait_for_leadership(Duration::from_secs(5)).await.unwrap();
+        } else {
+            node.start_as_learner().await.unwrap();
+        }
+        servers.push(serve_raft_rpc(listener, node.get_raft().cloned().unwrap()));
+        nodes.push(node);
+        addresses.push(address);
+    }
+    (nodes, servers, addresses)
+}
+
+#[tokio::test]
+async fn test_leader_joins_discovered_nodes_and_removes_stale_ones() {
+    let temp_dirs: Vec<TempDir> = (0..2).map(|_| TempDir::new().unwrap()).collect();
+    let (nodes, _servers, addresses) = start_nodes(&temp_dirs).await;
+    let resolver = Arc::new(MockResolver::default());
+    resolver.set("conflux-1.svc", &[&addresses[0]]);
+    resolver.set("conflux-2.svc", &[&addresses[1]]);
+    let mut leader = PeerReconciler::new(resolver.clone());
+    let mut follower = PeerReconciler::new(resolver.clone());
+
+    let changes = leader.reconcile(&nodes[0]).await.unwrap();
+    assert_eq!(changes.added, vec![2]);
+    assert_eq!(nodes[0].get_members().await, BTreeSet::from([1, 2]));
+    assert_eq!(
+        nodes[0].network_config().get_node_address(2).await,
+        Some(addresses[1].clone())
+    );
+
+    // 跟随者只更新地址表，不改变成员
+    assert!(follower.reconcile(&nodes[1]).await.unwrap().is_empty());
+    assert_eq!(
+        nodes[1].network_config().get_node_address(1).await,
+        Some(addresses[0].clone())
+    );
+    assert!(leader.reconcile(&nodes[0]).await.unwrap().is_empty());
+
+    // 节点连续多轮解析不存在后才被移除
+    resolver.set("conflux-2.svc", &[]);
+    for _ in 1..DNS_STALE_ROUNDS {
+        assert!(leader.reconcile(&nodes[0]).await.unwrap().is_empty());
+    }
+    let changes = leader.reconcile(&nodes[0]).await.unwrap();
+    assert_eq!(changes.removed, vec![2]);
+    assert_eq!(nodes[0].get_members().await, BTreeSet::from([1]));
+}
+
+#[tokio::test]
+async fn test_dns_failures_are_counted_and_keep_members() {
+    let temp_dirs: Vec<TempDir> = (0..2).map(|_| TempDir::new().unwrap()).collect();
+    let (nodes, _servers, addresses) = start_nodes(&temp_dirs).await;
+    let resolver = Arc::new(MockResolver::default());
+    resolver.set("conflux-1.svc", &[&addresses[0]]);
+    resolver.set("conflux-2.svc", &[&addresses[1]]);
+    let mut reconciler = PeerReconciler::new(resolver.clone());
+    reconciler.reconcile(&nodes[0]).await.unwrap();
+
+    resolver.set("conflux-1.svc", &[]);
+    resolver.set("conflux-2.svc", &[]);
+    for _ in 0..DNS_STALE_ROUNDS {
+        assert!(reconciler.reconcile(&nodes[0]).await.is_err());
+    }
+    assert_eq!(nodes[0].get_members().await, BTreeSet::from([1, 2]));
+
+    let node_metrics = nodes[0].metrics_collector().get_metrics_report().await.node_metrics;
+    assert_eq!(node_metrics.dns_resolution_failures, DNS_STALE_ROUNDS as u64);
+}
@@ -0,0 +1,209 @@
+//! 集群事件模块
+//!
+//! 领导权变更、成员变更、成员配置变化和日志压缩等集群状态变化通过事件总线广播，
+//! 订阅者（例如SSE端点）可以以编程方式观察集群状态，而不必解析日志
+
+use super::core::RaftNode;
+use crate::raft::types::{MemberRole, Node, NodeId};
+use chrono::{DateTime, Utc};
+use openraft::RaftMetrics;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tokio::sync::{broadcast, watch};
+use tracing::debug;
+
+/// 事件总线缓冲的事件数量，订阅者落后超过该数量时会丢失最旧的事件
+pub const CLUSTER_EVENT_BUFFER: usize = 256;
+
+/// 集群事件类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ClusterEventKind {
+    /// 本节点观察到的领导者发生变化
+    LeaderChanged,
+    /// 节点通过成员变更加入集群
+    NodeAdded,
+    /// 节点通过成员变更离开集群
+    NodeRemoved,
+    /// 日志压缩完成
+    LogCompacted,
+    /// 本节点观察到的成员配置发生变化
+    MembershipChanged,
+}
+
+impl ClusterEventKind {
+    /// 事件类型名称，与序列化结果一致
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::LeaderChanged => "leader_changed",
+            Self::NodeAdded => "node_added",
+            Self::NodeRemoved => "node_removed",
+            Self::LogCompacted => "log_compacted",
+            Self::MembershipChanged => "membership_changed",
+        }
+    }
+}
+
+/// 集群事件
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ClusterEvent {
+    /// 事件类型
+    pub kind: ClusterEventKind,
+    /// 事件发生时间
+    pub timestamp: DateTime<Utc>,
+    /// 发布事件的节点ID
+    pub node_id: NodeId,
+    /// 事件详情，内容随事件类型不同
+    pub details: Value,
+}
+
+impl ClusterEvent {
+    /// 创建一个以当前时间为时间戳的事件
+    pub fn new(kind: ClusterEventKind, node_id: NodeId, details: Value) -> Self {
+        Self {
+            kind,
+            timestamp: Utc::now(),
+            node_id,
+            details,
+        }
+    }
+}
+
+/// 集群事件总线
+///
+/// 克隆后共享同一个广播通道；没有订阅者时发布的事件直接丢弃
+#[derive(Debug, Clone)]
+pub struct ClusterEventBus {
+    sender: broadcast::Sender<ClusterEvent>,
+}
+
+impl ClusterEventBus {
+    /// 创建指定缓冲容量的事件总线
+    pub fn new(capacity: usize) -> Self {
+        let (sender, _) = broadcast::channel(capacity);
+        Self { sender }
+    }
+
+    /// 发布事件
+    pub fn publish(&self, event: ClusterEvent) {
+        debug!("Publishing cluster event {:?}", event.kind);
+        // 没有订阅者时发送失败，属于正常情况
+        let _ = self.sender.send(event);
+    }
+
+    /// 订阅后续发布的事件
+    pub fn subscribe(&self) -> broadcast::Receiver<ClusterEvent> {
+        self.sender.subscribe()
+    }
+}
+
+impl Default for ClusterEventBus {
+    fn default() -> Self {
+        Self::new(CLUSTER_EVENT_BUFFER)
+    }
+}
+
+impl RaftNode {
+    /// 订阅本节点发布的集群事件
+    pub fn subscribe_events(&self) -> broadcast::Receiver<ClusterEvent> {
+        self.event_bus().subscribe()
+    }
+
+    /// 以本节点身份发布集群事件
+    pub(crate) fn publish_event(&self, kind: ClusterEventKind, details: Value) {
+        self.event_bus()
+            .publish(ClusterEvent::new(kind, self.node_id(), details));
+    }
+}
+
+/// 启动领导者变化监控任务
+///
+/// 监听Raft指标，本节点观察到新的领导者时发布`LeaderChanged`事件。
+/// 选举期间领导者短暂为空不算变化，因此一次领导权转移只产生一个事件。
+/// 指标通道关闭后任务自动退出
+pub(crate) fn spawn_leader_monitor(
+    node_id: NodeId,
+    mut metrics: watch::Receiver<RaftMetrics<NodeId, Node>>,
+    event_bus: ClusterEventBus,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut last_leader = metrics.borrow_and_update().current_leader;
+
+        while metrics.changed().await.is_ok() {
+            let (leader, term) = {
+                let metrics = metrics.borrow_and_update();
+                (metrics.current_leader, metrics.current_term)
+            };
+            let Some(leader) = leader else {
+                continue;
+            };
+            if last_leader == Some(leader) {
+                continue;
+            }
+
+            event_bus.publish(ClusterEvent::new(
+                ClusterEventKind::LeaderChanged,
+                node_id,
+                json!({
+                    "previous_leader": last_leader,
+                    "new_leader": leader,
+                    "term": term,
+                }),
+            ));
+            last_leader = Some(leader);
+        }
+        debug!("Leader monitor for node {} stopped", node_id);
+    })
+}
+
+/// 启动成员配置变化监控任务
+///
+/// 监听Raft指标，成员配置日志发生变化时发布`MembershipChanged`事件，
+/// 详情中包含变更后的投票成员和学习者（含地址）。指标通道关闭后任务自动退出
+pub(crate) fn spawn_membership_monitor(
+    node_id: NodeId,
+    mut metrics: watch::Receiver<RaftMetrics<NodeId, Node>>,
+    event_bus: ClusterEventBus,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut last_log_id = *metrics.borrow_and_update().membership_config.log_id();
+
+        while metrics.changed().await.is_ok() {
+            let membership_config = metrics.borrow_and_update().membership_config.clone();
+            let log_id = *membership_config.log_id();
+            if log_id == last_log_id {
+                continue;
+            }
+            last_log_id = log_id;
+
+            let membership = membership_config.membership();
+            let voters: Vec<NodeId> = membership.voter_ids().collect();
+            let nodes: Vec<Value> = membership
+                .nodes()
+                .map(|(id, node)| {
+                    let role = if voters.contains(id) {
+                        MemberRole::Voter
+                    } else {
+                        MemberRole::Learner
+                    };
+                    json!({ "node_id": id, "address": node.addr, "role": role })
+                })
+                .collect();
+
+            event_bus.publish(ClusterEvent::new(
+                ClusterEventKind::MembershipChanged,
+                node_id,
+                json!({
+                    "log_index": log_id.map(|log_id| log_id.index),
+                    "voters": voters,
+                    "nodes": nodes,
+                }),
+            ));
+        }
+        debug!("Membership monitor for node {} stopped", node_id);
+    })
+}
+
+#[cfg(test)]
+#[path = "event_ops_tests.rs"]
+mod tests;
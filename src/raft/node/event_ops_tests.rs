@@ -0,0 +1,188 @@
+use super::*;
+use crate::raft::network::NetworkConfig;
+use crate::raft::network_server::serve_raft_rpc;
+use crate::raft::node::NodeConfig;
+use crate::raft::node::test_support::app_config;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use tempfile::TempDir;
+use tokio::net::TcpListener;
+use tokio::sync::broadcast::error::TryRecvError;
+use tokio::task::JoinHandle;
+
+/// 启动3个节点并组成集群，节点1为初始领导者
+async fn start_cluster(temp_dirs: &[TempDir]) -> (Vec<RaftNode>, Vec<JoinHandle<()>>) {
+    let mut listeners = Vec::new();
+    let mut addresses = HashMap::new();
+    for node_id in 1..=3 {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        addresses.insert(node_id, listener.local_addr().unwrap().to_string());
+        listeners.push(listener);
+    }
+    let network_config = NetworkConfig::new(addresses.clone());
+
+    let mut nodes = Vec::new();
+    let mut servers = Vec::new();
+    for ((node_id, listener), temp_dir) in (1..=3).zip(listeners).zip(temp_dirs) {
+        let config = NodeConfig {
+            node_id,
+            address: addresses[&node_id].clone(),
+            network_config: network_config.clone(),
+            ..Default::default()
+        };
+        let mut node = RaftNode::new(config, &app_config(temp_dir)).await.unwrap();
+        if node_id == 1 {
+            node.start().await.unwrap();
+            node.wait_for_leadership(Duration::from_secs(5)).await.unwrap();
+        } else {
+            node.start_as_learner().await.unwrap();
+        }
+        servers.push(serve_raft_rpc(listener, node.get_raft().cloned().unwrap()));
+        nodes.push(node);
+    }
+
+    for node_id in 2..=3 {
+        nodes[0]
+            .add_learner(node_id, addresses[&node_id].clone())
+            .await
+            .unwrap();
+    }
+    nodes[0].promote_learners().await.unwrap();
+    (nodes, servers)
+}
+
+/// 等待所有节点认可指定的领导者
+async fn wait_for_leader(nodes: &[RaftNode], leader: NodeId, timeout: Duration) -> bool {
+    let start = Instant::now();
+    while start.elapsed() < timeout {
+        let mut agreed = true;
+        for node in nodes {
+            agreed &= node.get_leader().await == Some(leader);
+        }
+        if agreed {
+            return true;
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+    false
+}
+
+#[tokio::test]
+async fn test_event_bus_delivers_to_all_subscribers() {
+    let bus = ClusterEventBus::new(8);
+    // 没有订阅者时发布不会出错
+    bus.publish(ClusterEvent::new(ClusterEventKind::NodeAdded, 1, json!({})));
+
+    let mut first = bus.subscribe();
+    let mut second = bus.clone().subscribe();
+    bus.publish(ClusterEvent::new(
+        ClusterEventKind::NodeRemoved,
+        1,
+        json!({ "node_id": 2 }),
+    ));
+
+    for receiver in [&mut first, &mut second] {
+        let event = receiver.recv().await.unwrap();
+        assert_eq!(event.kind, ClusterEventKind::NodeRemoved);
+        assert_eq!(event.node_id, 1);
+        assert_eq!(event.details["node_id"], 2);
+        assert!(matches!(receiver.try_recv(), Err(TryRecvError::Empty)));
+    }
+}
+
+#[test]
+fn test_cluster_event_serialization() {
+    let event = ClusterEvent::new(ClusterEventKind::LeaderChanged, 3, json!({ "new_leader": 3 }));
+    let value = serde_json::to_value(&event).unwrap();
+    assert_eq!(value["kind"], ClusterEventKind::LeaderChanged.as_str());
+    assert_eq!(value["node_id"], 3);
+    assert_eq!(value["details"]["new_leader"], 3);
+}
+
+#[tokio::test]
+async fn test_log_compaction_publishes_event() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut node = RaftNode::new(NodeConfig::default(), &app_config(&temp_dir))
+        .await
+        .unwrap();
+    node.start().await.unwrap();
+    node.wait_for_leadership(Duration::from_secs(5)).await.unwrap();
+
+    let mut events = node.subscribe_events();
+    node.trigger_log_compaction().await.unwrap();
+
+    let event = events.recv().await.unwrap();
+    assert_eq!(event.kind, ClusterEventKind::LogCompacted);
+    assert_eq!(event.node_id, node.node_id());
+    assert!(event.details.get("snapshot_size_bytes").is_some());
+}
+
+#[tokio::test]
+async fn test_leadership_change_emits_single_event() {
+    let temp_dirs: Vec<TempDir> = (0..3).map(|_| TempDir::new().unwrap()).collect();
+    let (nodes, servers) = start_cluster(&temp_dirs).await;
+    assert!(wait_for_leader(&nodes, 1, Duration::from_secs(5)).await);
+
+    let mut events = nodes[0].subscribe_events();
+    nodes[0].transfer_leadership(Some(2)).await.unwrap();
+    assert!(wait_for_leader(&nodes, 2, Duration::from_secs(10)).await);
+
+    // 等待选举相关的指标更新全部完成后再统计事件
+    tokio::time::sleep(Duration::from_millis(500)).await;
+    let mut leader_changes = Vec::new();
+    while let Ok(event) = events.try_recv() {
+        if event.kind == ClusterEventKind::LeaderChanged {
+            leader_changes.push(event);
+        }
+    }
+
+    assert_eq!(leader_changes.len(), 1);
+    let event = &leader_changes[0];
+    assert_eq!(event.node_id, 1);
+    assert_eq!(event.details["previous_leader"], 1);
+    assert_eq!(event.details["new_leader"], 2);
+
+    for server in servers {
+        server.abort();
+    }
+}
+
+#[tokio::test]
+async fn test_membership_change_publishes_events() {
+    let temp_dirs: Vec<TempDir> = (0..3).map(|_| TempDir::new().unwrap()).collect();
+    let (nodes, servers) = start_cluster(&temp_dirs).await;
+    assert!(wait_for_leader(&nodes, 1, Duration::from_secs(5)).await);
+
+    // 成员列表包含各节点的真实地址和角色
+    let members = nodes[0].get_members_with_addresses().await;
+    assert_eq!(members.len(), 3);
+    for (node_id, address) in &members {
+        assert_eq!(address, nodes[*node_id as usize - 1].address());
+    }
+    assert!(nodes[0]
+        .get_member_details()
+        .await
+        .iter()
+        .all(|member| member.role == MemberRole::Voter));
+
+    let mut events = nodes[0].subscribe_events();
+    nodes[0].remove_node(3).await.unwrap();
+
+    let mut kinds = Vec::new();
+    let mut final_voters = None;
+    while let Ok(Ok(event)) = tokio::time::timeout(Duration::from_secs(2), events.recv()).await {
+        if event.kind == ClusterEventKind::MembershipChanged {
+            final_voters = Some(event.details["voters"].clone());
+        }
+        kinds.push(event.kind);
+        if kinds.contains(&ClusterEventKind::NodeRemoved) && final_voters == Some(json!([1, 2])) {
+            break;
+        }
+    }
+    assert!(kinds.contains(&ClusterEventKind::NodeRemoved));
+    assert_eq!(final_voters, Some(json!([1, 2])));
+
+    for server in servers {
+        server.abort();
+    }
+}
@@ -0,0 +1,186 @@
+//! 节点健康检查模块
+//!
+//! 按组件（Raft、存储、领导者）汇总节点健康状态，供健康检查端点使用
+
+use super::core::RaftNode;
+use crate::raft::metrics::{ComponentHealth, HealthReport, HealthStatus};
+use crate::raft::store::Store;
+use crate::raft::types::{Node, NodeId};
+
+impl RaftNode {
+    /// 生成按组件划分的健康报告
+    ///
+    /// Raft未启动、状态机任务退出或存储不可读时为不健康；没有领导者时为降级。
+    /// 未应用的日志条目数（领导者上还包括最慢投票节点落后提交索引的条目数）
+    /// 超过配置的阈值时，Raft组件降级或不健康
+    ///
+    /// # Returns
+    ///
+    /// 返回包含各组件状态和说明的健康报告
+    pub async fn health_report(&self) -> HealthReport {
+        let storage = storage_health(&self.store());
+
+        if self.get_raft().is_none() {
+            return HealthReport::new(
+                ComponentHealth::new(HealthStatus::Unhealthy, "Raft not started"),
+                storage,
+                no_leader(),
+            );
+        }
+
+        let metrics = match self.get_metrics().await {
+            Ok(metrics) => metrics,
+            Err(e) => {
+                return HealthReport::new(
+                    ComponentHealth::new(HealthStatus::Unhealthy, e.to_string()),
+                    storage,
+                    no_leader(),
+                );
+            }
+        };
+
+        let collector = self.metrics_collector();
+        // 跟随者知道领导者即说明仍在收到心跳
+        if metrics.leader_id.is_some() && !metrics.is_leader {
+            collector.record_heartbeat().await;
+        }
+
+        let commit_lag = match self.get_raft() {
+            Some(raft) if metrics.is_leader => {
+                slowest_voter_lag(&raft.metrics().borrow(), metrics.commit_index)
+            }
+            _ => 0,
+        };
+        collector.update_commit_lag(commit_lag).await;
+
+        let node_health = collector.get_node_health().await;
+        let raft = if self.is_state_machine_running() {
+            ComponentHealth::new(
+                node_health.status,
+                format!(
+                    "term {}, applied {}/{}, apply lag {}, commit lag {}, health score {:.0}",
+                    metrics.current_term,
+                    metrics.last_applied,
+                    metrics.last_log_index,
+                    node_health.lag.apply_lag,
+                    node_health.lag.commit_lag,
+                    node_health.score
+                ),
+            )
+        } else {
+            ComponentHealth::new(HealthStatus::Unhealthy, "state machine task stopped")
+        };
+
+        let leader = match metrics.leader_id {
+            Some(leader_id) if metrics.is_leader => ComponentHealth::new(
+                HealthStatus::Healthy,
+                format!("this node ({}) is the leader", leader_id),
+            ),
+            Some(leader_id) => {
+                ComponentHealth::new(HealthStatus::Healthy, format!("leader is node {}", leader_id))
+            }
+            None => no_leader(),
+        };
+
+        HealthReport::new(raft, storage, leader).with_lag(node_health.lag)
+    }
+}
+
+/// 领导者提交索引与最慢投票节点已复制索引之间的差距
+///
+/// 只在领导者上有复制进度，其他节点返回0
+fn slowest_voter_lag(raft_metrics: &openraft::RaftMetrics<NodeId, Node>, commit_index: u64) -> u64 {
+    let Some(ref replication) = raft_metrics.replication else {
+        return 0;
+    };
+    raft_metrics
+        .membership_config
+        .membership()
+        .voter_ids()
+        .filter(|id| *id != raft_metrics.id)
+        .map(|id| {
+            let matched = replication
+                .get(&id)
+                .copied()
+                .flatten()
+                .map_or(0, |log_id| log_id.index);
+            commit_index.saturating_sub(matched)
+        })
+        .max()
+        .unwrap_or(0)
+}
+
+/// 检查存储是否可读
+///
+/// # Arguments
+///
+/// * `store` - 存储实例
+pub(crate) fn storage_health(store: &Store) -> ComponentHealth {
+    match store.check_health() {
+        Ok(()) => ComponentHealth::new(HealthStatus::Healthy, "RocksDB reachable"),
+        Err(e) => ComponentHealth::new(HealthStatus::Unhealthy, e.to_string()),
+    }
+}
+
+/// 没有领导者时的降级状态
+pub(crate) fn no_leader() -> ComponentHealth {
+    ComponentHealth::new(HealthStatus::Degraded, "no leader elected")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::raft::node::NodeConfig;
+    use crate::raft::node::test_support::app_config;
+    use std::time::Duration;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_health_report_of_stopped_node_is_unhealthy() {
+        let temp_dir = TempDir::new().unwrap();
+        let node = RaftNode::new(NodeConfig::default(), &app_config(&temp_dir))
+            .await
+            .unwrap();
+
+        let report = node.health_report().await;
+        assert_eq!(report.status, HealthStatus::Unhealthy);
+        assert_eq!(report.raft.status, HealthStatus::Unhealthy);
+        assert!(report.lag.is_none());
+        assert_eq!(report.storage.status, HealthStatus::Healthy);
+        assert_eq!(report.leader.status, HealthStatus::Degraded);
+    }
+
+    #[tokio::test]
+    async fn test_health_report_of_leader_is_healthy() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut node = RaftNode::new(NodeConfig::default(), &app_config(&temp_dir))
+            .await
+            .unwrap();
+        node.start().await.unwrap();
+        node.wait_for_leadership(Duration::from_secs(5)).await.unwrap();
+
+        let report = node.health_report().await;
+        assert_eq!(report.status, HealthStatus::Healthy, "{:?}", report);
+        assert!(report.leader.detail.contains("is the leader"));
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["status"], "healthy");
+        assert_eq!(json["storage"]["status"], "healthy");
+        assert_eq!(json["lag"]["commit_lag"], 0);
+        assert!(report.raft.detail.contains("commit lag 0"), "{}", report.raft.detail);
+    }
+
+    #[tokio::test]
+    async fn test_health_report_without_leader_is_degraded() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut node = RaftNode::new(NodeConfig::default(), &app_config(&temp_dir))
+            .await
+            .unwrap();
+        node.start_as_learner().await.unwrap();
+
+        let report = node.health_report().await;
+        assert_eq!(report.leader.status, HealthStatus::Degraded);
+        assert_eq!(report.storage.status, HealthStatus::Healthy);
+        assert!(report.status >= HealthStatus::Degraded);
+    }
+}
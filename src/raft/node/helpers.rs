@@ -1,6 +1,9 @@
 //! Raft节点辅助函数模块
 //!
 //! 提供创建和配置Raft节点的便利函数
+//!
+//! 这些函数通过 [`NodeConfig::builder`] 构造配置但不做验证，
+//! 无效配置会在 `RaftNode::new` 中被拒绝
 
 use super::config::{NodeConfig, ResourceLimits};
 use crate::raft::{network::NetworkConfig, types::NodeId};
@@ -29,16 +32,9 @@ use openraft::Config as RaftConfig;
 /// assert_eq!(config.address, "127.0.0.1:8080");
 /// ```
 pub fn create_node_config(node_id: NodeId, address: String) -> NodeConfig {
-    NodeConfig {
-        node_id,
-        address,
-        raft_config: RaftConfig::default(),
-        network_config: NetworkConfig::default(),
-        heartbeat_interval: 150,
-        election_timeout_min: 300,
-        election_timeout_max: 600,
-        resource_limits: ResourceLimits::default(),
-    }
+    NodeConfig::builder(node_id, address)
+        .timeouts(150, 300, 600)
+        .build_unchecked()
 }
 
 /// 创建带有自定义超时配置的节点配置
@@ -79,16 +75,9 @@ pub fn create_node_config_with_timeouts(
     election_timeout_min: u64,
     election_timeout_max: u64,
 ) -> NodeConfig {
-    NodeConfig {
-        node_id,
-        address,
-        raft_config: RaftConfig::default(),
-        network_config: NetworkConfig::default(),
-        heartbeat_interval,
-        election_timeout_min,
-        election_timeout_max,
-        resource_limits: ResourceLimits::default(),
-    }
+    NodeConfig::builder(node_id, address)
+        .timeouts(heartbeat_interval, election_timeout_min, election_timeout_max)
+        .build_unchecked()
 }
 
 /// 创建带有自定义资源限制的节点配置
@@ -122,16 +111,10 @@ pub fn create_node_config_with_limits(
     address: String,
     resource_limits: ResourceLimits,
 ) -> NodeConfig {
-    NodeConfig {
-        node_id,
-        address,
-        raft_config: RaftConfig::default(),
-        network_config: NetworkConfig::default(),
-        heartbeat_interval: 150,
-        election_timeout_min: 300,
-        election_timeout_max: 600,
-        resource_limits,
-    }
+    NodeConfig::builder(node_id, address)
+        .timeouts(150, 300, 600)
+        .resource_limits(resource_limits)
+        .build_unchecked()
 }
 
 /// 创建完全自定义的节点配置
@@ -179,16 +162,12 @@ pub fn create_custom_node_config(
     election_timeout_max: u64,
     resource_limits: ResourceLimits,
 ) -> NodeConfig {
-    NodeConfig {
-        node_id,
-        address,
-        raft_config,
-        network_config,
-        heartbeat_interval,
-        election_timeout_min,
-        election_timeout_max,
-        resource_limits,
-    }
+    NodeConfig::builder(node_id, address)
+        .raft_config(raft_config)
+        .network_config(network_config)
+        .timeouts(heartbeat_interval, election_timeout_min, election_timeout_max)
+        .resource_limits(resource_limits)
+        .build_unchecked()
 }
 
 /// 创建开发环境的节点配置
@@ -222,16 +201,10 @@ pub fn create_dev_node_config(node_id: NodeId, address: String) -> NodeConfig {
     resource_limits.max_requests_per_second = 1000;
     resource_limits.max_concurrent_requests = 200;
     
-    NodeConfig {
-        node_id,
-        address,
-        raft_config: RaftConfig::default(),
-        network_config: NetworkConfig::default(),
-        heartbeat_interval: 50,  // 更短的心跳间隔
-        election_timeout_min: 100,  // 更短的选举超时
-        election_timeout_max: 200,
-        resource_limits,
-    }
+    NodeConfig::builder(node_id, address)
+        .timeouts(50, 100, 200) // 更短的心跳间隔和选举超时
+        .resource_limits(resource_limits)
+        .build_unchecked()
 }
 
 /// 创建生产环境的节点配置
@@ -267,16 +240,10 @@ pub fn create_prod_node_config(node_id: NodeId, address: String) -> NodeConfig {
     resource_limits.max_request_size = 512 * 1024; // 512KB
     resource_limits.request_timeout_ms = 10000; // 10秒
     
-    NodeConfig {
-        node_id,
-        address,
-        raft_config: RaftConfig::default(),
-        network_config: NetworkConfig::default(),
-        heartbeat_interval: 200,  // 更长的心跳间隔
-        election_timeout_min: 500,  // 更长的选举超时
-        election_timeout_max: 1000,
-        resource_limits,
-    }
+    NodeConfig::builder(node_id, address)
+        .timeouts(200, 500, 1000) // 更长的心跳间隔和选举超时
+        .resource_limits(resource_limits)
+        .build_unchecked()
 }
 
 /// 验证节点配置的网络连通性
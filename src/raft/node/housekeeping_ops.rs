@@ -0,0 +1,114 @@
+//! 领导者定时维护模块
+//!
+//! 定时发布的激活、过期软删除配置和幂等键的清理等工作依赖时间，不能由各副本按自己的时钟在本地执行。
+//! 领导者定时检查是否有到期的工作，有则以普通命令的形式通过Raft提交，
+//! 命令中带有领导者提交时的时间，所有副本应用同一条日志，得到相同的状态
+
+use crate::raft::store::{
+    Store, IDEMPOTENCY_GC_INTERVAL, SCHEDULED_RELEASE_POLL_INTERVAL, SOFT_DELETE_GC_INTERVAL,
+};
+use crate::raft::types::{ClientRequest, ConfluxRaft, NodeId, RaftCommand};
+use chrono::{DateTime, Utc};
+use openraft::ServerState;
+use std::sync::Arc;
+use tracing::{debug, info, warn};
+
+/// 领导者定时检查的维护工作
+#[derive(Debug, Clone, Copy)]
+enum HousekeepingJob {
+    /// 激活到期的定时发布
+    ScheduledReleases,
+    /// 清理可恢复窗口已过的软删除配置
+    ExpiredConfigs,
+    /// 清理保留期已过的幂等键
+    IdempotencyKeys,
+}
+
+/// 维护工作在 `now` 时需要提交的命令，没有到期的工作时返回None
+///
+/// # Arguments
+///
+/// * `store` - 本节点的存储
+/// * `job` - 要检查的维护工作
+/// * `now` - 领导者当前时间，写入命令供所有副本使用
+async fn due_command(
+    store: &Store,
+    job: HousekeepingJob,
+    now: DateTime<Utc>,
+) -> Option<RaftCommand> {
+    match job {
+        HousekeepingJob::ScheduledReleases => match store.has_due_scheduled_releases(now) {
+            Ok(true) => Some(RaftCommand::ActivateScheduledReleases { now }),
+            Ok(false) => None,
+            Err(e) => {
+                warn!("Failed to check scheduled releases: {}", e);
+                None
+            }
+        },
+        HousekeepingJob::ExpiredConfigs => store
+            .has_expired_configs(now)
+            .await
+            .then_some(RaftCommand::PurgeExpiredConfigs { now }),
+        HousekeepingJob::IdempotencyKeys => match store.has_expired_idempotency_keys(now) {
+            Ok(true) => Some(RaftCommand::EvictIdempotencyKeys { now }),
+            Ok(false) => None,
+            Err(e) => {
+                warn!("Failed to check idempotency keys: {}", e);
+                None
+            }
+        },
+    }
+}
+
+/// 启动领导者定时维护任务
+///
+/// 每项维护工作按各自的间隔检查（定时发布为 [`SCHEDULED_RELEASE_POLL_INTERVAL`]，
+/// 软删除清理为 [`SOFT_DELETE_GC_INTERVAL`]，幂等键清理为 [`IDEMPOTENCY_GC_INTERVAL`]），
+/// 只有领导者提交维护命令；
+/// 提交失败（例如领导权已转移）留到下一轮由当时的领导者重试。Raft实例停止后任务自动退出
+pub(crate) fn spawn_housekeeping_monitor(
+    node_id: NodeId,
+    raft: ConfluxRaft,
+    store: Arc<Store>,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut releases = tokio::time::interval(SCHEDULED_RELEASE_POLL_INTERVAL);
+        let mut expired_configs = tokio::time::interval(SOFT_DELETE_GC_INTERVAL);
+        let mut idempotency_keys = tokio::time::interval(IDEMPOTENCY_GC_INTERVAL);
+        loop {
+            let job = tokio::select! {
+                _ = releases.tick() => HousekeepingJob::ScheduledReleases,
+                _ = expired_configs.tick() => HousekeepingJob::ExpiredConfigs,
+                _ = idempotency_keys.tick() => HousekeepingJob::IdempotencyKeys,
+            };
+
+            let Ok(server_state) = raft.with_raft_state(|st| st.server_state).await else {
+                break;
+            };
+            match server_state {
+                ServerState::Leader => {}
+                ServerState::Shutdown => break,
+                ServerState::Follower | ServerState::Candidate | ServerState::Learner => continue,
+            }
+
+            let Some(command) = due_command(&store, job, Utc::now()).await else {
+                continue;
+            };
+            let request = ClientRequest {
+                command,
+                idempotency_key: None,
+            };
+            match raft.client_write(request).await {
+                Ok(response) => info!(
+                    "Node {} applied housekeeping command: {}",
+                    node_id, response.data.message
+                ),
+                Err(e) => warn!(
+                    "Node {} failed to propose {:?} housekeeping command: {}",
+                    node_id, job, e
+                ),
+            }
+        }
+        debug!("Housekeeping monitor for node {} stopped", node_id);
+    })
+}
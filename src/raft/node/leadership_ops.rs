@@ -0,0 +1,187 @@
+//! 领导权移交模块
+//!
+//! 提供在节点维护前主动让出领导权的功能
+
+use super::core::RaftNode;
+use crate::error::{ConfluxError, RaftError, Result};
+use crate::raft::types::NodeId;
+use openraft::LogId;
+use std::collections::BTreeMap;
+use std::time::Duration;
+use tracing::{debug, info};
+
+/// 等待领导权移交完成的最长时间
+const LEADERSHIP_TRANSFER_TIMEOUT: Duration = Duration::from_secs(5);
+
+impl RaftNode {
+    /// 将领导权移交给其他节点
+    ///
+    /// 在目标节点上触发选举，目标节点以更高的任期发起投票后当前领导者会自动退位
+    ///
+    /// # Arguments
+    ///
+    /// * `target_node_id` - 目标节点ID；为None时选择日志复制进度最高的跟随者
+    ///
+    /// # Returns
+    ///
+    /// 如果领导权在超时时间内转移到其他节点返回Ok(())
+    ///
+    /// # Errors
+    ///
+    /// 如果Raft未初始化、当前节点不是领导者、目标节点无效、
+    /// 选举触发失败或在超时时间内领导权未发生变化，返回错误
+    pub async fn transfer_leadership(&self, target_node_id: Option<NodeId>) -> Result<()> {
+        let raft = self
+            .get_raft()
+            .ok_or(RaftError::NotInitialized)?;
+        let node_id = self.node_id();
+
+        let metrics = raft.metrics().borrow().clone();
+        if metrics.current_leader != Some(node_id) {
+            return Err(RaftError::NotLeader {
+                node_id,
+                leader: metrics.current_leader,
+            }
+            .into());
+        }
+
+        let voters: Vec<NodeId> = metrics.membership_config.membership().voter_ids().collect();
+        let target = match target_node_id {
+            Some(target) if target == node_id => {
+                return Err(ConfluxError::raft("Cannot transfer leadership to self"));
+            }
+            Some(target) if !voters.contains(&target) => {
+                return Err(ConfluxError::raft(format!(
+                    "Node {} is not a voting member of the cluster",
+                    target
+                )));
+            }
+            Some(target) => target,
+            None => select_transfer_target(node_id, &voters, metrics.replication.as_ref())
+                .ok_or_else(|| {
+                    ConfluxError::raft("No follower available for leadership transfer")
+                })?,
+        };
+
+        info!("Transferring leadership from node {} to node {}", node_id, target);
+
+        self.network_client(target)
+            .await
+            .trigger_elect()
+            .await
+            .map_err(|e| {
+                ConfluxError::raft(format!("Failed to trigger election on node {}: {}", target, e))
+            })?;
+
+        let start = std::time::Instant::now();
+        while start.elapsed() < LEADERSHIP_TRANSFER_TIMEOUT {
+            if self.get_leader().await != Some(node_id) {
+                info!(
+                    "Leadership transferred away from node {} (new leader: {:?})",
+                    node_id,
+                    self.get_leader().await
+                );
+                return Ok(());
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+
+        debug!("Leadership transfer from node {} to {} timed out", node_id, target);
+        Err(ConfluxError::raft("leadership transfer timeout"))
+    }
+}
+
+/// 选择领导权移交的目标节点
+///
+/// 在除自身外的投票成员中选择已复制日志索引最高的节点，索引相同时选择ID较小的节点
+fn select_transfer_target(
+    node_id: NodeId,
+    voters: &[NodeId],
+    replication: Option<&BTreeMap<NodeId, Option<LogId<NodeId>>>>,
+) -> Option<NodeId> {
+    let matched_index = |id: &NodeId| {
+        replication
+            .and_then(|replication| replication.get(id).copied().flatten())
+            .map(|log_id| log_id.index)
+    };
+
+    voters
+        .iter()
+        .filter(|&&id| id != node_id)
+        .max_by_key(|&&id| (matched_index(&id), std::cmp::Reverse(id)))
+        .copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{AppConfig, StorageConfig};
+    use crate::raft::node::NodeConfig;
+    use openraft::CommittedLeaderId;
+    use tempfile::TempDir;
+
+    async fn create_node(temp_dir: &TempDir) -> RaftNode {
+        let app_config = AppConfig {
+            storage: StorageConfig {
+                data_dir: temp_dir.path().to_string_lossy().to_string(),
+                cache_size_mb: 8,
+                write_buffer_size_mb: 8,
+                max_write_buffer_number: 2,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        RaftNode::new(NodeConfig::default(), &app_config).await.unwrap()
+    }
+
+    fn log_id(index: u64) -> Option<LogId<NodeId>> {
+        Some(LogId::new(CommittedLeaderId::new(1, 1), index))
+    }
+
+    #[test]
+    fn test_select_transfer_target_prefers_most_up_to_date_follower() {
+        let replication = BTreeMap::from([(1, log_id(10)), (2, log_id(7)), (3, log_id(9))]);
+        assert_eq!(select_transfer_target(1, &[1, 2, 3], Some(&replication)), Some(3));
+
+        // 复制进度相同时选择ID较小的节点
+        let replication = BTreeMap::from([(2, log_id(5)), (3, log_id(5)), (4, None)]);
+        assert_eq!(select_transfer_target(1, &[1, 2, 3, 4], Some(&replication)), Some(2));
+
+        assert_eq!(select_transfer_target(1, &[1], None), None);
+        assert_eq!(select_transfer_target(1, &[1, 2], None), Some(2));
+    }
+
+    #[tokio::test]
+    async fn test_transfer_leadership_requires_leader() {
+        let temp_dir = TempDir::new().unwrap();
+        let node = create_node(&temp_dir).await;
+        assert!(node.transfer_leadership(None).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_transfer_leadership_rejects_invalid_targets() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut node = create_node(&temp_dir).await;
+        node.start().await.unwrap();
+        node.wait_for_leadership(Duration::from_secs(5)).await.unwrap();
+        let node_id = node.node_id();
+
+        assert!(node.transfer_leadership(Some(node_id)).await.is_err());
+        assert!(node.transfer_leadership(Some(node_id + 100)).await.is_err());
+
+        // 单节点集群没有可移交的跟随者，领导权保持不变
+        let err = node.transfer_leadership(None).await.unwrap_err();
+        assert!(err.to_string().contains("No follower available"));
+        assert_eq!(node.get_leader().await, Some(node_id));
+    }
+
+    #[tokio::test]
+    async fn test_stop_leader_without_followers_succeeds() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut node = create_node(&temp_dir).await;
+        node.start().await.unwrap();
+        node.wait_for_leadership(Duration::from_secs(5)).await.unwrap();
+
+        assert!(node.stop().await.is_ok());
+    }
+}
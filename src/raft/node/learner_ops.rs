@@ -0,0 +1,100 @@
+//! 学习者加入模块
+//!
+//! 提供将新节点以学习者身份加入集群、再提升为投票成员的功能
+
+use super::core::RaftNode;
+use crate::error::{ConfluxError, RaftError, Result};
+use crate::raft::types::{Node, NodeId};
+use std::collections::BTreeSet;
+use std::time::{Duration, Instant};
+use tracing::info;
+
+impl RaftNode {
+    /// 将节点作为学习者加入集群，并等待其日志追上领导者
+    ///
+    /// 节点地址会被写入共享的网络地址表，使领导者能够向其复制日志
+    ///
+    /// # Arguments
+    ///
+    /// * `node_id` - 学习者节点ID
+    /// * `address` - 学习者节点的Raft RPC地址
+    ///
+    /// # Errors
+    ///
+    /// 如果Raft未初始化、当前节点不是领导者或学习者添加失败，返回错误
+    pub async fn add_learner(&self, node_id: NodeId, address: String) -> Result<()> {
+        let raft = self
+            .get_raft()
+            .ok_or(RaftError::NotInitialized)?;
+
+        self.network_config().add_node(node_id, address.clone()).await;
+        raft.add_learner(node_id, Node::new(address.clone()), true)
+            .await
+            .map_err(|e| {
+                ConfluxError::raft(format!("Failed to add learner {}: {}", node_id, e))
+            })?;
+
+        info!("Node {} at {} joined the cluster as a learner", node_id, address);
+        Ok(())
+    }
+
+    /// 等待学习者的日志追上领导者当前的最后日志索引
+    ///
+    /// # Arguments
+    ///
+    /// * `node_id` - 学习者节点ID
+    /// * `timeout` - 最长等待时间
+    ///
+    /// # Returns
+    ///
+    /// 在超时前追上返回true；Raft未初始化或超时返回false
+    pub(super) async fn wait_for_learner_catch_up(&self, node_id: NodeId, timeout: Duration) -> bool {
+        let Some(raft) = self.get_raft() else {
+            return false;
+        };
+        let target = raft.metrics().borrow().last_log_index.unwrap_or(0);
+
+        let start = Instant::now();
+        loop {
+            let matched = raft
+                .metrics()
+                .borrow()
+                .replication
+                .as_ref()
+                .and_then(|replication| replication.get(&node_id).copied().flatten())
+                .map(|log_id| log_id.index);
+            if matched.is_some_and(|index| index >= target) {
+                return true;
+            }
+            if start.elapsed() >= timeout {
+                return false;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+    }
+
+    /// 将当前所有学习者提升为投票成员
+    ///
+    /// # Returns
+    ///
+    /// 返回变更后的投票成员集合
+    ///
+    /// # Errors
+    ///
+    /// 如果Raft未初始化或成员变更失败，返回错误
+    pub async fn promote_learners(&self) -> Result<BTreeSet<NodeId>> {
+        let raft = self
+            .get_raft()
+            .ok_or(RaftError::NotInitialized)?;
+
+        let membership = raft.metrics().borrow().membership_config.membership().clone();
+        let voters: BTreeSet<NodeId> = membership.nodes().map(|(id, _)| *id).collect();
+
+        raft.change_membership(voters.clone(), false)
+            .await
+            .map_err(|e| ConfluxError::raft(format!("Failed to promote learners: {}", e)))?;
+
+        info!("Cluster voters are now {:?}", voters);
+        Ok(voters)
+    }
+}
@@ -0,0 +1,116 @@
+//! 领导者租约模块
+//!
+//! 领导者得到多数派确认后的一段时间内不可能有新的领导者当选，这段时间内的读请求无需再调用
+//! `ensure_linearizable` 向多数派确认领导权。租约时长为心跳间隔的90%，远小于选举超时
+
+use super::core::RaftNode;
+use crate::raft::types::{Node, NodeId};
+use openraft::{RaftMetrics, ServerState};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant};
+use tokio::sync::watch;
+use tracing::debug;
+
+/// 租约时长占心跳间隔的比例
+pub const LEADER_LEASE_HEARTBEAT_FRACTION: f64 = 0.9;
+
+/// 领导者租约
+///
+/// 领导者每次得到多数派确认时将到期时间延长到确认时刻加上租约时长，失去领导权时撤销
+#[derive(Debug)]
+pub struct LeaderLease {
+    /// 租约到期时间，None表示没有租约
+    expiry: Mutex<Option<Instant>>,
+    /// 每次确认后租约的有效时长
+    duration: Duration,
+}
+
+impl LeaderLease {
+    /// 按心跳间隔（毫秒）创建租约，初始没有租约
+    pub fn new(heartbeat_interval_ms: u64) -> Self {
+        Self {
+            expiry: Mutex::new(None),
+            duration: Duration::from_millis(heartbeat_interval_ms)
+                .mul_f64(LEADER_LEASE_HEARTBEAT_FRACTION),
+        }
+    }
+
+    /// 每次确认后租约的有效时长
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+
+    /// 多数派在 `acked_at` 确认了领导权，延长租约
+    ///
+    /// 租约只会延长，较早的确认不会缩短已有的租约
+    pub fn extend(&self, acked_at: Instant) {
+        let expiry = acked_at + self.duration;
+        let mut current = self.expiry.lock().unwrap_or_else(PoisonError::into_inner);
+        if current.is_none_or(|current| current < expiry) {
+            *current = Some(expiry);
+        }
+    }
+
+    /// 撤销租约，例如失去领导权后
+    pub fn revoke(&self) {
+        *self.expiry.lock().unwrap_or_else(PoisonError::into_inner) = None;
+    }
+
+    /// 租约到期时间
+    pub fn expiry(&self) -> Option<Instant> {
+        *self.expiry.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// 租约当前是否有效
+    pub fn is_valid(&self) -> bool {
+        self.expiry().is_some_and(|expiry| expiry > Instant::now())
+    }
+}
+
+/// 启动领导者租约维护任务
+///
+/// 监听Raft指标：本节点是领导者且得到过多数派确认时，按指标中距最近一次确认的时间延长租约；
+/// 不再是领导者时撤销租约。指标通道关闭后任务自动退出
+pub(crate) fn spawn_lease_monitor(
+    node_id: NodeId,
+    mut metrics: watch::Receiver<RaftMetrics<NodeId, Node>>,
+    lease: Arc<LeaderLease>,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        while metrics.changed().await.is_ok() {
+            let (state, since_quorum_ack) = {
+                let metrics = metrics.borrow_and_update();
+                (metrics.state, metrics.millis_since_quorum_ack)
+            };
+            match (state, since_quorum_ack) {
+                (ServerState::Leader, Some(millis)) => {
+                    let acked_at = Instant::now()
+                        .checked_sub(Duration::from_millis(millis))
+                        .unwrap_or_else(Instant::now);
+                    lease.extend(acked_at);
+                }
+                (ServerState::Leader, None) => {}
+                _ => lease.revoke(),
+            }
+        }
+        lease.revoke();
+        debug!("Leader lease monitor for node {} stopped", node_id);
+    })
+}
+
+impl RaftNode {
+    /// 本节点是否为领导者且租约有效
+    ///
+    /// 只读取本地指标，不与其他节点通信
+    pub fn holds_leader_lease(&self) -> bool {
+        let Some(raft) = self.get_raft() else {
+            return false;
+        };
+        let is_leader = raft.metrics().borrow().state == ServerState::Leader;
+        is_leader && self.leader_lease().is_valid()
+    }
+}
+
+#[cfg(test)]
+#[path = "lease_ops_tests.rs"]
+mod tests;
@@ -0,0 +1,23 @@
+use super::*;
+
+#[test]
+fn test_lease_expires_after_heartbeat_fraction() {
+    let lease = LeaderLease::new(1_000);
+    assert_eq!(lease.duration(), Duration::from_millis(900));
+    assert!(!lease.is_valid());
+
+    let acked_at = Instant::now();
+    lease.extend(acked_at);
+    assert_eq!(lease.expiry(), Some(acked_at + Duration::from_millis(900)));
+    assert!(lease.is_valid());
+
+    // An older acknowledgement does not shorten the lease
+    lease.extend(acked_at - Duration::from_millis(500));
+    assert_eq!(lease.expiry(), Some(acked_at + Duration::from_millis(900)));
+
+    // A lease acknowledged longer ago than its duration has expired
+    lease.revoke();
+    lease.extend(Instant::now() - Duration::from_secs(1));
+    assert!(lease.expiry().is_some());
+    assert!(!lease.is_valid());
+}
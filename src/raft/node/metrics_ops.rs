@@ -0,0 +1,100 @@
+//! 集群指标汇总模块
+//!
+//! 领导者通过网络层向各成员节点拉取指标摘要，汇总为集群级指标；
+//! 无法访问的节点标记为未知，不影响其他节点的汇总
+
+use super::core::RaftNode;
+use crate::error::{RaftError, Result};
+use crate::raft::metrics::{AggregatedClusterMetrics, ClusterNodeMetrics, NodeMetricsSummary};
+use crate::raft::types::NodeId;
+use std::collections::BTreeSet;
+use std::time::Duration;
+use tokio::task::JoinSet;
+use tracing::{debug, warn};
+
+/// 等待单个节点返回指标的最长时间
+const METRICS_PULL_TIMEOUT: Duration = Duration::from_secs(2);
+
+impl RaftNode {
+    /// 获取本节点的指标摘要
+    ///
+    /// 先用Raft的最新指标更新指标收集器，跟随者响应领导者的指标拉取时返回此摘要
+    ///
+    /// # Errors
+    ///
+    /// 如果Raft未初始化，返回错误
+    pub async fn local_metrics_summary(&self) -> Result<NodeMetricsSummary> {
+        let raft = self.get_raft().ok_or(RaftError::NotInitialized)?;
+        let raft_metrics = raft.metrics().borrow().clone();
+        Ok(self.metrics_collector().node_summary(&raft_metrics).await)
+    }
+
+    /// 汇总集群所有成员节点的指标
+    ///
+    /// 只能在领导者上调用。并发向每个成员拉取指标摘要，
+    /// 请求失败或超时的节点标记为未知，其余节点正常计入集群总计
+    ///
+    /// # Returns
+    ///
+    /// 返回按节点ID排序的各节点指标和集群总计
+    ///
+    /// # Errors
+    ///
+    /// 如果Raft未初始化或本节点不是领导者，返回错误
+    pub async fn aggregate_cluster_metrics(&self) -> Result<AggregatedClusterMetrics> {
+        let raft = self.get_raft().ok_or(RaftError::NotInitialized)?;
+        let node_id = self.node_id();
+        if !self.is_leader().await {
+            return Err(RaftError::NotLeader {
+                node_id,
+                leader: self.get_leader().await,
+            }
+            .into());
+        }
+
+        let members: BTreeSet<NodeId> = raft
+            .metrics()
+            .borrow()
+            .membership_config
+            .membership()
+            .nodes()
+            .map(|(id, _)| *id)
+            .collect();
+
+        let mut pulls = JoinSet::new();
+        for peer in members.into_iter().filter(|id| *id != node_id) {
+            let client = self.network_client(peer).await;
+            pulls.spawn(async move {
+                (peer, tokio::time::timeout(METRICS_PULL_TIMEOUT, client.pull_metrics()).await)
+            });
+        }
+
+        let mut nodes = vec![ClusterNodeMetrics::reported(self.local_metrics_summary().await?)];
+        while let Some(result) = pulls.join_next().await {
+            match result {
+                Ok((peer, Ok(Ok(summary)))) => {
+                    debug!("Pulled metrics from node {}", peer);
+                    nodes.push(ClusterNodeMetrics::reported(summary));
+                }
+                Ok((peer, Ok(Err(e)))) => {
+                    warn!("Failed to pull metrics from node {}: {}", peer, e);
+                    nodes.push(ClusterNodeMetrics::unknown(peer, e.to_string()));
+                }
+                Ok((peer, Err(_))) => {
+                    warn!("Metrics pull from node {} timed out", peer);
+                    nodes.push(ClusterNodeMetrics::unknown(
+                        peer,
+                        format!("no answer within {:?}", METRICS_PULL_TIMEOUT),
+                    ));
+                }
+                Err(e) => warn!("Metrics pull task failed: {}", e),
+            }
+        }
+
+        Ok(AggregatedClusterMetrics::new(node_id, nodes))
+    }
+}
+
+#[cfg(test)]
+#[path = "metrics_ops_tests.rs"]
+mod tests;
@@ -0,0 +1,138 @@
+use super::*;
+use crate::error::ConfluxError;
+use crate::raft::metrics::NodeMetricsState;
+use crate::raft::network::NetworkConfig;
+use crate::raft::network_server::{metrics_rpc_routes, raft_rpc_routes};
+use crate::raft::node::NodeConfig;
+use crate::raft::node::test_support::app_config;
+use crate::raft::types::{ClientRequest, ConfigFormat, ConfigNamespace, RaftCommand};
+use std::collections::HashMap;
+use tempfile::TempDir;
+use tokio::net::TcpListener;
+use tokio::task::JoinHandle;
+
+/// 启动3节点集群，节点1为领导者
+///
+/// 节点3只提供Raft RPC，不响应指标拉取，模拟无法获取指标的节点
+async fn start_cluster(temp_dirs: &[TempDir]) -> (Vec<RaftNode>, Vec<JoinHandle<()>>) {
+    let mut listeners = Vec::new();
+    let mut addresses = HashMap::new();
+    for node_id in 1..=3 {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        addresses.insert(node_id, listener.local_addr().unwrap().to_string());
+        listeners.push(listener);
+    }
+    let network_config = NetworkConfig::new(addresses.clone());
+
+    let mut nodes = Vec::new();
+    let mut servers = Vec::new();
+    for ((node_id, listener), temp_dir) in (1..=3).zip(listeners).zip(temp_dirs) {
+        let config = NodeConfig {
+            node_id,
+            address: addresses[&node_id].clone(),
+            network_config: network_config.clone(),
+            ..Default::default()
+        };
+        let mut node = RaftNode::new(config, &app_config(temp_dir)).await.unwrap();
+        if node_id == 1 {
+            node.start().await.unwrap();
+            node.wait_for_leadership(Duration::from_secs(5)).await.unwrap();
+        } else {
+            node.start_as_learner().await.unwrap();
+        }
+
+        let raft = node.get_raft().cloned().unwrap();
+        let mut routes = raft_rpc_routes(raft.clone());
+        if node_id != 3 {
+            routes = routes.merge(metrics_rpc_routes(raft, node.metrics_collector()));
+        }
+        servers.push(tokio::spawn(async move {
+            axum::serve(listener, routes).await.unwrap();
+        }));
+        nodes.push(node);
+    }
+
+    for node_id in 2..=3 {
+        nodes[0]
+            .add_learner(node_id, addresses[&node_id].clone())
+            .await
+            .unwrap();
+    }
+    nodes[0].promote_learners().await.unwrap();
+    (nodes, servers)
+}
+
+async fn write_config(node: &RaftNode, name: &str) {
+    node.client_write(ClientRequest {
+        command: RaftCommand::CreateConfig {
+            namespace: ConfigNamespace {
+                tenant: "tenant".to_string(),
+                app: "app".to_string(),
+                env: "prod".to_string(),
+            },
+            name: name.to_string(),
+            content: b"{}".to_vec(),
+            format: ConfigFormat::Json,
+            schema: None,
+            creator_id: 1,
+            description: "metrics test".to_string(),
+        },
+        idempotency_key: None,
+    })
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn test_leader_aggregates_cluster_metrics() {
+    let temp_dirs: Vec<TempDir> = (0..3).map(|_| TempDir::new().unwrap()).collect();
+    let (nodes, _servers) = start_cluster(&temp_dirs).await;
+    write_config(&nodes[0], "a.json").await;
+    write_config(&nodes[0], "b.json").await;
+
+    let metrics = nodes[0].aggregate_cluster_metrics().await.unwrap();
+    assert_eq!(metrics.leader_id, 1);
+    let node_ids: Vec<NodeId> = metrics.nodes.iter().map(|node| node.node_id).collect();
+    assert_eq!(node_ids, vec![1, 2, 3]);
+
+    let leader = metrics.nodes[0].metrics.as_ref().unwrap();
+    assert!(leader.is_leader);
+    assert_eq!(leader.total_requests, 2);
+    assert!(leader.request_rate > 0.0);
+
+    let follower = metrics.nodes[1].metrics.as_ref().unwrap();
+    assert_eq!(metrics.nodes[1].state, NodeMetricsState::Reported);
+    assert!(!follower.is_leader);
+    assert_eq!(follower.leader_id, Some(1));
+    assert_eq!(follower.current_term, leader.current_term);
+
+    // 不响应指标拉取的节点标记为未知，不影响其他节点
+    assert_eq!(metrics.nodes[2].state, NodeMetricsState::Unknown);
+    assert!(metrics.nodes[2].metrics.is_none());
+    assert!(metrics.nodes[2].error.is_some());
+
+    assert_eq!(metrics.totals.node_count, 3);
+    assert_eq!(metrics.totals.reporting_nodes, 2);
+    assert_eq!(metrics.totals.unknown_nodes, 1);
+    assert_eq!(metrics.totals.total_requests, 2);
+    assert_eq!(metrics.totals.failed_requests, 0);
+    assert_eq!(metrics.totals.error_rate, 0.0);
+}
+
+#[tokio::test]
+async fn test_follower_cannot_aggregate_metrics() {
+    let temp_dirs: Vec<TempDir> = (0..3).map(|_| TempDir::new().unwrap()).collect();
+    let (nodes, _servers) = start_cluster(&temp_dirs).await;
+
+    let err = nodes[1].aggregate_cluster_metrics().await.unwrap_err();
+    assert!(
+        matches!(err, ConfluxError::Raft(RaftError::NotLeader { node_id: 2, .. })),
+        "{}",
+        err
+    );
+
+    // 跟随者仍然可以报告自己的指标
+    let summary = nodes[1].local_metrics_summary().await.unwrap();
+    assert_eq!(summary.node_id, 2);
+    assert!(!summary.is_leader);
+}
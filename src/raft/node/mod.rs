@@ -6,9 +6,35 @@ mod config;
 mod resource_limiter;
 mod core;
 mod cluster_ops;
+mod snapshot_ops;
+mod leadership_ops;
+mod decommission_ops;
+mod learner_ops;
+mod lease_ops;
+mod read_index_ops;
+mod priority_ops;
+mod pre_vote_ops;
+mod discovery_ops;
+mod housekeeping_ops;
+mod reload_ops;
+mod health_ops;
+mod metrics_ops;
+mod event_ops;
 mod helpers;
+#[cfg(test)]
+pub(crate) mod test_support;
 
-pub use config::{NodeConfig, ResourceLimits};
-pub use resource_limiter::{ResourceLimiter, RequestPermit, ResourceStats};
+pub use config::{NodeConfig, NodeConfigBuilder, ResourceLimits, SnapshotStreamConfig};
+pub use resource_limiter::{
+    ResourceLimiter, RequestPermit, ResourceStats, ANONYMOUS_CLIENT_ID, RATE_LIMIT_EXCEEDED,
+};
+pub(crate) use resource_limiter::RateLimitState;
 pub use core::RaftNode;
+pub use snapshot_ops::SnapshotInfo;
+pub use lease_ops::{LeaderLease, LEADER_LEASE_HEARTBEAT_FRACTION};
+pub use event_ops::{ClusterEvent, ClusterEventBus, ClusterEventKind, CLUSTER_EVENT_BUFFER};
+pub use discovery_ops::{spawn_peer_discovery, PeerChanges, PeerReconciler, DNS_STALE_ROUNDS};
+pub(crate) use priority_ops::update_node_priority;
+pub(crate) use pre_vote_ops::handle_pre_vote;
+pub(crate) use health_ops::{no_leader, storage_health};
 pub use helpers::*;
\ No newline at end of file
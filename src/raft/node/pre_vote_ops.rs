@@ -0,0 +1,113 @@
+//! 预投票（Pre-Vote）模块
+//!
+//! openraft 0.9 没有内置预投票。启用后关闭openraft自身的选举计时器，由监控任务在选举超时后
+//! 先向其他投票成员发送预投票请求，获得多数同意才触发真正的选举。
+//! 被分区的节点得不到多数同意，不会不断增加任期，恢复连接后也不会打断现有领导者
+
+use super::cluster_ops::collect_pre_votes;
+use crate::error::{ConfluxError, Result};
+use crate::raft::metrics::RaftMetricsCollector;
+use crate::raft::network::{ConfluxNetworkFactory, PreVoteRequest, PreVoteResponse};
+use crate::raft::store::Store;
+use crate::raft::types::{ConfluxRaft, NodeId};
+use openraft::ServerState;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::RwLock;
+use tracing::{debug, info, warn};
+
+/// 处理收到的预投票请求
+///
+/// 只有同时满足以下条件才同意：本节点不是领导者、最近一个最小选举超时内没有收到领导者的消息，
+/// 且候选者的最后日志不落后于本节点的最后日志（与正式投票的日志检查一致，否则日志落后的节点
+/// 会不断赢得预投票、输掉选举并抬高任期）。处理过程不会改变本节点的任期和投票
+///
+/// # Arguments
+///
+/// * `raft` - 本节点的Raft实例
+/// * `request` - 预投票请求
+///
+/// # Errors
+///
+/// 如果Raft实例已停止，返回错误
+pub(crate) async fn handle_pre_vote(
+    raft: &ConfluxRaft,
+    request: &PreVoteRequest,
+) -> Result<PreVoteResponse> {
+    let lease = Duration::from_millis(raft.config().election_timeout_min);
+    let (server_state, vote, last_modified) = raft
+        .with_raft_state(|st| (st.server_state, *st.vote_ref(), st.vote_last_modified()))
+        .await
+        .map_err(|e| ConfluxError::raft(format!("Raft is not running: {}", e)))?;
+    let last_log = raft.data_metrics().borrow().last_log;
+
+    let leader_alive = server_state == ServerState::Leader
+        || (vote.is_committed() && last_modified.is_some_and(|at| at.elapsed() < lease));
+    let last_log = last_log.map_or((0, 0), |log_id| (log_id.leader_id.term, log_id.index));
+    let log_up_to_date = (request.last_log_term, request.last_log_index) >= last_log;
+    let granted = !leader_alive && log_up_to_date;
+
+    debug!(
+        "Pre-vote from node {}: granted={} (leader alive: {}, log up to date: {})",
+        request.candidate_id, granted, leader_alive, log_up_to_date
+    );
+    Ok(PreVoteResponse {
+        term: vote.leader_id().term,
+        granted,
+    })
+}
+
+/// 启动预投票选举监控任务
+///
+/// 每隔一个随机选举超时检查一次：跟随者或候选者在这段时间内没有收到领导者的消息时发起预投票，
+/// 获得多数同意后触发选举。Raft实例停止后任务自动退出
+pub(crate) fn spawn_pre_vote_monitor(
+    node_id: NodeId,
+    raft: ConfluxRaft,
+    store: Arc<Store>,
+    network_factory: Arc<RwLock<ConfluxNetworkFactory>>,
+    metrics: Arc<RaftMetricsCollector>,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        loop {
+            let config = raft.config().clone();
+            let timeout = Duration::from_millis(fastrand::u64(
+                config.election_timeout_min..=config.election_timeout_max,
+            ));
+            tokio::time::sleep(timeout).await;
+
+            let Ok((server_state, last_modified)) = raft
+                .with_raft_state(|st| (st.server_state, st.vote_last_modified()))
+                .await
+            else {
+                break;
+            };
+            match server_state {
+                ServerState::Follower | ServerState::Candidate => {}
+                ServerState::Shutdown => break,
+                ServerState::Learner | ServerState::Leader => continue,
+            }
+            if last_modified.is_some_and(|at| at.elapsed() < timeout) {
+                continue;
+            }
+
+            let factory = network_factory.read().await.clone();
+            match collect_pre_votes(node_id, &raft, &store, &factory, &metrics).await {
+                Ok(true) => {
+                    info!("Node {} won pre-vote, starting election", node_id);
+                    if let Err(e) = raft.trigger().elect().await {
+                        warn!("Failed to start election on node {}: {}", node_id, e);
+                        break;
+                    }
+                }
+                Ok(false) => {}
+                Err(e) => warn!("Pre-vote on node {} failed: {}", node_id, e),
+            }
+        }
+        debug!("Pre-vote monitor for node {} stopped", node_id);
+    })
+}
+
+#[cfg(test)]
+#[path = "pre_vote_ops_tests.rs"]
+mod tests;
@@ -0,0 +1,161 @@
+use super::*;
+use crate::raft::network::NetworkConfig;
+use crate::raft::network_server::serve_raft_rpc;
+use crate::raft::node::{NodeConfig, RaftNode};
+use crate::raft::node::test_support::app_config;
+use std::collections::HashMap;
+use std::time::Instant;
+use tempfile::TempDir;
+use tokio::net::TcpListener;
+use tokio::task::JoinHandle;
+
+/// 启动启用预投票的3节点集群，节点1为初始领导者
+async fn start_cluster(
+    temp_dirs: &[TempDir],
+) -> (Vec<RaftNode>, Vec<JoinHandle<()>>, HashMap<NodeId, String>) {
+    let mut listeners = Vec::new();
+    let mut addresses = HashMap::new();
+    for node_id in 1..=3 {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        addresses.insert(node_id, listener.local_addr().unwrap().to_string());
+        listeners.push(listener);
+    }
+    let network_config = NetworkConfig::new(addresses.clone());
+
+    let mut nodes = Vec::new();
+    let mut servers = Vec::new();
+    for ((node_id, listener), temp_dir) in (1..=3).zip(listeners).zip(temp_dirs) {
+        let config = NodeConfig {
+            node_id,
+            address: addresses[&node_id].clone(),
+            network_config: network_config.clone(),
+            pre_vote_enabled: true,
+            ..Default::default()
+        };
+        let mut node = RaftNode::new(config, &app_config(temp_dir)).await.unwrap();
+        if node_id == 1 {
+            node.start().await.unwrap();
+            node.wait_for_leadership(Duration::from_secs(5)).await.unwrap();
+        } else {
+            node.start_as_learner().await.unwrap();
+        }
+        servers.push(serve_raft_rpc(listener, node.get_raft().cloned().unwrap()));
+        nodes.push(node);
+    }
+
+    for node_id in 2..=3 {
+        nodes[0]
+            .add_learner(node_id, addresses[&node_id].clone())
+            .await
+            .unwrap();
+    }
+    nodes[0].promote_learners().await.unwrap();
+    (nodes, servers, addresses)
+}
+
+/// 等待所有节点认可同一个领导者
+async fn wait_for_leader(nodes: &[&RaftNode], timeout: Duration) -> Option<NodeId> {
+    let start = Instant::now();
+    while start.elapsed() < timeout {
+        let leader = nodes[0].get_leader().await;
+        let mut agreed = leader.is_some();
+        for node in &nodes[1..] {
+            agreed &= node.get_leader().await == leader;
+        }
+        if agreed {
+            return leader;
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+    None
+}
+
+async fn current_term(node: &RaftNode) -> u64 {
+    node.get_metrics().await.unwrap().current_term
+}
+
+#[tokio::test]
+async fn test_leader_and_its_followers_deny_pre_vote() {
+    let temp_dirs: Vec<TempDir> = (0..3).map(|_| TempDir::new().unwrap()).collect();
+    let (nodes, _servers, _addresses) = start_cluster(&temp_dirs).await;
+    let all: Vec<&RaftNode> = nodes.iter().collect();
+    assert_eq!(wait_for_leader(&all, Duration::from_secs(5)).await, Some(1));
+    let term = current_term(&nodes[0]).await;
+
+    let request = PreVoteRequest {
+        candidate_id: 3,
+        last_log_index: u64::MAX,
+        last_log_term: u64::MAX,
+    };
+    for node in &nodes[..2] {
+        let response = handle_pre_vote(node.get_raft().unwrap(), &request).await.unwrap();
+        assert!(!response.granted);
+        assert_eq!(response.term, term);
+    }
+
+    // 主动发起的预投票得不到多数同意，任期保持不变
+    assert!(!nodes[2].pre_vote().await.unwrap());
+    let node_metrics = nodes[2].metrics_collector().get_metrics_report().await.node_metrics;
+    assert_eq!(node_metrics.pre_vote_denied_count, 2);
+    assert_eq!(node_metrics.pre_vote_granted_count, 0);
+    assert_eq!(current_term(&nodes[2]).await, term);
+}
+
+#[tokio::test]
+async fn test_rejoining_node_does_not_disrupt_leader() {
+    let temp_dirs: Vec<TempDir> = (0..3).map(|_| TempDir::new().unwrap()).collect();
+    let (nodes, mut servers, addresses) = start_cluster(&temp_dirs).await;
+    let all: Vec<&RaftNode> = nodes.iter().collect();
+    assert_eq!(wait_for_leader(&all, Duration::from_secs(5)).await, Some(1));
+    let term = current_term(&nodes[0]).await;
+
+    // 隔离节点3：它收不到领导者的心跳，选举超时后会不断尝试预投票
+    servers[2].abort();
+    let _ = (&mut servers[2]).await;
+    tokio::time::sleep(Duration::from_secs(2)).await;
+
+    let node_metrics = nodes[2].metrics_collector().get_metrics_report().await.node_metrics;
+    assert!(node_metrics.pre_vote_denied_count > 0, "{:?}", node_metrics);
+    assert_eq!(node_metrics.pre_vote_granted_count, 0);
+    assert_eq!(current_term(&nodes[2]).await, term);
+
+    // 节点3重新加入后领导者和任期都不变
+    let listener = TcpListener::bind(&addresses[&3]).await.unwrap();
+    servers[2] = serve_raft_rpc(listener, nodes[2].get_raft().cloned().unwrap());
+    tokio::time::sleep(Duration::from_secs(1)).await;
+
+    assert_eq!(wait_for_leader(&all, Duration::from_secs(5)).await, Some(1));
+    for node in &nodes {
+        assert_eq!(current_term(node).await, term);
+    }
+}
+
+#[tokio::test]
+async fn test_followers_elect_new_leader_after_leader_stops() {
+    let temp_dirs: Vec<TempDir> = (0..3).map(|_| TempDir::new().unwrap()).collect();
+    let (nodes, servers, _addresses) = start_cluster(&temp_dirs).await;
+    let all: Vec<&RaftNode> = nodes.iter().collect();
+    assert_eq!(wait_for_leader(&all, Duration::from_secs(5)).await, Some(1));
+    let term = current_term(&nodes[0]).await;
+
+    servers[0].abort();
+    nodes[0].get_raft().unwrap().shutdown().await.unwrap();
+
+    // 跟随者仍记得旧领导者，直到新的选举完成
+    let followers = [&nodes[1], &nodes[2]];
+    let start = Instant::now();
+    let mut leader = None;
+    while start.elapsed() < Duration::from_secs(10) && !matches!(leader, Some(2) | Some(3)) {
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        leader = wait_for_leader(&followers, Duration::from_secs(1)).await;
+    }
+    assert!(matches!(leader, Some(2) | Some(3)), "{:?}", leader);
+    assert!(current_term(&nodes[1]).await > term);
+
+    let node_metrics = nodes[leader.unwrap() as usize - 1]
+        .metrics_collector()
+        .get_metrics_report()
+        .await
+        .node_metrics;
+    assert!(node_metrics.pre_vote_granted_count > 0, "{:?}", node_metrics);
+}
@@ -0,0 +1,209 @@
+//! 选举优先级模块
+//!
+//! 节点的选举优先级保存在集群成员元数据（`Node::data`）中。openraft的投票流程无法按优先级裁决，
+//! 因此由领导者在选举后检查：若存在日志已追平且优先级更高的投票成员，则将领导权移交给它。
+//! 优先级更高的节点不可用或日志落后时不会移交，集群可用性不受影响
+
+use super::core::RaftNode;
+use crate::error::{ConfluxError, RaftError, Result};
+use crate::raft::network::ConfluxNetworkFactory;
+use crate::raft::types::{ConfluxRaft, Node, NodeId, DEFAULT_ELECTION_PRIORITY};
+use openraft::{ChangeMembers, RaftMetrics};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::{watch, RwLock};
+use tracing::{debug, info, warn};
+
+/// 同一任期内向同一节点重复发起领导权移交的最小间隔
+const PRIORITY_TRANSFER_RETRY_INTERVAL: Duration = Duration::from_secs(2);
+
+impl RaftNode {
+    /// 获取本节点配置的选举优先级
+    pub fn election_priority(&self) -> u8 {
+        self.config().election_priority
+    }
+
+    /// 设置本节点的选举优先级，并写入集群成员元数据
+    ///
+    /// 当前节点不是领导者时，请求会转发给领导者
+    ///
+    /// # Arguments
+    ///
+    /// * `priority` - 选举优先级（0-255），数值越高越优先成为领导者
+    ///
+    /// # Errors
+    ///
+    /// 如果Raft未初始化、本节点不是集群成员或成员变更失败，返回错误
+    pub async fn set_election_priority(&mut self, priority: u8) -> Result<()> {
+        self.set_node_priority(self.node_id(), priority).await?;
+        self.config_mut().election_priority = priority;
+        Ok(())
+    }
+
+    /// 设置指定节点的选举优先级
+    ///
+    /// # Arguments
+    ///
+    /// * `node_id` - 目标节点ID
+    /// * `priority` - 选举优先级（0-255）
+    ///
+    /// # Errors
+    ///
+    /// 如果Raft未初始化、目标节点不是集群成员、没有领导者或成员变更失败，返回错误
+    pub async fn set_node_priority(&self, node_id: NodeId, priority: u8) -> Result<()> {
+        let raft = self
+            .get_raft()
+            .ok_or(RaftError::NotInitialized)?;
+
+        match self.get_leader().await {
+            Some(leader) if leader == self.node_id() => {
+                update_node_priority(raft, node_id, priority).await
+            }
+            Some(leader) => self
+                .network_client(leader)
+                .await
+                .set_node_priority(node_id, priority)
+                .await
+                .map_err(|e| {
+                    ConfluxError::raft(format!(
+                        "Failed to forward priority change to leader {}: {}",
+                        leader, e
+                    ))
+                }),
+            None => Err(RaftError::NoLeader.into()),
+        }
+    }
+
+    /// 获取集群成员的选举优先级
+    pub async fn node_priorities(&self) -> BTreeMap<NodeId, u8> {
+        self.get_raft()
+            .map(|raft| {
+                raft.metrics()
+                    .borrow()
+                    .membership_config
+                    .membership()
+                    .nodes()
+                    .map(|(id, node)| (*id, node.election_priority()))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+/// 在领导者上更新节点的选举优先级
+///
+/// # Errors
+///
+/// 如果目标节点不是集群成员或成员变更失败（例如当前节点不是领导者），返回错误
+pub(crate) async fn update_node_priority(
+    raft: &ConfluxRaft,
+    node_id: NodeId,
+    priority: u8,
+) -> Result<()> {
+    let node = raft
+        .metrics()
+        .borrow()
+        .membership_config
+        .membership()
+        .get_node(&node_id)
+        .cloned()
+        .ok_or_else(|| {
+            ConfluxError::raft(format!("Node {} is not a member of the cluster", node_id))
+        })?;
+
+    if node.election_priority() == priority {
+        return Ok(());
+    }
+
+    let nodes = BTreeMap::from([(node_id, node.with_election_priority(priority))]);
+    raft.change_membership(ChangeMembers::SetNodes(nodes), false)
+        .await
+        .map_err(|e| {
+            ConfluxError::raft(format!(
+                "Failed to set election priority of node {}: {}",
+                node_id, e
+            ))
+        })?;
+
+    info!("Election priority of node {} set to {}", node_id, priority);
+    Ok(())
+}
+
+/// 选择应接替当前领导者的节点
+///
+/// 候选者必须是投票成员、优先级高于领导者且已复制到领导者的最后日志索引；
+/// 优先级最高者优先，相同时选择ID较小的节点
+fn preferred_leader(node_id: NodeId, metrics: &RaftMetrics<NodeId, Node>) -> Option<NodeId> {
+    if metrics.current_leader != Some(node_id) {
+        return None;
+    }
+    let membership = metrics.membership_config.membership();
+    let priority_of = |id: &NodeId| {
+        membership
+            .get_node(id)
+            .map_or(DEFAULT_ELECTION_PRIORITY, Node::election_priority)
+    };
+    let own_priority = priority_of(&node_id);
+    let last_log_index = metrics.last_log_index?;
+    let replication = metrics.replication.as_ref()?;
+
+    membership
+        .voter_ids()
+        .filter(|id| *id != node_id && priority_of(id) > own_priority)
+        .filter(|id| {
+            replication
+                .get(id)
+                .copied()
+                .flatten()
+                .is_some_and(|log_id| log_id.index >= last_log_index)
+        })
+        .max_by_key(|id| (priority_of(id), std::cmp::Reverse(*id)))
+}
+
+/// 启动选举优先级监控任务
+///
+/// 本节点成为领导者后，若存在优先级更高且日志已追平的投票成员，则在该节点上触发选举。
+/// Raft实例关闭、指标通道关闭后任务自动退出
+pub(crate) fn spawn_priority_monitor(
+    node_id: NodeId,
+    mut metrics: watch::Receiver<RaftMetrics<NodeId, Node>>,
+    network_factory: Arc<RwLock<ConfluxNetworkFactory>>,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut last_attempt: Option<(u64, NodeId, Instant)> = None;
+
+        while metrics.changed().await.is_ok() {
+            let (term, target) = {
+                let metrics = metrics.borrow_and_update();
+                (metrics.current_term, preferred_leader(node_id, &metrics))
+            };
+            let Some(target) = target else {
+                continue;
+            };
+            if let Some((attempt_term, attempt_target, at)) = last_attempt {
+                if attempt_term == term
+                    && attempt_target == target
+                    && at.elapsed() < PRIORITY_TRANSFER_RETRY_INTERVAL
+                {
+                    continue;
+                }
+            }
+            last_attempt = Some((term, target, Instant::now()));
+
+            info!(
+                "Node {} hands leadership to higher-priority node {} (term {})",
+                node_id, target, term
+            );
+            let client = network_factory.read().await.client_for(target);
+            if let Err(e) = client.trigger_elect().await {
+                warn!("Failed to trigger election on node {}: {}", target, e);
+            }
+        }
+        debug!("Election priority monitor for node {} stopped", node_id);
+    })
+}
+
+#[cfg(test)]
+#[path = "priority_ops_tests.rs"]
+mod tests;
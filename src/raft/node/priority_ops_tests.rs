@@ -0,0 +1,128 @@
+use super::*;
+use crate::raft::network::NetworkConfig;
+use crate::raft::network_server::serve_raft_rpc;
+use crate::raft::node::NodeConfig;
+use crate::raft::node::test_support::app_config;
+use std::collections::HashMap;
+use tempfile::TempDir;
+use tokio::net::TcpListener;
+use tokio::task::JoinHandle;
+
+/// 启动3个节点并组成集群，节点1为初始领导者
+async fn start_cluster(temp_dirs: &[TempDir]) -> (Vec<RaftNode>, Vec<JoinHandle<()>>) {
+    let mut listeners = Vec::new();
+    let mut addresses = HashMap::new();
+    for node_id in 1..=3 {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        addresses.insert(node_id, listener.local_addr().unwrap().to_string());
+        listeners.push(listener);
+    }
+    let network_config = NetworkConfig::new(addresses.clone());
+
+    let mut nodes = Vec::new();
+    let mut servers = Vec::new();
+    for ((node_id, listener), temp_dir) in (1..=3).zip(listeners).zip(temp_dirs) {
+        let config = NodeConfig {
+            node_id,
+            address: addresses[&node_id].clone(),
+            network_config: network_config.clone(),
+            ..Default::default()
+        };
+        let mut node = RaftNode::new(config, &app_config(temp_dir)).await.unwrap();
+        if node_id == 1 {
+            node.start().await.unwrap();
+            node.wait_for_leadership(Duration::from_secs(5)).await.unwrap();
+        } else {
+            node.start_as_learner().await.unwrap();
+        }
+        servers.push(serve_raft_rpc(listener, node.get_raft().cloned().unwrap()));
+        nodes.push(node);
+    }
+
+    for node_id in 2..=3 {
+        nodes[0]
+            .add_learner(node_id, addresses[&node_id].clone())
+            .await
+            .unwrap();
+    }
+    nodes[0].promote_learners().await.unwrap();
+    (nodes, servers)
+}
+
+/// 等待所有节点认可指定的领导者
+async fn wait_for_leader(nodes: &[RaftNode], leader: NodeId, timeout: Duration) -> bool {
+    let start = Instant::now();
+    while start.elapsed() < timeout {
+        let mut agreed = true;
+        for node in nodes {
+            agreed &= node.get_leader().await == Some(leader);
+        }
+        if agreed {
+            return true;
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+    false
+}
+
+#[tokio::test]
+async fn test_initialize_records_own_priority() {
+    let temp_dir = TempDir::new().unwrap();
+    let config = NodeConfig {
+        election_priority: 200,
+        ..Default::default()
+    };
+    let mut node = RaftNode::new(config, &app_config(&temp_dir)).await.unwrap();
+    node.start().await.unwrap();
+    node.wait_for_leadership(Duration::from_secs(5)).await.unwrap();
+
+    assert_eq!(node.election_priority(), 200);
+    assert_eq!(node.node_priorities().await, BTreeMap::from([(1, 200)]));
+}
+
+#[tokio::test]
+async fn test_set_node_priority_requires_member() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut node = RaftNode::new(NodeConfig::default(), &app_config(&temp_dir))
+        .await
+        .unwrap();
+    assert!(node.set_node_priority(1, 10).await.is_err());
+
+    node.start().await.unwrap();
+    node.wait_for_leadership(Duration::from_secs(5)).await.unwrap();
+    let err = node.set_node_priority(7, 10).await.unwrap_err();
+    assert!(err.to_string().contains("not a member"));
+
+    node.set_election_priority(10).await.unwrap();
+    assert_eq!(node.election_priority(), 10);
+    assert_eq!(node.node_priorities().await, BTreeMap::from([(1, 10)]));
+}
+
+#[tokio::test]
+async fn test_higher_priority_node_becomes_leader() {
+    let temp_dirs: Vec<TempDir> = (0..3).map(|_| TempDir::new().unwrap()).collect();
+    let (mut nodes, servers) = start_cluster(&temp_dirs).await;
+    assert!(wait_for_leader(&nodes, 1, Duration::from_secs(5)).await);
+
+    // 节点3不是领导者，优先级变更会转发给节点1
+    nodes[2].set_election_priority(200).await.unwrap();
+    assert_eq!(
+        nodes[0].node_priorities().await,
+        BTreeMap::from([
+            (1, DEFAULT_ELECTION_PRIORITY),
+            (2, DEFAULT_ELECTION_PRIORITY),
+            (3, 200)
+        ])
+    );
+
+    // 领导者将领导权移交给日志已追平的更高优先级节点
+    assert!(wait_for_leader(&nodes, 3, Duration::from_secs(10)).await);
+
+    // 新领导者的优先级最高，领导权保持稳定
+    tokio::time::sleep(Duration::from_secs(1)).await;
+    assert!(wait_for_leader(&nodes, 3, Duration::from_secs(1)).await);
+
+    for server in servers {
+        server.abort();
+    }
+}
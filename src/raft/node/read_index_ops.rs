@@ -0,0 +1,56 @@
+//! 读索引模块
+//!
+//! 线性一致读需要领导者向多数派确认领导权（一次心跳往返）。读索引将这一步与读取本身分开：
+//! 确认一次领导权得到读索引后，同一请求中的多次本地读取可以复用它，不必逐次确认，降低领导者负载
+
+use super::core::RaftNode;
+use crate::error::{ConfluxError, RaftError, Result};
+use crate::raft::types::Node;
+use std::time::Instant;
+use tracing::debug;
+
+impl RaftNode {
+    /// 向多数派确认领导权，返回已在本节点应用的读索引
+    ///
+    /// 读索引是领导者确认领导权时已提交的最大日志索引（至少包含当前任期的空白日志）。
+    /// 返回时本节点已应用到该索引，因此：
+    /// - 调用开始前已向任何客户端确认提交的写入，索引都不大于读索引，之后的本地读取一定能看到
+    /// - 调用开始后才提交的写入可能也已应用，本地读取可能看到它们；读索引只是下界而不是时间点
+    ///
+    /// 确认成功时同时延长领导者租约
+    ///
+    /// # Errors
+    ///
+    /// Raft未初始化时返回 [`RaftError::NotInitialized`]；
+    /// 本节点不是领导者时返回 [`RaftError::NotLeader`]，其中包含已知的领导者；
+    /// 无法得到多数派确认时返回Raft错误
+    pub async fn read_index(&self) -> Result<u64> {
+        let Some(raft) = self.get_raft() else {
+            return Err(RaftError::NotInitialized.into());
+        };
+
+        let confirm_started = Instant::now();
+        match raft.ensure_linearizable().await {
+            Ok(read_log_id) => {
+                // 多数派在确认开始之后认可了领导权
+                self.leader_lease().extend(confirm_started);
+                let index = read_log_id.map_or(0, |log_id| log_id.index);
+                debug!("Node {} confirmed read index {}", self.node_id(), index);
+                Ok(index)
+            }
+            Err(e) => {
+                if let Some(forward) = e.forward_to_leader::<Node>() {
+                    return Err(RaftError::NotLeader {
+                        node_id: self.node_id(),
+                        leader: forward.leader_id,
+                    }
+                    .into());
+                }
+                Err(ConfluxError::raft(format!(
+                    "Cannot provide linearizable read: {}",
+                    e
+                )))
+            }
+        }
+    }
+}
@@ -0,0 +1,90 @@
+//! 配置热更新模块
+//!
+//! 节点订阅 [`watch_config_file`](crate::config::watch_config_file) 发出的配置，
+//! 资源限制变化时无需重启即可生效
+
+use super::core::RaftNode;
+use crate::config::AppConfig;
+use tokio::sync::broadcast::{self, error::RecvError};
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+
+impl RaftNode {
+    /// 订阅配置热更新，资源限制变化时更新资源限制器
+    ///
+    /// # Arguments
+    ///
+    /// * `updates` - 配置文件监听器发出的新配置
+    ///
+    /// # Returns
+    ///
+    /// 返回订阅任务的句柄，配置通道关闭后任务结束
+    pub fn subscribe_config_updates(
+        &self,
+        mut updates: broadcast::Receiver<AppConfig>,
+    ) -> JoinHandle<()> {
+        let resource_limiter = self.resource_limiter();
+        let node_id = self.node_id();
+        tokio::spawn(async move {
+            loop {
+                match updates.recv().await {
+                    Ok(config) => {
+                        if resource_limiter.get_limits() != config.resource_limits {
+                            info!("Node {} applying reloaded resource limits", node_id);
+                            resource_limiter.update_limits(config.resource_limits);
+                        }
+                    }
+                    Err(RecvError::Lagged(skipped)) => {
+                        warn!("Node {} skipped {} config updates", node_id, skipped);
+                    }
+                    Err(RecvError::Closed) => break,
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::StorageConfig;
+    use crate::raft::node::{NodeConfig, ResourceLimits};
+    use std::time::Duration;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_config_updates_reach_resource_limiter() {
+        let temp_dir = TempDir::new().unwrap();
+        let app_config = AppConfig {
+            storage: StorageConfig {
+                data_dir: temp_dir.path().to_string_lossy().to_string(),
+                cache_size_mb: 8,
+                write_buffer_size_mb: 8,
+                max_write_buffer_number: 2,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let node = RaftNode::new(NodeConfig::default(), &app_config).await.unwrap();
+
+        let (tx, rx) = broadcast::channel(4);
+        let handle = node.subscribe_config_updates(rx);
+        let limits = ResourceLimits {
+            max_requests_per_second: 7,
+            ..Default::default()
+        };
+        tx.send(AppConfig {
+            resource_limits: limits.clone(),
+            ..app_config
+        })
+        .unwrap();
+
+        // 通道关闭后订阅任务结束，此时更新已经处理完毕
+        drop(tx);
+        tokio::time::timeout(Duration::from_secs(5), handle)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(node.resource_limiter().get_limits(), limits);
+    }
+}
@@ -3,13 +3,21 @@
 //! 提供客户端请求的资源限制和速率控制功能
 
 use super::config::ResourceLimits;
-use crate::error::Result;
-use std::collections::HashMap;
+use crate::error::{RaftError, Result};
+use crate::raft::store::{ContentLimitRegistry, ContentLimits};
+use std::collections::{HashMap, VecDeque};
 use std::sync::atomic::{AtomicU32, AtomicUsize, Ordering};
-use std::sync::Arc;
-use std::time::{Duration, Instant};
+use std::sync::{Arc, RwLock as StdRwLock};
+use std::time::Duration;
 use tokio::sync::{RwLock, Semaphore};
-use tracing::warn;
+use tokio::time::Instant;
+use tracing::{info, warn};
+
+/// 超出客户端速率限制时的错误信息前缀，与`RaftError::RateLimited`的显示一致
+pub const RATE_LIMIT_EXCEEDED: &str = "Rate limit exceeded";
+
+/// 未认证请求共享的速率限制客户端ID
+pub const ANONYMOUS_CLIENT_ID: &str = "anonymous";
 
 /// 客户端资源限制器
 /// 
@@ -25,8 +33,8 @@ use tracing::warn;
 /// ```
 #[derive(Debug)]
 pub struct ResourceLimiter {
-    /// 资源限制配置
-    limits: ResourceLimits,
+    /// 资源限制配置，可在运行时热更新
+    limits: StdRwLock<ResourceLimits>,
     /// 并发请求限制信号量
     concurrent_requests: Semaphore,
     /// 当前内存使用量（待处理请求）
@@ -37,15 +45,58 @@ pub struct ResourceLimiter {
     total_requests: AtomicU32,
     /// 被拒绝的请求计数
     rejected_requests: AtomicU32,
+    /// 配置内容限制（与存储共享，支持按租户覆盖）
+    content_limits: Arc<ContentLimitRegistry>,
 }
 
 /// 客户端速率限制状态
+///
+/// 滑动窗口计数：记录最近 `window_duration` 内每个被接受请求的时间戳，
+/// 避免固定窗口在窗口边界处先突发、后静默的问题
 #[derive(Debug, Clone)]
-struct RateLimitState {
-    /// 当前时间窗口内的请求计数
-    request_count: u32,
-    /// 时间窗口开始时间
-    window_start: Instant,
+pub(crate) struct RateLimitState {
+    /// 窗口内被接受请求的时间戳，按先后顺序排列
+    timestamps: VecDeque<Instant>,
+    /// 滑动窗口长度
+    pub(crate) window_duration: Duration,
+}
+
+impl RateLimitState {
+    /// 创建空的速率限制状态
+    ///
+    /// # Arguments
+    ///
+    /// * `window_duration` - 滑动窗口长度
+    pub(crate) fn new(window_duration: Duration) -> Self {
+        Self {
+            timestamps: VecDeque::new(),
+            window_duration,
+        }
+    }
+
+    /// 在 `now` 时刻尝试接受一个请求
+    ///
+    /// 先淘汰早于窗口的时间戳，窗口内的请求数未达到 `max_requests` 时记录本次请求。
+    /// 被拒绝的请求不记录，持续超限的客户端在窗口滑过后即可恢复
+    ///
+    /// # Returns
+    ///
+    /// 请求被接受返回Ok(())，否则返回窗口内的请求数
+    pub(crate) fn try_acquire(&mut self, now: Instant, max_requests: u32) -> std::result::Result<(), u32> {
+        while let Some(&oldest) = self.timestamps.front() {
+            if now.duration_since(oldest) < self.window_duration {
+                break;
+            }
+            self.timestamps.pop_front();
+        }
+
+        let in_window = u32::try_from(self.timestamps.len()).unwrap_or(u32::MAX);
+        if in_window >= max_requests {
+            return Err(in_window);
+        }
+        self.timestamps.push_back(now);
+        Ok(())
+    }
 }
 
 impl ResourceLimiter {
@@ -64,13 +115,31 @@ impl ResourceLimiter {
     /// let limiter = ResourceLimiter::new(limits);
     /// ```
     pub fn new(limits: ResourceLimits) -> Self {
+        let content_limits = Arc::new(ContentLimitRegistry::new(limits.content_limits()));
+        Self::with_content_limits(limits, content_limits)
+    }
+
+    /// 使用共享的配置内容限制创建资源限制器
+    ///
+    /// 限制器会将 `limits` 中的内容限制设置为默认值，存储在写入时使用同一份限制
+    ///
+    /// # Arguments
+    ///
+    /// * `limits` - 资源限制配置
+    /// * `content_limits` - 与存储共享的配置内容限制
+    pub fn with_content_limits(
+        limits: ResourceLimits,
+        content_limits: Arc<ContentLimitRegistry>,
+    ) -> Self {
+        content_limits.set_defaults(limits.content_limits());
         Self {
             concurrent_requests: Semaphore::new(limits.max_concurrent_requests as usize),
-            limits,
+            limits: StdRwLock::new(limits),
             current_memory_usage: Arc::new(AtomicUsize::new(0)),
             rate_limit_state: RwLock::new(HashMap::new()),
             total_requests: AtomicU32::new(0),
             rejected_requests: AtomicU32::new(0),
+            content_limits,
         }
     }
 
@@ -81,7 +150,7 @@ impl ResourceLimiter {
     /// # Arguments
     /// 
     /// * `request_size` - 请求大小（字节）
-    /// * `client_id` - 可选的客户端ID，用于速率限制
+    /// * `client_id` - 可选的客户端ID，用于速率限制；为None时（节点内部请求）不做速率限制
     /// 
     /// # Returns
     /// 
@@ -102,52 +171,47 @@ impl ResourceLimiter {
     /// ```
     pub async fn check_request_allowed(&self, request_size: usize, client_id: Option<&str>) -> Result<RequestPermit<'_>> {
         self.total_requests.fetch_add(1, Ordering::Relaxed);
+        let limits = self.get_limits();
 
         // 检查请求大小限制
-        if request_size > self.limits.max_request_size {
+        if request_size > limits.max_request_size {
             self.rejected_requests.fetch_add(1, Ordering::Relaxed);
-            return Err(crate::error::ConfluxError::raft(format!(
-                "Request size {} exceeds limit {}",
-                request_size, self.limits.max_request_size
-            )));
+            return Err(RaftError::RequestTooLarge {
+                size: request_size,
+                limit: limits.max_request_size,
+            }
+            .into());
         }
 
         // 检查内存使用量限制
         let current_memory = self.current_memory_usage.load(Ordering::Relaxed);
-        if current_memory + request_size > self.limits.max_memory_usage {
+        if current_memory + request_size > limits.max_memory_usage {
             self.rejected_requests.fetch_add(1, Ordering::Relaxed);
-            return Err(crate::error::ConfluxError::raft(format!(
+            return Err(RaftError::ResourceLimitExceeded(format!(
                 "Memory usage limit exceeded: current={}, request={}, limit={}",
-                current_memory, request_size, self.limits.max_memory_usage
-            )));
+                current_memory, request_size, limits.max_memory_usage
+            ))
+            .into());
         }
 
-        // 检查客户端速率限制
+        // 检查客户端速率限制（滑动窗口）
         if let Some(client) = client_id {
             let mut state_map = self.rate_limit_state.write().await;
-            let now = Instant::now();
-            
-            let client_state = state_map.entry(client.to_string()).or_insert_with(|| RateLimitState {
-                request_count: 0,
-                window_start: now,
-            });
-
-            // 如果超过1秒则重置时间窗口
-            if now.duration_since(client_state.window_start) >= Duration::from_secs(1) {
-                client_state.request_count = 0;
-                client_state.window_start = now;
-            }
+            let window = limits.rate_limit_window();
+            let client_state = state_map
+                .entry(client.to_string())
+                .or_insert_with(|| RateLimitState::new(window));
+            // 窗口长度可热更新
+            client_state.window_duration = window;
 
-            // 检查速率限制
-            if client_state.request_count >= self.limits.max_requests_per_second {
+            if let Err(requests) = client_state.try_acquire(Instant::now(), limits.max_requests_per_second) {
                 self.rejected_requests.fetch_add(1, Ordering::Relaxed);
-                return Err(crate::error::ConfluxError::raft(format!(
-                    "Rate limit exceeded for client {}: {} requests/second",
-                    client, client_state.request_count
-                )));
+                return Err(RaftError::RateLimited {
+                    client: client.to_string(),
+                    requests,
+                }
+                .into());
             }
-
-            client_state.request_count += 1;
         }
 
         // 尝试获取并发请求许可
@@ -164,10 +228,11 @@ impl ResourceLimiter {
             }
             Err(_) => {
                 self.rejected_requests.fetch_add(1, Ordering::Relaxed);
-                Err(crate::error::ConfluxError::raft(format!(
+                Err(RaftError::ResourceLimitExceeded(format!(
                     "Too many concurrent requests: limit={}",
-                    self.limits.max_concurrent_requests
-                )))
+                    limits.max_concurrent_requests
+                ))
+                .into())
             }
         }
     }
@@ -195,7 +260,7 @@ impl ResourceLimiter {
             rejected_requests: self.rejected_requests.load(Ordering::Relaxed),
             current_memory_usage: self.current_memory_usage.load(Ordering::Relaxed),
             available_permits: self.concurrent_requests.available_permits(),
-            max_concurrent_requests: self.limits.max_concurrent_requests as usize,
+            max_concurrent_requests: self.get_limits().max_concurrent_requests as usize,
         }
     }
 
@@ -207,7 +272,7 @@ impl ResourceLimiter {
     /// 
     /// # Note
     /// 
-    /// 某些更改（如并发数限制）可能需要重启才能完全生效
+    /// 降低并发数限制时，已被占用的许可会在释放后才逐步收回
     /// 
     /// # Examples
     /// 
@@ -215,16 +280,30 @@ impl ResourceLimiter {
     /// use conflux::raft::node::{ResourceLimiter, ResourceLimits};
     /// 
     /// let limits = ResourceLimits::default();
-    /// let mut limiter = ResourceLimiter::new(limits);
+    /// let limiter = ResourceLimiter::new(limits);
     /// 
     /// let new_limits = ResourceLimits::new(200, 100, 2_000_000, 100_000_000, 10000);
     /// limiter.update_limits(new_limits);
     /// ```
-    pub fn update_limits(&mut self, new_limits: ResourceLimits) {
-        self.limits = new_limits;
-        // 注意：运行时更改信号量许可数是复杂的
-        // 这是一个简化的实现
-        warn!("Resource limits updated - some changes may require restart");
+    pub fn update_limits(&self, new_limits: ResourceLimits) {
+        self.content_limits.set_defaults(new_limits.content_limits());
+        let mut limits = self.limits.write().unwrap_or_else(|e| e.into_inner());
+        let old_permits = limits.max_concurrent_requests as usize;
+        let new_permits = new_limits.max_concurrent_requests as usize;
+        if new_permits > old_permits {
+            self.concurrent_requests.add_permits(new_permits - old_permits);
+        } else if new_permits < old_permits {
+            let forgotten = self.concurrent_requests.forget_permits(old_permits - new_permits);
+            if forgotten < old_permits - new_permits {
+                warn!(
+                    "Concurrent request limit lowered to {} while {} requests are in flight",
+                    new_permits,
+                    old_permits - new_permits - forgotten
+                );
+            }
+        }
+        *limits = new_limits;
+        info!("Resource limits updated: {:?}", *limits);
     }
 
     /// 获取当前资源限制配置
@@ -232,8 +311,20 @@ impl ResourceLimiter {
     /// # Returns
     /// 
     /// 返回当前的资源限制配置
-    pub fn get_limits(&self) -> &ResourceLimits {
-        &self.limits
+    pub fn get_limits(&self) -> ResourceLimits {
+        self.limits.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// 获取租户当前生效的配置内容限制
+    ///
+    /// 租户的覆盖值在应用 `RaftCommand::SetTenantLimits` 时设置，所有副本一致；
+    /// 未覆盖的限制使用 `ResourceLimits` 中的默认值
+    ///
+    /// # Arguments
+    ///
+    /// * `tenant` - 租户ID
+    pub fn tenant_limits(&self, tenant: &str) -> ContentLimits {
+        self.content_limits.limits_for(tenant)
     }
 }
 
@@ -320,6 +411,7 @@ impl ResourceStats {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::raft::store::TenantLimits;
 
     #[test]
     fn test_resource_limiter_creation() {
@@ -366,6 +458,104 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[tokio::test]
+    async fn test_update_limits_applies_at_runtime() {
+        let limits = ResourceLimits {
+            max_concurrent_requests: 2,
+            ..Default::default()
+        };
+        let limiter = ResourceLimiter::new(limits);
+        let _permit = limiter.check_request_allowed(16, None).await.unwrap();
+
+        limiter.update_limits(ResourceLimits {
+            max_concurrent_requests: 4,
+            max_request_size: 8,
+            ..Default::default()
+        });
+        assert_eq!(limiter.get_limits().max_request_size, 8);
+        assert!(limiter.check_request_allowed(16, None).await.is_err());
+        let stats = limiter.get_resource_stats();
+        assert_eq!(stats.max_concurrent_requests, 4);
+        assert_eq!(stats.available_permits, 3);
+
+        // 降低并发数时收回空闲的许可
+        limiter.update_limits(ResourceLimits {
+            max_concurrent_requests: 1,
+            ..Default::default()
+        });
+        assert_eq!(limiter.get_resource_stats().available_permits, 0);
+        assert!(limiter.check_request_allowed(16, None).await.is_err());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_steady_rate_at_limit_is_not_rejected() {
+        let limiter = ResourceLimiter::new(ResourceLimits::default());
+        assert_eq!(limiter.get_limits().max_requests_per_second, 100);
+
+        // 100 req/s，间隔10ms均匀到达，跨越多个窗口都不应被拒绝
+        for _ in 0..300 {
+            limiter.check_request_allowed(16, Some("client1")).await.unwrap();
+            tokio::time::advance(Duration::from_millis(10)).await;
+        }
+        assert_eq!(limiter.get_resource_stats().rejected_requests, 0);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_sliding_window_prevents_boundary_burst() {
+        let limits = ResourceLimits {
+            max_requests_per_second: 10,
+            ..Default::default()
+        };
+        let limiter = ResourceLimiter::new(limits);
+
+        // 窗口末尾的突发用完配额
+        tokio::time::advance(Duration::from_millis(900)).await;
+        for _ in 0..10 {
+            limiter.check_request_allowed(16, Some("client1")).await.unwrap();
+        }
+        // 固定窗口在1秒边界处会重置计数，滑动窗口仍然拒绝
+        tokio::time::advance(Duration::from_millis(200)).await;
+        let err = limiter.check_request_allowed(16, Some("client1")).await.err().unwrap();
+        assert!(err.to_string().contains(RATE_LIMIT_EXCEEDED), "{}", err);
+        // 其他客户端不受影响
+        limiter.check_request_allowed(16, Some("client2")).await.unwrap();
+
+        // 突发的请求滑出窗口后恢复
+        tokio::time::advance(Duration::from_millis(800)).await;
+        limiter.check_request_allowed(16, Some("client1")).await.unwrap();
+        assert_eq!(limiter.get_resource_stats().rejected_requests, 1);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_rate_limit_window_is_configurable() {
+        let limits = ResourceLimits {
+            max_requests_per_second: 2,
+            rate_limit_window_ms: 100,
+            ..Default::default()
+        };
+        let limiter = ResourceLimiter::new(limits);
+
+        limiter.check_request_allowed(16, Some("client1")).await.unwrap();
+        limiter.check_request_allowed(16, Some("client1")).await.unwrap();
+        assert!(limiter.check_request_allowed(16, Some("client1")).await.is_err());
+
+        tokio::time::advance(Duration::from_millis(100)).await;
+        limiter.check_request_allowed(16, Some("client1")).await.unwrap();
+    }
+
+    #[test]
+    fn test_rate_limit_state_evicts_expired_timestamps() {
+        let start = Instant::now();
+        let mut state = RateLimitState::new(Duration::from_secs(1));
+        assert_eq!(state.try_acquire(start, 2), Ok(()));
+        assert_eq!(state.try_acquire(start + Duration::from_millis(500), 2), Ok(()));
+        assert_eq!(state.try_acquire(start + Duration::from_millis(999), 2), Err(2));
+
+        // 第一个请求恰好滑出窗口
+        assert_eq!(state.try_acquire(start + Duration::from_secs(1), 2), Ok(()));
+        assert_eq!(state.timestamps.len(), 2);
+    }
+
     #[test]
     fn test_resource_stats() {
         let stats = ResourceStats {
@@ -380,4 +570,38 @@ mod tests {
         assert_eq!(stats.memory_usage_rate(2048), 0.5);
         assert_eq!(stats.concurrency_usage_rate(), 0.2);
     }
+
+    #[test]
+    fn test_tenant_content_limits_override_defaults() {
+        let limits = ResourceLimits {
+            max_config_content_bytes: 1024,
+            max_version_history: 5,
+            ..Default::default()
+        };
+        let registry = Arc::new(ContentLimitRegistry::default());
+        let limiter = ResourceLimiter::with_content_limits(limits, registry.clone());
+
+        // 默认值来自ResourceLimits，并同步到共享的限制表
+        assert_eq!(registry.limits_for("tenant1").max_config_content_bytes, 1024);
+
+        // 只覆盖部分限制时，其余限制仍使用默认值
+        registry.set_tenant_limits(
+            "tenant1",
+            TenantLimits {
+                max_config_content_bytes: None,
+                max_version_history: Some(2),
+            },
+        );
+        assert_eq!(
+            limiter.tenant_limits("tenant1"),
+            ContentLimits {
+                max_config_content_bytes: 1024,
+                max_version_history: 2,
+            }
+        );
+        assert_eq!(limiter.tenant_limits("tenant2").max_version_history, 5);
+
+        registry.set_tenant_limits("tenant1", TenantLimits::default());
+        assert_eq!(limiter.tenant_limits("tenant1").max_version_history, 5);
+    }
 }
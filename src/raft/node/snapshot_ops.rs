@@ -0,0 +1,278 @@
+//! 快照与日志压缩模块
+//!
+//! 提供手动触发快照、清理已快照日志以及查询快照信息的功能
+
+use super::core::RaftNode;
+use super::event_ops::ClusterEventKind;
+use crate::error::{ConfluxError, RaftError, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::time::Duration;
+use tracing::{debug, info};
+
+/// 等待快照完成的最长时间
+const SNAPSHOT_WAIT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// 快照信息
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SnapshotInfo {
+    /// 最近一次快照包含的最后日志索引
+    pub last_snapshot_index: u64,
+    /// 最近一次快照包含的最后日志任期
+    pub last_snapshot_term: u64,
+    /// 最近一次快照的数据大小（字节）
+    pub snapshot_size_bytes: u64,
+}
+
+impl RaftNode {
+    /// 手动触发日志压缩
+    ///
+    /// 先让Raft构建一个覆盖所有已应用日志的快照，再清理快照之前的日志
+    ///
+    /// # Returns
+    ///
+    /// 如果压缩成功返回Ok(())，否则返回错误
+    ///
+    /// # Errors
+    ///
+    /// 如果Raft未初始化、快照触发失败或在超时时间内未完成快照，返回错误
+    pub async fn trigger_log_compaction(&self) -> Result<()> {
+        let raft = self
+            .get_raft()
+            .ok_or(RaftError::NotInitialized)?;
+
+        let last_applied = raft.metrics().borrow().last_applied.map(|id| id.index);
+        info!(
+            "Triggering log compaction on node {} (last applied: {:?})",
+            self.node_id(),
+            last_applied
+        );
+
+        raft.trigger().snapshot().await.map_err(|e| {
+            ConfluxError::raft(format!("Failed to trigger snapshot: {}", e))
+        })?;
+
+        // 等待快照覆盖到触发时的已应用索引
+        let start = std::time::Instant::now();
+        let snapshot_index = loop {
+            let snapshot = raft.metrics().borrow().snapshot.map(|id| id.index);
+            if snapshot.is_some() && snapshot >= last_applied {
+                break snapshot;
+            }
+            if start.elapsed() > SNAPSHOT_WAIT_TIMEOUT {
+                return Err(ConfluxError::raft("Timeout waiting for snapshot to complete"));
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        };
+
+        if let Some(index) = snapshot_index {
+            raft.trigger().purge_log(index).await.map_err(|e| {
+                ConfluxError::raft(format!("Failed to purge logs: {}", e))
+            })?;
+            debug!("Requested log purge up to index {}", index);
+        }
+
+        // 快照已覆盖的命令持久化后，可以删除旧的WAL文件
+        let deleted_wal_files = self.store().compact_storage()?;
+        debug!("Deleted {} WAL files after compaction", deleted_wal_files);
+
+        let size = self.get_snapshot_info().await?.snapshot_size_bytes;
+        self.metrics_collector().record_compaction(size).await;
+        self.publish_event(
+            ClusterEventKind::LogCompacted,
+            json!({ "snapshot_index": snapshot_index, "snapshot_size_bytes": size }),
+        );
+
+        info!(
+            "Log compaction completed on node {} (snapshot index: {:?})",
+            self.node_id(),
+            snapshot_index
+        );
+        Ok(())
+    }
+
+    /// 获取最近一次快照的信息
+    ///
+    /// # Returns
+    ///
+    /// 返回快照的索引、任期和大小；尚未生成快照时各字段为0
+    ///
+    /// # Errors
+    ///
+    /// 如果Raft未初始化，返回错误
+    pub async fn get_snapshot_info(&self) -> Result<SnapshotInfo> {
+        let raft = self
+            .get_raft()
+            .ok_or(RaftError::NotInitialized)?;
+
+        let snapshot = raft.metrics().borrow().snapshot;
+        let snapshot_size_bytes = self.store().current_snapshot_size().await;
+
+        Ok(match snapshot {
+            Some(log_id) => SnapshotInfo {
+                last_snapshot_index: log_id.index,
+                last_snapshot_term: log_id.leader_id.term,
+                snapshot_size_bytes,
+            },
+            None => SnapshotInfo::default(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::AppConfig;
+    use crate::raft::node::test_support::app_config;
+    use crate::raft::node::NodeConfig;
+    use crate::raft::state_machine::ConfluxStateMachineWrapper;
+    use crate::raft::types::{ClientRequest, ConfigFormat, ConfigNamespace, RaftCommand};
+    use openraft::storage::{RaftSnapshotBuilder, RaftStateMachine};
+    use std::sync::Arc;
+    use tempfile::TempDir;
+
+    async fn create_started_node(temp_dir: &TempDir) -> RaftNode {
+        let app_config = app_config(temp_dir);
+
+        let mut node = RaftNode::new(NodeConfig::default(), &app_config).await.unwrap();
+        node.start().await.unwrap();
+        node.wait_for_leadership(Duration::from_secs(5)).await.unwrap();
+        node
+    }
+
+    async fn write_configs(node: &RaftNode, ids: std::ops::Range<usize>) {
+        let namespace = ConfigNamespace {
+            tenant: "tenant".to_string(),
+            app: "app".to_string(),
+            env: "prod".to_string(),
+        };
+        for i in ids {
+            node.client_write(ClientRequest {
+                command: RaftCommand::CreateConfig {
+                    namespace: namespace.clone(),
+                    name: format!("config-{}.json", i),
+                    content: b"{}".to_vec(),
+                    format: ConfigFormat::Json,
+                    schema: None,
+                    creator_id: 1,
+                    description: "snapshot test".to_string(),
+                },
+                idempotency_key: None,
+            })
+            .await
+            .unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_info_requires_raft() {
+        let temp_dir = TempDir::new().unwrap();
+        let app_config = app_config(&temp_dir);
+        let node = RaftNode::new(NodeConfig::default(), &app_config).await.unwrap();
+
+        assert!(node.get_snapshot_info().await.is_err());
+        assert!(node.trigger_log_compaction().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_trigger_log_compaction() {
+        let temp_dir = TempDir::new().unwrap();
+        let node = create_started_node(&temp_dir).await;
+        write_configs(&node, 0..5).await;
+
+        node.trigger_log_compaction().await.unwrap();
+
+        let metrics = node.get_metrics().await.unwrap();
+        let info = node.get_snapshot_info().await.unwrap();
+        assert_eq!(info.last_snapshot_index, metrics.last_applied);
+        assert!(info.last_snapshot_term >= 1);
+        assert!(info.snapshot_size_bytes > 0);
+
+        let report = node.get_comprehensive_metrics().await.unwrap();
+        assert_eq!(report.compaction_count, 1);
+    }
+
+    #[tokio::test]
+    async fn test_automatic_snapshot_after_threshold() {
+        let temp_dir = TempDir::new().unwrap();
+        let default_config = app_config(&temp_dir);
+        let app_config = AppConfig {
+            raft: crate::config::RaftConfig {
+                snapshot_threshold: 5,
+                max_applied_log_to_keep: 0,
+                ..default_config.raft
+            },
+            ..default_config
+        };
+        let mut node = RaftNode::new(NodeConfig::default(), &app_config).await.unwrap();
+        node.start().await.unwrap();
+        node.wait_for_leadership(Duration::from_secs(5)).await.unwrap();
+        let raft = node.get_raft().unwrap().clone();
+
+        let wait_for_snapshot_after = |index: u64| {
+            let raft = raft.clone();
+            async move {
+                for _ in 0..250 {
+                    let metrics = raft.metrics().borrow().clone();
+                    let snapshot = metrics.snapshot.map_or(0, |id| id.index);
+                    let purged = metrics.purged.map_or(0, |id| id.index);
+                    if snapshot > index && purged == snapshot {
+                        return snapshot;
+                    }
+                    tokio::time::sleep(Duration::from_millis(20)).await;
+                }
+                panic!("no snapshot after log index {}", index);
+            }
+        };
+
+        // 无需手动触发，跨过阈值后自动生成快照并清理日志
+        write_configs(&node, 0..6).await;
+        let first = wait_for_snapshot_after(0).await;
+
+        write_configs(&node, 6..12).await;
+        let second = wait_for_snapshot_after(first).await;
+        assert!(second >= first + 5);
+        assert_eq!(node.get_snapshot_info().await.unwrap().last_snapshot_index, second);
+    }
+
+    #[tokio::test]
+    async fn test_follower_reconciles_via_snapshot_after_compaction() {
+        let temp_dir = TempDir::new().unwrap();
+        let node = create_started_node(&temp_dir).await;
+        write_configs(&node, 0..3).await;
+        node.trigger_log_compaction().await.unwrap();
+        let leader_last_log_index = node.get_metrics().await.unwrap().last_log_index;
+
+        // 模拟一个落后的跟随者：通过快照传输追上领导者
+        let snapshot = node
+            .state_machine()
+            .unwrap()
+            .snapshot_builder()
+            .build_snapshot()
+            .await
+            .unwrap();
+
+        let follower_dir = TempDir::new().unwrap();
+        let (follower_store, _rx) = crate::raft::store::Store::new(follower_dir.path())
+            .await
+            .unwrap();
+        let follower_store = Arc::new(follower_store);
+        let mut follower_sm = ConfluxStateMachineWrapper::new(follower_store.clone());
+        follower_sm
+            .install_snapshot(&snapshot.meta, snapshot.snapshot)
+            .await
+            .unwrap();
+
+        let (follower_applied, _) = follower_sm.applied_state().await.unwrap();
+        assert_eq!(follower_applied, snapshot.meta.last_log_id);
+        assert!(follower_applied.map(|id| id.index).unwrap_or(0) <= leader_last_log_index);
+
+        // 快照包含存储数据，跟随者无需重放日志即可读取配置
+        let namespace = ConfigNamespace {
+            tenant: "tenant".to_string(),
+            app: "app".to_string(),
+            env: "prod".to_string(),
+        };
+        assert_eq!(follower_store.list_configs_in_namespace(&namespace).await.len(), 3);
+    }
+}
@@ -0,0 +1,19 @@
+//! 节点测试共用的辅助函数
+
+use crate::config::{AppConfig, StorageConfig};
+use tempfile::TempDir;
+
+/// 数据目录位于 `temp_dir` 的应用配置，RocksDB缓存和写缓冲取较小的值
+pub(crate) fn app_config(temp_dir: &TempDir) -> AppConfig {
+    AppConfig {
+        storage: StorageConfig {
+            data_dir: temp_dir.path().to_string_lossy().to_string(),
+            cache_size_mb: 8,
+            write_buffer_size_mb: 8,
+            max_write_buffer_number: 2,
+            cache_ttl_secs: 60,
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
@@ -2,6 +2,7 @@
 mod tests {
     use crate::raft::node::*;
     use crate::raft::network::NetworkConfig;
+    use crate::raft::types::DEFAULT_ELECTION_PRIORITY;
     use crate::config::AppConfig;
     use openraft::Config as RaftConfig;
     use std::collections::{BTreeSet, HashMap};
@@ -23,6 +24,10 @@ mod tests {
             election_timeout_min: 300,
             election_timeout_max: 600,
             resource_limits: ResourceLimits::default(),
+            election_priority: DEFAULT_ELECTION_PRIORITY,
+            snapshot_stream: SnapshotStreamConfig::default(),
+            pre_vote_enabled: false,
+            compress_log_entries: false,
         }
     }
 
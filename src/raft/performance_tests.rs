@@ -4,7 +4,7 @@
 mod performance_tests {
     use crate::config::AppConfig;
     use crate::raft::{
-        node::{NodeConfig, RaftNode, ResourceLimits},
+        node::{NodeConfig, RaftNode, ResourceLimits, SnapshotStreamConfig},
         types::*,
         validation::RaftInputValidator,
     };
@@ -27,10 +27,8 @@ mod performance_tests {
             },
             storage: crate::config::StorageConfig {
                 data_dir: format!("/tmp/conflux_perf_test_{}", test_id),
-                max_open_files: 1000,
                 cache_size_mb: 64,
-                write_buffer_size_mb: 64,
-                max_write_buffer_number: 3,
+                ..Default::default()
             },
             ..Default::default()
         }
@@ -52,7 +50,12 @@ mod performance_tests {
                 max_request_size: 10 * 1024 * 1024,   // 10MB
                 max_memory_usage: 1024 * 1024 * 1024, // 1GB
                 request_timeout_ms: 10000,
+                ..Default::default()
             },
+            election_priority: DEFAULT_ELECTION_PRIORITY,
+            snapshot_stream: SnapshotStreamConfig::default(),
+            pre_vote_enabled: false,
+            compress_log_entries: false,
         }
     }
 
@@ -183,16 +186,48 @@ mod performance_tests {
         );
     }
 
-    #[tokio::test]
-    async fn benchmark_metrics_collection() {
+    /// Append 100 configs with compressible content through Raft, then time
+    /// 1,000 basic metrics collections, 100 comprehensive metrics collections
+    /// and 1,000 health checks on the node
+    ///
+    /// Returns the append time, the metrics collection time and the log
+    /// compression ratio reported by the comprehensive metrics.
+    async fn time_appends_and_metrics(compress_log_entries: bool) -> (Duration, Duration, f64) {
         let app_config = create_test_app_config().await;
-        let node_config = create_test_node_config(1, 9001);
+        let mut node_config = create_test_node_config(1, 9001);
+        node_config.compress_log_entries = compress_log_entries;
 
         let mut node = RaftNode::new(node_config, &app_config).await.unwrap();
         assert!(node.start().await.is_ok());
+        node.wait_for_leadership(Duration::from_secs(5)).await.unwrap();
 
-        // Wait for initialization
-        tokio::time::sleep(Duration::from_millis(100)).await;
+        // Benchmark appending log entries large enough to be compressed
+        let content = "key = \"value\"\n".repeat(1000).into_bytes();
+        let start = Instant::now();
+        for i in 0..100 {
+            let response = node
+                .client_write(ClientRequest {
+                    command: RaftCommand::CreateConfig {
+                        namespace: ConfigNamespace {
+                            tenant: "perf".to_string(),
+                            app: "app".to_string(),
+                            env: "prod".to_string(),
+                        },
+                        name: format!("config-{}.toml", i),
+                        content: content.clone(),
+                        format: ConfigFormat::Toml,
+                        schema: None,
+                        creator_id: 1,
+                        description: "Compressible configuration".to_string(),
+                    },
+                    idempotency_key: None,
+                })
+                .await
+                .unwrap();
+            assert!(response.success, "{}", response.message);
+        }
+        let append_time = start.elapsed();
+        println!("100 log appends time: {:?}", append_time);
 
         // Benchmark basic metrics collection
         let start = Instant::now();
@@ -245,7 +280,43 @@ mod performance_tests {
             "Health checks should be fast"
         );
 
+        let (compressed, uncompressed) = node.store().log_compression_stats();
+        let report = node.get_comprehensive_metrics().await.unwrap();
+        assert!(uncompressed > 0, "Appended entries should be counted");
+        assert_eq!(
+            report.log_compression_ratio,
+            compressed as f64 / uncompressed as f64
+        );
+
         assert!(node.stop().await.is_ok());
+        (
+            append_time,
+            basic_metrics_time + comprehensive_metrics_time + health_check_time,
+            report.log_compression_ratio,
+        )
+    }
+
+    #[tokio::test]
+    async fn benchmark_metrics_collection() {
+        let (uncompressed_appends, uncompressed_metrics, uncompressed_ratio) =
+            time_appends_and_metrics(false).await;
+        let (compressed_appends, compressed_metrics, compressed_ratio) =
+            time_appends_and_metrics(true).await;
+
+        println!(
+            "Append time without / with log compression: {:?} / {:?}",
+            uncompressed_appends, compressed_appends
+        );
+        println!(
+            "Metrics collection time without / with log compression: {:?} / {:?}",
+            uncompressed_metrics, compressed_metrics
+        );
+        assert_eq!(uncompressed_ratio, 1.0);
+        assert!(
+            compressed_ratio < 0.5,
+            "Repetitive config content should compress well, got ratio {}",
+            compressed_ratio
+        );
     }
 
     #[tokio::test]
@@ -7,6 +7,7 @@ mod simple_cluster_tests {
     use crate::raft::{
         network::NetworkConfig,
         node::{NodeConfig, RaftNode},
+        types::DEFAULT_ELECTION_PRIORITY,
     };
     use openraft::Config as RaftConfig;
     use std::collections::HashMap;
@@ -35,6 +36,10 @@ mod simple_cluster_tests {
             election_timeout_min: 300,
             election_timeout_max: 600,
             resource_limits: crate::raft::node::ResourceLimits::default(),
+            election_priority: DEFAULT_ELECTION_PRIORITY,
+            snapshot_stream: crate::raft::node::SnapshotStreamConfig::default(),
+            pre_vote_enabled: false,
+            compress_log_entries: false,
         };
 
         let app_config = AppConfig {
@@ -44,6 +49,7 @@ mod simple_cluster_tests {
                 cache_size_mb: 8,
                 write_buffer_size_mb: 8,
                 max_write_buffer_number: 2,
+                ..Default::default()
             },
             ..Default::default()
         };
@@ -107,6 +113,10 @@ mod simple_cluster_tests {
             election_timeout_min: 300,
             election_timeout_max: 600,
             resource_limits: crate::raft::node::ResourceLimits::default(),
+            election_priority: DEFAULT_ELECTION_PRIORITY,
+            snapshot_stream: crate::raft::node::SnapshotStreamConfig::default(),
+            pre_vote_enabled: false,
+            compress_log_entries: false,
         };
 
         let app_config1 = AppConfig {
@@ -116,6 +126,7 @@ mod simple_cluster_tests {
                 cache_size_mb: 8,
                 write_buffer_size_mb: 8,
                 max_write_buffer_number: 2,
+                ..Default::default()
             },
             ..Default::default()
         };
@@ -130,6 +141,10 @@ mod simple_cluster_tests {
             election_timeout_min: 300,
             election_timeout_max: 600,
             resource_limits: crate::raft::node::ResourceLimits::default(),
+            election_priority: DEFAULT_ELECTION_PRIORITY,
+            snapshot_stream: crate::raft::node::SnapshotStreamConfig::default(),
+            pre_vote_enabled: false,
+            compress_log_entries: false,
         };
 
         let app_config2 = AppConfig {
@@ -139,6 +154,7 @@ mod simple_cluster_tests {
                 cache_size_mb: 8,
                 write_buffer_size_mb: 8,
                 max_write_buffer_number: 2,
+                ..Default::default()
             },
             ..Default::default()
         };
@@ -184,6 +200,10 @@ mod simple_cluster_tests {
             election_timeout_min: 300,
             election_timeout_max: 600,
             resource_limits: crate::raft::node::ResourceLimits::default(),
+            election_priority: DEFAULT_ELECTION_PRIORITY,
+            snapshot_stream: crate::raft::node::SnapshotStreamConfig::default(),
+            pre_vote_enabled: false,
+            compress_log_entries: false,
         };
 
         let app_config = AppConfig {
@@ -193,6 +213,7 @@ mod simple_cluster_tests {
                 cache_size_mb: 8,
                 write_buffer_size_mb: 8,
                 max_write_buffer_number: 2,
+                ..Default::default()
             },
             ..Default::default()
         };
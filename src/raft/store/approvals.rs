@@ -0,0 +1,227 @@
+use crate::error::{ConfluxError, Result};
+use crate::raft::types::*;
+use super::constants::CF_APPROVALS;
+use super::types::Store;
+use rocksdb::{Direction, IteratorMode, WriteBatch, DB};
+use tracing::warn;
+
+/// Error returned when releasing a gated version without an approval
+pub const APPROVAL_REQUIRED: &str = "approval required";
+
+/// Key prefix of approval records, followed by the big-endian approval ID
+const APPROVAL_PREFIX: u8 = 0x01;
+/// Key prefix of namespaces that require approval, followed by the namespace path
+const APPROVAL_REQUIRED_PREFIX: u8 = 0x02;
+
+impl Store {
+    /// Handle set approval required command
+    pub(crate) async fn handle_set_approval_required(
+        &self,
+        namespace: &ConfigNamespace,
+        required: bool,
+    ) -> Result<ClientWriteResponse> {
+        let cf = approvals_cf(&self.db)?;
+        let key = approval_required_key(namespace);
+        self.write_with(|batch| {
+            if required {
+                batch.put_cf(cf, key, []);
+            } else {
+                batch.delete_cf(cf, key);
+            }
+            Ok(())
+        })?;
+
+        Ok(Self::create_success_response(
+            format!(
+                "Release approval {} for namespace {}",
+                if required { "required" } else { "not required" },
+                namespace
+            ),
+            Some(serde_json::json!({
+                "namespace": namespace,
+                "requires_approval": required
+            })),
+        ))
+    }
+
+    /// Handle request release approval command
+    ///
+    /// Only one pending request may exist per version.
+    pub(crate) async fn handle_request_release_approval(
+        &self,
+        config_id: u64,
+        version_id: u64,
+        requested_by: u64,
+    ) -> Result<ClientWriteResponse> {
+        if self.validate_version_exists(config_id, version_id).await.is_err() {
+            return Ok(Self::create_error_response(format!(
+                "Version {} does not exist for config {}",
+                version_id, config_id
+            )));
+        }
+
+        let approvals = scan_approvals(&self.db)?;
+        if approvals
+            .iter()
+            .any(|a| a.is_for(config_id, version_id) && a.status == ApprovalStatus::Pending)
+        {
+            return Ok(Self::create_error_response(format!(
+                "An approval request for version {} of config {} is already pending",
+                version_id, config_id
+            )));
+        }
+
+        let id = approvals.iter().map(|a| a.id).max().unwrap_or(0) + 1;
+        let approval = ConfigApproval::new(id, config_id, version_id, requested_by);
+        self.write_with(|batch| put_approval(&self.db, batch, &approval))?;
+
+        Ok(Self::create_success_response(
+            format!(
+                "Requested approval {} for version {} of config {}",
+                id, version_id, config_id
+            ),
+            serde_json::to_value(&approval).ok(),
+        ))
+    }
+
+    /// Handle approve and reject release commands
+    ///
+    /// Resolves the pending request of the version with `status`. The
+    /// requester cannot review their own request.
+    pub(crate) async fn handle_review_release(
+        &self,
+        config_id: u64,
+        version_id: u64,
+        approver_id: u64,
+        status: ApprovalStatus,
+    ) -> Result<ClientWriteResponse> {
+        let pending = scan_approvals(&self.db)?
+            .into_iter()
+            .find(|a| a.is_for(config_id, version_id) && a.status == ApprovalStatus::Pending);
+        let Some(mut approval) = pending else {
+            return Ok(Self::create_error_response(format!(
+                "No pending approval request found for version {} of config {}",
+                version_id, config_id
+            )));
+        };
+
+        if approval.requested_by == approver_id {
+            return Ok(Self::create_error_response(format!(
+                "User {} cannot review their own approval request",
+                approver_id
+            )));
+        }
+
+        approval.approver_id = Some(approver_id);
+        approval.status = status;
+        self.write_with(|batch| put_approval(&self.db, batch, &approval))?;
+
+        Ok(Self::create_success_response(
+            format!(
+                "Approval {} for version {} of config {} is {:?}",
+                approval.id, version_id, config_id, status
+            ),
+            serde_json::to_value(&approval).ok(),
+        ))
+    }
+
+    /// Whether releases in `namespace` need an approved approval request
+    pub fn is_approval_required(&self, namespace: &ConfigNamespace) -> bool {
+        let result = approvals_cf(&self.db).and_then(|cf| {
+            self.db
+                .get_pinned_cf(cf, approval_required_key(namespace))
+                .map_err(|e| ConfluxError::storage(format!("Failed to read approval policy: {}", e)))
+        });
+        match result {
+            Ok(value) => value.is_some(),
+            Err(e) => {
+                warn!("Failed to read approval policy of {}: {}", namespace, e);
+                false
+            }
+        }
+    }
+
+    /// All approval requests of a config, oldest first
+    pub async fn get_approvals(&self, config_id: u64) -> Vec<ConfigApproval> {
+        match scan_approvals(&self.db) {
+            Ok(approvals) => approvals
+                .into_iter()
+                .filter(|a| a.config_id == config_id)
+                .collect(),
+            Err(e) => {
+                warn!("Failed to read approvals of config {}: {}", config_id, e);
+                Vec::new()
+            }
+        }
+    }
+
+    /// Reject releasing a version of a gated namespace that was not approved
+    pub async fn check_release_approval(
+        &self,
+        namespace: &ConfigNamespace,
+        config_id: u64,
+        version_id: u64,
+    ) -> Result<()> {
+        if !self.is_approval_required(namespace) {
+            return Ok(());
+        }
+        let approved = scan_approvals(&self.db)?
+            .iter()
+            .any(|a| a.is_for(config_id, version_id) && a.status == ApprovalStatus::Approved);
+        if approved {
+            Ok(())
+        } else {
+            Err(ConfluxError::validation(APPROVAL_REQUIRED))
+        }
+    }
+}
+
+fn approvals_cf(db: &DB) -> Result<&rocksdb::ColumnFamily> {
+    db.cf_handle(CF_APPROVALS)
+        .ok_or_else(|| ConfluxError::storage("Approvals column family not found"))
+}
+
+fn approval_key(id: u64) -> [u8; 9] {
+    let mut key = [APPROVAL_PREFIX; 9];
+    key[1..].copy_from_slice(&id.to_be_bytes());
+    key
+}
+
+fn approval_required_key(namespace: &ConfigNamespace) -> Vec<u8> {
+    let mut key = vec![APPROVAL_REQUIRED_PREFIX];
+    key.extend_from_slice(namespace.to_string().as_bytes());
+    key
+}
+
+fn put_approval(db: &DB, batch: &mut WriteBatch, approval: &ConfigApproval) -> Result<()> {
+    let cf = approvals_cf(db)?;
+    let data = serde_json::to_vec(approval).map_err(|e| {
+        ConfluxError::storage(format!("Failed to serialize approval: {}", e))
+    })?;
+    batch.put_cf(cf, approval_key(approval.id), data);
+    Ok(())
+}
+
+/// Read all approval records in ID order
+fn scan_approvals(db: &DB) -> Result<Vec<ConfigApproval>> {
+    let cf = approvals_cf(db)?;
+    let mut approvals = Vec::new();
+    let mode = IteratorMode::From(&[APPROVAL_PREFIX], Direction::Forward);
+    for item in db.iterator_cf(cf, mode) {
+        let (key, value) = item.map_err(|e| {
+            ConfluxError::storage(format!("Failed to read approval: {}", e))
+        })?;
+        if key.first() != Some(&APPROVAL_PREFIX) {
+            break;
+        }
+        let approval = serde_json::from_slice(&value).map_err(|e| {
+            ConfluxError::storage(format!("Failed to deserialize approval: {}", e))
+        })?;
+        approvals.push(approval);
+    }
+    Ok(approvals)
+}
+
+#[cfg(test)]
+#[path = "approvals_tests.rs"]
+mod tests;
@@ -0,0 +1,166 @@
+use super::*;
+use tempfile::{tempdir, TempDir};
+
+fn namespace() -> ConfigNamespace {
+    ConfigNamespace {
+        tenant: "tenant".to_string(),
+        app: "app".to_string(),
+        env: "prod".to_string(),
+    }
+}
+
+/// Create a config with a second version in a namespace that requires approval
+async fn create_gated_config() -> (Store, TempDir, u64) {
+    let dir = tempdir().unwrap();
+    let (store, _rx) = Store::new(dir.path()).await.unwrap();
+    let response = store
+        .apply_command(&RaftCommand::CreateConfig {
+            namespace: namespace(),
+            name: "app.json".to_string(),
+            content: b"{}".to_vec(),
+            format: ConfigFormat::Json,
+            schema: None,
+            creator_id: 1,
+            description: "initial".to_string(),
+        })
+        .await
+        .unwrap();
+    let config_id = response.config_id.unwrap();
+    store
+        .apply_command(&RaftCommand::CreateVersion {
+            config_id,
+            content: br#"{"v":2}"#.to_vec(),
+            format: None,
+            creator_id: 1,
+            description: "second".to_string(),
+        })
+        .await
+        .unwrap();
+    let response = store
+        .apply_command(&RaftCommand::SetApprovalRequired {
+            namespace: namespace(),
+            required: true,
+        })
+        .await
+        .unwrap();
+    assert!(response.success);
+    (store, dir, config_id)
+}
+
+async fn release(store: &Store, config_id: u64, version_id: u64) -> Result<ClientWriteResponse> {
+    store
+        .apply_command(&RaftCommand::ReleaseVersion { config_id, version_id })
+        .await
+}
+
+async fn request_approval(store: &Store, config_id: u64, requested_by: u64) -> ClientWriteResponse {
+    store
+        .apply_command(&RaftCommand::RequestReleaseApproval {
+            config_id,
+            version_id: 2,
+            requested_by,
+        })
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_approved_release_succeeds() {
+    let (store, _dir, config_id) = create_gated_config().await;
+    assert!(store.is_approval_required(&namespace()));
+
+    let err = release(&store, config_id, 2).await.unwrap_err();
+    assert!(matches!(err, ConfluxError::Validation(ref msg) if msg == APPROVAL_REQUIRED));
+
+    assert!(request_approval(&store, config_id, 1).await.success);
+    // A second request for the same version is rejected while the first is pending
+    assert!(!request_approval(&store, config_id, 1).await.success);
+    // Pending approvals do not unlock the release
+    assert!(release(&store, config_id, 2).await.is_err());
+
+    // The requester cannot approve their own request
+    let self_approval = store
+        .apply_command(&RaftCommand::ApproveRelease {
+            config_id,
+            version_id: 2,
+            approver_id: 1,
+        })
+        .await
+        .unwrap();
+    assert!(!self_approval.success);
+
+    let response = store
+        .apply_command(&RaftCommand::ApproveRelease {
+            config_id,
+            version_id: 2,
+            approver_id: 2,
+        })
+        .await
+        .unwrap();
+    assert!(response.success);
+
+    let approvals = store.get_approvals(config_id).await;
+    assert_eq!(approvals.len(), 1);
+    assert_eq!(approvals[0].status, ApprovalStatus::Approved);
+    assert_eq!(approvals[0].requested_by, 1);
+    assert_eq!(approvals[0].approver_id, Some(2));
+
+    assert!(release(&store, config_id, 2).await.unwrap().success);
+    let config = store.get_config_meta(config_id).await.unwrap();
+    assert_eq!(config.releases[0].version_id, 2);
+
+    // The approval only covers version 2
+    let err = release(&store, config_id, 1).await.unwrap_err();
+    assert!(err.to_string().contains(APPROVAL_REQUIRED));
+}
+
+#[tokio::test]
+async fn test_rejected_release_stays_blocked() {
+    let (store, _dir, config_id) = create_gated_config().await;
+
+    assert!(request_approval(&store, config_id, 1).await.success);
+    let response = store
+        .apply_command(&RaftCommand::RejectRelease {
+            config_id,
+            version_id: 2,
+            approver_id: 2,
+        })
+        .await
+        .unwrap();
+    assert!(response.success);
+
+    let approvals = store.get_approvals(config_id).await;
+    assert_eq!(approvals[0].status, ApprovalStatus::Rejected);
+    assert!(release(&store, config_id, 2).await.is_err());
+
+    // Nothing is pending anymore, so approving now fails
+    let response = store
+        .apply_command(&RaftCommand::ApproveRelease {
+            config_id,
+            version_id: 2,
+            approver_id: 2,
+        })
+        .await
+        .unwrap();
+    assert!(!response.success);
+
+    // A new request can be opened after a rejection and gets a new ID
+    let response = request_approval(&store, config_id, 1).await;
+    assert!(response.success);
+    assert_eq!(response.data.unwrap()["id"], 2);
+}
+
+#[tokio::test]
+async fn test_ungated_namespace_releases_freely() {
+    let (store, _dir, config_id) = create_gated_config().await;
+    store
+        .apply_command(&RaftCommand::SetApprovalRequired {
+            namespace: namespace(),
+            required: false,
+        })
+        .await
+        .unwrap();
+
+    assert!(!store.is_approval_required(&namespace()));
+    assert!(release(&store, config_id, 2).await.unwrap().success);
+}
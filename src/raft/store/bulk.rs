@@ -0,0 +1,68 @@
+use crate::error::Result;
+use crate::raft::types::*;
+use super::types::Store;
+use tracing::{info, warn};
+
+impl Store {
+    /// Handle bulk create configs command
+    ///
+    /// Creates the entries in order. An entry that fails, for instance because
+    /// the config already exists or its content is too large, is reported in
+    /// [`BulkCreateResponse::errors`] and does not stop the remaining entries.
+    pub(crate) async fn handle_bulk_create_configs(
+        &self,
+        entries: &[BulkConfigEntry],
+    ) -> Result<ClientWriteResponse> {
+        if entries.is_empty() {
+            return Ok(Self::create_error_response(
+                "Bulk create contains no configs".to_string(),
+            ));
+        }
+
+        let mut outcome = BulkCreateResponse::default();
+        for entry in entries {
+            let result = self
+                .handle_create_config(
+                    &entry.namespace,
+                    &entry.name,
+                    &entry.content,
+                    &entry.format,
+                    &entry.schema,
+                    &entry.creator_id,
+                    &entry.description,
+                )
+                .await;
+            let error = match result {
+                Ok(response) if response.success => match response.config_id {
+                    Some(config_id) => {
+                        outcome.created.push(config_id);
+                        continue;
+                    }
+                    None => "Created config has no ID".to_string(),
+                },
+                Ok(response) => response.message,
+                Err(e) => e.to_string(),
+            };
+            let key = format!("{}/{}", entry.namespace, entry.name);
+            warn!("Bulk create of {} failed: {}", key, error);
+            outcome.errors.push((key, error));
+        }
+
+        info!(
+            "Bulk created {} of {} configs",
+            outcome.created.len(),
+            entries.len()
+        );
+        let message = format!(
+            "Created {} of {} configurations",
+            outcome.created.len(),
+            entries.len()
+        );
+        let data = serde_json::to_value(&outcome).ok();
+        Ok(Self::create_success_response(message, data))
+    }
+}
+
+#[cfg(test)]
+#[path = "bulk_tests.rs"]
+mod tests;
@@ -0,0 +1,64 @@
+use super::*;
+use tempfile::tempdir;
+
+fn entry(env: &str, name: &str) -> BulkConfigEntry {
+    BulkConfigEntry {
+        namespace: ConfigNamespace {
+            tenant: "acme".to_string(),
+            app: "app".to_string(),
+            env: env.to_string(),
+        },
+        name: name.to_string(),
+        content: br#"{"v":1}"#.to_vec(),
+        format: ConfigFormat::Json,
+        schema: None,
+        creator_id: 1,
+        description: "imported".to_string(),
+    }
+}
+
+#[tokio::test]
+async fn test_bulk_create_reports_failed_entries_without_aborting() {
+    let dir = tempdir().unwrap();
+    let (store, _rx) = Store::new(dir.path()).await.unwrap();
+
+    let response = store
+        .apply_command(&RaftCommand::BulkCreateConfigs {
+            entries: vec![
+                entry("prod", "app.json"),
+                entry("staging", "app.json"),
+                // Duplicate of the first entry
+                entry("prod", "app.json"),
+                entry("prod", "db.json"),
+            ],
+        })
+        .await
+        .unwrap();
+    assert!(response.success);
+
+    let outcome: BulkCreateResponse = serde_json::from_value(response.data.unwrap()).unwrap();
+    assert_eq!(outcome.created.len(), 3);
+    assert_eq!(outcome.errors.len(), 1);
+    assert_eq!(outcome.errors[0].0, "acme/app/prod/app.json");
+    assert!(outcome.errors[0].1.contains("already exists"));
+
+    let staging = entry("staging", "app.json").namespace;
+    let created = store.get_config(&staging, "app.json").await.unwrap();
+    assert_eq!(created.id, outcome.created[1]);
+    assert_eq!(
+        store.get_config_version(created.id, 1).await.unwrap().content,
+        br#"{"v":1}"#.to_vec()
+    );
+}
+
+#[tokio::test]
+async fn test_bulk_create_without_entries_is_rejected() {
+    let dir = tempdir().unwrap();
+    let (store, _rx) = Store::new(dir.path()).await.unwrap();
+
+    let response = store
+        .apply_command(&RaftCommand::BulkCreateConfigs { entries: vec![] })
+        .await
+        .unwrap();
+    assert!(!response.success);
+}
@@ -0,0 +1,130 @@
+use super::*;
+use tempfile::tempdir;
+
+fn namespace() -> ConfigNamespace {
+    ConfigNamespace {
+        tenant: "acme".to_string(),
+        app: "app".to_string(),
+        env: "prod".to_string(),
+    }
+}
+
+async fn create_chain(store: &Store) -> u64 {
+    let config_id = store
+        .apply_command(&RaftCommand::CreateConfig {
+            namespace: namespace(),
+            name: "app.json".to_string(),
+            content: br#"{"v":1}"#.to_vec(),
+            format: ConfigFormat::Json,
+            schema: None,
+            creator_id: 1,
+            description: "v1".to_string(),
+        })
+        .await
+        .unwrap()
+        .config_id
+        .unwrap();
+    for v in 2..=4 {
+        store
+            .apply_command(&RaftCommand::CreateVersion {
+                config_id,
+                content: format!(r#"{{"v":{}}}"#, v).into_bytes(),
+                format: None,
+                creator_id: 1,
+                description: format!("v{}", v),
+            })
+            .await
+            .unwrap();
+    }
+    config_id
+}
+
+#[tokio::test]
+async fn test_versions_are_chained_to_their_predecessor() {
+    let dir = tempdir().unwrap();
+    let (store, _rx) = Store::new(dir.path()).await.unwrap();
+    let config_id = create_chain(&store).await;
+
+    let first = store.get_config_version(config_id, 1).await.unwrap();
+    let second = store.get_config_version(config_id, 2).await.unwrap();
+    assert_eq!(first.previous_hash, None);
+    assert_eq!(second.previous_hash, Some(first.content_hash));
+
+    let report = store.verify_version_chain(config_id).await.unwrap();
+    assert!(report.is_valid());
+    assert_eq!(report.versions_checked, 4);
+    assert!(report.unchained_versions.is_empty());
+
+    assert!(matches!(
+        store.verify_version_chain(config_id + 1).await,
+        Err(ConfluxError::NotFound(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_tampered_version_breaks_the_chain() {
+    let dir = tempdir().unwrap();
+    let (store, _rx) = Store::new(dir.path()).await.unwrap();
+    let config_id = create_chain(&store).await;
+
+    store
+        .versions
+        .write()
+        .await
+        .get_mut(&config_id)
+        .unwrap()
+        .get_mut(&2)
+        .unwrap()
+        .content = br#"{"v":"tampered"}"#.to_vec();
+
+    let report = store.verify_version_chain(config_id).await.unwrap();
+    assert!(!report.is_valid());
+    assert_eq!(report.tampered_versions, vec![2]);
+    assert_eq!(report.broken_links, vec![3]);
+
+    // Reads are only checked once verification on read is enabled
+    assert!(store.get_config_version(config_id, 2).await.is_some());
+    store.set_verify_chain_on_read(true);
+    assert!(store.get_config_version(config_id, 2).await.is_none());
+    assert!(store.get_config_version(config_id, 3).await.is_none());
+    assert!(store.get_config_version(config_id, 4).await.is_some());
+}
+
+#[tokio::test]
+async fn test_new_versions_chain_to_a_tampered_predecessor() {
+    let dir = tempdir().unwrap();
+    let (store, _rx) = Store::new(dir.path()).await.unwrap();
+    let config_id = create_chain(&store).await;
+    store.set_verify_chain_on_read(true);
+
+    let tampered = br#"{"v":"tampered"}"#.to_vec();
+    store
+        .versions
+        .write()
+        .await
+        .get_mut(&config_id)
+        .unwrap()
+        .get_mut(&4)
+        .unwrap()
+        .content = tampered.clone();
+    assert!(store.get_latest_version(config_id).await.is_none());
+
+    let response = store
+        .apply_command(&RaftCommand::CreateVersion {
+            config_id,
+            content: br#"{"v":5}"#.to_vec(),
+            format: None,
+            creator_id: 1,
+            description: "v5".to_string(),
+        })
+        .await
+        .unwrap();
+    assert!(response.success, "{}", response.message);
+
+    // The new version links to the stored predecessor instead of starting a new chain
+    let fifth = store.get_config_version(config_id, 5).await.unwrap();
+    assert_eq!(fifth.previous_hash, Some(ConfigVersion::hash_content(&tampered)));
+    let report = store.verify_version_chain(config_id).await.unwrap();
+    assert_eq!(report.tampered_versions, vec![4]);
+    assert!(report.unchained_versions.is_empty());
+}
@@ -0,0 +1,126 @@
+use crate::error::{ConfluxError, Result};
+use crate::raft::types::*;
+use super::types::Store;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info, warn};
+
+/// Outcome of cloning the configs of one namespace into another
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CloneReport {
+    /// Configs created or updated in the destination namespace
+    pub cloned: usize,
+    /// Configs left untouched because they already exist or are identical
+    pub skipped: usize,
+    /// Configs that could not be cloned
+    pub failed: usize,
+    /// Names of the configs that failed, with the reason
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub failures: Vec<String>,
+}
+
+impl Store {
+    /// Copy the latest version of every config in `src` into `dst`
+    ///
+    /// Configs missing from `dst` are created. Existing configs are skipped
+    /// unless `overwrite` is set, in which case they receive a new version
+    /// with the source content. Configs whose content already matches are
+    /// always skipped, so repeated clones are idempotent.
+    pub async fn clone_namespace(
+        &self,
+        src: &ConfigNamespace,
+        dst: &ConfigNamespace,
+        overwrite: bool,
+    ) -> Result<CloneReport> {
+        if src == dst {
+            return Err(ConfluxError::validation(
+                "Source and destination namespaces must differ",
+            ));
+        }
+
+        let mut configs = self.list_configs_in_namespace(src).await;
+        configs.sort_by(|a, b| a.name.cmp(&b.name));
+        info!(
+            "Cloning {} configs from {}/{}/{} to {}/{}/{} (overwrite: {})",
+            configs.len(),
+            src.tenant,
+            src.app,
+            src.env,
+            dst.tenant,
+            dst.app,
+            dst.env,
+            overwrite
+        );
+
+        let mut report = CloneReport::default();
+        for config in configs {
+            match self.clone_config(&config, src, dst, overwrite).await {
+                Ok(true) => report.cloned += 1,
+                Ok(false) => report.skipped += 1,
+                Err(e) => {
+                    warn!("Failed to clone config {}: {}", config.name, e);
+                    report.failed += 1;
+                    report.failures.push(format!("{}: {}", config.name, e));
+                }
+            }
+        }
+
+        info!("Namespace clone finished: {:?}", report);
+        Ok(report)
+    }
+
+    /// Clone a single config, returning whether anything was written
+    async fn clone_config(
+        &self,
+        config: &Config,
+        src: &ConfigNamespace,
+        dst: &ConfigNamespace,
+        overwrite: bool,
+    ) -> Result<bool> {
+        let latest = self.get_latest_version(config.id).await.ok_or_else(|| {
+            ConfluxError::storage(format!("No versions found for config {}", config.id))
+        })?;
+        let description = format!("Cloned from {}/{}/{}", src.tenant, src.app, src.env);
+
+        let command = match self.get_config(dst, &config.name).await {
+            None => RaftCommand::CreateConfig {
+                namespace: dst.clone(),
+                name: config.name.clone(),
+                content: latest.content,
+                format: latest.format,
+                schema: config.schema.clone(),
+                creator_id: latest.creator_id,
+                description,
+            },
+            Some(existing) => {
+                let unchanged = self
+                    .get_latest_version(existing.id)
+                    .await
+                    .is_some_and(|version| version.content_hash == latest.content_hash);
+                if !overwrite || unchanged {
+                    debug!("Skipping config {} (unchanged: {})", config.name, unchanged);
+                    return Ok(false);
+                }
+
+                RaftCommand::UpdateConfig {
+                    config_id: existing.id,
+                    namespace: dst.clone(),
+                    name: config.name.clone(),
+                    content: latest.content,
+                    format: latest.format,
+                    schema: config.schema.clone(),
+                    description,
+                }
+            }
+        };
+
+        let response = self.submit_command(command).await?;
+        if !response.success {
+            return Err(ConfluxError::storage(response.message));
+        }
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+#[path = "clone_tests.rs"]
+mod tests;
@@ -0,0 +1,112 @@
+use super::*;
+use crate::raft::store::test_support::create_managed_store;
+
+const CONFIG_COUNT: usize = 25;
+
+fn namespace(env: &str) -> ConfigNamespace {
+    ConfigNamespace {
+        tenant: "tenant".to_string(),
+        app: "app".to_string(),
+        env: env.to_string(),
+    }
+}
+
+async fn create_config(store: &Store, namespace: &ConfigNamespace, name: &str, content: &str) {
+    let response = store
+        .apply_command(&RaftCommand::CreateConfig {
+            namespace: namespace.clone(),
+            name: name.to_string(),
+            content: content.as_bytes().to_vec(),
+            format: ConfigFormat::Json,
+            schema: None,
+            creator_id: 1,
+            description: "initial".to_string(),
+        })
+        .await
+        .unwrap();
+    assert!(response.success);
+}
+
+async fn populate_staging(store: &Store) {
+    for i in 0..CONFIG_COUNT {
+        let name = format!("config-{:02}.json", i);
+        create_config(store, &namespace("staging"), &name, &format!("{{\"id\":{}}}", i)).await;
+    }
+}
+
+async fn latest_content(store: &Store, namespace: &ConfigNamespace, name: &str) -> Vec<u8> {
+    let config = store.get_config(namespace, name).await.unwrap();
+    store.get_latest_version(config.id).await.unwrap().content
+}
+
+#[tokio::test]
+async fn test_clone_namespace_copies_all_configs() {
+    let (store, _dir) = create_managed_store().await;
+    populate_staging(&store).await;
+
+    let report = store
+        .clone_namespace(&namespace("staging"), &namespace("production"), false)
+        .await
+        .unwrap();
+    assert_eq!(report.cloned, CONFIG_COUNT);
+    assert_eq!(report.skipped, 0);
+    assert_eq!(report.failed, 0);
+
+    let cloned = store.list_configs_in_namespace(&namespace("production")).await;
+    assert_eq!(cloned.len(), CONFIG_COUNT);
+    assert_eq!(
+        latest_content(&store, &namespace("production"), "config-07.json").await,
+        b"{\"id\":7}".to_vec()
+    );
+}
+
+#[tokio::test]
+async fn test_clone_namespace_is_idempotent() {
+    let (store, _dir) = create_managed_store().await;
+    populate_staging(&store).await;
+    let (src, dst) = (namespace("staging"), namespace("production"));
+
+    store.clone_namespace(&src, &dst, false).await.unwrap();
+    let versions_before = store.get_storage_stats().await.unwrap().versions_count;
+
+    for overwrite in [false, true] {
+        let report = store.clone_namespace(&src, &dst, overwrite).await.unwrap();
+        assert_eq!(report.cloned, 0);
+        assert_eq!(report.skipped, CONFIG_COUNT);
+    }
+
+    // Identical content never creates new versions
+    let versions_after = store.get_storage_stats().await.unwrap().versions_count;
+    assert_eq!(versions_before, versions_after);
+}
+
+#[tokio::test]
+async fn test_clone_namespace_overwrite_updates_changed_configs() {
+    let (store, _dir) = create_managed_store().await;
+    populate_staging(&store).await;
+    let (src, dst) = (namespace("staging"), namespace("production"));
+    create_config(&store, &dst, "config-00.json", "{\"id\":\"prod\"}").await;
+
+    let report = store.clone_namespace(&src, &dst, false).await.unwrap();
+    assert_eq!(report.cloned, CONFIG_COUNT - 1);
+    assert_eq!(report.skipped, 1);
+    assert_eq!(
+        latest_content(&store, &dst, "config-00.json").await,
+        b"{\"id\":\"prod\"}".to_vec()
+    );
+
+    let report = store.clone_namespace(&src, &dst, true).await.unwrap();
+    assert_eq!(report.cloned, 1);
+    assert_eq!(report.skipped, CONFIG_COUNT - 1);
+    assert_eq!(
+        latest_content(&store, &dst, "config-00.json").await,
+        b"{\"id\":0}".to_vec()
+    );
+}
+
+#[tokio::test]
+async fn test_clone_namespace_rejects_same_namespace() {
+    let (store, _dir) = create_managed_store().await;
+    let ns = namespace("staging");
+    assert!(store.clone_namespace(&ns, &ns, false).await.is_err());
+}
@@ -1,6 +1,9 @@
 use crate::error::Result;
 use crate::raft::types::*;
+use super::super::scheduler::ScheduledRelease;
 use super::super::types::{Store, ConfigChangeEvent, ConfigChangeType};
+use chrono::{DateTime, Utc};
+use std::collections::BTreeMap;
 
 impl Store {
     /// Handle update release rules command
@@ -22,6 +25,12 @@ impl Store {
 
         // Validate release rules - check if all referenced versions exist
         for release in releases {
+            if let Some(percent) = release.canary_percent.filter(|p| *p > 100) {
+                return Ok(Self::create_error_response(format!(
+                    "Canary percent must be between 0 and 100, got {}",
+                    percent
+                )));
+            }
             if let Err(_) = self.validate_version_exists(*config_id, release.version_id).await {
                 return Ok(Self::create_error_response(format!(
                     "Version {} does not exist for config {}",
@@ -46,7 +55,7 @@ impl Store {
         }
 
         // Send notification using config info we already have
-        let _ = self.change_notifier.send(ConfigChangeEvent {
+        self.notify_change(ConfigChangeEvent {
             config_id: *config_id,
             namespace: config.namespace.clone(),
             name: config.name.clone(),
@@ -62,4 +71,267 @@ impl Store {
             })),
         ))
     }
+
+    /// Handle schedule release command
+    ///
+    /// Adds a release with `effective_at` set to the config (ignored by reads until
+    /// it takes effect) and records it in the scheduled column family so the
+    /// leader can propose its activation once it is due.
+    pub(crate) async fn handle_schedule_release(
+        &self,
+        config_id: &u64,
+        version_id: &u64,
+        labels: &BTreeMap<String, String>,
+        effective_at: &DateTime<Utc>,
+    ) -> Result<ClientWriteResponse> {
+        let (config_key, config) = match self.find_config_by_id(*config_id).await {
+            Ok((key, config)) => (key, config),
+            Err(_) => {
+                return Ok(Self::create_error_response(format!(
+                    "Configuration with ID {} not found",
+                    config_id
+                )));
+            }
+        };
+
+        if self.validate_version_exists(*config_id, *version_id).await.is_err() {
+            return Ok(Self::create_error_response(format!(
+                "Version {} does not exist for config {}",
+                version_id, config_id
+            )));
+        }
+
+        // Keep the priority of the release being replaced
+        let priority = config
+            .releases
+            .iter()
+            .find(|r| r.labels == *labels && r.effective_at.is_none())
+            .map(|r| r.priority)
+            .unwrap_or(0);
+
+        {
+            let mut configs = self.configurations.write().await;
+            if let Some(config) = configs.get_mut(&config_key) {
+                config.releases.push(Release {
+                    labels: labels.clone(),
+                    version_id: *version_id,
+                    priority,
+                    effective_at: Some(*effective_at),
+                    canary_percent: None,
+                    weight: 1,
+                });
+                config.updated_at = Utc::now();
+                if let Err(e) = self.persist_config(&config_key, config).await {
+                    return Ok(Self::create_error_response(format!(
+                        "Failed to persist config update: {}", e
+                    )));
+                }
+            }
+        }
+
+        self.persist_scheduled_release(&ScheduledRelease {
+            config_id: *config_id,
+            version_id: *version_id,
+            labels: labels.clone(),
+            effective_at: *effective_at,
+        })
+        .await?;
+
+        self.notify_change(ConfigChangeEvent {
+            config_id: *config_id,
+            namespace: config.namespace.clone(),
+            name: config.name.clone(),
+            version_id: *version_id,
+            change_type: ConfigChangeType::ReleaseUpdated,
+        });
+
+        Ok(Self::create_success_response(
+            format!("Version {} scheduled for release at {}", version_id, effective_at),
+            Some(serde_json::json!({
+                "config_id": config_id,
+                "version_id": version_id,
+                "effective_at": effective_at
+            })),
+        ))
+    }
+
+    /// Handle set canary percent command
+    ///
+    /// Updates the canary release of `version_id` with these labels, or adds one
+    /// with a priority above every release that also matches its clients so it
+    /// is checked first; clients it does not select fall through to them.
+    pub(crate) async fn handle_set_canary_percent(
+        &self,
+        config_id: &u64,
+        version_id: &u64,
+        labels: &BTreeMap<String, String>,
+        percent: u8,
+    ) -> Result<ClientWriteResponse> {
+        if percent > 100 {
+            return Ok(Self::create_error_response(format!(
+                "Canary percent must be between 0 and 100, got {}",
+                percent
+            )));
+        }
+
+        let (config_key, config) = match self.find_config_by_id(*config_id).await {
+            Ok((key, config)) => (key, config),
+            Err(_) => {
+                return Ok(Self::create_error_response(format!(
+                    "Configuration with ID {} not found",
+                    config_id
+                )));
+            }
+        };
+
+        if self.validate_version_exists(*config_id, *version_id).await.is_err() {
+            return Ok(Self::create_error_response(format!(
+                "Version {} does not exist for config {}",
+                version_id, config_id
+            )));
+        }
+
+        {
+            let mut configs = self.configurations.write().await;
+            if let Some(config) = configs.get_mut(&config_key) {
+                let existing = config.releases.iter_mut().find(|r| {
+                    r.labels == *labels
+                        && r.version_id == *version_id
+                        && r.canary_percent.is_some()
+                        && r.effective_at.is_none()
+                });
+                match existing {
+                    Some(release) => release.canary_percent = Some(percent),
+                    None => {
+                        let priority = config
+                            .releases
+                            .iter()
+                            .filter(|r| r.matches(labels))
+                            .map(|r| r.priority)
+                            .max()
+                            .map_or(0, |p| p.saturating_add(1));
+                        let mut release = Release::new(labels.clone(), *version_id, priority);
+                        release.canary_percent = Some(percent);
+                        config.releases.push(release);
+                    }
+                }
+                config.updated_at = Utc::now();
+                if let Err(e) = self.persist_config(&config_key, config).await {
+                    return Ok(Self::create_error_response(format!(
+                        "Failed to persist config update: {}", e
+                    )));
+                }
+            }
+        }
+
+        self.notify_change(ConfigChangeEvent {
+            config_id: *config_id,
+            namespace: config.namespace.clone(),
+            name: config.name.clone(),
+            version_id: *version_id,
+            change_type: ConfigChangeType::ReleaseUpdated,
+        });
+
+        Ok(Self::create_success_response(
+            format!("Version {} now serves {}% of matching clients", version_id, percent),
+            Some(serde_json::json!({
+                "config_id": config_id,
+                "version_id": version_id,
+                "canary_percent": percent
+            })),
+        ))
+    }
+
+    /// Handle set release weights command
+    ///
+    /// Replaces the active non-canary releases without labels by one release
+    /// per `(version_id, weight)`, keeping their priority, so default traffic
+    /// is split between the versions in proportion to their weights.
+    pub(crate) async fn handle_set_release_weights(
+        &self,
+        config_id: &u64,
+        weights: &[(u64, u32)],
+    ) -> Result<ClientWriteResponse> {
+        let total: u64 = weights.iter().map(|(_, weight)| u64::from(*weight)).sum();
+        if total == 0 {
+            return Ok(Self::create_error_response(
+                "Release weights must sum to more than 0".to_string(),
+            ));
+        }
+        let mut seen = std::collections::HashSet::new();
+        if let Some((version_id, _)) = weights.iter().find(|(id, _)| !seen.insert(*id)) {
+            return Ok(Self::create_error_response(format!(
+                "Version {} is given more than one weight",
+                version_id
+            )));
+        }
+
+        let (config_key, config) = match self.find_config_by_id(*config_id).await {
+            Ok((key, config)) => (key, config),
+            Err(_) => {
+                return Ok(Self::create_error_response(format!(
+                    "Configuration with ID {} not found",
+                    config_id
+                )));
+            }
+        };
+
+        for (version_id, _) in weights {
+            if self.validate_version_exists(*config_id, *version_id).await.is_err() {
+                return Ok(Self::create_error_response(format!(
+                    "Version {} does not exist for config {}",
+                    version_id, config_id
+                )));
+            }
+        }
+
+        {
+            let mut configs = self.configurations.write().await;
+            if let Some(config) = configs.get_mut(&config_key) {
+                let is_split_release = |r: &Release| {
+                    r.is_default() && r.effective_at.is_none() && r.canary_percent.is_none()
+                };
+                let priority = config
+                    .releases
+                    .iter()
+                    .filter(|r| is_split_release(r))
+                    .map(|r| r.priority)
+                    .max()
+                    .unwrap_or(0);
+                config.releases.retain(|r| !is_split_release(r));
+                config.releases.extend(weights.iter().map(|(version_id, weight)| {
+                    let mut release = Release::new(BTreeMap::new(), *version_id, priority);
+                    release.weight = *weight;
+                    release
+                }));
+                config.updated_at = Utc::now();
+                if let Err(e) = self.persist_config(&config_key, config).await {
+                    return Ok(Self::create_error_response(format!(
+                        "Failed to persist config update: {}", e
+                    )));
+                }
+            }
+        }
+
+        self.notify_change(ConfigChangeEvent {
+            config_id: *config_id,
+            namespace: config.namespace.clone(),
+            name: config.name.clone(),
+            version_id: 0, // Several versions share the traffic
+            change_type: ConfigChangeType::ReleaseUpdated,
+        });
+
+        Ok(Self::create_success_response(
+            format!("Traffic split between {} versions", weights.len()),
+            Some(serde_json::json!({
+                "config_id": config_id,
+                "weights": weights
+                    .iter()
+                    .map(|(version_id, weight)| {
+                        serde_json::json!({ "version_id": version_id, "weight": weight })
+                    })
+                    .collect::<Vec<_>>()
+            })),
+        ))
+    }
 }
@@ -1,7 +1,9 @@
 use crate::error::Result;
 use crate::raft::types::*;
+use super::super::quotas::TenantUsage;
 use super::super::types::{Store, ConfigChangeEvent, ConfigChangeType};
 use std::collections::BTreeMap;
+use tracing::warn;
 
 impl Store {
     /// Handle create version command
@@ -24,16 +26,33 @@ impl Store {
             }
         };
 
-        // Generate new version ID
-        let version_id = {
+        let tenant = &existing_config.namespace.tenant;
+        if let Err(e) = self.check_new_version(tenant, *config_id, content.len()).await {
+            return Ok(Self::create_error_response(e.to_string()));
+        }
+
+        // Generate new version ID after the newest stored version, and chain
+        // the new version to it as stored, so a predecessor that fails
+        // verification does not make the new version start a chain of its own
+        let (version_id, previous) = {
             let versions = self.versions.read().await;
-            let empty_map = BTreeMap::new();
-            let config_versions = versions.get(config_id).unwrap_or(&empty_map);
-            config_versions.keys().max().copied().unwrap_or(0) + 1
+            let previous = versions
+                .get(config_id)
+                .and_then(BTreeMap::last_key_value)
+                .map(|(id, version)| (*id, version.clone()));
+            match previous {
+                Some((id, version)) => (id + 1, Some(version)),
+                None => (1, None),
+            }
         };
 
         // Determine format for new version
-        let version_format = if let Some(fmt) = format {
+        let version_format = if let Some(ConfigFormat::Unknown) = format {
+            match ConfigFormat::detect(content) {
+                Some(detected) => detected,
+                None => return Ok(Self::create_error_response(AMBIGUOUS_FORMAT.to_string())),
+            }
+        } else if let Some(fmt) = format {
             fmt.clone()
         } else {
             // Use the format from the config's latest version or default to JSON
@@ -47,9 +66,15 @@ impl Store {
                 .unwrap_or(ConfigFormat::Json);
             default_format
         };
+        if let Err(e) = self
+            .check_config_schema(&existing_config, content, &version_format)
+            .await
+        {
+            return Ok(Self::create_error_response(e.to_string()));
+        }
 
-        // Create new version
-        let version = ConfigVersion::new(
+        // Create new version, chained to its predecessor
+        let mut version = ConfigVersion::new(
             version_id,
             *config_id,
             content.to_vec(),
@@ -57,29 +82,39 @@ impl Store {
             *creator_id,
             description.to_string(),
         );
+        if let Some(previous) = &previous {
+            version = version.with_previous(previous);
+        }
+        if let Some(duplicate_id) = self
+            .find_version_with_hash(*config_id, &version.content_hash)
+            .await
+        {
+            warn!(
+                "Content of new version {} of config {} is identical to version {}",
+                version_id, config_id, duplicate_id
+            );
+        }
 
-        // Persist version and update config's latest_version_id
-        if let Err(e) = self.persist_version(&version).await {
+        // Persist the version together with the config's new latest_version_id
+        let mut updated_config = existing_config.clone();
+        updated_config.latest_version_id = version_id;
+        updated_config.updated_at = chrono::Utc::now();
+        if let Err(e) = self
+            .persist_config_and_version(&config_key, &updated_config, &version)
+            .await
+        {
             return Ok(Self::create_error_response(format!(
                 "Failed to persist version: {}", e
             )));
         }
 
-        {
-            let mut configs = self.configurations.write().await;
-            if let Some(config) = configs.get_mut(&config_key) {
-                config.latest_version_id = version_id;
-                config.updated_at = chrono::Utc::now();
-                // Persist updated config
-                if let Err(e) = self.persist_config(&config_key, config).await {
-                    return Ok(Self::create_error_response(format!(
-                        "Failed to persist config update: {}", e
-                    )));
-                }
-            }
-        }
+        self.configurations
+            .write()
+            .await
+            .insert(config_key, updated_config);
 
         // Store the new version in memory
+        let usage = TenantUsage::of_versions([&version]);
         {
             let mut versions = self.versions.write().await;
             versions
@@ -87,9 +122,13 @@ impl Store {
                 .or_insert_with(BTreeMap::new)
                 .insert(version_id, version);
         }
+        self.add_tenant_usage(tenant, usage);
+
+        // Drop the oldest versions beyond the config's version limit
+        let pruned = self.enforce_prune_policy(&existing_config).await?;
 
         // Send notification using config info we already have
-        let _ = self.change_notifier.send(ConfigChangeEvent {
+        self.notify_change(ConfigChangeEvent {
             config_id: *config_id,
             namespace: existing_config.namespace.clone(),
             name: existing_config.name.clone(),
@@ -101,7 +140,8 @@ impl Store {
             "Configuration version created successfully".to_string(),
             Some(serde_json::json!({
                 "config_id": config_id,
-                "version_id": version_id
+                "version_id": version_id,
+                "pruned_version_ids": pruned
             })),
         ))
     }
@@ -0,0 +1,80 @@
+use super::*;
+use super::super::store::sst_files_size;
+use tempfile::tempdir;
+
+fn namespace(env: &str) -> ConfigNamespace {
+    ConfigNamespace {
+        tenant: "acme".to_string(),
+        app: "app".to_string(),
+        env: env.to_string(),
+    }
+}
+
+async fn create_config(store: &Store, namespace: ConfigNamespace, versions: u64) -> u64 {
+    let content = |v: u64| format!(r#"{{"v":{},"payload":"{}"}}"#, v, "x".repeat(4096)).into_bytes();
+    let config_id = store
+        .apply_command(&RaftCommand::CreateConfig {
+            namespace,
+            name: "app.json".to_string(),
+            content: content(1),
+            format: ConfigFormat::Json,
+            schema: None,
+            creator_id: 1,
+            description: "v1".to_string(),
+        })
+        .await
+        .unwrap()
+        .config_id
+        .unwrap();
+    for v in 2..=versions {
+        store
+            .apply_command(&RaftCommand::CreateVersion {
+                config_id,
+                content: content(v),
+                format: None,
+                creator_id: 1,
+                description: format!("v{}", v),
+            })
+            .await
+            .unwrap();
+    }
+    config_id
+}
+
+#[test]
+fn test_id_runs() {
+    assert_eq!(id_runs(&[]), Vec::<(u64, u64)>::new());
+    assert_eq!(id_runs(&[1, 2, 3, 7, 9, 10]), vec![(1, 3), (7, 7), (9, 10)]);
+    assert_eq!(id_runs(&[u64::MAX - 1, u64::MAX]), vec![(u64::MAX - 1, u64::MAX)]);
+    assert_eq!(prefix_end(b"acme/app/prod/"), b"acme/app/prod0".to_vec());
+}
+
+#[tokio::test]
+async fn test_compact_namespace_after_deleting_versions() {
+    let dir = tempdir().unwrap();
+    let (store, _rx) = Store::new(dir.path()).await.unwrap();
+    let config_id = create_config(&store, namespace("prod"), 20).await;
+    create_config(&store, namespace("staging"), 3).await;
+
+    let response = store
+        .apply_command(&RaftCommand::DeleteVersions {
+            config_id,
+            version_ids: (2..20).collect(),
+        })
+        .await
+        .unwrap();
+    assert!(response.success, "{}", response.message);
+    store.flush_to_disk().await.unwrap();
+    let size_before = sst_files_size(&store.db).unwrap();
+
+    let stats = store.compact_namespace(&namespace("prod")).await.unwrap();
+    assert!(sst_files_size(&store.db).unwrap() <= size_before);
+    assert!(stats.space_reclaimed_estimate_bytes <= size_before);
+
+    // Versions of this and other namespaces survive compaction
+    assert_eq!(store.list_config_versions(config_id).await.len(), 2);
+    assert!(store.get_config(&namespace("staging"), "app.json").await.is_some());
+
+    // A namespace without configs compacts nothing
+    store.compact_namespace(&namespace("dev")).await.unwrap();
+}
@@ -1,9 +1,18 @@
-use crate::error::Result;
+use crate::error::{Result, ResultExt};
 use crate::raft::types::*;
+use crate::raft::validation::validate_namespace;
+use super::chain::version_link_intact;
+use super::constants::{CF_META, WAL_APPLIED_SEQUENCE_KEY};
+use super::limits::TenantLimits;
+use super::quotas::{TenantQuota, TenantUsage};
+use super::staging::discard_staged_writes;
 use super::types::{Store, ConfigChangeEvent, ConfigChangeType};
+use chrono::{DateTime, Utc};
 use sha2::Digest;
 use std::collections::BTreeMap;
+use std::future::Future;
 use tokio::sync::broadcast;
+use tracing::{error, warn};
 
 impl Store {
     /// Subscribe to configuration changes
@@ -18,39 +27,116 @@ impl Store {
     }
 
     /// Get configuration version
+    ///
+    /// When chain verification on read is enabled, versions that do not match
+    /// their own hash or their predecessor's content are not returned.
     pub async fn get_config_version(
         &self,
         config_id: u64,
         version_id: u64,
     ) -> Option<ConfigVersion> {
-        self.versions
-            .read()
-            .await
-            .get(&config_id)?
-            .get(&version_id)
-            .cloned()
+        let versions = self.versions.read().await;
+        let config_versions = versions.get(&config_id)?;
+        let version = config_versions.get(&version_id)?;
+
+        if self.verifies_chain_on_read() && !version_link_intact(config_versions, version) {
+            tracing::error!(
+                "Version {} of config {} failed hash chain verification",
+                version_id, config_id
+            );
+            return None;
+        }
+        Some(version.clone())
     }
 
     /// Get published configuration based on client labels
+    ///
+    /// Soft-deleted configs are not published. A config missing from the
+    /// namespace is resolved from the env it inherits from, if any. The
+    /// release is selected on every call; the selected version is served from
+    /// the published config read cache when possible.
     pub async fn get_published_config(
         &self,
         namespace: &ConfigNamespace,
         name: &str,
         client_labels: &BTreeMap<String, String>,
     ) -> Option<(Config, ConfigVersion)> {
-        let config = self.get_config(namespace, name).await?;
+        let config = self.find_inherited_config(namespace, name).await?;
 
         // Find matching release rule using the new method
         let version_id = config
             .find_matching_release(client_labels)
             .map(|r| r.version_id)
-            .or_else(|| config.get_default_release().map(|r| r.version_id))
+            .or_else(|| config.get_default_release(client_labels).map(|r| r.version_id))
             .unwrap_or(config.latest_version_id);
 
+        let key = (config.id, version_id);
+        if let Some(version) = self.published_cache.get(&key) {
+            return Some((config, version));
+        }
+
+        let generation = self.published_cache.generation();
         let version = self.get_config_version(config.id, version_id).await?;
+        self.published_cache.insert(key, version.clone(), generation);
+        Some((config, version))
+    }
+
+    /// Get the version of a config that was published to the client labels at `at`
+    ///
+    /// The store keeps no history of release rule changes, so the current
+    /// rules are assumed to have applied at `at`: the version they select is
+    /// returned if it already existed then. Otherwise, and for configs without
+    /// releases, the newest version created at or before `at` is returned.
+    /// Versions pruned since are not considered.
+    pub async fn get_config_at(
+        &self,
+        namespace: &ConfigNamespace,
+        name: &str,
+        at: DateTime<Utc>,
+        client_labels: &BTreeMap<String, String>,
+    ) -> Option<(Config, ConfigVersion)> {
+        let config = self
+            .get_config(namespace, name)
+            .await
+            .filter(|config| config.created_at <= at)
+            .filter(|config| config.deleted_at.is_none_or(|deleted_at| deleted_at > at))?;
+
+        let released_id = config
+            .find_matching_release(client_labels)
+            .or_else(|| config.get_default_release(client_labels))
+            .map(|release| release.version_id);
+        let versions = self.list_config_versions(config.id).await;
+        let existing: Vec<&ConfigVersion> = versions
+            .iter()
+            .filter(|version| version.created_at <= at)
+            .collect();
+
+        let version = released_id
+            .and_then(|id| existing.iter().find(|version| version.id == id))
+            .or_else(|| existing.iter().max_by_key(|version| version.id))
+            .map(|version| (*version).clone())?;
         Some((config, version))
     }
 
+    /// Find the live config named `name` in `namespace` or, failing that, in
+    /// the nearest env it inherits from
+    pub(crate) async fn find_inherited_config(
+        &self,
+        namespace: &ConfigNamespace,
+        name: &str,
+    ) -> Option<Config> {
+        for candidate in self.env_inheritance.chain(namespace) {
+            if let Some(config) = self
+                .get_config(&candidate, name)
+                .await
+                .filter(|config| !config.is_deleted())
+            {
+                return Some(config);
+            }
+        }
+        None
+    }
+
     /// Get configuration metadata by ID
     pub async fn get_config_meta(&self, config_id: u64) -> Option<Config> {
         let configs = self.configurations.read().await;
@@ -81,18 +167,112 @@ impl Store {
         self.get_config(namespace, name).await.is_some()
     }
 
-    /// Get all configurations in a namespace
+    /// Get all configurations in a namespace, excluding soft-deleted ones
     pub async fn list_configs_in_namespace(&self, namespace: &ConfigNamespace) -> Vec<Config> {
         let configs = self.configurations.read().await;
         configs
             .values()
-            .filter(|config| config.namespace == *namespace)
+            .filter(|config| config.namespace == *namespace && !config.is_deleted())
             .cloned()
             .collect()
     }
 
-    /// Apply a command to the store (for testing)
+    /// Apply a command to the store
+    ///
+    /// The command is journalled in the write-ahead log before it mutates any
+    /// state, then applied with [`Store::apply_journalled`]. Commands are
+    /// applied one at a time.
     pub async fn apply_command(&self, command: &RaftCommand) -> Result<ClientWriteResponse> {
+        let _applying = self.apply_lock.lock().await;
+        self.journal_and_apply(command).await
+    }
+
+    /// Journal a command in the write-ahead log and apply it
+    ///
+    /// Callers must hold `apply_lock`, so commands are journalled and applied
+    /// in the same order.
+    pub(crate) async fn journal_and_apply(&self, command: &RaftCommand) -> Result<ClientWriteResponse> {
+        let sequence = self.wal.append(command)?;
+        self.apply_journalled(sequence, command).await
+    }
+
+    /// Apply the journalled command with WAL `sequence`
+    ///
+    /// All RocksDB writes of the command are written in one batch together
+    /// with `sequence`. After a crash, the command is thus replayed exactly
+    /// when none of its writes landed. A command that fails keeps none of its
+    /// writes: only `sequence` is written and the in-memory state is rolled
+    /// back to what is on disk.
+    pub(crate) async fn apply_journalled(
+        &self,
+        sequence: u64,
+        command: &RaftCommand,
+    ) -> Result<ClientWriteResponse> {
+        self.apply_sequenced(sequence, self.dispatch_command(command))
+            .await
+    }
+
+    /// Run `apply`, the handler of the command with WAL `sequence`, as
+    /// described in [`Store::apply_journalled`]
+    async fn apply_sequenced(
+        &self,
+        sequence: u64,
+        apply: impl Future<Output = Result<ClientWriteResponse>>,
+    ) -> Result<ClientWriteResponse> {
+        let cf_meta = self.db.cf_handle(CF_META).ok_or_else(|| {
+            crate::error::ConfluxError::storage("Meta column family not found")
+        })?;
+        let staged = self
+            .staged(async {
+                let result = apply.await;
+                if result.is_err() {
+                    discard_staged_writes();
+                }
+                self.write_with(|batch| {
+                    batch.put_cf(cf_meta, WAL_APPLIED_SEQUENCE_KEY, sequence.to_be_bytes());
+                    Ok(())
+                })?;
+                Ok(result)
+            })
+            .await;
+        let result = match staged {
+            Ok(result) => result,
+            Err(e) => {
+                // The batch could not be written: memory must not keep what disk lacks
+                self.roll_back_to_disk(sequence).await;
+                return Err(e);
+            }
+        };
+        if result.is_err() {
+            self.roll_back_to_disk(sequence).await;
+        }
+        self.wal.mark_applied(sequence);
+        result
+    }
+
+    /// Replace the in-memory state changed by the failed command with WAL
+    /// `sequence` by what is on disk
+    ///
+    /// Handlers mostly reject commands with a failed response and only return
+    /// an error when storage fails, so the full reload this takes is rare.
+    async fn roll_back_to_disk(&self, sequence: u64) {
+        warn!("Rolling back the in-memory changes of failed command {}", sequence);
+        if let Err(e) = self.replace_state_from_disk().await {
+            error!("Failed to roll back command {}: {}", sequence, e);
+        }
+        self.published_cache.invalidate_all();
+    }
+
+    /// Apply state change directly (used by state machine to avoid circular dependency)
+    /// This method is similar to apply_command but is designed for use by the state machine
+    pub async fn apply_state_change(&self, command: &RaftCommand) -> Result<ClientWriteResponse> {
+        // It's called by the state machine to apply changes after consensus
+        self.apply_command(command).await
+    }
+
+    /// Apply a command without journalling it, for already journalled
+    /// commands and the sub-commands of a transaction
+    pub(crate) async fn dispatch_command(&self, command: &RaftCommand) -> Result<ClientWriteResponse> {
         match command {
             RaftCommand::CreateConfig {
                 namespace,
@@ -139,6 +319,30 @@ impl Store {
                 config_id,
                 releases,
             } => self.handle_update_release_rules(config_id, releases).await,
+            RaftCommand::ScheduleRelease {
+                config_id,
+                version_id,
+                labels,
+                effective_at,
+            } => {
+                self.handle_schedule_release(config_id, version_id, labels, effective_at)
+                    .await
+            }
+            RaftCommand::ActivateScheduledReleases { now } => {
+                self.handle_activate_scheduled_releases(now).await
+            }
+            RaftCommand::SetCanaryPercent {
+                config_id,
+                version_id,
+                labels,
+                percent,
+            } => {
+                self.handle_set_canary_percent(config_id, version_id, labels, *percent)
+                    .await
+            }
+            RaftCommand::SetReleaseWeights { config_id, weights } => {
+                self.handle_set_release_weights(config_id, weights).await
+            }
             RaftCommand::DeleteConfig { config_id } => {
                 self.handle_delete_config(config_id).await
             }
@@ -146,72 +350,126 @@ impl Store {
                 config_id,
                 version_ids,
             } => self.handle_delete_versions(config_id, version_ids).await,
-        }
-    }
-
-    /// Apply state change directly (used by state machine to avoid circular dependency)
-    /// This method is similar to apply_command but is designed for use by the state machine
-    pub async fn apply_state_change(&self, command: &RaftCommand) -> Result<ClientWriteResponse> {
-        // This is essentially the same as apply_command, but semantically different
-        // It's called by the state machine to apply changes after consensus
-        match command {
-            RaftCommand::CreateConfig {
+            RaftCommand::AddConfigDependency {
+                from_config_id,
+                to_config_id,
+            } => {
+                self.handle_add_config_dependency(from_config_id, to_config_id)
+                    .await
+            }
+            RaftCommand::RemoveConfigDependency {
+                from_config_id,
+                to_config_id,
+            } => {
+                self.handle_remove_config_dependency(from_config_id, to_config_id)
+                    .await
+            }
+            RaftCommand::SetApprovalRequired {
                 namespace,
-                name,
-                content,
-                format,
-                schema,
-                creator_id,
-                description,
+                required,
+            } => self.handle_set_approval_required(namespace, *required).await,
+            RaftCommand::RequestReleaseApproval {
+                config_id,
+                version_id,
+                requested_by,
             } => {
-                self.handle_create_config(
-                    namespace, name, content, format, schema, creator_id, description,
-                )
-                .await
+                self.handle_request_release_approval(*config_id, *version_id, *requested_by)
+                    .await
             }
-            RaftCommand::UpdateConfig {
+            RaftCommand::ApproveRelease {
                 config_id,
-                namespace,
+                version_id,
+                approver_id,
+            } => {
+                self.handle_review_release(*config_id, *version_id, *approver_id, ApprovalStatus::Approved)
+                    .await
+            }
+            RaftCommand::RejectRelease {
+                config_id,
+                version_id,
+                approver_id,
+            } => {
+                self.handle_review_release(*config_id, *version_id, *approver_id, ApprovalStatus::Rejected)
+                    .await
+            }
+            RaftCommand::RegisterSchema {
+                tenant,
                 name,
-                content,
-                format,
                 schema,
-                description,
+            } => self.handle_register_schema(tenant, name, schema).await,
+            RaftCommand::SetConfigSchema {
+                config_id,
+                schema_id,
+            } => self.handle_set_config_schema(*config_id, *schema_id).await,
+            RaftCommand::RegisterWebhook {
+                tenant,
+                url,
+                app,
+                env,
+                secret,
             } => {
-                self.handle_update_config(
-                    config_id, namespace, name, content, format, schema, description,
-                )
-                .await
+                self.handle_register_webhook(tenant, url, app.clone(), env.clone(), secret.clone())
+                    .await
             }
-            RaftCommand::CreateVersion {
-                config_id,
-                content,
-                format,
-                creator_id,
-                description,
+            RaftCommand::DeleteWebhook { tenant, webhook_id } => {
+                self.handle_delete_webhook(tenant, *webhook_id).await
+            }
+            RaftCommand::SoftDeleteNamespace {
+                namespace,
+                delete_after_days,
+                deleted_at,
             } => {
-                self.handle_create_version(config_id, content, format, creator_id, description)
+                self.handle_soft_delete_namespace(namespace, *delete_after_days, deleted_at)
                     .await
             }
-            RaftCommand::ReleaseVersion { config_id, version_id } => {
-                self.handle_release_version(config_id, version_id).await
+            RaftCommand::UndeleteNamespace { namespace, now } => {
+                self.handle_undelete_namespace(namespace, now).await
             }
-            RaftCommand::UpdateReleaseRules {
-                config_id,
-                releases,
-            } => self.handle_update_release_rules(config_id, releases).await,
-            RaftCommand::DeleteConfig { config_id } => {
-                self.handle_delete_config(config_id).await
+            RaftCommand::PurgeExpiredConfigs { now } => {
+                self.handle_purge_expired_configs(now).await
             }
-            RaftCommand::DeleteVersions {
+            RaftCommand::EvictIdempotencyKeys { now } => self.handle_evict_idempotency_keys(now),
+            RaftCommand::SetPrunePolicy {
                 config_id,
-                version_ids,
-            } => self.handle_delete_versions(config_id, version_ids).await,
+                max_versions,
+            } => self.handle_set_prune_policy(*config_id, *max_versions).await,
+            RaftCommand::PruneVersions {
+                config_id,
+                keep_last,
+            } => self.handle_prune_versions(*config_id, *keep_last).await,
+            RaftCommand::Transaction { commands } => self.handle_transaction(commands).await,
+            RaftCommand::BulkCreateConfigs { entries } => {
+                self.handle_bulk_create_configs(entries).await
+            }
+            RaftCommand::SetTenantQuota {
+                tenant,
+                max_configs,
+                max_versions_per_config,
+                max_total_bytes,
+            } => {
+                let quota = TenantQuota {
+                    max_configs: *max_configs,
+                    max_versions_per_config: *max_versions_per_config,
+                    max_total_bytes: *max_total_bytes,
+                };
+                self.handle_set_tenant_quota(tenant, quota).await
+            }
+            RaftCommand::SetTenantLimits {
+                tenant,
+                max_config_content_bytes,
+                max_version_history,
+            } => {
+                let limits = TenantLimits {
+                    max_config_content_bytes: *max_config_content_bytes,
+                    max_version_history: *max_version_history,
+                };
+                self.handle_set_tenant_limits(tenant, limits).await
+            }
         }
     }
 
     /// Handle create config command
-    async fn handle_create_config(
+    pub(super) async fn handle_create_config(
         &self,
         namespace: &ConfigNamespace,
         name: &str,
@@ -221,6 +479,13 @@ impl Store {
         creator_id: &u64,
         description: &str,
     ) -> Result<ClientWriteResponse> {
+        if let Err(e) = validate_namespace(namespace) {
+            return Ok(Self::create_error_response(e.to_string()));
+        }
+        if let Err(e) = self.check_new_config(&namespace.tenant, content.len()) {
+            return Ok(Self::create_error_response(e.to_string()));
+        }
+
         // Check if config already exists
         if self.config_exists(namespace, name).await {
             return Ok(Self::create_error_response(format!(
@@ -229,12 +494,10 @@ impl Store {
             )));
         }
 
-        let config_id = {
-            let mut next_id = self.next_config_id.write().await;
-            let id = *next_id;
-            *next_id += 1;
-            id
-        };
+        // Hold the counter until the config is stored, so the counter written
+        // with it is past every stored config ID
+        let mut next_id = self.next_config_id.write().await;
+        let config_id = *next_id;
 
         let version_id = 1;
         let now = chrono::Utc::now();
@@ -249,10 +512,17 @@ impl Store {
                 labels: BTreeMap::new(), // Default release
                 version_id,
                 priority: 0,
+                effective_at: None,
+                canary_percent: None,
+                weight: 1,
             }],
             schema: schema.clone(),
+            schema_id: None,
             created_at: now,
             updated_at: now,
+            deleted_at: None,
+            purge_at: None,
+            max_versions: None,
         };
 
         // Create version
@@ -265,13 +535,19 @@ impl Store {
             creator_id: *creator_id,
             created_at: now,
             description: description.to_string(),
+            delta_base_version_id: None,
+            previous_hash: None,
         };
 
         // Persist to RocksDB and update in-memory state
         let config_name_key = make_config_key(namespace, name);
-        self.persist_config(&config_name_key, &config).await?;
-        self.persist_version(&version).await?;
+        self.persist_new_config(&config_name_key, &config, &version, config_id + 1)
+            .await
+            .with_context(format!("Failed to persist new config {}", config_name_key))?;
+        *next_id = config_id + 1;
+        drop(next_id);
 
+        let usage = TenantUsage::of_config([&version]);
         self.configurations
             .write()
             .await
@@ -286,9 +562,10 @@ impl Store {
             .write()
             .await
             .insert(config_name_key, config_id);
+        self.add_tenant_usage(&namespace.tenant, usage);
 
         // Send notification
-        let _ = self.change_notifier.send(ConfigChangeEvent {
+        self.notify_change(ConfigChangeEvent {
             config_id,
             namespace: namespace.clone(),
             name: name.to_string(),
@@ -304,6 +581,7 @@ impl Store {
                 "config_id": config_id,
                 "version_id": version_id
             })),
+            log_index: None,
         })
     }
 
@@ -318,6 +596,10 @@ impl Store {
         schema: &Option<String>,
         description: &str,
     ) -> Result<ClientWriteResponse> {
+        if let Err(e) = validate_namespace(namespace) {
+            return Ok(Self::create_error_response(e.to_string()));
+        }
+
         // Find the existing config by ID
         let (config_key, mut existing_config) = match self.find_config_by_id(*config_id).await {
             Ok((key, config)) => (key, config),
@@ -329,18 +611,34 @@ impl Store {
             }
         };
 
-        // Generate new version ID for the updated content
-        let version_id = {
+        // Updates create a new version, so they are subject to the same limits
+        if let Err(e) = self
+            .check_new_version(&namespace.tenant, *config_id, content.len())
+            .await
+        {
+            return Ok(Self::create_error_response(e.to_string()));
+        }
+        if let Err(e) = self.check_config_schema(&existing_config, content, format).await {
+            return Ok(Self::create_error_response(e.to_string()));
+        }
+
+        // Generate new version ID for the updated content, chained to the
+        // newest stored version even if it fails verification
+        let (version_id, previous_hash) = {
             let versions = self.versions.read().await;
-            let empty_map = BTreeMap::new();
-            let config_versions = versions.get(config_id).unwrap_or(&empty_map);
-            config_versions.keys().max().copied().unwrap_or(0) + 1
+            match versions.get(config_id).and_then(BTreeMap::last_key_value) {
+                Some((id, previous)) => {
+                    (id + 1, Some(ConfigVersion::hash_content(&previous.content)))
+                }
+                None => (1, None),
+            }
         };
 
         let now = chrono::Utc::now();
 
         // Update config metadata
         let old_config_key = config_key.clone();
+        let old_tenant = existing_config.namespace.tenant.clone();
         let new_config_key = make_config_key(namespace, name);
 
         existing_config.namespace = namespace.clone();
@@ -359,11 +657,14 @@ impl Store {
             creator_id: 0, // UpdateConfig doesn't have creator_id, using 0 as system
             created_at: now,
             description: description.to_string(),
+            delta_base_version_id: None,
+            previous_hash,
         };
 
         // Persist to RocksDB and update in-memory state
-        self.persist_config(&new_config_key, &existing_config).await?;
-        self.persist_version(&version).await?;
+        self.persist_config_and_version(&new_config_key, &existing_config, &version)
+            .await
+            .with_context(format!("Failed to persist update of config {}", new_config_key))?;
 
         // Update in-memory structures
         {
@@ -375,13 +676,14 @@ impl Store {
             configs.insert(new_config_key.clone(), existing_config.clone());
         }
 
-        {
+        let added = TenantUsage::of_versions([&version]);
+        let moved = {
             let mut versions = self.versions.write().await;
-            versions
-                .entry(*config_id)
-                .or_insert_with(BTreeMap::new)
-                .insert(version_id, version);
-        }
+            let config_versions = versions.entry(*config_id).or_insert_with(BTreeMap::new);
+            let moved = TenantUsage::of_config(config_versions.values());
+            config_versions.insert(version_id, version);
+            moved
+        };
 
         {
             let mut name_index = self.name_index.write().await;
@@ -391,9 +693,15 @@ impl Store {
             }
             name_index.insert(new_config_key, *config_id);
         }
+        // A config moved to another tenant takes its usage along
+        if old_tenant != namespace.tenant {
+            self.remove_tenant_usage(&old_tenant, moved);
+            self.add_tenant_usage(&namespace.tenant, moved);
+        }
+        self.add_tenant_usage(&namespace.tenant, added);
 
         // Send notification
-        let _ = self.change_notifier.send(ConfigChangeEvent {
+        self.notify_change(ConfigChangeEvent {
             config_id: *config_id,
             namespace: namespace.clone(),
             name: name.to_string(),
@@ -409,6 +717,7 @@ impl Store {
                 "config_id": config_id,
                 "version_id": version_id
             })),
+            log_index: None,
         })
     }
 
@@ -437,6 +746,9 @@ impl Store {
             )));
         }
 
+        self.check_release_approval(&config.namespace, *config_id, *version_id)
+            .await?;
+
         // Update the config's release rules to include this version as the default
         {
             let mut configs = self.configurations.write().await;
@@ -444,7 +756,7 @@ impl Store {
                 // Add or update the default release to point to this version
                 let mut found_default = false;
                 for release in &mut config.releases {
-                    if release.labels.is_empty() {
+                    if release.labels.is_empty() && release.canary_percent.is_none() {
                         // This is the default release
                         release.version_id = *version_id;
                         found_default = true;
@@ -458,6 +770,9 @@ impl Store {
                         labels: BTreeMap::new(),
                         version_id: *version_id,
                         priority: 0,
+                        effective_at: None,
+                        canary_percent: None,
+                        weight: 1,
                     });
                 }
 
@@ -473,7 +788,7 @@ impl Store {
         }
 
         // Send notification
-        let _ = self.change_notifier.send(ConfigChangeEvent {
+        self.notify_change(ConfigChangeEvent {
             config_id: *config_id,
             namespace: config.namespace.clone(),
             name: config.name.clone(),
@@ -489,6 +804,7 @@ impl Store {
                 "config_id": config_id,
                 "version_id": version_id
             })),
+            log_index: None,
         })
     }
 }
@@ -382,4 +382,255 @@ mod tests {
         assert_eq!(event.name, "subscribe.json");
         assert_eq!(event.change_type, ConfigChangeType::Created);
     }
+
+    #[tokio::test]
+    async fn test_create_version_detects_unknown_format() {
+        let (store, _temp_dir) = create_test_store().await;
+        let create_response = store
+            .apply_command(&RaftCommand::CreateConfig {
+                namespace: ConfigNamespace {
+                    tenant: "test".to_string(),
+                    app: "app".to_string(),
+                    env: "test".to_string(),
+                },
+                name: "detect".to_string(),
+                content: b"{}".to_vec(),
+                format: ConfigFormat::Json,
+                schema: None,
+                creator_id: 1,
+                description: "initial".to_string(),
+            })
+            .await
+            .unwrap();
+        let config_id = create_response.config_id.unwrap();
+
+        let create_version = |content: &[u8]| RaftCommand::CreateVersion {
+            config_id,
+            content: content.to_vec(),
+            format: Some(ConfigFormat::Unknown),
+            creator_id: 1,
+            description: "detected".to_string(),
+        };
+
+        let samples: [(&[u8], ConfigFormat); 4] = [
+            (br#"{"enabled": true}"#, ConfigFormat::Json),
+            (b"enabled = true\n", ConfigFormat::Toml),
+            (b"enabled: true\n", ConfigFormat::Yaml),
+            (&[0xff, 0x00, 0xfe], ConfigFormat::Binary),
+        ];
+        for (content, format) in samples {
+            let response = store.apply_command(&create_version(content)).await.unwrap();
+            assert!(response.success);
+            let version_id = response.data.unwrap()["version_id"].as_u64().unwrap();
+            let version = store.get_config_version(config_id, version_id).await.unwrap();
+            assert_eq!(version.format, format);
+        }
+
+        let response = store
+            .apply_command(&create_version(b"just some words"))
+            .await
+            .unwrap();
+        assert!(!response.success);
+        assert_eq!(response.message, crate::raft::types::AMBIGUOUS_FORMAT);
+    }
+
+    #[tokio::test]
+    async fn test_binary_config_round_trip() {
+        let temp_dir = tempdir().unwrap();
+        let namespace = ConfigNamespace {
+            tenant: "test".to_string(),
+            app: "app".to_string(),
+            env: "prod".to_string(),
+        };
+        let blob: Vec<u8> = (0..100 * 1024).map(|i| (i * 31 % 251) as u8).collect();
+        let mut changed = blob.clone();
+        changed[50 * 1024] ^= 0xff;
+
+        let config_id = {
+            let (store, _) = Store::new(temp_dir.path()).await.unwrap();
+            let response = store
+                .apply_command(&RaftCommand::CreateConfig {
+                    namespace: namespace.clone(),
+                    name: "server.der".to_string(),
+                    content: blob.clone(),
+                    format: ConfigFormat::Binary,
+                    schema: None,
+                    creator_id: 1,
+                    description: "certificate".to_string(),
+                })
+                .await
+                .unwrap();
+            assert!(response.success, "{}", response.message);
+            let config_id = response.config_id.unwrap();
+
+            let response = store
+                .apply_command(&RaftCommand::CreateVersion {
+                    config_id,
+                    content: changed.clone(),
+                    format: None,
+                    creator_id: 1,
+                    description: "renewed".to_string(),
+                })
+                .await
+                .unwrap();
+            assert!(response.success, "{}", response.message);
+            config_id
+        };
+
+        let (store, _) = Store::new(temp_dir.path()).await.unwrap();
+        let first = store.get_config_version(config_id, 1).await.unwrap();
+        assert_eq!(first.format, ConfigFormat::Binary);
+        assert_eq!(first.content, blob);
+        let second = store.get_config_version(config_id, 2).await.unwrap();
+        assert_eq!(second.format, ConfigFormat::Binary);
+        assert_eq!(second.content, changed);
+        // Binary versions are never delta-encoded against their predecessor
+        let stored = store.read_version_from_disk(config_id, 2).unwrap().unwrap();
+        assert!(!stored.is_delta());
+    }
+
+    #[tokio::test]
+    async fn test_config_ids_survive_reload() {
+        let temp_dir = tempdir().unwrap();
+        let namespace = ConfigNamespace {
+            tenant: "test".to_string(),
+            app: "app".to_string(),
+            env: "prod".to_string(),
+        };
+        let create = |name: &str| RaftCommand::CreateConfig {
+            namespace: namespace.clone(),
+            name: name.to_string(),
+            content: br#"{"key": "value"}"#.to_vec(),
+            format: ConfigFormat::Json,
+            schema: None,
+            creator_id: 1,
+            description: "test".to_string(),
+        };
+
+        let mut ids = Vec::new();
+        for round in 0..3 {
+            // Each round reopens the store, as after a crash; metadata is
+            // never flushed separately
+            let (store, _) = Store::new(temp_dir.path()).await.unwrap();
+            for i in 0..2 {
+                let response = store
+                    .apply_command(&create(&format!("config-{}-{}.json", round, i)))
+                    .await
+                    .unwrap();
+                assert!(response.success);
+                ids.push(response.config_id.unwrap());
+            }
+        }
+
+        assert_eq!(ids, vec![1, 2, 3, 4, 5, 6]);
+        let (store, _) = Store::new(temp_dir.path()).await.unwrap();
+        assert_eq!(*store.next_config_id.read().await, 7);
+        assert_eq!(
+            store.get_config(&namespace, "config-0-1.json").await.unwrap().id,
+            2
+        );
+    }
+
+    #[tokio::test]
+    async fn test_create_and_update_reject_invalid_namespace() {
+        let (store, _temp_dir) = create_test_store().await;
+        let namespace = |tenant: &str| ConfigNamespace {
+            tenant: tenant.to_string(),
+            app: "app".to_string(),
+            env: "prod".to_string(),
+        };
+        let create = |tenant: &str| RaftCommand::CreateConfig {
+            namespace: namespace(tenant),
+            name: "app.json".to_string(),
+            content: b"{}".to_vec(),
+            format: ConfigFormat::Json,
+            schema: None,
+            creator_id: 1,
+            description: "test".to_string(),
+        };
+
+        for tenant in ["acme/app", "acme\u{0}", "acme\u{202e}"] {
+            let response = store.apply_command(&create(tenant)).await.unwrap();
+            assert!(!response.success, "{:?} was accepted", tenant);
+            assert!(response.message.contains("tenant"));
+            assert!(store.get_config(&namespace(tenant), "app.json").await.is_none());
+        }
+
+        let config_id = store
+            .apply_command(&create("acme"))
+            .await
+            .unwrap()
+            .config_id
+            .unwrap();
+        let response = store
+            .apply_command(&RaftCommand::UpdateConfig {
+                config_id,
+                namespace: namespace("acme/../other"),
+                name: "app.json".to_string(),
+                content: br#"{"v":2}"#.to_vec(),
+                format: ConfigFormat::Json,
+                schema: None,
+                description: "test".to_string(),
+            })
+            .await
+            .unwrap();
+        assert!(!response.success);
+        assert!(response.message.contains("key separator"));
+        assert_eq!(store.list_config_versions(config_id).await.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_failed_command_keeps_none_of_its_writes() {
+        use crate::error::ConfluxError;
+        use crate::raft::store::constants::{CF_META, WAL_APPLIED_SEQUENCE_KEY};
+
+        let (store, _temp_dir) = create_test_store().await;
+        let namespace = ConfigNamespace {
+            tenant: "tenant".to_string(),
+            app: "app".to_string(),
+            env: "prod".to_string(),
+        };
+        let config_id = store
+            .apply_command(&RaftCommand::CreateConfig {
+                namespace: namespace.clone(),
+                name: "app.json".to_string(),
+                content: br#"{"v":1}"#.to_vec(),
+                format: ConfigFormat::Json,
+                schema: None,
+                creator_id: 1,
+                description: "initial".to_string(),
+            })
+            .await
+            .unwrap()
+            .config_id
+            .unwrap();
+
+        // The command stages a new version, then fails
+        let command = RaftCommand::CreateVersion {
+            config_id,
+            content: br#"{"v":2}"#.to_vec(),
+            format: None,
+            creator_id: 1,
+            description: "next".to_string(),
+        };
+        let sequence = store.wal.append(&command).unwrap();
+        let result = store
+            .apply_sequenced(sequence, async {
+                let response = store.dispatch_command(&command).await?;
+                assert!(response.success);
+                assert_eq!(store.list_config_versions(config_id).await.len(), 2);
+                Err(ConfluxError::storage("injected failure"))
+            })
+            .await;
+        assert!(result.is_err());
+
+        // Neither disk nor memory keeps the version, but the command counts as applied
+        assert!(store.read_version_from_disk(config_id, 2).unwrap().is_none());
+        assert_eq!(store.list_config_versions(config_id).await.len(), 1);
+        assert_eq!(store.get_config(&namespace, "app.json").await.unwrap().latest_version_id, 1);
+        assert_eq!(store.tenant_usage("tenant").versions, 1);
+        let cf_meta = store.db.cf_handle(CF_META).unwrap();
+        let applied = store.db.get_cf(cf_meta, WAL_APPLIED_SEQUENCE_KEY).unwrap().unwrap();
+        assert_eq!(applied, sequence.to_be_bytes());
+    }
 }
@@ -0,0 +1,193 @@
+use crate::error::Result;
+use crate::raft::types::*;
+use super::types::Store;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+
+/// Interval between scheduled consistency checks
+pub const CONSISTENCY_CHECK_INTERVAL: Duration = Duration::from_secs(3600);
+
+/// Differences between the in-memory state of a store and RocksDB
+///
+/// Entries are prefixed with the kind of data they refer to:
+/// `config:<key>`, `version:<config_id>/<version_id>` or `name_index:<key>`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConsistencyReport {
+    /// Entries held in memory that are not stored on disk
+    pub missing_from_disk: Vec<String>,
+    /// Entries stored on disk that are not held in memory
+    pub missing_from_memory: Vec<String>,
+    /// Entries present in both places with different contents
+    pub content_mismatches: Vec<String>,
+    pub is_consistent: bool,
+}
+
+impl ConsistencyReport {
+    /// Compare two maps key by key, recording differences under `kind`
+    fn compare<K, V>(
+        &mut self,
+        kind: &str,
+        memory: &BTreeMap<K, V>,
+        disk: &BTreeMap<K, V>,
+        same: impl Fn(&V, &V) -> bool,
+    ) where
+        K: Ord + std::fmt::Display,
+    {
+        for (key, value) in memory {
+            match disk.get(key) {
+                None => self.missing_from_disk.push(format!("{}:{}", kind, key)),
+                Some(stored) if !same(value, stored) => {
+                    self.content_mismatches.push(format!("{}:{}", kind, key))
+                }
+                Some(_) => {}
+            }
+        }
+        for key in disk.keys().filter(|key| !memory.contains_key(key)) {
+            self.missing_from_memory.push(format!("{}:{}", kind, key));
+        }
+    }
+}
+
+/// Key of a version in a consistency report
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+struct VersionKey(u64, u64);
+
+impl std::fmt::Display for VersionKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.0, self.1)
+    }
+}
+
+/// Flatten per-config version maps into a single map keyed by config and version ID
+fn flatten_versions<'a>(
+    versions: impl IntoIterator<Item = &'a ConfigVersion>,
+) -> BTreeMap<VersionKey, &'a ConfigVersion> {
+    versions
+        .into_iter()
+        .map(|version| (VersionKey(version.config_id, version.id), version))
+        .collect()
+}
+
+impl Store {
+    /// Compare the in-memory state against a fresh read of RocksDB
+    ///
+    /// Configurations, versions and the name index are read from disk without
+    /// touching the live state. Versions are compared by content hash and
+    /// format, since disk may hold a version as a delta.
+    pub async fn check_consistency(&self) -> Result<ConsistencyReport> {
+        let disk_configs = self.read_configurations_from_disk()?;
+        let disk_versions = self.read_versions_from_disk()?;
+        let disk_name_index = self.read_name_index_from_disk()?;
+
+        let mut report = ConsistencyReport::default();
+        {
+            let configs = self.configurations.read().await;
+            report.compare("config", &*configs, &disk_configs, |a, b| {
+                serde_json::to_value(a).ok() == serde_json::to_value(b).ok()
+            });
+        }
+        {
+            let versions = self.versions.read().await;
+            report.compare(
+                "version",
+                &flatten_versions(versions.values().flat_map(BTreeMap::values)),
+                &flatten_versions(&disk_versions),
+                |a, b| a.content_hash == b.content_hash && a.format == b.format,
+            );
+        }
+        {
+            let name_index = self.name_index.read().await;
+            report.compare("name_index", &*name_index, &disk_name_index, |a, b| a == b);
+        }
+
+        report.is_consistent = report.missing_from_disk.is_empty()
+            && report.missing_from_memory.is_empty()
+            && report.content_mismatches.is_empty();
+        Ok(report)
+    }
+
+    /// Replace the in-memory configurations, versions and name index with RocksDB's
+    ///
+    /// Used to repair divergence found by [`Store::check_consistency`].
+    pub async fn resync_from_disk(&self) -> Result<()> {
+        self.replace_state_from_disk().await?;
+        self.published_cache.invalidate_all();
+        info!("Resynchronized in-memory state from disk");
+        Ok(())
+    }
+}
+
+/// Periodically checks a store for divergence between memory and disk
+///
+/// Divergence is logged; with [`ConsistencyChecker::with_repair`] the store is
+/// also resynchronized from disk.
+pub struct ConsistencyChecker {
+    store: Arc<Store>,
+    interval: Duration,
+    repair: bool,
+}
+
+impl ConsistencyChecker {
+    /// Create a checker running every [`CONSISTENCY_CHECK_INTERVAL`]
+    pub fn new(store: Arc<Store>) -> Self {
+        Self {
+            store,
+            interval: CONSISTENCY_CHECK_INTERVAL,
+            repair: false,
+        }
+    }
+
+    /// Replace the interval between checks
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Resynchronize the store from disk when a check finds divergence
+    pub fn with_repair(mut self, repair: bool) -> Self {
+        self.repair = repair;
+        self
+    }
+
+    /// Run a single check, repairing if enabled
+    pub async fn run_once(&self) -> Result<ConsistencyReport> {
+        let report = self.store.check_consistency().await?;
+        if !report.is_consistent {
+            warn!(
+                "Store diverged from disk: {} missing from disk, {} missing from memory, {} mismatched",
+                report.missing_from_disk.len(),
+                report.missing_from_memory.len(),
+                report.content_mismatches.len()
+            );
+            if self.repair {
+                self.store.resync_from_disk().await?;
+            }
+        }
+        Ok(report)
+    }
+
+    /// Start running checks in a background task
+    ///
+    /// The first check runs after one interval has passed.
+    pub fn spawn(self) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(self.interval);
+            // The first tick completes immediately
+            interval.tick().await;
+            loop {
+                interval.tick().await;
+                if let Err(e) = self.run_once().await {
+                    warn!("Consistency check failed: {}", e);
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+#[path = "consistency_tests.rs"]
+mod tests;
@@ -0,0 +1,117 @@
+use super::*;
+use tempfile::tempdir;
+
+fn namespace() -> ConfigNamespace {
+    ConfigNamespace {
+        tenant: "tenant".to_string(),
+        app: "app".to_string(),
+        env: "prod".to_string(),
+    }
+}
+
+async fn create_config(store: &Store, name: &str) -> u64 {
+    let response = store
+        .apply_command(&RaftCommand::CreateConfig {
+            namespace: namespace(),
+            name: name.to_string(),
+            content: b"{\"v\":1}".to_vec(),
+            format: ConfigFormat::Json,
+            schema: None,
+            creator_id: 1,
+            description: "v1".to_string(),
+        })
+        .await
+        .unwrap();
+    response.config_id.unwrap()
+}
+
+#[tokio::test]
+async fn test_fresh_store_is_consistent() {
+    let dir = tempdir().unwrap();
+    let (store, _rx) = Store::new(dir.path()).await.unwrap();
+    create_config(&store, "a.json").await;
+
+    let report = store.check_consistency().await.unwrap();
+    assert!(report.is_consistent, "{:?}", report);
+}
+
+#[tokio::test]
+async fn test_detects_injected_divergence() {
+    let dir = tempdir().unwrap();
+    let (store, _rx) = Store::new(dir.path()).await.unwrap();
+    let a = create_config(&store, "a.json").await;
+    create_config(&store, "b.json").await;
+
+    // Version only on disk
+    store.versions.write().await.get_mut(&a).unwrap().remove(&1);
+    // Config modified only in memory
+    let b_key = make_config_key(&namespace(), "b.json");
+    store
+        .configurations
+        .write()
+        .await
+        .get_mut(&b_key)
+        .unwrap()
+        .latest_version_id = 42;
+    // Name index entry only in memory
+    store
+        .name_index
+        .write()
+        .await
+        .insert(make_config_key(&namespace(), "ghost.json"), 99);
+
+    let report = store.check_consistency().await.unwrap();
+    assert!(!report.is_consistent);
+    assert_eq!(report.missing_from_memory, vec![format!("version:{}/1", a)]);
+    assert_eq!(report.content_mismatches, vec![format!("config:{}", b_key)]);
+    assert_eq!(
+        report.missing_from_disk,
+        vec![format!("name_index:{}", make_config_key(&namespace(), "ghost.json"))]
+    );
+}
+
+#[tokio::test]
+async fn test_checker_repairs_divergence() {
+    let dir = tempdir().unwrap();
+    let (store, _rx) = Store::new(dir.path()).await.unwrap();
+    let store = Arc::new(store);
+    let config_id = create_config(&store, "a.json").await;
+    store.delete_version_from_disk(config_id, 1).await.unwrap();
+
+    let checker = ConsistencyChecker::new(store.clone());
+    let report = checker.run_once().await.unwrap();
+    assert_eq!(report.missing_from_disk, vec![format!("version:{}/1", config_id)]);
+    // Without repair the divergence remains
+    assert!(!store.check_consistency().await.unwrap().is_consistent);
+
+    let checker = checker.with_repair(true);
+    assert!(!checker.run_once().await.unwrap().is_consistent);
+    assert!(store.check_consistency().await.unwrap().is_consistent);
+    assert!(store.list_config_versions(config_id).await.is_empty());
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_readers_never_see_a_partial_resync() {
+    let dir = tempdir().unwrap();
+    let (store, _rx) = Store::new(dir.path()).await.unwrap();
+    let store = Arc::new(store);
+    let config_id = create_config(&store, "a.json").await;
+
+    let reader = {
+        let store = store.clone();
+        tokio::spawn(async move {
+            for _ in 0..200 {
+                assert!(store.get_config(&namespace(), "a.json").await.is_some());
+                assert!(store.get_config_version(config_id, 1).await.is_some());
+                tokio::task::yield_now().await;
+            }
+        })
+    };
+    for _ in 0..20 {
+        store.resync_from_disk().await.unwrap();
+    }
+    reader.await.unwrap();
+
+    assert_eq!(store.tenant_usage("tenant").configs, 1);
+    assert!(store.check_consistency().await.unwrap().is_consistent);
+}
@@ -3,3 +3,32 @@ pub const CF_CONFIGS: &str = "configs";
 pub const CF_VERSIONS: &str = "versions";
 pub const CF_LOGS: &str = "logs";
 pub const CF_META: &str = "meta";
+pub const CF_SCHEDULED: &str = "scheduled";
+pub const CF_DEPENDENCIES: &str = "dependencies";
+pub const CF_APPROVALS: &str = "approvals";
+pub const CF_SCHEMAS: &str = "schemas";
+pub const CF_WEBHOOKS: &str = "webhooks";
+pub const CF_HASH_INDEX: &str = "hash_index";
+pub const CF_IDEMPOTENCY: &str = "idempotency";
+
+/// Every column family of the store database
+pub const COLUMN_FAMILIES: [&str; 11] = [
+    CF_CONFIGS,
+    CF_VERSIONS,
+    CF_LOGS,
+    CF_META,
+    CF_SCHEDULED,
+    CF_DEPENDENCIES,
+    CF_APPROVALS,
+    CF_SCHEMAS,
+    CF_WEBHOOKS,
+    CF_HASH_INDEX,
+    CF_IDEMPOTENCY,
+];
+
+/// Meta column family key of the next config ID counter
+pub const NEXT_CONFIG_ID_KEY: &[u8] = &[0x01];
+
+/// Meta column family key of the sequence of the last WAL command whose
+/// writes reached RocksDB; local to each node and never part of a snapshot
+pub const WAL_APPLIED_SEQUENCE_KEY: &[u8] = &[0x08];
@@ -0,0 +1,177 @@
+use crate::error::{ConfluxError, Result};
+use crate::raft::types::*;
+use super::constants::CF_HASH_INDEX;
+use super::types::Store;
+use rocksdb::{Direction, IteratorMode, WriteBatch, DB};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Content deduplication statistics over all versions in the hash index
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DedupStats {
+    /// Number of distinct content hashes
+    pub unique_contents: usize,
+    /// Number of indexed versions
+    pub total_versions: usize,
+    /// `total_versions / unique_contents`; 1.0 means no content is shared
+    pub dedup_ratio: f64,
+}
+
+impl Store {
+    /// Find every version, across all configs, whose content has the given hash
+    ///
+    /// Returns `(namespace, config name, version_id)` sorted by config key and
+    /// version. Index entries of versions that no longer exist are skipped.
+    pub async fn find_configs_by_content_hash(
+        &self,
+        hash: &str,
+    ) -> Result<Vec<(ConfigNamespace, String, u64)>> {
+        let entries = scan_hash_entries(&self.db, Some(hash))?;
+
+        let versions = self.versions.read().await;
+        let configs = self.configurations.read().await;
+        let configs_by_id: BTreeMap<u64, &Config> =
+            configs.values().map(|config| (config.id, config)).collect();
+
+        let mut matches: Vec<_> = entries
+            .into_iter()
+            .filter(|(_, config_id, version_id)| version_exists(&versions, *config_id, *version_id))
+            .filter_map(|(_, config_id, version_id)| {
+                configs_by_id
+                    .get(&config_id)
+                    .map(|config| (config.namespace.clone(), config.name.clone(), version_id))
+            })
+            .collect();
+        matches.sort_by(|a, b| {
+            (a.0.to_string(), &a.1, a.2).cmp(&(b.0.to_string(), &b.1, b.2))
+        });
+        Ok(matches)
+    }
+
+    /// Compute how much version content is shared, from the hash index
+    pub async fn dedup_stats(&self) -> Result<DedupStats> {
+        let entries = scan_hash_entries(&self.db, None)?;
+        let versions = self.versions.read().await;
+
+        let mut hashes = BTreeSet::new();
+        let mut total_versions = 0;
+        for (hash, config_id, version_id) in entries {
+            if version_exists(&versions, config_id, version_id) {
+                hashes.insert(hash);
+                total_versions += 1;
+            }
+        }
+
+        let unique_contents = hashes.len();
+        let dedup_ratio = if unique_contents == 0 {
+            1.0
+        } else {
+            total_versions as f64 / unique_contents as f64
+        };
+        Ok(DedupStats {
+            unique_contents,
+            total_versions,
+            dedup_ratio,
+        })
+    }
+
+    /// Find an existing version of a config with the given content hash
+    pub(crate) async fn find_version_with_hash(&self, config_id: u64, hash: &str) -> Option<u64> {
+        let versions = self.versions.read().await;
+        versions
+            .get(&config_id)?
+            .values()
+            .find(|version| version.content_hash == hash)
+            .map(|version| version.id)
+    }
+
+    /// Add a version's content hash to the hash index as part of `batch`
+    pub(crate) fn index_content_hash(&self, batch: &mut WriteBatch, version: &ConfigVersion) -> Result<()> {
+        let cf = hash_index_cf(&self.db)?;
+        let key = hash_index_key(&version.content_hash, version.config_id, version.id);
+        batch.put_cf(cf, key, []);
+        Ok(())
+    }
+
+    /// Remove a version's content hash from the hash index as part of `batch`
+    pub(crate) fn unindex_content_hash(
+        &self,
+        batch: &mut WriteBatch,
+        hash: &str,
+        config_id: u64,
+        version_id: u64,
+    ) -> Result<()> {
+        let cf = hash_index_cf(&self.db)?;
+        batch.delete_cf(cf, hash_index_key(hash, config_id, version_id));
+        Ok(())
+    }
+}
+
+fn version_exists(
+    versions: &BTreeMap<u64, BTreeMap<u64, ConfigVersion>>,
+    config_id: u64,
+    version_id: u64,
+) -> bool {
+    versions
+        .get(&config_id)
+        .is_some_and(|config_versions| config_versions.contains_key(&version_id))
+}
+
+fn hash_index_cf(db: &DB) -> Result<&rocksdb::ColumnFamily> {
+    db.cf_handle(CF_HASH_INDEX)
+        .ok_or_else(|| ConfluxError::storage("Hash index column family not found"))
+}
+
+/// Index key: hash, a 0x00 separator, then big-endian config and version IDs,
+/// so all versions with the same content are adjacent
+fn hash_index_key(hash: &str, config_id: u64, version_id: u64) -> Vec<u8> {
+    let mut key = hash_index_prefix(hash);
+    key.extend_from_slice(&config_id.to_be_bytes());
+    key.extend_from_slice(&version_id.to_be_bytes());
+    key
+}
+
+fn hash_index_prefix(hash: &str) -> Vec<u8> {
+    let mut prefix = Vec::with_capacity(hash.len() + 17);
+    prefix.extend_from_slice(hash.as_bytes());
+    prefix.push(0);
+    prefix
+}
+
+fn parse_hash_index_key(key: &[u8]) -> Option<(String, u64, u64)> {
+    let split = key.len().checked_sub(17)?;
+    if key[split] != 0 {
+        return None;
+    }
+    let hash = String::from_utf8(key[..split].to_vec()).ok()?;
+    let config_id = u64::from_be_bytes(key[split + 1..split + 9].try_into().ok()?);
+    let version_id = u64::from_be_bytes(key[split + 9..].try_into().ok()?);
+    Some((hash, config_id, version_id))
+}
+
+/// Read the `(hash, config_id, version_id)` entries of one hash, or of all
+/// hashes when `hash` is `None`
+fn scan_hash_entries(db: &DB, hash: Option<&str>) -> Result<Vec<(String, u64, u64)>> {
+    let cf = hash_index_cf(db)?;
+    let prefix = hash.map(hash_index_prefix);
+    let mode = match prefix {
+        Some(ref prefix) => IteratorMode::From(prefix, Direction::Forward),
+        None => IteratorMode::Start,
+    };
+
+    let mut entries = Vec::new();
+    for item in db.iterator_cf(cf, mode) {
+        let (key, _) = item.map_err(|e| {
+            ConfluxError::storage(format!("Failed to read content hash index: {}", e))
+        })?;
+        if prefix.as_ref().is_some_and(|prefix| !key.starts_with(prefix)) {
+            break;
+        }
+        entries.extend(parse_hash_index_key(&key));
+    }
+    Ok(entries)
+}
+
+#[cfg(test)]
+#[path = "dedup_tests.rs"]
+mod tests;
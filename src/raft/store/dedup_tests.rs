@@ -0,0 +1,132 @@
+use super::*;
+use crate::raft::store::test_support::create_store;
+use sha2::{Digest, Sha256};
+
+fn namespace(env: &str) -> ConfigNamespace {
+    ConfigNamespace {
+        tenant: "tenant".to_string(),
+        app: "app".to_string(),
+        env: env.to_string(),
+    }
+}
+
+fn hash(content: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(content))
+}
+
+async fn create_config(store: &Store, env: &str, name: &str, content: &[u8]) -> u64 {
+    let response = store
+        .apply_command(&RaftCommand::CreateConfig {
+            namespace: namespace(env),
+            name: name.to_string(),
+            content: content.to_vec(),
+            format: ConfigFormat::Json,
+            schema: None,
+            creator_id: 1,
+            description: "test".to_string(),
+        })
+        .await
+        .unwrap();
+    assert!(response.success);
+    response.config_id.unwrap()
+}
+
+async fn create_version(store: &Store, config_id: u64, content: &[u8]) {
+    let response = store
+        .apply_command(&RaftCommand::CreateVersion {
+            config_id,
+            content: content.to_vec(),
+            format: None,
+            creator_id: 1,
+            description: "update".to_string(),
+        })
+        .await
+        .unwrap();
+    assert!(response.success);
+}
+
+#[tokio::test]
+async fn test_hash_index_populated_on_persist() {
+    let (store, _dir) = create_store().await;
+    let shared = br#"{"pool":10}"#;
+    let prod = create_config(&store, "prod", "db.json", shared).await;
+    let staging = create_config(&store, "staging", "db.json", shared).await;
+    create_config(&store, "prod", "cache.json", br#"{"ttl":60}"#).await;
+
+    let entries = scan_hash_entries(&store.db, Some(&hash(shared))).unwrap();
+    assert_eq!(entries.len(), 2);
+    assert!(entries.contains(&(hash(shared), prod, 1)));
+    assert!(entries.contains(&(hash(shared), staging, 1)));
+    assert_eq!(scan_hash_entries(&store.db, None).unwrap().len(), 3);
+}
+
+#[tokio::test]
+async fn test_find_configs_by_content_hash() {
+    let (store, _dir) = create_store().await;
+    let shared = br#"{"pool":10}"#;
+    let prod = create_config(&store, "prod", "db.json", shared).await;
+    create_config(&store, "staging", "db.json", shared).await;
+    create_config(&store, "prod", "cache.json", br#"{"ttl":60}"#).await;
+    create_version(&store, prod, br#"{"pool":20}"#).await;
+
+    let matches = store.find_configs_by_content_hash(&hash(shared)).await.unwrap();
+    assert_eq!(
+        matches,
+        vec![
+            (namespace("prod"), "db.json".to_string(), 1),
+            (namespace("staging"), "db.json".to_string(), 1),
+        ]
+    );
+
+    let matches = store
+        .find_configs_by_content_hash(&hash(br#"{"pool":20}"#))
+        .await
+        .unwrap();
+    assert_eq!(matches, vec![(namespace("prod"), "db.json".to_string(), 2)]);
+
+    assert!(store
+        .find_configs_by_content_hash(&hash(b"unknown"))
+        .await
+        .unwrap()
+        .is_empty());
+}
+
+#[tokio::test]
+async fn test_deleted_version_removed_from_index() {
+    let (store, _dir) = create_store().await;
+    let content = br#"{"pool":10}"#;
+    let config_id = create_config(&store, "prod", "db.json", content).await;
+
+    store.delete_version_from_disk(config_id, 1).await.unwrap();
+    assert!(scan_hash_entries(&store.db, Some(&hash(content))).unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_dedup_stats() {
+    let (store, _dir) = create_store().await;
+    assert_eq!(
+        store.dedup_stats().await.unwrap(),
+        DedupStats {
+            unique_contents: 0,
+            total_versions: 0,
+            dedup_ratio: 1.0,
+        }
+    );
+
+    let shared = br#"{"pool":10}"#;
+    let prod = create_config(&store, "prod", "db.json", shared).await;
+    create_config(&store, "staging", "db.json", shared).await;
+    create_config(&store, "prod", "cache.json", br#"{"ttl":60}"#).await;
+    // Re-publishing identical content only warns and is counted as a duplicate
+    create_version(&store, prod, shared).await;
+    assert_eq!(store.find_version_with_hash(prod, &hash(shared)).await, Some(1));
+
+    assert_eq!(
+        store.dedup_stats().await.unwrap(),
+        DedupStats {
+            unique_contents: 2,
+            total_versions: 4,
+            dedup_ratio: 2.0,
+        }
+    );
+}
@@ -1,5 +1,6 @@
 use crate::error::Result;
 use crate::raft::types::*;
+use super::quotas::TenantUsage;
 use super::types::{Store, ConfigChangeEvent, ConfigChangeType};
 
 impl Store {
@@ -24,17 +25,26 @@ impl Store {
             let mut configs = self.configurations.write().await;
             configs.remove(&config_key);
         }
-        {
+        let removed_versions = {
             let mut versions = self.versions.write().await;
-            versions.remove(config_id);
-        }
+            versions.remove(config_id).unwrap_or_default()
+        };
         {
             let mut name_index = self.name_index.write().await;
             name_index.remove(&config_key);
         }
+        self.delete_config_from_disk(&config_key, &config).await?;
+        for version in removed_versions.values() {
+            self.remove_version_from_disk(*config_id, version.id, Some(&version.content_hash))?;
+        }
+        self.remove_all_dependencies(*config_id)?;
+        self.remove_tenant_usage(
+            &config.namespace.tenant,
+            TenantUsage::of_config(removed_versions.values()),
+        );
 
         // Send notification using config info we already have
-        let _ = self.change_notifier.send(ConfigChangeEvent {
+        self.notify_change(ConfigChangeEvent {
             config_id: *config_id,
             namespace: config.namespace.clone(),
             name: config.name.clone(),
@@ -57,7 +67,7 @@ impl Store {
         version_ids: &[u64],
     ) -> Result<ClientWriteResponse> {
         // Check if config exists using the new helper method
-        let _config = match self.find_config_by_id(*config_id).await {
+        let config = match self.find_config_by_id(*config_id).await {
             Ok((_, config)) => config,
             Err(_) => {
                 return Ok(Self::create_error_response(format!(
@@ -68,17 +78,24 @@ impl Store {
         };
 
         // Remove specified versions
-        let mut deleted_count = 0;
+        let mut deleted = Vec::new();
         {
             let mut versions = self.versions.write().await;
             if let Some(config_versions) = versions.get_mut(config_id) {
                 for version_id in version_ids {
-                    if config_versions.remove(version_id).is_some() {
-                        deleted_count += 1;
-                    }
+                    deleted.extend(config_versions.remove(version_id));
                 }
             }
         }
+        let deleted_count = deleted.len();
+        if deleted_count > 0 {
+            for version in &deleted {
+                self.remove_version_from_disk(*config_id, version.id, Some(&version.content_hash))?;
+            }
+            // Remaining versions may have been stored as deltas against removed ones
+            self.repack_versions(*config_id).await?;
+        }
+        self.remove_tenant_usage(&config.namespace.tenant, TenantUsage::of_versions(&deleted));
 
         Ok(Self::create_success_response(
             format!("Deleted {} versions successfully", deleted_count),
@@ -0,0 +1,218 @@
+use crate::error::{ConfluxError, Result};
+use crate::raft::types::*;
+use super::types::Store;
+use flate2::read::ZlibDecoder;
+use flate2::write::ZlibEncoder;
+use flate2::Compression;
+use std::collections::BTreeMap;
+use tracing::debug;
+
+/// Minimum size of the base version content before delta encoding is attempted
+pub const DELTA_MIN_BASE_SIZE: usize = 1024;
+
+/// Maximum number of stored versions sharing one full copy as their delta base
+///
+/// Deltas are encoded against the most recent full copy rather than the
+/// previous version, so resolving any version applies at most one patch.
+pub const DELTA_FULL_COPY_INTERVAL: usize = 16;
+
+/// Compute a compressed binary delta from `base` to `target`
+///
+/// Returns `None` when the base is too small or the delta would not be
+/// smaller than the full target content.
+pub fn encode_delta(base: &[u8], target: &[u8]) -> Result<Option<Vec<u8>>> {
+    if base.len() <= DELTA_MIN_BASE_SIZE {
+        return Ok(None);
+    }
+
+    // Raw bsdiff output is mostly zero bytes and is meant to be compressed
+    let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+    bsdiff::diff(base, target, &mut encoder)
+        .map_err(|e| ConfluxError::storage(format!("Failed to compute delta: {}", e)))?;
+    let patch = encoder
+        .finish()
+        .map_err(|e| ConfluxError::storage(format!("Failed to compress delta: {}", e)))?;
+
+    Ok((patch.len() < target.len()).then_some(patch))
+}
+
+/// Apply a delta produced by [`encode_delta`] to `base`
+pub fn apply_delta(base: &[u8], patch: &[u8]) -> Result<Vec<u8>> {
+    let mut target = Vec::new();
+    bsdiff::patch(base, &mut ZlibDecoder::new(patch), &mut target)
+        .map_err(|e| ConfluxError::storage(format!("Failed to apply delta: {}", e)))?;
+    Ok(target)
+}
+
+/// Resolve the full content of a stored version, following its delta chain
+///
+/// `config_versions` must contain every version referenced by the chain.
+/// Bases always have a lower ID than the versions built on them, which
+/// guarantees the walk terminates. Chains written by older releases may be
+/// longer than one link.
+pub(crate) fn resolve_version(
+    config_versions: &BTreeMap<u64, ConfigVersion>,
+    version: &ConfigVersion,
+) -> Result<ConfigVersion> {
+    let mut chain = vec![version];
+    let mut current = version;
+    while let Some(base_id) = current.delta_base_version_id {
+        if base_id >= current.id {
+            return Err(ConfluxError::storage(format!(
+                "Invalid delta base {} for version {}",
+                base_id, current.id
+            )));
+        }
+        current = config_versions.get(&base_id).ok_or_else(|| {
+            ConfluxError::storage(format!(
+                "Delta base version {} missing for config {}",
+                base_id, version.config_id
+            ))
+        })?;
+        chain.push(current);
+    }
+
+    let mut chain = chain.into_iter().rev();
+    let mut content = chain.next().map(|root| root.content.clone()).unwrap_or_default();
+    for delta in chain {
+        content = apply_delta(&content, &delta.content)?;
+    }
+
+    let mut resolved = version.clone();
+    resolved.content = content;
+    resolved.delta_base_version_id = None;
+    Ok(resolved)
+}
+
+/// Encode `version` as a delta against the full copy `base`
+///
+/// Returns the version unchanged when a delta would not save space.
+fn encode_against_base(version: &ConfigVersion, base: &ConfigVersion) -> Result<ConfigVersion> {
+    match encode_delta(&base.content, &version.content)? {
+        Some(patch) => {
+            debug!(
+                "Storing version {} of config {} as delta against {} ({} -> {} bytes)",
+                version.id,
+                version.config_id,
+                base.id,
+                version.content.len(),
+                patch.len()
+            );
+            let mut stored = version.clone();
+            stored.content = patch;
+            stored.delta_base_version_id = Some(base.id);
+            Ok(stored)
+        }
+        None => Ok(version.clone()),
+    }
+}
+
+impl Store {
+    /// Build the on-disk representation of a version
+    ///
+    /// Stores a delta against the full copy the previous version was encoded
+    /// against (or the previous version itself when it is stored in full), as
+    /// long as that base is larger than [`DELTA_MIN_BASE_SIZE`] and the delta
+    /// is smaller than the full content. A full copy is stored once
+    /// [`DELTA_FULL_COPY_INTERVAL`] versions share a base.
+    /// [`ConfigFormat::Binary`] content is always stored verbatim.
+    pub(crate) async fn encode_version_for_storage(
+        &self,
+        version: &ConfigVersion,
+    ) -> Result<ConfigVersion> {
+        if version.is_delta() || version.format == ConfigFormat::Binary {
+            return Ok(version.clone());
+        }
+
+        let previous_id = {
+            let versions = self.versions.read().await;
+            versions
+                .get(&version.config_id)
+                .and_then(|config_versions| config_versions.range(..version.id).next_back())
+                .map(|(id, _)| *id)
+        };
+        let Some(previous_id) = previous_id else {
+            return Ok(version.clone());
+        };
+
+        // The stored form of the predecessor names the full copy to encode against
+        let Some(previous) = self.read_version_from_disk(version.config_id, previous_id)? else {
+            return Ok(version.clone());
+        };
+        let base_id = previous.delta_base_version_id.unwrap_or(previous.id);
+
+        let versions = self.versions.read().await;
+        let Some(config_versions) = versions.get(&version.config_id) else {
+            return Ok(version.clone());
+        };
+        if config_versions.range(base_id..version.id).count() >= DELTA_FULL_COPY_INTERVAL {
+            return Ok(version.clone());
+        }
+        match config_versions.get(&base_id) {
+            Some(base) => encode_against_base(version, base),
+            None => Ok(version.clone()),
+        }
+    }
+
+    /// Recompute deltas for all versions of a configuration
+    ///
+    /// Versions are re-encoded in order against the most recent full copy,
+    /// following the same rules as [`Store::encode_version_for_storage`], and
+    /// rewritten to disk. Returns the number of versions stored as deltas.
+    pub async fn repack_versions(&self, config_id: u64) -> Result<usize> {
+        let versions = self.list_config_versions(config_id).await;
+        let mut delta_count = 0;
+        let mut base: Option<&ConfigVersion> = None;
+        let mut sharing_base = 0;
+        for version in &versions {
+            let stored = match base {
+                Some(base)
+                    if sharing_base < DELTA_FULL_COPY_INTERVAL
+                        && version.format != ConfigFormat::Binary =>
+                {
+                    encode_against_base(version, base)?
+                }
+                _ => version.clone(),
+            };
+            if stored.is_delta() {
+                delta_count += 1;
+                sharing_base += 1;
+            } else {
+                base = Some(version);
+                sharing_base = 1;
+            }
+            self.write_version_to_disk(&stored)?;
+        }
+
+        debug!(
+            "Repacked {} versions of config {} ({} deltas)",
+            versions.len(),
+            config_id,
+            delta_count
+        );
+        Ok(delta_count)
+    }
+
+    /// Bytes saved on disk by storing versions as deltas
+    pub(crate) async fn delta_saved_bytes(&self) -> Result<u64> {
+        let versions = self.versions.read().await;
+        let mut saved = 0u64;
+        for stored in self.read_versions_from_disk()? {
+            if !stored.is_delta() {
+                continue;
+            }
+            let Some(version) = versions
+                .get(&stored.config_id)
+                .and_then(|config_versions| config_versions.get(&stored.id))
+            else {
+                continue;
+            };
+            saved += version.content.len().saturating_sub(stored.content.len()) as u64;
+        }
+        Ok(saved)
+    }
+}
+
+#[cfg(test)]
+#[path = "delta_tests.rs"]
+mod tests;
@@ -0,0 +1,195 @@
+use super::*;
+use tempfile::tempdir;
+
+fn namespace() -> ConfigNamespace {
+    ConfigNamespace {
+        tenant: "tenant".to_string(),
+        app: "app".to_string(),
+        env: "prod".to_string(),
+    }
+}
+
+/// Build a JSON document large enough to be used as a delta base
+fn large_content(revision: u32) -> Vec<u8> {
+    let entries: Vec<String> = (0..100)
+        .map(|i| format!("\"key_{}\": \"value_{}\"", i, i))
+        .collect();
+    format!("{{\"revision\": {}, {}}}", revision, entries.join(", ")).into_bytes()
+}
+
+async fn create_config_with_versions(store: &Store, revisions: u32) -> u64 {
+    let response = store
+        .apply_command(&RaftCommand::CreateConfig {
+            namespace: namespace(),
+            name: "large.json".to_string(),
+            content: large_content(1),
+            format: ConfigFormat::Json,
+            schema: None,
+            creator_id: 1,
+            description: "v1".to_string(),
+        })
+        .await
+        .unwrap();
+    let config_id = response.config_id.unwrap();
+
+    for revision in 2..=revisions {
+        let response = store
+            .apply_command(&RaftCommand::CreateVersion {
+                config_id,
+                content: large_content(revision),
+                format: None,
+                creator_id: 1,
+                description: format!("v{}", revision),
+            })
+            .await
+            .unwrap();
+        assert!(response.success);
+    }
+    config_id
+}
+
+#[test]
+fn test_delta_round_trip() {
+    let base = large_content(1);
+    let target = large_content(2);
+    assert!(base.len() > DELTA_MIN_BASE_SIZE);
+
+    let patch = encode_delta(&base, &target).unwrap().unwrap();
+    assert!(patch.len() < target.len());
+    assert_eq!(apply_delta(&base, &patch).unwrap(), target);
+}
+
+#[test]
+fn test_small_base_is_not_delta_encoded() {
+    let base = b"{\"v\":1}".to_vec();
+    let target = b"{\"v\":2}".to_vec();
+    assert!(encode_delta(&base, &target).unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_versions_round_trip_through_disk() {
+    let temp_dir = tempdir().unwrap();
+    let config_id = {
+        let (store, _rx) = Store::new(temp_dir.path()).await.unwrap();
+        create_config_with_versions(&store, 3).await
+    };
+
+    let (store, _rx) = Store::new(temp_dir.path()).await.unwrap();
+
+    // Later versions are stored on disk as deltas against the first
+    let stored = store.read_versions_from_disk().unwrap();
+    assert_eq!(stored.iter().filter(|v| v.is_delta()).count(), 2);
+    assert!(stored
+        .iter()
+        .filter(|v| v.is_delta())
+        .all(|v| v.delta_base_version_id == Some(1)));
+
+    // Memory only holds full content
+    assert!(store.versions.read().await[&config_id]
+        .values()
+        .all(|v| !v.is_delta()));
+
+    for revision in 1..=3u32 {
+        let version = store
+            .get_config_version(config_id, revision as u64)
+            .await
+            .unwrap();
+        assert!(!version.is_delta());
+        assert_eq!(version.content, large_content(revision));
+        assert!(version.verify_integrity());
+    }
+
+    let versions = store.list_config_versions(config_id).await;
+    assert_eq!(versions.len(), 3);
+    assert!(versions.iter().all(|v| v.verify_integrity()));
+
+    let stats = store.get_storage_stats().await.unwrap();
+    assert!(stats.delta_saved_bytes > 0);
+}
+
+#[tokio::test]
+async fn test_delete_keeps_remaining_versions_resolvable() {
+    let temp_dir = tempdir().unwrap();
+    let config_id = {
+        let (store, _rx) = Store::new(temp_dir.path()).await.unwrap();
+        create_config_with_versions(&store, 3).await
+    };
+
+    let (store, _rx) = Store::new(temp_dir.path()).await.unwrap();
+    let response = store
+        .apply_command(&RaftCommand::DeleteVersions {
+            config_id,
+            version_ids: vec![2],
+        })
+        .await
+        .unwrap();
+    assert!(response.success);
+
+    let version = store.get_config_version(config_id, 3).await.unwrap();
+    assert_eq!(version.content, large_content(3));
+    drop(store);
+
+    // The removed version is gone from disk and the rest still resolve
+    let (store, _rx) = Store::new(temp_dir.path()).await.unwrap();
+    assert!(store.get_config_version(config_id, 2).await.is_none());
+    let version = store.get_config_version(config_id, 3).await.unwrap();
+    assert_eq!(version.content, large_content(3));
+}
+
+#[tokio::test]
+async fn test_full_copy_is_stored_every_interval() {
+    let temp_dir = tempdir().unwrap();
+    let (store, _rx) = Store::new(temp_dir.path()).await.unwrap();
+    let revisions = 2 * DELTA_FULL_COPY_INTERVAL as u32 + 1;
+    let config_id = create_config_with_versions(&store, revisions).await;
+
+    let full_copies: Vec<u64> = store
+        .read_versions_from_disk()
+        .unwrap()
+        .iter()
+        .filter(|v| !v.is_delta())
+        .map(|v| v.id)
+        .collect();
+    let interval = DELTA_FULL_COPY_INTERVAL as u64;
+    assert_eq!(full_copies, vec![1, interval + 1, 2 * interval + 1]);
+
+    // Every delta is one patch away from a full copy
+    for stored in store.read_versions_from_disk().unwrap() {
+        if let Some(base_id) = stored.delta_base_version_id {
+            assert!(full_copies.contains(&base_id));
+            assert!(stored.id - base_id < interval);
+        }
+    }
+
+    // Repacking produces the same layout
+    let delta_count = store.repack_versions(config_id).await.unwrap();
+    assert_eq!(delta_count, revisions as usize - full_copies.len());
+    drop(store);
+
+    let (store, _rx) = Store::new(temp_dir.path()).await.unwrap();
+    for revision in 1..=revisions {
+        let version = store
+            .get_config_version(config_id, revision as u64)
+            .await
+            .unwrap();
+        assert_eq!(version.content, large_content(revision));
+    }
+}
+
+#[tokio::test]
+async fn test_repack_versions() {
+    let temp_dir = tempdir().unwrap();
+    let (store, _rx) = Store::new(temp_dir.path()).await.unwrap();
+    let config_id = create_config_with_versions(&store, 4).await;
+
+    assert_eq!(store.repack_versions(config_id).await.unwrap(), 3);
+    assert_eq!(store.repack_versions(9999).await.unwrap(), 0);
+
+    for revision in 1..=4u32 {
+        let version = store
+            .get_config_version(config_id, revision as u64)
+            .await
+            .unwrap();
+        assert_eq!(version.content, large_content(revision));
+    }
+}
@@ -0,0 +1,279 @@
+use crate::error::{ConfluxError, Result};
+use crate::raft::types::*;
+use super::constants::CF_DEPENDENCIES;
+use super::types::Store;
+use rocksdb::{Direction, IteratorMode, DB};
+use std::collections::{BTreeMap, BTreeSet};
+use tracing::warn;
+
+/// A dependency edge: `from` references values of (depends on) `to`
+type Edge = (u64, u64);
+
+impl Store {
+    /// Handle add config dependency command
+    ///
+    /// Rejects self-dependencies, unknown configs and edges that would
+    /// introduce a cycle into the dependency graph.
+    pub(crate) async fn handle_add_config_dependency(
+        &self,
+        from_config_id: &u64,
+        to_config_id: &u64,
+    ) -> Result<ClientWriteResponse> {
+        let (from, to) = (*from_config_id, *to_config_id);
+        if from == to {
+            return Ok(Self::create_error_response(format!(
+                "Configuration {} cannot depend on itself",
+                from
+            )));
+        }
+        for config_id in [from, to] {
+            if self.get_config_meta(config_id).await.is_none() {
+                return Ok(Self::create_error_response(format!(
+                    "Configuration with ID {} not found",
+                    config_id
+                )));
+            }
+        }
+
+        // The new edge closes a cycle if `to` already (transitively) depends on `from`
+        if self.topological_sort(&[to]).await?.contains(&from) {
+            return Ok(Self::create_error_response(format!(
+                "Dependency {} -> {} would create a cycle",
+                from, to
+            )));
+        }
+
+        let cf = dependencies_cf(&self.db)?;
+        self.write_with(|batch| {
+            batch.put_cf(cf, edge_key(from, to), []);
+            Ok(())
+        })?;
+
+        Ok(Self::create_success_response(
+            format!("Configuration {} now depends on {}", from, to),
+            Some(serde_json::json!({
+                "from_config_id": from,
+                "to_config_id": to
+            })),
+        ))
+    }
+
+    /// Handle remove config dependency command
+    pub(crate) async fn handle_remove_config_dependency(
+        &self,
+        from_config_id: &u64,
+        to_config_id: &u64,
+    ) -> Result<ClientWriteResponse> {
+        let cf = dependencies_cf(&self.db)?;
+        self.write_with(|batch| {
+            batch.delete_cf(cf, edge_key(*from_config_id, *to_config_id));
+            Ok(())
+        })?;
+
+        Ok(Self::create_success_response(
+            format!(
+                "Removed dependency {} -> {}",
+                from_config_id, to_config_id
+            ),
+            None,
+        ))
+    }
+
+    /// Remove every dependency edge that involves `config_id`
+    pub(crate) fn remove_all_dependencies(&self, config_id: u64) -> Result<()> {
+        let cf = dependencies_cf(&self.db)?;
+        let edges = scan_edges(&self.db)?;
+        self.write_with(|batch| {
+            for (from, to) in edges {
+                if from == config_id || to == config_id {
+                    batch.delete_cf(cf, edge_key(from, to));
+                }
+            }
+            Ok(())
+        })
+    }
+
+    /// IDs of the configs that `config_id` directly depends on
+    pub async fn get_dependencies(&self, config_id: u64) -> Vec<u64> {
+        scan_dependencies_of(&self.db, config_id).unwrap_or_else(|e| {
+            warn!("Failed to read dependencies of config {}: {}", config_id, e);
+            Vec::new()
+        })
+    }
+
+    /// IDs of the configs that directly depend on `config_id`
+    pub async fn get_dependents(&self, config_id: u64) -> Vec<u64> {
+        match scan_edges(&self.db) {
+            Ok(edges) => edges
+                .into_iter()
+                .filter(|&(_, to)| to == config_id)
+                .map(|(from, _)| from)
+                .collect(),
+            Err(e) => {
+                warn!("Failed to read dependents of config {}: {}", config_id, e);
+                Vec::new()
+            }
+        }
+    }
+
+    /// Deployment order for `root_ids` and everything they transitively depend on
+    ///
+    /// Dependencies always come before the configs that depend on them.
+    /// Returns a validation error naming the cycle if the graph is not a DAG.
+    pub async fn topological_sort(&self, root_ids: &[u64]) -> Result<Vec<u64>> {
+        let mut graph = BTreeMap::new();
+        for (from, to) in scan_edges(&self.db)? {
+            graph.entry(from).or_insert_with(Vec::new).push(to);
+        }
+        sort_dependencies(&graph, root_ids)
+    }
+
+    /// Configs to roll back, in order, when rolling back `config_id`
+    ///
+    /// Without `cascade` only the config itself is returned. With `cascade`
+    /// all transitive dependents are included in reverse topological order,
+    /// so the most downstream configs are rolled back first and `config_id` last.
+    pub async fn rollback_order(&self, config_id: u64, cascade: bool) -> Result<Vec<u64>> {
+        if !cascade {
+            return Ok(vec![config_id]);
+        }
+
+        let edges = scan_edges(&self.db)?;
+        let mut affected = BTreeSet::from([config_id]);
+        let mut pending = vec![config_id];
+        while let Some(current) = pending.pop() {
+            for &(from, _) in edges.iter().filter(|&&(_, to)| to == current) {
+                if affected.insert(from) {
+                    pending.push(from);
+                }
+            }
+        }
+
+        // Sort the affected subgraph and reverse it: dependents before dependencies
+        let mut graph = BTreeMap::new();
+        for &(from, to) in &edges {
+            if affected.contains(&from) && affected.contains(&to) {
+                graph.entry(from).or_insert_with(Vec::new).push(to);
+            }
+        }
+        let roots: Vec<u64> = affected.into_iter().collect();
+        let mut order = sort_dependencies(&graph, &roots)?;
+        order.reverse();
+        Ok(order)
+    }
+
+    /// The version to roll back to: the latest version older than the released one
+    pub async fn previous_release_version(&self, config_id: u64) -> Option<u64> {
+        let config = self.get_config_meta(config_id).await?;
+        let current = config
+            .releases
+            .iter()
+            .find(|release| release.labels.is_empty() && release.effective_at.is_none())
+            .map_or(config.latest_version_id, |release| release.version_id);
+
+        self.versions
+            .read()
+            .await
+            .get(&config_id)?
+            .range(..current)
+            .next_back()
+            .map(|(&version_id, _)| version_id)
+    }
+}
+
+/// Depth-first topological sort over `graph` (config -> dependencies) from `roots`
+fn sort_dependencies(graph: &BTreeMap<u64, Vec<u64>>, roots: &[u64]) -> Result<Vec<u64>> {
+    let mut order = Vec::new();
+    let mut done = BTreeSet::new();
+    let mut path = Vec::new();
+    for &root in roots {
+        visit(root, graph, &mut done, &mut path, &mut order)?;
+    }
+    Ok(order)
+}
+
+fn visit(
+    node: u64,
+    graph: &BTreeMap<u64, Vec<u64>>,
+    done: &mut BTreeSet<u64>,
+    path: &mut Vec<u64>,
+    order: &mut Vec<u64>,
+) -> Result<()> {
+    if done.contains(&node) {
+        return Ok(());
+    }
+    if let Some(start) = path.iter().position(|&id| id == node) {
+        let cycle: Vec<String> = path[start..]
+            .iter()
+            .chain(std::iter::once(&node))
+            .map(|id| id.to_string())
+            .collect();
+        return Err(ConfluxError::validation(format!(
+            "dependency cycle detected: {}",
+            cycle.join(" -> ")
+        )));
+    }
+
+    path.push(node);
+    for &dependency in graph.get(&node).into_iter().flatten() {
+        visit(dependency, graph, done, path, order)?;
+    }
+    path.pop();
+
+    done.insert(node);
+    order.push(node);
+    Ok(())
+}
+
+fn dependencies_cf(db: &DB) -> Result<&rocksdb::ColumnFamily> {
+    db.cf_handle(CF_DEPENDENCIES)
+        .ok_or_else(|| ConfluxError::storage("Dependencies column family not found"))
+}
+
+/// Edge key: big-endian `from` followed by big-endian `to`, so edges group by source
+fn edge_key(from: u64, to: u64) -> [u8; 16] {
+    let mut key = [0u8; 16];
+    key[..8].copy_from_slice(&from.to_be_bytes());
+    key[8..].copy_from_slice(&to.to_be_bytes());
+    key
+}
+
+fn parse_edge_key(key: &[u8]) -> Option<Edge> {
+    let from = u64::from_be_bytes(key.get(..8)?.try_into().ok()?);
+    let to = u64::from_be_bytes(key.get(8..16)?.try_into().ok()?);
+    Some((from, to))
+}
+
+/// Read all dependency edges
+fn scan_edges(db: &DB) -> Result<Vec<Edge>> {
+    let cf = dependencies_cf(db)?;
+    let mut edges = Vec::new();
+    for item in db.iterator_cf(cf, IteratorMode::Start) {
+        let (key, _) = item.map_err(|e| {
+            ConfluxError::storage(format!("Failed to read config dependency: {}", e))
+        })?;
+        edges.extend(parse_edge_key(&key));
+    }
+    Ok(edges)
+}
+
+/// Read the direct dependencies of a single config
+fn scan_dependencies_of(db: &DB, config_id: u64) -> Result<Vec<u64>> {
+    let cf = dependencies_cf(db)?;
+    let prefix = config_id.to_be_bytes();
+    let mut dependencies = Vec::new();
+    for item in db.iterator_cf(cf, IteratorMode::From(&prefix, Direction::Forward)) {
+        let (key, _) = item.map_err(|e| {
+            ConfluxError::storage(format!("Failed to read config dependency: {}", e))
+        })?;
+        match parse_edge_key(&key) {
+            Some((from, to)) if from == config_id => dependencies.push(to),
+            _ => break,
+        }
+    }
+    Ok(dependencies)
+}
+
+#[cfg(test)]
+#[path = "dependencies_tests.rs"]
+mod tests;
@@ -0,0 +1,163 @@
+use super::*;
+use crate::raft::store::test_support::create_store;
+
+/// Create `count` configs and return their IDs
+async fn create_configs(store: &Store, count: usize) -> Vec<u64> {
+    let mut ids = Vec::new();
+    for i in 0..count {
+        let response = store
+            .apply_command(&RaftCommand::CreateConfig {
+                namespace: ConfigNamespace {
+                    tenant: "tenant".to_string(),
+                    app: "app".to_string(),
+                    env: "prod".to_string(),
+                },
+                name: format!("config-{}.json", i),
+                content: b"{}".to_vec(),
+                format: ConfigFormat::Json,
+                schema: None,
+                creator_id: 1,
+                description: "test".to_string(),
+            })
+            .await
+            .unwrap();
+        ids.push(response.config_id.unwrap());
+    }
+    ids
+}
+
+async fn add_dependency(store: &Store, from: u64, to: u64) -> ClientWriteResponse {
+    store
+        .apply_command(&RaftCommand::AddConfigDependency {
+            from_config_id: from,
+            to_config_id: to,
+        })
+        .await
+        .unwrap()
+}
+
+fn position(order: &[u64], id: u64) -> usize {
+    order.iter().position(|&x| x == id).unwrap()
+}
+
+#[tokio::test]
+async fn test_topological_sort_orders_dependencies_first() {
+    let (store, _dir) = create_store().await;
+    let ids = create_configs(&store, 4).await;
+    let (app, db, cache, base) = (ids[0], ids[1], ids[2], ids[3]);
+
+    // Diamond: app -> {db, cache} -> base
+    for (from, to) in [(app, db), (app, cache), (db, base), (cache, base)] {
+        assert!(add_dependency(&store, from, to).await.success);
+    }
+
+    let mut direct = store.get_dependencies(app).await;
+    direct.sort();
+    assert_eq!(direct, vec![db, cache]);
+    assert_eq!(store.get_dependencies(base).await, Vec::<u64>::new());
+    assert_eq!(store.get_dependents(base).await.len(), 2);
+
+    let order = store.topological_sort(&[app]).await.unwrap();
+    assert_eq!(order.len(), 4);
+    assert_eq!(order[0], base);
+    assert_eq!(order[3], app);
+    assert!(position(&order, db) < position(&order, app));
+    assert!(position(&order, cache) < position(&order, app));
+
+    // Sorting a leaf only includes its own dependencies
+    assert_eq!(store.topological_sort(&[db]).await.unwrap(), vec![base, db]);
+}
+
+#[tokio::test]
+async fn test_dependency_cycles_are_rejected() {
+    let (store, _dir) = create_store().await;
+    let ids = create_configs(&store, 3).await;
+
+    assert!(add_dependency(&store, ids[0], ids[1]).await.success);
+    assert!(add_dependency(&store, ids[1], ids[2]).await.success);
+
+    let response = add_dependency(&store, ids[2], ids[0]).await;
+    assert!(!response.success);
+    assert!(response.message.contains("cycle"));
+    assert!(!add_dependency(&store, ids[0], ids[0]).await.success);
+    assert!(!add_dependency(&store, ids[0], 999).await.success);
+
+    assert!(store.get_dependencies(ids[2]).await.is_empty());
+}
+
+#[test]
+fn test_sort_dependencies_reports_cycle_path() {
+    let graph = BTreeMap::from([(1, vec![2]), (2, vec![3]), (3, vec![1])]);
+    let err = sort_dependencies(&graph, &[1]).unwrap_err();
+    assert!(matches!(err, ConfluxError::Validation(_)));
+    assert!(err.to_string().contains("dependency cycle detected: 1 -> 2 -> 3 -> 1"));
+
+    let dag = BTreeMap::from([(1, vec![2, 3]), (2, vec![3])]);
+    assert_eq!(sort_dependencies(&dag, &[1]).unwrap(), vec![3, 2, 1]);
+}
+
+#[tokio::test]
+async fn test_rollback_order_puts_dependents_first() {
+    let (store, _dir) = create_store().await;
+    let ids = create_configs(&store, 4).await;
+    let (app, db, base, unrelated) = (ids[0], ids[1], ids[2], ids[3]);
+    for (from, to) in [(app, db), (db, base)] {
+        assert!(add_dependency(&store, from, to).await.success);
+    }
+
+    assert_eq!(store.rollback_order(base, false).await.unwrap(), vec![base]);
+    assert_eq!(
+        store.rollback_order(base, true).await.unwrap(),
+        vec![app, db, base]
+    );
+    assert_eq!(store.rollback_order(unrelated, true).await.unwrap(), vec![unrelated]);
+}
+
+#[tokio::test]
+async fn test_remove_and_delete_clean_up_edges() {
+    let (store, _dir) = create_store().await;
+    let ids = create_configs(&store, 3).await;
+    assert!(add_dependency(&store, ids[0], ids[1]).await.success);
+    assert!(add_dependency(&store, ids[0], ids[2]).await.success);
+
+    let response = store
+        .apply_command(&RaftCommand::RemoveConfigDependency {
+            from_config_id: ids[0],
+            to_config_id: ids[1],
+        })
+        .await
+        .unwrap();
+    assert!(response.success);
+    assert_eq!(store.get_dependencies(ids[0]).await, vec![ids[2]]);
+
+    store
+        .apply_command(&RaftCommand::DeleteConfig { config_id: ids[2] })
+        .await
+        .unwrap();
+    assert!(store.get_dependencies(ids[0]).await.is_empty());
+}
+
+#[tokio::test]
+async fn test_previous_release_version() {
+    let (store, _dir) = create_store().await;
+    let config_id = create_configs(&store, 1).await[0];
+    assert_eq!(store.previous_release_version(config_id).await, None);
+
+    for content in [b"v2", b"v3"] {
+        store
+            .apply_command(&RaftCommand::CreateVersion {
+                config_id,
+                content: content.to_vec(),
+                format: None,
+                creator_id: 1,
+                description: "next".to_string(),
+            })
+            .await
+            .unwrap();
+    }
+    store
+        .apply_command(&RaftCommand::ReleaseVersion { config_id, version_id: 3 })
+        .await
+        .unwrap();
+    assert_eq!(store.previous_release_version(config_id).await, Some(2));
+}
@@ -0,0 +1,110 @@
+use crate::error::Result;
+use crate::raft::types::*;
+use super::limits::ContentLimitRegistry;
+use super::read_cache::PublishedConfigCache;
+use super::transaction::{is_transactional, TransactionUndo};
+use super::types::Store;
+use dashmap::DashMap;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use tokio::sync::{broadcast, RwLock};
+
+impl Store {
+    /// Apply a command to a scratch copy of the store and return the response
+    /// it would produce
+    ///
+    /// The command goes through exactly the validation of the real command
+    /// handlers, run against a scratch store holding copies of the in-memory
+    /// entries the command may touch and a copy of the content limits. It
+    /// reads everything else from this store's RocksDB, while its writes are
+    /// staged and dropped; nothing is persisted, replicated or announced to
+    /// watchers.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error the command handler would return.
+    pub async fn dry_run(&self, command: &RaftCommand) -> Result<ClientWriteResponse> {
+        let scratch = self.scratch_for(command).await;
+        scratch
+            .without_writes(scratch.dispatch_command(command))
+            .await
+    }
+
+    /// A store sharing this store's RocksDB, with its own copy of the
+    /// in-memory entries `command` may touch
+    ///
+    /// Commands whose changes are limited to the configs they name, alone or
+    /// in a transaction, only get those configs with their versions and the
+    /// usage of their tenants. Any other command gets a copy of the whole
+    /// in-memory state.
+    async fn scratch_for(&self, command: &RaftCommand) -> Store {
+        let commands = match command {
+            RaftCommand::Transaction { commands } => commands.as_slice(),
+            command => std::slice::from_ref(command),
+        };
+
+        let mut touched = TransactionUndo {
+            next_config_id: *self.next_config_id.read().await,
+            ..TransactionUndo::default()
+        };
+        if commands.iter().all(is_transactional) {
+            for command in commands {
+                self.record_undo(&mut touched, command).await;
+            }
+        } else {
+            touched.configurations = self
+                .configurations
+                .read()
+                .await
+                .iter()
+                .map(|(key, config)| (key.clone(), Some(config.clone())))
+                .collect();
+            touched.versions = self
+                .versions
+                .read()
+                .await
+                .iter()
+                .map(|(config_id, versions)| (*config_id, Some(versions.clone())))
+                .collect();
+            touched.name_index = self
+                .name_index
+                .read()
+                .await
+                .iter()
+                .map(|(key, config_id)| (key.clone(), Some(*config_id)))
+                .collect();
+            touched.tenant_usage = self
+                .tenant_usage
+                .iter()
+                .map(|usage| (usage.key().clone(), Some(*usage.value())))
+                .collect();
+        }
+
+        let (change_notifier, _) = broadcast::channel(1);
+        Store {
+            configurations: Arc::new(RwLock::new(existing(touched.configurations))),
+            versions: Arc::new(RwLock::new(existing(touched.versions))),
+            name_index: Arc::new(RwLock::new(existing(touched.name_index))),
+            next_config_id: Arc::new(RwLock::new(touched.next_config_id)),
+            change_notifier: Arc::new(change_notifier),
+            event_sender: None,
+            content_limits: Arc::new(ContentLimitRegistry::clone(&self.content_limits)),
+            published_cache: Arc::new(PublishedConfigCache::default()),
+            apply_lock: Arc::new(tokio::sync::Mutex::new(())),
+            tenant_usage: Arc::new(DashMap::from_iter(existing(touched.tenant_usage))),
+            ..self.clone()
+        }
+    }
+}
+
+/// The entries of a recorded map that exist
+fn existing<K: Ord, V>(entries: BTreeMap<K, Option<V>>) -> BTreeMap<K, V> {
+    entries
+        .into_iter()
+        .filter_map(|(key, value)| Some((key, value?)))
+        .collect()
+}
+
+#[cfg(test)]
+#[path = "dry_run_tests.rs"]
+mod tests;
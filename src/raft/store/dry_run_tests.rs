@@ -0,0 +1,132 @@
+use super::*;
+use crate::raft::store::{CONTENT_TOO_LARGE, QUOTA_EXCEEDED};
+use crate::raft::store::test_support::create_store;
+use tempfile::TempDir;
+
+fn namespace() -> ConfigNamespace {
+    ConfigNamespace {
+        tenant: "tenant".to_string(),
+        app: "app".to_string(),
+        env: "prod".to_string(),
+    }
+}
+
+async fn create_store_with_config() -> (Store, TempDir, u64) {
+    let (store, dir) = create_store().await;
+    let response = store
+        .apply_command(&RaftCommand::CreateConfig {
+            namespace: namespace(),
+            name: "app.json".to_string(),
+            content: b"{}".to_vec(),
+            format: ConfigFormat::Json,
+            schema: None,
+            creator_id: 1,
+            description: "initial".to_string(),
+        })
+        .await
+        .unwrap();
+    (store, dir, response.config_id.unwrap())
+}
+
+fn create_version(config_id: u64, content: &[u8]) -> RaftCommand {
+    RaftCommand::CreateVersion {
+        config_id,
+        content: content.to_vec(),
+        format: None,
+        creator_id: 1,
+        description: "next".to_string(),
+    }
+}
+
+#[tokio::test]
+async fn test_dry_run_returns_response_without_changing_state() {
+    let (store, _dir, config_id) = create_store_with_config().await;
+    let mut changes = store.subscribe_changes();
+
+    let response = store.dry_run(&create_version(config_id, br#"{"v":2}"#)).await.unwrap();
+    assert!(response.success, "{}", response.message);
+    assert_eq!(response.data.unwrap()["version_id"], 2);
+
+    let config = store.get_config_meta(config_id).await.unwrap();
+    assert_eq!(config.latest_version_id, 1);
+    assert!(store.get_config_version(config_id, 2).await.is_none());
+    assert!(changes.try_recv().is_err());
+
+    // A duplicate config is rejected by the same check as a real write
+    let response = store
+        .dry_run(&RaftCommand::CreateConfig {
+            namespace: namespace(),
+            name: "app.json".to_string(),
+            content: b"{}".to_vec(),
+            format: ConfigFormat::Json,
+            schema: None,
+            creator_id: 1,
+            description: "duplicate".to_string(),
+        })
+        .await;
+    assert!(!response.is_ok_and(|r| r.success));
+}
+
+#[tokio::test]
+async fn test_dry_run_applies_store_validation() {
+    let (store, _dir, config_id) = create_store_with_config().await;
+    store
+        .apply_command(&RaftCommand::SetTenantLimits {
+            tenant: "tenant".to_string(),
+            max_config_content_bytes: Some(8),
+            max_version_history: None,
+        })
+        .await
+        .unwrap();
+
+    let response = store
+        .dry_run(&create_version(config_id, b"0123456789"))
+        .await
+        .unwrap();
+    assert!(!response.success);
+    assert!(response.message.contains(CONTENT_TOO_LARGE), "{}", response.message);
+
+    let response = store.dry_run(&create_version(config_id, b"[]")).await.unwrap();
+    assert!(response.success);
+    assert_eq!(store.get_config_meta(config_id).await.unwrap().latest_version_id, 1);
+}
+
+#[tokio::test]
+async fn test_dry_run_copies_only_the_touched_config() {
+    let (store, _dir, config_id) = create_store_with_config().await;
+    let other = store
+        .apply_command(&RaftCommand::CreateConfig {
+            namespace: namespace(),
+            name: "other.json".to_string(),
+            content: b"{}".to_vec(),
+            format: ConfigFormat::Json,
+            schema: None,
+            creator_id: 1,
+            description: "initial".to_string(),
+        })
+        .await
+        .unwrap();
+    assert!(other.success, "{}", other.message);
+    store
+        .apply_command(&RaftCommand::SetTenantQuota {
+            tenant: "tenant".to_string(),
+            max_configs: None,
+            max_versions_per_config: Some(1),
+            max_total_bytes: None,
+        })
+        .await
+        .unwrap();
+
+    let scratch = store.scratch_for(&create_version(config_id, b"[]")).await;
+    let configs = scratch.configurations.read().await;
+    assert_eq!(configs.len(), 1);
+    assert_eq!(configs.values().next().unwrap().id, config_id);
+    assert_eq!(scratch.versions.read().await.keys().collect::<Vec<_>>(), vec![&config_id]);
+    assert_eq!(scratch.tenant_usage("tenant"), store.tenant_usage("tenant"));
+    drop(configs);
+
+    // Quotas are read from the store itself
+    let response = store.dry_run(&create_version(config_id, b"[]")).await.unwrap();
+    assert!(!response.success);
+    assert!(response.message.contains(QUOTA_EXCEEDED), "{}", response.message);
+}
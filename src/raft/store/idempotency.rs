@@ -0,0 +1,198 @@
+use crate::error::{ConfluxError, Result};
+use crate::raft::types::*;
+use super::constants::CF_IDEMPOTENCY;
+use super::types::Store;
+use chrono::{DateTime, Utc};
+use rocksdb::{IteratorMode, WriteBatch, DB};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::time::Duration;
+use tracing::{debug, info, warn};
+
+/// How long the response of a write is kept under its idempotency key
+pub const IDEMPOTENCY_KEY_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Interval between the leader's scans for idempotency keys older than
+/// [`IDEMPOTENCY_KEY_TTL`]
+pub const IDEMPOTENCY_GC_INTERVAL: Duration = Duration::from_secs(600);
+
+/// Message prefix of the response to a command whose idempotency key was
+/// already used for a different command
+pub const IDEMPOTENCY_KEY_CONFLICT: &str = "idempotency key reused for a different request";
+
+/// Response of an applied write, recorded under its idempotency key
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct IdempotencyRecord {
+    response: ClientWriteResponse,
+    recorded_at: DateTime<Utc>,
+    /// SHA-256 of the applied command, absent on records written before
+    /// commands were hashed
+    #[serde(default)]
+    command_hash: Option<String>,
+}
+
+impl IdempotencyRecord {
+    fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        now.signed_duration_since(self.recorded_at)
+            .to_std()
+            .is_ok_and(|age| age >= IDEMPOTENCY_KEY_TTL)
+    }
+}
+
+impl Store {
+    /// Apply a command at most once per idempotency key
+    ///
+    /// Without a key the command is simply applied. With a key seen within
+    /// [`IDEMPOTENCY_KEY_TTL`] before the key was proposed, the response
+    /// recorded for it is returned and the command is not applied again;
+    /// otherwise the command is applied and its response recorded in the same
+    /// write batch. Commands that fail with an error are not recorded, so
+    /// they can be retried under the same key.
+    ///
+    /// A key recorded for a different command is not replayed: the command
+    /// is rejected with a response starting with [`IDEMPOTENCY_KEY_CONFLICT`].
+    pub async fn apply_idempotent(
+        &self,
+        idempotency_key: Option<&StampedIdempotencyKey>,
+        command: &RaftCommand,
+    ) -> Result<ClientWriteResponse> {
+        let Some(StampedIdempotencyKey { key, proposed_at }) = idempotency_key else {
+            return self.apply_state_change(command).await;
+        };
+
+        let command_hash = command_hash(command)?;
+        let _applying = self.apply_lock.lock().await;
+        if let Some(record) = read_record(&self.db, key)?.filter(|r| !r.is_expired(*proposed_at)) {
+            if record.command_hash.is_some_and(|hash| hash != command_hash) {
+                warn!("Idempotency key {} reused for a different command", key);
+                return Ok(Self::create_error_response(format!(
+                    "{}: key {} was used for another command within the last {} hours",
+                    IDEMPOTENCY_KEY_CONFLICT,
+                    key,
+                    IDEMPOTENCY_KEY_TTL.as_secs() / 3600
+                )));
+            }
+            debug!("Returning recorded response for idempotency key {}", key);
+            return Ok(record.response);
+        }
+
+        self.staged(async {
+            let result = self.journal_and_apply(command).await;
+            if let Ok(response) = &result {
+                let record = IdempotencyRecord {
+                    response: response.clone(),
+                    recorded_at: *proposed_at,
+                    command_hash: Some(command_hash),
+                };
+                self.write_with(|batch| put_record(&self.db, batch, key, &record))?;
+            }
+            Ok(result)
+        })
+        .await?
+    }
+
+    /// The response recorded under an idempotency key, unless it has expired
+    pub fn idempotent_response(&self, idempotency_key: &str) -> Result<Option<ClientWriteResponse>> {
+        let now = Utc::now();
+        Ok(read_record(&self.db, idempotency_key)?
+            .filter(|record| !record.is_expired(now))
+            .map(|record| record.response))
+    }
+
+    /// Whether any idempotency key has expired at `now`
+    ///
+    /// Lets the leader skip proposing an eviction when there is nothing to do.
+    pub fn has_expired_idempotency_keys(&self, now: DateTime<Utc>) -> Result<bool> {
+        Ok(!expired_keys(&self.db, now)?.is_empty())
+    }
+
+    /// Handle evict idempotency keys command
+    ///
+    /// Deletes the keys recorded longer than [`IDEMPOTENCY_KEY_TTL`] before
+    /// `now`, the leader's clock when it proposed the command, so every
+    /// replica keeps the same keys.
+    pub(crate) fn handle_evict_idempotency_keys(
+        &self,
+        now: &DateTime<Utc>,
+    ) -> Result<ClientWriteResponse> {
+        let keys = expired_keys(&self.db, *now)?;
+        let cf = idempotency_cf(&self.db)?;
+        self.write_with(|batch| {
+            for key in &keys {
+                batch.delete_cf(cf, key);
+            }
+            Ok(())
+        })?;
+
+        let evicted = keys.len();
+        if evicted > 0 {
+            info!("Evicted {} expired idempotency keys", evicted);
+        }
+        Ok(Self::create_success_response(
+            format!("Evicted {} expired idempotency keys", evicted),
+            Some(serde_json::json!({ "evicted": evicted })),
+        ))
+    }
+}
+
+fn idempotency_cf(db: &DB) -> Result<&rocksdb::ColumnFamily> {
+    db.cf_handle(CF_IDEMPOTENCY)
+        .ok_or_else(|| ConfluxError::storage("Idempotency column family not found"))
+}
+
+fn read_record(db: &DB, key: &str) -> Result<Option<IdempotencyRecord>> {
+    let cf = idempotency_cf(db)?;
+    let Some(data) = db.get_cf(cf, key.as_bytes()).map_err(|e| {
+        ConfluxError::storage(format!("Failed to read idempotency key: {}", e))
+    })?
+    else {
+        return Ok(None);
+    };
+    serde_json::from_slice(&data)
+        .map(Some)
+        .map_err(|e| ConfluxError::storage(format!("Failed to deserialize idempotency record: {}", e)))
+}
+
+/// Hex SHA-256 of the serialized command
+///
+/// Commands only hold ordered collections, so every replica serializes, and
+/// thus hashes, a command the same way.
+fn command_hash(command: &RaftCommand) -> Result<String> {
+    let data = serde_json::to_vec(command).map_err(|e| {
+        ConfluxError::storage(format!("Failed to serialize command for hashing: {}", e))
+    })?;
+    Ok(format!("{:x}", Sha256::digest(data)))
+}
+
+fn put_record(db: &DB, batch: &mut WriteBatch, key: &str, record: &IdempotencyRecord) -> Result<()> {
+    let cf = idempotency_cf(db)?;
+    let data = serde_json::to_vec(record).map_err(|e| {
+        ConfluxError::storage(format!("Failed to serialize idempotency record: {}", e))
+    })?;
+    batch.put_cf(cf, key.as_bytes(), data);
+    Ok(())
+}
+
+/// Keys of the idempotency records that have expired at `now`
+fn expired_keys(db: &DB, now: DateTime<Utc>) -> Result<Vec<Box<[u8]>>> {
+    let cf = idempotency_cf(db)?;
+    let mut keys = Vec::new();
+    for item in db.iterator_cf(cf, IteratorMode::Start) {
+        let (key, value) = item.map_err(|e| {
+            ConfluxError::storage(format!("Failed to read idempotency keys: {}", e))
+        })?;
+        let expired = match serde_json::from_slice::<IdempotencyRecord>(&value) {
+            Ok(record) => record.is_expired(now),
+            // Unreadable records cannot be returned either, so they are dropped too
+            Err(_) => true,
+        };
+        if expired {
+            keys.push(key);
+        }
+    }
+    Ok(keys)
+}
+
+#[cfg(test)]
+#[path = "idempotency_tests.rs"]
+mod tests;
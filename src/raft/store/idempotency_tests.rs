@@ -0,0 +1,175 @@
+use super::*;
+use chrono::Duration as ChronoDuration;
+use tempfile::tempdir;
+
+fn namespace() -> ConfigNamespace {
+    ConfigNamespace {
+        tenant: "acme".to_string(),
+        app: "app".to_string(),
+        env: "prod".to_string(),
+    }
+}
+
+fn create_config(name: &str) -> RaftCommand {
+    RaftCommand::CreateConfig {
+        namespace: namespace(),
+        name: name.to_string(),
+        content: b"{}".to_vec(),
+        format: ConfigFormat::Json,
+        schema: None,
+        creator_id: 1,
+        description: "initial".to_string(),
+    }
+}
+
+fn stamped(key: &str, proposed_at: DateTime<Utc>) -> StampedIdempotencyKey {
+    StampedIdempotencyKey {
+        key: key.to_string(),
+        proposed_at,
+    }
+}
+
+fn ttl() -> ChronoDuration {
+    ChronoDuration::from_std(IDEMPOTENCY_KEY_TTL).unwrap()
+}
+
+#[tokio::test]
+async fn test_repeated_key_returns_recorded_response() {
+    let dir = tempdir().unwrap();
+    let (store, _rx) = Store::new(dir.path()).await.unwrap();
+    let key = StampedIdempotencyKey::now("retry-1");
+
+    let first = store
+        .apply_idempotent(Some(&key), &create_config("app.json"))
+        .await
+        .unwrap();
+    assert!(first.success);
+
+    // The retry is not applied again, although a second create would fail
+    let retry = store
+        .apply_idempotent(Some(&key), &create_config("app.json"))
+        .await
+        .unwrap();
+    assert!(retry.success);
+    assert_eq!(retry.config_id, first.config_id);
+    assert_eq!(
+        store.list_configs_in_namespace_including_deleted(&namespace()).await.len(),
+        1
+    );
+    assert_eq!(
+        store.idempotent_response("retry-1").unwrap().unwrap().config_id,
+        first.config_id
+    );
+
+    // Another key applies the command
+    let other = store
+        .apply_idempotent(Some(&StampedIdempotencyKey::now("retry-2")), &create_config("other.json"))
+        .await
+        .unwrap();
+    assert_ne!(other.config_id, first.config_id);
+    assert!(store.idempotent_response("unknown").unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_key_reused_for_another_command_is_rejected() {
+    let dir = tempdir().unwrap();
+    let (store, _rx) = Store::new(dir.path()).await.unwrap();
+    let key = StampedIdempotencyKey::now("retry-1");
+
+    let first = store
+        .apply_idempotent(Some(&key), &create_config("app.json"))
+        .await
+        .unwrap();
+    assert!(first.success);
+
+    let reused = store
+        .apply_idempotent(Some(&key), &create_config("other.json"))
+        .await
+        .unwrap();
+    assert!(!reused.success);
+    assert!(reused.message.starts_with(IDEMPOTENCY_KEY_CONFLICT));
+    let configs = store.list_configs_in_namespace_including_deleted(&namespace()).await;
+    assert_eq!(configs.len(), 1);
+    assert_eq!(configs[0].name, "app.json");
+
+    // The recorded response is kept for retries of the original command
+    let retry = store
+        .apply_idempotent(Some(&key), &create_config("app.json"))
+        .await
+        .unwrap();
+    assert!(retry.success);
+    assert_eq!(retry.config_id, first.config_id);
+}
+
+#[tokio::test]
+async fn test_expiry_follows_proposal_time() {
+    let dir = tempdir().unwrap();
+    let (store, _rx) = Store::new(dir.path()).await.unwrap();
+    let recorded_at = Utc::now() - ttl() * 2;
+
+    let first = store
+        .apply_idempotent(Some(&stamped("retry", recorded_at)), &create_config("app.json"))
+        .await
+        .unwrap();
+    assert!(first.success);
+
+    // Proposed within the TTL of the recorded proposal, whatever this node's clock says
+    let retry = store
+        .apply_idempotent(
+            Some(&stamped("retry", recorded_at + ttl() / 2)),
+            &create_config("app.json"),
+        )
+        .await
+        .unwrap();
+    assert_eq!(retry.config_id, first.config_id);
+    assert_eq!(
+        store.list_configs_in_namespace_including_deleted(&namespace()).await.len(),
+        1
+    );
+
+    // Proposed after the TTL, the key no longer suppresses the command
+    let expired = store
+        .apply_idempotent(
+            Some(&stamped("retry", recorded_at + ttl())),
+            &create_config("other.json"),
+        )
+        .await
+        .unwrap();
+    assert!(expired.success);
+    assert_ne!(expired.config_id, first.config_id);
+}
+
+#[tokio::test]
+async fn test_expired_keys_are_evicted_through_commands() {
+    let dir = tempdir().unwrap();
+    let (store, _rx) = Store::new(dir.path()).await.unwrap();
+    let now = Utc::now();
+    store
+        .apply_idempotent(Some(&stamped("stale", now - ttl())), &create_config("app.json"))
+        .await
+        .unwrap();
+    store
+        .apply_idempotent(Some(&stamped("fresh", now)), &create_config("other.json"))
+        .await
+        .unwrap();
+    assert!(store.idempotent_response("stale").unwrap().is_none());
+    assert!(store.idempotent_response("fresh").unwrap().is_some());
+    assert!(store.has_expired_idempotency_keys(now).unwrap());
+
+    let response = store
+        .apply_command(&RaftCommand::EvictIdempotencyKeys { now })
+        .await
+        .unwrap();
+    assert!(response.success, "{}", response.message);
+    assert_eq!(response.data.unwrap()["evicted"], 1);
+    assert!(!store.has_expired_idempotency_keys(now).unwrap());
+    assert!(store.idempotent_response("fresh").unwrap().is_some());
+
+    let later = now + ttl();
+    assert!(store.has_expired_idempotency_keys(later).unwrap());
+    store
+        .apply_command(&RaftCommand::EvictIdempotencyKeys { now: later })
+        .await
+        .unwrap();
+    assert!(!store.has_expired_idempotency_keys(later).unwrap());
+}
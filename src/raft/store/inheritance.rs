@@ -0,0 +1,92 @@
+use crate::error::{ConfluxError, Result};
+use crate::raft::types::ConfigNamespace;
+use super::types::Store;
+use dashmap::DashMap;
+use std::collections::HashSet;
+use std::sync::Arc;
+
+/// Parent environments that namespaces fall back to for missing configs
+///
+/// A namespace whose env inherits from another env resolves configs it does
+/// not define from the namespace of the same tenant and app in the parent
+/// env, e.g. `prod` falling back to `staging`. Parents may themselves have
+/// parents; cycles are rejected when a parent is set.
+#[derive(Debug, Default)]
+pub struct EnvInheritance {
+    parents: DashMap<ConfigNamespace, String>,
+}
+
+impl EnvInheritance {
+    /// Make `namespace` inherit from `parent_env`, or stop inheriting with `None`
+    pub fn set_parent(&self, namespace: &ConfigNamespace, parent_env: Option<&str>) -> Result<()> {
+        let Some(parent_env) = parent_env else {
+            self.parents.remove(namespace);
+            return Ok(());
+        };
+
+        let parent = Self::sibling(namespace, parent_env);
+        if self.chain(&parent).contains(namespace) {
+            return Err(ConfluxError::validation(format!(
+                "Environment '{}' of {}/{} cannot inherit from '{}': inheritance cycle",
+                namespace.env, namespace.tenant, namespace.app, parent_env
+            )));
+        }
+
+        self.parents.insert(namespace.clone(), parent_env.to_string());
+        Ok(())
+    }
+
+    /// Env that `namespace` inherits from, if any
+    pub fn parent(&self, namespace: &ConfigNamespace) -> Option<String> {
+        self.parents.get(namespace).map(|parent| parent.clone())
+    }
+
+    /// `namespace` followed by its ancestors, nearest first
+    pub fn chain(&self, namespace: &ConfigNamespace) -> Vec<ConfigNamespace> {
+        let mut chain = vec![namespace.clone()];
+        let mut visited: HashSet<String> = HashSet::from([namespace.env.clone()]);
+        let mut current = namespace.clone();
+        while let Some(parent_env) = self.parent(&current) {
+            // Cycles are rejected by set_parent; this only bounds the walk
+            if !visited.insert(parent_env.clone()) {
+                break;
+            }
+            current = Self::sibling(&current, &parent_env);
+            chain.push(current.clone());
+        }
+        chain
+    }
+
+    fn sibling(namespace: &ConfigNamespace, env: &str) -> ConfigNamespace {
+        ConfigNamespace {
+            tenant: namespace.tenant.clone(),
+            app: namespace.app.clone(),
+            env: env.to_string(),
+        }
+    }
+}
+
+impl Store {
+    /// Environment inheritance rules of this store
+    pub fn env_inheritance(&self) -> Arc<EnvInheritance> {
+        self.env_inheritance.clone()
+    }
+
+    /// Make `namespace` fall back to `parent_env` for configs it does not define
+    ///
+    /// Cached published reads are dropped since they may have resolved
+    /// through the previous rules.
+    pub fn set_env_inheritance(
+        &self,
+        namespace: &ConfigNamespace,
+        parent_env: Option<&str>,
+    ) -> Result<()> {
+        self.env_inheritance.set_parent(namespace, parent_env)?;
+        self.published_cache.invalidate_all();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[path = "inheritance_tests.rs"]
+mod tests;
@@ -0,0 +1,93 @@
+use super::*;
+use crate::raft::types::*;
+use crate::raft::store::test_support::create_store;
+use std::collections::BTreeMap;
+
+fn namespace(env: &str) -> ConfigNamespace {
+    ConfigNamespace {
+        tenant: "acme".to_string(),
+        app: "app".to_string(),
+        env: env.to_string(),
+    }
+}
+
+fn create_config(env: &str, content: &[u8]) -> RaftCommand {
+    RaftCommand::CreateConfig {
+        namespace: namespace(env),
+        name: "app.json".to_string(),
+        content: content.to_vec(),
+        format: ConfigFormat::Json,
+        schema: None,
+        creator_id: 1,
+        description: "initial".to_string(),
+    }
+}
+
+async fn published_content(store: &Store, env: &str) -> Option<Vec<u8>> {
+    store
+        .get_published_config(&namespace(env), "app.json", &BTreeMap::new())
+        .await
+        .map(|(_, version)| version.content)
+}
+
+#[tokio::test]
+async fn test_missing_config_resolves_from_parent_env() {
+    let (store, _dir) = create_store().await;
+    store.apply_command(&create_config("staging", b"{\"env\":\"staging\"}")).await.unwrap();
+    assert_eq!(published_content(&store, "prod").await, None);
+
+    store.set_env_inheritance(&namespace("prod"), Some("staging")).unwrap();
+    assert_eq!(
+        published_content(&store, "prod").await.as_deref(),
+        Some(&b"{\"env\":\"staging\"}"[..])
+    );
+
+    // A config defined in the env itself takes precedence over the inherited one
+    store.apply_command(&create_config("prod", b"{\"env\":\"prod\"}")).await.unwrap();
+    assert_eq!(
+        published_content(&store, "prod").await.as_deref(),
+        Some(&b"{\"env\":\"prod\"}"[..])
+    );
+    assert_eq!(
+        published_content(&store, "staging").await.as_deref(),
+        Some(&b"{\"env\":\"staging\"}"[..])
+    );
+}
+
+#[tokio::test]
+async fn test_inheritance_follows_the_chain() {
+    let (store, _dir) = create_store().await;
+    store.apply_command(&create_config("dev", b"{}")).await.unwrap();
+    store.set_env_inheritance(&namespace("prod"), Some("staging")).unwrap();
+    store.set_env_inheritance(&namespace("staging"), Some("dev")).unwrap();
+
+    assert_eq!(
+        store.env_inheritance().chain(&namespace("prod")),
+        vec![namespace("prod"), namespace("staging"), namespace("dev")]
+    );
+    assert!(published_content(&store, "prod").await.is_some());
+
+    store.set_env_inheritance(&namespace("staging"), None).unwrap();
+    assert_eq!(published_content(&store, "prod").await, None);
+}
+
+#[test]
+fn test_inheritance_cycles_are_rejected() {
+    let inheritance = EnvInheritance::default();
+    assert!(inheritance.set_parent(&namespace("prod"), Some("prod")).is_err());
+
+    inheritance.set_parent(&namespace("prod"), Some("staging")).unwrap();
+    inheritance.set_parent(&namespace("staging"), Some("dev")).unwrap();
+    let err = inheritance
+        .set_parent(&namespace("dev"), Some("prod"))
+        .unwrap_err();
+    assert!(matches!(err, ConfluxError::Validation(_)));
+    assert_eq!(inheritance.parent(&namespace("dev")), None);
+
+    // Other apps are unaffected by the rules of this one
+    let other = ConfigNamespace {
+        app: "other".to_string(),
+        ..namespace("dev")
+    };
+    inheritance.set_parent(&other, Some("prod")).unwrap();
+}
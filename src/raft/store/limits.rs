@@ -0,0 +1,297 @@
+use crate::error::{ConfluxError, Result};
+use crate::raft::types::{ClientWriteResponse, ConfigNamespace};
+use crate::raft::validation::validate_tenant;
+use super::constants::CF_META;
+use super::types::Store;
+use dashmap::DashMap;
+use rocksdb::{Direction, IteratorMode};
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+/// Default maximum size of a single config version's content (512 KB)
+pub const DEFAULT_MAX_CONFIG_CONTENT_BYTES: usize = 512 * 1024;
+
+/// Default maximum number of versions kept per config
+pub const DEFAULT_MAX_VERSION_HISTORY: usize = 100;
+
+/// Error message prefix for content that exceeds the size limit
+pub const CONTENT_TOO_LARGE: &str = "content exceeds maximum size";
+
+/// Error message prefix for configs that reached the version limit
+pub const VERSION_LIMIT_REACHED: &str = "version history limit reached";
+
+/// Meta column family key prefix of tenant limit overrides, followed by the tenant
+const TENANT_LIMITS_PREFIX: u8 = 0x07;
+
+/// Limits applied to config content written through the store
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContentLimits {
+    /// Maximum size in bytes of a single version's content
+    pub max_config_content_bytes: usize,
+    /// Maximum number of versions a config may accumulate
+    pub max_version_history: usize,
+}
+
+impl Default for ContentLimits {
+    fn default() -> Self {
+        Self {
+            max_config_content_bytes: DEFAULT_MAX_CONFIG_CONTENT_BYTES,
+            max_version_history: DEFAULT_MAX_VERSION_HISTORY,
+        }
+    }
+}
+
+/// Per-tenant overrides of the default content limits, replicated through Raft
+///
+/// A limit left as `None` falls back to the node default.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TenantLimits {
+    /// Maximum size in bytes of a single version's content
+    pub max_config_content_bytes: Option<usize>,
+    /// Maximum number of versions a config may accumulate
+    pub max_version_history: Option<usize>,
+}
+
+impl TenantLimits {
+    /// Whether no limit is overridden
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Limits resulting from applying these overrides to `defaults`
+    pub fn apply_to(&self, defaults: ContentLimits) -> ContentLimits {
+        ContentLimits {
+            max_config_content_bytes: self
+                .max_config_content_bytes
+                .unwrap_or(defaults.max_config_content_bytes),
+            max_version_history: self
+                .max_version_history
+                .unwrap_or(defaults.max_version_history),
+        }
+    }
+}
+
+/// Node-wide content limits with per-tenant overrides
+///
+/// Shared between the store, which enforces the limits, and the node's
+/// resource limiter, which configures the defaults. Tenant overrides are
+/// only changed by applying [`crate::raft::types::RaftCommand::SetTenantLimits`],
+/// so every replica enforces the same overrides.
+#[derive(Debug)]
+pub struct ContentLimitRegistry {
+    max_config_content_bytes: AtomicUsize,
+    max_version_history: AtomicUsize,
+    tenant_overrides: DashMap<String, TenantLimits>,
+    namespace_max_versions: DashMap<ConfigNamespace, u32>,
+}
+
+impl Clone for ContentLimitRegistry {
+    fn clone(&self) -> Self {
+        Self {
+            max_config_content_bytes: AtomicUsize::new(
+                self.max_config_content_bytes.load(Ordering::Relaxed),
+            ),
+            max_version_history: AtomicUsize::new(self.max_version_history.load(Ordering::Relaxed)),
+            tenant_overrides: self.tenant_overrides.clone(),
+            namespace_max_versions: self.namespace_max_versions.clone(),
+        }
+    }
+}
+
+impl Default for ContentLimitRegistry {
+    fn default() -> Self {
+        Self::new(ContentLimits::default())
+    }
+}
+
+impl ContentLimitRegistry {
+    pub fn new(defaults: ContentLimits) -> Self {
+        Self {
+            max_config_content_bytes: AtomicUsize::new(defaults.max_config_content_bytes),
+            max_version_history: AtomicUsize::new(defaults.max_version_history),
+            tenant_overrides: DashMap::new(),
+            namespace_max_versions: DashMap::new(),
+        }
+    }
+
+    /// Replace the limits applied to tenants without an override
+    pub fn set_defaults(&self, defaults: ContentLimits) {
+        self.max_config_content_bytes
+            .store(defaults.max_config_content_bytes, Ordering::Relaxed);
+        self.max_version_history
+            .store(defaults.max_version_history, Ordering::Relaxed);
+    }
+
+    /// Limits applied to tenants without an override
+    pub fn defaults(&self) -> ContentLimits {
+        ContentLimits {
+            max_config_content_bytes: self.max_config_content_bytes.load(Ordering::Relaxed),
+            max_version_history: self.max_version_history.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Override the limits for a single tenant; empty overrides are removed
+    pub(crate) fn set_tenant_limits(&self, tenant: &str, limits: TenantLimits) {
+        if limits.is_empty() {
+            self.tenant_overrides.remove(tenant);
+        } else {
+            self.tenant_overrides.insert(tenant.to_string(), limits);
+        }
+    }
+
+    /// Remove every tenant override, before they are reloaded from disk
+    pub(crate) fn clear_tenant_limits(&self) {
+        self.tenant_overrides.clear();
+    }
+
+    /// Overrides set for a tenant
+    pub fn tenant_limits(&self, tenant: &str) -> TenantLimits {
+        self.tenant_overrides
+            .get(tenant)
+            .map(|limits| *limits)
+            .unwrap_or_default()
+    }
+
+    /// Set the number of versions kept by configs of a namespace without their own policy
+    ///
+    /// `None` removes the default so that versions are kept without limit.
+    pub fn set_default_max_versions(&self, namespace: &ConfigNamespace, max_versions: Option<u32>) {
+        match max_versions {
+            Some(max) => {
+                self.namespace_max_versions.insert(namespace.clone(), max);
+            }
+            None => {
+                self.namespace_max_versions.remove(namespace);
+            }
+        }
+    }
+
+    /// Number of versions kept by configs of a namespace without their own policy
+    pub fn default_max_versions(&self, namespace: &ConfigNamespace) -> Option<u32> {
+        self.namespace_max_versions.get(namespace).map(|max| *max)
+    }
+
+    /// Effective limits for a tenant
+    pub fn limits_for(&self, tenant: &str) -> ContentLimits {
+        self.tenant_limits(tenant).apply_to(self.defaults())
+    }
+}
+
+impl Store {
+    /// Content limits enforced by this store
+    pub fn content_limits(&self) -> Arc<ContentLimitRegistry> {
+        self.content_limits.clone()
+    }
+
+    /// Handle set tenant limits command
+    ///
+    /// Overrides without any limit remove the tenant's override.
+    pub(crate) async fn handle_set_tenant_limits(
+        &self,
+        tenant: &str,
+        limits: TenantLimits,
+    ) -> Result<ClientWriteResponse> {
+        if let Err(e) = validate_tenant(tenant) {
+            return Ok(Self::create_error_response(e.to_string()));
+        }
+
+        let cf = meta_cf(&self.db)?;
+        let key = tenant_limits_key(tenant);
+        if limits.is_empty() {
+            self.write_with(|batch| {
+                batch.delete_cf(cf, key);
+                Ok(())
+            })?;
+        } else {
+            let data = serde_json::to_vec(&limits).map_err(|e| {
+                ConfluxError::storage(format!("Failed to serialize tenant limits: {}", e))
+            })?;
+            self.write_with(|batch| {
+                batch.put_cf(cf, key, data);
+                Ok(())
+            })?;
+        }
+        self.content_limits.set_tenant_limits(tenant, limits);
+
+        Ok(Self::create_success_response(
+            format!("Content limits of tenant {} updated", tenant),
+            Some(serde_json::json!({
+                "tenant": tenant,
+                "limits": limits,
+                "effective": self.content_limits.limits_for(tenant)
+            })),
+        ))
+    }
+
+    /// Replace the tenant overrides of the registry with the ones stored on disk
+    pub(crate) fn load_tenant_limits(&self) -> Result<()> {
+        let cf = meta_cf(&self.db)?;
+        self.content_limits.clear_tenant_limits();
+        let prefix = [TENANT_LIMITS_PREFIX];
+        let iter = self
+            .db
+            .iterator_cf(cf, IteratorMode::From(&prefix, Direction::Forward));
+        for item in iter {
+            let (key, value) = item.map_err(|e| {
+                ConfluxError::storage(format!("Failed to read tenant limits: {}", e))
+            })?;
+            if key.first() != Some(&TENANT_LIMITS_PREFIX) {
+                break;
+            }
+            let tenant = String::from_utf8(key[1..].to_vec()).map_err(|e| {
+                ConfluxError::storage(format!("Invalid tenant limits key: {}", e))
+            })?;
+            let limits: TenantLimits = serde_json::from_slice(&value).map_err(|e| {
+                ConfluxError::storage(format!("Failed to deserialize tenant limits: {}", e))
+            })?;
+            self.content_limits.set_tenant_limits(&tenant, limits);
+        }
+        Ok(())
+    }
+
+    /// Reject content larger than the tenant's maximum config size
+    pub fn check_content_size(&self, tenant: &str, content_len: usize) -> Result<()> {
+        let max = self.content_limits.limits_for(tenant).max_config_content_bytes;
+        if content_len > max {
+            return Err(ConfluxError::validation(format!(
+                "{} ({} > {} bytes)",
+                CONTENT_TOO_LARGE, content_len, max
+            )));
+        }
+        Ok(())
+    }
+
+    /// Reject new versions for configs that reached the tenant's version limit
+    pub async fn check_version_history(&self, tenant: &str, config_id: u64) -> Result<()> {
+        let max = self.content_limits.limits_for(tenant).max_version_history;
+        let count = self
+            .versions
+            .read()
+            .await
+            .get(&config_id)
+            .map_or(0, |versions| versions.len());
+        if count >= max {
+            return Err(ConfluxError::validation(format!(
+                "{} ({} versions, limit {})",
+                VERSION_LIMIT_REACHED, count, max
+            )));
+        }
+        Ok(())
+    }
+}
+
+fn meta_cf(db: &rocksdb::DB) -> Result<&rocksdb::ColumnFamily> {
+    db.cf_handle(CF_META)
+        .ok_or_else(|| ConfluxError::storage("Meta column family not found"))
+}
+
+fn tenant_limits_key(tenant: &str) -> Vec<u8> {
+    let mut key = vec![TENANT_LIMITS_PREFIX];
+    key.extend_from_slice(tenant.as_bytes());
+    key
+}
+
+#[cfg(test)]
+#[path = "limits_tests.rs"]
+mod tests;
@@ -0,0 +1,194 @@
+use super::*;
+use crate::raft::types::*;
+use crate::raft::store::test_support::create_store;
+use tempfile::tempdir;
+
+fn namespace(tenant: &str) -> ConfigNamespace {
+    ConfigNamespace {
+        tenant: tenant.to_string(),
+        app: "app".to_string(),
+        env: "prod".to_string(),
+    }
+}
+
+fn create_config(tenant: &str, content: Vec<u8>) -> RaftCommand {
+    RaftCommand::CreateConfig {
+        namespace: namespace(tenant),
+        name: "app.json".to_string(),
+        content,
+        format: ConfigFormat::Json,
+        schema: None,
+        creator_id: 1,
+        description: "initial".to_string(),
+    }
+}
+
+fn create_version(config_id: u64, content: Vec<u8>) -> RaftCommand {
+    RaftCommand::CreateVersion {
+        config_id,
+        content,
+        format: None,
+        creator_id: 1,
+        description: "next".to_string(),
+    }
+}
+
+fn set_tenant_limits(
+    tenant: &str,
+    max_config_content_bytes: Option<usize>,
+    max_version_history: Option<usize>,
+) -> RaftCommand {
+    RaftCommand::SetTenantLimits {
+        tenant: tenant.to_string(),
+        max_config_content_bytes,
+        max_version_history,
+    }
+}
+
+fn small_limits() -> ContentLimits {
+    ContentLimits {
+        max_config_content_bytes: 16,
+        max_version_history: 3,
+    }
+}
+
+#[tokio::test]
+async fn test_content_size_limit_boundary() {
+    let (store, _dir) = create_store().await;
+    store.content_limits().set_defaults(small_limits());
+
+    let response = store
+        .apply_command(&create_config("acme", vec![b'a'; 17]))
+        .await
+        .unwrap();
+    assert!(!response.success);
+    assert!(response.message.contains(CONTENT_TOO_LARGE), "{}", response.message);
+
+    let response = store
+        .apply_command(&create_config("acme", vec![b'a'; 16]))
+        .await
+        .unwrap();
+    assert!(response.success);
+    let config_id = response.config_id.unwrap();
+
+    assert!(!store.apply_command(&create_version(config_id, vec![b'b'; 17])).await.unwrap().success);
+    assert!(store.apply_command(&create_version(config_id, vec![b'b'; 16])).await.unwrap().success);
+}
+
+#[tokio::test]
+async fn test_version_history_limit_boundary() {
+    let (store, _dir) = create_store().await;
+    store.content_limits().set_defaults(small_limits());
+
+    let config_id = store
+        .apply_command(&create_config("acme", b"v1".to_vec()))
+        .await
+        .unwrap()
+        .config_id
+        .unwrap();
+    for content in [b"v2", b"v3"] {
+        assert!(store.apply_command(&create_version(config_id, content.to_vec())).await.unwrap().success);
+    }
+    assert_eq!(store.list_config_versions(config_id).await.len(), 3);
+
+    let response = store
+        .apply_command(&create_version(config_id, b"v4".to_vec()))
+        .await
+        .unwrap();
+    assert!(!response.success);
+    assert!(response.message.contains(VERSION_LIMIT_REACHED), "{}", response.message);
+
+    // Updates also create versions and are held to the same limit
+    let update = RaftCommand::UpdateConfig {
+        config_id,
+        namespace: namespace("acme"),
+        name: "app.json".to_string(),
+        content: b"v4".to_vec(),
+        format: ConfigFormat::Json,
+        schema: None,
+        description: "update".to_string(),
+    };
+    assert!(!store.apply_command(&update).await.unwrap().success);
+    assert_eq!(store.list_config_versions(config_id).await.len(), 3);
+}
+
+#[tokio::test]
+async fn test_tenant_overrides_take_precedence() {
+    let (store, _dir) = create_store().await;
+    let limits = store.content_limits();
+    limits.set_defaults(small_limits());
+    for command in [
+        set_tenant_limits("big", Some(1024), Some(10)),
+        set_tenant_limits("tiny", Some(4), Some(1)),
+    ] {
+        assert!(store.apply_command(&command).await.unwrap().success);
+    }
+
+    assert!(store.apply_command(&create_config("big", vec![b'a'; 1024])).await.unwrap().success);
+    assert!(!store.apply_command(&create_config("acme", vec![b'a'; 17])).await.unwrap().success);
+    assert!(!store.apply_command(&create_config("tiny", vec![b'a'; 5])).await.unwrap().success);
+
+    let config_id = store
+        .apply_command(&create_config("tiny", b"v1".to_vec()))
+        .await
+        .unwrap()
+        .config_id
+        .unwrap();
+    assert!(!store.apply_command(&create_version(config_id, b"v2".to_vec())).await.unwrap().success);
+
+    // Clearing the override falls back to the defaults
+    let response = store
+        .apply_command(&set_tenant_limits("tiny", None, None))
+        .await
+        .unwrap();
+    assert!(response.success);
+    assert!(limits.tenant_limits("tiny").is_empty());
+    assert_eq!(limits.limits_for("tiny"), small_limits());
+    assert!(store.apply_command(&create_version(config_id, b"v2".to_vec())).await.unwrap().success);
+}
+
+#[tokio::test]
+async fn test_tenant_limits_survive_reload() {
+    let dir = tempdir().unwrap();
+    {
+        let (store, _rx) = Store::new(dir.path()).await.unwrap();
+        let response = store
+            .apply_command(&set_tenant_limits("acme", None, Some(2)))
+            .await
+            .unwrap();
+        assert!(response.success, "{}", response.message);
+
+        let response = store
+            .apply_command(&set_tenant_limits("", Some(1), None))
+            .await
+            .unwrap();
+        assert!(!response.success);
+    }
+
+    let (store, _rx) = Store::new(dir.path()).await.unwrap();
+    let limits = store.content_limits().limits_for("acme");
+    assert_eq!(limits.max_version_history, 2);
+    assert_eq!(limits.max_config_content_bytes, DEFAULT_MAX_CONFIG_CONTENT_BYTES);
+    assert_eq!(
+        store.content_limits().limits_for("other").max_version_history,
+        DEFAULT_MAX_VERSION_HISTORY
+    );
+}
+
+#[tokio::test]
+async fn test_dry_run_does_not_change_tenant_limits() {
+    let (store, _dir) = create_store().await;
+    let response = store
+        .dry_run(&set_tenant_limits("acme", Some(4), None))
+        .await
+        .unwrap();
+    assert!(response.success);
+    assert!(store.content_limits().tenant_limits("acme").is_empty());
+}
+
+#[test]
+fn test_default_content_limits() {
+    let limits = ContentLimitRegistry::default();
+    assert_eq!(limits.defaults().max_config_content_bytes, 512 * 1024);
+    assert_eq!(limits.limits_for("any").max_version_history, 100);
+}
@@ -0,0 +1,88 @@
+//! Encoding of the Raft log entries held by the store
+//!
+//! Entries are serialized as JSON behind a one-byte prefix telling whether
+//! the JSON is zstd compressed, so entries written before and after
+//! compression is toggled can be read back alike.
+
+use crate::raft::types::*;
+use openraft::{Entry, EntryPayload};
+use std::io;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+/// Prefix of an entry whose JSON is zstd compressed
+pub(crate) const LOG_ENTRY_COMPRESSED: u8 = 0x5A;
+
+/// Prefix of an entry stored as plain JSON
+pub(crate) const LOG_ENTRY_RAW: u8 = 0x00;
+
+/// zstd level of compressed entries, favouring speed over ratio
+const LOG_COMPRESSION_LEVEL: i32 = 3;
+
+/// Encodes log entries and counts the bytes it writes
+#[derive(Debug, Default)]
+pub(crate) struct LogCodec {
+    compress: AtomicBool,
+    compressed_bytes: AtomicU64,
+    uncompressed_bytes: AtomicU64,
+}
+
+impl LogCodec {
+    /// Compress the entries encoded from now on, or stop compressing them
+    pub(crate) fn set_compression(&self, enabled: bool) {
+        self.compress.store(enabled, Ordering::Relaxed);
+    }
+
+    /// Serialize `entry` for the log
+    ///
+    /// Only entries carrying a `ClientRequest` are compressed: membership
+    /// changes and blank entries are too small to benefit.
+    pub(crate) fn encode(&self, entry: &Entry<TypeConfig>) -> io::Result<Vec<u8>> {
+        let json = serde_json::to_vec(entry)?;
+        let compress = self.compress.load(Ordering::Relaxed)
+            && matches!(entry.payload, EntryPayload::Normal(_));
+
+        let uncompressed_len = json.len() as u64;
+        let (prefix, body) = if compress {
+            (
+                LOG_ENTRY_COMPRESSED,
+                zstd::bulk::compress(&json, LOG_COMPRESSION_LEVEL)?,
+            )
+        } else {
+            (LOG_ENTRY_RAW, json)
+        };
+        self.uncompressed_bytes
+            .fetch_add(uncompressed_len, Ordering::Relaxed);
+        self.compressed_bytes
+            .fetch_add(body.len() as u64, Ordering::Relaxed);
+
+        let mut encoded = Vec::with_capacity(body.len() + 1);
+        encoded.push(prefix);
+        encoded.extend_from_slice(&body);
+        Ok(encoded)
+    }
+
+    /// Bytes of entry JSON as stored and before compression, excluding the
+    /// prefix, over all entries encoded since the store was opened
+    pub(crate) fn stats(&self) -> (u64, u64) {
+        (
+            self.compressed_bytes.load(Ordering::Relaxed),
+            self.uncompressed_bytes.load(Ordering::Relaxed),
+        )
+    }
+}
+
+/// Deserialize an entry written by [`LogCodec::encode`]
+pub(crate) fn decode_log_entry(encoded: &[u8]) -> io::Result<Entry<TypeConfig>> {
+    match encoded.split_first() {
+        Some((&LOG_ENTRY_RAW, json)) => Ok(serde_json::from_slice(json)?),
+        Some((&LOG_ENTRY_COMPRESSED, compressed)) => {
+            let json = zstd::stream::decode_all(compressed)?;
+            Ok(serde_json::from_slice(&json)?)
+        }
+        Some((prefix, _)) => Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("Unknown log entry prefix {:#04x}", prefix),
+        )),
+        None => Err(io::Error::new(io::ErrorKind::InvalidData, "Empty log entry")),
+    }
+}
@@ -4,16 +4,68 @@ mod types;
 mod store;
 mod persistence;
 mod config_ops;
+mod approvals;
+mod bulk;
+mod chain;
+mod clone;
 mod commands;
+mod compaction;
+mod consistency;
 mod delete_handlers;
+mod dry_run;
+mod dependencies;
+mod dedup;
+mod delta;
+mod idempotency;
+mod inheritance;
+mod limits;
+mod log_codec;
+mod pruning;
+mod quotas;
+mod read_cache;
+mod schemas;
+mod search;
+mod snapshot_stream;
+mod soft_delete;
+mod staging;
+mod webhooks;
+mod webhook_notifier;
+mod template;
+mod values;
+mod scheduler;
 mod raft_impl;
 // 注释掉旧的 raft_storage，使用新的 v2 版本
 // mod raft_storage;
 mod raft_storage_v2;
 mod transaction;
+mod wal;
 
 // Re-export public types and functions
-pub use types::{Store, StateMachineManager};
+pub use approvals::APPROVAL_REQUIRED;
+pub use chain::ChainVerificationReport;
+pub use clone::CloneReport;
+pub use compaction::CompactionStats;
+pub use consistency::{ConsistencyChecker, ConsistencyReport, CONSISTENCY_CHECK_INTERVAL};
+pub use dedup::DedupStats;
+pub use delta::{apply_delta, encode_delta, DELTA_FULL_COPY_INTERVAL, DELTA_MIN_BASE_SIZE};
+pub use idempotency::{IDEMPOTENCY_GC_INTERVAL, IDEMPOTENCY_KEY_CONFLICT, IDEMPOTENCY_KEY_TTL};
+pub use inheritance::EnvInheritance;
+pub use persistence::StorageStats;
+pub use quotas::{TenantQuota, TenantQuotaUsage, TenantUsage, QUOTA_EXCEEDED};
+pub use limits::{
+    ContentLimitRegistry, ContentLimits, TenantLimits, CONTENT_TOO_LARGE,
+    DEFAULT_MAX_CONFIG_CONTENT_BYTES, DEFAULT_MAX_VERSION_HISTORY, VERSION_LIMIT_REACHED,
+};
+pub(crate) use log_codec::decode_log_entry;
+pub use template::render_template;
+pub use snapshot_stream::{
+    read_snapshot_chunk, snapshot_file, spill_snapshot, RestoredSnapshot, SnapshotStream,
+};
+pub use soft_delete::SOFT_DELETE_GC_INTERVAL;
+pub use scheduler::{ScheduledRelease, SCHEDULED_RELEASE_POLL_INTERVAL};
+pub use wal::{WalStats, WAL_MAX_FILE_BYTES};
+pub use webhook_notifier::WebhookNotifier;
+pub use types::{ConfigChangeEvent, ConfigChangeType, ConfluxSnapshot, Store, StateMachineManager};
 // Commented out unused exports until needed
 // pub use types::{ConfluxStateMachine, ConfluxSnapshot, ConfigChangeEvent, ConfigChangeType};
 
@@ -28,3 +80,5 @@ mod raft_storage_tests;
 mod raft_impl_tests;
 #[cfg(test)]
 mod test_fixes;
+#[cfg(test)]
+mod test_support;
@@ -1,8 +1,9 @@
 use crate::error::Result;
 use crate::raft::types::*;
 use super::constants::*;
+use super::delta::resolve_version;
 use super::types::Store;
-use rocksdb::IteratorMode;
+use rocksdb::{IteratorMode, WriteBatch};
 use std::collections::BTreeMap;
 use tracing::{debug, info, warn};
 
@@ -22,6 +23,9 @@ impl Store {
         
         // Load metadata
         self.load_metadata().await?;
+
+        self.load_tenant_limits()?;
+        self.rebuild_tenant_usage().await;
         
         info!("Successfully loaded all data from disk");
         Ok(())
@@ -30,14 +34,22 @@ impl Store {
     /// Load configurations from RocksDB
     async fn load_configurations(&self) -> Result<()> {
         debug!("Loading configurations from RocksDB");
-        
+
+        let stored_configs = self.read_configurations_from_disk()?;
+        let count = stored_configs.len();
+        self.configurations.write().await.extend(stored_configs);
+
+        debug!("Loaded {} configurations", count);
+        Ok(())
+    }
+
+    /// Read all configurations from RocksDB, keyed by config key
+    pub(crate) fn read_configurations_from_disk(&self) -> Result<BTreeMap<ConfigKey, Config>> {
         let cf_configs = self.db.cf_handle(CF_CONFIGS).ok_or_else(|| {
             crate::error::ConfluxError::storage("Configurations column family not found")
         })?;
 
-        let mut configurations = self.configurations.write().await;
-        let mut count = 0;
-
+        let mut configurations = BTreeMap::new();
         for item in self.db.iterator_cf(cf_configs, IteratorMode::Start) {
             let (key, value) = item.map_err(|e| {
                 crate::error::ConfluxError::storage(format!("Failed to read config: {}", e))
@@ -52,68 +64,130 @@ impl Store {
             })?;
 
             configurations.insert(config_key, config);
-            count += 1;
         }
 
-        debug!("Loaded {} configurations", count);
-        Ok(())
+        Ok(configurations)
     }
 
     /// Load versions from RocksDB
     async fn load_versions(&self) -> Result<()> {
         debug!("Loading versions from RocksDB");
-        
+
+        let resolved = self.read_resolved_versions_from_disk()?;
+        let count: usize = resolved.values().map(BTreeMap::len).sum();
+        self.versions.write().await.extend(resolved);
+
+        debug!("Loaded {} versions", count);
+        Ok(())
+    }
+
+    /// Read all versions from RocksDB with their full content, keyed by config and version ID
+    fn read_resolved_versions_from_disk(
+        &self,
+    ) -> Result<BTreeMap<u64, BTreeMap<u64, ConfigVersion>>> {
+        let mut stored_versions: BTreeMap<u64, BTreeMap<u64, ConfigVersion>> = BTreeMap::new();
+        for version in self.read_versions_from_disk()? {
+            stored_versions
+                .entry(version.config_id)
+                .or_default()
+                .insert(version.id, version);
+        }
+
+        // Deltas are resolved once here, so memory only ever holds full content.
+        // Versions are resolved in ID order against the already resolved ones,
+        // which holds every base a delta can refer to.
+        let mut versions: BTreeMap<u64, BTreeMap<u64, ConfigVersion>> = BTreeMap::new();
+        for (config_id, stored) in stored_versions {
+            let config_versions = versions.entry(config_id).or_default();
+            for version in stored.into_values() {
+                match resolve_version(config_versions, &version) {
+                    Ok(resolved) => {
+                        config_versions.insert(resolved.id, resolved);
+                    }
+                    Err(e) => warn!(
+                        "Skipping version {} of config {}: {}",
+                        version.id, config_id, e
+                    ),
+                }
+            }
+        }
+        Ok(versions)
+    }
+
+    /// Read a single version from RocksDB in its stored (possibly delta) form
+    pub(crate) fn read_version_from_disk(
+        &self,
+        config_id: u64,
+        version_id: u64,
+    ) -> Result<Option<ConfigVersion>> {
         let cf_versions = self.db.cf_handle(CF_VERSIONS).ok_or_else(|| {
             crate::error::ConfluxError::storage("Versions column family not found")
         })?;
 
-        let mut versions = self.versions.write().await;
-        let mut count = 0;
+        let stored = self
+            .db
+            .get_cf(cf_versions, make_version_key(config_id, version_id))
+            .map_err(|e| {
+                crate::error::ConfluxError::storage(format!("Failed to read version: {}", e))
+            })?;
+        stored
+            .map(|data| {
+                serde_json::from_slice(&data).map_err(|e| {
+                    crate::error::ConfluxError::storage(format!(
+                        "Failed to deserialize version: {}",
+                        e
+                    ))
+                })
+            })
+            .transpose()
+    }
+
+    /// Read all versions from RocksDB in their stored (possibly delta) form
+    pub(crate) fn read_versions_from_disk(&self) -> Result<Vec<ConfigVersion>> {
+        let cf_versions = self.db.cf_handle(CF_VERSIONS).ok_or_else(|| {
+            crate::error::ConfluxError::storage("Versions column family not found")
+        })?;
 
+        let mut versions = Vec::new();
         for item in self.db.iterator_cf(cf_versions, IteratorMode::Start) {
             let (key, value) = item.map_err(|e| {
                 crate::error::ConfluxError::storage(format!("Failed to read version: {}", e))
             })?;
 
-            // Parse version key (config_id + version_id)
+            // Version key is config_id + version_id
             if key.len() < 16 {
                 warn!("Invalid version key length: {}", key.len());
                 continue;
             }
 
-            let config_id = u64::from_be_bytes([
-                key[0], key[1], key[2], key[3], key[4], key[5], key[6], key[7],
-            ]);
-            let version_id = u64::from_be_bytes([
-                key[8], key[9], key[10], key[11], key[12], key[13], key[14], key[15],
-            ]);
-
             let version: ConfigVersion = serde_json::from_slice(&value).map_err(|e| {
                 crate::error::ConfluxError::storage(format!("Failed to deserialize version: {}", e))
             })?;
-
-            versions
-                .entry(config_id)
-                .or_insert_with(BTreeMap::new)
-                .insert(version_id, version);
-            count += 1;
+            versions.push(version);
         }
 
-        debug!("Loaded {} versions", count);
-        Ok(())
+        Ok(versions)
     }
 
     /// Load name index from RocksDB
     async fn load_name_index(&self) -> Result<()> {
         debug!("Loading name index from RocksDB");
-        
+
+        let stored_index = self.read_name_index_from_disk()?;
+        let count = stored_index.len();
+        self.name_index.write().await.extend(stored_index);
+
+        debug!("Loaded {} name index entries", count);
+        Ok(())
+    }
+
+    /// Read the name index from RocksDB, mapping config keys to config IDs
+    pub(crate) fn read_name_index_from_disk(&self) -> Result<BTreeMap<ConfigKey, u64>> {
         let cf_meta = self.db.cf_handle(CF_META).ok_or_else(|| {
             crate::error::ConfluxError::storage("Meta column family not found")
         })?;
 
-        let mut name_index = self.name_index.write().await;
-        let mut count = 0;
-
+        let mut name_index = BTreeMap::new();
         for item in self.db.iterator_cf(cf_meta, IteratorMode::Start) {
             let (key, value) = item.map_err(|e| {
                 crate::error::ConfluxError::storage(format!("Failed to read name index: {}", e))
@@ -134,42 +208,133 @@ impl Store {
             ]);
 
             name_index.insert(name_key, config_id);
-            count += 1;
         }
 
-        debug!("Loaded {} name index entries", count);
-        Ok(())
+        Ok(name_index)
     }
 
     /// Load metadata from RocksDB
     async fn load_metadata(&self) -> Result<()> {
         debug!("Loading metadata from RocksDB");
-        
+
+        if let Some(next_id) = self.read_next_config_id_from_disk()? {
+            *self.next_config_id.write().await = next_id;
+            debug!("Loaded next_config_id: {}", next_id);
+        }
+
+        Ok(())
+    }
+
+    /// Read the stored next config ID counter, if any
+    fn read_next_config_id_from_disk(&self) -> Result<Option<u64>> {
         let cf_meta = self.db.cf_handle(CF_META).ok_or_else(|| {
             crate::error::ConfluxError::storage("Meta column family not found")
         })?;
 
-        // Load next_config_id (key: 0x01)
-        if let Some(value) = self.db.get_cf(cf_meta, &[0x01]).map_err(|e| {
+        let value = self.db.get_cf(cf_meta, NEXT_CONFIG_ID_KEY).map_err(|e| {
             crate::error::ConfluxError::storage(format!("Failed to read next_config_id: {}", e))
-        })? {
-            if value.len() >= 8 {
-                let next_id = u64::from_be_bytes([
-                    value[0], value[1], value[2], value[3], 
-                    value[4], value[5], value[6], value[7],
-                ]);
-                *self.next_config_id.write().await = next_id;
-                debug!("Loaded next_config_id: {}", next_id);
-            }
+        })?;
+        Ok(value.filter(|value| value.len() >= 8).map(|value| {
+            u64::from_be_bytes([
+                value[0], value[1], value[2], value[3],
+                value[4], value[5], value[6], value[7],
+            ])
+        }))
+    }
+
+    /// Replace the in-memory state with a fresh read of RocksDB
+    ///
+    /// Configurations, versions, the name index and the config ID counter are
+    /// read first and then swapped in while holding all of their write locks,
+    /// so readers see either the old state or the new one, never a partly
+    /// loaded one. The counter is kept past every stored config ID.
+    pub(crate) async fn replace_state_from_disk(&self) -> Result<()> {
+        let configurations = self.read_configurations_from_disk()?;
+        let versions = self.read_resolved_versions_from_disk()?;
+        let name_index = self.read_name_index_from_disk()?;
+        let max_id = configurations.values().map(|config| config.id).max();
+        let next_id = self
+            .read_next_config_id_from_disk()?
+            .unwrap_or(1)
+            .max(max_id.map_or(1, |id| id + 1));
+
+        {
+            let mut configurations_guard = self.configurations.write().await;
+            let mut versions_guard = self.versions.write().await;
+            let mut name_index_guard = self.name_index.write().await;
+            let mut next_id_guard = self.next_config_id.write().await;
+            *configurations_guard = configurations;
+            *versions_guard = versions;
+            *name_index_guard = name_index;
+            *next_id_guard = next_id;
         }
 
+        self.load_tenant_limits()?;
+        self.rebuild_tenant_usage().await;
         Ok(())
     }
 
     /// Persist a configuration to RocksDB
     pub async fn persist_config(&self, config_key: &str, config: &Config) -> Result<()> {
         debug!("Persisting config: {}", config_key);
-        
+
+        self.write_with(|batch| self.put_config(batch, config_key, config))?;
+
+        debug!("Successfully persisted config: {}", config_key);
+        Ok(())
+    }
+
+    /// Persist a configuration and one of its versions in a single atomic write
+    ///
+    /// The config, its name index entry, the version (stored as by
+    /// [`Store::persist_version`]) and its content hash index entry go into one
+    /// `WriteBatch`, so a crash never leaves a config referencing a version
+    /// that was not written, or a version without its config.
+    pub async fn persist_config_and_version(
+        &self,
+        config_key: &str,
+        config: &Config,
+        version: &ConfigVersion,
+    ) -> Result<()> {
+        debug!("Persisting config {} with version {}", config_key, version.id);
+
+        let stored = self.encode_version_for_storage(version).await?;
+        self.write_with(|batch| {
+            self.put_config(batch, config_key, config)?;
+            self.put_version(batch, &stored)
+        })
+    }
+
+    /// Persist a newly created configuration, its first version and the config ID counter
+    ///
+    /// Everything [`Store::persist_config_and_version`] writes plus
+    /// `next_config_id` goes into one `WriteBatch`, so after a crash the stored
+    /// counter is always past every stored config ID and an ID is never handed
+    /// out twice.
+    pub async fn persist_new_config(
+        &self,
+        config_key: &str,
+        config: &Config,
+        version: &ConfigVersion,
+        next_config_id: u64,
+    ) -> Result<()> {
+        debug!("Persisting new config: {} (next_config_id={})", config_key, next_config_id);
+
+        let cf_meta = self.db.cf_handle(CF_META).ok_or_else(|| {
+            crate::error::ConfluxError::storage("Meta column family not found")
+        })?;
+
+        let stored = self.encode_version_for_storage(version).await?;
+        self.write_with(|batch| {
+            self.put_config(batch, config_key, config)?;
+            self.put_version(batch, &stored)?;
+            batch.put_cf(cf_meta, NEXT_CONFIG_ID_KEY, next_config_id.to_be_bytes());
+            Ok(())
+        })
+    }
+
+    /// Add a configuration and its name index entry to `batch`
+    fn put_config(&self, batch: &mut WriteBatch, config_key: &str, config: &Config) -> Result<()> {
         let cf_configs = self.db.cf_handle(CF_CONFIGS).ok_or_else(|| {
             crate::error::ConfluxError::storage("Configurations column family not found")
         })?;
@@ -183,26 +348,37 @@ impl Store {
             crate::error::ConfluxError::storage(format!("Failed to serialize config: {}", e))
         })?;
 
-        // Store config
-        self.db.put_cf(cf_configs, config_key.as_bytes(), config_data).map_err(|e| {
-            crate::error::ConfluxError::storage(format!("Failed to store config: {}", e))
-        })?;
+        batch.put_cf(cf_configs, config_key.as_bytes(), config_data);
 
         // Update name index
         let name_index_key = make_name_index_key(&config.namespace, &config.name);
-        let config_id_bytes = config.id.to_be_bytes();
-        self.db.put_cf(cf_meta, &name_index_key, &config_id_bytes).map_err(|e| {
-            crate::error::ConfluxError::storage(format!("Failed to update name index: {}", e))
-        })?;
-
-        debug!("Successfully persisted config: {}", config_key);
+        batch.put_cf(cf_meta, &name_index_key, config.id.to_be_bytes());
         Ok(())
     }
 
     /// Persist a version to RocksDB
+    ///
+    /// The content is stored as a delta against an earlier full copy when that
+    /// saves space (see [`Store::encode_version_for_storage`]); binary content
+    /// is stored verbatim. The content hash is recorded in the hash index
+    /// alongside it.
     pub async fn persist_version(&self, version: &ConfigVersion) -> Result<()> {
         debug!("Persisting version: config_id={}, version_id={}", version.config_id, version.id);
-        
+
+        let stored = self.encode_version_for_storage(version).await?;
+        self.write_version_to_disk(&stored)
+    }
+
+    /// Write a version to RocksDB exactly as given
+    pub(crate) fn write_version_to_disk(&self, version: &ConfigVersion) -> Result<()> {
+        self.write_with(|batch| self.put_version(batch, version))?;
+
+        debug!("Successfully persisted version: config_id={}, version_id={}", version.config_id, version.id);
+        Ok(())
+    }
+
+    /// Add a version, exactly as given, and its content hash index entry to `batch`
+    fn put_version(&self, batch: &mut WriteBatch, version: &ConfigVersion) -> Result<()> {
         let cf_versions = self.db.cf_handle(CF_VERSIONS).ok_or_else(|| {
             crate::error::ConfluxError::storage("Versions column family not found")
         })?;
@@ -215,13 +391,8 @@ impl Store {
             crate::error::ConfluxError::storage(format!("Failed to serialize version: {}", e))
         })?;
 
-        // Store version
-        self.db.put_cf(cf_versions, &version_key, version_data).map_err(|e| {
-            crate::error::ConfluxError::storage(format!("Failed to store version: {}", e))
-        })?;
-
-        debug!("Successfully persisted version: config_id={}, version_id={}", version.config_id, version.id);
-        Ok(())
+        batch.put_cf(cf_versions, &version_key, version_data);
+        self.index_content_hash(batch, version)
     }
 
     /// Persist metadata to RocksDB
@@ -233,12 +404,12 @@ impl Store {
         })?;
 
         // Persist next_config_id
-        let next_config_id_key = vec![0x01];
         let next_id = *self.next_config_id.read().await;
         let next_id_bytes = next_id.to_be_bytes();
-        
-        self.db.put_cf(cf_meta, &next_config_id_key, &next_id_bytes).map_err(|e| {
-            crate::error::ConfluxError::storage(format!("Failed to persist next_config_id: {}", e))
+
+        self.write_with(|batch| {
+            batch.put_cf(cf_meta, NEXT_CONFIG_ID_KEY, next_id_bytes);
+            Ok(())
         })?;
 
         debug!("Successfully persisted metadata");
@@ -257,15 +428,12 @@ impl Store {
             crate::error::ConfluxError::storage("Meta column family not found")
         })?;
 
-        // Delete config
-        self.db.delete_cf(cf_configs, config_key.as_bytes()).map_err(|e| {
-            crate::error::ConfluxError::storage(format!("Failed to delete config: {}", e))
-        })?;
-
-        // Delete name index
+        // Delete config and its name index entry
         let name_index_key = make_name_index_key(&config.namespace, &config.name);
-        self.db.delete_cf(cf_meta, &name_index_key).map_err(|e| {
-            crate::error::ConfluxError::storage(format!("Failed to delete name index: {}", e))
+        self.write_with(|batch| {
+            batch.delete_cf(cf_configs, config_key.as_bytes());
+            batch.delete_cf(cf_meta, &name_index_key);
+            Ok(())
         })?;
 
         debug!("Successfully deleted config from disk: {}", config_key);
@@ -274,31 +442,54 @@ impl Store {
 
     /// Delete a version from RocksDB
     pub async fn delete_version_from_disk(&self, config_id: u64, version_id: u64) -> Result<()> {
+        let content_hash = self
+            .read_version_from_disk(config_id, version_id)?
+            .map(|version| version.content_hash);
+        self.remove_version_from_disk(config_id, version_id, content_hash.as_deref())
+    }
+
+    /// Delete a version and, given its content hash, its hash index entry from RocksDB
+    ///
+    /// Callers holding the removed version pass its hash, so that a version
+    /// whose write is still staged is unindexed as well.
+    pub(crate) fn remove_version_from_disk(
+        &self,
+        config_id: u64,
+        version_id: u64,
+        content_hash: Option<&str>,
+    ) -> Result<()> {
         debug!("Deleting version from disk: config_id={}, version_id={}", config_id, version_id);
-        
+
         let cf_versions = self.db.cf_handle(CF_VERSIONS).ok_or_else(|| {
             crate::error::ConfluxError::storage("Versions column family not found")
         })?;
 
-        // Create version key
         let version_key = make_version_key(config_id, version_id);
-
-        // Delete version
-        self.db.delete_cf(cf_versions, &version_key).map_err(|e| {
-            crate::error::ConfluxError::storage(format!("Failed to delete version: {}", e))
+        self.write_with(|batch| {
+            // Drop the version from the hash index, which is keyed by its content hash
+            if let Some(hash) = content_hash {
+                self.unindex_content_hash(batch, hash, config_id, version_id)?;
+            }
+            batch.delete_cf(cf_versions, &version_key);
+            Ok(())
         })?;
 
         debug!("Successfully deleted version from disk: config_id={}, version_id={}", config_id, version_id);
         Ok(())
     }
 
-    /// Force flush all data to disk
+    /// Force flush the memtables of every column family to disk
     pub async fn flush_to_disk(&self) -> Result<()> {
         debug!("Flushing all data to disk");
-        
-        self.db.flush().map_err(|e| {
-            crate::error::ConfluxError::storage(format!("Failed to flush to disk: {}", e))
-        })?;
+
+        for name in COLUMN_FAMILIES {
+            let cf = self.db.cf_handle(name).ok_or_else(|| {
+                crate::error::ConfluxError::storage(format!("Column family {} not found", name))
+            })?;
+            self.db.flush_cf(cf).map_err(|e| {
+                crate::error::ConfluxError::storage(format!("Failed to flush to disk: {}", e))
+            })?;
+        }
 
         debug!("Successfully flushed all data to disk");
         Ok(())
@@ -312,12 +503,17 @@ impl Store {
         let versions_count = self.versions.read().await.values().map(|v| v.len()).sum();
         let name_index_count = self.name_index.read().await.len();
         let next_config_id = *self.next_config_id.read().await;
+        let delta_saved_bytes = self.delta_saved_bytes().await?;
+        let sst_size_bytes = super::store::sst_files_size(&self.db)?;
 
         Ok(StorageStats {
             configs_count,
             versions_count,
             name_index_count,
             next_config_id,
+            delta_saved_bytes,
+            sst_size_bytes,
+            cache_hit_rate: self.cache_hit_rate(),
         })
     }
 }
@@ -329,6 +525,12 @@ pub struct StorageStats {
     pub versions_count: usize,
     pub name_index_count: usize,
     pub next_config_id: u64,
+    /// Bytes saved on disk by delta-encoded versions
+    pub delta_saved_bytes: u64,
+    /// Size of the RocksDB SST files over all column families
+    pub sst_size_bytes: u64,
+    /// Fraction of published config reads served from the read cache
+    pub cache_hit_rate: f64,
 }
 
 #[cfg(test)]
@@ -369,8 +571,12 @@ mod tests {
             latest_version_id: 1,
             releases: vec![],
             schema: None,
+            schema_id: None,
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
+            deleted_at: None,
+            purge_at: None,
+            max_versions: None,
         };
 
         let config_key = make_config_key(&namespace, "test-config");
@@ -395,6 +601,64 @@ mod tests {
         assert_eq!(loaded_config.unwrap().id, 1);
     }
 
+    #[tokio::test]
+    async fn test_persist_config_and_version_together() {
+        let temp_dir = TempDir::new().unwrap();
+        let namespace = ConfigNamespace {
+            tenant: "test".to_string(),
+            app: "app".to_string(),
+            env: "dev".to_string(),
+        };
+        let config = Config {
+            id: 7,
+            namespace: namespace.clone(),
+            name: "db.json".to_string(),
+            latest_version_id: 1,
+            releases: vec![Release::default(1)],
+            schema: None,
+            schema_id: None,
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+            deleted_at: None,
+            purge_at: None,
+            max_versions: None,
+        };
+        let version = ConfigVersion::new(
+            1,
+            7,
+            br#"{"pool": 10}"#.to_vec(),
+            ConfigFormat::Json,
+            1,
+            "initial".to_string(),
+        );
+
+        {
+            let (store, _) = Store::new(temp_dir.path()).await.unwrap();
+            let config_key = make_config_key(&namespace, "db.json");
+            store
+                .persist_config_and_version(&config_key, &config, &version)
+                .await
+                .unwrap();
+        }
+
+        // A fresh store sees the config, its name index entry and the version
+        let (store, _) = Store::new(temp_dir.path()).await.unwrap();
+        assert_eq!(store.get_config(&namespace, "db.json").await.unwrap().id, 7);
+        assert_eq!(
+            store.read_name_index_from_disk().unwrap().values().copied().collect::<Vec<_>>(),
+            vec![7]
+        );
+        let stored = store.get_config_version(7, 1).await.unwrap();
+        assert_eq!(stored.content, version.content);
+        assert_eq!(
+            store
+                .find_configs_by_content_hash(&version.content_hash)
+                .await
+                .unwrap(),
+            vec![(namespace, "db.json".to_string(), 1)]
+        );
+    }
+
     #[tokio::test]
     async fn test_storage_stats() {
         let (store, _temp_dir) = create_test_store().await;
@@ -404,6 +668,7 @@ mod tests {
         assert_eq!(stats.versions_count, 0);
         assert_eq!(stats.name_index_count, 0);
         assert_eq!(stats.next_config_id, 1);
+        assert_eq!(stats.delta_saved_bytes, 0);
     }
 }
 
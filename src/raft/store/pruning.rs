@@ -0,0 +1,150 @@
+use crate::error::Result;
+use crate::raft::types::*;
+use super::quotas::TenantUsage;
+use super::types::Store;
+use std::collections::BTreeSet;
+use tracing::debug;
+
+impl Store {
+    /// Handle set prune policy command
+    pub(crate) async fn handle_set_prune_policy(
+        &self,
+        config_id: u64,
+        max_versions: Option<u32>,
+    ) -> Result<ClientWriteResponse> {
+        if max_versions == Some(0) {
+            return Ok(Self::create_error_response(
+                "max_versions must be at least 1".to_string(),
+            ));
+        }
+
+        let (config_key, _) = match self.find_config_by_id(config_id).await {
+            Ok(found) => found,
+            Err(_) => {
+                return Ok(Self::create_error_response(format!(
+                    "Configuration with ID {} not found",
+                    config_id
+                )));
+            }
+        };
+
+        {
+            let mut configs = self.configurations.write().await;
+            if let Some(config) = configs.get_mut(&config_key) {
+                config.max_versions = max_versions;
+                config.updated_at = chrono::Utc::now();
+                self.persist_config(&config_key, config).await?;
+            }
+        }
+
+        // Apply the new policy to the versions already stored
+        let pruned = match max_versions {
+            Some(max) => self.prune_versions(config_id, max).await?,
+            None => Vec::new(),
+        };
+
+        Ok(Self::create_success_response(
+            "Prune policy updated successfully".to_string(),
+            Some(serde_json::json!({
+                "config_id": config_id,
+                "max_versions": max_versions,
+                "pruned_version_ids": pruned
+            })),
+        ))
+    }
+
+    /// Handle prune versions command
+    pub(crate) async fn handle_prune_versions(
+        &self,
+        config_id: u64,
+        keep_last: u32,
+    ) -> Result<ClientWriteResponse> {
+        if keep_last == 0 {
+            return Ok(Self::create_error_response(
+                "keep_last must be at least 1".to_string(),
+            ));
+        }
+        if self.find_config_by_id(config_id).await.is_err() {
+            return Ok(Self::create_error_response(format!(
+                "Configuration with ID {} not found",
+                config_id
+            )));
+        }
+
+        let pruned = self.prune_versions(config_id, keep_last).await?;
+        Ok(Self::create_success_response(
+            format!("Pruned {} versions successfully", pruned.len()),
+            Some(serde_json::json!({
+                "config_id": config_id,
+                "pruned_version_ids": pruned
+            })),
+        ))
+    }
+
+    /// Apply the config's version limit, or its namespace default, after a new version
+    pub(crate) async fn enforce_prune_policy(&self, config: &Config) -> Result<Vec<u64>> {
+        let max_versions = config
+            .max_versions
+            .or_else(|| self.content_limits.default_max_versions(&config.namespace));
+        match max_versions {
+            Some(max) => self.prune_versions(config.id, max).await,
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// Delete the oldest versions of a config until at most `keep_last` remain
+    ///
+    /// Versions referenced by a release rule and the latest version are never
+    /// deleted, so fewer versions may be removed than requested. Remaining
+    /// versions are repacked so that no delta refers to a pruned base.
+    /// Returns the IDs of the deleted versions.
+    pub(crate) async fn prune_versions(&self, config_id: u64, keep_last: u32) -> Result<Vec<u64>> {
+        let (_, config) = self.find_config_by_id(config_id).await?;
+        let protected: BTreeSet<u64> = config
+            .releases
+            .iter()
+            .map(|release| release.version_id)
+            .chain(std::iter::once(config.latest_version_id))
+            .collect();
+
+        let candidates: Vec<u64> = {
+            let versions = self.versions.read().await;
+            let Some(config_versions) = versions.get(&config_id) else {
+                return Ok(Vec::new());
+            };
+            let excess = config_versions.len().saturating_sub(keep_last as usize);
+            config_versions
+                .keys()
+                .filter(|version_id| !protected.contains(version_id))
+                .take(excess)
+                .copied()
+                .collect()
+        };
+        if candidates.is_empty() {
+            return Ok(candidates);
+        }
+
+        let mut removed = Vec::new();
+        {
+            let mut versions = self.versions.write().await;
+            if let Some(config_versions) = versions.get_mut(&config_id) {
+                for version_id in &candidates {
+                    removed.extend(config_versions.remove(version_id));
+                }
+            }
+        }
+        for version in &removed {
+            self.remove_version_from_disk(config_id, version.id, Some(&version.content_hash))?;
+        }
+        // Remaining versions may have been stored as deltas against removed ones
+        self.repack_versions(config_id).await?;
+        self.remove_tenant_usage(&config.namespace.tenant, TenantUsage::of_versions(&removed));
+
+        debug!("Pruned versions {:?} of config {}", candidates, config_id);
+        Ok(candidates)
+    }
+}
+
+#[cfg(test)]
+#[path = "pruning_tests.rs"]
+mod tests;
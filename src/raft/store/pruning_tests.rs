@@ -0,0 +1,166 @@
+use super::*;
+use std::collections::BTreeMap;
+use tempfile::tempdir;
+
+fn namespace() -> ConfigNamespace {
+    ConfigNamespace {
+        tenant: "tenant".to_string(),
+        app: "app".to_string(),
+        env: "prod".to_string(),
+    }
+}
+
+/// Create a config whose default release points at version 1
+async fn create_config(store: &Store) -> u64 {
+    let response = store
+        .apply_command(&RaftCommand::CreateConfig {
+            namespace: namespace(),
+            name: "app.json".to_string(),
+            content: b"{\"v\":1}".to_vec(),
+            format: ConfigFormat::Json,
+            schema: None,
+            creator_id: 1,
+            description: "v1".to_string(),
+        })
+        .await
+        .unwrap();
+    response.config_id.unwrap()
+}
+
+async fn create_versions(store: &Store, config_id: u64, range: std::ops::RangeInclusive<u64>) {
+    for v in range {
+        let response = store
+            .apply_command(&RaftCommand::CreateVersion {
+                config_id,
+                content: format!("{{\"v\":{}}}", v).into_bytes(),
+                format: None,
+                creator_id: 1,
+                description: format!("v{}", v),
+            })
+            .await
+            .unwrap();
+        assert!(response.success, "{}", response.message);
+    }
+}
+
+async fn version_ids(store: &Store, config_id: u64) -> Vec<u64> {
+    store
+        .list_config_versions(config_id)
+        .await
+        .iter()
+        .map(|version| version.id)
+        .collect()
+}
+
+#[tokio::test]
+async fn test_prune_keeps_released_version_even_if_oldest() {
+    let dir = tempdir().unwrap();
+    let (store, _rx) = Store::new(dir.path()).await.unwrap();
+    let config_id = create_config(&store).await;
+    create_versions(&store, config_id, 2..=5).await;
+
+    let response = store
+        .apply_command(&RaftCommand::PruneVersions {
+            config_id,
+            keep_last: 2,
+        })
+        .await
+        .unwrap();
+    assert!(response.success, "{}", response.message);
+
+    // Version 1 is still released, version 5 is the latest
+    assert_eq!(version_ids(&store, config_id).await, vec![1, 5]);
+    let (_, published) = store
+        .get_published_config(&namespace(), "app.json", &BTreeMap::new())
+        .await
+        .unwrap();
+    assert_eq!(published.id, 1);
+}
+
+#[tokio::test]
+async fn test_prune_policy_applies_on_create_version() {
+    let dir = tempdir().unwrap();
+    let (store, _rx) = Store::new(dir.path()).await.unwrap();
+    let config_id = create_config(&store).await;
+
+    let response = store
+        .apply_command(&RaftCommand::SetPrunePolicy {
+            config_id,
+            max_versions: Some(3),
+        })
+        .await
+        .unwrap();
+    assert!(response.success, "{}", response.message);
+
+    create_versions(&store, config_id, 2..=6).await;
+    assert_eq!(version_ids(&store, config_id).await, vec![1, 5, 6]);
+}
+
+#[tokio::test]
+async fn test_namespace_default_max_versions() {
+    let dir = tempdir().unwrap();
+    let (store, _rx) = Store::new(dir.path()).await.unwrap();
+    let config_id = create_config(&store).await;
+    store
+        .content_limits()
+        .set_default_max_versions(&namespace(), Some(2));
+
+    create_versions(&store, config_id, 2..=4).await;
+    assert_eq!(version_ids(&store, config_id).await, vec![1, 4]);
+
+    // A config policy overrides the namespace default
+    store
+        .apply_command(&RaftCommand::SetPrunePolicy {
+            config_id,
+            max_versions: Some(4),
+        })
+        .await
+        .unwrap();
+    create_versions(&store, config_id, 5..=7).await;
+    assert_eq!(version_ids(&store, config_id).await, vec![1, 5, 6, 7]);
+}
+
+#[tokio::test]
+async fn test_pruned_versions_stay_deleted_after_reload() {
+    let dir = tempdir().unwrap();
+    let config_id = {
+        let (store, _rx) = Store::new(dir.path()).await.unwrap();
+        let config_id = create_config(&store).await;
+        create_versions(&store, config_id, 2..=4).await;
+        store
+            .apply_command(&RaftCommand::PruneVersions {
+                config_id,
+                keep_last: 1,
+            })
+            .await
+            .unwrap();
+        config_id
+    };
+
+    let (store, _rx) = Store::new(dir.path()).await.unwrap();
+    assert_eq!(version_ids(&store, config_id).await, vec![1, 4]);
+}
+
+#[tokio::test]
+async fn test_prune_rejects_zero_and_unknown_config() {
+    let dir = tempdir().unwrap();
+    let (store, _rx) = Store::new(dir.path()).await.unwrap();
+    let config_id = create_config(&store).await;
+
+    for command in [
+        RaftCommand::PruneVersions {
+            config_id,
+            keep_last: 0,
+        },
+        RaftCommand::PruneVersions {
+            config_id: 99,
+            keep_last: 1,
+        },
+        RaftCommand::SetPrunePolicy {
+            config_id,
+            max_versions: Some(0),
+        },
+    ] {
+        assert!(!store.apply_command(&command).await.unwrap().success);
+    }
+}
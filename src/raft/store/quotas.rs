@@ -0,0 +1,289 @@
+use crate::error::{ConfluxError, Result};
+use crate::raft::types::*;
+use crate::raft::validation::validate_tenant;
+use super::constants::CF_META;
+use super::types::Store;
+use rocksdb::DB;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Error message prefix for writes rejected by a tenant quota
+pub const QUOTA_EXCEEDED: &str = "tenant quota exceeded";
+
+/// Meta column family key prefix of tenant quotas, followed by the tenant
+const TENANT_QUOTA_PREFIX: u8 = 0x06;
+
+/// Limits on what a tenant may store, replicated through Raft (None = unlimited)
+///
+/// Enforced next to the tenant's [`TenantLimits`](super::TenantLimits): a
+/// write must stay within both.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TenantQuota {
+    /// Maximum number of configs
+    pub max_configs: Option<u64>,
+    /// Maximum number of versions of a single config
+    #[serde(default)]
+    pub max_versions_per_config: Option<u64>,
+    /// Maximum bytes of version content over all configs
+    pub max_total_bytes: Option<u64>,
+}
+
+impl TenantQuota {
+    /// Whether no limit is set
+    pub fn is_unlimited(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// What a tenant currently stores
+///
+/// Kept up to date by adding and subtracting the usage of the configs and
+/// versions each command creates or removes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TenantUsage {
+    /// Number of configs, including soft-deleted ones not yet purged
+    pub configs: u64,
+    /// Number of versions over all configs
+    pub versions: u64,
+    /// Bytes of version content at full size, whether or not stored as a delta
+    pub total_bytes: u64,
+}
+
+impl TenantUsage {
+    /// Usage of one config holding `versions`
+    pub(crate) fn of_config<'a>(versions: impl IntoIterator<Item = &'a ConfigVersion>) -> Self {
+        Self {
+            configs: 1,
+            ..Self::of_versions(versions)
+        }
+    }
+
+    /// Usage of `versions`, without their config
+    pub(crate) fn of_versions<'a>(versions: impl IntoIterator<Item = &'a ConfigVersion>) -> Self {
+        versions.into_iter().fold(Self::default(), |usage, version| Self {
+            versions: usage.versions + 1,
+            total_bytes: usage.total_bytes + content_bytes(version.content.len()),
+            ..usage
+        })
+    }
+
+    fn add(&mut self, other: Self) {
+        self.configs += other.configs;
+        self.versions += other.versions;
+        self.total_bytes += other.total_bytes;
+    }
+
+    fn subtract(&mut self, other: Self) {
+        self.configs = self.configs.saturating_sub(other.configs);
+        self.versions = self.versions.saturating_sub(other.versions);
+        self.total_bytes = self.total_bytes.saturating_sub(other.total_bytes);
+    }
+}
+
+/// A tenant's quota next to its current usage
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TenantQuotaUsage {
+    pub tenant: String,
+    pub quota: TenantQuota,
+    pub usage: TenantUsage,
+}
+
+impl Store {
+    /// Handle set tenant quota command
+    ///
+    /// A quota without any limit removes the tenant's quota.
+    pub(crate) async fn handle_set_tenant_quota(
+        &self,
+        tenant: &str,
+        quota: TenantQuota,
+    ) -> Result<ClientWriteResponse> {
+        if let Err(e) = validate_tenant(tenant) {
+            return Ok(Self::create_error_response(e.to_string()));
+        }
+
+        let cf = meta_cf(&self.db)?;
+        let key = tenant_quota_key(tenant);
+        if quota.is_unlimited() {
+            self.write_with(|batch| {
+                batch.delete_cf(cf, key);
+                Ok(())
+            })?;
+        } else {
+            let data = serde_json::to_vec(&quota).map_err(|e| {
+                ConfluxError::storage(format!("Failed to serialize tenant quota: {}", e))
+            })?;
+            self.write_with(|batch| {
+                batch.put_cf(cf, key, data);
+                Ok(())
+            })?;
+        }
+
+        Ok(Self::create_success_response(
+            format!("Quota of tenant {} updated", tenant),
+            Some(serde_json::json!({
+                "tenant": tenant,
+                "quota": quota
+            })),
+        ))
+    }
+
+    /// Quota of a tenant, unlimited when none was set
+    pub fn tenant_quota(&self, tenant: &str) -> Result<TenantQuota> {
+        let cf = meta_cf(&self.db)?;
+        let Some(data) = self
+            .db
+            .get_pinned_cf(cf, tenant_quota_key(tenant))
+            .map_err(|e| ConfluxError::storage(format!("Failed to read tenant quota: {}", e)))?
+        else {
+            return Ok(TenantQuota::default());
+        };
+        serde_json::from_slice(&data)
+            .map_err(|e| ConfluxError::storage(format!("Failed to deserialize tenant quota: {}", e)))
+    }
+
+    /// Current usage of a tenant
+    pub fn tenant_usage(&self, tenant: &str) -> TenantUsage {
+        self.tenant_usage
+            .get(tenant)
+            .map(|usage| *usage)
+            .unwrap_or_default()
+    }
+
+    /// Quota and current usage of a tenant
+    pub fn tenant_quota_usage(&self, tenant: &str) -> Result<TenantQuotaUsage> {
+        Ok(TenantQuotaUsage {
+            tenant: tenant.to_string(),
+            quota: self.tenant_quota(tenant)?,
+            usage: self.tenant_usage(tenant),
+        })
+    }
+
+    /// Reject a new config of `content_len` bytes that exceeds the tenant's
+    /// content limits or quota
+    ///
+    /// Command handlers turn the error into a failed response, so every
+    /// replica rejects the command the same way.
+    pub(crate) fn check_new_config(&self, tenant: &str, content_len: usize) -> Result<()> {
+        self.check_content_size(tenant, content_len)?;
+        let quota = self.tenant_quota(tenant)?;
+        let usage = self.tenant_usage(tenant);
+        if let Some(max) = quota.max_configs.filter(|max| usage.configs >= *max) {
+            return Err(quota_exceeded(format!(
+                "tenant {} has {} configs, limit {}",
+                tenant, usage.configs, max
+            )));
+        }
+        check_bytes(tenant, &quota, &usage, content_len)
+    }
+
+    /// Reject a new version of `content_len` bytes for a config that exceeds
+    /// the tenant's content limits or quota
+    ///
+    /// Command handlers turn the error into a failed response, so every
+    /// replica rejects the command the same way.
+    pub(crate) async fn check_new_version(
+        &self,
+        tenant: &str,
+        config_id: u64,
+        content_len: usize,
+    ) -> Result<()> {
+        self.check_content_size(tenant, content_len)?;
+        self.check_version_history(tenant, config_id).await?;
+        let quota = self.tenant_quota(tenant)?;
+        if let Some(max) = quota.max_versions_per_config {
+            let count = self
+                .versions
+                .read()
+                .await
+                .get(&config_id)
+                .map_or(0, |versions| versions.len() as u64);
+            if count >= max {
+                return Err(quota_exceeded(format!(
+                    "config {} has {} versions, limit {}",
+                    config_id, count, max
+                )));
+            }
+        }
+        check_bytes(tenant, &quota, &self.tenant_usage(tenant), content_len)
+    }
+
+    /// Add the usage of newly stored configs or versions to a tenant
+    pub(crate) fn add_tenant_usage(&self, tenant: &str, usage: TenantUsage) {
+        if usage != TenantUsage::default() {
+            self.tenant_usage
+                .entry(tenant.to_string())
+                .or_default()
+                .add(usage);
+        }
+    }
+
+    /// Subtract the usage of removed configs or versions from a tenant,
+    /// forgetting the tenant once it stores nothing
+    pub(crate) fn remove_tenant_usage(&self, tenant: &str, usage: TenantUsage) {
+        if let Some(mut current) = self.tenant_usage.get_mut(tenant) {
+            current.subtract(usage);
+        }
+        self.tenant_usage
+            .remove_if(tenant, |_, current| *current == TenantUsage::default());
+    }
+
+    /// Recount the usage of every tenant, after the in-memory state was replaced
+    pub(crate) async fn rebuild_tenant_usage(&self) {
+        let usage = {
+            let configs = self.configurations.read().await;
+            let versions = self.versions.read().await;
+            let mut usage: HashMap<String, TenantUsage> = HashMap::new();
+            for config in configs.values() {
+                let config_versions = versions.get(&config.id).into_iter().flat_map(|v| v.values());
+                usage
+                    .entry(config.namespace.tenant.clone())
+                    .or_default()
+                    .add(TenantUsage::of_config(config_versions));
+            }
+            usage
+        };
+        self.tenant_usage.clear();
+        self.tenant_usage.extend(usage);
+    }
+}
+
+/// Bytes a version of `content_len` bytes counts against the quota
+///
+/// Versions count their full content, both in the usage and when a new
+/// version is checked, whether or not they are stored as a delta.
+fn content_bytes(content_len: usize) -> u64 {
+    content_len as u64
+}
+
+fn check_bytes(tenant: &str, quota: &TenantQuota, usage: &TenantUsage, content_len: usize) -> Result<()> {
+    let Some(max) = quota.max_total_bytes else {
+        return Ok(());
+    };
+    let total = usage.total_bytes.saturating_add(content_bytes(content_len));
+    if total > max {
+        return Err(quota_exceeded(format!(
+            "tenant {} would store {} bytes, limit {}",
+            tenant, total, max
+        )));
+    }
+    Ok(())
+}
+
+fn quota_exceeded(detail: String) -> ConfluxError {
+    ConfluxError::validation(format!("{} ({})", QUOTA_EXCEEDED, detail))
+}
+
+fn meta_cf(db: &DB) -> Result<&rocksdb::ColumnFamily> {
+    db.cf_handle(CF_META)
+        .ok_or_else(|| ConfluxError::storage("Meta column family not found"))
+}
+
+fn tenant_quota_key(tenant: &str) -> Vec<u8> {
+    let mut key = vec![TENANT_QUOTA_PREFIX];
+    key.extend_from_slice(tenant.as_bytes());
+    key
+}
+
+#[cfg(test)]
+#[path = "quotas_tests.rs"]
+mod tests;
@@ -0,0 +1,312 @@
+use super::*;
+use tempfile::tempdir;
+
+fn namespace(tenant: &str, env: &str) -> ConfigNamespace {
+    ConfigNamespace {
+        tenant: tenant.to_string(),
+        app: "app".to_string(),
+        env: env.to_string(),
+    }
+}
+
+fn create_config_command(namespace: ConfigNamespace, content: &str) -> RaftCommand {
+    RaftCommand::CreateConfig {
+        namespace,
+        name: "app.json".to_string(),
+        content: content.as_bytes().to_vec(),
+        format: ConfigFormat::Json,
+        schema: None,
+        creator_id: 1,
+        description: "v1".to_string(),
+    }
+}
+
+fn create_version_command(config_id: u64, content: &str) -> RaftCommand {
+    RaftCommand::CreateVersion {
+        config_id,
+        content: content.as_bytes().to_vec(),
+        format: None,
+        creator_id: 1,
+        description: "next".to_string(),
+    }
+}
+
+fn set_quota_command(
+    tenant: &str,
+    max_configs: Option<u64>,
+    max_versions_per_config: Option<u64>,
+    max_total_bytes: Option<u64>,
+) -> RaftCommand {
+    RaftCommand::SetTenantQuota {
+        tenant: tenant.to_string(),
+        max_configs,
+        max_versions_per_config,
+        max_total_bytes,
+    }
+}
+
+#[tokio::test]
+async fn test_config_quota_rejects_writes() {
+    let dir = tempdir().unwrap();
+    let (store, _rx) = Store::new(dir.path()).await.unwrap();
+
+    let response = store
+        .apply_command(&set_quota_command("acme", Some(1), None, None))
+        .await
+        .unwrap();
+    assert!(response.success, "{}", response.message);
+
+    let config_id = store
+        .apply_command(&create_config_command(namespace("acme", "prod"), r#"{"v":1}"#))
+        .await
+        .unwrap()
+        .config_id
+        .unwrap();
+
+    let response = store
+        .apply_command(&create_config_command(namespace("acme", "staging"), r#"{"v":1}"#))
+        .await
+        .unwrap();
+    assert!(!response.success);
+    assert!(response.message.contains(QUOTA_EXCEEDED), "{}", response.message);
+
+    // Other tenants are not limited
+    let response = store
+        .apply_command(&create_config_command(namespace("other", "prod"), r#"{"v":1}"#))
+        .await
+        .unwrap();
+    assert!(response.success, "{}", response.message);
+
+    // The config quota does not limit versions
+    let response = store
+        .apply_command(&create_version_command(config_id, r#"{"v":2}"#))
+        .await
+        .unwrap();
+    assert!(response.success, "{}", response.message);
+}
+
+#[tokio::test]
+async fn test_versions_per_config_quota() {
+    let dir = tempdir().unwrap();
+    let (store, _rx) = Store::new(dir.path()).await.unwrap();
+    store
+        .apply_command(&set_quota_command("acme", None, Some(2), None))
+        .await
+        .unwrap();
+
+    let config_id = store
+        .apply_command(&create_config_command(namespace("acme", "prod"), r#"{"v":1}"#))
+        .await
+        .unwrap()
+        .config_id
+        .unwrap();
+    let response = store
+        .apply_command(&create_version_command(config_id, r#"{"v":2}"#))
+        .await
+        .unwrap();
+    assert!(response.success, "{}", response.message);
+
+    let response = store
+        .apply_command(&create_version_command(config_id, r#"{"v":3}"#))
+        .await
+        .unwrap();
+    assert!(!response.success);
+    assert!(response.message.contains(QUOTA_EXCEEDED), "{}", response.message);
+    assert_eq!(store.list_config_versions(config_id).await.len(), 2);
+
+    // The limit applies per config
+    let other_id = store
+        .apply_command(&create_config_command(namespace("acme", "staging"), r#"{"v":1}"#))
+        .await
+        .unwrap()
+        .config_id
+        .unwrap();
+    let response = store
+        .apply_command(&create_version_command(other_id, r#"{"v":2}"#))
+        .await
+        .unwrap();
+    assert!(response.success, "{}", response.message);
+}
+
+#[tokio::test]
+async fn test_total_bytes_quota() {
+    let dir = tempdir().unwrap();
+    let (store, _rx) = Store::new(dir.path()).await.unwrap();
+    store
+        .apply_command(&set_quota_command("acme", None, None, Some(20)))
+        .await
+        .unwrap();
+
+    let config_id = store
+        .apply_command(&create_config_command(namespace("acme", "prod"), r#"{"v":1}"#))
+        .await
+        .unwrap()
+        .config_id
+        .unwrap();
+
+    let response = store
+        .apply_command(&create_version_command(config_id, r#"{"value":"too large"}"#))
+        .await
+        .unwrap();
+    assert!(!response.success);
+    assert!(response.message.contains(QUOTA_EXCEEDED), "{}", response.message);
+}
+
+#[tokio::test]
+async fn test_usage_follows_creates_and_deletes() {
+    let dir = tempdir().unwrap();
+    let (store, _rx) = Store::new(dir.path()).await.unwrap();
+    assert_eq!(store.tenant_usage("acme"), TenantUsage::default());
+
+    let config_id = store
+        .apply_command(&create_config_command(namespace("acme", "prod"), r#"{"v":1}"#))
+        .await
+        .unwrap()
+        .config_id
+        .unwrap();
+    store
+        .apply_command(&create_version_command(config_id, r#"{"v":2}"#))
+        .await
+        .unwrap();
+
+    let usage = store.tenant_usage("acme");
+    assert_eq!(usage.configs, 1);
+    assert_eq!(usage.versions, 2);
+    assert_eq!(usage.total_bytes, 14);
+
+    store
+        .apply_command(&RaftCommand::DeleteVersions {
+            config_id,
+            version_ids: vec![1],
+        })
+        .await
+        .unwrap();
+    let usage = store.tenant_usage("acme");
+    assert_eq!(usage.configs, 1);
+    assert_eq!(usage.versions, 1);
+    assert_eq!(usage.total_bytes, 7);
+
+    store
+        .apply_command(&RaftCommand::DeleteConfig { config_id })
+        .await
+        .unwrap();
+    assert_eq!(store.tenant_usage("acme"), TenantUsage::default());
+}
+
+#[tokio::test]
+async fn test_quota_usage_survives_reload_and_can_be_cleared() {
+    let dir = tempdir().unwrap();
+    {
+        let (store, _rx) = Store::new(dir.path()).await.unwrap();
+        store
+            .apply_command(&set_quota_command("acme", Some(5), None, None))
+            .await
+            .unwrap();
+        store
+            .apply_command(&create_config_command(namespace("acme", "prod"), r#"{"v":1}"#))
+            .await
+            .unwrap();
+    }
+
+    let (store, _rx) = Store::new(dir.path()).await.unwrap();
+    let quota_usage = store.tenant_quota_usage("acme").unwrap();
+    assert_eq!(quota_usage.quota.max_configs, Some(5));
+    assert_eq!(quota_usage.usage.configs, 1);
+
+    store
+        .apply_command(&set_quota_command("acme", None, None, None))
+        .await
+        .unwrap();
+    assert!(store.tenant_quota("acme").unwrap().is_unlimited());
+
+    let response = store
+        .apply_command(&set_quota_command("", Some(1), None, None))
+        .await
+        .unwrap();
+    assert!(!response.success);
+}
+
+#[tokio::test]
+async fn test_delta_encoded_versions_count_their_full_size() {
+    let dir = tempdir().unwrap();
+    let (store, _rx) = Store::new(dir.path()).await.unwrap();
+    let content = |v: u32| format!(r#"{{"v":{},"padding":"{}"}}"#, v, "x".repeat(2000));
+
+    let config_id = store
+        .apply_command(&create_config_command(namespace("acme", "prod"), &content(1)))
+        .await
+        .unwrap()
+        .config_id
+        .unwrap();
+    store
+        .apply_command(&create_version_command(config_id, &content(2)))
+        .await
+        .unwrap();
+    let full_size = content(1).len() as u64;
+    assert_eq!(store.tenant_usage("acme").total_bytes, 2 * full_size);
+
+    // The quota check counts a new version the same way as the usage
+    store
+        .apply_command(&set_quota_command("acme", None, None, Some(3 * full_size - 1)))
+        .await
+        .unwrap();
+    let response = store
+        .apply_command(&create_version_command(config_id, &content(3)))
+        .await
+        .unwrap();
+    assert!(!response.success);
+    assert!(response.message.contains(QUOTA_EXCEEDED), "{}", response.message);
+}
+
+#[tokio::test]
+async fn test_usage_counters_match_a_recount() {
+    let dir = tempdir().unwrap();
+    let (store, _rx) = Store::new(dir.path()).await.unwrap();
+
+    let moved_id = store
+        .apply_command(&create_config_command(namespace("acme", "prod"), r#"{"v":1}"#))
+        .await
+        .unwrap()
+        .config_id
+        .unwrap();
+    let kept_id = store
+        .apply_command(&create_config_command(namespace("acme", "staging"), r#"{"v":1}"#))
+        .await
+        .unwrap()
+        .config_id
+        .unwrap();
+    for v in 2..=4 {
+        store
+            .apply_command(&create_version_command(kept_id, &format!(r#"{{"v":{}}}"#, v)))
+            .await
+            .unwrap();
+    }
+    store
+        .apply_command(&RaftCommand::PruneVersions {
+            config_id: kept_id,
+            keep_last: 2,
+        })
+        .await
+        .unwrap();
+
+    // Moving a config to another tenant moves its usage along
+    let response = store
+        .apply_command(&RaftCommand::UpdateConfig {
+            config_id: moved_id,
+            namespace: namespace("other", "prod"),
+            name: "app.json".to_string(),
+            content: br#"{"v":2}"#.to_vec(),
+            format: ConfigFormat::Json,
+            schema: None,
+            description: "moved".to_string(),
+        })
+        .await
+        .unwrap();
+    assert!(response.success, "{}", response.message);
+
+    let counted = (store.tenant_usage("acme"), store.tenant_usage("other"));
+    assert_eq!(counted.0.configs, 1);
+    assert_eq!(counted.1.versions, 2);
+    store.rebuild_tenant_usage().await;
+    assert_eq!(counted, (store.tenant_usage("acme"), store.tenant_usage("other")));
+}
@@ -1,4 +1,5 @@
 use crate::raft::types::*;
+use super::log_codec::decode_log_entry;
 use super::types::Store;
 use openraft::{
     storage::RaftLogReader,
@@ -18,8 +19,7 @@ impl RaftLogReader<TypeConfig> for Arc<Store> {
         {
             let logs = self.logs.read().await;
             for (_, serialized) in logs.range(range.clone()) {
-                let entry: Entry<TypeConfig> =
-                    serde_json::from_str(serialized).map_err(|e| StorageIOError::read_logs(&e))?;
+                let entry = decode_log_entry(serialized).map_err(|e| StorageIOError::read_logs(&e))?;
                 entries.push(entry);
             }
         }
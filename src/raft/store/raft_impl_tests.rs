@@ -123,6 +123,9 @@ mod tests {
                     labels: std::collections::BTreeMap::new(),
                     version_id: 1,
                     priority: 0,
+                    effective_at: None,
+                    canary_percent: None,
+                    weight: 1,
                 },
             ],
         };
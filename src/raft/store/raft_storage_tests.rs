@@ -5,12 +5,12 @@ mod tests {
     use crate::raft::types::TypeConfig;
     use crate::raft::types::*;
     use openraft::{
-        storage::{RaftLogStorage, RaftStateMachine, SnapshotMeta},
+        storage::{RaftLogReader, RaftLogStorage, RaftStateMachine, SnapshotMeta},
         CommittedLeaderId, Entry, EntryPayload, LogId, StoredMembership, Vote,
     };
-    use std::io::Cursor;
     use std::sync::Arc;
     use tempfile::TempDir;
+    use tokio::io::AsyncWriteExt;
 
     async fn create_test_store() -> (Arc<Store>, TempDir) {
         let temp_dir = TempDir::new().unwrap();
@@ -79,6 +79,7 @@ mod tests {
                         creator_id: 1,
                         description: "Test configuration".to_string(),
                     },
+                    idempotency_key: None,
                 }),
             },
         ];
@@ -87,8 +88,8 @@ mod tests {
         {
             let mut logs = store.logs.write().await;
             for entry in &entries {
-                let serialized = serde_json::to_string(entry).unwrap();
-                logs.insert(entry.log_id.index, serialized);
+                let encoded = store.log_codec.encode(entry).unwrap();
+                logs.insert(entry.log_id.index, encoded);
             }
         }
 
@@ -204,6 +205,7 @@ mod tests {
                     creator_id: 1,
                     description: "Test configuration".to_string(),
                 },
+                idempotency_key: None,
             }),
         }];
 
@@ -218,6 +220,55 @@ mod tests {
         assert_eq!(last_applied, Some(LogId::new(leader_id, 1)));
     }
 
+    #[tokio::test]
+    async fn test_state_machine_applies_idempotency_key_once() {
+        let (store, _temp_dir) = create_test_store().await;
+        let mut sm = ConfluxStateMachineWrapper::new(store.clone());
+
+        // A retried request is appended to the log a second time under the same key
+        let leader_id = CommittedLeaderId::new(1, 0);
+        let idempotency_key = StampedIdempotencyKey::now("user-1:retry");
+        let entries: Vec<Entry<TypeConfig>> = (1..=2)
+            .map(|index| Entry {
+                log_id: LogId::new(leader_id, index),
+                payload: EntryPayload::Normal(ClientRequest {
+                    command: RaftCommand::CreateVersion {
+                        config_id: 1,
+                        content: b"{\"v\":2}".to_vec(),
+                        format: None,
+                        creator_id: 1,
+                        description: "retried".to_string(),
+                    },
+                    idempotency_key: Some(idempotency_key.clone()),
+                }),
+            })
+            .collect();
+        store
+            .apply_command(&RaftCommand::CreateConfig {
+                namespace: ConfigNamespace {
+                    tenant: "test".to_string(),
+                    app: "app".to_string(),
+                    env: "dev".to_string(),
+                },
+                name: "test-config".to_string(),
+                content: b"{}".to_vec(),
+                format: ConfigFormat::Json,
+                schema: None,
+                creator_id: 1,
+                description: "Test configuration".to_string(),
+            })
+            .await
+            .unwrap();
+
+        let responses = RaftStateMachine::<TypeConfig>::apply(&mut sm, entries)
+            .await
+            .unwrap();
+        assert!(responses[0].success, "{}", responses[0].message);
+        assert_eq!(responses[1].message, responses[0].message);
+        assert_eq!(responses[1].data, responses[0].data);
+        assert_eq!(store.list_config_versions(1).await.len(), 2);
+    }
+
     #[tokio::test]
     async fn test_last_applied_state() {
         let (store, _temp_dir) = create_test_store().await;
@@ -257,11 +308,18 @@ mod tests {
             snapshot_id: "test-snapshot".to_string(),
         };
 
+        // Receive the snapshot data the way openraft writes received chunks
+        let mut snapshot =
+            RaftStateMachine::<crate::raft::types::TypeConfig>::begin_receiving_snapshot(&mut sm)
+                .await
+                .unwrap();
+        snapshot.write_all(&snapshot_data).await.unwrap();
+
         // Install snapshot
         RaftStateMachine::<crate::raft::types::TypeConfig>::install_snapshot(
             &mut sm,
             &meta,
-            Box::new(Cursor::new(snapshot_data.clone())),
+            snapshot,
         )
         .await
         .unwrap();
@@ -297,4 +355,69 @@ mod tests {
         assert!(snapshot.meta.snapshot_id.starts_with("snapshot-"));
         assert_eq!(snapshot.meta.last_log_id, None); // No logs applied yet
     }
+
+    #[tokio::test]
+    async fn test_compressed_log_entries_round_trip() {
+        use crate::raft::store::log_codec::{LOG_ENTRY_COMPRESSED, LOG_ENTRY_RAW};
+        use crate::raft::ConfluxLogStorage;
+
+        let (store, _temp_dir) = create_test_store().await;
+        let leader_id = CommittedLeaderId::new(1, 0);
+        let large_entry = |index| Entry {
+            log_id: LogId::new(leader_id, index),
+            payload: EntryPayload::Normal(ClientRequest {
+                command: RaftCommand::CreateConfig {
+                    namespace: ConfigNamespace {
+                        tenant: "test".to_string(),
+                        app: "app".to_string(),
+                        env: "dev".to_string(),
+                    },
+                    name: "large-config".to_string(),
+                    content: "feature.enabled = true\n".repeat(4096).into_bytes(),
+                    format: ConfigFormat::Toml,
+                    schema: None,
+                    creator_id: 1,
+                    description: "Large configuration".to_string(),
+                },
+                idempotency_key: None,
+            }),
+        };
+
+        let entries = vec![
+            large_entry(1),
+            Entry {
+                log_id: LogId::new(leader_id, 2),
+                payload: EntryPayload::Blank,
+            },
+            large_entry(3),
+        ];
+        {
+            let mut logs = store.logs.write().await;
+            // Written before compression is enabled, so stored raw
+            logs.insert(1, store.log_codec.encode(&entries[0]).unwrap());
+            store.set_log_compression(true);
+            logs.insert(2, store.log_codec.encode(&entries[1]).unwrap());
+            logs.insert(3, store.log_codec.encode(&entries[2]).unwrap());
+
+            assert_eq!(logs[&1][0], LOG_ENTRY_RAW);
+            // Blank entries are never compressed
+            assert_eq!(logs[&2][0], LOG_ENTRY_RAW);
+            assert_eq!(logs[&3][0], LOG_ENTRY_COMPRESSED);
+            assert!(logs[&3].len() < logs[&1].len() / 10);
+        }
+
+        let read = RaftLogReader::<TypeConfig>::try_get_log_entries(&mut store.clone(), 1..=3)
+            .await
+            .unwrap();
+        assert_eq!(read, entries);
+        let log_state = RaftLogStorage::<TypeConfig>::get_log_state(&mut store.clone())
+            .await
+            .unwrap();
+        assert_eq!(log_state.last_log_id, Some(LogId::new(leader_id, 3)));
+
+        let (compressed_bytes, uncompressed_bytes) =
+            ConfluxLogStorage::new(store.clone()).log_compression_stats();
+        assert!(compressed_bytes < uncompressed_bytes / 2);
+        assert_eq!((compressed_bytes, uncompressed_bytes), store.log_compression_stats());
+    }
 }
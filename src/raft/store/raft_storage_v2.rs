@@ -3,6 +3,8 @@
 //! 这个文件实现了 RaftLogStorage 和 RaftStateMachine 的分离接口
 
 use crate::raft::types::*;
+use super::log_codec::decode_log_entry;
+use super::snapshot_stream::snapshot_file;
 use super::types::Store;
 use openraft::{
     storage::{LogState, Snapshot, SnapshotMeta, RaftLogStorage, RaftStateMachine, LogFlushed},
@@ -10,6 +12,7 @@ use openraft::{
     StorageError, StorageIOError, StoredMembership, Vote,
 };
 use std::sync::Arc;
+use tokio::io::{AsyncSeekExt, AsyncWriteExt};
 
 // 实现 RaftLogStorage for Arc<Store>
 impl RaftLogStorage<TypeConfig> for Arc<Store> {
@@ -22,8 +25,7 @@ impl RaftLogStorage<TypeConfig> for Arc<Store> {
         let last = match last_serialized {
             None => None,
             Some(entry) => {
-                let entry: Entry<TypeConfig> = serde_json::from_str(entry)
-                    .map_err(|e| StorageIOError::read_logs(&e))?;
+                let entry = decode_log_entry(entry).map_err(|e| StorageIOError::read_logs(&e))?;
                 Some(entry.log_id)
             }
         };
@@ -58,7 +60,9 @@ impl RaftLogStorage<TypeConfig> for Arc<Store> {
         let mut logs = self.logs.write().await;
         for entry in entries {
             let log_id = entry.log_id;
-            let serialized = serde_json::to_string(&entry)
+            let serialized = self
+                .log_codec
+                .encode(&entry)
                 .map_err(|e| StorageIOError::write_logs(&e))?;
             logs.insert(log_id.index, serialized);
         }
@@ -117,19 +121,30 @@ impl RaftStateMachine<TypeConfig> for crate::raft::state_machine::ConfluxStateMa
         &mut self,
     ) -> Result<Box<<TypeConfig as openraft::RaftTypeConfig>::SnapshotData>, StorageError<NodeId>> {
         tracing::debug!("Beginning to receive snapshot");
-        Ok(Box::new(std::io::Cursor::new(Vec::new())))
+        // 接收的分块写入临时文件，不在内存中累积
+        let file = snapshot_file()
+            .await
+            .map_err(|e| StorageIOError::write_snapshot(None, &e))?;
+        Ok(Box::new(file))
     }
 
     async fn install_snapshot(
         &mut self,
         meta: &SnapshotMeta<NodeId, Node>,
-        snapshot: Box<<TypeConfig as openraft::RaftTypeConfig>::SnapshotData>,
+        mut snapshot: Box<<TypeConfig as openraft::RaftTypeConfig>::SnapshotData>,
     ) -> Result<(), StorageError<NodeId>> {
         tracing::debug!("Installing snapshot: {:?}", meta);
         
-        let data = snapshot.into_inner();
-        let mut sm = self.inner().write().await;
-        sm.restore_from_snapshot(&data).await?;
+        // 接收分块后文件位于末尾，写完后从头读取快照流
+        snapshot
+            .flush()
+            .await
+            .map_err(|e| StorageIOError::write_snapshot(Some(meta.signature()), &e))?;
+        snapshot
+            .rewind()
+            .await
+            .map_err(|e| StorageIOError::read_snapshot(Some(meta.signature()), &e))?;
+        self.install_snapshot_stream(snapshot.as_mut()).await?;
         
         tracing::info!("Snapshot installed successfully");
         Ok(())
@@ -140,7 +155,7 @@ impl RaftStateMachine<TypeConfig> for crate::raft::state_machine::ConfluxStateMa
     ) -> Result<Option<Snapshot<TypeConfig>>, StorageError<NodeId>> {
         tracing::debug!("Getting current snapshot");
         
-        let mut builder = crate::raft::state_machine::ConfluxSnapshotBuilder::new(self.inner().clone());
+        let mut builder = self.snapshot_builder();
         match builder.build_snapshot().await {
             Ok(snapshot) => Ok(Some(snapshot)),
             Err(e) => {
@@ -153,6 +168,6 @@ impl RaftStateMachine<TypeConfig> for crate::raft::state_machine::ConfluxStateMa
     type SnapshotBuilder = crate::raft::state_machine::ConfluxSnapshotBuilder;
 
     async fn get_snapshot_builder(&mut self) -> Self::SnapshotBuilder {
-        crate::raft::state_machine::ConfluxSnapshotBuilder::new(self.inner().clone())
+        self.snapshot_builder()
     }
 }
\ No newline at end of file
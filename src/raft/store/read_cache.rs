@@ -0,0 +1,173 @@
+use super::types::{ConfigChangeEvent, Store};
+use crate::raft::types::*;
+use lru::LruCache;
+use std::num::NonZeroUsize;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, Instant};
+
+/// Maximum number of published config reads kept in the cache
+pub const PUBLISHED_CACHE_CAPACITY: usize = 10_000;
+
+/// Default time a cached published config read stays valid
+pub const DEFAULT_PUBLISHED_CACHE_TTL: Duration = Duration::from_secs(60);
+
+/// Cache key: config ID and the ID of the version its releases selected
+pub type PublishedCacheKey = (u64, u64);
+
+/// A cached published version together with the time it was read
+type PublishedCacheEntry = (ConfigVersion, Instant);
+
+/// LRU cache of the versions served by `Store::get_published_config`
+///
+/// The release serving a client is selected on every read from the current
+/// config, so the cache holds one entry per published version however many
+/// clients read it, and a scheduled release is served as soon as it becomes
+/// effective. Entries expire after the configured TTL and are invalidated
+/// whenever a change event is emitted for their config.
+#[derive(Debug)]
+pub struct PublishedConfigCache {
+    entries: Mutex<LruCache<PublishedCacheKey, PublishedCacheEntry>>,
+    ttl_ms: AtomicU64,
+    /// Bumped on every invalidation so a read racing a write does not cache stale data
+    generation: AtomicU64,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl Default for PublishedConfigCache {
+    fn default() -> Self {
+        Self::new(PUBLISHED_CACHE_CAPACITY, DEFAULT_PUBLISHED_CACHE_TTL)
+    }
+}
+
+impl PublishedConfigCache {
+    pub fn new(capacity: usize, ttl: Duration) -> Self {
+        let capacity = NonZeroUsize::new(capacity).unwrap_or(NonZeroUsize::MIN);
+        Self {
+            entries: Mutex::new(LruCache::new(capacity)),
+            ttl_ms: AtomicU64::new(ttl.as_millis() as u64),
+            generation: AtomicU64::new(0),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    fn entries(&self) -> MutexGuard<'_, LruCache<PublishedCacheKey, PublishedCacheEntry>> {
+        self.entries.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Time a cached entry stays valid
+    pub fn ttl(&self) -> Duration {
+        Duration::from_millis(self.ttl_ms.load(Ordering::Relaxed))
+    }
+
+    /// Change the time a cached entry stays valid; a zero TTL disables caching
+    pub fn set_ttl(&self, ttl: Duration) {
+        self.ttl_ms.store(ttl.as_millis() as u64, Ordering::Relaxed);
+    }
+
+    /// Current invalidation generation, to be passed back to `insert`
+    pub fn generation(&self) -> u64 {
+        self.generation.load(Ordering::Acquire)
+    }
+
+    /// Look up a cached read, counting the hit or miss
+    pub fn get(&self, key: &PublishedCacheKey) -> Option<ConfigVersion> {
+        let ttl = self.ttl();
+        let mut entries = self.entries();
+        let cached = match entries.get(key) {
+            Some((version, cached_at)) if cached_at.elapsed() < ttl => Some(version.clone()),
+            Some(_) => {
+                entries.pop(key);
+                None
+            }
+            None => None,
+        };
+        drop(entries);
+
+        let counter = if cached.is_some() { &self.hits } else { &self.misses };
+        counter.fetch_add(1, Ordering::Relaxed);
+        cached
+    }
+
+    /// Cache a read made while the cache was at `generation`
+    ///
+    /// The entry is discarded if an invalidation happened since, because the
+    /// read may predate the change.
+    pub fn insert(&self, key: PublishedCacheKey, version: ConfigVersion, generation: u64) {
+        if self.ttl().is_zero() {
+            return;
+        }
+        let mut entries = self.entries();
+        if self.generation() == generation {
+            entries.put(key, (version, Instant::now()));
+        }
+    }
+
+    /// Drop every cached read of the given config
+    pub fn invalidate_config(&self, config_id: u64) {
+        let mut entries = self.entries();
+        self.generation.fetch_add(1, Ordering::AcqRel);
+        let stale: Vec<PublishedCacheKey> = entries
+            .iter()
+            .map(|(key, _)| *key)
+            .filter(|(id, _)| *id == config_id)
+            .collect();
+        for key in stale {
+            entries.pop(&key);
+        }
+    }
+
+    /// Drop every cached read, e.g. after the state was replaced by a snapshot
+    pub fn invalidate_all(&self) {
+        let mut entries = self.entries();
+        self.generation.fetch_add(1, Ordering::AcqRel);
+        entries.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries().is_empty()
+    }
+
+    /// Fraction of lookups served from the cache, or 0.0 before any lookup
+    pub fn hit_rate(&self) -> f64 {
+        let hits = self.hits.load(Ordering::Relaxed);
+        let total = hits + self.misses.load(Ordering::Relaxed);
+        if total == 0 {
+            0.0
+        } else {
+            hits as f64 / total as f64
+        }
+    }
+}
+
+impl Store {
+    /// Cache of published config reads
+    pub fn published_cache(&self) -> Arc<PublishedConfigCache> {
+        self.published_cache.clone()
+    }
+
+    /// Fraction of `get_published_config` calls served from the read cache
+    pub fn cache_hit_rate(&self) -> f64 {
+        self.published_cache.hit_rate()
+    }
+
+    /// Invalidate cached reads of the changed config, then broadcast the event
+    ///
+    /// Inside a transaction the broadcast is deferred until it commits.
+    pub(crate) fn notify_change(&self, event: ConfigChangeEvent) {
+        self.published_cache.invalidate_config(event.config_id);
+        if let Some(event) = super::transaction::defer_change(event) {
+            let _ = self.change_notifier.send(event);
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "read_cache_tests.rs"]
+mod tests;
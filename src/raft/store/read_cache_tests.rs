@@ -0,0 +1,160 @@
+use super::*;
+use std::collections::BTreeMap;
+use crate::raft::store::test_support::create_store;
+
+fn namespace() -> ConfigNamespace {
+    ConfigNamespace {
+        tenant: "tenant".to_string(),
+        app: "app".to_string(),
+        env: "prod".to_string(),
+    }
+}
+
+async fn create_config(store: &Store) -> u64 {
+    let response = store
+        .apply_command(&RaftCommand::CreateConfig {
+            namespace: namespace(),
+            name: "app.json".to_string(),
+            content: br#"{"v":1}"#.to_vec(),
+            format: ConfigFormat::Json,
+            schema: None,
+            creator_id: 1,
+            description: "initial".to_string(),
+        })
+        .await
+        .unwrap();
+    response.config_id.unwrap()
+}
+
+#[tokio::test]
+async fn test_second_read_is_served_from_cache() {
+    let (store, _dir) = create_store().await;
+    create_config(&store).await;
+    let labels = BTreeMap::new();
+
+    let (_, first) = store.get_published_config(&namespace(), "app.json", &labels).await.unwrap();
+    assert_eq!(store.published_cache().len(), 1);
+    assert_eq!(store.cache_hit_rate(), 0.0);
+
+    let (_, second) = store.get_published_config(&namespace(), "app.json", &labels).await.unwrap();
+    assert_eq!(second.id, first.id);
+    assert_eq!(second.content, first.content);
+    assert_eq!(store.cache_hit_rate(), 0.5);
+
+    let stats = store.get_storage_stats().await.unwrap();
+    assert_eq!(stats.cache_hit_rate, 0.5);
+}
+
+#[tokio::test]
+async fn test_create_version_invalidates_cached_read() {
+    let (store, _dir) = create_store().await;
+    let config_id = create_config(&store).await;
+    let labels = BTreeMap::new();
+
+    let (before, _) = store.get_published_config(&namespace(), "app.json", &labels).await.unwrap();
+    assert_eq!(before.latest_version_id, 1);
+
+    store
+        .apply_command(&RaftCommand::CreateVersion {
+            config_id,
+            content: br#"{"v":2}"#.to_vec(),
+            format: None,
+            creator_id: 1,
+            description: "second".to_string(),
+        })
+        .await
+        .unwrap();
+    assert!(store.published_cache().is_empty());
+
+    // The fresh read reflects the new version instead of the cached metadata
+    let (after, _) = store.get_published_config(&namespace(), "app.json", &labels).await.unwrap();
+    assert_eq!(after.latest_version_id, 2);
+}
+
+#[tokio::test]
+async fn test_clients_share_the_entry_of_their_version() {
+    let (store, _dir) = create_store().await;
+    create_config(&store).await;
+
+    for instance in 0..100 {
+        let labels = BTreeMap::from([
+            (CANARY_CLIENT_LABEL.to_string(), format!("instance-{}", instance)),
+            ("region".to_string(), "eu".to_string()),
+        ]);
+        store.get_published_config(&namespace(), "app.json", &labels).await.unwrap();
+    }
+    assert_eq!(store.published_cache().len(), 1);
+    assert_eq!(store.cache_hit_rate(), 0.99);
+}
+
+#[tokio::test]
+async fn test_scheduled_release_is_served_once_effective() {
+    let (store, _dir) = create_store().await;
+    let config_id = create_config(&store).await;
+    store
+        .apply_command(&RaftCommand::CreateVersion {
+            config_id,
+            content: br#"{"v":2}"#.to_vec(),
+            format: None,
+            creator_id: 1,
+            description: "second".to_string(),
+        })
+        .await
+        .unwrap();
+    let scheduled = Release {
+        effective_at: Some(chrono::Utc::now() + chrono::Duration::milliseconds(200)),
+        ..Release::default(2)
+    };
+    let response = store
+        .apply_command(&RaftCommand::UpdateReleaseRules {
+            config_id,
+            releases: vec![Release::default(1), scheduled],
+        })
+        .await
+        .unwrap();
+    assert!(response.success, "{}", response.message);
+
+    let labels = BTreeMap::new();
+    let (_, version) = store.get_published_config(&namespace(), "app.json", &labels).await.unwrap();
+    assert_eq!(version.id, 1);
+
+    // No change event is emitted when the release becomes effective
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    let (_, version) = store.get_published_config(&namespace(), "app.json", &labels).await.unwrap();
+    assert_eq!(version.id, 2);
+}
+
+/// A version read through a fresh store
+async fn published_version() -> ConfigVersion {
+    let (store, _dir) = create_store().await;
+    create_config(&store).await;
+    store
+        .get_published_config(&namespace(), "app.json", &BTreeMap::new())
+        .await
+        .unwrap()
+        .1
+}
+
+#[tokio::test]
+async fn test_insert_after_invalidation_is_discarded() {
+    let version = published_version().await;
+    let cache = PublishedConfigCache::default();
+    let generation = cache.generation();
+    cache.invalidate_config(version.config_id);
+
+    let key = (version.config_id, version.id);
+    cache.insert(key, version, generation);
+    assert!(cache.get(&key).is_none());
+}
+
+#[tokio::test]
+async fn test_expired_entry_is_not_served() {
+    let version = published_version().await;
+    let cache = PublishedConfigCache::new(PUBLISHED_CACHE_CAPACITY, Duration::from_millis(1));
+    let key = (version.config_id, version.id);
+    cache.insert(key, version, cache.generation());
+
+    tokio::time::sleep(Duration::from_millis(5)).await;
+    assert!(cache.get(&key).is_none());
+    assert!(cache.is_empty());
+}
@@ -0,0 +1,161 @@
+use crate::error::{ConfluxError, Result};
+use crate::raft::types::*;
+use super::constants::CF_SCHEDULED;
+use super::types::Store;
+use chrono::{DateTime, Utc};
+use rocksdb::{IteratorMode, WriteBatch, DB};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::time::Duration;
+use tracing::{info, warn};
+
+/// Interval at which the leader checks for scheduled releases that have become due
+pub const SCHEDULED_RELEASE_POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+/// A release staged to become active at a future time
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ScheduledRelease {
+    pub config_id: u64,
+    pub version_id: u64,
+    pub labels: BTreeMap<String, String>,
+    pub effective_at: DateTime<Utc>,
+}
+
+impl ScheduledRelease {
+    /// Storage key: effective_at (ms) + config_id + version_id, all big-endian,
+    /// so that iterating the column family yields releases in activation order
+    pub(crate) fn key(&self) -> Vec<u8> {
+        let mut key = Vec::with_capacity(24);
+        let millis = self.effective_at.timestamp_millis().max(0) as u64;
+        key.extend_from_slice(&millis.to_be_bytes());
+        key.extend_from_slice(&self.config_id.to_be_bytes());
+        key.extend_from_slice(&self.version_id.to_be_bytes());
+        key
+    }
+
+    /// The release rules of `config` once this release is activated
+    ///
+    /// The activated release replaces any release with the same labels, except
+    /// for other releases scheduled to take effect later.
+    pub fn activated_releases(&self, config: &Config) -> Vec<Release> {
+        let priority = config
+            .releases
+            .iter()
+            .find(|r| r.labels == self.labels && r.effective_at == Some(self.effective_at))
+            .or_else(|| config.releases.iter().find(|r| r.labels == self.labels))
+            .map(|r| r.priority)
+            .unwrap_or(0);
+
+        let mut releases: Vec<Release> = config
+            .releases
+            .iter()
+            .filter(|r| {
+                r.labels != self.labels || r.effective_at.is_some_and(|at| at > self.effective_at)
+            })
+            .cloned()
+            .collect();
+        releases.push(Release::new(self.labels.clone(), self.version_id, priority));
+        releases
+    }
+}
+
+impl Store {
+    /// Persist a scheduled release to the scheduled column family
+    pub(crate) async fn persist_scheduled_release(&self, scheduled: &ScheduledRelease) -> Result<()> {
+        let cf = self.db.cf_handle(CF_SCHEDULED).ok_or_else(|| {
+            ConfluxError::storage("Scheduled column family not found")
+        })?;
+
+        let data = serde_json::to_vec(scheduled).map_err(|e| {
+            ConfluxError::storage(format!("Failed to serialize scheduled release: {}", e))
+        })?;
+
+        self.write_with(|batch| {
+            batch.put_cf(cf, scheduled.key(), data);
+            Ok(())
+        })
+    }
+
+    /// List all scheduled releases that have not been activated yet, in activation order
+    pub async fn list_scheduled_releases(&self) -> Result<Vec<ScheduledRelease>> {
+        scan_scheduled(&self.db, None)
+    }
+
+    /// Whether any scheduled release is due at `now`
+    ///
+    /// Lets the leader skip proposing an activation when there is nothing to do.
+    pub fn has_due_scheduled_releases(&self, now: DateTime<Utc>) -> Result<bool> {
+        Ok(!scan_scheduled(&self.db, Some(now))?.is_empty())
+    }
+
+    /// Handle activate scheduled releases command
+    ///
+    /// Activates every scheduled release whose `effective_at` is at or before
+    /// `now`, the leader's clock when it proposed the command, and removes the
+    /// scheduled entries, so every replica activates the same releases.
+    pub(crate) async fn handle_activate_scheduled_releases(
+        &self,
+        now: &DateTime<Utc>,
+    ) -> Result<ClientWriteResponse> {
+        let mut activated = 0;
+        for scheduled in scan_scheduled(&self.db, Some(*now))? {
+            if let Some(config) = self.get_config_meta(scheduled.config_id).await {
+                let releases = scheduled.activated_releases(&config);
+                let response = self
+                    .handle_update_release_rules(&scheduled.config_id, &releases)
+                    .await?;
+                if response.success {
+                    activated += 1;
+                    info!(
+                        "Activated scheduled release of version {} for config {}",
+                        scheduled.version_id, scheduled.config_id
+                    );
+                } else {
+                    warn!("Failed to activate scheduled release: {}", response.message);
+                }
+            }
+            self.write_with(|batch| delete_scheduled(&self.db, batch, &scheduled))?;
+        }
+
+        Ok(Self::create_success_response(
+            format!("Activated {} scheduled releases", activated),
+            Some(serde_json::json!({ "activated": activated })),
+        ))
+    }
+}
+
+/// Read scheduled releases in activation order, optionally only those due by `until`
+fn scan_scheduled(db: &DB, until: Option<DateTime<Utc>>) -> Result<Vec<ScheduledRelease>> {
+    let cf = db.cf_handle(CF_SCHEDULED).ok_or_else(|| {
+        ConfluxError::storage("Scheduled column family not found")
+    })?;
+
+    let mut releases = Vec::new();
+    for item in db.iterator_cf(cf, IteratorMode::Start) {
+        let (_, value) = item.map_err(|e| {
+            ConfluxError::storage(format!("Failed to read scheduled release: {}", e))
+        })?;
+        let scheduled: ScheduledRelease = serde_json::from_slice(&value).map_err(|e| {
+            ConfluxError::storage(format!("Failed to deserialize scheduled release: {}", e))
+        })?;
+        if until.is_some_and(|until| scheduled.effective_at > until) {
+            break;
+        }
+        releases.push(scheduled);
+    }
+    Ok(releases)
+}
+
+/// Remove a scheduled release entry as part of `batch`
+fn delete_scheduled(db: &DB, batch: &mut WriteBatch, scheduled: &ScheduledRelease) -> Result<()> {
+    let cf = db.cf_handle(CF_SCHEDULED).ok_or_else(|| {
+        ConfluxError::storage("Scheduled column family not found")
+    })?;
+
+    batch.delete_cf(cf, scheduled.key());
+    Ok(())
+}
+
+#[cfg(test)]
+#[path = "scheduler_tests.rs"]
+mod tests;
@@ -0,0 +1,164 @@
+use super::*;
+use chrono::Duration as ChronoDuration;
+use tempfile::tempdir;
+
+fn namespace() -> ConfigNamespace {
+    ConfigNamespace {
+        tenant: "tenant".to_string(),
+        app: "app".to_string(),
+        env: "prod".to_string(),
+    }
+}
+
+/// Create a config with two versions; version 1 is the active default release
+async fn create_config_with_two_versions(store: &Store) -> u64 {
+    let response = store
+        .apply_command(&RaftCommand::CreateConfig {
+            namespace: namespace(),
+            name: "app.json".to_string(),
+            content: b"{\"v\":1}".to_vec(),
+            format: ConfigFormat::Json,
+            schema: None,
+            creator_id: 1,
+            description: "v1".to_string(),
+        })
+        .await
+        .unwrap();
+    let config_id = response.config_id.unwrap();
+
+    let response = store
+        .apply_command(&RaftCommand::CreateVersion {
+            config_id,
+            content: b"{\"v\":2}".to_vec(),
+            format: None,
+            creator_id: 1,
+            description: "v2".to_string(),
+        })
+        .await
+        .unwrap();
+    assert!(response.success);
+    config_id
+}
+
+async fn published_version(store: &Store) -> u64 {
+    let (_, version) = store
+        .get_published_config(&namespace(), "app.json", &BTreeMap::new())
+        .await
+        .unwrap();
+    version.id
+}
+
+async fn schedule(store: &Store, config_id: u64, effective_at: DateTime<Utc>) {
+    let response = store
+        .apply_command(&RaftCommand::ScheduleRelease {
+            config_id,
+            version_id: 2,
+            labels: BTreeMap::new(),
+            effective_at,
+        })
+        .await
+        .unwrap();
+    assert!(response.success, "{}", response.message);
+}
+
+/// Apply the activation the leader would propose at `now`, returning how many releases it activated
+async fn activate_due_releases(store: &Store, now: DateTime<Utc>) -> u64 {
+    let response = store
+        .apply_command(&RaftCommand::ActivateScheduledReleases { now })
+        .await
+        .unwrap();
+    assert!(response.success, "{}", response.message);
+    response.data.unwrap()["activated"].as_u64().unwrap()
+}
+
+#[tokio::test]
+async fn test_future_release_is_ignored_until_effective() {
+    let dir = tempdir().unwrap();
+    let (store, _rx) = Store::new(dir.path()).await.unwrap();
+    let config_id = create_config_with_two_versions(&store).await;
+
+    schedule(&store, config_id, Utc::now() + ChronoDuration::hours(1)).await;
+
+    assert_eq!(published_version(&store).await, 1);
+    assert_eq!(store.list_scheduled_releases().await.unwrap().len(), 1);
+
+    // Nothing is due yet
+    assert!(!store.has_due_scheduled_releases(Utc::now()).unwrap());
+    assert_eq!(activate_due_releases(&store, Utc::now()).await, 0);
+    assert_eq!(published_version(&store).await, 1);
+}
+
+#[tokio::test]
+async fn test_scheduled_release_becomes_active() {
+    let dir = tempdir().unwrap();
+    let (store, _rx) = Store::new(dir.path()).await.unwrap();
+    let config_id = create_config_with_two_versions(&store).await;
+
+    let effective_at = Utc::now() + ChronoDuration::hours(1);
+    schedule(&store, config_id, effective_at).await;
+    assert_eq!(published_version(&store).await, 1);
+
+    let now = effective_at + ChronoDuration::seconds(1);
+    assert!(store.has_due_scheduled_releases(now).unwrap());
+    assert_eq!(activate_due_releases(&store, now).await, 1);
+    assert_eq!(published_version(&store).await, 2);
+    assert!(store.list_scheduled_releases().await.unwrap().is_empty());
+
+    // The pending release was replaced by a plain active release
+    let config = store.get_config_meta(config_id).await.unwrap();
+    assert_eq!(config.releases.len(), 1);
+    assert_eq!(config.releases[0].version_id, 2);
+    assert_eq!(config.releases[0].effective_at, None);
+}
+
+#[tokio::test]
+async fn test_past_effective_at_is_served_before_activation() {
+    let dir = tempdir().unwrap();
+    let (store, _rx) = Store::new(dir.path()).await.unwrap();
+    let config_id = create_config_with_two_versions(&store).await;
+
+    schedule(&store, config_id, Utc::now() - ChronoDuration::seconds(1)).await;
+
+    // Reads honour effective_at even before the scheduler has run
+    assert_eq!(published_version(&store).await, 2);
+}
+
+#[tokio::test]
+async fn test_schedule_release_rejects_unknown_version() {
+    let dir = tempdir().unwrap();
+    let (store, _rx) = Store::new(dir.path()).await.unwrap();
+    let config_id = create_config_with_two_versions(&store).await;
+
+    let response = store
+        .apply_command(&RaftCommand::ScheduleRelease {
+            config_id,
+            version_id: 99,
+            labels: BTreeMap::new(),
+            effective_at: Utc::now(),
+        })
+        .await
+        .unwrap();
+    assert!(!response.success);
+    assert!(store.list_scheduled_releases().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_activation_uses_the_proposed_time() {
+    let dir = tempdir().unwrap();
+    let (store, _rx) = Store::new(dir.path()).await.unwrap();
+    let config_id = create_config_with_two_versions(&store).await;
+    let effective_at = Utc::now() - ChronoDuration::hours(1);
+    schedule(&store, config_id, effective_at).await;
+
+    // A command proposed before the release was due leaves it scheduled,
+    // however late a replica applies it
+    let before = effective_at - ChronoDuration::seconds(1);
+    assert_eq!(activate_due_releases(&store, before).await, 0);
+    assert_eq!(store.list_scheduled_releases().await.unwrap().len(), 1);
+
+    assert_eq!(activate_due_releases(&store, effective_at).await, 1);
+    let config = store.get_config_meta(config_id).await.unwrap();
+    assert_eq!(config.releases.len(), 1);
+    assert_eq!(config.releases[0].effective_at, None);
+    assert!(store.list_scheduled_releases().await.unwrap().is_empty());
+}
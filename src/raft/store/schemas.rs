@@ -0,0 +1,217 @@
+use crate::error::{ConfluxError, Result};
+use crate::raft::types::*;
+use super::constants::CF_SCHEMAS;
+use super::types::Store;
+use rocksdb::{Direction, IteratorMode, WriteBatch, DB};
+use tracing::warn;
+
+/// Key prefix of schema records, followed by the big-endian schema ID
+const SCHEMA_PREFIX: u8 = 0x01;
+
+impl Store {
+    /// Handle register schema command
+    ///
+    /// The document must be a JSON object or boolean. Registering a name the
+    /// tenant already uses replaces that schema's document and keeps its ID.
+    pub(crate) async fn handle_register_schema(
+        &self,
+        tenant: &str,
+        name: &str,
+        schema: &str,
+    ) -> Result<ClientWriteResponse> {
+        if tenant.is_empty() || name.is_empty() {
+            return Ok(Self::create_error_response(
+                "Schema tenant and name must not be empty".to_string(),
+            ));
+        }
+        if let Err(e) = parse_schema(schema) {
+            return Ok(Self::create_error_response(format!(
+                "Invalid schema '{}': {}",
+                name, e
+            )));
+        }
+
+        let schemas = scan_schemas(&self.db)?;
+        let record = match schemas.iter().find(|s| s.tenant == tenant && s.name == name) {
+            Some(existing) => RegisteredSchema {
+                schema: schema.to_string(),
+                updated_at: chrono::Utc::now(),
+                ..existing.clone()
+            },
+            None => {
+                let id = schemas.iter().map(|s| s.id).max().unwrap_or(0) + 1;
+                RegisteredSchema::new(id, tenant, name, schema)
+            }
+        };
+        self.write_with(|batch| put_schema(&self.db, batch, &record))?;
+
+        Ok(Self::create_success_response(
+            format!("Registered schema '{}' for tenant {}", name, tenant),
+            Some(serde_json::json!({
+                "schema_id": record.id,
+                "tenant": tenant,
+                "name": name
+            })),
+        ))
+    }
+
+    /// Handle set config schema command
+    ///
+    /// The schema must be registered for the config's own tenant. Existing
+    /// versions are not re-validated; the schema applies to new versions.
+    pub(crate) async fn handle_set_config_schema(
+        &self,
+        config_id: u64,
+        schema_id: Option<u64>,
+    ) -> Result<ClientWriteResponse> {
+        let (config_key, mut config) = match self.find_config_by_id(config_id).await {
+            Ok(found) => found,
+            Err(_) => {
+                return Ok(Self::create_error_response(format!(
+                    "Configuration with ID {} not found",
+                    config_id
+                )));
+            }
+        };
+
+        if let Some(schema_id) = schema_id {
+            let registered = self
+                .get_schema(schema_id)
+                .await
+                .is_some_and(|schema| schema.tenant == config.namespace.tenant);
+            if !registered {
+                return Ok(Self::create_error_response(format!(
+                    "Schema {} not found for tenant {}",
+                    schema_id, config.namespace.tenant
+                )));
+            }
+        }
+
+        config.schema_id = schema_id;
+        config.updated_at = chrono::Utc::now();
+        self.persist_config(&config_key, &config).await?;
+        self.configurations.write().await.insert(config_key, config);
+
+        Ok(Self::create_success_response(
+            match schema_id {
+                Some(schema_id) => format!("Configuration {} now uses schema {}", config_id, schema_id),
+                None => format!("Configuration {} no longer uses a schema", config_id),
+            },
+            Some(serde_json::json!({
+                "config_id": config_id,
+                "schema_id": schema_id
+            })),
+        ))
+    }
+
+    /// Get a registered schema by ID
+    pub async fn get_schema(&self, schema_id: u64) -> Option<RegisteredSchema> {
+        let result = schemas_cf(&self.db).and_then(|cf| {
+            self.db
+                .get_pinned_cf(cf, schema_key(schema_id))
+                .map_err(|e| ConfluxError::storage(format!("Failed to read schema: {}", e)))
+        });
+        match result {
+            Ok(Some(value)) => serde_json::from_slice(&value)
+                .map_err(|e| warn!("Failed to deserialize schema {}: {}", schema_id, e))
+                .ok(),
+            Ok(None) => None,
+            Err(e) => {
+                warn!("Failed to read schema {}: {}", schema_id, e);
+                None
+            }
+        }
+    }
+
+    /// All schemas registered for a tenant, in ID order
+    pub async fn list_schemas(&self, tenant: &str) -> Vec<RegisteredSchema> {
+        match scan_schemas(&self.db) {
+            Ok(schemas) => schemas.into_iter().filter(|s| s.tenant == tenant).collect(),
+            Err(e) => {
+                warn!("Failed to read schemas of tenant {}: {}", tenant, e);
+                Vec::new()
+            }
+        }
+    }
+
+    /// Validate version content against the schema the config references
+    ///
+    /// Configs without a schema reference accept any content, and
+    /// [`ConfigFormat::Binary`] content is opaque and never validated.
+    ///
+    /// # Errors
+    ///
+    /// Returns a validation error starting with [`SCHEMA_VALIDATION_FAILED`]
+    /// that lists every violation, or names the schema if it no longer exists.
+    pub async fn check_config_schema(
+        &self,
+        config: &Config,
+        content: &[u8],
+        format: &ConfigFormat,
+    ) -> Result<()> {
+        let Some(schema_id) = config.schema_id else {
+            return Ok(());
+        };
+        if *format == ConfigFormat::Binary {
+            return Ok(());
+        }
+        let schema = self.get_schema(schema_id).await.ok_or_else(|| {
+            ConfluxError::validation(format!(
+                "{}: schema {} is not registered",
+                SCHEMA_VALIDATION_FAILED, schema_id
+            ))
+        })?;
+        schema.validate(content, format).map_err(|errors| {
+            ConfluxError::validation(format!(
+                "{} against schema '{}': {}",
+                SCHEMA_VALIDATION_FAILED,
+                schema.name,
+                errors.join("; ")
+            ))
+        })
+    }
+}
+
+fn schemas_cf(db: &DB) -> Result<&rocksdb::ColumnFamily> {
+    db.cf_handle(CF_SCHEMAS)
+        .ok_or_else(|| ConfluxError::storage("Schemas column family not found"))
+}
+
+fn schema_key(id: u64) -> [u8; 9] {
+    let mut key = [SCHEMA_PREFIX; 9];
+    key[1..].copy_from_slice(&id.to_be_bytes());
+    key
+}
+
+fn put_schema(db: &DB, batch: &mut WriteBatch, schema: &RegisteredSchema) -> Result<()> {
+    let cf = schemas_cf(db)?;
+    let data = serde_json::to_vec(schema).map_err(|e| {
+        ConfluxError::storage(format!("Failed to serialize schema: {}", e))
+    })?;
+    batch.put_cf(cf, schema_key(schema.id), data);
+    Ok(())
+}
+
+/// Read all schema records in ID order
+fn scan_schemas(db: &DB) -> Result<Vec<RegisteredSchema>> {
+    let cf = schemas_cf(db)?;
+    let mut schemas = Vec::new();
+    let mode = IteratorMode::From(&[SCHEMA_PREFIX], Direction::Forward);
+    for item in db.iterator_cf(cf, mode) {
+        let (key, value) = item.map_err(|e| {
+            ConfluxError::storage(format!("Failed to read schema: {}", e))
+        })?;
+        if key.first() != Some(&SCHEMA_PREFIX) {
+            break;
+        }
+        let schema = serde_json::from_slice(&value).map_err(|e| {
+            ConfluxError::storage(format!("Failed to deserialize schema: {}", e))
+        })?;
+        schemas.push(schema);
+    }
+    Ok(schemas)
+}
+
+#[cfg(test)]
+#[path = "schemas_tests.rs"]
+mod tests;
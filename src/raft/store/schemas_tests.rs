@@ -0,0 +1,137 @@
+use super::*;
+use tempfile::{tempdir, TempDir};
+
+const PORT_SCHEMA: &str = r#"{
+    "type": "object",
+    "required": ["port"],
+    "properties": {
+        "port": {"type": "integer", "minimum": 1, "maximum": 65535}
+    }
+}"#;
+
+fn namespace(tenant: &str) -> ConfigNamespace {
+    ConfigNamespace {
+        tenant: tenant.to_string(),
+        app: "app".to_string(),
+        env: "prod".to_string(),
+    }
+}
+
+async fn register(store: &Store, tenant: &str, name: &str, schema: &str) -> ClientWriteResponse {
+    store
+        .apply_command(&RaftCommand::RegisterSchema {
+            tenant: tenant.to_string(),
+            name: name.to_string(),
+            schema: schema.to_string(),
+        })
+        .await
+        .unwrap()
+}
+
+async fn create_config(store: &Store, tenant: &str) -> u64 {
+    let response = store
+        .apply_command(&RaftCommand::CreateConfig {
+            namespace: namespace(tenant),
+            name: "app.json".to_string(),
+            content: br#"{"port":80}"#.to_vec(),
+            format: ConfigFormat::Json,
+            schema: None,
+            creator_id: 1,
+            description: "initial".to_string(),
+        })
+        .await
+        .unwrap();
+    response.config_id.unwrap()
+}
+
+async fn set_schema(store: &Store, config_id: u64, schema_id: Option<u64>) -> ClientWriteResponse {
+    store
+        .apply_command(&RaftCommand::SetConfigSchema { config_id, schema_id })
+        .await
+        .unwrap()
+}
+
+async fn create_version(store: &Store, config_id: u64, content: &[u8]) -> ClientWriteResponse {
+    store
+        .apply_command(&RaftCommand::CreateVersion {
+            config_id,
+            content: content.to_vec(),
+            format: None,
+            creator_id: 1,
+            description: "next".to_string(),
+        })
+        .await
+        .unwrap()
+}
+
+async fn setup() -> (Store, TempDir, u64, u64) {
+    let dir = tempdir().unwrap();
+    let (store, _rx) = Store::new(dir.path()).await.unwrap();
+    let response = register(&store, "tenant", "port", PORT_SCHEMA).await;
+    assert!(response.success, "{}", response.message);
+    let schema_id = response.data.unwrap()["schema_id"].as_u64().unwrap();
+    let config_id = create_config(&store, "tenant").await;
+    assert!(set_schema(&store, config_id, Some(schema_id)).await.success);
+    (store, dir, config_id, schema_id)
+}
+
+#[tokio::test]
+async fn test_versions_are_validated_against_referenced_schema() {
+    let (store, _dir, config_id, schema_id) = setup().await;
+    assert_eq!(store.get_config_meta(config_id).await.unwrap().schema_id, Some(schema_id));
+
+    assert!(create_version(&store, config_id, br#"{"port":8080}"#).await.success);
+
+    let response = create_version(&store, config_id, br#"{"port":70000}"#).await;
+    assert!(!response.success);
+    let message = response.message;
+    assert!(message.contains(SCHEMA_VALIDATION_FAILED), "{}", message);
+    assert!(message.contains("$.port"), "{}", message);
+
+    let response = create_version(&store, config_id, br#"{"host":"a"}"#).await;
+    assert!(!response.success);
+    assert!(response.message.contains("port"));
+
+    // Rejected versions are not stored
+    let config = store.get_config_meta(config_id).await.unwrap();
+    assert_eq!(config.latest_version_id, 2);
+
+    // Dropping the reference lifts the validation
+    assert!(set_schema(&store, config_id, None).await.success);
+    assert!(create_version(&store, config_id, br#"{"host":"a"}"#).await.success);
+}
+
+#[tokio::test]
+async fn test_reregistering_schema_keeps_id_and_replaces_document() {
+    let (store, _dir, config_id, schema_id) = setup().await;
+
+    let response = register(&store, "tenant", "port", r#"{"type":"object"}"#).await;
+    assert_eq!(response.data.unwrap()["schema_id"], schema_id);
+    assert!(create_version(&store, config_id, br#"{"host":"a"}"#).await.success);
+
+    let response = register(&store, "tenant", "other", "true").await;
+    assert_eq!(response.data.unwrap()["schema_id"], schema_id + 1);
+
+    let schemas = store.list_schemas("tenant").await;
+    assert_eq!(
+        schemas.iter().map(|s| s.name.as_str()).collect::<Vec<_>>(),
+        ["port", "other"]
+    );
+    assert_eq!(store.get_schema(schema_id).await.unwrap().schema, r#"{"type":"object"}"#);
+    assert!(store.list_schemas("someone-else").await.is_empty());
+}
+
+#[tokio::test]
+async fn test_invalid_and_foreign_schemas_are_rejected() {
+    let (store, _dir, _config_id, schema_id) = setup().await;
+
+    assert!(!register(&store, "tenant", "broken", "not json").await.success);
+    assert!(!register(&store, "tenant", "array", "[]").await.success);
+    assert!(store.list_schemas("tenant").await.len() == 1);
+
+    // Schemas of one tenant cannot be referenced by another tenant's configs
+    let foreign_config = create_config(&store, "other").await;
+    assert!(!set_schema(&store, foreign_config, Some(schema_id)).await.success);
+    assert!(!set_schema(&store, foreign_config, Some(99)).await.success);
+    assert!(!set_schema(&store, 99, None).await.success);
+}
@@ -0,0 +1,67 @@
+use crate::raft::types::*;
+use super::types::Store;
+
+impl Store {
+    /// Search live configurations by metadata
+    ///
+    /// Soft-deleted configs are never returned. Scans the in-memory
+    /// configuration map, checking cheap config-level criteria
+    /// first and only consulting versions when version-level criteria are set.
+    /// Results are sorted by `updated_at`, most recently updated first.
+    pub async fn search_configs(&self, filter: &ConfigFilter) -> Vec<Config> {
+        let configs = self.configurations.read().await;
+
+        let candidates: Vec<&Config> = configs
+            .values()
+            .filter(|config| !config.is_deleted() && filter.matches_config(config))
+            .collect();
+
+        let mut results: Vec<Config> = if filter.has_version_criteria() {
+            let versions = self.versions.read().await;
+            candidates
+                .into_iter()
+                .filter(|config| {
+                    versions
+                        .get(&config.id)
+                        .is_some_and(|config_versions| {
+                            filter.matches_versions(config_versions.values())
+                        })
+                })
+                .cloned()
+                .collect()
+        } else {
+            candidates.into_iter().cloned().collect()
+        };
+
+        results.sort_by_key(|config| std::cmp::Reverse(config.updated_at));
+        results
+    }
+
+    /// List live configurations in a namespace
+    ///
+    /// Optionally restricted to names starting with `prefix` and to configs
+    /// with a release whose labels satisfy `label_query`. Results are sorted
+    /// by name.
+    pub async fn list_configs(
+        &self,
+        namespace: &ConfigNamespace,
+        prefix: Option<&str>,
+        label_query: Option<&LabelQuery>,
+    ) -> Vec<Config> {
+        let configs = self.configurations.read().await;
+        let mut results: Vec<Config> = configs
+            .values()
+            .filter(|config| config.namespace == *namespace && !config.is_deleted())
+            .filter(|config| prefix.is_none_or(|prefix| config.name.starts_with(prefix)))
+            .filter(|config| label_query.is_none_or(|query| config.matches_label_query(query)))
+            .cloned()
+            .collect();
+
+        results.sort_by(|a, b| a.name.cmp(&b.name));
+        results
+    }
+}
+
+#[cfg(test)]
+#[path = "search_tests.rs"]
+mod tests;
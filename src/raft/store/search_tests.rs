@@ -0,0 +1,347 @@
+use crate::raft::{
+    types::{ConfigFilter, ConfigFormat, ConfigNamespace, LabelQuery, RaftCommand, Release},
+    Store,
+};
+use chrono::{Duration, TimeZone, Utc};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use tempfile::tempdir;
+
+fn namespace(env: &str) -> ConfigNamespace {
+    ConfigNamespace {
+        tenant: "tenant".to_string(),
+        app: "app".to_string(),
+        env: env.to_string(),
+    }
+}
+
+async fn create_config(
+    store: &Store,
+    namespace: &ConfigNamespace,
+    name: &str,
+    creator_id: u64,
+    description: &str,
+) -> u64 {
+    let response = store
+        .apply_command(&RaftCommand::CreateConfig {
+            namespace: namespace.clone(),
+            name: name.to_string(),
+            content: b"{}".to_vec(),
+            format: ConfigFormat::Json,
+            schema: None,
+            creator_id,
+            description: description.to_string(),
+        })
+        .await
+        .unwrap();
+    assert!(response.success);
+    store.get_config(namespace, name).await.unwrap().id
+}
+
+/// Set created/updated timestamps as days offset from a fixed origin
+async fn set_timestamps(store: &Store, namespace: &ConfigNamespace, name: &str, day: i64) {
+    let origin = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+    let mut configs = store.configurations.write().await;
+    let config = configs
+        .values_mut()
+        .find(|c| c.namespace == *namespace && c.name == name)
+        .unwrap();
+    config.created_at = origin + Duration::days(day);
+    config.updated_at = origin + Duration::days(day);
+}
+
+/// Builds a store with:
+/// - prod/db.toml      creator 1, "database settings", day 1
+/// - prod/db-replica   creator 2, "replica database",  day 5
+/// - prod/cache.json   creator 1, "redis cache",       day 10
+/// - staging/db.toml   creator 2, "staging database",  day 3
+async fn create_test_store() -> (Arc<Store>, tempfile::TempDir) {
+    let temp_dir = tempdir().unwrap();
+    let (store, _) = Store::new(temp_dir.path()).await.unwrap();
+
+    let prod = namespace("prod");
+    let staging = namespace("staging");
+
+    create_config(&store, &prod, "db.toml", 1, "database settings").await;
+    create_config(&store, &prod, "db-replica.toml", 2, "replica database").await;
+    create_config(&store, &prod, "cache.json", 1, "Redis cache").await;
+    create_config(&store, &staging, "db.toml", 2, "staging database").await;
+
+    set_timestamps(&store, &prod, "db.toml", 1).await;
+    set_timestamps(&store, &prod, "db-replica.toml", 5).await;
+    set_timestamps(&store, &prod, "cache.json", 10).await;
+    set_timestamps(&store, &staging, "db.toml", 3).await;
+
+    (Arc::new(store), temp_dir)
+}
+
+fn names(configs: &[crate::raft::types::Config]) -> Vec<String> {
+    configs
+        .iter()
+        .map(|c| format!("{}/{}", c.namespace.env, c.name))
+        .collect()
+}
+
+fn day(day: i64) -> chrono::DateTime<Utc> {
+    Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap() + Duration::days(day)
+}
+
+#[tokio::test]
+async fn test_empty_filter_returns_all_sorted_by_updated_at() {
+    let (store, _temp_dir) = create_test_store().await;
+
+    let results = store.search_configs(&ConfigFilter::default()).await;
+    assert_eq!(
+        names(&results),
+        vec![
+            "prod/cache.json",
+            "prod/db-replica.toml",
+            "staging/db.toml",
+            "prod/db.toml"
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_filter_by_namespace() {
+    let (store, _temp_dir) = create_test_store().await;
+
+    let filter = ConfigFilter {
+        namespace: Some(namespace("staging")),
+        ..Default::default()
+    };
+    assert_eq!(names(&store.search_configs(&filter).await), vec!["staging/db.toml"]);
+}
+
+#[tokio::test]
+async fn test_filter_by_name_prefix() {
+    let (store, _temp_dir) = create_test_store().await;
+
+    let filter = ConfigFilter {
+        name_prefix: Some("db".to_string()),
+        ..Default::default()
+    };
+    assert_eq!(
+        names(&store.search_configs(&filter).await),
+        vec!["prod/db-replica.toml", "staging/db.toml", "prod/db.toml"]
+    );
+}
+
+#[tokio::test]
+async fn test_filter_by_date_range() {
+    let (store, _temp_dir) = create_test_store().await;
+
+    let after = ConfigFilter {
+        created_after: Some(day(4)),
+        ..Default::default()
+    };
+    assert_eq!(
+        names(&store.search_configs(&after).await),
+        vec!["prod/cache.json", "prod/db-replica.toml"]
+    );
+
+    let before = ConfigFilter {
+        created_before: Some(day(4)),
+        ..Default::default()
+    };
+    assert_eq!(
+        names(&store.search_configs(&before).await),
+        vec!["staging/db.toml", "prod/db.toml"]
+    );
+
+    let between = ConfigFilter {
+        created_after: Some(day(2)),
+        created_before: Some(day(6)),
+        ..Default::default()
+    };
+    assert_eq!(
+        names(&store.search_configs(&between).await),
+        vec!["prod/db-replica.toml", "staging/db.toml"]
+    );
+}
+
+#[tokio::test]
+async fn test_filter_by_creator() {
+    let (store, _temp_dir) = create_test_store().await;
+
+    let filter = ConfigFilter {
+        creator_id: Some(2),
+        ..Default::default()
+    };
+    assert_eq!(
+        names(&store.search_configs(&filter).await),
+        vec!["prod/db-replica.toml", "staging/db.toml"]
+    );
+}
+
+#[tokio::test]
+async fn test_filter_by_description_is_case_insensitive() {
+    let (store, _temp_dir) = create_test_store().await;
+
+    let filter = ConfigFilter {
+        description_contains: Some("REDIS".to_string()),
+        ..Default::default()
+    };
+    assert_eq!(names(&store.search_configs(&filter).await), vec!["prod/cache.json"]);
+}
+
+#[tokio::test]
+async fn test_filter_by_creator_of_later_version() {
+    let (store, _temp_dir) = create_test_store().await;
+    let prod = namespace("prod");
+    let config = store.get_config(&prod, "cache.json").await.unwrap();
+
+    store
+        .apply_command(&RaftCommand::CreateVersion {
+            config_id: config.id,
+            content: b"{\"ttl\": 60}".to_vec(),
+            format: Some(ConfigFormat::Json),
+            creator_id: 3,
+            description: "tune ttl".to_string(),
+        })
+        .await
+        .unwrap();
+
+    let filter = ConfigFilter {
+        creator_id: Some(3),
+        ..Default::default()
+    };
+    let results = store.search_configs(&filter).await;
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].id, config.id);
+}
+
+#[tokio::test]
+async fn test_combined_filters() {
+    let (store, _temp_dir) = create_test_store().await;
+
+    let filter = ConfigFilter {
+        tenant: Some("tenant".to_string()),
+        namespace: Some(namespace("prod")),
+        name_prefix: Some("db".to_string()),
+        created_after: Some(day(0)),
+        created_before: Some(day(7)),
+        creator_id: Some(2),
+        description_contains: Some("database".to_string()),
+    };
+    assert_eq!(names(&store.search_configs(&filter).await), vec!["prod/db-replica.toml"]);
+
+    // Creator and description must both match, not just one of them
+    let mismatched = ConfigFilter {
+        creator_id: Some(1),
+        description_contains: Some("replica".to_string()),
+        ..Default::default()
+    };
+    assert!(store.search_configs(&mismatched).await.is_empty());
+}
+
+#[tokio::test]
+async fn test_search_is_limited_to_one_tenant() {
+    let (store, _temp_dir) = create_test_store().await;
+    let other = ConfigNamespace {
+        tenant: "other".to_string(),
+        ..namespace("prod")
+    };
+    create_config(&store, &other, "db.toml", 1, "other tenant database").await;
+
+    let filter = ConfigFilter {
+        tenant: Some("other".to_string()),
+        ..Default::default()
+    };
+    let results = store.search_configs(&filter).await;
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].namespace, other);
+
+    let filter = ConfigFilter {
+        tenant: Some("tenant".to_string()),
+        name_prefix: Some("db".to_string()),
+        ..Default::default()
+    };
+    assert_eq!(
+        names(&store.search_configs(&filter).await),
+        vec!["prod/db-replica.toml", "staging/db.toml", "prod/db.toml"]
+    );
+}
+
+#[tokio::test]
+async fn test_search_skips_soft_deleted_configs() {
+    let (store, _temp_dir) = create_test_store().await;
+    let response = store
+        .apply_command(&RaftCommand::SoftDeleteNamespace {
+            namespace: namespace("staging"),
+            delete_after_days: 7,
+            deleted_at: Utc::now(),
+        })
+        .await
+        .unwrap();
+    assert!(response.success, "{}", response.message);
+
+    let filter = ConfigFilter {
+        name_prefix: Some("db".to_string()),
+        ..Default::default()
+    };
+    assert_eq!(
+        names(&store.search_configs(&filter).await),
+        vec!["prod/db-replica.toml", "prod/db.toml"]
+    );
+}
+
+/// Replace the releases of a config with one release per label set
+async fn set_release_labels(
+    store: &Store,
+    namespace: &ConfigNamespace,
+    name: &str,
+    label_sets: &[&[(&str, &str)]],
+) {
+    let mut configs = store.configurations.write().await;
+    let config = configs
+        .values_mut()
+        .find(|c| c.namespace == *namespace && c.name == name)
+        .unwrap();
+    config.releases = label_sets
+        .iter()
+        .map(|pairs| {
+            let labels: BTreeMap<String, String> = pairs
+                .iter()
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .collect();
+            Release::new(labels, config.latest_version_id, 1)
+        })
+        .collect();
+}
+
+#[tokio::test]
+async fn test_list_configs_with_prefix_and_label_query() {
+    let (store, _temp_dir) = create_test_store().await;
+    let prod = namespace("prod");
+
+    set_release_labels(&store, &prod, "db.toml", &[&[("env", "prod"), ("region", "us-east-1")]]).await;
+    set_release_labels(&store, &prod, "db-replica.toml", &[&[("env", "prod"), ("region", "eu-west-1")]]).await;
+    set_release_labels(&store, &prod, "cache.json", &[&[("env", "canary")], &[("region", "us-east-1")]]).await;
+
+    // Without filters all live configs of the namespace are listed by name
+    assert_eq!(
+        names(&store.list_configs(&prod, None, None).await),
+        vec!["prod/cache.json", "prod/db-replica.toml", "prod/db.toml"]
+    );
+    assert_eq!(
+        names(&store.list_configs(&prod, Some("db"), None).await),
+        vec!["prod/db-replica.toml", "prod/db.toml"]
+    );
+
+    let query = LabelQuery::parse("env=prod AND region IN us-east-1").unwrap();
+    assert_eq!(
+        names(&store.list_configs(&prod, None, Some(&query)).await),
+        vec!["prod/db.toml"]
+    );
+
+    let query = LabelQuery::parse("region = us-east-1 OR env ~= can.*").unwrap();
+    assert_eq!(
+        names(&store.list_configs(&prod, None, Some(&query)).await),
+        vec!["prod/cache.json", "prod/db.toml"]
+    );
+    assert_eq!(
+        names(&store.list_configs(&prod, Some("db"), Some(&query)).await),
+        vec!["prod/db.toml"]
+    );
+}
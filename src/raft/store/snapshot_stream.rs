@@ -0,0 +1,545 @@
+//! Streaming snapshot encoding and decoding
+//!
+//! A snapshot is the state machine header followed by every key/value pair of
+//! the state column families. It is produced on a blocking thread from a
+//! RocksDB snapshot and handed out in fixed-size chunks through an
+//! [`AsyncRead`], so the node never holds more than a few chunks of an
+//! unsent snapshot. Installing decodes chunk by chunk and writes each chunk's
+//! records in one batch. Between the two, snapshots are kept in anonymous
+//! temporary files rather than in memory.
+//!
+//! Layout: `MAGIC`, a flags byte, then (deflated if the compression flag is
+//! set) a length-prefixed header followed by records of
+//! `[cf u8][key_len u32][key][value_len u32][value]` and an end marker.
+
+use super::constants::*;
+use super::types::Store;
+use crate::error::{ConfluxError, Result};
+use flate2::write::{DeflateDecoder, DeflateEncoder};
+use flate2::Compression;
+use rocksdb::{IteratorMode, WriteBatch, DB};
+use std::io::{self, Write};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tokio::fs::File;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncSeekExt, AsyncWriteExt, ReadBuf};
+use tokio::sync::{mpsc, oneshot};
+use tracing::{debug, info};
+
+/// Marker at the start of streamed snapshots; older snapshots hold only the header
+const SNAPSHOT_MAGIC: &[u8; 8] = b"CFXSNAP1";
+
+/// Flags byte bit set when the snapshot body is deflated
+const FLAG_COMPRESSED: u8 = 0x01;
+
+/// Record tag that ends the snapshot body
+const END_OF_SNAPSHOT: u8 = 0xFF;
+
+/// Column families holding state machine data, indexed by their record tag
+const STATE_COLUMN_FAMILIES: [&str; 10] = [
+    CF_CONFIGS,
+    CF_VERSIONS,
+    CF_META,
+    CF_SCHEDULED,
+    CF_DEPENDENCIES,
+    CF_APPROVALS,
+    CF_SCHEMAS,
+    CF_WEBHOOKS,
+    CF_HASH_INDEX,
+    CF_IDEMPOTENCY,
+];
+
+/// Whether a record belongs to this node rather than to the replicated state
+///
+/// Such records are neither sent in nor replaced by a snapshot.
+fn is_local_record(name: &str, key: &[u8]) -> bool {
+    name == CF_META && key == WAL_APPLIED_SEQUENCE_KEY
+}
+
+/// Encoded chunks buffered ahead of the reader
+const CHUNKS_IN_FLIGHT: usize = 2;
+
+/// Snapshot data as a stream of bytes
+pub type SnapshotStream = Box<dyn AsyncRead + Send + Unpin>;
+
+/// A snapshot read back from a stream
+#[derive(Debug)]
+pub struct RestoredSnapshot {
+    /// State machine header the snapshot was built with
+    pub header: Vec<u8>,
+    /// Total bytes read from the stream
+    pub bytes_read: u64,
+}
+
+impl Store {
+    /// Stream the current state with `header` in front, in `chunk_size` chunks
+    ///
+    /// The RocksDB snapshot is taken before this returns, so callers holding
+    /// the state machine lock get a stream consistent with `header`.
+    pub async fn snapshot_stream(
+        &self,
+        header: Vec<u8>,
+        chunk_size: usize,
+        compression: bool,
+    ) -> Result<SnapshotStream> {
+        let (tx, rx) = mpsc::channel(CHUNKS_IN_FLIGHT);
+        let (taken_tx, taken_rx) = oneshot::channel();
+        let db = self.db.clone();
+        let chunk_size = chunk_size.max(1);
+
+        tokio::task::spawn_blocking(move || {
+            let snapshot = db.snapshot();
+            let _ = taken_tx.send(());
+            let writer = ChunkWriter::new(tx.clone(), chunk_size);
+            if let Err(e) = encode_snapshot(&db, &snapshot, writer, &header, compression) {
+                let _ = tx.blocking_send(Err(e));
+            }
+        });
+
+        taken_rx
+            .await
+            .map_err(|_| ConfluxError::storage("Snapshot encoder stopped before starting"))?;
+        Ok(Box::new(ChunkReader::new(rx)))
+    }
+
+    /// Replace the state with a snapshot read from `reader` in `chunk_size` chunks
+    ///
+    /// Snapshots without the stream marker predate streaming and carry only
+    /// the header; they are returned as is and leave the stored data untouched.
+    pub async fn restore_snapshot_stream<R>(
+        &self,
+        reader: &mut R,
+        chunk_size: usize,
+    ) -> Result<RestoredSnapshot>
+    where
+        R: AsyncRead + Unpin + ?Sized,
+    {
+        let mut chunk = vec![0u8; chunk_size.max(SNAPSHOT_MAGIC.len() + 1)];
+        let mut prefix = Vec::new();
+        while prefix.len() < SNAPSHOT_MAGIC.len() + 1 {
+            let n = read_chunk(reader, &mut chunk).await?;
+            if n == 0 {
+                break;
+            }
+            prefix.extend_from_slice(&chunk[..n]);
+        }
+        let mut bytes_read = prefix.len() as u64;
+
+        if !prefix.starts_with(SNAPSHOT_MAGIC) || prefix.len() <= SNAPSHOT_MAGIC.len() {
+            reader.read_to_end(&mut prefix).await.map_err(|e| {
+                ConfluxError::storage(format!("Failed to read snapshot: {}", e))
+            })?;
+            let bytes_read = prefix.len() as u64;
+            return Ok(RestoredSnapshot {
+                header: prefix,
+                bytes_read,
+            });
+        }
+
+        self.clear_state_column_families()?;
+        let flags = prefix[SNAPSHOT_MAGIC.len()];
+        let body = &prefix[SNAPSHOT_MAGIC.len() + 1..];
+        let mut sink = if flags & FLAG_COMPRESSED != 0 {
+            BodySink::Compressed(DeflateDecoder::new(RecordWriter::new(&self.db)))
+        } else {
+            BodySink::Plain(RecordWriter::new(&self.db))
+        };
+
+        sink.write_all(body).map_err(snapshot_write_error)?;
+        loop {
+            let n = read_chunk(reader, &mut chunk).await?;
+            if n == 0 {
+                break;
+            }
+            bytes_read += n as u64;
+            sink.write_all(&chunk[..n]).map_err(snapshot_write_error)?;
+        }
+        let records = sink.finish().map_err(snapshot_write_error)?;
+        let (header, record_count) = records.finish()?;
+
+        self.reload_state_from_disk().await?;
+        info!(
+            "Restored {} records ({} bytes) from snapshot stream",
+            record_count, bytes_read
+        );
+        Ok(RestoredSnapshot { header, bytes_read })
+    }
+
+    /// Delete every key of the state column families
+    fn clear_state_column_families(&self) -> Result<()> {
+        for name in STATE_COLUMN_FAMILIES {
+            let cf = state_cf(&self.db, name)?;
+            let mut batch = WriteBatch::default();
+            for item in self.db.iterator_cf(cf, IteratorMode::Start) {
+                let (key, _) = item.map_err(|e| {
+                    ConfluxError::storage(format!("Failed to read {}: {}", name, e))
+                })?;
+                if !is_local_record(name, &key) {
+                    batch.delete_cf(cf, key);
+                }
+            }
+            self.db.write(batch).map_err(|e| {
+                ConfluxError::storage(format!("Failed to clear {}: {}", name, e))
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Rebuild the in-memory caches from the restored column families
+    ///
+    /// The persisted counter may lag behind the restored configs; the reload
+    /// keeps it past every restored ID so that none is handed out again.
+    async fn reload_state_from_disk(&self) -> Result<()> {
+        self.replace_state_from_disk().await?;
+        self.published_cache.invalidate_all();
+        Ok(())
+    }
+}
+
+/// Create an empty anonymous temporary file for snapshot data
+///
+/// The file is removed as soon as it is dropped.
+pub async fn snapshot_file() -> io::Result<File> {
+    let file = tokio::task::spawn_blocking(tempfile::tempfile)
+        .await
+        .map_err(io::Error::other)??;
+    Ok(File::from_std(file))
+}
+
+/// Copy a snapshot stream into a new snapshot file in `chunk_size` chunks
+///
+/// The returned file is flushed and positioned at its start.
+pub async fn spill_snapshot<R>(reader: &mut R, chunk_size: usize) -> io::Result<File>
+where
+    R: AsyncRead + Unpin + ?Sized,
+{
+    let mut file = snapshot_file().await?;
+    let mut chunk = vec![0u8; chunk_size.max(1)];
+    loop {
+        let n = reader.read(&mut chunk).await?;
+        if n == 0 {
+            break;
+        }
+        file.write_all(&chunk[..n]).await?;
+    }
+    file.flush().await?;
+    file.rewind().await?;
+    Ok(file)
+}
+
+/// Read the next `chunk_size` bytes of snapshot data, fewer only at its end
+pub async fn read_snapshot_chunk<R>(reader: &mut R, chunk_size: usize) -> io::Result<Vec<u8>>
+where
+    R: AsyncRead + Unpin + ?Sized,
+{
+    let mut chunk = vec![0u8; chunk_size];
+    let mut filled = 0;
+    while filled < chunk.len() {
+        let n = reader.read(&mut chunk[filled..]).await?;
+        if n == 0 {
+            break;
+        }
+        filled += n;
+    }
+    chunk.truncate(filled);
+    Ok(chunk)
+}
+
+fn state_cf<'a>(db: &'a DB, name: &str) -> Result<&'a rocksdb::ColumnFamily> {
+    db.cf_handle(name)
+        .ok_or_else(|| ConfluxError::storage(format!("Column family {} not found", name)))
+}
+
+async fn read_chunk<R>(reader: &mut R, chunk: &mut [u8]) -> Result<usize>
+where
+    R: AsyncRead + Unpin + ?Sized,
+{
+    reader
+        .read(chunk)
+        .await
+        .map_err(|e| ConfluxError::storage(format!("Failed to read snapshot: {}", e)))
+}
+
+fn snapshot_write_error(e: io::Error) -> ConfluxError {
+    ConfluxError::storage(format!("Failed to restore snapshot: {}", e))
+}
+
+/// Write the whole snapshot to `writer`, flushing the last partial chunk
+fn encode_snapshot(
+    db: &DB,
+    snapshot: &rocksdb::Snapshot<'_>,
+    mut writer: ChunkWriter,
+    header: &[u8],
+    compression: bool,
+) -> io::Result<()> {
+    writer.write_all(SNAPSHOT_MAGIC)?;
+    if compression {
+        writer.write_all(&[FLAG_COMPRESSED])?;
+        let mut encoder = DeflateEncoder::new(writer, Compression::fast());
+        encode_body(db, snapshot, &mut encoder, header)?;
+        writer = encoder.finish()?;
+    } else {
+        writer.write_all(&[0])?;
+        encode_body(db, snapshot, &mut writer, header)?;
+    }
+    writer.send_remaining()
+}
+
+fn encode_body(
+    db: &DB,
+    snapshot: &rocksdb::Snapshot<'_>,
+    out: &mut impl Write,
+    header: &[u8],
+) -> io::Result<()> {
+    write_bytes(out, header)?;
+    let mut count = 0u64;
+    for (tag, name) in STATE_COLUMN_FAMILIES.iter().enumerate() {
+        let cf = state_cf(db, name).map_err(io::Error::other)?;
+        for item in snapshot.iterator_cf(cf, IteratorMode::Start) {
+            let (key, value) = item.map_err(io::Error::other)?;
+            if is_local_record(name, &key) {
+                continue;
+            }
+            out.write_all(&[tag as u8])?;
+            write_bytes(out, &key)?;
+            write_bytes(out, &value)?;
+            count += 1;
+        }
+    }
+    out.write_all(&[END_OF_SNAPSHOT])?;
+    debug!("Encoded {} records into snapshot stream", count);
+    Ok(())
+}
+
+fn write_bytes(out: &mut impl Write, bytes: &[u8]) -> io::Result<()> {
+    let len = u32::try_from(bytes.len())
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "snapshot entry too large"))?;
+    out.write_all(&len.to_be_bytes())?;
+    out.write_all(bytes)
+}
+
+/// Splits written bytes into fixed-size chunks sent to a [`ChunkReader`]
+struct ChunkWriter {
+    tx: mpsc::Sender<io::Result<Vec<u8>>>,
+    chunk_size: usize,
+    buf: Vec<u8>,
+}
+
+impl ChunkWriter {
+    fn new(tx: mpsc::Sender<io::Result<Vec<u8>>>, chunk_size: usize) -> Self {
+        Self {
+            tx,
+            chunk_size,
+            buf: Vec::with_capacity(chunk_size),
+        }
+    }
+
+    fn send(&self, chunk: Vec<u8>) -> io::Result<()> {
+        self.tx
+            .blocking_send(Ok(chunk))
+            .map_err(|_| io::Error::new(io::ErrorKind::BrokenPipe, "snapshot reader dropped"))
+    }
+
+    fn send_remaining(mut self) -> io::Result<()> {
+        if self.buf.is_empty() {
+            return Ok(());
+        }
+        let chunk = std::mem::take(&mut self.buf);
+        self.send(chunk)
+    }
+}
+
+impl Write for ChunkWriter {
+    fn write(&mut self, data: &[u8]) -> io::Result<usize> {
+        self.buf.extend_from_slice(data);
+        while self.buf.len() >= self.chunk_size {
+            let rest = self.buf.split_off(self.chunk_size);
+            let chunk = std::mem::replace(&mut self.buf, rest);
+            self.send(chunk)?;
+        }
+        Ok(data.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// [`AsyncRead`] over the chunks produced by the snapshot encoder
+struct ChunkReader {
+    rx: mpsc::Receiver<io::Result<Vec<u8>>>,
+    chunk: Vec<u8>,
+    pos: usize,
+}
+
+impl ChunkReader {
+    fn new(rx: mpsc::Receiver<io::Result<Vec<u8>>>) -> Self {
+        Self {
+            rx,
+            chunk: Vec::new(),
+            pos: 0,
+        }
+    }
+}
+
+impl AsyncRead for ChunkReader {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        loop {
+            if self.pos < self.chunk.len() {
+                let n = buf.remaining().min(self.chunk.len() - self.pos);
+                let start = self.pos;
+                buf.put_slice(&self.chunk[start..start + n]);
+                self.pos += n;
+                return Poll::Ready(Ok(()));
+            }
+            match self.rx.poll_recv(cx) {
+                Poll::Ready(Some(Ok(chunk))) => {
+                    self.chunk = chunk;
+                    self.pos = 0;
+                }
+                Poll::Ready(Some(Err(e))) => return Poll::Ready(Err(e)),
+                Poll::Ready(None) => return Poll::Ready(Ok(())),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+/// Destination of the snapshot body, inflating it first when compressed
+enum BodySink<'a> {
+    Plain(RecordWriter<'a>),
+    Compressed(DeflateDecoder<RecordWriter<'a>>),
+}
+
+impl<'a> BodySink<'a> {
+    fn finish(self) -> io::Result<RecordWriter<'a>> {
+        match self {
+            BodySink::Plain(records) => Ok(records),
+            BodySink::Compressed(decoder) => decoder.finish(),
+        }
+    }
+}
+
+impl Write for BodySink<'_> {
+    fn write(&mut self, data: &[u8]) -> io::Result<usize> {
+        match self {
+            BodySink::Plain(records) => records.write(data),
+            BodySink::Compressed(decoder) => decoder.write(data),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            BodySink::Plain(records) => records.flush(),
+            BodySink::Compressed(decoder) => decoder.flush(),
+        }
+    }
+}
+
+/// Parses the snapshot body and writes each batch of complete records to RocksDB
+struct RecordWriter<'a> {
+    db: &'a DB,
+    buf: Vec<u8>,
+    header: Option<Vec<u8>>,
+    records: u64,
+    ended: bool,
+}
+
+impl<'a> RecordWriter<'a> {
+    fn new(db: &'a DB) -> Self {
+        Self {
+            db,
+            buf: Vec::new(),
+            header: None,
+            records: 0,
+            ended: false,
+        }
+    }
+
+    /// Header and number of records once the end marker has been seen
+    fn finish(self) -> Result<(Vec<u8>, u64)> {
+        match self.header {
+            Some(header) if self.ended => Ok((header, self.records)),
+            _ => Err(ConfluxError::storage("Snapshot stream ended early")),
+        }
+    }
+
+    /// Write every complete record in the buffer, keeping a trailing partial one
+    fn apply_buffered(&mut self) -> io::Result<()> {
+        let mut pos = 0;
+        if self.header.is_none() {
+            match read_bytes(&self.buf, pos) {
+                Some((header, next)) => {
+                    self.header = Some(header.to_vec());
+                    pos = next;
+                }
+                None => return Ok(()),
+            }
+        }
+
+        let mut batch = WriteBatch::default();
+        while !self.ended && pos < self.buf.len() {
+            let tag = self.buf[pos];
+            if tag == END_OF_SNAPSHOT {
+                self.ended = true;
+                pos += 1;
+                break;
+            }
+            let name = STATE_COLUMN_FAMILIES.get(tag as usize).ok_or_else(|| {
+                io::Error::new(io::ErrorKind::InvalidData, "unknown snapshot record")
+            })?;
+            let Some((key, after_key)) = read_bytes(&self.buf, pos + 1) else {
+                break;
+            };
+            let Some((value, after_value)) = read_bytes(&self.buf, after_key) else {
+                break;
+            };
+            if !is_local_record(name, key) {
+                let cf = state_cf(self.db, name).map_err(io::Error::other)?;
+                batch.put_cf(cf, key, value);
+            }
+            self.records += 1;
+            pos = after_value;
+        }
+
+        if !batch.is_empty() {
+            self.db.write(batch).map_err(io::Error::other)?;
+        }
+        if self.ended && pos < self.buf.len() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "data after end of snapshot",
+            ));
+        }
+        self.buf.drain(..pos);
+        Ok(())
+    }
+}
+
+/// Length-prefixed bytes at `pos` and the position after them, if complete
+fn read_bytes(buf: &[u8], pos: usize) -> Option<(&[u8], usize)> {
+    let len_bytes: [u8; 4] = buf.get(pos..pos + 4)?.try_into().ok()?;
+    let start = pos + 4;
+    let end = start + u32::from_be_bytes(len_bytes) as usize;
+    Some((buf.get(start..end)?, end))
+}
+
+impl Write for RecordWriter<'_> {
+    fn write(&mut self, data: &[u8]) -> io::Result<usize> {
+        self.buf.extend_from_slice(data);
+        self.apply_buffered()?;
+        Ok(data.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[path = "snapshot_stream_tests.rs"]
+mod tests;
@@ -0,0 +1,120 @@
+use super::*;
+use crate::raft::types::*;
+use crate::raft::store::test_support::create_store;
+
+const HEADER: &[u8] = br#"{"last_applied_log":null}"#;
+
+fn namespace() -> ConfigNamespace {
+    ConfigNamespace {
+        tenant: "tenant".to_string(),
+        app: "app".to_string(),
+        env: "prod".to_string(),
+    }
+}
+
+async fn create_config(store: &Store, name: &str, content: &str) {
+    let response = store
+        .apply_command(&RaftCommand::CreateConfig {
+            namespace: namespace(),
+            name: name.to_string(),
+            content: content.as_bytes().to_vec(),
+            format: ConfigFormat::Json,
+            schema: None,
+            creator_id: 1,
+            description: "initial".to_string(),
+        })
+        .await
+        .unwrap();
+    assert!(response.success);
+}
+
+async fn read_all(stream: &mut SnapshotStream, chunk_size: usize) -> Vec<u8> {
+    let mut data = Vec::new();
+    let mut chunk = vec![0u8; chunk_size * 2];
+    loop {
+        let n = stream.read(&mut chunk).await.unwrap();
+        if n == 0 {
+            return data;
+        }
+        assert!(n <= chunk_size, "read {} bytes from {} byte chunks", n, chunk_size);
+        data.extend_from_slice(&chunk[..n]);
+    }
+}
+
+async fn assert_round_trip(compression: bool) {
+    let (leader, _leader_dir) = create_store().await;
+    for i in 0..20 {
+        create_config(&leader, &format!("config-{}", i), &format!("{{\"value\": {}}}", i)).await;
+    }
+    let (follower, _follower_dir) = create_store().await;
+    create_config(&follower, "stale", "{}").await;
+
+    let chunk_size = 64;
+    let mut stream = leader
+        .snapshot_stream(HEADER.to_vec(), chunk_size, compression)
+        .await
+        .unwrap();
+    let data = read_all(&mut stream, chunk_size).await;
+    assert!(data.starts_with(SNAPSHOT_MAGIC));
+
+    let restored = follower
+        .restore_snapshot_stream(&mut std::io::Cursor::new(data.clone()), chunk_size)
+        .await
+        .unwrap();
+    assert_eq!(restored.header, HEADER);
+    assert_eq!(restored.bytes_read, data.len() as u64);
+
+    assert!(follower.get_config(&namespace(), "stale").await.is_none());
+    assert_eq!(follower.list_configs_in_namespace(&namespace()).await.len(), 20);
+    let (config, version) = follower
+        .get_published_config(&namespace(), "config-7", &Default::default())
+        .await
+        .unwrap();
+    assert_eq!(config.id, leader.get_config(&namespace(), "config-7").await.unwrap().id);
+    assert_eq!(version.content, b"{\"value\": 7}");
+
+    // New configs continue from the leader's id sequence
+    create_config(&follower, "config-20", "{}").await;
+    assert_eq!(follower.get_config(&namespace(), "config-20").await.unwrap().id, 21);
+}
+
+#[tokio::test]
+async fn test_snapshot_stream_round_trip() {
+    assert_round_trip(false).await;
+}
+
+#[tokio::test]
+async fn test_compressed_snapshot_stream_round_trip() {
+    assert_round_trip(true).await;
+}
+
+#[tokio::test]
+async fn test_header_only_snapshot_leaves_store_untouched() {
+    let (store, _dir) = create_store().await;
+    create_config(&store, "kept", "{}").await;
+
+    let restored = store
+        .restore_snapshot_stream(&mut std::io::Cursor::new(HEADER.to_vec()), 16)
+        .await
+        .unwrap();
+    assert_eq!(restored.header, HEADER);
+    assert!(store.get_config(&namespace(), "kept").await.is_some());
+}
+
+#[tokio::test]
+async fn test_truncated_snapshot_stream_is_rejected() {
+    let (leader, _leader_dir) = create_store().await;
+    create_config(&leader, "config", "{}").await;
+    let mut stream = leader
+        .snapshot_stream(HEADER.to_vec(), 1024, false)
+        .await
+        .unwrap();
+    let mut data = read_all(&mut stream, 1024).await;
+    data.truncate(data.len() - 1);
+
+    let (follower, _follower_dir) = create_store().await;
+    let result = follower
+        .restore_snapshot_stream(&mut std::io::Cursor::new(data), 1024)
+        .await;
+    assert!(result.is_err());
+}
@@ -0,0 +1,193 @@
+use crate::error::Result;
+use crate::raft::types::*;
+use super::types::{ConfigChangeEvent, ConfigChangeType, Store};
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use std::collections::BTreeMap;
+use std::time::Duration;
+use tokio::sync::RwLock;
+use tracing::{info, warn};
+
+/// Interval at which the leader checks for soft-deleted configs whose recovery window has expired
+pub const SOFT_DELETE_GC_INTERVAL: Duration = Duration::from_secs(300);
+
+impl Store {
+    /// Get all configurations in a namespace, including soft-deleted ones
+    pub async fn list_configs_in_namespace_including_deleted(
+        &self,
+        namespace: &ConfigNamespace,
+    ) -> Vec<Config> {
+        let configs = self.configurations.read().await;
+        configs
+            .values()
+            .filter(|config| config.namespace == *namespace)
+            .cloned()
+            .collect()
+    }
+
+    /// Handle soft delete namespace command
+    ///
+    /// Marks every live config of the namespace as deleted at `deleted_at`,
+    /// the time the command was proposed. They stay on disk and can be
+    /// restored with `UndeleteNamespace` until `delete_after_days` have
+    /// passed, after which the leader proposes `PurgeExpiredConfigs`.
+    pub(crate) async fn handle_soft_delete_namespace(
+        &self,
+        namespace: &ConfigNamespace,
+        delete_after_days: u8,
+        deleted_at: &DateTime<Utc>,
+    ) -> Result<ClientWriteResponse> {
+        let now = *deleted_at;
+        let purge_at = now + ChronoDuration::days(i64::from(delete_after_days));
+
+        let mut deleted = Vec::new();
+        {
+            let mut configs = self.configurations.write().await;
+            for (key, config) in configs.iter_mut() {
+                if config.namespace != *namespace || config.is_deleted() {
+                    continue;
+                }
+                config.deleted_at = Some(now);
+                config.purge_at = Some(purge_at);
+                self.persist_config(key, config).await?;
+                deleted.push(config.clone());
+            }
+        }
+
+        if deleted.is_empty() {
+            return Ok(Self::create_error_response(format!(
+                "No configurations found in namespace {}",
+                namespace
+            )));
+        }
+
+        for config in &deleted {
+            self.notify_change(ConfigChangeEvent {
+                config_id: config.id,
+                namespace: config.namespace.clone(),
+                name: config.name.clone(),
+                version_id: 0,
+                change_type: ConfigChangeType::Deleted,
+            });
+        }
+
+        Ok(Self::create_success_response(
+            format!(
+                "Soft-deleted {} configurations in namespace {}",
+                deleted.len(),
+                namespace
+            ),
+            Some(serde_json::json!({
+                "namespace": namespace,
+                "deleted_count": deleted.len(),
+                "purge_at": purge_at
+            })),
+        ))
+    }
+
+    /// Handle undelete namespace command
+    ///
+    /// Restores the soft-deleted configs of the namespace whose recovery
+    /// window has not expired at `now`, the time the command was proposed.
+    pub(crate) async fn handle_undelete_namespace(
+        &self,
+        namespace: &ConfigNamespace,
+        now: &DateTime<Utc>,
+    ) -> Result<ClientWriteResponse> {
+        let mut restored = Vec::new();
+        {
+            let mut configs = self.configurations.write().await;
+            for (key, config) in configs.iter_mut() {
+                if config.namespace != *namespace || !is_recoverable(config, *now) {
+                    continue;
+                }
+                config.deleted_at = None;
+                config.purge_at = None;
+                self.persist_config(key, config).await?;
+                restored.push(config.clone());
+            }
+        }
+
+        if restored.is_empty() {
+            return Ok(Self::create_error_response(format!(
+                "No recoverable configurations found in namespace {}",
+                namespace
+            )));
+        }
+
+        for config in &restored {
+            self.notify_change(ConfigChangeEvent {
+                config_id: config.id,
+                namespace: config.namespace.clone(),
+                name: config.name.clone(),
+                version_id: config.latest_version_id,
+                change_type: ConfigChangeType::Created,
+            });
+        }
+
+        Ok(Self::create_success_response(
+            format!(
+                "Restored {} configurations in namespace {}",
+                restored.len(),
+                namespace
+            ),
+            Some(serde_json::json!({
+                "namespace": namespace,
+                "restored_count": restored.len()
+            })),
+        ))
+    }
+
+    /// Check if any soft-deleted config's recovery window ended at or before `now`
+    pub async fn has_expired_configs(&self, now: DateTime<Utc>) -> bool {
+        !expired_config_ids(&self.configurations, now).await.is_empty()
+    }
+
+    /// Handle purge expired configs command
+    ///
+    /// Permanently deletes the soft-deleted configs whose recovery window
+    /// ended at or before `now`, the leader's clock when it proposed the
+    /// command, so every replica purges the same configs.
+    pub(crate) async fn handle_purge_expired_configs(
+        &self,
+        now: &DateTime<Utc>,
+    ) -> Result<ClientWriteResponse> {
+        let mut purged = 0;
+        for config_id in expired_config_ids(&self.configurations, *now).await {
+            let response = self.handle_delete_config(&config_id).await?;
+            if response.success {
+                purged += 1;
+                info!("Purged soft-deleted config {}", config_id);
+            } else {
+                warn!("Failed to purge config {}: {}", config_id, response.message);
+            }
+        }
+
+        Ok(Self::create_success_response(
+            format!("Purged {} soft-deleted configurations", purged),
+            Some(serde_json::json!({ "purged": purged })),
+        ))
+    }
+}
+
+/// Check if a soft-deleted config can still be restored at `now`
+fn is_recoverable(config: &Config, now: DateTime<Utc>) -> bool {
+    config.is_deleted() && config.purge_at.is_none_or(|at| at > now)
+}
+
+/// IDs of soft-deleted configs whose recovery window ended at or before `now`
+async fn expired_config_ids(
+    configurations: &RwLock<BTreeMap<ConfigKey, Config>>,
+    now: DateTime<Utc>,
+) -> Vec<u64> {
+    configurations
+        .read()
+        .await
+        .values()
+        .filter(|config| config.is_deleted() && !is_recoverable(config, now))
+        .map(|config| config.id)
+        .collect()
+}
+
+#[cfg(test)]
+#[path = "soft_delete_tests.rs"]
+mod tests;
@@ -0,0 +1,180 @@
+use super::*;
+use tempfile::tempdir;
+
+fn namespace() -> ConfigNamespace {
+    ConfigNamespace {
+        tenant: "tenant".to_string(),
+        app: "app".to_string(),
+        env: "prod".to_string(),
+    }
+}
+
+async fn create_config(store: &Store, name: &str) -> u64 {
+    let response = store
+        .apply_command(&RaftCommand::CreateConfig {
+            namespace: namespace(),
+            name: name.to_string(),
+            content: b"{\"v\":1}".to_vec(),
+            format: ConfigFormat::Json,
+            schema: None,
+            creator_id: 1,
+            description: "v1".to_string(),
+        })
+        .await
+        .unwrap();
+    assert!(response.success, "{}", response.message);
+    response.config_id.unwrap()
+}
+
+async fn soft_delete(store: &Store, delete_after_days: u8) -> ClientWriteResponse {
+    store
+        .apply_command(&RaftCommand::SoftDeleteNamespace {
+            namespace: namespace(),
+            delete_after_days,
+            deleted_at: Utc::now(),
+        })
+        .await
+        .unwrap()
+}
+
+async fn undelete(store: &Store, now: DateTime<Utc>) -> ClientWriteResponse {
+    store
+        .apply_command(&RaftCommand::UndeleteNamespace {
+            namespace: namespace(),
+            now,
+        })
+        .await
+        .unwrap()
+}
+
+/// Apply `PurgeExpiredConfigs` as the leader would and return how many configs it purged
+async fn purge_expired(store: &Store, now: DateTime<Utc>) -> u64 {
+    let response = store
+        .apply_command(&RaftCommand::PurgeExpiredConfigs { now })
+        .await
+        .unwrap();
+    assert!(response.success, "{}", response.message);
+    response.data.unwrap()["purged"].as_u64().unwrap()
+}
+
+#[tokio::test]
+async fn test_soft_deleted_configs_are_hidden() {
+    let dir = tempdir().unwrap();
+    let (store, _rx) = Store::new(dir.path()).await.unwrap();
+    create_config(&store, "a.json").await;
+    create_config(&store, "b.json").await;
+
+    assert!(store
+        .get_published_config(&namespace(), "a.json", &BTreeMap::new())
+        .await
+        .is_some());
+
+    let response = soft_delete(&store, 7).await;
+    assert!(response.success, "{}", response.message);
+
+    assert!(store
+        .get_published_config(&namespace(), "a.json", &BTreeMap::new())
+        .await
+        .is_none());
+    assert!(store.list_configs_in_namespace(&namespace()).await.is_empty());
+
+    let including_deleted = store
+        .list_configs_in_namespace_including_deleted(&namespace())
+        .await;
+    assert_eq!(including_deleted.len(), 2);
+    assert!(including_deleted.iter().all(|config| config.is_deleted()));
+}
+
+#[tokio::test]
+async fn test_undelete_within_recovery_window() {
+    let dir = tempdir().unwrap();
+    let (store, _rx) = Store::new(dir.path()).await.unwrap();
+    create_config(&store, "a.json").await;
+
+    assert!(soft_delete(&store, 7).await.success);
+    let response = undelete(&store, Utc::now()).await;
+    assert!(response.success, "{}", response.message);
+
+    assert!(store
+        .get_published_config(&namespace(), "a.json", &BTreeMap::new())
+        .await
+        .is_some());
+    assert_eq!(store.list_configs_in_namespace(&namespace()).await.len(), 1);
+
+    // Nothing is left for the GC to purge
+    assert_eq!(purge_expired(&store, Utc::now()).await, 0);
+}
+
+#[tokio::test]
+async fn test_undelete_after_recovery_window_fails_and_gc_purges() {
+    let dir = tempdir().unwrap();
+    let (store, _rx) = Store::new(dir.path()).await.unwrap();
+    let config_id = create_config(&store, "a.json").await;
+
+    assert!(soft_delete(&store, 7).await.success);
+
+    // Still recoverable before the window ends
+    assert!(!store.has_expired_configs(Utc::now()).await);
+    assert_eq!(purge_expired(&store, Utc::now()).await, 0);
+
+    let after_window = Utc::now() + ChronoDuration::days(8);
+    assert!(store.has_expired_configs(after_window).await);
+    assert_eq!(purge_expired(&store, after_window).await, 1);
+    assert!(store.get_config_meta(config_id).await.is_none());
+    assert!(store
+        .list_configs_in_namespace_including_deleted(&namespace())
+        .await
+        .is_empty());
+
+    assert!(!undelete(&store, Utc::now()).await.success);
+}
+
+#[tokio::test]
+async fn test_expired_window_cannot_be_undeleted() {
+    let dir = tempdir().unwrap();
+    let (store, _rx) = Store::new(dir.path()).await.unwrap();
+    create_config(&store, "a.json").await;
+
+    // A zero-day window expires immediately
+    assert!(soft_delete(&store, 0).await.success);
+    let response = undelete(&store, Utc::now()).await;
+    assert!(!response.success);
+    assert!(store.list_configs_in_namespace(&namespace()).await.is_empty());
+}
+
+#[tokio::test]
+async fn test_recovery_window_uses_the_proposed_time() {
+    let dir = tempdir().unwrap();
+    let (store, _rx) = Store::new(dir.path()).await.unwrap();
+    create_config(&store, "a.json").await;
+    assert!(soft_delete(&store, 7).await.success);
+
+    // A command proposed after the window ends fails, whatever this node's clock says
+    let response = undelete(&store, Utc::now() + ChronoDuration::days(8)).await;
+    assert!(!response.success);
+    assert!(undelete(&store, Utc::now()).await.success);
+}
+
+#[tokio::test]
+async fn test_purged_config_stays_gone_after_reload() {
+    let dir = tempdir().unwrap();
+    {
+        let (store, _rx) = Store::new(dir.path()).await.unwrap();
+        create_config(&store, "a.json").await;
+        assert!(soft_delete(&store, 0).await.success);
+        assert_eq!(purge_expired(&store, Utc::now()).await, 1);
+    }
+
+    let (store, _rx) = Store::new(dir.path()).await.unwrap();
+    assert!(store
+        .list_configs_in_namespace_including_deleted(&namespace())
+        .await
+        .is_empty());
+}
+
+#[tokio::test]
+async fn test_soft_delete_of_empty_namespace_fails() {
+    let dir = tempdir().unwrap();
+    let (store, _rx) = Store::new(dir.path()).await.unwrap();
+    assert!(!soft_delete(&store, 7).await.success);
+}
@@ -0,0 +1,82 @@
+use crate::error::{ConfluxError, Result};
+use super::types::Store;
+use rocksdb::WriteBatch;
+use std::cell::RefCell;
+use std::future::Future;
+
+tokio::task_local! {
+    /// RocksDB writes of the command being applied on this task, not yet written
+    static STAGED_WRITES: RefCell<WriteBatch>;
+}
+
+/// Drop the writes staged so far on this task
+///
+/// Does nothing when no writes are being staged.
+pub(crate) fn discard_staged_writes() {
+    let _ = STAGED_WRITES.try_with(|batch| batch.borrow_mut().clear());
+}
+
+impl Store {
+    /// Add writes to the batch staged on this task, or write them right away
+    ///
+    /// Inside [`Store::staged`] the writes only reach RocksDB together with
+    /// everything else staged by the same command.
+    pub(crate) fn write_with(
+        &self,
+        stage: impl FnOnce(&mut WriteBatch) -> Result<()>,
+    ) -> Result<()> {
+        let mut stage = Some(stage);
+        if let Ok(result) = STAGED_WRITES.try_with(|batch| {
+            stage
+                .take()
+                .map_or(Ok(()), |stage| stage(&mut batch.borrow_mut()))
+        }) {
+            return result;
+        }
+
+        let mut batch = WriteBatch::default();
+        if let Some(stage) = stage {
+            stage(&mut batch)?;
+        }
+        self.commit_batch(batch)
+    }
+
+    /// Run `apply` with every write made through [`Store::write_with`] staged
+    /// into one batch, written atomically once `apply` succeeds
+    ///
+    /// When `apply` fails the staged writes are dropped. Called while writes
+    /// are already being staged on this task, `apply` joins that batch and
+    /// the outer caller writes it.
+    pub(crate) async fn staged<T>(&self, apply: impl Future<Output = Result<T>>) -> Result<T> {
+        if STAGED_WRITES.try_with(|_| ()).is_ok() {
+            return apply.await;
+        }
+
+        let (result, batch) = STAGED_WRITES
+            .scope(RefCell::new(WriteBatch::default()), async {
+                let result = apply.await;
+                (result, STAGED_WRITES.with(RefCell::take))
+            })
+            .await;
+        let result = result?;
+        self.commit_batch(batch)?;
+        Ok(result)
+    }
+
+    /// Run `apply` with every write made through [`Store::write_with`] staged
+    /// into a batch that is dropped afterwards, so nothing reaches RocksDB
+    pub(crate) async fn without_writes<T>(&self, apply: impl Future<Output = T>) -> T {
+        STAGED_WRITES
+            .scope(RefCell::new(WriteBatch::default()), apply)
+            .await
+    }
+
+    fn commit_batch(&self, batch: WriteBatch) -> Result<()> {
+        if batch.is_empty() {
+            return Ok(());
+        }
+        self.db
+            .write(batch)
+            .map_err(|e| ConfluxError::storage(format!("Failed to write batch: {}", e)))
+    }
+}
@@ -1,35 +1,75 @@
-use crate::error::Result;
+use crate::config::{CompactionStyle, CompressionType, StorageConfig};
+use crate::error::{ConfluxError, Result};
 use super::constants::*;
-use super::types::{Store, StateChangeEvent};
-use rocksdb::{ColumnFamilyDescriptor, Options as RocksDbOptions, DB};
+use super::inheritance::EnvInheritance;
+use super::limits::ContentLimitRegistry;
+use super::log_codec::LogCodec;
+use super::read_cache::PublishedConfigCache;
+use super::wal::{CommandWal, WalStats, WAL_DIR, WAL_MAX_FILE_BYTES};
+use super::types::{ConfluxSnapshot, Store, StateChangeEvent};
+use crate::raft::types::{ClientWriteResponse, RaftCommand};
+use dashmap::DashMap;
+use rocksdb::{
+    BlockBasedOptions, Cache, ColumnFamilyDescriptor, DBCompactionStyle, DBCompressionType,
+    Options as RocksDbOptions, DB,
+};
 use std::collections::BTreeMap;
 use std::path::Path;
+use std::sync::atomic::AtomicBool;
 use std::sync::{Arc, Mutex};
-use tokio::sync::{broadcast, RwLock, mpsc};
+use tokio::sync::{broadcast, oneshot, RwLock, mpsc};
+use tracing::{debug, info, warn};
+
 impl Store {
-    /// Create a new Store instance with RocksDB backend
+    /// Create a new Store instance with RocksDB backend and default tuning
     /// Returns the store and the event receiver for state machine communication
     pub async fn new<P: AsRef<Path>>(path: P) -> Result<(Self, mpsc::Receiver<StateChangeEvent>)> {
+        Self::open(path, &StorageConfig::default()).await
+    }
+
+    /// Create a new Store instance with RocksDB tuned by `storage`
+    ///
+    /// The block cache size, write buffers, open file limit, compaction style
+    /// and compression of `storage` are applied to every column family; its
+    /// `data_dir` is ignored in favour of `path`.
+    pub async fn open<P: AsRef<Path>>(
+        path: P,
+        storage: &StorageConfig,
+    ) -> Result<(Self, mpsc::Receiver<StateChangeEvent>)> {
         let (change_notifier, _) = broadcast::channel(1000);
 
         // Create RocksDB options
-        let mut opts = RocksDbOptions::default();
+        let mut opts = rocksdb_options(storage);
         opts.create_if_missing(true);
         opts.create_missing_column_families(true);
+        info!(
+            "Opening RocksDB with max_open_files={}, block_cache={}MB, write_buffer={}MB x {}, \
+             compaction={:?}, compression={:?}",
+            storage.max_open_files,
+            storage.cache_size_mb,
+            storage.write_buffer_size_mb,
+            storage.max_write_buffer_number,
+            storage.compaction_style,
+            storage.compression
+        );
 
-        // Define column families
-        let cfs = vec![
-            ColumnFamilyDescriptor::new(CF_CONFIGS, RocksDbOptions::default()),
-            ColumnFamilyDescriptor::new(CF_VERSIONS, RocksDbOptions::default()),
-            ColumnFamilyDescriptor::new(CF_LOGS, RocksDbOptions::default()),
-            ColumnFamilyDescriptor::new(CF_META, RocksDbOptions::default()),
-        ];
+        // Define column families, sharing the tuned options and block cache
+        let cfs = COLUMN_FAMILIES
+            .into_iter()
+            .map(|name| ColumnFamilyDescriptor::new(name, opts.clone()));
 
         // Open database
-        let db = DB::open_cf_descriptors(&opts, path, cfs).map_err(|e| {
+        let db = DB::open_cf_descriptors(&opts, path.as_ref(), cfs).map_err(|e| {
             crate::error::ConfluxError::storage(format!("Failed to open RocksDB: {}", e))
         })?;
 
+        // Commands journalled but not applied before a crash are replayed below
+        let (wal, unapplied) = CommandWal::open(
+            path.as_ref().join(WAL_DIR),
+            WAL_MAX_FILE_BYTES,
+            read_wal_applied_sequence(&db)?,
+        )?;
+
         // 创建事件通道用于与状态机通信
         let (event_sender, event_receiver) = mpsc::channel(1000);
 
@@ -41,18 +81,215 @@ impl Store {
             next_config_id: Arc::new(RwLock::new(1)),
             change_notifier: Arc::new(change_notifier),
             logs: Arc::new(RwLock::new(BTreeMap::new())),
+            log_codec: Arc::new(LogCodec::default()),
             last_purged_log_id: Arc::new(RwLock::new(None)),
             vote: Arc::new(RwLock::new(None)),
             // 移除 state_machine 字段，使用事件通信
             current_snapshot: Arc::new(RwLock::new(None)),
             snapshot_idx: Arc::new(Mutex::new(0)),
             event_sender: Some(event_sender),
+            content_limits: Arc::new(ContentLimitRegistry::default()),
+            published_cache: Arc::new(PublishedConfigCache::default()),
+            env_inheritance: Arc::new(EnvInheritance::default()),
+            verify_chain_on_read: Arc::new(AtomicBool::new(storage.verify_version_chain)),
+            wal: Arc::new(wal),
+            apply_lock: Arc::new(tokio::sync::Mutex::new(())),
+            tenant_usage: Arc::new(DashMap::new()),
         };
 
         // Load existing data from RocksDB into memory cache
         store.load_from_disk().await?;
+        store.replay_wal(unapplied).await?;
 
         Ok((store, event_receiver))
     }
 
+    /// Check that the RocksDB backend can still be read
+    pub fn check_health(&self) -> Result<()> {
+        let cf_meta = self
+            .db
+            .cf_handle(CF_META)
+            .ok_or_else(|| ConfluxError::storage("Meta column family not found"))?;
+        self.db
+            .get_cf(cf_meta, NEXT_CONFIG_ID_KEY)
+            .map(|_| ())
+            .map_err(|e| ConfluxError::storage(format!("RocksDB read failed: {}", e)))
+    }
+
+    /// Compress the Raft log entries carrying client requests with zstd
+    ///
+    /// Applies to entries appended from now on; entries already in the log
+    /// keep their encoding and stay readable.
+    pub fn set_log_compression(&self, enabled: bool) {
+        self.log_codec.set_compression(enabled);
+    }
+
+    /// Bytes of Raft log entries as stored and before compression, as
+    /// `(compressed_bytes, uncompressed_bytes)`
+    pub fn log_compression_stats(&self) -> (u64, u64) {
+        self.log_codec.stats()
+    }
+
+    /// File count, size and last sequence of the command write-ahead log
+    pub fn wal_stats(&self) -> WalStats {
+        self.wal.stats()
+    }
+
+    /// Flush and compact every column family, then delete the WAL files
+    /// whose commands are now durable in RocksDB
+    ///
+    /// Returns the number of deleted WAL files.
+    pub fn compact_storage(&self) -> Result<usize> {
+        for name in COLUMN_FAMILIES {
+            let cf = column_family(&self.db, name)?;
+            self.db
+                .flush_cf(cf)
+                .map_err(|e| ConfluxError::storage(format!("Failed to flush {}: {}", name, e)))?;
+        }
+        compact_column_families(&self.db, &COLUMN_FAMILIES)?;
+        self.wal.prune()
+    }
+
+    /// Compact the whole key range of one column family, or of all of them
+    ///
+    /// Runs on the blocking thread pool, since a full compaction can take
+    /// long enough to stall the async runtime.
+    pub async fn compact_range(&self, cf: Option<&str>) -> Result<()> {
+        let names = match cf {
+            Some(name) => vec![COLUMN_FAMILIES
+                .into_iter()
+                .find(|known| *known == name)
+                .ok_or_else(|| {
+                    ConfluxError::validation(format!("Unknown column family: {}", name))
+                })?],
+            None => COLUMN_FAMILIES.to_vec(),
+        };
+
+        let db = self.db.clone();
+        tokio::task::spawn_blocking(move || compact_column_families(&db, &names))
+            .await
+            .map_err(|e| ConfluxError::internal(format!("Compaction task failed: {}", e)))?
+    }
+
+    /// Apply the commands a crash left journalled but unapplied
+    async fn replay_wal(&self, unapplied: Vec<(u64, RaftCommand)>) -> Result<()> {
+        if unapplied.is_empty() {
+            return Ok(());
+        }
+        info!("Replaying {} commands from the write-ahead log", unapplied.len());
+
+        for (sequence, command) in unapplied {
+            match self.apply_journalled(sequence, &command).await {
+                Ok(response) if !response.success => warn!(
+                    "Replayed WAL command {} was rejected: {}",
+                    sequence, response.message
+                ),
+                Ok(_) => {}
+                Err(e) => warn!("Replaying WAL command {} failed: {}", sequence, e),
+            }
+        }
+        Ok(())
+    }
+
+    /// Size in bytes of the most recently built snapshot (0 if none)
+    pub async fn current_snapshot_size(&self) -> u64 {
+        self.current_snapshot
+            .read()
+            .await
+            .as_ref()
+            .map(|snapshot| snapshot.size_bytes)
+            .unwrap_or(0)
+    }
+
+    /// Record the most recently built snapshot
+    pub(crate) async fn set_current_snapshot(&self, snapshot: ConfluxSnapshot) {
+        *self.current_snapshot.write().await = Some(snapshot);
+    }
+
+    /// Submit a command to the state machine manager and wait for its result
+    pub(crate) async fn submit_command(&self, command: RaftCommand) -> Result<ClientWriteResponse> {
+        let sender = self
+            .event_sender
+            .as_ref()
+            .ok_or_else(|| ConfluxError::internal("State machine channel not available"))?;
+
+        let (response_sender, response_receiver) = oneshot::channel();
+        sender
+            .send(StateChangeEvent::CommandApplied {
+                command,
+                response_sender,
+            })
+            .await
+            .map_err(|_| ConfluxError::internal("State machine channel closed"))?;
+
+        response_receiver
+            .await
+            .map_err(|_| ConfluxError::internal("State machine dropped response"))?
+            .map_err(ConfluxError::internal)
+    }
+}
+
+fn column_family<'a>(db: &'a DB, name: &str) -> Result<&'a rocksdb::ColumnFamily> {
+    db.cf_handle(name)
+        .ok_or_else(|| ConfluxError::storage(format!("Column family {} not found", name)))
+}
+
+/// Sequence of the last WAL command whose writes are in RocksDB (0 if none)
+fn read_wal_applied_sequence(db: &DB) -> Result<u64> {
+    let cf = column_family(db, CF_META)?;
+    let value = db
+        .get_pinned_cf(cf, WAL_APPLIED_SEQUENCE_KEY)
+        .map_err(|e| ConfluxError::storage(format!("Failed to read WAL sequence: {}", e)))?;
+    Ok(value
+        .and_then(|v| <[u8; 8]>::try_from(v.as_ref()).ok())
+        .map_or(0, u64::from_be_bytes))
 }
+
+fn compact_column_families(db: &DB, names: &[&str]) -> Result<()> {
+    for &name in names {
+        db.compact_range_cf(column_family(db, name)?, None::<&[u8]>, None::<&[u8]>);
+        debug!("Compacted column family {}", name);
+    }
+    Ok(())
+}
+
+/// Total size of the SST files of every column family
+pub(crate) fn sst_files_size(db: &DB) -> Result<u64> {
+    let mut total = 0;
+    for name in COLUMN_FAMILIES {
+        total += db
+            .property_int_value_cf(column_family(db, name)?, "rocksdb.total-sst-files-size")
+            .map_err(|e| ConfluxError::storage(format!("Failed to read SST size: {}", e)))?
+            .unwrap_or(0);
+    }
+    Ok(total)
+}
+
+/// RocksDB options with the tuning of `storage` applied
+fn rocksdb_options(storage: &StorageConfig) -> RocksDbOptions {
+    let mut block_opts = BlockBasedOptions::default();
+    let cache = Cache::new_lru_cache(storage.cache_size_mb * 1024 * 1024);
+    block_opts.set_block_cache(&cache);
+
+    let mut opts = RocksDbOptions::default();
+    opts.set_block_based_table_factory(&block_opts);
+    opts.set_max_open_files(storage.max_open_files);
+    opts.set_write_buffer_size(storage.write_buffer_size_mb * 1024 * 1024);
+    opts.set_max_write_buffer_number(storage.max_write_buffer_number);
+    opts.set_compaction_style(match storage.compaction_style {
+        CompactionStyle::Level => DBCompactionStyle::Level,
+        CompactionStyle::Universal => DBCompactionStyle::Universal,
+        CompactionStyle::Fifo => DBCompactionStyle::Fifo,
+    });
+    opts.set_compression_type(match storage.compression {
+        CompressionType::None => DBCompressionType::None,
+        CompressionType::Snappy => DBCompressionType::Snappy,
+        CompressionType::Lz4 => DBCompressionType::Lz4,
+        CompressionType::Zstd => DBCompressionType::Zstd,
+    });
+    opts
+}
+
+#[cfg(test)]
+#[path = "store_tests.rs"]
+mod tests;
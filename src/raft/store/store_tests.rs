@@ -0,0 +1,198 @@
+use super::*;
+use tempfile::tempdir;
+
+fn storage(cache_size_mb: usize) -> StorageConfig {
+    StorageConfig {
+        cache_size_mb,
+        write_buffer_size_mb: 1,
+        max_write_buffer_number: 2,
+        ..StorageConfig::default()
+    }
+}
+
+#[tokio::test]
+async fn test_open_with_tiny_cache() {
+    for cache_size_mb in [0, 1] {
+        let temp_dir = tempdir().unwrap();
+        let (store, _rx) = Store::open(temp_dir.path(), &storage(cache_size_mb))
+            .await
+            .unwrap();
+        store.check_health().unwrap();
+    }
+}
+
+#[tokio::test]
+async fn test_open_with_compaction_and_compression() {
+    let cases = [
+        (CompactionStyle::Level, CompressionType::None, 1000),
+        (CompactionStyle::Universal, CompressionType::Zstd, 1000),
+        (CompactionStyle::Level, CompressionType::Snappy, 1000),
+        (CompactionStyle::Fifo, CompressionType::Lz4, -1),
+    ];
+
+    for (compaction_style, compression, max_open_files) in cases {
+        let temp_dir = tempdir().unwrap();
+        let config = StorageConfig {
+            compaction_style,
+            compression,
+            max_open_files,
+            ..storage(8)
+        };
+        let (store, _rx) = Store::open(temp_dir.path(), &config).await.unwrap();
+        store.check_health().unwrap();
+        drop(store);
+
+        // Data written with the tuned options is readable after reopening
+        Store::open(temp_dir.path(), &config).await.unwrap();
+    }
+}
+
+#[test]
+fn test_storage_config_tunables_deserialize() {
+    let config: StorageConfig = serde_json::from_value(serde_json::json!({
+        "data_dir": "/tmp/conflux",
+        "max_open_files": -1,
+        "cache_size_mb": 16,
+        "write_buffer_size_mb": 4,
+        "max_write_buffer_number": 2,
+        "compaction_style": "universal",
+        "compression": "zstd",
+    }))
+    .unwrap();
+    assert_eq!(config.compaction_style, CompactionStyle::Universal);
+    assert_eq!(config.compression, CompressionType::Zstd);
+
+    // Older config files without the tunables keep the defaults
+    let config: StorageConfig = serde_json::from_value(serde_json::json!({
+        "data_dir": "/tmp/conflux",
+        "max_open_files": 1000,
+        "cache_size_mb": 16,
+        "write_buffer_size_mb": 4,
+        "max_write_buffer_number": 2,
+    }))
+    .unwrap();
+    assert_eq!(config.compaction_style, CompactionStyle::Level);
+    assert_eq!(config.compression, CompressionType::Lz4);
+}
+
+fn create_config_command() -> RaftCommand {
+    RaftCommand::CreateConfig {
+        namespace: crate::raft::types::ConfigNamespace {
+            tenant: "tenant".to_string(),
+            app: "app".to_string(),
+            env: "prod".to_string(),
+        },
+        name: "db.json".to_string(),
+        content: br#"{"pool":10}"#.to_vec(),
+        format: crate::raft::types::ConfigFormat::Json,
+        schema: None,
+        creator_id: 1,
+        description: "initial".to_string(),
+    }
+}
+
+fn create_version_command(config_id: u64) -> RaftCommand {
+    RaftCommand::CreateVersion {
+        config_id,
+        content: br#"{"pool":20}"#.to_vec(),
+        format: None,
+        creator_id: 1,
+        description: "bump pool".to_string(),
+    }
+}
+
+#[tokio::test]
+async fn test_wal_replays_command_interrupted_by_crash() {
+    let temp_dir = tempdir().unwrap();
+    let config_id = {
+        let (store, _rx) = Store::new(temp_dir.path()).await.unwrap();
+        let response = store.apply_command(&create_config_command()).await.unwrap();
+        let config_id = response.config_id.unwrap();
+
+        // Crash after the command is journalled but before it is applied
+        store.wal.append(&create_version_command(config_id)).unwrap();
+        assert_eq!(store.list_config_versions(config_id).await.len(), 1);
+        config_id
+    };
+
+    let (store, _rx) = Store::new(temp_dir.path()).await.unwrap();
+    let versions = store.list_config_versions(config_id).await;
+    assert_eq!(versions.len(), 2);
+    assert_eq!(versions[1].content, br#"{"pool":20}"#.to_vec());
+    assert_eq!(store.wal_stats().last_sequence, 2);
+    drop(store);
+
+    // The replayed command's sequence was stored with its writes, so it is not replayed again
+    let (store, _rx) = Store::new(temp_dir.path()).await.unwrap();
+    assert_eq!(store.list_config_versions(config_id).await.len(), 2);
+}
+
+#[tokio::test]
+async fn test_wal_never_replays_or_reuses_stored_sequences() {
+    let temp_dir = tempdir().unwrap();
+    {
+        let (store, _rx) = Store::new(temp_dir.path()).await.unwrap();
+        let response = store.apply_command(&create_config_command()).await.unwrap();
+        store
+            .apply_command(&create_version_command(response.config_id.unwrap()))
+            .await
+            .unwrap();
+    }
+
+    // Both commands are still journalled, but their writes are in RocksDB
+    let (store, _rx) = Store::new(temp_dir.path()).await.unwrap();
+    assert_eq!(store.list_config_versions(1).await.len(), 2);
+    drop(store);
+
+    // Without the WAL files, sequences continue after the stored one
+    std::fs::remove_dir_all(temp_dir.path().join(WAL_DIR)).unwrap();
+    let (store, _rx) = Store::new(temp_dir.path()).await.unwrap();
+    assert_eq!(store.wal_stats().last_sequence, 2);
+    assert_eq!(store.list_config_versions(1).await.len(), 2);
+}
+
+#[tokio::test]
+async fn test_wal_stats_and_compaction() {
+    let temp_dir = tempdir().unwrap();
+    let (store, _rx) = Store::new(temp_dir.path()).await.unwrap();
+    assert_eq!(
+        store.wal_stats(),
+        WalStats {
+            file_count: 1,
+            size_bytes: 0,
+            last_sequence: 0,
+        }
+    );
+
+    let response = store.apply_command(&create_config_command()).await.unwrap();
+    store
+        .apply_command(&create_version_command(response.config_id.unwrap()))
+        .await
+        .unwrap();
+
+    let stats = store.wal_stats();
+    assert_eq!(stats.file_count, 1);
+    assert_eq!(stats.last_sequence, 2);
+    assert!(stats.size_bytes > 0);
+
+    // The active file is kept; nothing older exists yet
+    assert_eq!(store.compact_storage().unwrap(), 0);
+    assert_eq!(store.list_config_versions(1).await.len(), 2);
+}
+
+#[tokio::test]
+async fn test_compact_range() {
+    let temp_dir = tempdir().unwrap();
+    let (store, _rx) = Store::new(temp_dir.path()).await.unwrap();
+    store.apply_command(&create_config_command()).await.unwrap();
+    store.flush_to_disk().await.unwrap();
+
+    store.compact_range(None).await.unwrap();
+    store.compact_range(Some(CF_VERSIONS)).await.unwrap();
+    let error = store.compact_range(Some("unknown")).await.unwrap_err();
+    assert!(matches!(error, ConfluxError::Validation(_)));
+
+    // Compacted data is still readable
+    assert_eq!(store.list_config_versions(1).await.len(), 1);
+    assert!(store.get_storage_stats().await.unwrap().sst_size_bytes > 0);
+}
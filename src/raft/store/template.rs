@@ -0,0 +1,125 @@
+use crate::error::{ConfluxError, Result};
+use crate::raft::types::*;
+use super::types::Store;
+use regex::{Captures, Regex};
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::LazyLock;
+
+/// Matches `${NAME}` placeholders in config content
+static PLACEHOLDER: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\$\{([A-Za-z_][A-Za-z0-9_.\-]*)\}").expect("placeholder pattern is valid")
+});
+
+impl Store {
+    /// Get the published config with `${NAME}` placeholders substituted
+    ///
+    /// Substitution only affects the returned content: the stored version and
+    /// its `content_hash` (computed over the template) are left untouched.
+    /// Placeholders without a matching variable are kept as-is unless `strict`
+    /// is set, in which case an error is returned. Binary configs are returned
+    /// unchanged.
+    pub async fn get_rendered_config(
+        &self,
+        namespace: &ConfigNamespace,
+        name: &str,
+        client_labels: &BTreeMap<String, String>,
+        template_variables: &HashMap<String, String>,
+        strict: bool,
+    ) -> Result<Option<(Config, ConfigVersion)>> {
+        let Some((config, mut version)) =
+            self.get_published_config(namespace, name, client_labels).await
+        else {
+            return Ok(None);
+        };
+
+        if version.format == ConfigFormat::Binary {
+            return Ok(Some((config, version)));
+        }
+        if let Cow::Owned(rendered) = render_template(&version.content, template_variables, strict)? {
+            version.content = rendered;
+        }
+        Ok(Some((config, version)))
+    }
+}
+
+/// Substitute `${NAME}` placeholders in `content` with values from `variables`
+///
+/// Variable values may themselves contain placeholders, which are expanded
+/// recursively; circular references are rejected. Content that is not valid
+/// UTF-8 is returned unchanged.
+pub fn render_template<'a>(
+    content: &'a [u8],
+    variables: &HashMap<String, String>,
+    strict: bool,
+) -> Result<Cow<'a, [u8]>> {
+    if variables.is_empty() && !strict {
+        return Ok(Cow::Borrowed(content));
+    }
+    let Ok(text) = std::str::from_utf8(content) else {
+        return Ok(Cow::Borrowed(content));
+    };
+    if !PLACEHOLDER.is_match(text) {
+        return Ok(Cow::Borrowed(content));
+    }
+
+    let mut stack = Vec::new();
+    let rendered = expand(text, variables, strict, &mut stack)?;
+    Ok(Cow::Owned(rendered.into_bytes()))
+}
+
+/// Expand placeholders in `text`, tracking the chain of variables being expanded
+fn expand(
+    text: &str,
+    variables: &HashMap<String, String>,
+    strict: bool,
+    stack: &mut Vec<String>,
+) -> Result<String> {
+    let mut error = None;
+    let rendered = PLACEHOLDER.replace_all(text, |caps: &Captures| {
+        let name = &caps[1];
+        if error.is_some() {
+            return caps[0].to_string();
+        }
+
+        let Some(value) = variables.get(name) else {
+            if strict {
+                error = Some(ConfluxError::validation(format!(
+                    "undefined template variable: {}",
+                    name
+                )));
+            }
+            return caps[0].to_string();
+        };
+
+        if stack.iter().any(|entry| entry == name) {
+            let mut chain = stack.clone();
+            chain.push(name.to_string());
+            error = Some(ConfluxError::validation(format!(
+                "circular template variable reference: {}",
+                chain.join(" -> ")
+            )));
+            return caps[0].to_string();
+        }
+
+        stack.push(name.to_string());
+        let expanded = expand(value, variables, strict, stack);
+        stack.pop();
+        match expanded {
+            Ok(expanded) => expanded,
+            Err(e) => {
+                error = Some(e);
+                caps[0].to_string()
+            }
+        }
+    });
+
+    match error {
+        Some(e) => Err(e),
+        None => Ok(rendered.into_owned()),
+    }
+}
+
+#[cfg(test)]
+#[path = "template_tests.rs"]
+mod tests;
@@ -0,0 +1,116 @@
+use super::*;
+use tempfile::tempdir;
+
+fn vars(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+    pairs
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect()
+}
+
+fn render(content: &str, variables: &HashMap<String, String>, strict: bool) -> Result<String> {
+    render_template(content.as_bytes(), variables, strict)
+        .map(|rendered| String::from_utf8(rendered.into_owned()).unwrap())
+}
+
+#[test]
+fn test_substitutes_known_variables() {
+    let variables = vars(&[("DB_HOST", "localhost"), ("PORT", "5432")]);
+    assert_eq!(
+        render("url=postgres://${DB_HOST}:${PORT}/app", &variables, false).unwrap(),
+        "url=postgres://localhost:5432/app"
+    );
+}
+
+#[test]
+fn test_missing_variables_are_kept_unless_strict() {
+    let variables = vars(&[("DB_HOST", "localhost")]);
+    assert_eq!(
+        render("${DB_HOST}:${PORT}", &variables, false).unwrap(),
+        "localhost:${PORT}"
+    );
+
+    let err = render("${DB_HOST}:${PORT}", &variables, true).unwrap_err();
+    assert!(matches!(err, ConfluxError::Validation(ref msg) if msg.contains("PORT")));
+}
+
+#[test]
+fn test_no_op_substitution_borrows_content() {
+    let content = b"plain ${UNSET} content";
+    assert!(matches!(
+        render_template(content, &HashMap::new(), false).unwrap(),
+        Cow::Borrowed(_)
+    ));
+    assert!(matches!(
+        render_template(b"no placeholders", &vars(&[("A", "1")]), true).unwrap(),
+        Cow::Borrowed(_)
+    ));
+    // Non UTF-8 content is never rewritten
+    assert!(matches!(
+        render_template(&[0xff, 0xfe], &vars(&[("A", "1")]), true).unwrap(),
+        Cow::Borrowed(_)
+    ));
+}
+
+#[test]
+fn test_nested_variables_are_expanded() {
+    let variables = vars(&[("URL", "http://${HOST}:${PORT}"), ("HOST", "db"), ("PORT", "80")]);
+    assert_eq!(render("${URL}/x", &variables, true).unwrap(), "http://db:80/x");
+}
+
+#[test]
+fn test_circular_references_are_rejected() {
+    let variables = vars(&[("A", "${B}"), ("B", "${C}"), ("C", "${A}")]);
+    let err = render("value=${A}", &variables, false).unwrap_err();
+    assert!(err.to_string().contains("circular template variable reference: A -> B -> C -> A"));
+
+    let err = render("${SELF}", &vars(&[("SELF", "x${SELF}")]), false).unwrap_err();
+    assert!(err.to_string().contains("circular"));
+}
+
+#[tokio::test]
+async fn test_get_rendered_config_keeps_stored_content() {
+    let dir = tempdir().unwrap();
+    let (store, _rx) = Store::new(dir.path()).await.unwrap();
+    let namespace = ConfigNamespace {
+        tenant: "tenant".to_string(),
+        app: "app".to_string(),
+        env: "prod".to_string(),
+    };
+    let template = b"{\"host\":\"${DB_HOST}\"}".to_vec();
+    store
+        .apply_command(&RaftCommand::CreateConfig {
+            namespace: namespace.clone(),
+            name: "db.json".to_string(),
+            content: template.clone(),
+            format: ConfigFormat::Json,
+            schema: None,
+            creator_id: 1,
+            description: "template".to_string(),
+        })
+        .await
+        .unwrap();
+
+    let labels = BTreeMap::new();
+    let (_, version) = store
+        .get_rendered_config(&namespace, "db.json", &labels, &vars(&[("DB_HOST", "localhost")]), true)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(version.content, b"{\"host\":\"localhost\"}".to_vec());
+
+    // The stored template and its hash are unchanged
+    let (_, stored) = store.get_published_config(&namespace, "db.json", &labels).await.unwrap();
+    assert_eq!(stored.content, template);
+    assert_eq!(version.content_hash, stored.content_hash);
+
+    assert!(store
+        .get_rendered_config(&namespace, "db.json", &labels, &HashMap::new(), true)
+        .await
+        .is_err());
+    assert!(store
+        .get_rendered_config(&namespace, "missing.json", &labels, &HashMap::new(), true)
+        .await
+        .unwrap()
+        .is_none());
+}
@@ -0,0 +1,24 @@
+//! Fixtures shared by the store tests
+
+use super::types::{StateMachineManager, Store};
+use std::sync::Arc;
+use tempfile::{tempdir, TempDir};
+
+/// Create a store in a fresh temporary directory
+///
+/// The directory is returned alongside, as the store must not outlive it.
+pub(crate) async fn create_store() -> (Store, TempDir) {
+    let dir = tempdir().unwrap();
+    let (store, _rx) = Store::new(dir.path()).await.unwrap();
+    (store, dir)
+}
+
+/// Create a store whose state machine manager applies submitted commands
+pub(crate) async fn create_managed_store() -> (Arc<Store>, TempDir) {
+    let dir = tempdir().unwrap();
+    let (store, rx) = Store::new(dir.path()).await.unwrap();
+    let store = Arc::new(store);
+    let mut manager = StateMachineManager::new(store.clone(), rx);
+    tokio::spawn(async move { manager.run().await });
+    (store, dir)
+}
@@ -268,4 +268,323 @@ mod tests {
         assert!(default_release.is_default());
         assert!(default_release.matches(&BTreeMap::new()));
     }
+
+    fn client(instance: usize) -> BTreeMap<String, String> {
+        let mut labels = BTreeMap::new();
+        labels.insert("region".to_string(), "eu".to_string());
+        labels.insert(CANARY_CLIENT_LABEL.to_string(), format!("instance-{}", instance));
+        labels
+    }
+
+    #[test]
+    fn test_canary_release_selects_stable_share_of_clients() {
+        let mut canary = Release::new(BTreeMap::new(), 2, 1);
+        canary.canary_percent = Some(20);
+
+        let selected = |release: &Release| {
+            (0..10_000)
+                .filter(|i| release.selects_client(7, &client(*i)))
+                .collect::<Vec<_>>()
+        };
+        let cohort = selected(&canary);
+        assert!((1_500..=2_500).contains(&cohort.len()), "{}", cohort.len());
+
+        // Raising the percentage only adds clients
+        canary.canary_percent = Some(50);
+        let wider = selected(&canary);
+        assert!(cohort.iter().all(|i| wider.contains(i)));
+
+        // Clients without an identifier never get the canary
+        let mut anonymous = client(0);
+        anonymous.remove(CANARY_CLIENT_LABEL);
+        assert!(!canary.selects_client(7, &anonymous));
+        assert!(Release::default(1).selects_client(7, &anonymous));
+    }
+
+    #[tokio::test]
+    async fn test_canary_percent_routes_published_config() {
+        let (store, _temp_dir) = create_test_store().await;
+        let namespace = ConfigNamespace {
+            tenant: "test".to_string(),
+            app: "myapp".to_string(),
+            env: "prod".to_string(),
+        };
+
+        let response = store
+            .apply_command(&RaftCommand::CreateConfig {
+                namespace: namespace.clone(),
+                name: "app.json".to_string(),
+                content: br#"{"v":1}"#.to_vec(),
+                format: ConfigFormat::Json,
+                schema: None,
+                creator_id: 1,
+                description: "stable".to_string(),
+            })
+            .await
+            .unwrap();
+        let config_id = response.config_id.unwrap();
+        store
+            .apply_command(&RaftCommand::CreateVersion {
+                config_id,
+                content: br#"{"v":2}"#.to_vec(),
+                format: None,
+                creator_id: 1,
+                description: "canary".to_string(),
+            })
+            .await
+            .unwrap();
+
+        let mut canary_labels = BTreeMap::new();
+        canary_labels.insert("region".to_string(), "eu".to_string());
+        let set_canary = |percent| RaftCommand::SetCanaryPercent {
+            config_id,
+            version_id: 2,
+            labels: canary_labels.clone(),
+            percent,
+        };
+        assert!(!store.apply_command(&set_canary(101)).await.unwrap().success);
+        assert!(store.apply_command(&set_canary(10)).await.unwrap().success);
+        // Adjusting the percentage updates the canary release in place
+        assert!(store.apply_command(&set_canary(30)).await.unwrap().success);
+
+        let config = store.get_config_meta(config_id).await.unwrap();
+        assert_eq!(config.releases.len(), 2);
+        assert!(config.get_default_release(&BTreeMap::new()).is_some_and(|r| r.version_id == 1));
+
+        let mut served_canary = 0;
+        for i in 0..10_000 {
+            let (_, version) = store
+                .get_published_config(&namespace, "app.json", &client(i))
+                .await
+                .unwrap();
+            if version.id == 2 {
+                served_canary += 1;
+            }
+        }
+        assert!((2_500..=3_500).contains(&served_canary), "{}", served_canary);
+
+        // Clients outside the canary labels keep the stable version
+        let mut other_region = client(0);
+        other_region.insert("region".to_string(), "us".to_string());
+        let (_, version) = store
+            .get_published_config(&namespace, "app.json", &other_region)
+            .await
+            .unwrap();
+        assert_eq!(version.id, 1);
+    }
+
+    #[tokio::test]
+    async fn test_release_weights_split_published_config() {
+        let (store, _temp_dir) = create_test_store().await;
+        let namespace = ConfigNamespace {
+            tenant: "test".to_string(),
+            app: "myapp".to_string(),
+            env: "prod".to_string(),
+        };
+
+        let response = store
+            .apply_command(&RaftCommand::CreateConfig {
+                namespace: namespace.clone(),
+                name: "app.json".to_string(),
+                content: br#"{"v":"a"}"#.to_vec(),
+                format: ConfigFormat::Json,
+                schema: None,
+                creator_id: 1,
+                description: "variant a".to_string(),
+            })
+            .await
+            .unwrap();
+        let config_id = response.config_id.unwrap();
+        store
+            .apply_command(&RaftCommand::CreateVersion {
+                config_id,
+                content: br#"{"v":"b"}"#.to_vec(),
+                format: None,
+                creator_id: 1,
+                description: "variant b".to_string(),
+            })
+            .await
+            .unwrap();
+
+        let set_weights = |weights: Vec<(u64, u32)>| RaftCommand::SetReleaseWeights {
+            config_id,
+            weights,
+        };
+        assert!(!store.apply_command(&set_weights(vec![])).await.unwrap().success);
+        assert!(!store.apply_command(&set_weights(vec![(1, 0), (2, 0)])).await.unwrap().success);
+        assert!(!store.apply_command(&set_weights(vec![(1, 1), (3, 1)])).await.unwrap().success);
+        assert!(!store.apply_command(&set_weights(vec![(1, 1), (1, 2)])).await.unwrap().success);
+        assert!(store.apply_command(&set_weights(vec![(1, 3), (2, 1)])).await.unwrap().success);
+        // Setting the weights again replaces the split instead of adding to it
+        assert!(store.apply_command(&set_weights(vec![(1, 1), (2, 1)])).await.unwrap().success);
+
+        let config = store.get_config_meta(config_id).await.unwrap();
+        assert_eq!(config.releases.len(), 2);
+
+        let mut served_b = 0;
+        for i in 0..10_000 {
+            let (_, version) = store
+                .get_published_config(&namespace, "app.json", &client(i))
+                .await
+                .unwrap();
+            if version.id == 2 {
+                served_b += 1;
+            }
+        }
+        assert!((4_500..=5_500).contains(&served_b), "{}", served_b);
+    }
+
+    #[tokio::test]
+    async fn test_rollout_percentage_through_release_rules() {
+        let (store, _temp_dir) = create_test_store().await;
+        let namespace = ConfigNamespace {
+            tenant: "test".to_string(),
+            app: "myapp".to_string(),
+            env: "prod".to_string(),
+        };
+
+        let response = store
+            .apply_command(&RaftCommand::CreateConfig {
+                namespace: namespace.clone(),
+                name: "app.json".to_string(),
+                content: br#"{"v":1}"#.to_vec(),
+                format: ConfigFormat::Json,
+                schema: None,
+                creator_id: 1,
+                description: "stable".to_string(),
+            })
+            .await
+            .unwrap();
+        let config_id = response.config_id.unwrap();
+        store
+            .apply_command(&RaftCommand::CreateVersion {
+                config_id,
+                content: br#"{"v":2}"#.to_vec(),
+                format: None,
+                creator_id: 1,
+                description: "rollout".to_string(),
+            })
+            .await
+            .unwrap();
+
+        let releases = |percent: u8| {
+            vec![
+                Release::default(1),
+                Release {
+                    canary_percent: Some(percent),
+                    ..Release::new(BTreeMap::new(), 2, 1)
+                },
+            ]
+        };
+        let update = |percent| RaftCommand::UpdateReleaseRules {
+            config_id,
+            releases: releases(percent),
+        };
+        assert!(!store.apply_command(&update(150)).await.unwrap().success);
+        assert!(store.apply_command(&update(20)).await.unwrap().success);
+
+        let mut rolled_out = Vec::new();
+        for i in 0..10_000 {
+            let (_, version) = store
+                .get_published_config(&namespace, "app.json", &client(i))
+                .await
+                .unwrap();
+            if version.id == 2 {
+                rolled_out.push(i);
+            }
+        }
+        assert!((1_500..=2_500).contains(&rolled_out.len()), "{}", rolled_out.len());
+
+        // Widening the rollout keeps every client that already had the new version
+        assert!(store.apply_command(&update(50)).await.unwrap().success);
+        for &i in &rolled_out {
+            let (_, version) = store
+                .get_published_config(&namespace, "app.json", &client(i))
+                .await
+                .unwrap();
+            assert_eq!(version.id, 2);
+        }
+
+        // Clients without an instance ID stay on the stable version
+        let (_, version) = store
+            .get_published_config(&namespace, "app.json", &BTreeMap::new())
+            .await
+            .unwrap();
+        assert_eq!(version.id, 1);
+    }
+
+    #[tokio::test]
+    async fn test_get_config_at_past_timestamps() {
+        let (store, _temp_dir) = create_test_store().await;
+        let namespace = ConfigNamespace {
+            tenant: "test".to_string(),
+            app: "myapp".to_string(),
+            env: "prod".to_string(),
+        };
+
+        let response = store
+            .apply_command(&RaftCommand::CreateConfig {
+                namespace: namespace.clone(),
+                name: "app.json".to_string(),
+                content: br#"{"v":1}"#.to_vec(),
+                format: ConfigFormat::Json,
+                schema: None,
+                creator_id: 1,
+                description: "v1".to_string(),
+            })
+            .await
+            .unwrap();
+        let config_id = response.config_id.unwrap();
+        for v in 2..=3 {
+            tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+            store
+                .apply_command(&RaftCommand::CreateVersion {
+                    config_id,
+                    content: format!(r#"{{"v":{}}}"#, v).into_bytes(),
+                    format: None,
+                    creator_id: 1,
+                    description: format!("v{}", v),
+                })
+                .await
+                .unwrap();
+        }
+        store
+            .apply_command(&RaftCommand::UpdateReleaseRules {
+                config_id,
+                releases: vec![Release::default(3)],
+            })
+            .await
+            .unwrap();
+
+        let versions = store.list_config_versions(config_id).await;
+        let created_at = |id: u64| versions.iter().find(|v| v.id == id).unwrap().created_at;
+        let labels = BTreeMap::new();
+        let version_at = |at| {
+            let store = store.clone();
+            let namespace = namespace.clone();
+            let labels = labels.clone();
+            async move {
+                store
+                    .get_config_at(&namespace, "app.json", at, &labels)
+                    .await
+                    .map(|(_, version)| version.id)
+            }
+        };
+
+        // Before the config existed
+        let before = created_at(1) - chrono::Duration::seconds(1);
+        assert_eq!(version_at(before).await, None);
+
+        // The released version did not exist yet: the newest version at the time
+        assert_eq!(version_at(created_at(1)).await, Some(1));
+        assert_eq!(version_at(created_at(2)).await, Some(2));
+        assert_eq!(
+            version_at(created_at(3) - chrono::Duration::milliseconds(1)).await,
+            Some(2)
+        );
+
+        // Once it existed, the released version
+        assert_eq!(version_at(created_at(3)).await, Some(3));
+        assert_eq!(version_at(chrono::Utc::now()).await, Some(3));
+    }
 }
@@ -1,16 +1,251 @@
 use crate::error::Result;
 use crate::raft::types::*;
-use super::types::Store;
+use super::quotas::TenantUsage;
+use super::staging::discard_staged_writes;
+use super::types::{ConfigChangeEvent, Store};
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::pin::Pin;
+use tracing::warn;
+
+tokio::task_local! {
+    /// Change events raised by the sub-commands of the transaction running on this task
+    static PENDING_CHANGES: RefCell<Vec<ConfigChangeEvent>>;
+}
+
+/// Check if a command may be part of a transaction
+///
+/// Only commands whose state lives in the configurations, versions and name
+/// index of the configs they name can be undone, so commands touching other column families
+/// (dependencies, approvals, schedules, schemas, webhooks) are rejected.
+pub(crate) fn is_transactional(command: &RaftCommand) -> bool {
+    matches!(
+        command,
+        RaftCommand::CreateConfig { .. }
+            | RaftCommand::UpdateConfig { .. }
+            | RaftCommand::CreateVersion { .. }
+            | RaftCommand::ReleaseVersion { .. }
+            | RaftCommand::UpdateReleaseRules { .. }
+            | RaftCommand::SetCanaryPercent { .. }
+            | RaftCommand::SetReleaseWeights { .. }
+            | RaftCommand::DeleteVersions { .. }
+            | RaftCommand::SetPrunePolicy { .. }
+            | RaftCommand::PruneVersions { .. }
+    )
+}
+
+/// Hold back a change event raised inside a transaction until it commits
+///
+/// Returns the event when no transaction is running on the current task.
+pub(crate) fn defer_change(event: ConfigChangeEvent) -> Option<ConfigChangeEvent> {
+    let mut event = Some(event);
+    match PENDING_CHANGES.try_with(|changes| changes.borrow_mut().extend(event.take())) {
+        Ok(()) => None,
+        Err(_) => event,
+    }
+}
+
+/// In-memory entries touched by a transaction, as they were before it
+///
+/// `None` records an entry that did not exist yet. Only the entries of the
+/// configs the sub-commands name are captured, so undoing a transaction does
+/// not depend on the size of the store. Dry runs copy the same entries into
+/// their scratch store.
+#[derive(Default)]
+pub(super) struct TransactionUndo {
+    pub(super) configurations: BTreeMap<ConfigKey, Option<Config>>,
+    pub(super) versions: BTreeMap<u64, Option<BTreeMap<u64, ConfigVersion>>>,
+    pub(super) name_index: BTreeMap<ConfigKey, Option<u64>>,
+    pub(super) tenant_usage: BTreeMap<String, Option<TenantUsage>>,
+    pub(super) next_config_id: u64,
+}
 
 impl Store {
-    /// Execute a transactional operation with rollback support
-    pub(crate) async fn execute_transaction<F, R>(&self, operation: F) -> Result<R>
-    where
-        F: FnOnce() -> Result<R>,
-    {
-        // For now, this is a simple wrapper, but can be extended
-        // to support more complex transaction semantics
-        operation()
+    /// Handle transaction command
+    ///
+    /// Applies the sub-commands in order, staging all their RocksDB writes in
+    /// one batch that is only written once every sub-command succeeded. If any
+    /// of them fails, the batch is dropped and the in-memory entries they
+    /// touched are restored, so nothing of the transaction is kept. Change
+    /// events are only broadcast once the transaction committed.
+    pub(crate) async fn handle_transaction(
+        &self,
+        commands: &[RaftCommand],
+    ) -> Result<ClientWriteResponse> {
+        if commands.is_empty() {
+            return Ok(Self::create_error_response(
+                "Transaction contains no commands".to_string(),
+            ));
+        }
+        if let Some(index) = commands.iter().position(|command| !is_transactional(command)) {
+            return Ok(Self::create_error_response(format!(
+                "Command {} is not allowed in a transaction",
+                index
+            )));
+        }
+
+        let mut undo = TransactionUndo {
+            next_config_id: *self.next_config_id.read().await,
+            ..TransactionUndo::default()
+        };
+        let staged = self
+            .staged(PENDING_CHANGES.scope(RefCell::new(Vec::new()), async {
+                let outcome = self.apply_transaction_commands(commands, &mut undo).await;
+                if outcome.is_err() {
+                    discard_staged_writes();
+                }
+                Ok((outcome, PENDING_CHANGES.with(RefCell::take)))
+            }))
+            .await;
+        let (outcome, changes) = match staged {
+            Ok(staged) => staged,
+            Err(e) => {
+                // The batch could not be written: memory must not keep what disk lacks
+                self.undo_transaction(undo).await;
+                return Err(e);
+            }
+        };
+
+        match outcome {
+            Ok(responses) => {
+                for event in changes {
+                    let _ = self.change_notifier.send(event);
+                }
+                Ok(Self::create_success_response(
+                    format!("Transaction of {} commands applied successfully", commands.len()),
+                    Some(serde_json::json!({ "results": responses })),
+                ))
+            }
+            Err((index, message)) => {
+                warn!("Rolling back transaction, command {} failed: {}", index, message);
+                self.undo_transaction(undo).await;
+                Ok(Self::create_error_response(format!(
+                    "Transaction rolled back, command {} failed: {}",
+                    index, message
+                )))
+            }
+        }
+    }
+
+    /// Apply sub-commands until one fails, returning its index and message
+    ///
+    /// The entries each sub-command may modify are recorded in `undo` before
+    /// it runs.
+    async fn apply_transaction_commands(
+        &self,
+        commands: &[RaftCommand],
+        undo: &mut TransactionUndo,
+    ) -> std::result::Result<Vec<ClientWriteResponse>, (usize, String)> {
+        let mut responses = Vec::with_capacity(commands.len());
+        for (index, command) in commands.iter().enumerate() {
+            self.record_undo(undo, command).await;
+
+            // Boxed because transactions are themselves applied through dispatch_command;
+            // the transaction as a whole is already journalled in the WAL
+            let apply: Pin<Box<dyn Future<Output = Result<ClientWriteResponse>> + Send + '_>> =
+                Box::pin(self.dispatch_command(command));
+            match apply.await {
+                Ok(response) if response.success => responses.push(response),
+                Ok(response) => return Err((index, response.message)),
+                Err(e) => return Err((index, e.to_string())),
+            }
+        }
+        Ok(responses)
+    }
+
+    /// Record the entries `command` may modify, unless an earlier sub-command
+    /// already recorded them
+    pub(super) async fn record_undo(&self, undo: &mut TransactionUndo, command: &RaftCommand) {
+        let config_id = match command.config_id() {
+            Some(config_id) => config_id,
+            // A created config takes the next free ID
+            None => *self.next_config_id.read().await,
+        };
+
+        let configs = self.configurations.read().await;
+        let mut keys: Vec<ConfigKey> = configs
+            .iter()
+            .filter(|(_, config)| config.id == config_id)
+            .map(|(key, _)| key.clone())
+            .collect();
+        let mut tenants: Vec<String> = keys
+            .iter()
+            .filter_map(|key| configs.get(key))
+            .map(|config| config.namespace.tenant.clone())
+            .collect();
+        // Creating or renaming a config also claims the key of its new name
+        if let RaftCommand::CreateConfig { namespace, name, .. }
+        | RaftCommand::UpdateConfig { namespace, name, .. } = command
+        {
+            keys.push(make_config_key(namespace, name));
+            tenants.push(namespace.tenant.clone());
+        }
+
+        for key in &keys {
+            undo.configurations
+                .entry(key.clone())
+                .or_insert_with(|| configs.get(key).cloned());
+        }
+        drop(configs);
+
+        if !undo.versions.contains_key(&config_id) {
+            let config_versions = self.versions.read().await.get(&config_id).cloned();
+            undo.versions.insert(config_id, config_versions);
+        }
+        {
+            let name_index = self.name_index.read().await;
+            for key in keys {
+                let entry = name_index.get(&key).copied();
+                undo.name_index.entry(key).or_insert(entry);
+            }
+        }
+        for tenant in tenants {
+            let usage = self.tenant_usage.get(&tenant).map(|usage| *usage);
+            undo.tenant_usage.entry(tenant).or_insert(usage);
+        }
+    }
+
+    /// Put back the in-memory entries recorded before a transaction
+    ///
+    /// All maps are restored under their write locks together, so readers see
+    /// either the transaction's changes or none of them.
+    async fn undo_transaction(&self, undo: TransactionUndo) {
+        {
+            let mut configs = self.configurations.write().await;
+            let mut versions = self.versions.write().await;
+            let mut name_index = self.name_index.write().await;
+            let mut next_config_id = self.next_config_id.write().await;
+
+            for (key, config) in undo.configurations {
+                match config {
+                    Some(config) => configs.insert(key, config),
+                    None => configs.remove(&key),
+                };
+            }
+            for (config_id, config_versions) in undo.versions {
+                match config_versions {
+                    Some(config_versions) => versions.insert(config_id, config_versions),
+                    None => versions.remove(&config_id),
+                };
+            }
+            for (key, config_id) in undo.name_index {
+                match config_id {
+                    Some(config_id) => name_index.insert(key, config_id),
+                    None => name_index.remove(&key),
+                };
+            }
+            *next_config_id = undo.next_config_id;
+        }
+
+        for (tenant, usage) in undo.tenant_usage {
+            if let Some(usage) = usage {
+                self.tenant_usage.insert(tenant, usage);
+            } else {
+                self.tenant_usage.remove(&tenant);
+            }
+        }
+        self.published_cache.invalidate_all();
     }
 
     /// Find config by ID with better error handling
@@ -54,6 +289,7 @@ impl Store {
             success: false,
             message,
             data: None,
+            log_index: None,
         }
     }
 
@@ -64,6 +300,7 @@ impl Store {
             success: true,
             message,
             data,
+            log_index: None,
         }
     }
 
@@ -76,3 +313,7 @@ impl Store {
             .unwrap_or_else(|| "unknown".to_string())
     }
 }
+
+#[cfg(test)]
+#[path = "transaction_tests.rs"]
+mod tests;
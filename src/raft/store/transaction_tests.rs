@@ -0,0 +1,180 @@
+use super::*;
+use crate::raft::store::test_support::create_store;
+use tempfile::tempdir;
+use tokio::sync::broadcast::error::TryRecvError;
+
+fn namespace() -> ConfigNamespace {
+    ConfigNamespace {
+        tenant: "tenant".to_string(),
+        app: "app".to_string(),
+        env: "prod".to_string(),
+    }
+}
+
+async fn create_config(store: &Store, name: &str) -> u64 {
+    let response = store
+        .apply_command(&RaftCommand::CreateConfig {
+            namespace: namespace(),
+            name: name.to_string(),
+            content: b"{\"v\":1}".to_vec(),
+            format: ConfigFormat::Json,
+            schema: None,
+            creator_id: 1,
+            description: "v1".to_string(),
+        })
+        .await
+        .unwrap();
+    response.config_id.unwrap()
+}
+
+fn create_version(config_id: u64, content: &str) -> RaftCommand {
+    RaftCommand::CreateVersion {
+        config_id,
+        content: content.as_bytes().to_vec(),
+        format: None,
+        creator_id: 1,
+        description: "tx".to_string(),
+    }
+}
+
+#[tokio::test]
+async fn test_transaction_applies_all_commands() {
+    let (store, _dir) = create_store().await;
+    let flag = create_config(&store, "flag.json").await;
+    let setting = create_config(&store, "setting.json").await;
+    let mut changes = store.subscribe_changes();
+
+    let response = store
+        .apply_command(&RaftCommand::Transaction {
+            commands: vec![
+                create_version(flag, r#"{"enabled":true}"#),
+                create_version(setting, r#"{"limit":10}"#),
+                RaftCommand::ReleaseVersion {
+                    config_id: setting,
+                    version_id: 2,
+                },
+            ],
+        })
+        .await
+        .unwrap();
+    assert!(response.success, "{}", response.message);
+
+    assert_eq!(store.get_config_meta(flag).await.unwrap().latest_version_id, 2);
+    let (_, published) = store
+        .get_published_config(&namespace(), "setting.json", &BTreeMap::new())
+        .await
+        .unwrap();
+    assert_eq!(published.id, 2);
+
+    // Events are broadcast once the transaction committed
+    let mut received = 0;
+    while changes.try_recv().is_ok() {
+        received += 1;
+    }
+    assert!(received >= 3);
+}
+
+#[tokio::test]
+async fn test_failed_transaction_rolls_back_memory_and_disk() {
+    let dir = tempdir().unwrap();
+    {
+        let (store, _rx) = Store::new(dir.path()).await.unwrap();
+        let flag = create_config(&store, "flag.json").await;
+        let mut changes = store.subscribe_changes();
+
+        let response = store
+            .apply_command(&RaftCommand::Transaction {
+                commands: vec![
+                    create_version(flag, r#"{"enabled":true}"#),
+                    RaftCommand::CreateConfig {
+                        namespace: namespace(),
+                        name: "new.json".to_string(),
+                        content: b"{}".to_vec(),
+                        format: ConfigFormat::Json,
+                        schema: None,
+                        creator_id: 1,
+                        description: "new".to_string(),
+                    },
+                    create_version(99, "{}"),
+                ],
+            })
+            .await
+            .unwrap();
+        assert!(!response.success);
+        assert!(response.message.contains("command 2"), "{}", response.message);
+
+        assert_eq!(store.get_config_meta(flag).await.unwrap().latest_version_id, 1);
+        assert_eq!(store.list_config_versions(flag).await.len(), 1);
+        assert!(store.get_config(&namespace(), "new.json").await.is_none());
+        assert!(store.check_consistency().await.unwrap().is_consistent);
+        assert_eq!(changes.try_recv().unwrap_err(), TryRecvError::Empty);
+    }
+
+    // Nothing of the transaction survives a reload either
+    let (store, _rx) = Store::new(dir.path()).await.unwrap();
+    assert_eq!(store.list_config_versions(1).await.len(), 1);
+    assert!(store.get_config(&namespace(), "new.json").await.is_none());
+}
+
+#[tokio::test]
+async fn test_rollback_only_undoes_the_transaction() {
+    let dir = tempdir().unwrap();
+    {
+        let (store, _rx) = Store::new(dir.path()).await.unwrap();
+        let flag = create_config(&store, "flag.json").await;
+        let other = create_config(&store, "other.json").await;
+        let usage = store.tenant_usage("tenant");
+
+        let response = store
+            .apply_command(&RaftCommand::Transaction {
+                commands: vec![
+                    RaftCommand::UpdateConfig {
+                        config_id: flag,
+                        namespace: namespace(),
+                        name: "renamed.json".to_string(),
+                        content: b"{\"v\":2}".to_vec(),
+                        format: ConfigFormat::Json,
+                        schema: None,
+                        description: "rename".to_string(),
+                    },
+                    create_version(99, "{}"),
+                ],
+            })
+            .await
+            .unwrap();
+        assert!(!response.success);
+
+        assert_eq!(store.get_config(&namespace(), "flag.json").await.unwrap().id, flag);
+        assert!(store.get_config(&namespace(), "renamed.json").await.is_none());
+        assert_eq!(store.tenant_usage("tenant"), usage);
+        assert!(store.check_consistency().await.unwrap().is_consistent);
+
+        // Writes after the rollback are persisted on their own
+        store.apply_command(&create_version(other, r#"{"v":2}"#)).await.unwrap();
+    }
+
+    let (store, _rx) = Store::new(dir.path()).await.unwrap();
+    assert!(store.get_config(&namespace(), "flag.json").await.is_some());
+    assert!(store.get_config(&namespace(), "renamed.json").await.is_none());
+    assert_eq!(store.list_config_versions(1).await.len(), 1);
+    assert_eq!(store.list_config_versions(2).await.len(), 2);
+}
+
+#[tokio::test]
+async fn test_transaction_rejects_unsupported_commands() {
+    let (store, _dir) = create_store().await;
+    let flag = create_config(&store, "flag.json").await;
+
+    for commands in [
+        vec![],
+        vec![create_version(flag, "{}"), RaftCommand::DeleteConfig { config_id: flag }],
+        vec![RaftCommand::Transaction { commands: vec![create_version(flag, "{}")] }],
+    ] {
+        let response = store
+            .apply_command(&RaftCommand::Transaction { commands })
+            .await
+            .unwrap();
+        assert!(!response.success);
+    }
+    assert_eq!(store.list_config_versions(flag).await.len(), 1);
+}
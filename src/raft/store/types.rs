@@ -1,10 +1,15 @@
+use crate::app::EventSink;
 use crate::raft::types::*;
+use dashmap::DashMap;
 use openraft::{storage::SnapshotMeta, LogId, StoredMembership, Vote};
 use rocksdb::DB;
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
+use std::sync::atomic::AtomicBool;
 use std::sync::{Arc, Mutex};
+use tokio::sync::broadcast::error::TryRecvError;
 use tokio::sync::{broadcast, mpsc, oneshot, RwLock};
+use tracing::warn;
 
 /// Store with RocksDB backend implementing RaftLogStorage
 #[derive(Clone, Debug)]
@@ -27,8 +32,11 @@ pub struct Store {
     /// Change notification broadcaster
     pub(crate) change_notifier: Arc<broadcast::Sender<ConfigChangeEvent>>,
 
-    /// Raft log storage (serialized as JSON strings like memstore)
-    pub(crate) logs: Arc<RwLock<BTreeMap<u64, String>>>,
+    /// Raft log storage, each entry encoded by `log_codec`
+    pub(crate) logs: Arc<RwLock<BTreeMap<u64, Vec<u8>>>>,
+
+    /// Encoder of log entries, optionally compressing them
+    pub(crate) log_codec: Arc<super::log_codec::LogCodec>,
 
     /// Last purged log ID
     pub(crate) last_purged_log_id: Arc<RwLock<Option<LogId<NodeId>>>>,
@@ -48,10 +56,31 @@ pub struct Store {
 
     /// 事件发送器，用于与状态机通信
     pub(crate) event_sender: Option<mpsc::Sender<StateChangeEvent>>,
+
+    /// Content size and version history limits, with per-tenant overrides
+    pub(crate) content_limits: Arc<super::limits::ContentLimitRegistry>,
+
+    /// LRU cache of published config reads, invalidated on config change events
+    pub(crate) published_cache: Arc<super::read_cache::PublishedConfigCache>,
+
+    /// Parent environments that namespaces fall back to for missing configs
+    pub(crate) env_inheritance: Arc<super::inheritance::EnvInheritance>,
+
+    /// Whether version reads are checked against the hash chain
+    pub(crate) verify_chain_on_read: Arc<AtomicBool>,
+
+    /// Journal of commands, written before they mutate any state
+    pub(crate) wal: Arc<super::wal::CommandWal>,
+
+    /// Held while a command is applied, so that applied WAL sequences reach
+    /// RocksDB in order
+    pub(crate) apply_lock: Arc<tokio::sync::Mutex<()>>,
+
+    /// Config, version and byte usage of each tenant, checked against its quota
+    pub(crate) tenant_usage: Arc<DashMap<String, super::quotas::TenantUsage>>,
 }
 
 /// 状态机管理器，负责处理状态变更事件循环
-#[derive(Debug)]
 pub struct StateMachineManager {
     /// Store实例用于处理状态变更
     store: Arc<Store>,
@@ -59,6 +88,17 @@ pub struct StateMachineManager {
     event_receiver: mpsc::Receiver<StateChangeEvent>,
     /// 状态机实例
     state: crate::raft::state_machine::ConfluxStateMachine,
+    /// 外部事件发布目标，以及订阅的配置变更事件
+    event_sink: Option<(Arc<dyn EventSink>, broadcast::Receiver<ConfigChangeEvent>)>,
+}
+
+impl std::fmt::Debug for StateMachineManager {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StateMachineManager")
+            .field("state", &self.state)
+            .field("event_sink", &self.event_sink.is_some())
+            .finish_non_exhaustive()
+    }
 }
 
 impl StateMachineManager {
@@ -68,9 +108,19 @@ impl StateMachineManager {
             state: crate::raft::state_machine::ConfluxStateMachine::new(store.clone()),
             store,
             event_receiver,
+            event_sink: None,
         }
     }
 
+    /// 应用命令后将产生的配置变更事件发布到 `sink`
+    ///
+    /// 每个副本都会应用同样的命令，因此集群中每个节点都会发布一份相同的事件，
+    /// 订阅方可按 `config_id` 和 `version_id` 去重
+    pub fn with_event_sink(mut self, sink: Arc<dyn EventSink>) -> Self {
+        self.event_sink = Some((sink, self.store.subscribe_changes()));
+        self
+    }
+
     /// 运行事件处理循环
     pub async fn run(&mut self) {
         while let Some(event) = self.event_receiver.recv().await {
@@ -85,6 +135,7 @@ impl StateMachineManager {
                         .await
                         .map_err(|e| format!("State change failed: {}", e));
                     let _ = response_sender.send(result);
+                    self.publish_changes().await;
                 }
                 StateChangeEvent::SnapshotRequest { response_sender } => {
                     let result = self
@@ -99,6 +150,31 @@ impl StateMachineManager {
     }
 }
 
+impl StateMachineManager {
+    /// 将已应用命令产生的配置变更事件交给外部发布目标
+    async fn publish_changes(&mut self) {
+        let Some((sink, changes)) = self.event_sink.as_mut() else {
+            return;
+        };
+        loop {
+            match changes.try_recv() {
+                Ok(event) => {
+                    if let Err(e) = sink.publish(&event).await {
+                        warn!(
+                            "Failed to publish change event for config {}: {}",
+                            event.config_id, e
+                        );
+                    }
+                }
+                Err(TryRecvError::Lagged(skipped)) => {
+                    warn!("Event sink skipped {} change events", skipped);
+                }
+                Err(TryRecvError::Empty | TryRecvError::Closed) => break,
+            }
+        }
+    }
+}
+
 /// 状态变更事件类型
 #[derive(Debug)]
 pub enum StateChangeEvent {
@@ -121,15 +197,17 @@ pub struct ConfluxStateMachine {
     // Configuration data is stored in the main Store struct
 }
 
-/// Snapshot data for Conflux
+/// Metadata and size of a built snapshot
+///
+/// The snapshot data itself is streamed to its consumer and not kept here.
 #[derive(Debug)]
 pub struct ConfluxSnapshot {
     pub meta: SnapshotMeta<NodeId, Node>,
-    pub data: Vec<u8>,
+    pub size_bytes: u64,
 }
 
 /// Configuration change event
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ConfigChangeEvent {
     pub config_id: u64,
     pub namespace: ConfigNamespace,
@@ -139,7 +217,7 @@ pub struct ConfigChangeEvent {
 }
 
 /// Type of configuration change
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ConfigChangeType {
     Created,
     Updated,
@@ -0,0 +1,42 @@
+use crate::error::{ConfluxError, Result};
+use crate::raft::types::*;
+use super::types::Store;
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+impl Store {
+    /// Read a single typed value from the published version of a config
+    ///
+    /// The version published for the client labels is parsed according to its
+    /// format and `key_path` is followed through it, e.g. `database.hosts.0`.
+    /// Fails with `NotFound` when the config or key does not exist, and with
+    /// a validation error when the content cannot be parsed by key.
+    pub async fn get_config_value(
+        &self,
+        namespace: &ConfigNamespace,
+        name: &str,
+        key_path: &str,
+        client_labels: &BTreeMap<String, String>,
+    ) -> Result<Value> {
+        let (_, version) = self
+            .get_published_config(namespace, name, client_labels)
+            .await
+            .ok_or_else(|| {
+                ConfluxError::NotFound(format!("Configuration {}/{} not found", namespace, name))
+            })?;
+
+        let document = content_to_document(&version.content, &version.format).map_err(|e| {
+            ConfluxError::validation(format!("Configuration {}/{}: {}", namespace, name, e))
+        })?;
+        lookup_key_path(&document, key_path).cloned().ok_or_else(|| {
+            ConfluxError::NotFound(format!(
+                "Key {} not found in configuration {}/{}",
+                key_path, namespace, name
+            ))
+        })
+    }
+}
+
+#[cfg(test)]
+#[path = "values_tests.rs"]
+mod tests;
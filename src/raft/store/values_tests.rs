@@ -0,0 +1,69 @@
+use super::*;
+use std::collections::BTreeMap;
+use tempfile::tempdir;
+
+fn namespace() -> ConfigNamespace {
+    ConfigNamespace {
+        tenant: "acme".to_string(),
+        app: "app".to_string(),
+        env: "prod".to_string(),
+    }
+}
+
+async fn create_config(store: &Store, name: &str, content: &[u8], format: ConfigFormat) {
+    store
+        .apply_command(&RaftCommand::CreateConfig {
+            namespace: namespace(),
+            name: name.to_string(),
+            content: content.to_vec(),
+            format,
+            schema: None,
+            creator_id: 1,
+            description: "initial".to_string(),
+        })
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_get_config_value_by_key_path() {
+    let dir = tempdir().unwrap();
+    let (store, _rx) = Store::new(dir.path()).await.unwrap();
+    let (ns, labels) = (namespace(), BTreeMap::new());
+    create_config(
+        &store,
+        "app.yaml",
+        b"database:\n  port: 5432\n  hosts: [a, b]\n  tls: true\n",
+        ConfigFormat::Yaml,
+    )
+    .await;
+
+    let value = |key: &'static str| store.get_config_value(&ns, "app.yaml", key, &labels);
+    assert_eq!(value("database.port").await.unwrap(), Value::from(5432));
+    assert_eq!(value("database.tls").await.unwrap(), Value::Bool(true));
+    assert_eq!(value("database.hosts.1").await.unwrap(), Value::from("b"));
+    assert_eq!(
+        value("database").await.unwrap()["hosts"],
+        Value::from(vec!["a", "b"])
+    );
+    assert!(matches!(value("database.user").await, Err(ConfluxError::NotFound(_))));
+    assert!(matches!(
+        store.get_config_value(&ns, "missing.yaml", "a", &labels).await,
+        Err(ConfluxError::NotFound(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_get_config_value_rejects_unparseable_content() {
+    let dir = tempdir().unwrap();
+    let (store, _rx) = Store::new(dir.path()).await.unwrap();
+    create_config(&store, "blob.bin", &[0, 1, 2], ConfigFormat::Binary).await;
+    create_config(&store, "broken.json", b"{", ConfigFormat::Json).await;
+
+    for name in ["blob.bin", "broken.json"] {
+        let result = store
+            .get_config_value(&namespace(), name, "a", &BTreeMap::new())
+            .await;
+        assert!(matches!(result, Err(ConfluxError::Validation(_))), "{}", name);
+    }
+}
@@ -0,0 +1,309 @@
+use crate::error::{ConfluxError, Result};
+use crate::raft::types::RaftCommand;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, PoisonError};
+use tracing::{debug, info, warn};
+
+/// Size after which the active WAL file is closed and a new one is started
+pub const WAL_MAX_FILE_BYTES: u64 = 64 * 1024 * 1024;
+
+/// Directory below the store path holding the WAL files
+pub(crate) const WAL_DIR: &str = "wal";
+
+const WAL_EXTENSION: &str = "wal";
+
+/// Write-ahead log file statistics
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WalStats {
+    /// Number of WAL files on disk, including the active one
+    pub file_count: usize,
+    /// Total size of the WAL files
+    pub size_bytes: u64,
+    /// Sequence of the most recently journalled command (0 if none)
+    pub last_sequence: u64,
+}
+
+/// One line of a WAL file: a command about to be applied; `C` is a borrowed
+/// command when writing
+#[derive(Debug, Serialize, Deserialize)]
+struct WalRecord<C = RaftCommand> {
+    sequence: u64,
+    command: C,
+}
+
+/// Append-only journal of the commands applied to the store
+///
+/// Every command is written, and synced, before it mutates any state. The
+/// store writes the sequence of each applied command to RocksDB in the same
+/// batch as the command's own writes, so the commands after the last such
+/// sequence are exactly the ones a crash interrupted; [`CommandWal::open`]
+/// returns them for replay. Files are named after the first sequence they may
+/// contain.
+#[derive(Debug)]
+pub(crate) struct CommandWal {
+    dir: PathBuf,
+    max_file_bytes: u64,
+    state: Mutex<WalState>,
+}
+
+#[derive(Debug)]
+struct WalState {
+    /// The active file, which new records are appended to
+    file: File,
+    /// `(first sequence, size in bytes)` of every file, oldest first; the
+    /// last one is the active file
+    files: Vec<(u64, u64)>,
+    last_sequence: u64,
+    /// Journalled commands that have not been marked applied yet
+    pending: BTreeSet<u64>,
+}
+
+impl CommandWal {
+    /// Open the WAL in `dir`, creating it if needed
+    ///
+    /// `applied_sequence` is the last sequence whose writes are in RocksDB.
+    /// Returns the WAL and the commands journalled after it, in sequence order.
+    pub(crate) fn open(
+        dir: impl AsRef<Path>,
+        max_file_bytes: u64,
+        applied_sequence: u64,
+    ) -> Result<(Self, Vec<(u64, RaftCommand)>)> {
+        let dir = dir.as_ref().to_path_buf();
+        fs::create_dir_all(&dir).map_err(|e| {
+            ConfluxError::storage(format!("Failed to create WAL directory: {}", e))
+        })?;
+
+        let mut files = Vec::new();
+        let mut unapplied = BTreeMap::new();
+        // Never hand out a sequence again that RocksDB already counts as applied,
+        // even if the WAL files were lost
+        let mut last_sequence = applied_sequence;
+        for first_sequence in list_wal_files(&dir)? {
+            let path = wal_file_path(&dir, first_sequence);
+            let size = read_wal_file(&path, &mut unapplied, &mut last_sequence)?;
+            files.push((first_sequence, size));
+        }
+        unapplied.retain(|&sequence, _| sequence > applied_sequence);
+
+        let file = match files.last() {
+            Some(&(first_sequence, _)) => open_for_append(&wal_file_path(&dir, first_sequence))?,
+            None => {
+                files.push((last_sequence + 1, 0));
+                create_wal_file(&dir, last_sequence + 1)?
+            }
+        };
+
+        if !unapplied.is_empty() {
+            info!(
+                "WAL in {} has {} unapplied commands",
+                dir.display(),
+                unapplied.len()
+            );
+        }
+
+        let wal = Self {
+            dir,
+            max_file_bytes,
+            state: Mutex::new(WalState {
+                file,
+                files,
+                last_sequence,
+                pending: unapplied.keys().copied().collect(),
+            }),
+        };
+        Ok((wal, unapplied.into_iter().collect()))
+    }
+
+    /// Journal a command before it is applied, returning its sequence
+    pub(crate) fn append(&self, command: &RaftCommand) -> Result<u64> {
+        let mut state = self.lock();
+        let sequence = state.last_sequence + 1;
+
+        let active_first = state.files.last().map_or(sequence, |&(first, _)| first);
+        if state.active_bytes() >= self.max_file_bytes && active_first < sequence {
+            state.file = create_wal_file(&self.dir, sequence)?;
+            state.files.push((sequence, 0));
+            debug!("Rotated WAL to file starting at sequence {}", sequence);
+        }
+
+        state.write(&WalRecord { sequence, command })?;
+        state.last_sequence = sequence;
+        state.pending.insert(sequence);
+        Ok(sequence)
+    }
+
+    /// Stop tracking the command with `sequence` once its writes, and its
+    /// sequence, are in RocksDB
+    ///
+    /// Nothing is written: the sequence stored in RocksDB is what keeps the
+    /// command from being replayed.
+    pub(crate) fn mark_applied(&self, sequence: u64) {
+        self.lock().pending.remove(&sequence);
+    }
+
+    /// Delete the inactive files whose commands have all been applied
+    ///
+    /// Must only be called once the applied state is durable in RocksDB.
+    /// Returns the number of deleted files.
+    pub(crate) fn prune(&self) -> Result<usize> {
+        let mut state = self.lock();
+        let oldest_pending = state.pending.first().copied().unwrap_or(u64::MAX);
+
+        // A file holds the sequences up to the first sequence of the next file
+        let deletable = state
+            .files
+            .windows(2)
+            .take_while(|pair| pair[1].0 <= oldest_pending)
+            .count();
+        for &(first_sequence, _) in &state.files[..deletable] {
+            fs::remove_file(wal_file_path(&self.dir, first_sequence)).map_err(|e| {
+                ConfluxError::storage(format!("Failed to delete WAL file: {}", e))
+            })?;
+        }
+        state.files.drain(..deletable);
+
+        if deletable > 0 {
+            debug!("Deleted {} applied WAL files", deletable);
+        }
+        Ok(deletable)
+    }
+
+    /// File count, size and last sequence of the WAL
+    pub(crate) fn stats(&self) -> WalStats {
+        let state = self.lock();
+        WalStats {
+            file_count: state.files.len(),
+            size_bytes: state.files.iter().map(|&(_, size)| size).sum(),
+            last_sequence: state.last_sequence,
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, WalState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl WalState {
+    fn active_bytes(&self) -> u64 {
+        self.files.last().map_or(0, |&(_, size)| size)
+    }
+
+    /// Append one record as a JSON line and sync it to disk
+    fn write(&mut self, record: &WalRecord<&RaftCommand>) -> Result<()> {
+        let mut line = serde_json::to_vec(record)?;
+        line.push(b'\n');
+        self.file
+            .write_all(&line)
+            .and_then(|_| self.file.sync_data())
+            .map_err(|e| ConfluxError::storage(format!("Failed to write WAL: {}", e)))?;
+        if let Some((_, size)) = self.files.last_mut() {
+            *size += line.len() as u64;
+        }
+        Ok(())
+    }
+}
+
+fn wal_file_path(dir: &Path, first_sequence: u64) -> PathBuf {
+    dir.join(format!("{:020}.{}", first_sequence, WAL_EXTENSION))
+}
+
+/// First sequences of the WAL files in `dir`, in ascending order
+fn list_wal_files(dir: &Path) -> Result<Vec<u64>> {
+    let entries = fs::read_dir(dir)
+        .map_err(|e| ConfluxError::storage(format!("Failed to list WAL directory: {}", e)))?;
+
+    let mut sequences = Vec::new();
+    for entry in entries {
+        let path = entry
+            .map_err(|e| ConfluxError::storage(format!("Failed to list WAL directory: {}", e)))?
+            .path();
+        if path.extension().and_then(|ext| ext.to_str()) != Some(WAL_EXTENSION) {
+            continue;
+        }
+        if let Some(sequence) = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .and_then(|stem| stem.parse().ok())
+        {
+            sequences.push(sequence);
+        }
+    }
+    sequences.sort_unstable();
+    Ok(sequences)
+}
+
+/// Read one WAL file, adding its commands to `unapplied`; returns the file size
+///
+/// Lines that cannot be parsed, such as one torn by a crash mid-write, are
+/// skipped.
+fn read_wal_file(
+    path: &Path,
+    unapplied: &mut BTreeMap<u64, RaftCommand>,
+    last_sequence: &mut u64,
+) -> Result<u64> {
+    let file = File::open(path)
+        .map_err(|e| ConfluxError::storage(format!("Failed to open WAL file: {}", e)))?;
+
+    let mut size = 0;
+    for line in BufReader::new(file).split(b'\n') {
+        let line =
+            line.map_err(|e| ConfluxError::storage(format!("Failed to read WAL file: {}", e)))?;
+        // Counted with its newline, which is added to a torn last line on open
+        size += line.len() as u64 + 1;
+        if line.is_empty() {
+            continue;
+        }
+
+        match serde_json::from_slice(&line) {
+            Ok(WalRecord { sequence, command }) => {
+                *last_sequence = (*last_sequence).max(sequence);
+                unapplied.insert(sequence, command);
+            }
+            Err(e) => warn!("Skipping unreadable record in {}: {}", path.display(), e),
+        }
+    }
+    Ok(size)
+}
+
+fn create_wal_file(dir: &Path, first_sequence: u64) -> Result<File> {
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(wal_file_path(dir, first_sequence))
+        .map_err(|e| ConfluxError::storage(format!("Failed to create WAL file: {}", e)))
+}
+
+/// Open an existing file for appending, ending a torn last line first so the
+/// next record starts on a line of its own
+fn open_for_append(path: &Path) -> Result<File> {
+    let mut file = OpenOptions::new()
+        .read(true)
+        .append(true)
+        .open(path)
+        .map_err(|e| ConfluxError::storage(format!("Failed to open WAL file: {}", e)))?;
+
+    let on_disk = file
+        .metadata()
+        .map_err(|e| ConfluxError::storage(format!("Failed to open WAL file: {}", e)))?
+        .len();
+    if on_disk > 0 {
+        let mut last = [0u8];
+        file.seek(SeekFrom::End(-1))
+            .and_then(|_| file.read_exact(&mut last))
+            .map_err(|e| ConfluxError::storage(format!("Failed to read WAL file: {}", e)))?;
+        if last[0] != b'\n' {
+            file.write_all(b"\n")
+                .and_then(|_| file.sync_data())
+                .map_err(|e| ConfluxError::storage(format!("Failed to write WAL: {}", e)))?;
+        }
+    }
+    Ok(file)
+}
+
+#[cfg(test)]
+#[path = "wal_tests.rs"]
+mod tests;
@@ -0,0 +1,133 @@
+use super::*;
+use crate::raft::types::ConfigNamespace;
+use tempfile::tempdir;
+
+fn command(config_id: u64) -> RaftCommand {
+    RaftCommand::SetPrunePolicy {
+        config_id,
+        max_versions: Some(5),
+    }
+}
+
+fn config_ids(commands: &[(u64, RaftCommand)]) -> Vec<(u64, u64)> {
+    commands
+        .iter()
+        .map(|(sequence, command)| match command {
+            RaftCommand::SetPrunePolicy { config_id, .. } => (*sequence, *config_id),
+            other => panic!("unexpected command {:?}", other),
+        })
+        .collect()
+}
+
+#[test]
+fn test_commands_after_applied_sequence_returned_on_open() {
+    let dir = tempdir().unwrap();
+    {
+        let (wal, pending) = CommandWal::open(dir.path(), WAL_MAX_FILE_BYTES, 0).unwrap();
+        assert!(pending.is_empty());
+        for config_id in 1..=3 {
+            assert_eq!(wal.append(&command(config_id)).unwrap(), config_id);
+        }
+    }
+
+    let (_, pending) = CommandWal::open(dir.path(), WAL_MAX_FILE_BYTES, 1).unwrap();
+    assert_eq!(config_ids(&pending), vec![(2, 2), (3, 3)]);
+
+    let (wal, pending) = CommandWal::open(dir.path(), WAL_MAX_FILE_BYTES, 3).unwrap();
+    assert!(pending.is_empty());
+    // Sequences continue after the last journalled command
+    assert_eq!(wal.append(&command(4)).unwrap(), 4);
+    assert_eq!(wal.stats().last_sequence, 4);
+}
+
+#[test]
+fn test_sequences_continue_after_applied_sequence() {
+    let dir = tempdir().unwrap();
+    // No WAL files, but RocksDB already holds the writes of command 7
+    let (wal, pending) = CommandWal::open(dir.path(), WAL_MAX_FILE_BYTES, 7).unwrap();
+    assert!(pending.is_empty());
+    assert_eq!(wal.append(&command(1)).unwrap(), 8);
+    assert_eq!(list_wal_files(dir.path()).unwrap(), vec![8]);
+}
+
+#[test]
+fn test_torn_record_is_skipped() {
+    let dir = tempdir().unwrap();
+    {
+        let (wal, _) = CommandWal::open(dir.path(), WAL_MAX_FILE_BYTES, 0).unwrap();
+        wal.append(&command(1)).unwrap();
+    }
+    // A crash in the middle of writing the next record
+    let path = wal_file_path(dir.path(), 1);
+    let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+    file.write_all(br#"{"sequence":2,"comm"#).unwrap();
+    drop(file);
+
+    let (wal, pending) = CommandWal::open(dir.path(), WAL_MAX_FILE_BYTES, 0).unwrap();
+    assert_eq!(config_ids(&pending), vec![(1, 1)]);
+    wal.mark_applied(1);
+    wal.append(&command(2)).unwrap();
+    drop(wal);
+
+    // Records appended after the torn line are still readable
+    let (_, pending) = CommandWal::open(dir.path(), WAL_MAX_FILE_BYTES, 1).unwrap();
+    assert_eq!(config_ids(&pending), vec![(2, 2)]);
+}
+
+#[test]
+fn test_rotation_and_prune() {
+    let dir = tempdir().unwrap();
+    // Small enough that every command starts a new file
+    let (wal, _) = CommandWal::open(dir.path(), 1, 0).unwrap();
+    for config_id in 1..=3 {
+        let sequence = wal.append(&command(config_id)).unwrap();
+        assert_eq!(sequence, config_id);
+    }
+    assert_eq!(wal.stats().file_count, 3);
+
+    // Files holding a pending command are kept
+    wal.mark_applied(1);
+    assert_eq!(wal.prune().unwrap(), 1);
+    assert_eq!(wal.stats().file_count, 2);
+
+    wal.mark_applied(2);
+    wal.mark_applied(3);
+    // The active file is never deleted
+    assert_eq!(wal.prune().unwrap(), 1);
+
+    let stats = wal.stats();
+    assert_eq!(stats.file_count, 1);
+    assert_eq!(stats.last_sequence, 3);
+    assert_eq!(
+        stats.size_bytes,
+        fs::metadata(wal_file_path(dir.path(), 3)).unwrap().len()
+    );
+    assert_eq!(list_wal_files(dir.path()).unwrap(), vec![3]);
+}
+
+#[test]
+fn test_command_round_trip() {
+    let dir = tempdir().unwrap();
+    let namespace = ConfigNamespace {
+        tenant: "tenant".to_string(),
+        app: "app".to_string(),
+        env: "prod".to_string(),
+    };
+    {
+        let (wal, _) = CommandWal::open(dir.path(), WAL_MAX_FILE_BYTES, 0).unwrap();
+        wal.append(&RaftCommand::SoftDeleteNamespace {
+            namespace: namespace.clone(),
+            delete_after_days: 7,
+            deleted_at: chrono::Utc::now(),
+        })
+        .unwrap();
+    }
+
+    let (_, pending) = CommandWal::open(dir.path(), WAL_MAX_FILE_BYTES, 0).unwrap();
+    match &pending[..] {
+        [(1, RaftCommand::SoftDeleteNamespace { namespace: restored, delete_after_days: 7, .. })] => {
+            assert_eq!(restored, &namespace);
+        }
+        other => panic!("unexpected pending commands {:?}", other),
+    }
+}
@@ -0,0 +1,143 @@
+use crate::raft::client::RetryPolicy;
+use crate::raft::types::*;
+use super::types::{ConfigChangeEvent, Store};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::task::JoinHandle;
+use tracing::{debug, warn};
+
+/// Timeout of a single webhook delivery attempt
+const WEBHOOK_REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Delivers configuration change events to registered webhooks
+///
+/// Each event from [`Store::subscribe_changes`] is serialized to JSON and
+/// `POST`ed to every webhook whose namespace filter matches. Failed deliveries
+/// (connection errors or non-2xx responses) are retried with exponential
+/// backoff; a webhook with a secret gets the [`WEBHOOK_SIGNATURE_HEADER`].
+pub struct WebhookNotifier {
+    store: Arc<Store>,
+    client: reqwest::Client,
+    retry_policy: RetryPolicy,
+    leader: Option<(ConfluxRaft, NodeId)>,
+}
+
+impl WebhookNotifier {
+    /// Create a notifier for the changes of `store`
+    pub fn new(store: Arc<Store>) -> Self {
+        Self {
+            store,
+            client: reqwest::Client::builder()
+                .timeout(WEBHOOK_REQUEST_TIMEOUT)
+                .build()
+                .unwrap_or_default(),
+            retry_policy: RetryPolicy {
+                max_attempts: 5,
+                base_delay: Duration::from_millis(500),
+                max_delay: Duration::from_secs(30),
+                jitter: Duration::from_millis(250),
+            },
+            leader: None,
+        }
+    }
+
+    /// Replace the retry policy of failed deliveries
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Only deliver events while `node_id` is the leader of `raft`
+    ///
+    /// Every replica applies the same changes, so without this check each node
+    /// of the cluster would send its own copy of every event.
+    pub fn with_leader_check(mut self, raft: ConfluxRaft, node_id: NodeId) -> Self {
+        self.leader = Some((raft, node_id));
+        self
+    }
+
+    /// Start delivering events in a background task
+    ///
+    /// The task ends when the store's change channel is closed.
+    pub fn spawn(self) -> JoinHandle<()> {
+        let mut changes = self.store.subscribe_changes();
+        tokio::spawn(async move {
+            loop {
+                match changes.recv().await {
+                    Ok(event) => self.notify(&event).await,
+                    Err(RecvError::Lagged(skipped)) => {
+                        warn!("Webhook notifier skipped {} change events", skipped);
+                    }
+                    Err(RecvError::Closed) => break,
+                }
+            }
+        })
+    }
+
+    /// Deliver one event to all matching webhooks
+    pub async fn notify(&self, event: &ConfigChangeEvent) {
+        if let Some((raft, node_id)) = &self.leader {
+            if raft.metrics().borrow().current_leader != Some(*node_id) {
+                return;
+            }
+        }
+
+        let webhooks = self.store.webhooks_for(&event.namespace).await;
+        if webhooks.is_empty() {
+            return;
+        }
+        let payload = match serde_json::to_vec(event) {
+            Ok(payload) => payload,
+            Err(e) => {
+                warn!("Failed to serialize change event of config {}: {}", event.config_id, e);
+                return;
+            }
+        };
+        for webhook in &webhooks {
+            self.deliver(webhook, &payload).await;
+        }
+    }
+
+    /// POST `payload` to `webhook`, retrying failures; returns whether it was delivered
+    async fn deliver(&self, webhook: &Webhook, payload: &[u8]) -> bool {
+        let max_attempts = self.retry_policy.max_attempts.max(1);
+        for attempt in 1..=max_attempts {
+            let mut request = self
+                .client
+                .post(&webhook.url)
+                .header(reqwest::header::CONTENT_TYPE, "application/json")
+                .body(payload.to_vec());
+            if let Some(signature) = webhook.signature(payload) {
+                request = request.header(WEBHOOK_SIGNATURE_HEADER, signature);
+            }
+
+            let error = match request.send().await {
+                Ok(response) if response.status().is_success() => {
+                    debug!("Delivered change event to webhook {}", webhook.id);
+                    return true;
+                }
+                Ok(response) => format!("status {}", response.status()),
+                Err(e) => e.to_string(),
+            };
+            if attempt == max_attempts {
+                warn!(
+                    "Giving up on webhook {} ({}) after {} attempts: {}",
+                    webhook.id, webhook.url, attempt, error
+                );
+                break;
+            }
+            let delay = self.retry_policy.delay(attempt);
+            debug!(
+                "Webhook {} delivery failed ({}), retrying in {:?}",
+                webhook.id, error, delay
+            );
+            tokio::time::sleep(delay).await;
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+#[path = "webhook_notifier_tests.rs"]
+mod tests;
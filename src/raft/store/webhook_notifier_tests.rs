@@ -0,0 +1,154 @@
+use super::*;
+use axum::body::Bytes;
+use axum::extract::State;
+use axum::http::{HeaderMap, StatusCode};
+use axum::routing::post;
+use axum::Router;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Mutex;
+use tempfile::tempdir;
+use tokio::net::TcpListener;
+
+/// Receiver that rejects the first `failures` requests and records the rest
+#[derive(Default)]
+struct Receiver {
+    failures: u32,
+    attempts: AtomicU32,
+    received: Mutex<Vec<(Option<String>, Bytes)>>,
+}
+
+async fn receive(
+    State(receiver): State<Arc<Receiver>>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> StatusCode {
+    if receiver.attempts.fetch_add(1, Ordering::SeqCst) < receiver.failures {
+        return StatusCode::SERVICE_UNAVAILABLE;
+    }
+    let signature = headers
+        .get(WEBHOOK_SIGNATURE_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string);
+    receiver.received.lock().unwrap().push((signature, body));
+    StatusCode::NO_CONTENT
+}
+
+async fn serve(receiver: Arc<Receiver>) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}/hook", listener.local_addr().unwrap());
+    let app = Router::new().route("/hook", post(receive)).with_state(receiver);
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    url
+}
+
+fn fast_retries(max_attempts: u32) -> RetryPolicy {
+    RetryPolicy {
+        max_attempts,
+        base_delay: Duration::from_millis(10),
+        max_delay: Duration::from_millis(50),
+        jitter: Duration::ZERO,
+    }
+}
+
+fn namespace() -> ConfigNamespace {
+    ConfigNamespace {
+        tenant: "acme".to_string(),
+        app: "web".to_string(),
+        env: "prod".to_string(),
+    }
+}
+
+async fn register(store: &Store, url: &str, secret: Option<&str>) {
+    let response = store
+        .apply_command(&RaftCommand::RegisterWebhook {
+            tenant: "acme".to_string(),
+            url: url.to_string(),
+            app: None,
+            env: None,
+            secret: secret.map(str::to_string),
+        })
+        .await
+        .unwrap();
+    assert!(response.success, "{}", response.message);
+}
+
+async fn create_config(store: &Store) {
+    let response = store
+        .apply_command(&RaftCommand::CreateConfig {
+            namespace: namespace(),
+            name: "app.json".to_string(),
+            content: br#"{"port":80}"#.to_vec(),
+            format: ConfigFormat::Json,
+            schema: None,
+            creator_id: 1,
+            description: "initial".to_string(),
+        })
+        .await
+        .unwrap();
+    assert!(response.success, "{}", response.message);
+}
+
+async fn wait_for_deliveries(receiver: &Receiver, count: usize) {
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while receiver.received.lock().unwrap().len() < count {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .expect("webhook was not delivered");
+}
+
+#[tokio::test]
+async fn test_change_events_are_delivered_with_retry_and_signature() {
+    let dir = tempdir().unwrap();
+    let (store, _rx) = Store::new(dir.path()).await.unwrap();
+    let store = Arc::new(store);
+    let receiver = Arc::new(Receiver {
+        failures: 2,
+        ..Default::default()
+    });
+    register(&store, &serve(receiver.clone()).await, Some("s3cret")).await;
+    let _task = WebhookNotifier::new(store.clone())
+        .with_retry_policy(fast_retries(5))
+        .spawn();
+
+    create_config(&store).await;
+    wait_for_deliveries(&receiver, 1).await;
+
+    assert_eq!(receiver.attempts.load(Ordering::SeqCst), 3);
+    let (signature, body) = receiver.received.lock().unwrap()[0].clone();
+    assert!(verify_webhook_signature("s3cret", &body, &signature.unwrap()));
+    let event: ConfigChangeEvent = serde_json::from_slice(&body).unwrap();
+    assert_eq!(event.namespace, namespace());
+    assert_eq!(event.name, "app.json");
+}
+
+#[tokio::test]
+async fn test_delivery_gives_up_after_max_attempts() {
+    let dir = tempdir().unwrap();
+    let (store, _rx) = Store::new(dir.path()).await.unwrap();
+    let store = Arc::new(store);
+    let failing = Arc::new(Receiver {
+        failures: u32::MAX,
+        ..Default::default()
+    });
+    let unsigned = Arc::new(Receiver::default());
+    register(&store, &serve(failing.clone()).await, None).await;
+    register(&store, &serve(unsigned.clone()).await, None).await;
+    let notifier = WebhookNotifier::new(store.clone()).with_retry_policy(fast_retries(3));
+
+    let event = ConfigChangeEvent {
+        config_id: 1,
+        namespace: namespace(),
+        name: "app.json".to_string(),
+        version_id: 1,
+        change_type: super::super::types::ConfigChangeType::Updated,
+    };
+    notifier.notify(&event).await;
+
+    assert_eq!(failing.attempts.load(Ordering::SeqCst), 3);
+    // A failing webhook does not block the others, and no secret means no signature
+    let received = unsigned.received.lock().unwrap().clone();
+    assert_eq!(received.len(), 1);
+    assert_eq!(received[0].0, None);
+}
@@ -0,0 +1,190 @@
+use crate::error::{ConfluxError, Result};
+use crate::raft::types::*;
+use super::constants::CF_WEBHOOKS;
+use super::types::Store;
+use rocksdb::{Direction, IteratorMode, WriteBatch, DB};
+use tracing::warn;
+
+/// Key prefix of webhook records, followed by the big-endian webhook ID
+const WEBHOOK_PREFIX: u8 = 0x01;
+
+/// Key of the last assigned webhook ID, so IDs of deleted webhooks are not reused
+const LAST_WEBHOOK_ID_KEY: &[u8] = &[0x00];
+
+impl Store {
+    /// Handle register webhook command
+    ///
+    /// The URL must be an absolute `http` or `https` URL. Each registration
+    /// gets a new ID, so the same URL can be registered with different
+    /// namespace filters.
+    pub(crate) async fn handle_register_webhook(
+        &self,
+        tenant: &str,
+        url: &str,
+        app: Option<String>,
+        env: Option<String>,
+        secret: Option<String>,
+    ) -> Result<ClientWriteResponse> {
+        if tenant.is_empty() {
+            return Ok(Self::create_error_response(
+                "Webhook tenant must not be empty".to_string(),
+            ));
+        }
+        if let Err(e) = validate_webhook_url(url) {
+            return Ok(Self::create_error_response(format!(
+                "Invalid webhook URL '{}': {}",
+                url, e
+            )));
+        }
+
+        let id = last_webhook_id(&self.db)? + 1;
+        let webhook = Webhook {
+            id,
+            tenant: tenant.to_string(),
+            url: url.to_string(),
+            app: app.filter(|app| !app.is_empty()),
+            env: env.filter(|env| !env.is_empty()),
+            secret: secret.filter(|secret| !secret.is_empty()),
+            created_at: chrono::Utc::now(),
+        };
+        self.write_with(|batch| put_webhook(&self.db, batch, &webhook))?;
+
+        Ok(Self::create_success_response(
+            format!("Registered webhook {} for tenant {}", id, tenant),
+            Some(serde_json::json!({
+                "webhook_id": id,
+                "tenant": tenant,
+                "url": url
+            })),
+        ))
+    }
+
+    /// Handle delete webhook command
+    ///
+    /// Only webhooks of the given tenant can be deleted.
+    pub(crate) async fn handle_delete_webhook(
+        &self,
+        tenant: &str,
+        webhook_id: u64,
+    ) -> Result<ClientWriteResponse> {
+        let owned = self
+            .get_webhook(webhook_id)
+            .await
+            .is_some_and(|webhook| webhook.tenant == tenant);
+        if !owned {
+            return Ok(Self::create_error_response(format!(
+                "Webhook {} not found for tenant {}",
+                webhook_id, tenant
+            )));
+        }
+
+        let cf = webhooks_cf(&self.db)?;
+        self.write_with(|batch| {
+            batch.delete_cf(cf, webhook_key(webhook_id));
+            Ok(())
+        })?;
+
+        Ok(Self::create_success_response(
+            format!("Deleted webhook {} of tenant {}", webhook_id, tenant),
+            Some(serde_json::json!({ "webhook_id": webhook_id })),
+        ))
+    }
+
+    /// Get a registered webhook by ID
+    pub async fn get_webhook(&self, webhook_id: u64) -> Option<Webhook> {
+        let result = webhooks_cf(&self.db).and_then(|cf| {
+            self.db
+                .get_pinned_cf(cf, webhook_key(webhook_id))
+                .map_err(|e| ConfluxError::storage(format!("Failed to read webhook: {}", e)))
+        });
+        match result {
+            Ok(Some(value)) => serde_json::from_slice(&value)
+                .map_err(|e| warn!("Failed to deserialize webhook {}: {}", webhook_id, e))
+                .ok(),
+            Ok(None) => None,
+            Err(e) => {
+                warn!("Failed to read webhook {}: {}", webhook_id, e);
+                None
+            }
+        }
+    }
+
+    /// All webhooks registered for a tenant, in ID order
+    pub async fn list_webhooks(&self, tenant: &str) -> Vec<Webhook> {
+        self.all_webhooks()
+            .into_iter()
+            .filter(|w| w.tenant == tenant)
+            .collect()
+    }
+
+    /// Webhooks to notify of a change in `namespace`, in ID order
+    pub async fn webhooks_for(&self, namespace: &ConfigNamespace) -> Vec<Webhook> {
+        self.all_webhooks()
+            .into_iter()
+            .filter(|w| w.matches(namespace))
+            .collect()
+    }
+
+    fn all_webhooks(&self) -> Vec<Webhook> {
+        scan_webhooks(&self.db).unwrap_or_else(|e| {
+            warn!("Failed to read webhooks: {}", e);
+            Vec::new()
+        })
+    }
+}
+
+fn webhooks_cf(db: &DB) -> Result<&rocksdb::ColumnFamily> {
+    db.cf_handle(CF_WEBHOOKS)
+        .ok_or_else(|| ConfluxError::storage("Webhooks column family not found"))
+}
+
+fn webhook_key(id: u64) -> [u8; 9] {
+    let mut key = [WEBHOOK_PREFIX; 9];
+    key[1..].copy_from_slice(&id.to_be_bytes());
+    key
+}
+
+fn last_webhook_id(db: &DB) -> Result<u64> {
+    let cf = webhooks_cf(db)?;
+    let value = db
+        .get_pinned_cf(cf, LAST_WEBHOOK_ID_KEY)
+        .map_err(|e| ConfluxError::storage(format!("Failed to read webhook ID: {}", e)))?;
+    Ok(value
+        .and_then(|v| <[u8; 8]>::try_from(v.as_ref()).ok())
+        .map_or(0, u64::from_be_bytes))
+}
+
+/// Store a new webhook and record its ID as the last assigned one, as part of `batch`
+fn put_webhook(db: &DB, batch: &mut WriteBatch, webhook: &Webhook) -> Result<()> {
+    let cf = webhooks_cf(db)?;
+    let data = serde_json::to_vec(webhook).map_err(|e| {
+        ConfluxError::storage(format!("Failed to serialize webhook: {}", e))
+    })?;
+    batch.put_cf(cf, webhook_key(webhook.id), data);
+    batch.put_cf(cf, LAST_WEBHOOK_ID_KEY, webhook.id.to_be_bytes());
+    Ok(())
+}
+
+/// Read all webhook records in ID order
+fn scan_webhooks(db: &DB) -> Result<Vec<Webhook>> {
+    let cf = webhooks_cf(db)?;
+    let mut webhooks = Vec::new();
+    let mode = IteratorMode::From(&[WEBHOOK_PREFIX], Direction::Forward);
+    for item in db.iterator_cf(cf, mode) {
+        let (key, value) = item.map_err(|e| {
+            ConfluxError::storage(format!("Failed to read webhook: {}", e))
+        })?;
+        if key.first() != Some(&WEBHOOK_PREFIX) {
+            break;
+        }
+        let webhook = serde_json::from_slice(&value).map_err(|e| {
+            ConfluxError::storage(format!("Failed to deserialize webhook: {}", e))
+        })?;
+        webhooks.push(webhook);
+    }
+    Ok(webhooks)
+}
+
+#[cfg(test)]
+#[path = "webhooks_tests.rs"]
+mod tests;
@@ -0,0 +1,103 @@
+use super::*;
+use tempfile::{tempdir, TempDir};
+
+fn namespace(tenant: &str, app: &str) -> ConfigNamespace {
+    ConfigNamespace {
+        tenant: tenant.to_string(),
+        app: app.to_string(),
+        env: "prod".to_string(),
+    }
+}
+
+async fn register(
+    store: &Store,
+    tenant: &str,
+    url: &str,
+    app: Option<&str>,
+) -> ClientWriteResponse {
+    store
+        .apply_command(&RaftCommand::RegisterWebhook {
+            tenant: tenant.to_string(),
+            url: url.to_string(),
+            app: app.map(str::to_string),
+            env: None,
+            secret: Some("s3cret".to_string()),
+        })
+        .await
+        .unwrap()
+}
+
+async fn delete(store: &Store, tenant: &str, webhook_id: u64) -> ClientWriteResponse {
+    store
+        .apply_command(&RaftCommand::DeleteWebhook {
+            tenant: tenant.to_string(),
+            webhook_id,
+        })
+        .await
+        .unwrap()
+}
+
+fn webhook_id(response: &ClientWriteResponse) -> u64 {
+    assert!(response.success, "{}", response.message);
+    response.data.as_ref().unwrap()["webhook_id"].as_u64().unwrap()
+}
+
+async fn setup() -> (Store, TempDir) {
+    let dir = tempdir().unwrap();
+    let (store, _rx) = Store::new(dir.path()).await.unwrap();
+    (store, dir)
+}
+
+#[tokio::test]
+async fn test_register_and_list_webhooks() {
+    let (store, _dir) = setup().await;
+    let first = webhook_id(&register(&store, "acme", "https://hooks.example.com/a", None).await);
+    let second =
+        webhook_id(&register(&store, "acme", "https://hooks.example.com/b", Some("web")).await);
+    webhook_id(&register(&store, "other", "https://hooks.example.com/c", None).await);
+
+    let webhooks = store.list_webhooks("acme").await;
+    assert_eq!(webhooks.iter().map(|w| w.id).collect::<Vec<_>>(), vec![first, second]);
+    assert_eq!(webhooks[1].app.as_deref(), Some("web"));
+    assert_eq!(webhooks[1].secret.as_deref(), Some("s3cret"));
+    assert_eq!(store.get_webhook(second).await, Some(webhooks[1].clone()));
+}
+
+#[tokio::test]
+async fn test_invalid_webhook_url_is_rejected() {
+    let (store, _dir) = setup().await;
+    let response = register(&store, "acme", "ftp://hooks.example.com", None).await;
+    assert!(!response.success);
+    assert!(response.message.contains("Invalid webhook URL"), "{}", response.message);
+    assert!(store.list_webhooks("acme").await.is_empty());
+}
+
+#[tokio::test]
+async fn test_delete_webhook_is_tenant_scoped_and_ids_are_not_reused() {
+    let (store, _dir) = setup().await;
+    let id = webhook_id(&register(&store, "acme", "https://hooks.example.com/a", None).await);
+
+    let response = delete(&store, "other", id).await;
+    assert!(!response.success);
+    assert!(store.get_webhook(id).await.is_some());
+
+    assert!(delete(&store, "acme", id).await.success);
+    assert!(store.get_webhook(id).await.is_none());
+    assert!(!delete(&store, "acme", id).await.success);
+
+    let next = webhook_id(&register(&store, "acme", "https://hooks.example.com/a", None).await);
+    assert!(next > id);
+}
+
+#[tokio::test]
+async fn test_webhooks_for_applies_namespace_filter() {
+    let (store, _dir) = setup().await;
+    let all = webhook_id(&register(&store, "acme", "https://hooks.example.com/a", None).await);
+    let web =
+        webhook_id(&register(&store, "acme", "https://hooks.example.com/b", Some("web")).await);
+
+    let ids = |webhooks: Vec<Webhook>| webhooks.iter().map(|w| w.id).collect::<Vec<_>>();
+    assert_eq!(ids(store.webhooks_for(&namespace("acme", "web")).await), vec![all, web]);
+    assert_eq!(ids(store.webhooks_for(&namespace("acme", "api")).await), vec![all]);
+    assert!(store.webhooks_for(&namespace("other", "web")).await.is_empty());
+}
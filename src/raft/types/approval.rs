@@ -0,0 +1,45 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// State of a release approval request
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ApprovalStatus {
+    Pending,
+    Approved,
+    Rejected,
+}
+
+/// Approval request for releasing a configuration version
+///
+/// Releases in namespaces that require approval are only allowed once an
+/// approval for the exact `config_id + version_id` is in `Approved` state.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ConfigApproval {
+    pub id: u64,
+    pub config_id: u64,
+    pub version_id: u64,
+    pub requested_by: u64,
+    pub approver_id: Option<u64>,
+    pub status: ApprovalStatus,
+    pub created_at: DateTime<Utc>,
+}
+
+impl ConfigApproval {
+    /// Create a pending approval request
+    pub fn new(id: u64, config_id: u64, version_id: u64, requested_by: u64) -> Self {
+        Self {
+            id,
+            config_id,
+            version_id,
+            requested_by,
+            approver_id: None,
+            status: ApprovalStatus::Pending,
+            created_at: Utc::now(),
+        }
+    }
+
+    /// Whether this approval covers the given version
+    pub fn is_for(&self, config_id: u64, version_id: u64) -> bool {
+        self.config_id == config_id && self.version_id == version_id
+    }
+}
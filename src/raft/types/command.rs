@@ -1,7 +1,9 @@
 use crate::raft::types::{ConfigFormat, Release};
 
 use super::config::ConfigNamespace;
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 /// Raft command enumeration
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -46,6 +48,161 @@ pub enum RaftCommand {
         config_id: u64,
         releases: Vec<Release>,
     },
+    /// Stage a release that becomes active at a future time
+    ScheduleRelease {
+        config_id: u64,
+        version_id: u64,
+        labels: BTreeMap<String, String>,
+        effective_at: DateTime<Utc>,
+    },
+    /// Activate the scheduled releases due at `now`, the proposing leader's clock
+    ActivateScheduledReleases { now: DateTime<Utc> },
+    /// Serve `version_id` to `percent` of the clients matching `labels`
+    SetCanaryPercent {
+        config_id: u64,
+        version_id: u64,
+        labels: BTreeMap<String, String>,
+        percent: u8,
+    },
+    /// Split the traffic of the default release between versions by weight
+    SetReleaseWeights {
+        config_id: u64,
+        /// `(version_id, weight)` pairs
+        weights: Vec<(u64, u32)>,
+    },
+    /// Record that `from_config_id` depends on `to_config_id`
+    AddConfigDependency { from_config_id: u64, to_config_id: u64 },
+    /// Remove a dependency edge between two configurations
+    RemoveConfigDependency { from_config_id: u64, to_config_id: u64 },
+    /// Turn the release approval requirement of a namespace on or off
+    SetApprovalRequired {
+        namespace: ConfigNamespace,
+        required: bool,
+    },
+    /// Open a pending approval request for releasing a version
+    RequestReleaseApproval {
+        config_id: u64,
+        version_id: u64,
+        requested_by: u64,
+    },
+    /// Approve the pending release request of a version
+    ApproveRelease {
+        config_id: u64,
+        version_id: u64,
+        approver_id: u64,
+    },
+    /// Reject the pending release request of a version
+    RejectRelease {
+        config_id: u64,
+        version_id: u64,
+        approver_id: u64,
+    },
+    /// Register a shared JSON Schema, replacing the document of an existing `tenant + name`
+    RegisterSchema {
+        tenant: String,
+        name: String,
+        schema: String,
+    },
+    /// Make a configuration reference a registered schema, or none
+    SetConfigSchema {
+        config_id: u64,
+        schema_id: Option<u64>,
+    },
+    /// Register a webhook notified of configuration changes in a tenant
+    RegisterWebhook {
+        tenant: String,
+        url: String,
+        app: Option<String>,
+        env: Option<String>,
+        secret: Option<String>,
+    },
+    /// Delete a webhook of a tenant
+    DeleteWebhook {
+        tenant: String,
+        webhook_id: u64,
+    },
+    /// Soft-delete every config in a namespace, recoverable for `delete_after_days`
+    /// counted from `deleted_at`, the proposer's clock
+    SoftDeleteNamespace {
+        namespace: ConfigNamespace,
+        delete_after_days: u8,
+        deleted_at: DateTime<Utc>,
+    },
+    /// Restore the soft-deleted configs of a namespace whose recovery window
+    /// has not expired at `now`, the proposer's clock
+    UndeleteNamespace {
+        namespace: ConfigNamespace,
+        now: DateTime<Utc>,
+    },
+    /// Purge the soft-deleted configs whose recovery window expired at `now`,
+    /// the proposing leader's clock
+    PurgeExpiredConfigs { now: DateTime<Utc> },
+    /// Forget the idempotency keys recorded longer than the key TTL before
+    /// `now`, the proposing leader's clock
+    EvictIdempotencyKeys { now: DateTime<Utc> },
+    /// Set how many versions a config keeps before the oldest are pruned (None = unlimited)
+    SetPrunePolicy {
+        config_id: u64,
+        max_versions: Option<u32>,
+    },
+    /// Delete the oldest versions of a config, keeping the newest `keep_last`
+    PruneVersions { config_id: u64, keep_last: u32 },
+    /// Apply several commands atomically: all of them succeed or none is applied
+    Transaction { commands: Vec<RaftCommand> },
+    /// Create many configurations, possibly in different namespaces, in one
+    /// log entry; each entry succeeds or fails on its own
+    BulkCreateConfigs { entries: Vec<BulkConfigEntry> },
+    /// Limit how many configs, versions per config and content bytes a
+    /// tenant may store (None = unlimited)
+    SetTenantQuota {
+        tenant: String,
+        max_configs: Option<u64>,
+        max_versions_per_config: Option<u64>,
+        max_total_bytes: Option<u64>,
+    },
+    /// Override the content size and version history limits of a tenant
+    /// (None = node default; without any limit the override is removed)
+    SetTenantLimits {
+        tenant: String,
+        max_config_content_bytes: Option<usize>,
+        max_version_history: Option<usize>,
+    },
+}
+
+/// A configuration to create as part of [`RaftCommand::BulkCreateConfigs`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BulkConfigEntry {
+    pub namespace: ConfigNamespace,
+    pub name: String,
+    pub content: Vec<u8>,
+    pub format: ConfigFormat,
+    pub schema: Option<String>,
+    pub creator_id: u64,
+    pub description: String,
+}
+
+impl BulkConfigEntry {
+    /// Estimate the memory usage of this entry in bytes
+    fn estimate_size(&self) -> usize {
+        std::mem::size_of::<Self>()
+            + self.namespace.tenant.len()
+            + self.namespace.app.len()
+            + self.namespace.env.len()
+            + self.name.len()
+            + self.content.len()
+            + self.schema.as_ref().map_or(0, |s| s.len() + 24)
+            + self.description.len()
+            + 120
+    }
+}
+
+/// Outcome of [`RaftCommand::BulkCreateConfigs`], carried in the response data
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BulkCreateResponse {
+    /// IDs of the configurations created, in entry order
+    pub created: Vec<u64>,
+    /// `tenant/app/env/name` and error message of each entry that was not created
+    pub errors: Vec<(String, String)>,
 }
 
 impl RaftCommand {
@@ -55,10 +212,34 @@ impl RaftCommand {
             RaftCommand::CreateConfig { .. } => None, // New config, no ID yet
             RaftCommand::CreateVersion { config_id, .. } => Some(*config_id),
             RaftCommand::UpdateReleaseRules { config_id, .. } => Some(*config_id),
+            RaftCommand::ScheduleRelease { config_id, .. } => Some(*config_id),
+            RaftCommand::ActivateScheduledReleases { .. } => None,
+            RaftCommand::SetCanaryPercent { config_id, .. } => Some(*config_id),
+            RaftCommand::SetReleaseWeights { config_id, .. } => Some(*config_id),
             RaftCommand::DeleteConfig { config_id } => Some(*config_id),
             RaftCommand::DeleteVersions { config_id, .. } => Some(*config_id),
             RaftCommand::UpdateConfig { config_id, .. } => Some(*config_id),
             RaftCommand::ReleaseVersion { config_id, .. } => Some(*config_id),
+            RaftCommand::AddConfigDependency { from_config_id, .. } => Some(*from_config_id),
+            RaftCommand::RemoveConfigDependency { from_config_id, .. } => Some(*from_config_id),
+            RaftCommand::SetApprovalRequired { .. } => None,
+            RaftCommand::RequestReleaseApproval { config_id, .. } => Some(*config_id),
+            RaftCommand::ApproveRelease { config_id, .. } => Some(*config_id),
+            RaftCommand::RejectRelease { config_id, .. } => Some(*config_id),
+            RaftCommand::RegisterSchema { .. } => None,
+            RaftCommand::SetConfigSchema { config_id, .. } => Some(*config_id),
+            RaftCommand::RegisterWebhook { .. } => None,
+            RaftCommand::DeleteWebhook { .. } => None,
+            RaftCommand::SoftDeleteNamespace { .. } => None,
+            RaftCommand::UndeleteNamespace { .. } => None,
+            RaftCommand::PurgeExpiredConfigs { .. } => None,
+            RaftCommand::EvictIdempotencyKeys { .. } => None,
+            RaftCommand::SetPrunePolicy { config_id, .. } => Some(*config_id),
+            RaftCommand::PruneVersions { config_id, .. } => Some(*config_id),
+            RaftCommand::Transaction { .. } => None,
+            RaftCommand::BulkCreateConfigs { .. } => None,
+            RaftCommand::SetTenantQuota { .. } => None,
+            RaftCommand::SetTenantLimits { .. } => None,
         }
     }
 
@@ -68,29 +249,65 @@ impl RaftCommand {
             RaftCommand::CreateConfig { creator_id, .. } => Some(*creator_id),
             RaftCommand::CreateVersion { creator_id, .. } => Some(*creator_id),
             RaftCommand::UpdateReleaseRules { .. } => None,
+            RaftCommand::ScheduleRelease { .. } => None,
+            RaftCommand::ActivateScheduledReleases { .. } => None,
+            RaftCommand::SetCanaryPercent { .. } => None,
+            RaftCommand::SetReleaseWeights { .. } => None,
             RaftCommand::DeleteConfig { .. } => None,
             RaftCommand::DeleteVersions { .. } => None,
             RaftCommand::UpdateConfig { .. } => None,
             RaftCommand::ReleaseVersion { .. } => None,
+            RaftCommand::AddConfigDependency { .. } => None,
+            RaftCommand::RemoveConfigDependency { .. } => None,
+            RaftCommand::SetApprovalRequired { .. } => None,
+            RaftCommand::RequestReleaseApproval { requested_by, .. } => Some(*requested_by),
+            RaftCommand::ApproveRelease { .. } => None,
+            RaftCommand::RejectRelease { .. } => None,
+            RaftCommand::RegisterSchema { .. } => None,
+            RaftCommand::SetConfigSchema { .. } => None,
+            RaftCommand::RegisterWebhook { .. } => None,
+            RaftCommand::DeleteWebhook { .. } => None,
+            RaftCommand::SoftDeleteNamespace { .. } => None,
+            RaftCommand::UndeleteNamespace { .. } => None,
+            RaftCommand::PurgeExpiredConfigs { .. } => None,
+            RaftCommand::EvictIdempotencyKeys { .. } => None,
+            RaftCommand::SetPrunePolicy { .. } => None,
+            RaftCommand::PruneVersions { .. } => None,
+            RaftCommand::Transaction { .. } => None,
+            RaftCommand::BulkCreateConfigs { .. } => None,
+            RaftCommand::SetTenantQuota { .. } => None,
+            RaftCommand::SetTenantLimits { .. } => None,
         }
     }
 
     /// Check if this command modifies configuration content
     pub fn modifies_content(&self) -> bool {
-        matches!(
-            self,
-            RaftCommand::CreateConfig { .. }
-                | RaftCommand::CreateVersion { .. }
-                | RaftCommand::UpdateConfig { .. }
-        )
+        match self {
+            RaftCommand::Transaction { commands } => commands.iter().any(Self::modifies_content),
+            _ => matches!(
+                self,
+                RaftCommand::CreateConfig { .. }
+                    | RaftCommand::CreateVersion { .. }
+                    | RaftCommand::UpdateConfig { .. }
+                    | RaftCommand::BulkCreateConfigs { .. }
+            ),
+        }
     }
 
     /// Check if this command modifies release rules
     pub fn modifies_releases(&self) -> bool {
-        matches!(
-            self,
-            RaftCommand::UpdateReleaseRules { .. } | RaftCommand::ReleaseVersion { .. }
-        )
+        match self {
+            RaftCommand::Transaction { commands } => commands.iter().any(Self::modifies_releases),
+            _ => matches!(
+                self,
+                RaftCommand::UpdateReleaseRules { .. }
+                    | RaftCommand::ReleaseVersion { .. }
+                    | RaftCommand::ScheduleRelease { .. }
+                    | RaftCommand::ActivateScheduledReleases { .. }
+                    | RaftCommand::SetCanaryPercent { .. }
+                    | RaftCommand::SetReleaseWeights { .. }
+            ),
+        }
     }
 
     /// Estimate the memory usage of this command in bytes
@@ -179,6 +396,103 @@ impl RaftCommand {
                 
                 base_size + releases_size
             }
+            RaftCommand::ScheduleRelease { labels, .. }
+            | RaftCommand::SetCanaryPercent { labels, .. } => {
+                let base_size = std::mem::size_of::<RaftCommand>();
+                let labels_size = labels
+                    .iter()
+                    .fold(48, |acc, (k, v)| acc + k.len() + v.len() + 48);
+
+                base_size + labels_size
+            }
+            RaftCommand::ActivateScheduledReleases { .. }
+            | RaftCommand::PurgeExpiredConfigs { .. }
+            | RaftCommand::EvictIdempotencyKeys { .. } => {
+                // Only contains a timestamp
+                std::mem::size_of::<RaftCommand>()
+            }
+            RaftCommand::SetReleaseWeights { weights, .. } => {
+                let base_size = std::mem::size_of::<RaftCommand>();
+                // Vec<(u64, u32)> + heap allocation overhead
+                let weights_size = weights.len() * 16 + 24;
+
+                base_size + weights_size
+            }
+            RaftCommand::AddConfigDependency { .. } | RaftCommand::RemoveConfigDependency { .. } => {
+                // Only contains two u64 values
+                std::mem::size_of::<RaftCommand>()
+            }
+            RaftCommand::SetApprovalRequired { namespace, .. } => {
+                let base_size = std::mem::size_of::<RaftCommand>();
+                let namespace_size = namespace.tenant.len() + namespace.app.len() + namespace.env.len() + 48;
+
+                base_size + namespace_size
+            }
+            RaftCommand::RequestReleaseApproval { .. }
+            | RaftCommand::ApproveRelease { .. }
+            | RaftCommand::RejectRelease { .. } => {
+                // Only contains three u64 values
+                std::mem::size_of::<RaftCommand>()
+            }
+            RaftCommand::RegisterSchema { tenant, name, schema } => {
+                let base_size = std::mem::size_of::<RaftCommand>();
+                let strings_size = tenant.len() + name.len() + schema.len() + 72;
+
+                base_size + strings_size
+            }
+            RaftCommand::SetConfigSchema { .. } => {
+                // Only contains a u64 and an optional u64
+                std::mem::size_of::<RaftCommand>()
+            }
+            RaftCommand::RegisterWebhook {
+                tenant,
+                url,
+                app,
+                env,
+                secret,
+            } => {
+                let base_size = std::mem::size_of::<RaftCommand>();
+                let optional_size = [app, env, secret]
+                    .iter()
+                    .map(|value| value.as_ref().map_or(0, |v| v.len() + 24))
+                    .sum::<usize>();
+                let strings_size = tenant.len() + url.len() + 48 + optional_size;
+
+                base_size + strings_size
+            }
+            RaftCommand::DeleteWebhook { tenant, .. } => {
+                std::mem::size_of::<RaftCommand>() + tenant.len() + 24
+            }
+            RaftCommand::SoftDeleteNamespace { namespace, .. }
+            | RaftCommand::UndeleteNamespace { namespace, .. } => {
+                std::mem::size_of::<RaftCommand>()
+                    + namespace.tenant.len()
+                    + namespace.app.len()
+                    + namespace.env.len()
+                    + 48
+            }
+            RaftCommand::SetPrunePolicy { .. } | RaftCommand::PruneVersions { .. } => {
+                // Only contains a u64 and a (possibly optional) u32
+                std::mem::size_of::<RaftCommand>()
+            }
+            RaftCommand::Transaction { commands } => {
+                std::mem::size_of::<RaftCommand>()
+                    + 24
+                    + commands.iter().map(Self::estimate_size).sum::<usize>()
+            }
+            RaftCommand::BulkCreateConfigs { entries } => {
+                std::mem::size_of::<RaftCommand>()
+                    + 24
+                    + entries.iter().map(BulkConfigEntry::estimate_size).sum::<usize>()
+            }
+            RaftCommand::SetTenantQuota { tenant, .. } => {
+                // Three optional u64 limits besides the tenant
+                std::mem::size_of::<RaftCommand>() + tenant.len() + 24
+            }
+            RaftCommand::SetTenantLimits { tenant, .. } => {
+                // Two optional limits besides the tenant
+                std::mem::size_of::<RaftCommand>() + tenant.len() + 16
+            }
         }
     }
 }
@@ -187,6 +501,42 @@ impl RaftCommand {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ClientRequest {
     pub command: RaftCommand,
+    /// Client-chosen key under which the state machine records the response,
+    /// so a retried request returns that response instead of applying again
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub idempotency_key: Option<StampedIdempotencyKey>,
+}
+
+/// Idempotency key of a request, stamped with the time it was proposed
+///
+/// Every replica decides whether the key has expired and records it with
+/// `proposed_at`, so they agree regardless of their own clocks.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StampedIdempotencyKey {
+    pub key: String,
+    pub proposed_at: DateTime<Utc>,
+}
+
+impl StampedIdempotencyKey {
+    /// Stamp a key with the current time, for a request about to be proposed
+    pub fn now(key: impl Into<String>) -> Self {
+        Self {
+            key: key.into(),
+            proposed_at: Utc::now(),
+        }
+    }
+}
+
+impl ClientRequest {
+    /// Size of the request payload in bytes
+    ///
+    /// Measured by serializing the request into a compact binary encoding, so
+    /// config content, labels and other heap data count at their real length.
+    /// Falls back to `RaftCommand::estimate_size` if the request cannot be encoded.
+    pub fn payload_size(&self) -> usize {
+        bincode::serde::encode_into_std_write(self, &mut std::io::sink(), bincode::config::standard())
+            .unwrap_or_else(|_| std::mem::size_of_val(self) + self.command.estimate_size())
+    }
 }
 
 /// Client response for write operations
@@ -196,6 +546,9 @@ pub struct ClientWriteResponse {
     pub success: bool,
     pub message: String,
     pub data: Option<serde_json::Value>,
+    /// Index of the Raft log entry the write was committed at, filled in by the node
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub log_index: Option<u64>,
 }
 
 impl Default for ClientWriteResponse {
@@ -205,6 +558,7 @@ impl Default for ClientWriteResponse {
             success: false,
             message: "No operation performed".to_string(),
             data: None,
+            log_index: None,
         }
     }
 }
\ No newline at end of file
@@ -137,7 +137,7 @@ mod tests {
             description: "test".to_string(),
         };
 
-        let request = ClientRequest { command };
+        let request = ClientRequest { command, idempotency_key: None };
         let serialized = serde_json::to_string(&request).unwrap();
         let deserialized: ClientRequest = serde_json::from_str(&serialized).unwrap();
 
@@ -165,6 +165,7 @@ mod tests {
             success: true,
             message: "Operation successful".to_string(),
             data: Some(serde_json::json!({"version": 1})),
+            log_index: None,
         };
 
         assert_eq!(response.config_id, Some(123));
@@ -180,6 +181,7 @@ mod tests {
             success: true,
             message: "Test message".to_string(),
             data: Some(serde_json::json!({"key": "value"})),
+            log_index: None,
         };
 
         let serialized = serde_json::to_string(&response).unwrap();
@@ -190,4 +192,25 @@ mod tests {
         assert_eq!(response.message, deserialized.message);
         assert_eq!(response.data, deserialized.data);
     }
+
+    #[test]
+    fn test_payload_size_counts_content_bytes() {
+        let request = |len: usize| ClientRequest {
+            command: RaftCommand::CreateVersion {
+                config_id: 1,
+                content: vec![b'x'; len],
+                format: None,
+                creator_id: 1,
+                description: "payload".to_string(),
+            },
+            idempotency_key: None,
+        };
+
+        let small = request(16).payload_size();
+        let large = request(2 * 1024 * 1024).payload_size();
+        assert!(large >= 2 * 1024 * 1024);
+        // Only the varint length prefix grows beyond the content itself
+        let growth = large - small - (2 * 1024 * 1024 - 16);
+        assert!(growth <= 8);
+    }
 }
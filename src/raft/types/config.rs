@@ -1,7 +1,13 @@
+use regex::Regex;
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::str::FromStr;
 use super::helpers::make_config_key;
 
+/// Client label that identifies a client for canary routing
+pub const CANARY_CLIENT_LABEL: &str = "instance_id";
+
 /// Configuration namespace identifier
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub struct ConfigNamespace {
@@ -24,6 +30,11 @@ pub enum ConfigFormat {
     Toml,
     Properties,
     Xml,
+    /// Opaque binary content such as certificates or compiled schemas, stored
+    /// verbatim and never parsed, validated or rendered as text
+    Binary,
+    /// Format not given by the caller, detected from the content on write
+    Unknown,
 }
 
 /// Core configuration metadata
@@ -35,8 +46,20 @@ pub struct Config {
     pub latest_version_id: u64,
     pub releases: Vec<Release>,
     pub schema: Option<String>,
+    /// Registered schema that new versions are validated against
+    #[serde(default)]
+    pub schema_id: Option<u64>,
     pub created_at: chrono::DateTime<chrono::Utc>,
     pub updated_at: chrono::DateTime<chrono::Utc>,
+    /// Time the config was soft-deleted together with its namespace (None = live)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deleted_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// End of the recovery window, after which a soft-deleted config is purged
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub purge_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Number of versions kept before the oldest are pruned (None = namespace default)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_versions: Option<u32>,
 }
 
 impl Config {
@@ -45,19 +68,45 @@ impl Config {
         make_config_key(&self.namespace, &self.name)
     }
 
+    /// Check if this config has been soft-deleted
+    pub fn is_deleted(&self) -> bool {
+        self.deleted_at.is_some()
+    }
+
     /// Get the default release (highest priority or fallback)
-    pub fn get_default_release(&self) -> Option<&Release> {
-        self.releases.iter().max_by_key(|r| r.priority)
+    ///
+    /// Releases that have not yet become effective and canary releases, which
+    /// only serve their own share of clients, are ignored. Releases sharing
+    /// the winning priority split traffic by weight like matching releases do.
+    pub fn get_default_release(&self, client_labels: &BTreeMap<String, String>) -> Option<&Release> {
+        let now = chrono::Utc::now();
+        let candidates = self
+            .releases
+            .iter()
+            .filter(|r| r.is_effective_at(now) && r.canary_percent.is_none())
+            .collect();
+        self.pick_release(candidates, client_labels)
     }
 
     /// Find matching release for given client labels
+    ///
+    /// Releases are tried in descending priority. A canary release only
+    /// matches the clients selected by [`Release::selects_client`]; the
+    /// others fall through to the next matching release, so a percentage
+    /// rollout needs a higher priority than the release it is replacing.
+    ///
+    /// When several non-canary releases share the winning label set, priority
+    /// and `effective_at`, traffic is split between them by `weight`, see
+    /// [`Release::pick_weighted`].
     pub fn find_matching_release(
         &self,
         client_labels: &BTreeMap<String, String>,
     ) -> Option<&Release> {
-        let mut matching_releases: Vec<_> = self
+        let now = chrono::Utc::now();
+        let matching_releases = self
             .releases
             .iter()
+            .filter(|release| release.is_effective_at(now))
             .filter(|release| {
                 // Check if client labels match release labels
                 release
@@ -65,13 +114,49 @@ impl Config {
                     .iter()
                     .all(|(key, value)| client_labels.get(key) == Some(value))
             })
+            .filter(|release| release.selects_client(self.id, client_labels))
             .collect();
+        self.pick_release(matching_releases, client_labels)
+    }
 
-        // Sort by priority (descending)
-        matching_releases.sort_by(|a, b| b.priority.cmp(&a.priority));
+    /// Pick the release serving a client among `candidates`
+    fn pick_release<'a>(
+        &'a self,
+        mut candidates: Vec<&'a Release>,
+        client_labels: &BTreeMap<String, String>,
+    ) -> Option<&'a Release> {
+        // Sort by priority (descending); on ties a scheduled release that has
+        // become effective wins over the release it replaces
+        candidates.sort_by(|a, b| {
+            (b.priority, b.effective_at).cmp(&(a.priority, a.effective_at))
+        });
+
+        let best = *candidates.first()?;
+        if best.canary_percent.is_some() {
+            return Some(best);
+        }
 
-        // Return the highest priority matching release
-        matching_releases.first().copied()
+        // Releases sharing the winning rule split its traffic by weight
+        let group: Vec<&Release> = candidates
+            .into_iter()
+            .filter(|r| {
+                r.canary_percent.is_none()
+                    && r.labels == best.labels
+                    && r.priority == best.priority
+                    && r.effective_at == best.effective_at
+            })
+            .collect();
+        Release::pick_weighted(&group, self.id, client_labels).or(Some(best))
+    }
+
+    /// Check if any release of this config targets labels matching the query
+    ///
+    /// Each release's label set is evaluated on its own, so `AND` only
+    /// matches labels that are targeted together by the same release.
+    pub fn matches_label_query(&self, query: &LabelQuery) -> bool {
+        self.releases
+            .iter()
+            .any(|release| query.matches(&release.labels))
     }
 }
 
@@ -81,6 +166,22 @@ pub struct Release {
     pub labels: BTreeMap<String, String>,
     pub version_id: u64,
     pub priority: i32,
+    /// Time at which this release becomes active (None = active immediately)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub effective_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Share of matching clients (0-100) served by this release (None = all)
+    ///
+    /// Also accepted as `rollout_percentage` in release rule payloads.
+    #[serde(default, alias = "rollout_percentage", skip_serializing_if = "Option::is_none")]
+    pub canary_percent: Option<u8>,
+    /// Relative share of traffic when several releases have the same labels
+    /// and priority (0 = never selected while others have weight)
+    #[serde(default = "default_release_weight")]
+    pub weight: u32,
+}
+
+fn default_release_weight() -> u32 {
+    1
 }
 
 impl Release {
@@ -90,6 +191,9 @@ impl Release {
             labels,
             version_id,
             priority,
+            effective_at: None,
+            canary_percent: None,
+            weight: default_release_weight(),
         }
     }
 
@@ -99,9 +203,17 @@ impl Release {
             labels: BTreeMap::new(),
             version_id,
             priority: 0,
+            effective_at: None,
+            canary_percent: None,
+            weight: default_release_weight(),
         }
     }
 
+    /// Check if this release is active at the given time
+    pub fn is_effective_at(&self, now: chrono::DateTime<chrono::Utc>) -> bool {
+        self.effective_at.is_none_or(|at| at <= now)
+    }
+
     /// Check if this release matches the given client labels
     pub fn matches(&self, client_labels: &BTreeMap<String, String>) -> bool {
         self.labels
@@ -109,8 +221,394 @@ impl Release {
             .all(|(key, value)| client_labels.get(key) == Some(value))
     }
 
+    /// Check if this release serves a client whose labels match it
+    ///
+    /// Non-canary releases serve every client. A canary release serves a
+    /// client when the hash of the config ID and the client's
+    /// [`CANARY_CLIENT_LABEL`] falls below `canary_percent`, so each client
+    /// keeps getting the same version and raising the percentage only adds
+    /// clients. Clients without the label never receive a canary.
+    pub fn selects_client(
+        &self,
+        config_id: u64,
+        client_labels: &BTreeMap<String, String>,
+    ) -> bool {
+        let Some(percent) = self.canary_percent else {
+            return true;
+        };
+        let Some(client_id) = client_labels.get(CANARY_CLIENT_LABEL) else {
+            return false;
+        };
+        let mut hasher = DefaultHasher::new();
+        config_id.hash(&mut hasher);
+        client_id.hash(&mut hasher);
+        hasher.finish() % 100 < u64::from(percent)
+    }
+
+    /// Pick one of several releases sharing a rule, in proportion to their weights
+    ///
+    /// The hash of the config ID and the client's [`CANARY_CLIENT_LABEL`]
+    /// selects a point in the total weight, so each client keeps getting the
+    /// same version while the weights are unchanged. Clients without the
+    /// label get the release with the highest weight. Returns None only for
+    /// an empty slice.
+    pub fn pick_weighted<'a>(
+        releases: &[&'a Release],
+        config_id: u64,
+        client_labels: &BTreeMap<String, String>,
+    ) -> Option<&'a Release> {
+        let heaviest = releases.iter().copied().reduce(|best, r| {
+            if r.weight > best.weight { r } else { best }
+        })?;
+        let total: u64 = releases.iter().map(|r| u64::from(r.weight)).sum();
+        let Some(client_id) = client_labels.get(CANARY_CLIENT_LABEL) else {
+            return Some(heaviest);
+        };
+        if total == 0 {
+            return Some(heaviest);
+        }
+
+        // Salted so the split is independent of canary selection
+        let mut hasher = DefaultHasher::new();
+        "weight".hash(&mut hasher);
+        config_id.hash(&mut hasher);
+        client_id.hash(&mut hasher);
+        let mut point = hasher.finish() % total;
+        for release in releases.iter().copied() {
+            let weight = u64::from(release.weight);
+            if point < weight {
+                return Some(release);
+            }
+            point -= weight;
+        }
+        Some(heaviest)
+    }
+
     /// Check if this is a default release (no labels)
     pub fn is_default(&self) -> bool {
         self.labels.is_empty()
     }
 }
+
+/// Error returned when a label query cannot be parsed
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("Invalid label query at position {position}: {message}")]
+pub struct LabelQueryError {
+    /// Byte offset in the query where the error was detected
+    pub position: usize,
+    pub message: String,
+}
+
+impl LabelQueryError {
+    fn new(position: usize, message: impl Into<String>) -> Self {
+        Self {
+            position,
+            message: message.into(),
+        }
+    }
+}
+
+/// Boolean expression over labels, used to filter configs by release labels
+///
+/// Grammar (keywords are case-insensitive, `AND` binds tighter than `OR`):
+///
+/// ```text
+/// query := and ("OR" and)*
+/// and   := term ("AND" term)*
+/// term  := "(" query ")"
+///        | key "=" value | key "!=" value | key "~=" regex
+///        | key "IN" value ("," value)* | key "IN" "(" value ("," value)* ")"
+///        | key "IN" "[" value ("," value)* "]"
+/// ```
+///
+/// Keys and values are bare words or double-quoted strings; quote values
+/// that contain whitespace, parentheses, brackets, commas or operator
+/// characters.
+/// Regexes must match the whole label value.
+#[derive(Debug, Clone)]
+pub enum LabelQuery {
+    /// Label is present with this value
+    Equals { key: String, value: String },
+    /// Label is absent or has a different value
+    NotEquals { key: String, value: String },
+    /// Label is present and its value matches the regex
+    Matches { key: String, pattern: Regex },
+    /// Label is present with one of these values
+    In { key: String, values: Vec<String> },
+    And(Box<LabelQuery>, Box<LabelQuery>),
+    Or(Box<LabelQuery>, Box<LabelQuery>),
+}
+
+impl LabelQuery {
+    /// Parse a label query expression
+    pub fn parse(input: &str) -> Result<Self, LabelQueryError> {
+        let tokens = tokenize(input)?;
+        if tokens.is_empty() {
+            return Err(LabelQueryError::new(0, "query is empty"));
+        }
+
+        let mut parser = QueryParser {
+            tokens,
+            pos: 0,
+            end: input.len(),
+        };
+        let query = parser.parse_or()?;
+        if parser.peek().is_some() {
+            return Err(LabelQueryError::new(
+                parser.position(),
+                "expected AND, OR or end of query",
+            ));
+        }
+        Ok(query)
+    }
+
+    /// Evaluate the query against a set of labels
+    pub fn matches(&self, labels: &BTreeMap<String, String>) -> bool {
+        match self {
+            Self::Equals { key, value } => labels.get(key) == Some(value),
+            Self::NotEquals { key, value } => labels.get(key) != Some(value),
+            Self::Matches { key, pattern } => labels
+                .get(key)
+                .is_some_and(|value| pattern.is_match(value)),
+            Self::In { key, values } => labels
+                .get(key)
+                .is_some_and(|value| values.contains(value)),
+            Self::And(left, right) => left.matches(labels) && right.matches(labels),
+            Self::Or(left, right) => left.matches(labels) || right.matches(labels),
+        }
+    }
+}
+
+impl FromStr for LabelQuery {
+    type Err = LabelQueryError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum QueryToken {
+    Word(String),
+    Quoted(String),
+    LParen,
+    RParen,
+    LBracket,
+    RBracket,
+    Comma,
+    Eq,
+    NotEq,
+    RegexEq,
+}
+
+/// Characters that end a bare word
+const QUERY_SPECIAL_CHARS: &str = "()[]=,!~\"";
+
+fn tokenize(input: &str) -> Result<Vec<(usize, QueryToken)>, LabelQueryError> {
+    let mut tokens = Vec::new();
+    let mut chars = input.char_indices().peekable();
+
+    while let Some(&(pos, c)) = chars.peek() {
+        match c {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '(' | ')' | '[' | ']' | ',' | '=' => {
+                chars.next();
+                let token = match c {
+                    '(' => QueryToken::LParen,
+                    ')' => QueryToken::RParen,
+                    '[' => QueryToken::LBracket,
+                    ']' => QueryToken::RBracket,
+                    ',' => QueryToken::Comma,
+                    _ => QueryToken::Eq,
+                };
+                tokens.push((pos, token));
+            }
+            '!' | '~' => {
+                chars.next();
+                if chars.next_if(|&(_, next)| next == '=').is_none() {
+                    return Err(LabelQueryError::new(pos, format!("expected '=' after '{}'", c)));
+                }
+                let token = if c == '!' {
+                    QueryToken::NotEq
+                } else {
+                    QueryToken::RegexEq
+                };
+                tokens.push((pos, token));
+            }
+            '"' => {
+                chars.next();
+                let mut value = String::new();
+                loop {
+                    match chars.next() {
+                        Some((_, '"')) => break,
+                        // Only quotes and backslashes are escaped, so regex
+                        // escapes such as `\d` pass through unchanged
+                        Some((_, '\\')) => {
+                            match chars.next_if(|&(_, next)| next == '"' || next == '\\') {
+                                Some((_, escaped)) => value.push(escaped),
+                                None => value.push('\\'),
+                            }
+                        }
+                        Some((_, ch)) => value.push(ch),
+                        None => {
+                            return Err(LabelQueryError::new(pos, "unterminated quoted string"));
+                        }
+                    }
+                }
+                tokens.push((pos, QueryToken::Quoted(value)));
+            }
+            _ => {
+                let mut word = String::new();
+                while let Some((_, ch)) = chars
+                    .next_if(|&(_, ch)| !ch.is_whitespace() && !QUERY_SPECIAL_CHARS.contains(ch))
+                {
+                    word.push(ch);
+                }
+                tokens.push((pos, QueryToken::Word(word)));
+            }
+        }
+    }
+
+    Ok(tokens)
+}
+
+fn is_keyword(word: &str) -> bool {
+    ["AND", "OR", "IN"]
+        .iter()
+        .any(|keyword| word.eq_ignore_ascii_case(keyword))
+}
+
+/// Recursive descent parser over the tokens of a label query
+struct QueryParser {
+    tokens: Vec<(usize, QueryToken)>,
+    pos: usize,
+    /// Length of the input, reported as the position of errors at the end
+    end: usize,
+}
+
+impl QueryParser {
+    fn peek(&self) -> Option<&QueryToken> {
+        self.tokens.get(self.pos).map(|(_, token)| token)
+    }
+
+    fn position(&self) -> usize {
+        self.tokens
+            .get(self.pos)
+            .map(|(position, _)| *position)
+            .unwrap_or(self.end)
+    }
+
+    fn advance(&mut self) -> Option<QueryToken> {
+        let token = self.tokens.get(self.pos).map(|(_, token)| token.clone());
+        self.pos += 1;
+        token
+    }
+
+    fn peek_keyword(&self, keyword: &str) -> bool {
+        matches!(self.peek(), Some(QueryToken::Word(word)) if word.eq_ignore_ascii_case(keyword))
+    }
+
+    fn parse_or(&mut self) -> Result<LabelQuery, LabelQueryError> {
+        let mut query = self.parse_and()?;
+        while self.peek_keyword("OR") {
+            self.advance();
+            let right = self.parse_and()?;
+            query = LabelQuery::Or(Box::new(query), Box::new(right));
+        }
+        Ok(query)
+    }
+
+    fn parse_and(&mut self) -> Result<LabelQuery, LabelQueryError> {
+        let mut query = self.parse_term()?;
+        while self.peek_keyword("AND") {
+            self.advance();
+            let right = self.parse_term()?;
+            query = LabelQuery::And(Box::new(query), Box::new(right));
+        }
+        Ok(query)
+    }
+
+    fn parse_term(&mut self) -> Result<LabelQuery, LabelQueryError> {
+        if self.peek() == Some(&QueryToken::LParen) {
+            self.advance();
+            let query = self.parse_or()?;
+            self.expect(QueryToken::RParen, "')'")?;
+            return Ok(query);
+        }
+
+        let key = self.parse_value("label key")?;
+        let position = self.position();
+        match self.advance() {
+            Some(QueryToken::Eq) => {
+                let value = self.parse_value("label value")?;
+                Ok(LabelQuery::Equals { key, value })
+            }
+            Some(QueryToken::NotEq) => {
+                let value = self.parse_value("label value")?;
+                Ok(LabelQuery::NotEquals { key, value })
+            }
+            Some(QueryToken::RegexEq) => {
+                let position = self.position();
+                let pattern = self.parse_value("regex")?;
+                let pattern = Regex::new(&format!("^(?:{})$", pattern)).map_err(|e| {
+                    LabelQueryError::new(position, format!("invalid regex: {}", e))
+                })?;
+                Ok(LabelQuery::Matches { key, pattern })
+            }
+            Some(QueryToken::Word(word)) if word.eq_ignore_ascii_case("IN") => {
+                let values = self.parse_value_list()?;
+                Ok(LabelQuery::In { key, values })
+            }
+            _ => Err(LabelQueryError::new(
+                position,
+                "expected '=', '!=', '~=' or IN",
+            )),
+        }
+    }
+
+    fn parse_value_list(&mut self) -> Result<Vec<String>, LabelQueryError> {
+        let closing = match self.peek() {
+            Some(QueryToken::LParen) => Some((QueryToken::RParen, "')'")),
+            Some(QueryToken::LBracket) => Some((QueryToken::RBracket, "']'")),
+            _ => None,
+        };
+        if closing.is_some() {
+            self.advance();
+        }
+
+        let mut values = vec![self.parse_value("label value")?];
+        while self.peek() == Some(&QueryToken::Comma) {
+            self.advance();
+            values.push(self.parse_value("label value")?);
+        }
+
+        if let Some((token, expected)) = closing {
+            self.expect(token, expected)?;
+        }
+        Ok(values)
+    }
+
+    fn parse_value(&mut self, expected: &str) -> Result<String, LabelQueryError> {
+        let position = self.position();
+        match self.advance() {
+            Some(QueryToken::Word(word)) if !is_keyword(&word) => Ok(word),
+            Some(QueryToken::Quoted(value)) => Ok(value),
+            _ => Err(LabelQueryError::new(position, format!("expected {}", expected))),
+        }
+    }
+
+    fn expect(&mut self, token: QueryToken, expected: &str) -> Result<(), LabelQueryError> {
+        let position = self.position();
+        if self.advance() == Some(token) {
+            Ok(())
+        } else {
+            Err(LabelQueryError::new(position, format!("expected {}", expected)))
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "config_tests.rs"]
+mod tests;
@@ -0,0 +1,208 @@
+use super::*;
+
+fn labels(pairs: &[(&str, &str)]) -> BTreeMap<String, String> {
+    pairs
+        .iter()
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect()
+}
+
+fn matches(query: &str, pairs: &[(&str, &str)]) -> bool {
+    LabelQuery::parse(query).unwrap().matches(&labels(pairs))
+}
+
+#[test]
+fn test_label_query_equals_and_not_equals() {
+    assert!(matches("env=prod", &[("env", "prod")]));
+    assert!(!matches("env = prod", &[("env", "staging")]));
+    assert!(!matches("env=prod", &[]));
+
+    assert!(matches("env!=prod", &[("env", "staging")]));
+    assert!(matches("env != prod", &[]));
+    assert!(!matches("env!=prod", &[("env", "prod")]));
+}
+
+#[test]
+fn test_label_query_in() {
+    assert!(matches("region IN us-east-1", &[("region", "us-east-1")]));
+    assert!(matches("region in (us-east-1, us-west-2)", &[("region", "us-west-2")]));
+    assert!(matches("region IN us-east-1,eu-west-1", &[("region", "eu-west-1")]));
+    assert!(!matches("region IN (us-east-1, us-west-2)", &[("region", "eu-west-1")]));
+    assert!(!matches("region IN us-east-1", &[]));
+}
+
+#[test]
+fn test_label_query_in_bracket_list() {
+    let query = "region IN [us-east-1, eu-west-1]";
+    assert!(matches(query, &[("region", "us-east-1")]));
+    assert!(matches(query, &[("region", "eu-west-1")]));
+    assert!(!matches(query, &[("region", "us-west-2")]));
+    assert!(!matches(query, &[]));
+
+    match LabelQuery::parse(query).unwrap() {
+        LabelQuery::In { key, values } => {
+            assert_eq!(key, "region");
+            assert_eq!(values, vec!["us-east-1", "eu-west-1"]);
+        }
+        other => panic!("Expected an IN query, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_label_query_regex() {
+    assert!(matches("region ~= us-.*", &[("region", "us-east-1")]));
+    assert!(matches(r#"zone ~= "[a-c]\d""#, &[("zone", "b2")]));
+    // The regex must match the whole value
+    assert!(!matches("region ~= east", &[("region", "us-east-1")]));
+    assert!(!matches("region ~= us-.*", &[]));
+}
+
+#[test]
+fn test_label_query_boolean_logic() {
+    let prod_east = [("env", "prod"), ("region", "us-east-1")];
+    let prod_eu = [("env", "prod"), ("region", "eu-west-1")];
+    let staging_east = [("env", "staging"), ("region", "us-east-1")];
+
+    assert!(matches("env=prod AND region IN us-east-1", &prod_east));
+    assert!(!matches("env=prod AND region IN us-east-1", &prod_eu));
+    assert!(matches("env=staging OR region=eu-west-1", &prod_eu));
+    assert!(!matches("env=staging or region=eu-west-1", &prod_east));
+
+    // AND binds tighter than OR
+    let query = "env=staging OR env=prod AND region=eu-west-1";
+    assert!(matches(query, &staging_east));
+    assert!(matches(query, &prod_eu));
+    assert!(!matches(query, &prod_east));
+
+    // Parentheses override precedence
+    let query = "(env=staging OR env=prod) AND region=us-east-1";
+    assert!(matches(query, &staging_east));
+    assert!(matches(query, &prod_east));
+    assert!(!matches(query, &prod_eu));
+
+    let nested = "((env=prod AND (region ~= us-.* OR tier IN (gold, silver))) OR team != ops)";
+    assert!(matches(nested, &[("env", "prod"), ("region", "eu-west-1"), ("tier", "gold"), ("team", "ops")]));
+    assert!(!matches(nested, &[("env", "prod"), ("region", "eu-west-1"), ("team", "ops")]));
+    assert!(matches(nested, &[("team", "dev")]));
+}
+
+#[test]
+fn test_label_query_quoted_values() {
+    assert!(matches(r#""team name" = "core (infra)""#, &[("team name", "core (infra)")]));
+    assert!(matches(r#"keyword = "AND""#, &[("keyword", "AND")]));
+    assert!(matches(r#"quote = "say \"hi\"""#, &[("quote", r#"say "hi""#)]));
+}
+
+#[test]
+fn test_label_query_malformed() {
+    let cases = [
+        ("", 0),
+        ("   ", 0),
+        ("env", 3),
+        ("env=", 4),
+        ("env prod", 4),
+        ("env=prod AND", 12),
+        ("env=prod OR OR env=dev", 12),
+        ("(env=prod", 9),
+        ("env=prod)", 8),
+        ("env=prod region=us", 9),
+        ("region IN ()", 11),
+        ("region IN (a, b", 15),
+        ("region IN [a, b", 15),
+        ("region IN (a, b]", 15),
+        ("region = [a]", 9),
+        ("env ! prod", 4),
+        ("env=\"prod", 4),
+        ("AND=prod", 0),
+        ("region ~= \"[a-\"", 10),
+    ];
+
+    for (query, position) in cases {
+        let err = LabelQuery::parse(query).unwrap_err();
+        assert_eq!(err.position, position, "query {:?}: {}", query, err);
+    }
+    assert!("env=(".parse::<LabelQuery>().is_err());
+}
+
+#[test]
+fn test_config_matches_label_query_per_release() {
+    let config = Config {
+        id: 1,
+        namespace: ConfigNamespace {
+            tenant: "tenant".to_string(),
+            app: "app".to_string(),
+            env: "prod".to_string(),
+        },
+        name: "db.toml".to_string(),
+        latest_version_id: 2,
+        releases: vec![
+            Release::new(labels(&[("env", "prod"), ("region", "us-east-1")]), 2, 10),
+            Release::new(labels(&[("env", "staging"), ("region", "eu-west-1")]), 1, 5),
+        ],
+        schema: None,
+        schema_id: None,
+        created_at: chrono::Utc::now(),
+        updated_at: chrono::Utc::now(),
+        deleted_at: None,
+        purge_at: None,
+        max_versions: None,
+    };
+
+    let query = |q: &str| LabelQuery::parse(q).unwrap();
+    assert!(config.matches_label_query(&query("env=prod AND region IN us-east-1")));
+    assert!(config.matches_label_query(&query("region=eu-west-1")));
+    // Labels from different releases are not combined
+    assert!(!config.matches_label_query(&query("env=prod AND region=eu-west-1")));
+}
+
+#[test]
+fn test_weighted_releases_split_traffic() {
+    let weighted = |version_id, weight| Release {
+        weight,
+        ..Release::default(version_id)
+    };
+    let config = Config {
+        id: 7,
+        namespace: ConfigNamespace {
+            tenant: "tenant".to_string(),
+            app: "app".to_string(),
+            env: "prod".to_string(),
+        },
+        name: "ab.json".to_string(),
+        latest_version_id: 3,
+        releases: vec![weighted(1, 70), weighted(2, 20), weighted(3, 10)],
+        schema: None,
+        schema_id: None,
+        created_at: chrono::Utc::now(),
+        updated_at: chrono::Utc::now(),
+        deleted_at: None,
+        purge_at: None,
+        max_versions: None,
+    };
+
+    const CLIENTS: usize = 10_000;
+    let mut served = BTreeMap::new();
+    for i in 0..CLIENTS {
+        let client = labels(&[(CANARY_CLIENT_LABEL, &format!("instance-{}", i))]);
+        let release = config.find_matching_release(&client).unwrap();
+        *served.entry(release.version_id).or_insert(0usize) += 1;
+
+        // The same client keeps getting the same version
+        assert_eq!(config.find_matching_release(&client), Some(release));
+        // Without matching labels, the default release is split the same way
+        assert_eq!(config.get_default_release(&client), Some(release));
+    }
+    for (version_id, expected) in [(1, 0.70), (2, 0.20), (3, 0.10)] {
+        let share = served[&version_id] as f64 / CLIENTS as f64;
+        assert!(
+            (share - expected).abs() < 0.05,
+            "version {} served {:.3}, expected {:.2}",
+            version_id, share, expected
+        );
+    }
+
+    // Clients without an instance ID get the heaviest release
+    let anonymous = config.find_matching_release(&BTreeMap::new()).unwrap();
+    assert_eq!(anonymous.version_id, 1);
+    assert_eq!(config.get_default_release(&BTreeMap::new()).unwrap().version_id, 1);
+}
@@ -0,0 +1,160 @@
+use super::config::ConfigFormat;
+use super::version::ConfigVersion;
+use serde::Serialize;
+
+/// Difference between the content of two versions of a config
+///
+/// Text content is compared line by line. Binary content, or content that is
+/// not valid UTF-8, is never interpreted as text: only whether it changed
+/// and by how many bytes is reported.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "format", rename_all = "lowercase")]
+pub enum VersionDiff {
+    Text {
+        changed: bool,
+        size_delta_bytes: i64,
+        /// Single hunk covering the lines between the common prefix and
+        /// suffix, removed lines prefixed with `-` and added lines with `+`
+        patch: String,
+    },
+    Binary {
+        changed: bool,
+        size_delta_bytes: i64,
+    },
+}
+
+impl VersionDiff {
+    /// Compare the content of version `from` with version `to`
+    pub fn between(from: &ConfigVersion, to: &ConfigVersion) -> Self {
+        let changed = from.content_hash != to.content_hash;
+        let size_delta_bytes = to.content.len() as i64 - from.content.len() as i64;
+
+        match as_text(from).zip(as_text(to)) {
+            Some((old, new)) => VersionDiff::Text {
+                changed,
+                size_delta_bytes,
+                patch: line_patch(old, new),
+            },
+            None => VersionDiff::Binary {
+                changed,
+                size_delta_bytes,
+            },
+        }
+    }
+}
+
+/// Content of a version that is diffed as text
+fn as_text(version: &ConfigVersion) -> Option<&str> {
+    if version.format == ConfigFormat::Binary {
+        return None;
+    }
+    std::str::from_utf8(&version.content).ok()
+}
+
+/// Patch replacing the differing lines of `old` with those of `new`
+///
+/// Empty if the texts are identical. The hunk header gives the 1-based start
+/// line and line count in each text, as in a unified diff.
+fn line_patch(old: &str, new: &str) -> String {
+    let old_lines: Vec<&str> = old.lines().collect();
+    let new_lines: Vec<&str> = new.lines().collect();
+
+    let prefix = old_lines
+        .iter()
+        .zip(&new_lines)
+        .take_while(|(a, b)| a == b)
+        .count();
+    let suffix = old_lines[prefix..]
+        .iter()
+        .rev()
+        .zip(new_lines[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let removed = &old_lines[prefix..old_lines.len() - suffix];
+    let added = &new_lines[prefix..new_lines.len() - suffix];
+    if removed.is_empty() && added.is_empty() {
+        return String::new();
+    }
+
+    let mut patch = format!(
+        "@@ -{},{} +{},{} @@\n",
+        prefix + 1,
+        removed.len(),
+        prefix + 1,
+        added.len()
+    );
+    for line in removed {
+        patch.push('-');
+        patch.push_str(line);
+        patch.push('\n');
+    }
+    for line in added {
+        patch.push('+');
+        patch.push_str(line);
+        patch.push('\n');
+    }
+    patch
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn version(id: u64, content: &[u8], format: ConfigFormat) -> ConfigVersion {
+        ConfigVersion::new(id, 1, content.to_vec(), format, 1, String::new())
+    }
+
+    #[test]
+    fn test_text_diff() {
+        let from = version(1, b"a = 1\nb = 2\nc = 3\n", ConfigFormat::Toml);
+        let to = version(2, b"a = 1\nb = 20\nb2 = 4\nc = 3\n", ConfigFormat::Toml);
+
+        assert_eq!(
+            VersionDiff::between(&from, &to),
+            VersionDiff::Text {
+                changed: true,
+                size_delta_bytes: 8,
+                patch: "@@ -2,1 +2,2 @@\n-b = 2\n+b = 20\n+b2 = 4\n".to_string(),
+            }
+        );
+        assert_eq!(
+            VersionDiff::between(&from, &from),
+            VersionDiff::Text {
+                changed: false,
+                size_delta_bytes: 0,
+                patch: String::new(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_binary_diff() {
+        let from = version(1, &[0x30, 0x82, 0x01, 0x0a], ConfigFormat::Binary);
+        let to = version(2, &[0x30, 0x82, 0x01, 0x0a, 0x02, 0x82], ConfigFormat::Binary);
+
+        let diff = VersionDiff::between(&from, &to);
+        assert_eq!(
+            diff,
+            VersionDiff::Binary {
+                changed: true,
+                size_delta_bytes: 2,
+            }
+        );
+        assert_eq!(
+            serde_json::to_value(&diff).unwrap(),
+            serde_json::json!({ "format": "binary", "changed": true, "size_delta_bytes": 2 })
+        );
+
+        // Binary content that happens to be valid UTF-8 is not diffed as text
+        let from = version(1, b"same", ConfigFormat::Binary);
+        let to = version(2, b"same", ConfigFormat::Binary);
+        assert_eq!(
+            VersionDiff::between(&from, &to),
+            VersionDiff::Binary {
+                changed: false,
+                size_delta_bytes: 0,
+            }
+        );
+    }
+}
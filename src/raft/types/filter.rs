@@ -0,0 +1,93 @@
+use serde::{Deserialize, Serialize};
+use super::config::{Config, ConfigNamespace};
+use super::version::ConfigVersion;
+
+/// Filter criteria for searching configurations by metadata
+///
+/// All criteria are optional; a config must satisfy every criterion that is set.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct ConfigFilter {
+    /// Restrict results to the configs of one tenant
+    pub tenant: Option<String>,
+    /// Restrict results to a single namespace
+    pub namespace: Option<ConfigNamespace>,
+    /// Config name must start with this prefix
+    pub name_prefix: Option<String>,
+    /// Config must have been created strictly after this instant
+    pub created_after: Option<chrono::DateTime<chrono::Utc>>,
+    /// Config must have been created strictly before this instant
+    pub created_before: Option<chrono::DateTime<chrono::Utc>>,
+    /// At least one version must have been created by this user
+    pub creator_id: Option<u64>,
+    /// At least one version description must contain this keyword (case-insensitive)
+    pub description_contains: Option<String>,
+}
+
+impl ConfigFilter {
+    /// Check the criteria that only depend on config metadata
+    pub fn matches_config(&self, config: &Config) -> bool {
+        if let Some(ref tenant) = self.tenant {
+            if config.namespace.tenant != *tenant {
+                return false;
+            }
+        }
+        if let Some(ref namespace) = self.namespace {
+            if config.namespace != *namespace {
+                return false;
+            }
+        }
+        if let Some(ref prefix) = self.name_prefix {
+            if !config.name.starts_with(prefix.as_str()) {
+                return false;
+            }
+        }
+        if let Some(after) = self.created_after {
+            if config.created_at <= after {
+                return false;
+            }
+        }
+        if let Some(before) = self.created_before {
+            if config.created_at >= before {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Whether any version-level criteria are set
+    pub fn has_version_criteria(&self) -> bool {
+        self.creator_id.is_some() || self.description_contains.is_some()
+    }
+
+    /// Check the criteria that depend on the versions of a config
+    pub fn matches_versions<'a, I>(&self, versions: I) -> bool
+    where
+        I: IntoIterator<Item = &'a ConfigVersion>,
+    {
+        let keyword = self
+            .description_contains
+            .as_ref()
+            .map(|k| k.to_lowercase());
+
+        let mut creator_matched = self.creator_id.is_none();
+        let mut description_matched = keyword.is_none();
+
+        for version in versions {
+            if !creator_matched && self.creator_id == Some(version.creator_id) {
+                creator_matched = true;
+            }
+            if !description_matched {
+                if let Some(ref keyword) = keyword {
+                    if version.description.to_lowercase().contains(keyword.as_str()) {
+                        description_matched = true;
+                    }
+                }
+            }
+            if creator_matched && description_matched {
+                return true;
+            }
+        }
+
+        creator_matched && description_matched
+    }
+}
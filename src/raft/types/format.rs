@@ -0,0 +1,93 @@
+use super::config::ConfigFormat;
+use yaml_rust2::{Yaml, YamlLoader};
+
+/// Error returned when the format of content cannot be detected unambiguously
+pub const AMBIGUOUS_FORMAT: &str = "ambiguous format";
+
+impl ConfigFormat {
+    /// Detect the format of config content by parsing it as JSON, TOML and YAML
+    ///
+    /// Content that is not UTF-8 or contains NUL bytes is [`ConfigFormat::Binary`]
+    /// and is not handed to any text parser. Otherwise the format is returned
+    /// only if exactly one parser accepts the content.
+    /// JSON is a subset of YAML, so YAML is only tried for content that is
+    /// not JSON, and only documents whose root is a mapping or sequence count
+    /// as YAML; any other text would parse as a YAML string scalar.
+    /// Empty content is never detected.
+    pub fn detect(content: &[u8]) -> Option<ConfigFormat> {
+        let Some(text) = as_text(content) else {
+            return Some(ConfigFormat::Binary);
+        };
+        if text.trim().is_empty() {
+            return None;
+        }
+
+        let is_json = serde_json::from_str::<serde_json::Value>(text).is_ok();
+        let is_toml = toml::from_str::<toml::Table>(text).is_ok();
+        let is_yaml = !is_json && is_yaml_document(text);
+
+        match (is_json, is_toml, is_yaml) {
+            (true, false, false) => Some(ConfigFormat::Json),
+            (false, true, false) => Some(ConfigFormat::Toml),
+            (false, false, true) => Some(ConfigFormat::Yaml),
+            _ => None,
+        }
+    }
+}
+
+/// `content` as text, or None if it is binary
+///
+/// NUL bytes are valid UTF-8 but do not occur in text config formats.
+fn as_text(content: &[u8]) -> Option<&str> {
+    if content.contains(&0) {
+        return None;
+    }
+    std::str::from_utf8(content).ok()
+}
+
+/// Whether `text` is a single YAML document with a mapping or sequence root
+pub(crate) fn is_yaml_document(text: &str) -> bool {
+    match YamlLoader::load_from_str(text) {
+        Ok(docs) => matches!(docs.as_slice(), [Yaml::Hash(_) | Yaml::Array(_)]),
+        Err(_) => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_json() {
+        let content = br#"{"database": {"host": "localhost", "port": 5432}}"#;
+        assert_eq!(ConfigFormat::detect(content), Some(ConfigFormat::Json));
+        assert_eq!(ConfigFormat::detect(b"[1, 2, 3]"), Some(ConfigFormat::Json));
+    }
+
+    #[test]
+    fn test_detect_toml() {
+        let content = b"title = \"app\"\n\n[database]\nhost = \"localhost\"\nport = 5432\n";
+        assert_eq!(ConfigFormat::detect(content), Some(ConfigFormat::Toml));
+    }
+
+    #[test]
+    fn test_detect_yaml() {
+        let content = b"database:\n  host: localhost\n  port: 5432\nfeatures:\n  - a\n  - b\n";
+        assert_eq!(ConfigFormat::detect(content), Some(ConfigFormat::Yaml));
+    }
+
+    #[test]
+    fn test_detect_binary() {
+        let binary = Some(ConfigFormat::Binary);
+        assert_eq!(ConfigFormat::detect(&[0xff, 0xfe, 0x00, 0x01, 0x89]), binary);
+        assert_eq!(ConfigFormat::detect(b"\x00\x01\x02\x03"), binary);
+        // Valid UTF-8 with NUL bytes is not text either
+        assert_eq!(ConfigFormat::detect(b"{\"a\": 1}\x00"), binary);
+    }
+
+    #[test]
+    fn test_detect_rejects_plain_text() {
+        assert_eq!(ConfigFormat::detect(b"just some words"), None);
+        assert_eq!(ConfigFormat::detect(b"  \n"), None);
+    }
+}
@@ -1,31 +1,43 @@
-use openraft::{BasicNode, Raft};
+use openraft::Raft;
 
 // 子模块声明
+pub mod approval;
 pub mod config;
 pub mod version;
 pub mod command;
+pub mod diff;
+pub mod filter;
+pub mod format;
 pub mod helpers;
+pub mod node;
+pub mod schema;
+pub mod webhook;
 
 // 重新导出所有公共类型
+pub use approval::*;
 pub use config::*;
 pub use version::*;
 pub use command::*;
+pub use diff::*;
+pub use filter::*;
+pub use format::*;
 pub use helpers::*;
+pub use node::*;
+pub use schema::*;
+pub use webhook::*;
 
 /// Node ID type for the Raft cluster
 pub type NodeId = u64;
 
-/// Node information for cluster membership
-pub type Node = BasicNode;
-
-// Declare Raft types using openraft macro
+// Declare Raft types using openraft macro; snapshot data lives in a temporary
+// file so that it is never held in memory as a whole
 openraft::declare_raft_types!(
     pub TypeConfig:
         D = ClientRequest,
         R = ClientWriteResponse,
         NodeId = NodeId,
         Node = Node,
-        SnapshotData = std::io::Cursor<Vec<u8>>,
+        SnapshotData = tokio::fs::File,
 );
 
 /// Type alias for the Raft instance
@@ -38,6 +50,8 @@ pub struct RaftMetrics {
     pub current_term: u64,
     pub last_log_index: u64,
     pub last_applied: u64,
+    /// Highest log index known to be committed by this node
+    pub commit_index: u64,
     pub leader_id: Option<NodeId>,
     pub membership: std::collections::BTreeSet<NodeId>,
     pub is_leader: bool,
@@ -0,0 +1,70 @@
+use super::NodeId;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Key under which a node's election priority is stored in `Node::data`
+pub const ELECTION_PRIORITY_KEY: &str = "election_priority";
+
+/// Election priority of nodes that never set one
+pub const DEFAULT_ELECTION_PRIORITY: u8 = 128;
+
+/// Node information for cluster membership
+///
+/// Serializes compatibly with openraft's `BasicNode`, with node-level
+/// attributes such as the election priority kept in `data`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Node {
+    pub addr: String,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub data: BTreeMap<String, String>,
+}
+
+impl Node {
+    pub fn new(addr: impl Into<String>) -> Self {
+        Self {
+            addr: addr.into(),
+            data: BTreeMap::new(),
+        }
+    }
+
+    /// Election priority of this node; higher is preferred as leader
+    pub fn election_priority(&self) -> u8 {
+        self.data
+            .get(ELECTION_PRIORITY_KEY)
+            .and_then(|priority| priority.parse().ok())
+            .unwrap_or(DEFAULT_ELECTION_PRIORITY)
+    }
+
+    pub fn with_election_priority(mut self, priority: u8) -> Self {
+        self.data
+            .insert(ELECTION_PRIORITY_KEY.to_string(), priority.to_string());
+        self
+    }
+}
+
+/// Role of a node in the current cluster membership
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MemberRole {
+    Voter,
+    Learner,
+}
+
+/// A cluster member together with its Raft RPC address and role
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MemberInfo {
+    pub node_id: NodeId,
+    pub address: String,
+    pub role: MemberRole,
+}
+
+impl fmt::Display for Node {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.addr)?;
+        if !self.data.is_empty() {
+            write!(f, " {:?}", self.data)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,464 @@
+use super::config::ConfigFormat;
+use chrono::{DateTime, Utc};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Number, Value};
+use yaml_rust2::{Yaml, YamlLoader};
+
+/// Error returned when content does not conform to the config's registered schema
+pub const SCHEMA_VALIDATION_FAILED: &str = "schema validation failed";
+
+/// A JSON Schema registered once per tenant and referenced by configs by id
+///
+/// Registering a schema under an existing `tenant + name` replaces its
+/// document and keeps its id, so every referencing config picks it up.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RegisteredSchema {
+    pub id: u64,
+    pub tenant: String,
+    pub name: String,
+    /// JSON Schema document
+    pub schema: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl RegisteredSchema {
+    /// Create a schema record
+    pub fn new(id: u64, tenant: &str, name: &str, schema: &str) -> Self {
+        let now = Utc::now();
+        Self {
+            id,
+            tenant: tenant.to_string(),
+            name: name.to_string(),
+            schema: schema.to_string(),
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// Validate config content of `format` against this schema
+    ///
+    /// JSON, YAML and TOML content is checked; other formats cannot be
+    /// mapped onto a JSON document and are rejected.
+    /// Returns every violation found, each prefixed with its path.
+    pub fn validate(&self, content: &[u8], format: &ConfigFormat) -> Result<(), Vec<String>> {
+        let schema = parse_schema(&self.schema).map_err(|e| vec![e])?;
+        let instance = content_to_json(content, format).map_err(|e| vec![e])?;
+        let mut errors = Vec::new();
+        validate_value(&schema, &instance, "$", &mut errors);
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+/// Parse a JSON Schema document, which must be an object or a boolean
+pub fn parse_schema(schema: &str) -> Result<Value, String> {
+    match serde_json::from_str(schema) {
+        Ok(value @ (Value::Object(_) | Value::Bool(_))) => Ok(value),
+        Ok(_) => Err("schema must be a JSON object or boolean".to_string()),
+        Err(e) => Err(format!("schema is not valid JSON: {}", e)),
+    }
+}
+
+/// Parse config content into a JSON document for key lookups
+///
+/// Besides the formats accepted by schema validation, properties files are
+/// read as a flat object keyed by the full property name, with `true`/`false`
+/// and numeric values coerced to their JSON types.
+pub fn content_to_document(content: &[u8], format: &ConfigFormat) -> Result<Value, String> {
+    match format {
+        ConfigFormat::Json | ConfigFormat::Yaml | ConfigFormat::Toml => {
+            content_to_json(content, format)
+        }
+        ConfigFormat::Properties => {
+            let text = std::str::from_utf8(content)
+                .map_err(|_| "content is not valid UTF-8".to_string())?;
+            Ok(properties_to_json(text))
+        }
+        other => Err(format!("{:?} content cannot be read by key", other)),
+    }
+}
+
+/// Parse config content into a JSON document for validation
+fn content_to_json(content: &[u8], format: &ConfigFormat) -> Result<Value, String> {
+    let text = std::str::from_utf8(content).map_err(|_| "content is not valid UTF-8".to_string())?;
+    match format {
+        ConfigFormat::Json => {
+            serde_json::from_str(text).map_err(|e| format!("content is not valid JSON: {}", e))
+        }
+        ConfigFormat::Toml => {
+            toml::from_str(text).map_err(|e| format!("content is not valid TOML: {}", e))
+        }
+        ConfigFormat::Yaml => match YamlLoader::load_from_str(text) {
+            Ok(docs) => Ok(docs.first().map_or(Value::Null, yaml_to_json)),
+            Err(e) => Err(format!("content is not valid YAML: {}", e)),
+        },
+        other => Err(format!("{:?} content cannot be validated against a JSON Schema", other)),
+    }
+}
+
+fn properties_to_json(text: &str) -> Value {
+    let entries = text
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#') && !line.starts_with('!'))
+        .map(|line| match line.find(['=', ':']) {
+            Some(i) => (line[..i].trim(), line[i + 1..].trim()),
+            None => (line, ""),
+        })
+        .map(|(key, value)| (key.to_string(), coerce_scalar(value)));
+    Value::Object(entries.collect())
+}
+
+/// Coerce an untyped scalar to a boolean or number when it reads as one
+fn coerce_scalar(value: &str) -> Value {
+    if let Ok(b) = value.parse::<bool>() {
+        return Value::Bool(b);
+    }
+    if let Ok(i) = value.parse::<i64>() {
+        return Value::from(i);
+    }
+    value
+        .parse::<f64>()
+        .ok()
+        .filter(|f| f.is_finite())
+        .and_then(Number::from_f64)
+        .map_or_else(|| Value::String(value.to_string()), Value::Number)
+}
+
+/// Look up a dotted key path such as `database.hosts.0` in a JSON document
+///
+/// Array elements are addressed by index. A key containing dots, as in
+/// properties files, matches before the path is split.
+pub fn lookup_key_path<'a>(document: &'a Value, path: &str) -> Option<&'a Value> {
+    if let Some(value) = document.get(path) {
+        return Some(value);
+    }
+    path.split('.').try_fold(document, |value, segment| match value {
+        Value::Object(map) => map.get(segment),
+        Value::Array(items) => segment.parse::<usize>().ok().and_then(|i| items.get(i)),
+        _ => None,
+    })
+}
+
+fn yaml_to_json(yaml: &Yaml) -> Value {
+    match yaml {
+        Yaml::Real(s) => s
+            .parse::<f64>()
+            .ok()
+            .and_then(Number::from_f64)
+            .map_or_else(|| Value::String(s.clone()), Value::Number),
+        Yaml::Integer(i) => Value::from(*i),
+        Yaml::String(s) => Value::String(s.clone()),
+        Yaml::Boolean(b) => Value::Bool(*b),
+        Yaml::Array(items) => Value::Array(items.iter().map(yaml_to_json).collect()),
+        Yaml::Hash(hash) => Value::Object(
+            hash.iter()
+                .map(|(k, v)| {
+                    let key = match yaml_to_json(k) {
+                        Value::String(s) => s,
+                        other => other.to_string(),
+                    };
+                    (key, yaml_to_json(v))
+                })
+                .collect::<Map<_, _>>(),
+        ),
+        Yaml::Null | Yaml::Alias(_) | Yaml::BadValue => Value::Null,
+    }
+}
+
+/// Validate `instance` against `schema`, collecting violations into `errors`
+///
+/// Supports the commonly used JSON Schema keywords: `type`, `enum`, `const`,
+/// numeric and string bounds, `pattern`, `items`, `properties`, `required`,
+/// `additionalProperties`, `allOf`, `anyOf`, `oneOf` and `not`.
+/// Unknown keywords are ignored.
+fn validate_value(schema: &Value, instance: &Value, path: &str, errors: &mut Vec<String>) {
+    let schema = match schema {
+        Value::Bool(true) => return,
+        Value::Bool(false) => {
+            errors.push(format!("{}: no value is allowed", path));
+            return;
+        }
+        Value::Object(schema) => schema,
+        _ => return,
+    };
+
+    if let Some(expected) = schema.get("type") {
+        let types: Vec<&str> = match expected {
+            Value::String(t) => vec![t.as_str()],
+            Value::Array(ts) => ts.iter().filter_map(Value::as_str).collect(),
+            _ => Vec::new(),
+        };
+        if !types.is_empty() && !types.iter().any(|t| has_type(instance, t)) {
+            errors.push(format!("{}: expected {}, found {}", path, types.join(" or "), type_name(instance)));
+            return;
+        }
+    }
+    if let Some(Value::Array(allowed)) = schema.get("enum") {
+        if !allowed.contains(instance) {
+            errors.push(format!("{}: {} is not one of {}", path, instance, Value::Array(allowed.clone())));
+        }
+    }
+    if let Some(expected) = schema.get("const") {
+        if expected != instance {
+            errors.push(format!("{}: expected {}", path, expected));
+        }
+    }
+
+    match instance {
+        Value::Number(n) => validate_number(schema, n.as_f64().unwrap_or(f64::NAN), path, errors),
+        Value::String(s) => validate_string(schema, s, path, errors),
+        Value::Array(items) => validate_array(schema, items, path, errors),
+        Value::Object(object) => validate_object(schema, object, path, errors),
+        Value::Null | Value::Bool(_) => {}
+    }
+
+    if let Some(Value::Array(schemas)) = schema.get("allOf") {
+        for sub in schemas {
+            validate_value(sub, instance, path, errors);
+        }
+    }
+    if let Some(Value::Array(schemas)) = schema.get("anyOf") {
+        if !schemas.iter().any(|sub| is_valid(sub, instance)) {
+            errors.push(format!("{}: does not match any schema in anyOf", path));
+        }
+    }
+    if let Some(Value::Array(schemas)) = schema.get("oneOf") {
+        let matches = schemas.iter().filter(|sub| is_valid(sub, instance)).count();
+        if matches != 1 {
+            errors.push(format!("{}: matches {} schemas in oneOf, expected exactly 1", path, matches));
+        }
+    }
+    if let Some(sub) = schema.get("not") {
+        if is_valid(sub, instance) {
+            errors.push(format!("{}: must not match the schema in not", path));
+        }
+    }
+}
+
+fn is_valid(schema: &Value, instance: &Value) -> bool {
+    let mut errors = Vec::new();
+    validate_value(schema, instance, "", &mut errors);
+    errors.is_empty()
+}
+
+fn has_type(instance: &Value, expected: &str) -> bool {
+    match expected {
+        "integer" => match instance {
+            Value::Number(n) => n.is_i64() || n.is_u64() || n.as_f64().is_some_and(|f| f.fract() == 0.0),
+            _ => false,
+        },
+        "number" => instance.is_number(),
+        other => type_name(instance) == other,
+    }
+}
+
+fn type_name(instance: &Value) -> &'static str {
+    match instance {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+fn validate_number(schema: &Map<String, Value>, n: f64, path: &str, errors: &mut Vec<String>) {
+    let bound = |key: &str| schema.get(key).and_then(Value::as_f64);
+    if let Some(min) = bound("minimum") {
+        if n < min {
+            errors.push(format!("{}: {} is less than the minimum of {}", path, n, min));
+        }
+    }
+    if let Some(max) = bound("maximum") {
+        if n > max {
+            errors.push(format!("{}: {} is greater than the maximum of {}", path, n, max));
+        }
+    }
+    if let Some(min) = bound("exclusiveMinimum") {
+        if n <= min {
+            errors.push(format!("{}: {} must be greater than {}", path, n, min));
+        }
+    }
+    if let Some(max) = bound("exclusiveMaximum") {
+        if n >= max {
+            errors.push(format!("{}: {} must be less than {}", path, n, max));
+        }
+    }
+    if let Some(step) = bound("multipleOf").filter(|step| *step > 0.0) {
+        if (n / step).fract() != 0.0 {
+            errors.push(format!("{}: {} is not a multiple of {}", path, n, step));
+        }
+    }
+}
+
+fn validate_string(schema: &Map<String, Value>, s: &str, path: &str, errors: &mut Vec<String>) {
+    let len = s.chars().count() as u64;
+    if let Some(min) = schema.get("minLength").and_then(Value::as_u64) {
+        if len < min {
+            errors.push(format!("{}: shorter than {} characters", path, min));
+        }
+    }
+    if let Some(max) = schema.get("maxLength").and_then(Value::as_u64) {
+        if len > max {
+            errors.push(format!("{}: longer than {} characters", path, max));
+        }
+    }
+    if let Some(pattern) = schema.get("pattern").and_then(Value::as_str) {
+        match Regex::new(pattern) {
+            Ok(re) if re.is_match(s) => {}
+            Ok(_) => errors.push(format!("{}: does not match pattern {}", path, pattern)),
+            Err(e) => errors.push(format!("{}: invalid pattern {}: {}", path, pattern, e)),
+        }
+    }
+}
+
+fn validate_array(schema: &Map<String, Value>, items: &[Value], path: &str, errors: &mut Vec<String>) {
+    let len = items.len() as u64;
+    if let Some(min) = schema.get("minItems").and_then(Value::as_u64) {
+        if len < min {
+            errors.push(format!("{}: fewer than {} items", path, min));
+        }
+    }
+    if let Some(max) = schema.get("maxItems").and_then(Value::as_u64) {
+        if len > max {
+            errors.push(format!("{}: more than {} items", path, max));
+        }
+    }
+    if schema.get("uniqueItems") == Some(&Value::Bool(true)) {
+        let duplicate = items
+            .iter()
+            .enumerate()
+            .any(|(i, item)| items[..i].contains(item));
+        if duplicate {
+            errors.push(format!("{}: items are not unique", path));
+        }
+    }
+    if let Some(item_schema) = schema.get("items") {
+        for (i, item) in items.iter().enumerate() {
+            validate_value(item_schema, item, &format!("{}[{}]", path, i), errors);
+        }
+    }
+}
+
+fn validate_object(
+    schema: &Map<String, Value>,
+    object: &Map<String, Value>,
+    path: &str,
+    errors: &mut Vec<String>,
+) {
+    if let Some(Value::Array(required)) = schema.get("required") {
+        for key in required.iter().filter_map(Value::as_str) {
+            if !object.contains_key(key) {
+                errors.push(format!("{}: missing required property {}", path, key));
+            }
+        }
+    }
+
+    let properties = schema.get("properties").and_then(Value::as_object);
+    let additional = schema.get("additionalProperties");
+    for (key, value) in object {
+        let child = format!("{}.{}", path, key);
+        match properties.and_then(|p| p.get(key)) {
+            Some(property_schema) => validate_value(property_schema, value, &child, errors),
+            None => match additional {
+                Some(Value::Bool(false)) => {
+                    errors.push(format!("{}: additional property {} is not allowed", path, key))
+                }
+                Some(additional_schema) => validate_value(additional_schema, value, &child, errors),
+                None => {}
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCHEMA: &str = r#"{
+        "type": "object",
+        "required": ["host", "port"],
+        "properties": {
+            "host": {"type": "string", "minLength": 1},
+            "port": {"type": "integer", "minimum": 1, "maximum": 65535},
+            "mode": {"enum": ["primary", "replica"]},
+            "tags": {"type": "array", "items": {"type": "string", "pattern": "^[a-z]+$"}}
+        },
+        "additionalProperties": false
+    }"#;
+
+    fn schema() -> RegisteredSchema {
+        RegisteredSchema::new(1, "tenant", "database", SCHEMA)
+    }
+
+    #[test]
+    fn test_conforming_content_in_each_format() {
+        let json = br#"{"host": "db", "port": 5432, "tags": ["a", "b"]}"#;
+        assert_eq!(schema().validate(json, &ConfigFormat::Json), Ok(()));
+
+        let yaml = b"host: db\nport: 5432\nmode: replica\n";
+        assert_eq!(schema().validate(yaml, &ConfigFormat::Yaml), Ok(()));
+
+        let toml = b"host = \"db\"\nport = 5432\n";
+        assert_eq!(schema().validate(toml, &ConfigFormat::Toml), Ok(()));
+    }
+
+    #[test]
+    fn test_violations_are_reported_with_paths() {
+        let content = br#"{"port": 70000, "mode": "standby", "tags": ["ok", "NO"], "extra": 1}"#;
+        let errors = schema().validate(content, &ConfigFormat::Json).unwrap_err();
+
+        assert!(errors.iter().any(|e| e == "$: missing required property host"), "{:?}", errors);
+        assert!(errors.iter().any(|e| e.starts_with("$.port: 70000 is greater")));
+        assert!(errors.iter().any(|e| e.starts_with("$.mode:")));
+        assert!(errors.iter().any(|e| e.starts_with("$.tags[1]: does not match pattern")));
+        assert!(errors.iter().any(|e| e == "$: additional property extra is not allowed"));
+        assert_eq!(errors.len(), 5);
+    }
+
+    #[test]
+    fn test_type_mismatch_and_combinators() {
+        let errors = schema().validate(b"port: \"5432\"\nhost: db\n", &ConfigFormat::Yaml).unwrap_err();
+        assert_eq!(errors, vec!["$.port: expected integer, found string".to_string()]);
+
+        let any_of = RegisteredSchema::new(2, "t", "n", r#"{"anyOf": [{"type": "string"}, {"type": "null"}]}"#);
+        assert_eq!(any_of.validate(b"null", &ConfigFormat::Json), Ok(()));
+        assert!(any_of.validate(b"1", &ConfigFormat::Json).is_err());
+    }
+
+    #[test]
+    fn test_unparseable_schema_and_content() {
+        assert!(parse_schema("[1]").is_err());
+        assert!(parse_schema("not json").is_err());
+        assert!(schema().validate(b"{", &ConfigFormat::Json).is_err());
+        assert!(schema().validate(b"a=1", &ConfigFormat::Properties).is_err());
+    }
+
+    #[test]
+    fn test_key_path_lookup_in_each_format() {
+        let json = content_to_document(br#"{"db": {"hosts": ["a", "b"], "port": 5432}}"#, &ConfigFormat::Json).unwrap();
+        assert_eq!(lookup_key_path(&json, "db.port"), Some(&Value::from(5432)));
+        assert_eq!(lookup_key_path(&json, "db.hosts.1"), Some(&Value::from("b")));
+        assert_eq!(lookup_key_path(&json, "db.hosts.2"), None);
+        assert_eq!(lookup_key_path(&json, "db.port.value"), None);
+
+        let yaml = content_to_document(b"db:\n  enabled: true\n", &ConfigFormat::Yaml).unwrap();
+        assert_eq!(lookup_key_path(&yaml, "db.enabled"), Some(&Value::Bool(true)));
+
+        let properties = b"# comment\ndb.port=5432\ndb.ratio = 0.5\ndb.debug: false\ndb.name=main\n";
+        let properties = content_to_document(properties, &ConfigFormat::Properties).unwrap();
+        assert_eq!(lookup_key_path(&properties, "db.port"), Some(&Value::from(5432)));
+        assert_eq!(lookup_key_path(&properties, "db.ratio"), Some(&Value::from(0.5)));
+        assert_eq!(lookup_key_path(&properties, "db.debug"), Some(&Value::Bool(false)));
+        assert_eq!(lookup_key_path(&properties, "db.name"), Some(&Value::from("main")));
+
+        assert!(content_to_document(b"\x00\x01", &ConfigFormat::Binary).is_err());
+    }
+}
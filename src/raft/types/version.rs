@@ -12,6 +12,13 @@ pub struct ConfigVersion {
     pub creator_id: u64,
     pub created_at: chrono::DateTime<chrono::Utc>,
     pub description: String,
+    /// When set, `content` holds a binary delta against this version's content
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub delta_base_version_id: Option<u64>,
+    /// SHA-256 of the previous version's content, chaining the history so
+    /// that a tampered version is detected (None for the first version)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub previous_hash: Option<String>,
 }
 
 impl ConfigVersion {
@@ -24,8 +31,7 @@ impl ConfigVersion {
         creator_id: u64,
         description: String,
     ) -> Self {
-        use sha2::{Digest, Sha256};
-        let content_hash = format!("{:x}", Sha256::digest(&content));
+        let content_hash = Self::hash_content(&content);
 
         Self {
             id,
@@ -36,14 +42,31 @@ impl ConfigVersion {
             creator_id,
             created_at: chrono::Utc::now(),
             description,
+            delta_base_version_id: None,
+            previous_hash: None,
         }
     }
 
+    /// Chain this version to `previous`, the version it succeeds
+    pub fn with_previous(mut self, previous: &ConfigVersion) -> Self {
+        self.previous_hash = Some(Self::hash_content(&previous.content));
+        self
+    }
+
+    /// Hex-encoded SHA-256 of `content`, as stored in `content_hash`
+    pub fn hash_content(content: &[u8]) -> String {
+        use sha2::{Digest, Sha256};
+        format!("{:x}", Sha256::digest(content))
+    }
+
+    /// Check if the content is stored as a delta against another version
+    pub fn is_delta(&self) -> bool {
+        self.delta_base_version_id.is_some()
+    }
+
     /// Verify content integrity
     pub fn verify_integrity(&self) -> bool {
-        use sha2::{Digest, Sha256};
-        let computed_hash = format!("{:x}", Sha256::digest(&self.content));
-        computed_hash == self.content_hash
+        Self::hash_content(&self.content) == self.content_hash
     }
 
     /// Get content as string (for text formats)
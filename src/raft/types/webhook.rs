@@ -0,0 +1,141 @@
+use super::config::ConfigNamespace;
+use ring::hmac;
+use serde::{Deserialize, Serialize};
+
+/// Header carrying the HMAC-SHA256 signature of a webhook payload
+///
+/// The value is `sha256=` followed by the lowercase hex digest of the
+/// request body, keyed with the webhook secret.
+pub const WEBHOOK_SIGNATURE_HEADER: &str = "X-Conflux-Signature";
+
+/// HTTP callback notified of configuration changes in a tenant
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Webhook {
+    pub id: u64,
+    pub tenant: String,
+    /// Endpoint receiving `POST` requests with the serialized change event
+    pub url: String,
+    /// Only notify changes in this app (None = every app of the tenant)
+    #[serde(default)]
+    pub app: Option<String>,
+    /// Only notify changes in this environment (None = every environment)
+    #[serde(default)]
+    pub env: Option<String>,
+    /// Key of the payload signature (None = requests are not signed)
+    #[serde(default)]
+    pub secret: Option<String>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl Webhook {
+    /// Check whether changes in `namespace` are sent to this webhook
+    pub fn matches(&self, namespace: &ConfigNamespace) -> bool {
+        namespace.tenant == self.tenant
+            && self.app.as_ref().is_none_or(|app| *app == namespace.app)
+            && self.env.as_ref().is_none_or(|env| *env == namespace.env)
+    }
+
+    /// Value of [`WEBHOOK_SIGNATURE_HEADER`] for `payload`, if the webhook has a secret
+    pub fn signature(&self, payload: &[u8]) -> Option<String> {
+        self.secret
+            .as_deref()
+            .map(|secret| sign_webhook_payload(secret, payload))
+    }
+}
+
+/// Sign a webhook payload with `secret`
+pub fn sign_webhook_payload(secret: &str, payload: &[u8]) -> String {
+    let key = hmac::Key::new(hmac::HMAC_SHA256, secret.as_bytes());
+    let digest: String = hmac::sign(&key, payload)
+        .as_ref()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect();
+    format!("sha256={}", digest)
+}
+
+/// Check a [`WEBHOOK_SIGNATURE_HEADER`] value in constant time
+pub fn verify_webhook_signature(secret: &str, payload: &[u8], signature: &str) -> bool {
+    let Some(hex) = signature.strip_prefix("sha256=") else {
+        return false;
+    };
+    if hex.len() % 2 != 0 || !hex.is_ascii() {
+        return false;
+    }
+    let tag: Option<Vec<u8>> = (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).ok())
+        .collect();
+    let key = hmac::Key::new(hmac::HMAC_SHA256, secret.as_bytes());
+    tag.is_some_and(|tag| hmac::verify(&key, payload, &tag).is_ok())
+}
+
+/// Check that a webhook URL is an absolute `http` or `https` URL
+pub fn validate_webhook_url(url: &str) -> Result<(), String> {
+    let parsed = reqwest::Url::parse(url).map_err(|e| format!("invalid URL: {}", e))?;
+    match parsed.scheme() {
+        "http" | "https" if parsed.host().is_some() => Ok(()),
+        "http" | "https" => Err("URL has no host".to_string()),
+        scheme => Err(format!("unsupported scheme '{}'", scheme)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn webhook(app: Option<&str>, env: Option<&str>, secret: Option<&str>) -> Webhook {
+        Webhook {
+            id: 1,
+            tenant: "acme".to_string(),
+            url: "https://hooks.example.com/conflux".to_string(),
+            app: app.map(str::to_string),
+            env: env.map(str::to_string),
+            secret: secret.map(str::to_string),
+            created_at: chrono::Utc::now(),
+        }
+    }
+
+    fn namespace(tenant: &str, app: &str, env: &str) -> ConfigNamespace {
+        ConfigNamespace {
+            tenant: tenant.to_string(),
+            app: app.to_string(),
+            env: env.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_webhook_namespace_filter() {
+        let all = webhook(None, None, None);
+        assert!(all.matches(&namespace("acme", "web", "prod")));
+        assert!(!all.matches(&namespace("other", "web", "prod")));
+
+        let prod_web = webhook(Some("web"), Some("prod"), None);
+        assert!(prod_web.matches(&namespace("acme", "web", "prod")));
+        assert!(!prod_web.matches(&namespace("acme", "web", "dev")));
+        assert!(!prod_web.matches(&namespace("acme", "api", "prod")));
+    }
+
+    #[test]
+    fn test_webhook_signature_round_trip() {
+        let payload = br#"{"config_id":1}"#;
+        assert_eq!(webhook(None, None, None).signature(payload), None);
+
+        let signature = webhook(None, None, Some("s3cret")).signature(payload).unwrap();
+        assert!(signature.starts_with("sha256="));
+        assert_eq!(signature.len(), "sha256=".len() + 64);
+        assert!(verify_webhook_signature("s3cret", payload, &signature));
+        assert!(!verify_webhook_signature("other", payload, &signature));
+        assert!(!verify_webhook_signature("s3cret", b"tampered", &signature));
+        assert!(!verify_webhook_signature("s3cret", payload, "sha256=zz"));
+        assert!(!verify_webhook_signature("s3cret", payload, &signature[7..]));
+    }
+
+    #[test]
+    fn test_validate_webhook_url() {
+        assert!(validate_webhook_url("https://hooks.example.com/conflux").is_ok());
+        assert!(validate_webhook_url("http://127.0.0.1:8080/hook").is_ok());
+        assert!(validate_webhook_url("ftp://example.com/hook").is_err());
+        assert!(validate_webhook_url("not a url").is_err());
+    }
+}
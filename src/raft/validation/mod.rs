@@ -5,6 +5,7 @@
 mod cluster_validation;
 mod comprehensive;
 mod config;
+mod namespace_validation;
 mod node_validation;
 mod raft_input_validator;
 mod timeout_validation;
@@ -12,6 +13,9 @@ mod timeout_validation;
 pub use cluster_validation::ClusterValidator;
 pub use comprehensive::{ClusterSuggestions, ComprehensiveValidator};
 pub use config::ValidationConfig;
+pub use namespace_validation::{
+    validate_namespace, validate_tenant, MAX_NAMESPACE_COMPONENT_LEN, NAMESPACE_KEY_SEPARATOR,
+};
 pub use node_validation::NodeValidator;
 pub use raft_input_validator::RaftInputValidator;
 pub use timeout_validation::TimeoutValidator;
@@ -38,4 +42,8 @@ mod config_tests;
 
 #[cfg(test)]
 #[path = "node_validation_test.rs"]
-mod node_validation_tests;
\ No newline at end of file
+mod node_validation_tests;
+
+#[cfg(test)]
+#[path = "namespace_validation_test.rs"]
+mod namespace_validation_tests;
@@ -0,0 +1,79 @@
+//! 命名空间验证模块
+//!
+//! 命名空间的 `tenant/app/env` 通过 `make_config_key` 以 `/` 拼接为RocksDB键，
+//! 各部分只允许ASCII字母、数字、`-` 和 `_`，避免分隔符或控制字符破坏键空间
+
+use crate::error::{ConfluxError, Result};
+use crate::raft::types::ConfigNamespace;
+
+/// 命名空间各部分的最大长度（字节）
+pub const MAX_NAMESPACE_COMPONENT_LEN: usize = 64;
+
+/// 配置键中分隔命名空间各部分的字符
+pub const NAMESPACE_KEY_SEPARATOR: char = '/';
+
+/// 验证命名空间的 `tenant`、`app` 和 `env`
+///
+/// 每部分不能为空，长度不超过 [`MAX_NAMESPACE_COMPONENT_LEN`]，
+/// 只能包含ASCII字母、数字、`-` 和 `_`
+///
+/// # Errors
+///
+/// 返回指出不合法部分及原因的验证错误
+///
+/// # Examples
+///
+/// ```rust
+/// use conflux::raft::types::ConfigNamespace;
+/// use conflux::raft::validation::validate_namespace;
+///
+/// let namespace = ConfigNamespace {
+///     tenant: "acme".to_string(),
+///     app: "billing-api".to_string(),
+///     env: "prod_eu".to_string(),
+/// };
+/// assert!(validate_namespace(&namespace).is_ok());
+/// ```
+pub fn validate_namespace(namespace: &ConfigNamespace) -> Result<()> {
+    validate_component("tenant", &namespace.tenant)?;
+    validate_component("app", &namespace.app)?;
+    validate_component("env", &namespace.env)
+}
+
+/// 验证单独使用的租户ID，规则与命名空间的 `tenant` 部分相同
+pub fn validate_tenant(tenant: &str) -> Result<()> {
+    validate_component("tenant", tenant)
+}
+
+fn validate_component(component: &str, value: &str) -> Result<()> {
+    if value.is_empty() {
+        return Err(ConfluxError::validation(format!(
+            "Namespace {} cannot be empty",
+            component
+        )));
+    }
+    if value.len() > MAX_NAMESPACE_COMPONENT_LEN {
+        return Err(ConfluxError::validation(format!(
+            "Namespace {} is too long: {} bytes (max: {})",
+            component,
+            value.len(),
+            MAX_NAMESPACE_COMPONENT_LEN
+        )));
+    }
+    if value.contains(NAMESPACE_KEY_SEPARATOR) {
+        return Err(ConfluxError::validation(format!(
+            "Namespace {} {:?} cannot contain the key separator '{}'",
+            component, value, NAMESPACE_KEY_SEPARATOR
+        )));
+    }
+    if let Some(invalid) = value
+        .chars()
+        .find(|c| !(c.is_ascii_alphanumeric() || *c == '-' || *c == '_'))
+    {
+        return Err(ConfluxError::validation(format!(
+            "Namespace {} {:?} contains invalid character {:?}; only ASCII letters, digits, '-' and '_' are allowed",
+            component, value, invalid
+        )));
+    }
+    Ok(())
+}
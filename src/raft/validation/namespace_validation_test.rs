@@ -0,0 +1,67 @@
+#[cfg(test)]
+mod namespace_validation_tests {
+    use crate::error::ConfluxError;
+    use crate::raft::types::ConfigNamespace;
+    use crate::raft::validation::{validate_namespace, MAX_NAMESPACE_COMPONENT_LEN};
+
+    fn namespace(tenant: &str, app: &str, env: &str) -> ConfigNamespace {
+        ConfigNamespace {
+            tenant: tenant.to_string(),
+            app: app.to_string(),
+            env: env.to_string(),
+        }
+    }
+
+    fn rejection(namespace: &ConfigNamespace) -> String {
+        match validate_namespace(namespace) {
+            Err(ConfluxError::Validation(message)) => message,
+            other => panic!("Expected validation error for {:?}, got {:?}", namespace, other),
+        }
+    }
+
+    #[test]
+    fn test_validate_namespace_accepts_allowed_characters() {
+        assert!(validate_namespace(&namespace("acme", "billing-api", "prod_eu")).is_ok());
+        assert!(validate_namespace(&namespace("Tenant1", "APP", "dev-2")).is_ok());
+
+        let longest = "a".repeat(MAX_NAMESPACE_COMPONENT_LEN);
+        assert!(validate_namespace(&namespace(&longest, "app", "dev")).is_ok());
+    }
+
+    #[test]
+    fn test_validate_namespace_rejects_key_separator() {
+        let message = rejection(&namespace("acme/other", "app", "dev"));
+        assert!(message.contains("tenant"));
+        assert!(message.contains("key separator"));
+
+        // A separator would otherwise let two namespaces share one key
+        assert!(validate_namespace(&namespace("acme", "app/prod", "x")).is_err());
+        assert!(validate_namespace(&namespace("acme", "app", "../prod")).is_err());
+    }
+
+    #[test]
+    fn test_validate_namespace_rejects_control_and_unicode_characters() {
+        for env in [
+            "prod\0",
+            "prod\n",
+            "pr\u{7f}od",
+            "prod\u{202e}",
+            "pr\u{200b}od",
+            "prod\u{85}",
+            "prödd",
+            "prod env",
+        ] {
+            let message = rejection(&namespace("acme", "app", env));
+            assert!(message.contains("env"), "{}", message);
+        }
+    }
+
+    #[test]
+    fn test_validate_namespace_rejects_empty_and_long_components() {
+        assert!(rejection(&namespace("", "app", "dev")).contains("tenant cannot be empty"));
+        assert!(rejection(&namespace("acme", "", "dev")).contains("app cannot be empty"));
+
+        let too_long = "a".repeat(MAX_NAMESPACE_COMPONENT_LEN + 1);
+        assert!(rejection(&namespace("acme", &too_long, "dev")).contains("too long"));
+    }
+}
@@ -92,6 +92,31 @@ impl RaftInputValidator {
             .validate_add_node(node_id, address, existing_nodes)
     }
 
+    /// 验证节点地址
+    ///
+    /// # Arguments
+    ///
+    /// * `address` - 节点地址，格式为 `ip:port`
+    ///
+    /// # Returns
+    ///
+    /// 如果验证通过返回解析后的地址，否则返回错误
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use conflux::raft::validation::RaftInputValidator;
+    ///
+    /// let validator = RaftInputValidator::new();
+    /// assert!(validator.validate_node_address("127.0.0.1:8081").is_ok());
+    /// assert!(validator.validate_node_address("not-an-address").is_err());
+    /// ```
+    pub fn validate_node_address(&self, address: &str) -> Result<std::net::SocketAddr> {
+        self.comprehensive_validator
+            .node_validator
+            .validate_node_address(address)
+    }
+
     /// 验证移除节点操作
     ///
     /// # Arguments